@@ -9,7 +9,7 @@ use revm_primitives::hardfork::SpecId;
 use std::hint::black_box;
 use std::time::Duration;
 
-use evolve_ev_reth::evm_config::AndePrecompileProvider;
+use evolve_ev_reth::evm_config::{AndePrecompileConfig, AndePrecompileProvider};
 
 /// Benchmark ANDE precompile provider creation
 fn bench_ande_precompile_creation(c: &mut Criterion) {
@@ -18,7 +18,7 @@ fn bench_ande_precompile_creation(c: &mut Criterion) {
     
     group.bench_function("provider_creation", |b| {
         b.iter(|| {
-            let provider = black_box(AndePrecompileProvider::new(SpecId::CANCUN));
+            let provider = black_box(AndePrecompileProvider::new(SpecId::CANCUN, AndePrecompileConfig::default()));
             black_box(provider);
         });
     });
@@ -58,7 +58,7 @@ fn bench_ande_address_validation(c: &mut Criterion) {
     let mut group = c.benchmark_group("ande_address_validation");
     group.measurement_time(Duration::from_secs(10));
     
-    let provider = AndePrecompileProvider::new(SpecId::CANCUN);
+    let provider = AndePrecompileProvider::new(SpecId::CANCUN, AndePrecompileConfig::default());
     let ande_address = 0xfd;
     let invalid_address = 0x99;
     
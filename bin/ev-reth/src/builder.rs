@@ -1,8 +1,12 @@
-use alloy_primitives::U256;
 use clap::Parser;
-use ev_node::{EvolvePayloadBuilder, EvolvePayloadBuilderConfig};
+use ev_node::{
+    create_payload_builder_service, create_payload_builder_service_with_parallel, EvolveBuildOutcome,
+    EvolvePayloadBuilder, EvolvePayloadBuilderConfig, SharedPayloadBuilderConfig,
+};
 use evolve_ev_reth::EvolvePayloadAttributes;
-use evolve_ev_reth::evm_config::create_ande_evm_config;
+use evolve_ev_reth::evm_config::AndeEvmConfig;
+use evolve_ev_reth::mev::{InMemoryMevStore, MevAuctionClient, MevConfig, MevOpportunityStore};
+use evolve_ev_reth::rpc::{HealthRegistry, PayloadStatsBuffer, DEFAULT_PAYLOAD_STATS_CAPACITY};
 use reth_basic_payload_builder::{
     BuildArguments, BuildOutcome, HeaderForPayload, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -12,7 +16,6 @@ use reth_ethereum::{
     node::{
         api::{payload::PayloadBuilderAttributes, FullNodeTypes, NodeTypes},
         builder::{components::PayloadBuilderBuilder, BuilderContext},
-        EthEvmConfig,
     },
     pool::{PoolTransaction, TransactionPool},
     primitives::Header,
@@ -22,10 +25,13 @@ use reth_payload_builder::{EthBuiltPayload, PayloadBuilderError};
 use reth_provider::HeaderProvider;
 use reth_revm::cached::CachedReads;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
-use crate::{attributes::EvolveEnginePayloadBuilderAttributes, EvolveEngineTypes};
+use crate::{
+    attributes::EvolveEnginePayloadBuilderAttributes, parallel::ParallelArgs, sequencer::SequencerRegistryArgs,
+    EvolveEngineTypes,
+};
 use evolve_ev_reth::config::set_current_block_gas_limit;
 
 /// Evolve-specific command line arguments
@@ -38,6 +44,50 @@ pub struct EvolveArgs {
         help = "Enable Evolve integration for transaction processing via Engine API"
     )]
     pub enable_evolve: bool,
+
+    /// Serve the `ande_getMevStats`, `ande_getMevOpportunities`, `ande_simulateBundle`,
+    /// `ande_sendBundle` and `ande_getBundleStatus` RPC methods
+    #[arg(
+        long = "ev-reth.mev-rpc",
+        help = "Enable MEV detection and expose the ande_getMevStats, ande_getMevOpportunities, \
+                ande_simulateBundle, ande_sendBundle and ande_getBundleStatus RPC methods; bundle \
+                submissions are only rate limited per connection, limit them per IP in a proxy"
+    )]
+    pub enable_mev_rpc: bool,
+
+    /// Payload builder config file, whose `[mev]` section configures MEV integration
+    #[arg(
+        long = "ev-reth.config",
+        value_name = "PATH",
+        help = "TOML payload builder config file; ANDE_MEV_* variables override its [mev] section"
+    )]
+    pub config: Option<PathBuf>,
+
+    /// Serve the `ande_reloadConfig` RPC method
+    #[arg(
+        long = "ev-reth.admin-rpc",
+        requires = "config",
+        help = "Expose the ande_reloadConfig RPC method, reloading the --ev-reth.config file, \
+                on the authenticated engine API transport"
+    )]
+    pub enable_admin_rpc: bool,
+
+    /// Number of recently built blocks whose stats `ande_getPayloadStats` serves
+    #[arg(
+        long = "ev-reth.payload-stats-blocks",
+        value_name = "BLOCKS",
+        default_value_t = DEFAULT_PAYLOAD_STATS_CAPACITY,
+        help = "Number of recently built blocks whose stats ande_getPayloadStats keeps"
+    )]
+    pub payload_stats_blocks: usize,
+
+    /// Registration of the sequencer with the AndeSequencerRegistry
+    #[command(flatten)]
+    pub sequencer: SequencerRegistryArgs,
+
+    /// Parallel execution of payload transactions
+    #[command(flatten)]
+    pub parallel: ParallelArgs,
 }
 
 /// Evolve payload service builder that integrates with the evolve payload builder
@@ -45,14 +95,70 @@ pub struct EvolveArgs {
 #[non_exhaustive]
 pub struct EvolvePayloadBuilderBuilder {
     config: EvolvePayloadBuilderConfig,
+    mev_store: Arc<dyn MevOpportunityStore>,
+    mev_auction: Option<Arc<MevAuctionClient>>,
+    live_config: Option<SharedPayloadBuilderConfig>,
+    payload_stats: Option<Arc<PayloadStatsBuffer>>,
+    health: Option<Arc<HealthRegistry>>,
 }
 
 impl EvolvePayloadBuilderBuilder {
     /// Create a new builder with evolve args
-    pub fn new(_args: &EvolveArgs) -> Self {
-        let config = EvolvePayloadBuilderConfig::new();
+    pub fn new(args: &EvolveArgs) -> Self {
+        Self::with_config(args, EvolvePayloadBuilderConfig::new())
+    }
+
+    /// Create a new builder with evolve args and a loaded payload builder config
+    pub fn with_config(args: &EvolveArgs, mut config: EvolvePayloadBuilderConfig) -> Self {
+        // The MEV RPC serves what detection records while building payloads
+        if args.enable_mev_rpc && config.mev.is_none() {
+            config.mev = Some(MevConfig {
+                enable_auction: false,
+                enable_distribution: false,
+                ..Default::default()
+            });
+        }
+        args.parallel.apply(&mut config.parallel);
         info!("Created Evolve payload builder with config: {:?}", config);
-        Self { config }
+        Self {
+            config,
+            mev_store: Arc::new(InMemoryMevStore::default()),
+            mev_auction: None,
+            live_config: None,
+            payload_stats: None,
+            health: None,
+        }
+    }
+
+    /// Record detected MEV opportunities in `store`
+    pub fn with_mev_store(mut self, store: Arc<dyn MevOpportunityStore>) -> Self {
+        self.mev_store = store;
+        self
+    }
+
+    /// Place the winning bundle of `auction`, fed by `ande_sendBundle`, at the top of each block
+    pub fn with_mev_auction(mut self, auction: Option<Arc<MevAuctionClient>>) -> Self {
+        self.mev_auction = auction;
+        self
+    }
+
+    /// Build each block with the configuration in `config`, kept up to date
+    /// by the config watcher
+    pub fn with_live_config(mut self, config: Option<SharedPayloadBuilderConfig>) -> Self {
+        self.live_config = config;
+        self
+    }
+
+    /// Record the stats of each built block in `stats`, served by `ande_getPayloadStats`
+    pub fn with_payload_stats(mut self, stats: Option<Arc<PayloadStatsBuffer>>) -> Self {
+        self.payload_stats = stats;
+        self
+    }
+
+    /// Report the parallel executor's worker threads in `health`, served by `ande_health`
+    pub fn with_health(mut self, health: Option<Arc<HealthRegistry>>) -> Self {
+        self.health = health;
+        self
     }
 }
 
@@ -76,7 +182,7 @@ where
     pub(crate) config: EvolvePayloadBuilderConfig,
 }
 
-impl<Node, Pool> PayloadBuilderBuilder<Node, Pool, EthEvmConfig> for EvolvePayloadBuilderBuilder
+impl<Node, Pool> PayloadBuilderBuilder<Node, Pool, AndeEvmConfig> for EvolvePayloadBuilderBuilder
 where
     Node: FullNodeTypes<
         Types: NodeTypes<
@@ -95,18 +201,51 @@ where
         self,
         ctx: &BuilderContext<Node>,
         pool: Pool,
-        _evm_config: EthEvmConfig,
+        ande_evm_config: AndeEvmConfig,
     ) -> eyre::Result<Self::PayloadBuilder> {
-        // Create ANDE EVM config with Token Duality precompiles
-        let ande_evm_config = create_ande_evm_config(ctx.chain_spec().clone());
-
-        tracing::info!("✅ ANDE Token Duality precompile enabled at 0x00...FD");
-
-        let evolve_builder = Arc::new(EvolvePayloadBuilder::new(
-            Arc::new(ctx.provider().clone()),
-            ande_evm_config,
-            self.config.clone(),
-        ));
+        // Payloads are built with the EVM blocks are imported with, set up by
+        // the executor builder
+        let client = Arc::new(ctx.provider().clone());
+        let parallel_config = self.config.parallel.executor_config().map(|mut parallel_config| {
+            // Relative trace directories are under the datadir
+            parallel_config.trace_dir =
+                parallel_config.trace_dir.map(|trace_dir| ctx.config().datadir().data_dir().join(trace_dir));
+            parallel_config
+        });
+        let evolve_builder = match parallel_config {
+            Some(parallel_config) => {
+                info!(
+                    preset = self.config.parallel.preset.as_str(),
+                    "✅ Parallel execution enabled: {:?}", parallel_config
+                );
+                create_payload_builder_service_with_parallel(
+                    client,
+                    ande_evm_config,
+                    Some(parallel_config),
+                    self.config.clone(),
+                )
+            }
+            None => {
+                info!("Parallel execution disabled, payload transactions execute sequentially");
+                create_payload_builder_service(client, ande_evm_config, self.config.clone())
+            }
+        };
+        let mut evolve_builder = evolve_builder
+            .ok_or_else(|| eyre::eyre!("Failed to create the Evolve payload builder"))?
+            .with_mev_store(self.mev_store);
+        if let Some(mev_auction) = self.mev_auction {
+            evolve_builder = evolve_builder.with_mev_auction(mev_auction);
+        }
+        if let Some(live_config) = self.live_config {
+            evolve_builder = evolve_builder.with_live_config(live_config);
+        }
+        if let Some(payload_stats) = self.payload_stats {
+            evolve_builder = evolve_builder.with_payload_stats(payload_stats);
+        }
+        if let (Some(health), Some(pool)) = (&self.health, evolve_builder.parallel_worker_pool()) {
+            health.register_worker_pool(pool.clone());
+        }
+        let evolve_builder = Arc::new(evolve_builder);
 
         Ok(EvolveEnginePayloadBuilder {
             evolve_builder,
@@ -168,24 +307,35 @@ where
 
         // Build the payload using the evolve payload builder - use spawn_blocking for async work
         let evolve_builder = self.evolve_builder.clone();
-        let sealed_block = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(evolve_builder.build_payload(evolve_attrs))
+        let outcome = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(evolve_builder.try_build_payload(evolve_attrs))
         })
         .map_err(PayloadBuilderError::other)?;
+        let built = match outcome {
+            EvolveBuildOutcome::Built(built) => built,
+            // Another sequencer's turn: nothing to build, and nothing went wrong
+            EvolveBuildOutcome::Skipped { block_number, designated_producer } => {
+                info!(
+                    "Evolve engine payload builder: skipping block {}, designated producer is {}",
+                    block_number, designated_producer
+                );
+                return Ok(BuildOutcome::Cancelled);
+            }
+        };
 
         info!(
-            "Evolve engine payload builder: built block with {} transactions, gas used: {}",
-            sealed_block.transaction_count(),
-            sealed_block.gas_used
+            "Evolve engine payload builder: built block with {} transactions, gas used: {}, fees: {}",
+            built.block.transaction_count(),
+            built.block.gas_used,
+            built.fees
         );
 
         // Convert to EthBuiltPayload
-        let gas_used = sealed_block.gas_used;
         let built_payload = EthBuiltPayload::new(
             attributes.payload_id(), // Use the proper payload ID from attributes
-            Arc::new(sealed_block),
-            U256::from(gas_used), // Block gas used
-            None,                 // No blob sidecar for evolve
+            Arc::new(built.block),
+            built.fees,
+            None, // No blob sidecar for evolve
         );
 
         Ok(BuildOutcome::Better {
@@ -223,18 +373,12 @@ where
 
         // Build empty payload - use spawn_blocking for async work
         let evolve_builder = self.evolve_builder.clone();
-        let sealed_block = tokio::task::block_in_place(|| {
+        let built = tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(evolve_builder.build_payload(evolve_attrs))
         })
         .map_err(PayloadBuilderError::other)?;
 
-        let gas_used = sealed_block.gas_used;
-        Ok(EthBuiltPayload::new(
-            attributes.payload_id(),
-            Arc::new(sealed_block),
-            U256::from(gas_used),
-            None,
-        ))
+        Ok(EthBuiltPayload::new(attributes.payload_id(), Arc::new(built.block), built.fees, None))
     }
 
     /// Determines how to handle a request for a payload that is currently being built.
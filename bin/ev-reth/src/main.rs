@@ -8,28 +8,49 @@
 pub mod attributes;
 pub mod builder;
 pub mod error;
+pub mod parallel;
+pub mod sequencer;
 pub mod validator;
 
+use alloy_primitives::Address;
 use alloy_rpc_types::engine::{
     ExecutionData, ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3,
     ExecutionPayloadEnvelopeV4, ExecutionPayloadEnvelopeV5, ExecutionPayloadV1,
 };
 use clap::Parser;
+use ev_node::{
+    AndeExecutorBuilder, ConfigSwitches, ConfigWatcher, EvolvePayloadBuilderConfig, SharedPayloadBuilderConfig,
+    DEFAULT_CONFIG_POLL_INTERVAL,
+};
 use evolve_ev_reth::{
-    config::EvolveConfig,
     consensus::EvolveConsensusBuilder,
-    rpc::txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+    evm_config::SharedValidatorSnapshot,
+    mev::{InMemoryMevStore, MevAuctionClient, MevOpportunityStore},
+    rpc::{
+        admin::{
+            AndeAdminApiImpl, AndeAdminApiServer, AndeRuntimeApiImpl, AndeRuntimeApiServer, RuntimeSwitchSource,
+        },
+        bundle::{AndeBundleApiImpl, AndeBundleApiServer},
+        consensus::{AndeConsensusApiImpl, AndeConsensusApiServer},
+        duality::{AndeDualityApiImpl, AndeDualityApiServer},
+        health::{AndeHealthApiImpl, AndeHealthApiServer, HealthRegistry},
+        mev::{AndeMevApiImpl, AndeMevApiServer},
+        simulate::{AndeSimulationApiImpl, AndeSimulationApiServer},
+        stats::{AndeStatsApiImpl, AndeStatsApiServer, PayloadStatsBuffer},
+        txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+    },
 };
 use reth_ethereum::{
     chainspec::ChainSpec,
+    evm::primitives::ConfigureEvm,
     node::{
-        api::{EngineTypes, FullNodeTypes, NodeTypes, PayloadTypes},
+        api::{EngineTypes, FullNodeComponents, FullNodeTypes, NodeTypes, PayloadTypes},
         builder::{
             components::{BasicPayloadServiceBuilder, ComponentsBuilder},
             rpc::RpcAddOns,
             Node, NodeAdapter,
         },
-        node::{EthereumExecutorBuilder, EthereumNetworkBuilder, EthereumPoolBuilder},
+        node::{EthereumNetworkBuilder, EthereumPoolBuilder},
         EthereumEthApiBuilder,
     },
     primitives::SealedBlock,
@@ -37,6 +58,7 @@ use reth_ethereum::{
 use reth_ethereum_cli::{chainspec::EthereumChainSpecParser, Cli};
 use reth_payload_builder::EthBuiltPayload;
 use serde::{Deserialize, Serialize};
+use std::sync::{atomic::Ordering, Arc, RwLock};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
@@ -97,17 +119,88 @@ impl EngineTypes for EvolveEngineTypes {
 }
 
 /// Evolve node type
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct EvolveNode {
     /// Evolve-specific arguments
     pub args: EvolveArgs,
+    /// Store the payload builder records detected MEV opportunities in
+    pub mev_store: Arc<dyn MevOpportunityStore>,
+    /// Auction the payload builder takes the top-of-block bundle from, if any
+    pub mev_auction: Option<Arc<MevAuctionClient>>,
+    /// Validator set served by the validator-set precompile
+    pub validator_snapshot: SharedValidatorSnapshot,
+    /// Payload builder configuration
+    pub payload_config: EvolvePayloadBuilderConfig,
+    /// Payload builder configuration kept up to date by the config watcher, if any
+    pub live_config: Option<SharedPayloadBuilderConfig>,
+    /// Stats of the blocks built recently, if served
+    pub payload_stats: Option<Arc<PayloadStatsBuffer>>,
+    /// Subsystems reported by `ande_health`, if served
+    pub health: Option<Arc<HealthRegistry>>,
 }
 
 impl EvolveNode {
     /// Create a new evolve node with the given arguments
-    pub const fn new(args: EvolveArgs) -> Self {
-        Self { args }
+    pub fn new(args: EvolveArgs) -> Self {
+        Self {
+            args,
+            mev_store: Arc::new(InMemoryMevStore::default()),
+            mev_auction: None,
+            validator_snapshot: SharedValidatorSnapshot::default(),
+            payload_config: EvolvePayloadBuilderConfig::new(),
+            live_config: None,
+            payload_stats: None,
+            health: None,
+        }
+    }
+
+    /// Build payloads with `config`
+    pub fn with_payload_config(mut self, config: EvolvePayloadBuilderConfig) -> Self {
+        self.payload_config = config;
+        self
+    }
+
+    /// Record detected MEV opportunities in `store`
+    pub fn with_mev_store(mut self, store: Arc<dyn MevOpportunityStore>) -> Self {
+        self.mev_store = store;
+        self
+    }
+
+    /// Place the winning bundle of `auction` at the top of each block
+    pub fn with_mev_auction(mut self, auction: Option<Arc<MevAuctionClient>>) -> Self {
+        self.mev_auction = auction;
+        self
+    }
+
+    /// Serve the validator-set precompile from `snapshot`
+    pub fn with_validator_snapshot(mut self, snapshot: SharedValidatorSnapshot) -> Self {
+        self.validator_snapshot = snapshot;
+        self
+    }
+
+    /// Build payloads with the configuration in `config`, reloaded while the node runs
+    pub fn with_live_config(mut self, config: Option<SharedPayloadBuilderConfig>) -> Self {
+        self.live_config = config;
+        self
+    }
+
+    /// Record the stats of each built block in `stats`
+    pub fn with_payload_stats(mut self, stats: Option<Arc<PayloadStatsBuffer>>) -> Self {
+        self.payload_stats = stats;
+        self
+    }
+
+    /// Register the subsystems the node starts in `health`
+    pub fn with_health(mut self, health: Option<Arc<HealthRegistry>>) -> Self {
+        self.health = health;
+        self
+    }
+}
+
+impl Default for EvolveNode {
+    fn default() -> Self {
+        Self::new(EvolveArgs::default())
     }
 }
 
@@ -130,7 +223,7 @@ where
         EthereumPoolBuilder,
         BasicPayloadServiceBuilder<EvolvePayloadBuilderBuilder>,
         EthereumNetworkBuilder,
-        EthereumExecutorBuilder,
+        AndeExecutorBuilder,
         EvolveConsensusBuilder,
     >;
     type AddOns = EvolveNodeAddOns<NodeAdapter<N>>;
@@ -139,9 +232,17 @@ where
         ComponentsBuilder::default()
             .node_types::<N>()
             .pool(EthereumPoolBuilder::default())
-            .executor(EthereumExecutorBuilder::default())
+            .executor(AndeExecutorBuilder::from_config(
+                self.payload_config.clone(),
+                self.validator_snapshot.clone(),
+            ))
             .payload(BasicPayloadServiceBuilder::new(
-                EvolvePayloadBuilderBuilder::new(&self.args),
+                EvolvePayloadBuilderBuilder::with_config(&self.args, self.payload_config.clone())
+                    .with_mev_store(self.mev_store.clone())
+                    .with_mev_auction(self.mev_auction.clone())
+                    .with_live_config(self.live_config.clone())
+                    .with_payload_stats(self.payload_stats.clone())
+                    .with_health(self.health.clone()),
             ))
             .network(EthereumNetworkBuilder::default())
             .consensus(EvolveConsensusBuilder::default())
@@ -175,22 +276,108 @@ fn main() {
             info!("=== EV-RETH: Starting with args: {:?} ===", evolve_args);
             info!("=== EV-RETH: Evolve node mode enabled ===");
             info!("=== EV-RETH: Using custom payload builder with transaction support ===");
+            let payload_config = EvolvePayloadBuilderConfig::load(evolve_args.config.as_deref())?;
+            info!("=== EV-RETH: MEV config: {:?} ===", payload_config.mev);
+            // Shared between the payload builder, which records detected MEV, and the MEV RPC
+            let mev_store: Arc<dyn MevOpportunityStore> = Arc::new(InMemoryMevStore::default());
+            // Fed by the consensus client, read by the validator-set precompile
+            let validator_snapshot = SharedValidatorSnapshot::default();
+            let enable_mev_rpc = evolve_args.enable_mev_rpc;
+            // Shared between the bundle RPC, which searchers bid through, and the payload builder
+            let mev_auction = enable_mev_rpc.then(|| {
+                let contract = payload_config.mev.as_ref().and_then(|mev| mev.auction_address).unwrap_or_default();
+                Arc::new(MevAuctionClient::new(contract, Address::ZERO))
+            });
+            let enable_admin_rpc = evolve_args.enable_admin_rpc;
+            // Recorded by the payload builder, read by the stats RPC
+            let payload_stats = Arc::new(PayloadStatsBuffer::new(evolve_args.payload_stats_blocks));
+            // Filled by the node components as they start, read by the health RPC
+            let health = Arc::new(HealthRegistry::new(payload_config.health));
+            let sequencer_args = evolve_args.sequencer.clone();
+            // Applies changes of the config file without a restart
+            let config_watcher = evolve_args
+                .config
+                .as_ref()
+                .map(|path| Arc::new(ConfigWatcher::new(path, payload_config.clone())));
+            let rpc_config_watcher = config_watcher.clone();
+            // Read by the payload builder, switched at runtime with or without a config file
+            let live_config = config_watcher
+                .as_ref()
+                .map_or_else(|| Arc::new(RwLock::new(payload_config.clone())), |watcher| watcher.shared());
+            let runtime_switches: Arc<dyn RuntimeSwitchSource> = match &config_watcher {
+                Some(watcher) => watcher.clone(),
+                None => Arc::new(ConfigSwitches::new(live_config.clone())),
+            };
+            let max_txpool_bytes = payload_config.txpool.max_txpool_bytes;
             let handle = builder
-                .node(EvolveNode::new(evolve_args))
+                .node(
+                    EvolveNode::new(evolve_args)
+                        .with_mev_store(mev_store.clone())
+                        .with_mev_auction(mev_auction.clone())
+                        .with_validator_snapshot(validator_snapshot)
+                        .with_live_config(Some(live_config))
+                        .with_payload_stats(Some(payload_stats.clone()))
+                        .with_health(Some(health.clone()))
+                        .with_payload_config(payload_config),
+                )
                 .extend_rpc_modules(move |ctx| {
-                    // Build custom txpool RPC with config + optional CLI/env override
-                    let evolve_cfg = EvolveConfig::default();
-                    let evolve_txpool =
-                        EvolveTxpoolApiImpl::new(ctx.pool().clone(), evolve_cfg.max_txpool_bytes);
+                    // Build custom txpool RPC from the [txpool] section of the config
+                    let evolve_txpool = EvolveTxpoolApiImpl::new(ctx.pool().clone(), max_txpool_bytes);
+
+                    // Runtime switches and reloading are only served on the authenticated transport
+                    ctx.auth_module.merge_auth_methods(AndeRuntimeApiImpl::new(runtime_switches).into_rpc())?;
+                    if let Some(watcher) = rpc_config_watcher {
+                        let max_bytes = evolve_txpool.max_bytes_handle();
+                        watcher.on_reload(move |config| {
+                            max_bytes.store(config.txpool.max_txpool_bytes, Ordering::Relaxed)
+                        });
+                        let health = health.clone();
+                        watcher.on_reload(move |config| health.set_thresholds(config.health));
+                        // Without a config file there is nothing to reload, clap rejects the admin RPC flag
+                        if enable_admin_rpc {
+                            info!("=== EV-RETH: Admin RPC enabled on the authenticated transport ===");
+                            ctx.auth_module.merge_auth_methods(AndeAdminApiImpl::new(watcher).into_rpc())?;
+                        }
+                    }
 
                     // Merge into all enabled transports (HTTP / WS)
                     ctx.modules.merge_configured(evolve_txpool.into_rpc())?;
+                    // Read-only stats of the blocks built recently
+                    ctx.modules.merge_configured(AndeStatsApiImpl::new(payload_stats).into_rpc())?;
+                    // Only the worker pool runs in this binary, the other subsystems report null
+                    ctx.modules.merge_configured(AndeHealthApiImpl::new(health).into_rpc())?;
+                    // Read-only consensus data, reporting that no consensus client runs in this binary
+                    ctx.modules.merge_configured(AndeConsensusApiImpl::new(None).into_rpc())?;
+                    // Caps and transfers of the ANDE precompile, as configured for block execution
+                    let precompile_config =
+                        ctx.node().evm_config().evm_factory().precompile_provider().config().clone();
+                    let duality = AndeDualityApiImpl::new(ctx.provider().clone(), precompile_config);
+                    ctx.modules.merge_configured(duality.into_rpc())?;
+
+                    // MEV data stays off public endpoints unless explicitly enabled
+                    if enable_mev_rpc {
+                        info!("=== EV-RETH: MEV RPC enabled ===");
+                        ctx.modules.merge_configured(AndeMevApiImpl::new(mev_store).into_rpc())?;
+                        // Searchers dry-run bundles against the node's state and EVM
+                        let simulation =
+                            AndeSimulationApiImpl::new(ctx.provider().clone(), ctx.node().evm_config().clone());
+                        ctx.modules.merge_configured(simulation.into_rpc())?;
+                    }
+                    // Searchers bid for the top of the block through the auction the payload builder reads
+                    if let Some(auction) = mev_auction {
+                        let bundles = AndeBundleApiImpl::new(auction, ctx.config().chain.chain.id());
+                        ctx.modules.merge_configured(bundles.into_rpc())?;
+                    }
                     Ok(())
                 })
                 .launch()
                 .await?;
 
             info!("=== EV-RETH: Node launched successfully with ev-reth payload builder ===");
+            // Runs until the node exits
+            let _config_watch =
+                config_watcher.map(|watcher| watcher.start_polling_task(DEFAULT_CONFIG_POLL_INTERVAL));
+            let _heartbeat = sequencer_args.start_auto_registration().await?;
             handle.node_exit_future.await
         },
     ) {
@@ -0,0 +1,43 @@
+use clap::Args;
+use ev_node::{ParallelPreset, ParallelTuning};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+
+/// Parallel execution of payload transactions, overriding the `[parallel]`
+/// section of the payload builder config
+#[derive(Debug, Clone, Args, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ParallelArgs {
+    /// Build blocks with the parallel executor
+    #[arg(
+        long = "ev-reth.parallel",
+        env = "EV_RETH_PARALLEL",
+        help = "Execute payload transactions in parallel"
+    )]
+    pub enabled: bool,
+
+    /// Preset the parallel executor settings start from
+    #[arg(
+        long = "ev-reth.parallel-preset",
+        value_name = "PRESET",
+        env = "EV_RETH_PARALLEL_PRESET",
+        help = "Parallel executor preset: default, high_throughput, low_latency or testing"
+    )]
+    pub preset: Option<ParallelPreset>,
+
+    /// Worker threads of the parallel executor
+    #[arg(long = "ev-reth.parallel-concurrency", value_name = "THREADS", env = "EV_RETH_PARALLEL_CONCURRENCY")]
+    pub concurrency_level: Option<NonZeroUsize>,
+}
+
+impl ParallelArgs {
+    /// Apply the flags given on the command line to `parallel`
+    pub fn apply(&self, parallel: &mut ParallelTuning) {
+        parallel.enabled |= self.enabled;
+        if let Some(preset) = self.preset {
+            parallel.preset = preset;
+        }
+        if let Some(concurrency_level) = self.concurrency_level {
+            parallel.concurrency_level = Some(concurrency_level);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use alloy_primitives::{Address, B256};
+use clap::Args;
+use evolve_ev_reth::sequencer_registry::{read_sequencer_key, SequencerRegistryClient};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+/// Registration of the node's sequencer with the AndeSequencerRegistry
+#[derive(Debug, Clone, Args, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SequencerRegistryArgs {
+    /// Register with the AndeSequencerRegistry at startup if needed, and send heartbeats
+    #[arg(
+        long = "ande.sequencer-auto-register",
+        requires_all = ["registry", "key_file", "p2p_peer_id", "rpc_endpoint"],
+        help = "Register the sequencer with the AndeSequencerRegistry at startup if needed, and send heartbeats"
+    )]
+    pub auto_register: bool,
+
+    /// AndeSequencerRegistry contract address
+    #[arg(long = "ande.sequencer-registry", value_name = "ADDRESS", env = "ANDE_SEQUENCER_REGISTRY_ADDRESS")]
+    pub registry: Option<Address>,
+
+    /// RPC endpoints of the chain the registry is deployed on, by priority
+    #[arg(
+        long = "ande.rpc-url",
+        value_name = "URL",
+        env = "ANDE_RPC_URL",
+        value_delimiter = ',',
+        default_value = "http://localhost:8545"
+    )]
+    pub rpc_urls: Vec<String>,
+
+    /// File holding the hex-encoded sequencer private key
+    #[arg(long = "ande.sequencer-key-file", value_name = "PATH", env = "SEQUENCER_PRIVATE_KEY_FILE")]
+    pub key_file: Option<PathBuf>,
+
+    /// libp2p peer ID the sequencer is registered with
+    #[arg(long = "ande.sequencer-p2p-peer-id", value_name = "PEER_ID")]
+    pub p2p_peer_id: Option<B256>,
+
+    /// RPC endpoint the sequencer is registered with
+    #[arg(long = "ande.sequencer-rpc-endpoint", value_name = "URL")]
+    pub rpc_endpoint: Option<String>,
+
+    /// Seconds between two heartbeats
+    #[arg(long = "ande.sequencer-heartbeat-interval", value_name = "SECONDS", default_value_t = 60)]
+    pub heartbeat_interval_secs: u64,
+}
+
+impl SequencerRegistryArgs {
+    /// Register the sequencer if it isn't yet and start sending heartbeats,
+    /// when auto-registration is enabled
+    pub async fn start_auto_registration(&self) -> eyre::Result<Option<JoinHandle<()>>> {
+        if !self.auto_register {
+            return Ok(None);
+        }
+        let (Some(registry), Some(key_file), Some(p2p_peer_id), Some(rpc_endpoint)) =
+            (self.registry, &self.key_file, self.p2p_peer_id, &self.rpc_endpoint)
+        else {
+            eyre::bail!(
+                "--ande.sequencer-auto-register requires the registry address, sequencer key file, \
+                 p2p peer ID and RPC endpoint"
+            );
+        };
+
+        let signer = read_sequencer_key(key_file)?;
+        let rpc_urls: Vec<&str> = self.rpc_urls.iter().map(String::as_str).collect();
+        let client = Arc::new(SequencerRegistryClient::connect(&rpc_urls, registry, signer)?);
+        if client.ensure_registered(p2p_peer_id, rpc_endpoint.clone()).await? {
+            info!("=== EV-RETH: Sequencer {} registered ===", client.sequencer_address());
+        }
+
+        let interval = Duration::from_secs(self.heartbeat_interval_secs);
+        Ok(Some(client.start_heartbeat_task(interval)))
+    }
+}
@@ -7,12 +7,16 @@
 //!
 //! - `AndeConsensus`: Main consensus contract with proposer selection
 //! - `AndeNativeStaking`: Staking contract with voting power calculation
+//! - `IAndeNativeStaking`: Staking queries read by the node
 //! - `AndeSequencerRegistry`: Sequencer registration and management
+//! - `MEVAuctionManager`: MEV bundle auction settled by the sequencer
+//! - `MEVDistributor`: Receives captured MEV for distribution to stakers
 
 #![warn(missing_docs, unreachable_pub)]
 #![deny(unused_must_use, rust_2018_idioms)]
 
 use alloy::sol;
+use std::path::{Path, PathBuf};
 
 // Generate Rust bindings from Solidity contract ABI
 sol! {
@@ -39,13 +43,162 @@ sol! {
     "../../../andechain/out/AndeSequencerRegistry.sol/AndeSequencerRegistry.json"
 }
 
+sol! {
+    /// Staking queries of the AndeNativeStaking contract read by the node
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IAndeNativeStaking {
+        struct Delegation {
+            address validator;
+            uint256 amount;
+            uint256 since;
+        }
+
+        struct UnbondingEntry {
+            uint256 amount;
+            uint256 completionTime;
+        }
+
+        function getVotingPower(address account) external view returns (uint256);
+
+        function totalStaked() external view returns (uint256);
+
+        function getDelegations(address delegator) external view returns (Delegation[] memory);
+
+        function getUnbondingEntries(address account) external view returns (UnbondingEntry[] memory);
+    }
+}
+
+sol! {
+    /// Functions of the MEVAuctionManager contract called by the sequencer
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface MEVAuctionManager {
+        function submitBundle(
+            bytes32 bundleHash,
+            address searcher,
+            uint256 bidAmount,
+            uint256 targetBlock,
+            bytes32[] calldata txHashes
+        ) external;
+
+        function markBundleExecuted(bytes32 bundleHash, uint256 mevCaptured, uint256 bidPaid) external;
+
+        function markBundleRejected(bytes32 bundleHash, string calldata reason) external;
+
+        function collateralOf(address searcher) external view returns (uint256);
+    }
+}
+
+sol! {
+    /// Functions of the MEVDistributor contract called by the sequencer
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface MEVDistributor {
+        function depositMEV() external payable;
+
+        function settleEpoch() external;
+
+        function currentEpoch() external view returns (uint256);
+
+        function epochDuration() external view returns (uint256);
+
+        function epochStartTime() external view returns (uint256);
+
+        function designatedSettler() external view returns (address);
+
+        function stakersBps() external view returns (uint256);
+
+        function protocolBps() external view returns (uint256);
+
+        function treasuryBps() external view returns (uint256);
+
+        event SplitUpdated(uint256 stakersBps, uint256 protocolBps, uint256 treasuryBps);
+
+        function getEpochData(uint256 epoch) external view returns (
+            uint256 totalMEV,
+            uint256 stakersReward,
+            uint256 protocolFee,
+            uint256 treasuryAmount,
+            bool settled,
+            uint256 timestamp
+        );
+    }
+}
+
 // Re-export main contract types
 pub use AndeConsensus::*;
 pub use AndeNativeStaking::*;
 pub use AndeSequencerRegistry::*;
 
+/// Environment variable naming the deployments file read by [`ContractAddresses::resolve`]
+pub const DEPLOYMENTS_FILE_ENV: &str = "ANDE_DEPLOYMENTS_FILE";
+
+/// Deployments file read by [`ContractAddresses::resolve`] when
+/// [`DEPLOYMENTS_FILE_ENV`] isn't set
+pub const DEFAULT_DEPLOYMENTS_FILE: &str = "deployments.toml";
+
+/// Chain id of local development networks (anvil, hardhat)
+pub const DEV_CHAIN_ID: u64 = 31337;
+
+/// Errors loading [`ContractAddresses`]
+#[derive(Debug, thiserror::Error)]
+pub enum ContractAddressesError {
+    /// The deployments file could not be read
+    #[error("Failed to read deployments file {path}: {source}")]
+    Io {
+        /// File that was read
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+    /// The deployments file isn't valid TOML
+    #[error("Malformed deployments file {path}: {source}")]
+    Malformed {
+        /// File that was read
+        path: PathBuf,
+        /// Underlying error
+        source: Box<toml::de::Error>,
+    },
+    /// The deployments file has no section for the chain
+    #[error("Deployments file {path} has no [{chain_id}] section")]
+    MissingSection {
+        /// File that was read
+        path: PathBuf,
+        /// Chain the addresses were looked up for
+        chain_id: u64,
+    },
+    /// A required address is missing from the chain's section
+    #[error("Deployments file {path} is missing `{key}` in section [{chain_id}]")]
+    MissingKey {
+        /// File that was read
+        path: PathBuf,
+        /// Chain the addresses were looked up for
+        chain_id: u64,
+        /// Missing key
+        key: &'static str,
+    },
+    /// A required environment variable isn't set
+    #[error("Environment variable {0} is not set")]
+    MissingEnv(&'static str),
+    /// An address could not be parsed
+    #[error("Invalid address for {name}: {reason}")]
+    InvalidAddress {
+        /// Key or environment variable holding the address
+        name: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// No source has addresses for the chain
+    #[error("No contract addresses for chain {0} in the deployments file, environment or defaults")]
+    Unresolved(u64),
+}
+
 /// Contract addresses configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ContractAddresses {
     /// AndeConsensus contract address
     pub consensus: alloy_primitives::Address,
@@ -53,6 +206,10 @@ pub struct ContractAddresses {
     pub staking: alloy_primitives::Address,
     /// AndeSequencerRegistry contract address
     pub sequencer_registry: alloy_primitives::Address,
+    /// MEVAuctionManager contract address, if deployed
+    pub mev_auction: Option<alloy_primitives::Address>,
+    /// MEVDistributor contract address, if deployed
+    pub mev_distributor: Option<alloy_primitives::Address>,
 }
 
 impl ContractAddresses {
@@ -62,29 +219,20 @@ impl ContractAddresses {
     /// - `ANDE_CONSENSUS_ADDRESS`
     /// - `ANDE_STAKING_ADDRESS`
     /// - `ANDE_SEQUENCER_REGISTRY_ADDRESS`
+    ///
+    /// `ANDE_MEV_AUCTION_ADDRESS` and `ANDE_MEV_DISTRIBUTOR_ADDRESS` are read
+    /// if set.
     pub fn from_env() -> eyre::Result<Self> {
-        let consensus = std::env::var("ANDE_CONSENSUS_ADDRESS")?
-            .parse()
-            .map_err(|e| eyre::eyre!("Invalid consensus address: {}", e))?;
-            
-        let staking = std::env::var("ANDE_STAKING_ADDRESS")?
-            .parse()
-            .map_err(|e| eyre::eyre!("Invalid staking address: {}", e))?;
-
-        let sequencer_registry = std::env::var("ANDE_SEQUENCER_REGISTRY_ADDRESS")?
-            .parse()
-            .map_err(|e| eyre::eyre!("Invalid sequencer registry address: {}", e))?;
-            
-        Ok(Self { consensus, staking, sequencer_registry })
+        Ok(Self::from_vars(|name| std::env::var(name).ok())?)
     }
-    
+
     /// Create new contract addresses manually
     pub const fn new(
         consensus: alloy_primitives::Address,
         staking: alloy_primitives::Address,
         sequencer_registry: alloy_primitives::Address,
     ) -> Self {
-        Self { consensus, staking, sequencer_registry }
+        Self { consensus, staking, sequencer_registry, mev_auction: None, mev_distributor: None }
     }
 
     /// Create from hex strings (useful for testing)
@@ -102,6 +250,133 @@ impl ContractAddresses {
         
         Ok(Self::new(consensus, staking, sequencer_registry))
     }
+
+    /// Load the addresses of chain `chain_id` from a TOML deployments file
+    ///
+    /// The file has a section per chain id:
+    ///
+    /// ```toml
+    /// [31337]
+    /// consensus = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+    /// staking = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"
+    /// sequencer_registry = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0"
+    /// # Optional
+    /// mev_auction = "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"
+    /// mev_distributor = "0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9"
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>, chain_id: u64) -> Result<Self, ContractAddressesError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| ContractAddressesError::Io { path: path.to_path_buf(), source })?;
+        let deployments: toml::Table = contents.parse().map_err(|source| ContractAddressesError::Malformed {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })?;
+
+        let section = deployments
+            .get(&chain_id.to_string())
+            .and_then(toml::Value::as_table)
+            .ok_or_else(|| ContractAddressesError::MissingSection { path: path.to_path_buf(), chain_id })?;
+        let address = |key: &'static str| -> Result<Option<alloy_primitives::Address>, ContractAddressesError> {
+            let Some(value) = section.get(key) else {
+                return Ok(None);
+            };
+            let name = format!("`{key}` in section [{chain_id}] of {}", path.display());
+            let value = value
+                .as_str()
+                .ok_or_else(|| ContractAddressesError::InvalidAddress { name: name.clone(), reason: "not a string".into() })?;
+            parse_address(name, value).map(Some)
+        };
+        let required = |key: &'static str| {
+            address(key)?.ok_or_else(|| ContractAddressesError::MissingKey { path: path.to_path_buf(), chain_id, key })
+        };
+
+        Ok(Self {
+            consensus: required("consensus")?,
+            staking: required("staking")?,
+            sequencer_registry: required("sequencer_registry")?,
+            mev_auction: address("mev_auction")?,
+            mev_distributor: address("mev_distributor")?,
+        })
+    }
+
+    /// Addresses of chain `chain_id`, from the first source that has them
+    ///
+    /// Sources, by precedence:
+    /// 1. The deployments file named by [`DEPLOYMENTS_FILE_ENV`], or
+    ///    [`DEFAULT_DEPLOYMENTS_FILE`] if it exists, when it has a section for
+    ///    the chain
+    /// 2. The environment variables read by [`Self::from_env`], when
+    ///    `ANDE_CONSENSUS_ADDRESS` is set
+    /// 3. The built-in defaults, see [`Self::default_for`]
+    ///
+    /// A source that has addresses for the chain but fails to load, e.g. a
+    /// malformed file or a section missing a key, is an error rather than
+    /// skipped, so misconfigurations don't silently fall back to other
+    /// addresses.
+    pub fn resolve(chain_id: u64) -> Result<Self, ContractAddressesError> {
+        let env = |name: &str| std::env::var(name).ok();
+        let file = env(DEPLOYMENTS_FILE_ENV).map(PathBuf::from).or_else(|| {
+            let default = PathBuf::from(DEFAULT_DEPLOYMENTS_FILE);
+            default.exists().then_some(default)
+        });
+        Self::resolve_from(chain_id, file.as_deref(), env)
+    }
+
+    /// [`Self::resolve`], reading the deployments file at `file` and the
+    /// environment through `env`
+    fn resolve_from(
+        chain_id: u64,
+        file: Option<&Path>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ContractAddressesError> {
+        if let Some(file) = file {
+            match Self::from_file(file, chain_id) {
+                Err(ContractAddressesError::MissingSection { .. }) => {}
+                result => return result,
+            }
+        }
+        if env("ANDE_CONSENSUS_ADDRESS").is_some() {
+            return Self::from_vars(env);
+        }
+        Self::default_for(chain_id).ok_or(ContractAddressesError::Unresolved(chain_id))
+    }
+
+    /// Built-in addresses of chain `chain_id`, if known
+    ///
+    /// Only [`DEV_CHAIN_ID`] has defaults: the addresses the contracts get
+    /// when deployed in order by the first anvil account on a fresh chain.
+    pub fn default_for(chain_id: u64) -> Option<Self> {
+        (chain_id == DEV_CHAIN_ID).then(|| Self {
+            consensus: alloy_primitives::address!("5FbDB2315678afecb367f032d93F642f64180aa3"),
+            staking: alloy_primitives::address!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512"),
+            sequencer_registry: alloy_primitives::address!("9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0"),
+            mev_auction: None,
+            mev_distributor: None,
+        })
+    }
+
+    /// Read the addresses from the environment variables, looked up with `env`
+    fn from_vars(env: impl Fn(&str) -> Option<String>) -> Result<Self, ContractAddressesError> {
+        let address = |name: &'static str| env(name).map(|value| parse_address(name.to_string(), &value)).transpose();
+        let required = |name: &'static str| address(name)?.ok_or(ContractAddressesError::MissingEnv(name));
+
+        Ok(Self {
+            consensus: required("ANDE_CONSENSUS_ADDRESS")?,
+            staking: required("ANDE_STAKING_ADDRESS")?,
+            sequencer_registry: required("ANDE_SEQUENCER_REGISTRY_ADDRESS")?,
+            mev_auction: address("ANDE_MEV_AUCTION_ADDRESS")?,
+            mev_distributor: address("ANDE_MEV_DISTRIBUTOR_ADDRESS")?,
+        })
+    }
+}
+
+/// Parse the address held by `name`
+fn parse_address(name: String, value: &str) -> Result<alloy_primitives::Address, ContractAddressesError> {
+    value.trim().parse().map_err(|e: alloy_primitives::hex::FromHexError| ContractAddressesError::InvalidAddress {
+        name,
+        reason: e.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -133,4 +408,106 @@ mod tests {
             "0x5fbdb2315678afecb367f032d93f642f64180aa3"
         );
     }
-}
\ No newline at end of file
+
+    const DEPLOYMENTS: &str = r#"
+[31337]
+consensus = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+staking = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"
+sequencer_registry = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0"
+mev_auction = "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"
+
+[6174]
+consensus = "0x0000000000000000000000000000000000000001"
+staking = "0x0000000000000000000000000000000000000002"
+sequencer_registry = "0x0000000000000000000000000000000000000003"
+"#;
+
+    fn deployments_file(contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        file
+    }
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn test_contract_addresses_from_file() {
+        let file = deployments_file(DEPLOYMENTS);
+
+        let dev = ContractAddresses::from_file(file.path(), 31337).unwrap();
+        assert_eq!(dev, ContractAddresses { mev_auction: dev.mev_auction, ..ContractAddresses::default_for(31337).unwrap() });
+        assert_eq!(dev.mev_auction, Some("0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9".parse().unwrap()));
+        assert_eq!(dev.mev_distributor, None);
+
+        let other = ContractAddresses::from_file(file.path(), 6174).unwrap();
+        assert_eq!(other.sequencer_registry, alloy_primitives::Address::with_last_byte(3));
+        assert_eq!(other.mev_auction, None);
+    }
+
+    #[test]
+    fn test_contract_addresses_malformed_files() {
+        let missing_section = ContractAddresses::from_file(deployments_file(DEPLOYMENTS).path(), 1).unwrap_err();
+        assert!(matches!(missing_section, ContractAddressesError::MissingSection { chain_id: 1, .. }));
+        assert!(missing_section.to_string().contains("no [1] section"), "{missing_section}");
+
+        let file = deployments_file("[1]\nconsensus = \"0x0000000000000000000000000000000000000001\"\n");
+        let missing_key = ContractAddresses::from_file(file.path(), 1).unwrap_err();
+        assert!(
+            matches!(missing_key, ContractAddressesError::MissingKey { chain_id: 1, key: "staking", .. }),
+            "{missing_key}"
+        );
+        assert!(missing_key.to_string().contains("missing `staking` in section [1]"), "{missing_key}");
+
+        let file = deployments_file(&DEPLOYMENTS.replace("0x0000000000000000000000000000000000000002", "0x1234"));
+        let invalid = ContractAddresses::from_file(file.path(), 6174).unwrap_err();
+        assert!(invalid.to_string().contains("`staking` in section [6174]"), "{invalid}");
+
+        let file = deployments_file("[31337\nconsensus = ");
+        assert!(matches!(
+            ContractAddresses::from_file(file.path(), 31337),
+            Err(ContractAddressesError::Malformed { .. })
+        ));
+        assert!(matches!(
+            ContractAddresses::from_file("/nonexistent/deployments.toml", 31337),
+            Err(ContractAddressesError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_contract_addresses_resolution_order() {
+        let file = deployments_file(DEPLOYMENTS);
+        let vars = [
+            ("ANDE_CONSENSUS_ADDRESS", "0x00000000000000000000000000000000000000aa"),
+            ("ANDE_STAKING_ADDRESS", "0x00000000000000000000000000000000000000bb"),
+            ("ANDE_SEQUENCER_REGISTRY_ADDRESS", "0x00000000000000000000000000000000000000cc"),
+        ];
+
+        // The file wins when it has a section for the chain
+        let resolved = ContractAddresses::resolve_from(6174, Some(file.path()), env(&vars)).unwrap();
+        assert_eq!(resolved, ContractAddresses::from_file(file.path(), 6174).unwrap());
+
+        // Then the environment
+        let resolved = ContractAddresses::resolve_from(1, Some(file.path()), env(&vars)).unwrap();
+        assert_eq!(resolved.consensus, alloy_primitives::Address::with_last_byte(0xaa));
+        let resolved = ContractAddresses::resolve_from(1, None, env(&vars)).unwrap();
+        assert_eq!(resolved.sequencer_registry, alloy_primitives::Address::with_last_byte(0xcc));
+
+        // Then the defaults, known for the dev chain only
+        let resolved = ContractAddresses::resolve_from(DEV_CHAIN_ID, None, env(&[])).unwrap();
+        assert_eq!(Some(resolved), ContractAddresses::default_for(DEV_CHAIN_ID));
+        assert!(matches!(
+            ContractAddresses::resolve_from(1, Some(file.path()), env(&[])),
+            Err(ContractAddressesError::Unresolved(1))
+        ));
+
+        // Broken sources are errors rather than skipped
+        let broken = deployments_file("[1]\nconsensus = \"0x01\"\n");
+        assert!(ContractAddresses::resolve_from(1, Some(broken.path()), env(&vars)).is_err());
+        assert!(matches!(
+            ContractAddresses::resolve_from(1, None, env(&vars[..1])),
+            Err(ContractAddressesError::MissingEnv("ANDE_STAKING_ADDRESS"))
+        ));
+    }
+}
@@ -1,23 +1,210 @@
 //! Block Attestation Module
 //!
 //! Handles signing and attesting blocks to the AndeConsensusV2 contract.
+//!
+//! Attestations that fail to be submitted, e.g. during an RPC outage, are
+//! queued and retried with exponential backoff by a background task, until
+//! they succeed or the block gets finalized without them. The queue can be
+//! persisted to the node's datadir so it survives restarts.
 
 use alloy::{
-    primitives::{keccak256, Bytes, B256},
+    primitives::{Bytes, B256},
     signers::local::PrivateKeySigner,
 };
+use async_trait::async_trait;
 use eyre::Result;
-use std::sync::Arc;
-use tracing::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::{
+    consensus_client::{sign_block_proposal, AndeConsensusClient, ConsensusClientError},
+    metrics::AttestationMetrics,
+    rpc::validator::AttestationStatusSource,
+};
+
+/// Name of the file the retry queue is persisted to, in the datadir
+pub const ATTESTATION_QUEUE_FILE: &str = "attestation_queue.json";
+
+/// Delay before the first retry of a failed attestation
+pub const ATTESTATION_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Maximum delay between two retries of a failed attestation
+pub const ATTESTATION_RETRY_MAX_DELAY: Duration = Duration::from_secs(120);
+
+/// Interval at which the retry task looks for attestations due for a retry
+const RETRY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Consensus contract calls made by the attester
+#[async_trait]
+pub trait ConsensusBackend: Send + Sync {
+    /// Submit the signed attestation of a block
+    async fn propose_block(&self, block_number: u64, block_hash: B256, signature: Bytes) -> Result<B256>;
+
+    /// Whether a block is already finalized on-chain
+    async fn is_block_finalized(&self, block_hash: B256) -> Result<bool>;
+}
+
+#[async_trait]
+impl ConsensusBackend for AndeConsensusClient {
+    async fn propose_block(&self, block_number: u64, block_hash: B256, signature: Bytes) -> Result<B256> {
+        Ok(AndeConsensusClient::propose_block(self, block_number, block_hash, signature).await?)
+    }
+
+    async fn is_block_finalized(&self, block_hash: B256) -> Result<bool> {
+        Ok(AndeConsensusClient::is_block_finalized(self, block_hash).await?)
+    }
+}
+
+/// Attestation waiting to be retried
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingAttestation {
+    /// Block number to attest
+    pub block_number: u64,
+    /// Hash of the block
+    pub block_hash: B256,
+    /// When the attestation was first attempted, in milliseconds since the Unix epoch
+    pub first_attempt_ms: u64,
+    /// Number of failed attempts
+    pub attempts: u32,
+    /// When the attestation is due for a retry, in milliseconds since the Unix epoch
+    pub next_attempt_ms: u64,
+}
+
+/// Delay before retrying an attestation that failed `attempts` times
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 1u32.checked_shl(attempts.saturating_sub(1)).unwrap_or(u32::MAX);
+    ATTESTATION_RETRY_BASE_DELAY.saturating_mul(factor).min(ATTESTATION_RETRY_MAX_DELAY)
+}
+
+/// Whether `err` is the contract rejecting the attestation, which retrying won't change
+fn is_rejection(err: &eyre::Report) -> bool {
+    err.downcast_ref::<ConsensusClientError>().is_some_and(|err| !err.is_transient())
+}
+
+/// Current time, in milliseconds since the Unix epoch
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or_default()
+}
+
+/// Failed attestations, by block number and hash, optionally persisted to a file
+#[derive(Debug, Default)]
+pub struct AttestationRetryQueue {
+    pending: BTreeMap<(u64, B256), PendingAttestation>,
+    path: Option<PathBuf>,
+}
 
-use crate::consensus_client::AndeConsensusClient;
+impl AttestationRetryQueue {
+    /// Create an empty queue kept in memory only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the queue persisted at `path`, empty if the file doesn't exist yet
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let pending: Vec<PendingAttestation> = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let pending =
+            pending.into_iter().map(|attestation| ((attestation.block_number, attestation.block_hash), attestation));
+        Ok(Self { pending: pending.collect(), path: Some(path) })
+    }
+
+    /// Queue the attestation of a block first attempted at `now_ms`
+    ///
+    /// An attestation already queued is left as is.
+    pub fn push(&mut self, block_number: u64, block_hash: B256, now_ms: u64) -> Result<()> {
+        self.pending.entry((block_number, block_hash)).or_insert_with(|| PendingAttestation {
+            block_number,
+            block_hash,
+            first_attempt_ms: now_ms,
+            attempts: 1,
+            next_attempt_ms: now_ms.saturating_add(retry_delay(1).as_millis() as u64),
+        });
+        self.persist()
+    }
+
+    /// Attestations due for a retry at `now_ms`, oldest block first
+    pub fn due(&self, now_ms: u64) -> Vec<PendingAttestation> {
+        self.pending.values().filter(|attestation| attestation.next_attempt_ms <= now_ms).cloned().collect()
+    }
+
+    /// Record another failed attempt at `now_ms`, pushing the next retry back
+    pub fn record_failure(&mut self, block_number: u64, block_hash: B256, now_ms: u64) -> Result<()> {
+        if let Some(attestation) = self.pending.get_mut(&(block_number, block_hash)) {
+            attestation.attempts = attestation.attempts.saturating_add(1);
+            attestation.next_attempt_ms =
+                now_ms.saturating_add(retry_delay(attestation.attempts).as_millis() as u64);
+        }
+        self.persist()
+    }
+
+    /// Remove an attestation, submitted or no longer needed
+    pub fn remove(&mut self, block_number: u64, block_hash: B256) -> Result<Option<PendingAttestation>> {
+        let removed = self.pending.remove(&(block_number, block_hash));
+        self.persist()?;
+        Ok(removed)
+    }
+
+    /// Number of queued attestations
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no attestation is queued
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Write the queue to its file, if any
+    fn persist(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let pending: Vec<_> = self.pending.values().collect();
+        // Replace the file at once, so a crash never leaves it half written
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&pending)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
 
 /// Block attester for signing and submitting blocks to consensus contract
 pub struct BlockAttester {
     /// Signer for creating ECDSA signatures
     signer: PrivateKeySigner,
     /// Consensus client for submitting proposals
-    consensus_client: Arc<AndeConsensusClient>,
+    consensus_client: Arc<dyn ConsensusBackend>,
+    /// Attestations to retry
+    retry_queue: Mutex<AttestationRetryQueue>,
+    /// Highest block attested successfully, 0 until the first attestation
+    last_attested: AtomicU64,
+    /// Prometheus metrics
+    metrics: AttestationMetrics,
+}
+
+impl fmt::Debug for BlockAttester {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockAttester")
+            .field("signer", &self.signer.address())
+            .field("pending_attestations", &self.pending_attestations())
+            .field("last_attested", &self.last_attested)
+            .finish_non_exhaustive()
+    }
 }
 
 impl BlockAttester {
@@ -26,7 +213,7 @@ impl BlockAttester {
     /// # Arguments
     /// * `signer` - Private key signer for attestations
     /// * `consensus_client` - Consensus contract client
-    pub fn new(signer: PrivateKeySigner, consensus_client: Arc<AndeConsensusClient>) -> Self {
+    pub fn new(signer: PrivateKeySigner, consensus_client: Arc<dyn ConsensusBackend>) -> Self {
         info!(
             "BlockAttester initialized with signer address: {:?}",
             signer.address()
@@ -34,9 +221,23 @@ impl BlockAttester {
         Self {
             signer,
             consensus_client,
+            retry_queue: Mutex::new(AttestationRetryQueue::new()),
+            last_attested: AtomicU64::new(0),
+            metrics: AttestationMetrics::default(),
         }
     }
 
+    /// Persist the retry queue to [`ATTESTATION_QUEUE_FILE`] in `datadir`,
+    /// resuming the attestations queued before a restart
+    pub fn with_datadir(self, datadir: &Path) -> Result<Self> {
+        let queue = AttestationRetryQueue::open(datadir.join(ATTESTATION_QUEUE_FILE))?;
+        if !queue.is_empty() {
+            info!("Resuming {} queued attestations", queue.len());
+        }
+        self.metrics.retry_queue_depth.set(queue.len() as f64);
+        Ok(Self { retry_queue: Mutex::new(queue), ..self })
+    }
+
     /// Attest a block by signing and submitting to consensus contract
     ///
     /// # Arguments
@@ -44,22 +245,125 @@ impl BlockAttester {
     /// * `block_hash` - Hash of the block
     ///
     /// # Returns
-    /// Transaction hash of the attestation
+    /// Transaction hash of the attestation. If it failed for a reason that may
+    /// go away, e.g. the RPC endpoint being down, the attestation is queued
+    /// for a retry by [`Self::start_retry_task`].
     pub async fn attest_block(&self, block_number: u64, block_hash: B256) -> Result<B256> {
+        let result = self.try_attest_block(block_number, block_hash).await;
+        match &result {
+            Ok(_) => self.record_success(block_number),
+            // Rejected by the contract, e.g. not our turn: retrying won't help
+            Err(e) if is_rejection(e) => {
+                self.metrics.failures.increment(1);
+                warn!("Attestation of block {} rejected, not retrying: {}", block_number, e);
+            }
+            Err(_) => {
+                self.metrics.failures.increment(1);
+                let mut queue = self.retry_queue();
+                if let Err(e) = queue.push(block_number, block_hash, now_ms()) {
+                    warn!("Failed to persist attestation retry queue: {}", e);
+                }
+                self.metrics.retry_queue_depth.set(queue.len() as f64);
+            }
+        }
+        result
+    }
+
+    /// Number of attestations waiting to be retried
+    pub fn pending_attestations(&self) -> usize {
+        self.retry_queue().len()
+    }
+
+    /// Highest block attested successfully, if any
+    pub fn last_attested_block(&self) -> Option<u64> {
+        Some(self.last_attested.load(Ordering::Relaxed)).filter(|block| *block != 0)
+    }
+
+    fn record_success(&self, block_number: u64) {
+        self.metrics.successes.increment(1);
+        self.last_attested.fetch_max(block_number, Ordering::Relaxed);
+    }
+
+    fn retry_queue(&self) -> MutexGuard<'_, AttestationRetryQueue> {
+        self.retry_queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Retry the queued attestations that are due
+    pub async fn retry_pending(&self) {
+        self.retry_due(now_ms()).await
+    }
+
+    /// Retry the attestations due at `now_ms`
+    ///
+    /// Attestations of blocks already finalized on-chain are dropped, as they
+    /// can no longer count.
+    async fn retry_due(&self, now_ms: u64) {
+        let due = self.retry_queue().due(now_ms);
+        for attestation in due {
+            let (block_number, block_hash) = (attestation.block_number, attestation.block_hash);
+
+            let outcome = match self.consensus_client.is_block_finalized(block_hash).await {
+                Ok(true) => {
+                    warn!(
+                        "Dropping attestation of block {} after {} attempts: already finalized",
+                        block_number, attestation.attempts
+                    );
+                    self.metrics.dropped.increment(1);
+                    self.retry_queue().remove(block_number, block_hash)
+                }
+                Ok(false) => match self.try_attest_block(block_number, block_hash).await {
+                    Ok(_) => {
+                        self.record_success(block_number);
+                        self.retry_queue().remove(block_number, block_hash)
+                    }
+                    Err(e) if is_rejection(&e) => {
+                        warn!("Dropping attestation of block {}: rejected: {}", block_number, e);
+                        self.metrics.failures.increment(1);
+                        self.metrics.dropped.increment(1);
+                        self.retry_queue().remove(block_number, block_hash)
+                    }
+                    Err(e) => {
+                        debug!("Retry {} of block {} attestation failed: {}", attestation.attempts, block_number, e);
+                        self.metrics.failures.increment(1);
+                        self.retry_queue().record_failure(block_number, block_hash, now_ms)
+                    }
+                },
+                Err(e) => {
+                    debug!("Failed to check finalization of block {}: {}", block_number, e);
+                    self.retry_queue().record_failure(block_number, block_hash, now_ms)
+                }
+            };
+            if let Err(e) = outcome {
+                warn!("Failed to persist attestation retry queue: {}", e);
+            }
+        }
+        self.metrics.retry_queue_depth.set(self.pending_attestations() as f64);
+    }
+
+    /// Start the background task retrying failed attestations
+    pub fn start_retry_task(self: Arc<Self>) -> JoinHandle<()> {
+        info!("Starting attestation retry task");
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RETRY_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                self.retry_pending().await;
+            }
+        })
+    }
+
+    /// Sign and submit the attestation for a block
+    async fn try_attest_block(&self, block_number: u64, block_hash: B256) -> Result<B256> {
         debug!(
             "Attesting block {} with hash {:?}",
             block_number, block_hash
         );
 
-        // 1. Create message to sign (blockNumber || blockHash)
-        let message = Self::create_attestation_message(block_number, block_hash);
-        debug!("Attestation message hash: {:?}", message);
-
-        // 2. Sign the message
-        let signature = self.sign_message(message).await?;
+        // 1. Sign the proposal digest verified by the contract
+        let signature = sign_block_proposal(&self.signer, block_number, block_hash)?;
         debug!("Signature created: {} bytes", signature.len());
 
-        // 3. Submit to consensus contract
+        // 2. Submit to consensus contract
         let tx_hash = self
             .consensus_client
             .propose_block(block_number, block_hash, signature)
@@ -73,85 +377,58 @@ impl BlockAttester {
         Ok(tx_hash)
     }
 
-    /// Create attestation message hash
-    ///
-    /// Message format: keccak256(abi.encodePacked(blockNumber, blockHash))
-    fn create_attestation_message(block_number: u64, block_hash: B256) -> B256 {
-        let mut message = Vec::new();
-        message.extend_from_slice(&block_number.to_be_bytes());
-        message.extend_from_slice(block_hash.as_slice());
-
-        keccak256(&message)
-    }
-
-    /// Sign a message using eth_sign format
-    ///
-    /// This creates an Ethereum signed message hash and signs it.
-    async fn sign_message(&self, message_hash: B256) -> Result<Bytes> {
-        // Ethereum signed message: "\x19Ethereum Signed Message:\n32" + message_hash
-        let eth_message = Self::eth_signed_message_hash(message_hash);
-
-        // Sign the message
-        let signature = self.signer.sign_hash(&eth_message).await?;
-
-        // Convert to bytes (r + s + v format)
-        let mut sig_bytes = Vec::new();
-        sig_bytes.extend_from_slice(signature.r().as_le_slice());
-        sig_bytes.extend_from_slice(signature.s().as_le_slice());
-        sig_bytes.push(signature.v().y_parity_byte());
-
-        Ok(Bytes::from(sig_bytes))
+    /// Get the signer's address
+    pub fn address(&self) -> alloy::primitives::Address {
+        self.signer.address()
     }
+}
 
-    /// Create Ethereum signed message hash
-    ///
-    /// Format: keccak256("\x19Ethereum Signed Message:\n32" + message_hash)
-    fn eth_signed_message_hash(message_hash: B256) -> B256 {
-        let mut eth_message = Vec::new();
-        eth_message.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
-        eth_message.extend_from_slice(message_hash.as_slice());
-
-        keccak256(&eth_message)
+impl AttestationStatusSource for BlockAttester {
+    fn pending_attestations(&self) -> usize {
+        Self::pending_attestations(self)
     }
 
-    /// Get the signer's address
-    pub fn address(&self) -> alloy::primitives::Address {
-        self.signer.address()
+    fn last_attested_block(&self) -> Option<u64> {
+        Self::last_attested_block(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy::primitives::address;
+    use crate::consensus_client::block_proposal_digest;
+    use alloy::primitives::{address, keccak256};
 
     #[test]
     fn test_attestation_message() {
         let block_number = 12345u64;
         let block_hash = B256::from([1u8; 32]);
 
-        let message = BlockAttester::create_attestation_message(block_number, block_hash);
+        let message = block_proposal_digest(block_number, block_hash);
 
         // Verify message is deterministic
-        let message2 = BlockAttester::create_attestation_message(block_number, block_hash);
+        let message2 = block_proposal_digest(block_number, block_hash);
         assert_eq!(message, message2);
 
         // Verify different inputs produce different hashes
-        let message3 = BlockAttester::create_attestation_message(block_number + 1, block_hash);
+        let message3 = block_proposal_digest(block_number + 1, block_hash);
         assert_ne!(message, message3);
     }
 
     #[test]
     fn test_eth_signed_message_hash() {
-        let message_hash = B256::from([1u8; 32]);
-        let eth_hash = BlockAttester::eth_signed_message_hash(message_hash);
+        let block_hash = B256::from([1u8; 32]);
 
-        // Verify deterministic
-        let eth_hash2 = BlockAttester::eth_signed_message_hash(message_hash);
-        assert_eq!(eth_hash, eth_hash2);
+        // keccak256(abi.encodePacked(uint256 blockNumber, bytes32 blockHash))
+        let mut packed = [0u8; 64];
+        packed[31] = 7;
+        packed[32..].copy_from_slice(block_hash.as_slice());
+        let message_hash = keccak256(packed);
 
-        // Verify format
-        assert_ne!(eth_hash, message_hash); // Should be different after wrapping
+        // Wrapped as "\x19Ethereum Signed Message:\n32" + message_hash
+        let mut eth_message = b"\x19Ethereum Signed Message:\n32".to_vec();
+        eth_message.extend_from_slice(message_hash.as_slice());
+        assert_eq!(block_proposal_digest(7, block_hash), keccak256(&eth_message));
     }
 
     #[tokio::test]
@@ -166,4 +443,128 @@ mod tests {
         // Just verify the address is accessible
         assert_ne!(expected_address, address!("0000000000000000000000000000000000000000"));
     }
+
+    /// Backend failing its first submissions, recording the successful ones
+    #[derive(Default)]
+    struct FlakyBackend {
+        failures_left: Mutex<usize>,
+        finalized: Mutex<Vec<B256>>,
+        submitted: Mutex<Vec<(u64, B256)>>,
+    }
+
+    impl FlakyBackend {
+        fn failing(failures: usize) -> Arc<Self> {
+            Arc::new(Self { failures_left: Mutex::new(failures), ..Default::default() })
+        }
+    }
+
+    #[async_trait]
+    impl ConsensusBackend for FlakyBackend {
+        async fn propose_block(&self, block_number: u64, block_hash: B256, _signature: Bytes) -> Result<B256> {
+            let mut failures_left = self.failures_left.lock().unwrap();
+            if *failures_left > 0 {
+                *failures_left -= 1;
+                return Err(eyre::eyre!("connection refused"));
+            }
+            self.submitted.lock().unwrap().push((block_number, block_hash));
+            Ok(B256::repeat_byte(0xee))
+        }
+
+        async fn is_block_finalized(&self, block_hash: B256) -> Result<bool> {
+            Ok(self.finalized.lock().unwrap().contains(&block_hash))
+        }
+    }
+
+    const HOUR_MS: u64 = 3_600_000;
+
+    #[tokio::test]
+    async fn test_failed_attestation_eventually_submitted() {
+        let backend = FlakyBackend::failing(2);
+        let attester = BlockAttester::new(PrivateKeySigner::random(), backend.clone());
+        let block_hash = B256::repeat_byte(0x01);
+
+        assert!(attester.attest_block(7, block_hash).await.is_err());
+        assert_eq!(attester.pending_attestations(), 1);
+        assert_eq!(attester.last_attested_block(), None);
+
+        // Not retried before its backoff elapses
+        let now = now_ms();
+        attester.retry_due(now).await;
+        assert_eq!(*backend.failures_left.lock().unwrap(), 1);
+
+        // The first retry fails too and is pushed back, the second goes through
+        attester.retry_due(now + HOUR_MS).await;
+        assert_eq!(attester.pending_attestations(), 1);
+        attester.retry_due(now + HOUR_MS).await;
+        assert_eq!(attester.pending_attestations(), 1, "retried before its backoff elapsed");
+        attester.retry_due(now + 2 * HOUR_MS).await;
+        assert_eq!(attester.pending_attestations(), 0);
+        assert_eq!(*backend.submitted.lock().unwrap(), [(7, block_hash)]);
+        assert_eq!(attester.last_attested_block(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_finalized_block_attestation_dropped() {
+        let backend = FlakyBackend::failing(1);
+        let attester = BlockAttester::new(PrivateKeySigner::random(), backend.clone());
+        let block_hash = B256::repeat_byte(0x01);
+
+        assert!(attester.attest_block(7, block_hash).await.is_err());
+        backend.finalized.lock().unwrap().push(block_hash);
+
+        attester.retry_due(now_ms() + HOUR_MS).await;
+        assert_eq!(attester.pending_attestations(), 0);
+        assert!(backend.submitted.lock().unwrap().is_empty());
+    }
+
+    /// Backend whose contract rejects every attestation
+    struct RejectingBackend;
+
+    #[async_trait]
+    impl ConsensusBackend for RejectingBackend {
+        async fn propose_block(&self, _block_number: u64, _block_hash: B256, _signature: Bytes) -> Result<B256> {
+            Err(ConsensusClientError::ContractRevert { selector: None, data: Bytes::new() }.into())
+        }
+
+        async fn is_block_finalized(&self, _block_hash: B256) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_attestation_not_retried() {
+        let attester = BlockAttester::new(PrivateKeySigner::random(), Arc::new(RejectingBackend));
+
+        assert!(attester.attest_block(7, B256::repeat_byte(0x01)).await.is_err());
+        assert_eq!(attester.pending_attestations(), 0);
+    }
+
+    #[test]
+    fn test_retry_queue_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ATTESTATION_QUEUE_FILE);
+        let block_hash = B256::repeat_byte(0x01);
+
+        let mut queue = AttestationRetryQueue::open(&path).unwrap();
+        queue.push(7, block_hash, 1_000).unwrap();
+        queue.record_failure(7, block_hash, 2_000).unwrap();
+        queue.push(8, B256::repeat_byte(0x02), 3_000).unwrap();
+
+        // Survives a restart
+        let mut queue = AttestationRetryQueue::open(&path).unwrap();
+        assert_eq!(queue.len(), 2);
+        let first = &queue.due(u64::MAX)[0];
+        assert_eq!((first.block_number, first.first_attempt_ms, first.attempts), (7, 1_000, 2));
+        assert_eq!(first.next_attempt_ms, 2_000 + retry_delay(2).as_millis() as u64);
+
+        queue.remove(7, block_hash).unwrap();
+        assert_eq!(AttestationRetryQueue::open(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        let delays: Vec<_> = (1..=8).map(|attempts| retry_delay(attempts).as_secs()).collect();
+        assert_eq!(delays, [2, 4, 8, 16, 32, 64, 120, 120]);
+        assert_eq!(retry_delay(u32::MAX), ATTESTATION_RETRY_MAX_DELAY);
+    }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 /// Default maximum bytes for txpool transactions (1.85 MiB)
 pub const DEFAULT_MAX_TXPOOL_BYTES: u64 = 1_939_865; // 1.85 MiB = 1,939,865 bytes
@@ -8,13 +11,29 @@ pub const DEFAULT_MAX_TXPOOL_BYTES: u64 = 1_939_865; // 1.85 MiB = 1,939,865 byt
 /// This caps how much total gas worth of transactions the txpool RPC returns.
 pub const DEFAULT_MAX_TXPOOL_GAS: u64 = 30_000_000; // 30M gas
 
+/// What the payload builder does with transactions over the txpool caps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxpoolOverflowPolicy {
+    /// Drop transactions from the tail of the list until it fits
+    #[default]
+    Truncate,
+    /// Reject the whole payload
+    Reject,
+}
+
 /// Configuration for Evolve-specific functionality
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EvolveConfig {
-    /// Maximum bytes of transactions to return from the txpool
+    /// Maximum bytes of transactions to return from the txpool, and to
+    /// accept in a payload; zero disables the cap
     pub max_txpool_bytes: u64,
-    /// Maximum gas of transactions to return from the txpool
+    /// Maximum gas of transactions to return from the txpool, and to accept
+    /// in a payload; zero disables the cap
     pub max_txpool_gas: u64,
+    /// What to do with payloads whose transactions exceed the caps
+    pub overflow_policy: TxpoolOverflowPolicy,
 }
 
 impl Default for EvolveConfig {
@@ -22,6 +41,7 @@ impl Default for EvolveConfig {
         Self {
             max_txpool_bytes: DEFAULT_MAX_TXPOOL_BYTES,
             max_txpool_gas: DEFAULT_MAX_TXPOOL_GAS,
+            overflow_policy: TxpoolOverflowPolicy::Truncate,
         }
     }
 }
@@ -32,6 +52,7 @@ impl EvolveConfig {
         Self {
             max_txpool_bytes,
             max_txpool_gas: DEFAULT_MAX_TXPOOL_GAS,
+            overflow_policy: TxpoolOverflowPolicy::Truncate,
         }
     }
 
@@ -40,6 +61,87 @@ impl EvolveConfig {
         Self {
             max_txpool_bytes,
             max_txpool_gas,
+            overflow_policy: TxpoolOverflowPolicy::Truncate,
+        }
+    }
+
+    /// Sets what to do with payloads whose transactions exceed the caps
+    pub const fn with_overflow_policy(mut self, policy: TxpoolOverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+}
+
+/// Default lowest gas limit accepted for a payload
+pub const DEFAULT_MIN_GAS_LIMIT: u64 = 5_000;
+
+/// Default highest gas limit accepted for a payload
+pub const DEFAULT_MAX_GAS_LIMIT: u64 = DEFAULT_MAX_TXPOOL_GAS;
+
+/// Default number of seconds a payload timestamp may run ahead of local time
+pub const DEFAULT_MAX_TIMESTAMP_DRIFT_SECS: u64 = 15;
+
+/// Bounds payload attributes are validated against before a block is built,
+/// see [`EvolvePayloadAttributes::validate_against`](crate::EvolvePayloadAttributes::validate_against)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PayloadValidationConfig {
+    /// Lowest gas limit accepted
+    pub min_gas_limit: u64,
+    /// Highest gas limit accepted
+    pub max_gas_limit: u64,
+    /// Seconds the timestamp may run ahead of local time
+    pub max_timestamp_drift_secs: u64,
+    /// Whether a non-zero fee recipient is required, as in consensus mode
+    pub require_fee_recipient: bool,
+}
+
+impl Default for PayloadValidationConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PayloadValidationConfig {
+    /// Creates a new `PayloadValidationConfig` with the default bounds
+    pub const fn new() -> Self {
+        Self {
+            min_gas_limit: DEFAULT_MIN_GAS_LIMIT,
+            max_gas_limit: DEFAULT_MAX_GAS_LIMIT,
+            max_timestamp_drift_secs: DEFAULT_MAX_TIMESTAMP_DRIFT_SECS,
+            require_fee_recipient: false,
+        }
+    }
+}
+
+/// How long the payload builder waits on the consensus after building a block
+/// before considering the build complete
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum FinalizationPolicy {
+    /// Return the block as soon as it's built
+    #[default]
+    FireAndForget,
+    /// Wait until an attestation of the block is recorded by the consensus
+    /// contract, for at most [`DEFAULT_INCLUSION_TIMEOUT`]
+    WaitForInclusion,
+    /// Wait until the block reaches the finalization threshold
+    WaitForFinalization {
+        /// Longest wait before the build fails
+        timeout: Duration,
+    },
+}
+
+/// Longest wait for an attestation under [`FinalizationPolicy::WaitForInclusion`]
+pub const DEFAULT_INCLUSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl FinalizationPolicy {
+    /// Longest wait on the consensus, `None` if the policy doesn't wait
+    pub const fn timeout(&self) -> Option<Duration> {
+        match self {
+            Self::FireAndForget => None,
+            Self::WaitForInclusion => Some(DEFAULT_INCLUSION_TIMEOUT),
+            Self::WaitForFinalization { timeout } => Some(*timeout),
         }
     }
 }
@@ -1,5 +1,11 @@
 //! Evolve custom consensus implementation that allows same timestamps across blocks.
+//!
+//! Optionally, imported blocks are also checked against the producer schedule
+//! of the AndeConsensus contract: a block whose beneficiary isn't the
+//! producer designated for its height is rejected, see
+//! [`EvolveConsensusBuilder::with_producer_schedule`].
 
+use alloy_primitives::Address;
 use reth_chainspec::ChainSpec;
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
 use reth_consensus_common::validation::{
@@ -12,17 +18,107 @@ use reth_ethereum_primitives::{Block, BlockBody, EthPrimitives, Receipt};
 use reth_execution_types::BlockExecutionResult;
 use reth_node_api::{FullNodeTypes, NodeTypes};
 use reth_primitives::{RecoveredBlock, SealedBlock, SealedHeader};
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
+use tracing::debug;
+
+/// Producers designated for each block, e.g. the schedule cached by the
+/// consensus client or attestations embedded in the headers
+pub trait ProducerScheduleSource: fmt::Debug + Send + Sync {
+    /// Producer designated for block `block_number`, `None` if unknown
+    fn designated_producer(&self, block_number: u64) -> Option<Address>;
+}
+
+/// Error returned when a block wasn't produced by its designated producer
+#[derive(Debug, thiserror::Error)]
+#[error("Block {block_number} produced by {producer}, but {designated_producer} is its designated producer")]
+pub struct UnexpectedProducer {
+    /// Block that was validated
+    pub block_number: u64,
+    /// Beneficiary of the block
+    pub producer: Address,
+    /// Producer designated for the block
+    pub designated_producer: Address,
+}
+
+/// Error returned when the producer designated for a block is unknown and the
+/// grace mode is off
+#[derive(Debug, thiserror::Error)]
+#[error("Designated producer of block {block_number} is unknown")]
+pub struct UnknownProducer {
+    /// Block that was validated
+    pub block_number: u64,
+}
+
+/// Verification of block producers against a schedule
+#[derive(Debug, Clone)]
+struct ProducerVerification {
+    /// Schedule the producers are checked against
+    schedule: Arc<dyn ProducerScheduleSource>,
+    /// Whether blocks whose designated producer is unknown are accepted
+    grace: bool,
+}
+
+impl ProducerVerification {
+    /// Check that `header` was produced by its designated producer
+    fn verify(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        let (block_number, producer) = (header.number, header.beneficiary);
+        match self.schedule.designated_producer(block_number) {
+            Some(designated_producer) if designated_producer == producer => Ok(()),
+            Some(designated_producer) => Err(ConsensusError::Custom(Arc::new(UnexpectedProducer {
+                block_number,
+                producer,
+                designated_producer,
+            }))),
+            // Historical schedules aren't available while syncing from genesis
+            None if self.grace => {
+                debug!(block_number, ?producer, "Designated producer unknown, accepting block");
+                Ok(())
+            }
+            None => Err(ConsensusError::Custom(Arc::new(UnknownProducer { block_number }))),
+        }
+    }
+}
 
 /// Builder for `EvolveConsensus`
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EvolveConsensusBuilder;
+pub struct EvolveConsensusBuilder {
+    /// Schedule the block producers are verified against, if any
+    producer_schedule: Option<Arc<dyn ProducerScheduleSource>>,
+    /// Whether blocks whose designated producer is unknown are accepted
+    sync_grace: bool,
+}
 
 impl EvolveConsensusBuilder {
     /// Create a new `EvolveConsensusBuilder`
     pub const fn new() -> Self {
-        Self
+        Self { producer_schedule: None, sync_grace: false }
+    }
+
+    /// Reject imported blocks whose beneficiary isn't the producer `schedule`
+    /// designates for their height
+    ///
+    /// Blocks whose designated producer the schedule doesn't know are
+    /// rejected too, unless the grace mode is on, see [`Self::with_sync_grace`].
+    pub fn with_producer_schedule(mut self, schedule: Arc<dyn ProducerScheduleSource>) -> Self {
+        self.producer_schedule = Some(schedule);
+        self
+    }
+
+    /// Accept the blocks whose designated producer is unknown, e.g. while
+    /// syncing from genesis; blocks of a known producer are still checked
+    pub const fn with_sync_grace(mut self, grace: bool) -> Self {
+        self.sync_grace = grace;
+        self
+    }
+
+    /// Build the consensus implementation with the configured verifications
+    pub fn build_with(&self, chain_spec: Arc<ChainSpec>) -> Arc<EvolveConsensus> {
+        let producer_verification = self
+            .producer_schedule
+            .clone()
+            .map(|schedule| ProducerVerification { schedule, grace: self.sync_grace });
+        Arc::new(EvolveConsensus { producer_verification, ..EvolveConsensus::new(chain_spec) })
     }
 
     /// Build the consensus implementation
@@ -39,7 +135,7 @@ where
     type Consensus = Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>>;
 
     async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(Arc::new(EvolveConsensus::new(ctx.chain_spec())) as Self::Consensus)
+        Ok(self.build_with(ctx.chain_spec()) as Self::Consensus)
     }
 }
 
@@ -52,13 +148,15 @@ where
 pub struct EvolveConsensus {
     /// Inner Ethereum beacon consensus for standard validation
     inner: EthBeaconConsensus<ChainSpec>,
+    /// Verification of the block producers, off if `None`
+    producer_verification: Option<ProducerVerification>,
 }
 
 impl EvolveConsensus {
     /// Create a new Evolve consensus instance
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
         let inner = EthBeaconConsensus::new(chain_spec);
-        Self { inner }
+        Self { inner, producer_verification: None }
     }
 }
 
@@ -75,6 +173,10 @@ impl HeaderValidator for EvolveConsensus {
     ) -> Result<(), ConsensusError> {
         validate_against_parent_hash_number(header.header(), parent)?;
 
+        if let Some(verification) = &self.producer_verification {
+            verification.verify(header)?;
+        }
+
         let h = header.header();
         let ph = parent.header();
         if h.timestamp < ph.timestamp {
@@ -2,22 +2,285 @@
 //!
 //! Provides integration between ev-reth and the AndeConsensus smart contract
 //! for block producer selection, attestation, and validator synchronization.
+//!
+//! A client connected over WebSocket ([`AndeConsensusClient::new_ws`]) follows
+//! the contract's `ValidatorSetUpdated`, `BlockProposed` and `BlockFinalized`
+//! events as they are emitted. Polling the contract every 30 seconds is only
+//! the fallback while the subscription is down, and the only option over HTTP.
+//!
+//! The producers of the upcoming blocks are fetched ahead of time into a
+//! [`ProducerScheduleCache`], so block production doesn't wait on, nor
+//! requires, the RPC endpoint at the moment a block is built.
+//!
+//! Other subsystems react to the consensus through
+//! [`AndeConsensusClient::subscribe`] rather than opening their own contract
+//! subscription, see [`ConsensusEvent`].
 
 use alloy::{
     network::EthereumWallet,
-    primitives::{Address, Bytes, FixedBytes, B256, U256},
-    providers::{Provider, ProviderBuilder, RootProvider},
-    signers::local::PrivateKeySigner,
-    transports::http::{Client, Http},
+    primitives::{eip191_hash_message, keccak256, Address, Bytes, FixedBytes, B256, U256},
+    providers::{DynProvider, PendingTransactionError, Provider, ProviderBuilder, WatchTxError, WsConnect},
+    rpc::{
+        client::RpcClient,
+        types::{Filter, Log},
+    },
+    signers::{local::PrivateKeySigner, SignerSync},
+    sol_types::{SolEvent, SolEventInterface, SolInterface},
+    transports::{http::reqwest::Url, TransportError, TransportErrorKind},
+};
+use crate::{
+    consensus::ProducerScheduleSource,
+    evm_config::{validator_set::MAX_FINALIZED_BLOCKS, SharedValidatorSnapshot},
+    failover::{FailoverStatus, FailoverTransport},
+    rpc::{
+        health::{ConnectionHealth, ConsensusHealthSource},
+        validator::{RpcValidatorInfo, ValidatorStatusSource},
+    },
 };
 use ande_consensus_bindings::{AndeConsensus, ContractAddresses};
-use eyre::Result;
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, PoisonError,
+};
+use std::time::{Duration, Instant};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    RwLock,
+};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
+/// Errors of [`AndeConsensusClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum ConsensusClientError {
+    /// The RPC URL could not be parsed
+    #[error("Invalid RPC URL {url}: {reason}")]
+    InvalidRpcUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// No RPC endpoint was given
+    #[error("At least one AndeConsensus RPC endpoint is required")]
+    NoRpcEndpoint,
+    /// The node could not be reached, or failed to answer
+    #[error("AndeConsensus RPC request failed: {0}")]
+    Transport(TransportError),
+    /// The contract reverted the call
+    #[error("AndeConsensus call reverted with {data}")]
+    ContractRevert {
+        /// Selector of the custom error, if the revert data holds one
+        selector: Option<FixedBytes<4>>,
+        /// Revert data
+        data: Bytes,
+    },
+    /// The client has no wallet to send transactions with
+    #[error("Wallet not configured, cannot send AndeConsensus transactions")]
+    NotConfigured,
+    /// The contract's answer could not be decoded
+    #[error("AndeConsensus response could not be decoded: {0}")]
+    Decode(String),
+    /// The transaction wasn't confirmed in time
+    #[error("AndeConsensus transaction not confirmed in time")]
+    Timeout,
+    /// The block proposal could not be signed
+    #[error("Failed to sign block proposal: {0}")]
+    Signing(#[from] alloy::signers::Error),
+}
+
+impl ConsensusClientError {
+    /// Classify a revert with `data`
+    fn revert(data: Bytes) -> Self {
+        let selector = data.get(..4).map(FixedBytes::from_slice);
+        Self::ContractRevert { selector, data }
+    }
+
+    /// Custom error of the AndeConsensus contract the call reverted with, if known
+    pub fn revert_error(&self) -> Option<AndeConsensus::AndeConsensusErrors> {
+        match self {
+            Self::ContractRevert { data, .. } => AndeConsensus::AndeConsensusErrors::abi_decode(data).ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the contract refused the call because it's another producer's turn
+    pub fn is_not_our_turn(&self) -> bool {
+        matches!(self.revert_error(), Some(AndeConsensus::AndeConsensusErrors::NotOurTurn(_)))
+    }
+
+    /// Whether the same call may succeed if retried later
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Transport(_) | Self::Timeout)
+    }
+}
+
+impl From<TransportError> for ConsensusClientError {
+    fn from(err: TransportError) -> Self {
+        match err.as_error_resp().and_then(|payload| payload.as_revert_data()) {
+            Some(data) => Self::revert(data),
+            None => Self::Transport(err),
+        }
+    }
+}
+
+impl From<alloy::contract::Error> for ConsensusClientError {
+    fn from(err: alloy::contract::Error) -> Self {
+        match err {
+            alloy::contract::Error::TransportError(err) => err.into(),
+            alloy::contract::Error::PendingTransactionError(err) => err.into(),
+            alloy::contract::Error::AbiError(err) | alloy::contract::Error::ZeroData(_, err) => {
+                Self::Decode(err.to_string())
+            }
+            err => Self::Decode(err.to_string()),
+        }
+    }
+}
+
+impl From<PendingTransactionError> for ConsensusClientError {
+    fn from(err: PendingTransactionError) -> Self {
+        match err {
+            PendingTransactionError::TransportError(err) => err.into(),
+            PendingTransactionError::TxWatcher(WatchTxError::Timeout) => Self::Timeout,
+            err => Self::Transport(TransportErrorKind::custom(err)),
+        }
+    }
+}
+
+type Result<T, E = ConsensusClientError> = std::result::Result<T, E>;
+
+/// Parse the HTTP RPC URL `url`
+fn parse_rpc_url(url: &str) -> Result<Url> {
+    url.parse::<Url>().map_err(|err| ConsensusClientError::InvalidRpcUrl {
+        url: url.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+/// Digest of a block proposal, as verified by `AndeConsensus.proposeBlock`
+///
+/// The contract recovers the producer with
+/// `ECDSA.recover(MessageHashUtils.toEthSignedMessageHash(keccak256(abi.encodePacked(blockNumber, blockHash))), signature)`,
+/// `blockNumber` being a `uint256`: the digest is the EIP-191 hash of the
+/// keccak of the 64-byte big-endian block number and block hash.
+pub fn block_proposal_digest(block_number: u64, block_hash: B256) -> B256 {
+    let message = keccak256([U256::from(block_number).to_be_bytes::<32>(), block_hash.0].concat());
+    eip191_hash_message(message)
+}
+
+/// Sign the proposal of a block with `signer`
+///
+/// The signature is the 65 bytes `r || s || v`, `v` being 27 or 28, as
+/// expected by OpenZeppelin's `ECDSA.recover`.
+pub fn sign_block_proposal(signer: &PrivateKeySigner, block_number: u64, block_hash: B256) -> Result<Bytes> {
+    let signature = signer.sign_hash_sync(&block_proposal_digest(block_number, block_hash))?;
+    Ok(Bytes::copy_from_slice(&signature.as_bytes()))
+}
+
+/// Interval of the validator set poll
+pub const VALIDATOR_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before the first attempt to re-subscribe to the consensus events
+pub const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Interval at which the producer schedule is fetched ahead
+const SCHEDULE_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Events buffered per subscriber before the slowest ones start lagging
+pub const CONSENSUS_EVENT_CAPACITY: usize = 256;
+
+/// Change of the consensus seen by the client, see [`AndeConsensusClient::subscribe`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsensusEvent {
+    /// The active validator set changed
+    ValidatorSetUpdated {
+        /// New active validators
+        validators: Vec<Address>,
+    },
+    /// A block proposal was recorded by the contract
+    BlockProposed {
+        /// Block number
+        number: u64,
+        /// Block hash
+        hash: B256,
+        /// Producer of the block
+        producer: Address,
+    },
+    /// A block reached the finalization threshold
+    BlockFinalized {
+        /// Block hash
+        hash: B256,
+        /// Attestation power the block was finalized with
+        power: U256,
+    },
+    /// A new epoch started
+    EpochAdvanced {
+        /// New epoch
+        epoch: u64,
+    },
+}
+
+/// Broadcast of the [`ConsensusEvent`]s to the subscribed subsystems
+///
+/// Delivery is best-effort. Events are only sent to the receivers subscribed
+/// at the time, and each receiver buffers at most the channel capacity: one
+/// falling further behind gets [`RecvError::Lagged`] with the number of events
+/// it missed, and resumes with the oldest event still buffered. Receivers
+/// should then read the current state from the client, e.g.
+/// [`AndeConsensusClient::get_cached_validators`], instead of relying on the
+/// events they missed.
+#[derive(Debug, Clone)]
+pub struct ConsensusEventStream {
+    sender: broadcast::Sender<ConsensusEvent>,
+}
+
+impl ConsensusEventStream {
+    /// Create a stream buffering `capacity` events per receiver
+    pub fn new(capacity: usize) -> Self {
+        Self { sender: broadcast::channel(capacity).0 }
+    }
+
+    /// Receive the events sent from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<ConsensusEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Send `event` to the current receivers, if any
+    pub fn emit(&self, event: ConsensusEvent) {
+        // Fails only when nobody is subscribed
+        let _ = self.sender.send(event);
+    }
+
+    /// Number of current receivers
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for ConsensusEventStream {
+    fn default() -> Self {
+        Self::new(CONSENSUS_EVENT_CAPACITY)
+    }
+}
+
+/// How far ahead, and how long, producers are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProducerScheduleConfig {
+    /// Number of upcoming blocks whose producer is fetched ahead
+    pub lookahead: u64,
+    /// Age past which a cached producer is no longer trusted
+    pub max_age: Duration,
+}
+
+impl Default for ProducerScheduleConfig {
+    fn default() -> Self {
+        Self { lookahead: 32, max_age: Duration::from_secs(120) }
+    }
+}
+
 /// Ande Consensus Contract Client
 /// 
 /// Handles all interactions with the AndeConsensus smart contract:
@@ -29,63 +292,139 @@ use tracing::{debug, error, info, warn};
 #[derive(Clone)]
 pub struct AndeConsensusClient {
     /// Consensus contract instance
-    consensus: AndeConsensus::AndeConsensusInstance<
-        Http<Client>,
-        Arc<RootProvider<Http<Client>>>,
-    >,
+    consensus: AndeConsensus::AndeConsensusInstance<DynProvider>,
     /// Provider for RPC calls
-    provider: Arc<RootProvider<Http<Client>>>,
+    provider: DynProvider,
+    /// Failover between the HTTP endpoints, if connected over HTTP
+    failover: Option<FailoverTransport>,
+    /// Whether the provider supports subscriptions
+    pubsub: bool,
     /// Wallet for signing transactions
     wallet: Option<EthereumWallet>,
+    /// Key of the node, signing its block proposals
+    signer: Option<PrivateKeySigner>,
     /// Cached active validators
     validators: Arc<RwLock<Vec<Address>>>,
+    /// Blocks seen finalized through `BlockFinalized` events
+    finalized: Arc<RwLock<FinalizationCache>>,
+    /// Producers of the upcoming blocks
+    producer_schedule: Arc<RwLock<ProducerScheduleCache>>,
+    /// How far ahead, and how long, producers are cached
+    schedule_config: ProducerScheduleConfig,
     /// Last synced block number for event filtering
     last_synced_block: Arc<RwLock<u64>>,
+    /// Snapshot read by the validator-set precompile, if any
+    validator_snapshot: Option<SharedValidatorSnapshot>,
+    /// Consensus changes broadcast to the other subsystems
+    events: ConsensusEventStream,
+    /// Calls made to the node, as reported by `ande_health`
+    connection: Arc<ConnectionHealth>,
+    /// When the validator set was last synced from the contract
+    last_validator_sync: Arc<Mutex<Option<Instant>>>,
+    /// Whether the validator set follows the contract's events
+    following_events: Arc<AtomicBool>,
 }
 
 impl AndeConsensusClient {
     /// Create a new consensus client
     ///
+    /// Requests go to the first endpoint of `rpc_urls` that is up, see
+    /// [`FailoverTransport`].
+    ///
     /// # Arguments
-    /// * `rpc_url` - RPC endpoint URL
+    /// * `rpc_urls` - RPC endpoint URLs, by priority
     /// * `addresses` - Contract addresses
     /// * `signer` - Optional signer for transactions
     pub async fn new(
-        rpc_url: &str,
+        rpc_urls: &[&str],
         addresses: ContractAddresses,
         signer: Option<PrivateKeySigner>,
     ) -> Result<Self> {
         info!("Initializing AndeConsensusClient");
-        info!("  RPC URL: {}", rpc_url);
+        info!("  RPC URLs: {:?}", rpc_urls);
         info!("  Consensus contract: {:?}", addresses.consensus);
         
         // Build provider
-        let provider = if let Some(signer) = signer {
-            let wallet = EthereumWallet::from(signer.clone());
+        let (primary, fallbacks) = rpc_urls.split_first().ok_or(ConsensusClientError::NoRpcEndpoint)?;
+        let fallbacks = fallbacks.iter().map(|url| parse_rpc_url(url)).collect::<Result<Vec<_>>>()?;
+        let transport = FailoverTransport::new(parse_rpc_url(primary)?, fallbacks);
+        let rpc_client = RpcClient::new(transport.clone(), false);
+        let provider = if let Some(signer) = signer.clone() {
             ProviderBuilder::new()
-                .with_recommended_fillers()
-                .wallet(wallet.clone())
-                .on_http(rpc_url.parse()?)
+                .wallet(EthereumWallet::from(signer))
+                .connect_client(rpc_client)
+                .erased()
         } else {
             ProviderBuilder::new()
-                .on_http(rpc_url.parse()?)
+                .connect_client(rpc_client)
+                .erased()
         };
         
-        let provider = Arc::new(provider);
-        
+        Self::from_provider(provider, Some(transport), addresses, signer).await
+    }
+
+    /// Create a consensus client connected over WebSocket
+    ///
+    /// The client can follow the contract's events as they are emitted, see
+    /// [`Self::start_validator_sync_task`].
+    ///
+    /// # Arguments
+    /// * `ws_url` - WebSocket RPC endpoint URL
+    /// * `addresses` - Contract addresses
+    /// * `signer` - Optional signer for transactions
+    pub async fn new_ws(
+        ws_url: &str,
+        addresses: ContractAddresses,
+        signer: Option<PrivateKeySigner>,
+    ) -> Result<Self> {
+        info!("Initializing AndeConsensusClient");
+        info!("  WebSocket URL: {}", ws_url);
+        info!("  Consensus contract: {:?}", addresses.consensus);
+
+        let ws = WsConnect::new(ws_url);
+        let provider = if let Some(signer) = signer.clone() {
+            ProviderBuilder::new()
+                .wallet(EthereumWallet::from(signer))
+                .connect_ws(ws)
+                .await?
+                .erased()
+        } else {
+            ProviderBuilder::new().connect_ws(ws).await?.erased()
+        };
+
+        Self::from_provider(provider, None, addresses, signer).await
+    }
+
+    /// Create a client for the contracts at `addresses` over `provider`, a
+    /// pubsub one unless connected through `failover`
+    async fn from_provider(
+        provider: DynProvider,
+        failover: Option<FailoverTransport>,
+        addresses: ContractAddresses,
+        signer: Option<PrivateKeySigner>,
+    ) -> Result<Self> {
         // Create consensus contract instance
         let consensus = AndeConsensus::new(addresses.consensus, provider.clone());
         
-        // Initialize empty validator list
-        let validators = Arc::new(RwLock::new(Vec::new()));
-        let last_synced_block = Arc::new(RwLock::new(0));
-        
         let client = Self {
             consensus,
             provider,
-            wallet: signer.map(EthereumWallet::from),
-            validators,
-            last_synced_block,
+            pubsub: failover.is_none(),
+            failover,
+            wallet: signer.clone().map(EthereumWallet::from),
+            signer,
+            validators: Arc::new(RwLock::new(Vec::new())),
+            finalized: Arc::new(RwLock::new(FinalizationCache::default())),
+            producer_schedule: Arc::new(RwLock::new(ProducerScheduleCache::new(
+                ProducerScheduleConfig::default().max_age,
+            ))),
+            schedule_config: ProducerScheduleConfig::default(),
+            last_synced_block: Arc::new(RwLock::new(0)),
+            validator_snapshot: None,
+            events: ConsensusEventStream::default(),
+            connection: Arc::new(ConnectionHealth::new()),
+            last_validator_sync: Arc::new(Mutex::new(None)),
+            following_events: Arc::new(AtomicBool::new(false)),
         };
         
         // Initial validator sync
@@ -95,26 +434,131 @@ impl AndeConsensusClient {
         Ok(client)
     }
 
+    /// HTTP endpoint currently serving the requests, and failovers so far
+    ///
+    /// `None` when connected over WebSocket.
+    pub fn endpoint_status(&self) -> Option<FailoverStatus> {
+        self.failover.as_ref().map(FailoverTransport::status)
+    }
+
+    /// Record whether the call that returned `result` reached the node
+    fn track<T>(&self, result: std::result::Result<T, impl Into<ConsensusClientError>>) -> Result<T> {
+        let result = result.map_err(Into::into);
+        self.connection.record(!matches!(&result, Err(err) if err.is_transient()));
+        result
+    }
+
+    /// Record that the validator set was just synced from the contract
+    fn record_validator_sync(&self) {
+        *self.last_validator_sync.lock().unwrap_or_else(PoisonError::into_inner) = Some(Instant::now());
+    }
+
+    /// Receive the consensus changes seen by the client from now on
+    ///
+    /// Events are emitted by the background tasks, see
+    /// [`Self::start_validator_sync_task`] and
+    /// [`Self::start_producer_schedule_task`]. Delivery is best-effort, see
+    /// [`ConsensusEventStream`].
+    pub fn subscribe(&self) -> broadcast::Receiver<ConsensusEvent> {
+        self.events.subscribe()
+    }
+
+    /// Keep `snapshot`, read by the validator-set precompile, up to date with
+    /// the validators and finalized blocks seen by this client
+    pub async fn with_validator_snapshot(mut self, snapshot: SharedValidatorSnapshot) -> Result<Self> {
+        self.validator_snapshot = Some(snapshot);
+        let validators = self.get_cached_validators().await;
+        self.refresh_validator_snapshot(&validators).await?;
+        Ok(self)
+    }
+
+    /// Fetch and cache producers `config.lookahead` blocks ahead, refusing
+    /// those cached longer than `config.max_age`
+    pub fn with_producer_schedule_config(mut self, config: ProducerScheduleConfig) -> Self {
+        self.schedule_config = config;
+        self.producer_schedule = Arc::new(RwLock::new(ProducerScheduleCache::new(config.max_age)));
+        self
+    }
+
     /// Get the designated block producer for a given block number
     ///
-    /// Uses the weighted round-robin selection based on voting power.
+    /// Uses the weighted round-robin selection based on voting power. Served
+    /// from the producer schedule when it holds the block, so it keeps
+    /// working while the RPC endpoint is briefly down.
     pub async fn get_block_producer(&self, block_number: u64) -> Result<Address> {
+        if let Some(producer) = self.producer_schedule.read().await.get(block_number, Instant::now()) {
+            debug!("Block {} producer (cached): {:?}", block_number, producer);
+            return Ok(producer);
+        }
+
+        let producer = self.fetch_block_producer(block_number).await?;
+        self.producer_schedule.write().await.insert(block_number, producer, Instant::now());
+        Ok(producer)
+    }
+
+    /// Query the designated block producer from the contract
+    async fn fetch_block_producer(&self, block_number: u64) -> Result<Address> {
         debug!("Querying block producer for block {}", block_number);
         
-        let producer = self
-            .consensus
-            .getBlockProducer(U256::from(block_number))
-            .call()
-            .await?
-            ._0;
+        let producer = self.track(self.consensus.getBlockProducer(U256::from(block_number)).call().await)?._0;
         
         debug!("Block {} producer: {:?}", block_number, producer);
         Ok(producer)
     }
 
+    /// Fetch the producers of the blocks following the chain head that the
+    /// schedule doesn't hold yet
+    ///
+    /// The schedule starts over when the epoch changes, as the producers of
+    /// the new epoch may differ.
+    pub async fn prefetch_producer_schedule(&self) -> Result<()> {
+        let epoch = self.get_current_epoch().await?;
+        let next_block = self.track(self.provider.get_block_number().await)? + 1;
+
+        let missing = {
+            let mut schedule = self.producer_schedule.write().await;
+            if schedule.observe_epoch(epoch) {
+                info!("Epoch {} started, refetching the producer schedule", epoch);
+                self.events.emit(ConsensusEvent::EpochAdvanced { epoch });
+            }
+            schedule.prune_below(next_block);
+            schedule.missing(next_block, self.schedule_config.lookahead, Instant::now())
+        };
+
+        for block_number in missing {
+            let producer = self.fetch_block_producer(block_number).await?;
+            self.producer_schedule.write().await.insert(block_number, producer, Instant::now());
+        }
+        Ok(())
+    }
+
+    /// Start background task fetching the producer schedule ahead
+    pub fn start_producer_schedule_task(self) -> JoinHandle<()> {
+        info!("Starting producer schedule task ({} blocks ahead)", self.schedule_config.lookahead);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SCHEDULE_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.prefetch_producer_schedule().await {
+                    warn!("Failed to prefetch the producer schedule: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Sign the proposal of a block with the node key and submit it
+    ///
+    /// Requires the client to be created with a signer.
+    pub async fn sign_and_propose_block(&self, block_number: u64, block_hash: B256) -> Result<B256> {
+        let signer = self.signer.as_ref().ok_or(ConsensusClientError::NotConfigured)?;
+        let signature = sign_block_proposal(signer, block_number, block_hash)?;
+        self.propose_block(block_number, block_hash, signature).await
+    }
+
     /// Propose a block to the consensus contract
     ///
-    /// This sends a transaction with the block hash and signature.
+    /// This sends a transaction with the block hash and signature, produced
+    /// by an external signer over [`block_proposal_digest`].
     /// Requires wallet to be configured.
     pub async fn propose_block(
         &self,
@@ -123,7 +567,7 @@ impl AndeConsensusClient {
         signature: Bytes,
     ) -> Result<B256> {
         if self.wallet.is_none() {
-            return Err(eyre::eyre!("Wallet not configured, cannot propose blocks"));
+            return Err(ConsensusClientError::NotConfigured);
         }
         
         info!(
@@ -131,17 +575,9 @@ impl AndeConsensusClient {
             block_number, block_hash
         );
         
-        let tx_hash = self
-            .consensus
-            .proposeBlock(
-                U256::from(block_number),
-                block_hash,
-                signature,
-            )
-            .send()
-            .await?
-            .watch()
-            .await?;
+        let pending =
+            self.track(self.consensus.proposeBlock(U256::from(block_number), block_hash, signature).send().await)?;
+        let tx_hash = self.track(pending.watch().await)?;
         
         info!(
             "Block {} proposed successfully, tx: {:?}",
@@ -155,7 +591,7 @@ impl AndeConsensusClient {
     pub async fn get_active_validators(&self) -> Result<Vec<Address>> {
         debug!("Fetching active validators");
         
-        let validators = self.consensus.getActiveValidators().call().await?._0;
+        let validators = self.track(self.consensus.getActiveValidators().call().await)?._0;
         
         debug!("Found {} active validators", validators.len());
         Ok(validators)
@@ -166,12 +602,35 @@ impl AndeConsensusClient {
         let validators = self.get_active_validators().await?;
         
         let mut cache = self.validators.write().await;
+        let changed = *cache != validators;
         *cache = validators.clone();
+        drop(cache);
+        self.refresh_validator_snapshot(&validators).await?;
+        if changed {
+            self.events.emit(ConsensusEvent::ValidatorSetUpdated { validators: validators.clone() });
+        }
         
+        self.record_validator_sync();
         info!("Synced {} validators to cache", validators.len());
         Ok(())
     }
 
+    /// Replace the active set of the validator snapshot with `validators` and
+    /// their voting power
+    async fn refresh_validator_snapshot(&self, validators: &[Address]) -> Result<()> {
+        let Some(snapshot) = &self.validator_snapshot else {
+            return Ok(());
+        };
+
+        let mut active = Vec::with_capacity(validators.len());
+        for validator in validators {
+            active.push((*validator, self.get_validator_info(*validator).await?.power));
+        }
+        // Published at once, so blocks never capture a partially updated set
+        snapshot.write().unwrap_or_else(std::sync::PoisonError::into_inner).set_validators(active);
+        Ok(())
+    }
+
     /// Get cached validators (fast, no RPC call)
     pub async fn get_cached_validators(&self) -> Vec<Address> {
         self.validators.read().await.clone()
@@ -181,7 +640,7 @@ impl AndeConsensusClient {
     pub async fn get_validator_info(&self, validator: Address) -> Result<ValidatorInfo> {
         debug!("Fetching info for validator {:?}", validator);
         
-        let info = self.consensus.getValidatorInfo(validator).call().await?;
+        let info = self.track(self.consensus.getValidatorInfo(validator).call().await)?;
         
         Ok(ValidatorInfo {
             validator: info.validator,
@@ -203,23 +662,19 @@ impl AndeConsensusClient {
 
     /// Get current epoch number
     pub async fn get_current_epoch(&self) -> Result<u64> {
-        let epoch = self.consensus.currentEpoch().call().await?._0;
+        let epoch = self.track(self.consensus.currentEpoch().call().await)?._0;
         Ok(epoch.to())
     }
 
     /// Check if address is validator
     pub async fn is_validator(&self, address: Address) -> Result<bool> {
-        let is_val = self.consensus.isValidator(address).call().await?._0;
+        let is_val = self.track(self.consensus.isValidator(address).call().await)?._0;
         Ok(is_val)
     }
 
     /// Get block proposal information
     pub async fn get_block_proposal(&self, block_number: u64) -> Result<Option<BlockProposal>> {
-        let proposal = self
-            .consensus
-            .getBlockProposal(U256::from(block_number))
-            .call()
-            .await?;
+        let proposal = self.track(self.consensus.getBlockProposal(U256::from(block_number)).call().await)?;
         
         // Check if proposal exists (verified = true)
         if !proposal.verified {
@@ -250,26 +705,55 @@ impl AndeConsensusClient {
         let validators = self.get_active_validators().await?;
         
         let mut cache = self.validators.write().await;
+        let changed = *cache != validators;
         *cache = validators.clone();
+        drop(cache);
+        self.refresh_validator_snapshot(&validators).await?;
+        if changed {
+            self.events.emit(ConsensusEvent::ValidatorSetUpdated { validators: validators.clone() });
+        }
         
         // Update last synced block to current
-        if let Ok(current_block) = self.provider.get_block_number().await {
+        if let Ok(current_block) = self.track(self.provider.get_block_number().await) {
             let mut last_synced = self.last_synced_block.write().await;
             *last_synced = current_block;
             debug!("Updated last synced block to {}", current_block);
         }
         
+        self.record_validator_sync();
         info!("Synced {} validators from events", validators.len());
         Ok(())
     }
 
-    /// Start background task to periodically sync validator set
+    /// Start background task keeping the validator set up to date
     ///
-    /// This spawns a tokio task that syncs validators every 30 seconds
+    /// Over WebSocket, the task follows the contract's events, and re-subscribes
+    /// with exponential backoff whenever the subscription drops, polling the
+    /// contract in the meantime. Over HTTP, it syncs validators every 30 seconds.
     pub fn start_validator_sync_task(self) -> JoinHandle<()> {
+        if self.pubsub {
+            info!("Starting background consensus event task");
+            return tokio::spawn(async move {
+                let mut backoff = ReconnectBackoff::new(INITIAL_RECONNECT_DELAY, VALIDATOR_POLL_INTERVAL);
+                loop {
+                    if let Err(e) = self.follow_events(&mut backoff).await {
+                        warn!("Consensus event subscription dropped: {}", e);
+                    }
+                    self.following_events.store(false, Ordering::Relaxed);
+                    // Poll until the subscription is back
+                    if let Err(e) = self.sync_validator_set_from_events().await {
+                        error!("Failed to sync validator set: {}", e);
+                    }
+                    let delay = backoff.next_delay();
+                    debug!("Re-subscribing to consensus events in {:?} (attempt {})", delay, backoff.attempts());
+                    tokio::time::sleep(delay).await;
+                }
+            });
+        }
+
         info!("Starting background validator sync task");
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            let mut interval = tokio::time::interval(VALIDATOR_POLL_INTERVAL);
             loop {
                 interval.tick().await;
                 if let Err(e) = self.sync_validator_set_from_events().await {
@@ -281,11 +765,99 @@ impl AndeConsensusClient {
         })
     }
 
+    /// Follow the consensus contract's events until the subscription drops
+    ///
+    /// `backoff` starts over once subscribed.
+    async fn follow_events(&self, backoff: &mut ReconnectBackoff) -> Result<()> {
+        let filter = Filter::new().address(*self.consensus.address()).event_signature(vec![
+            AndeConsensus::ValidatorSetUpdated::SIGNATURE_HASH,
+            AndeConsensus::BlockProposed::SIGNATURE_HASH,
+            AndeConsensus::BlockFinalized::SIGNATURE_HASH,
+        ]);
+        let mut subscription = self.track(self.provider.subscribe_logs(&filter).await)?;
+        backoff.reset();
+        info!("Subscribed to consensus events");
+
+        // Catch up on the updates missed while unsubscribed
+        self.sync_validator_set_from_events().await?;
+        self.following_events.store(true, Ordering::Relaxed);
+
+        loop {
+            match subscription.recv().await {
+                Ok(log) => self.apply_log(&log).await?,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Missed {} consensus events, resyncing validators", missed);
+                    self.sync_validator_set_from_events().await?;
+                }
+                Err(RecvError::Closed) => {
+                    return Err(ConsensusClientError::Transport(TransportErrorKind::backend_gone()))
+                }
+            }
+        }
+    }
+
+    /// Update the cached validators and finalized blocks with the event in `log`
+    async fn apply_log(&self, log: &Log) -> Result<()> {
+        let event = match AndeConsensus::AndeConsensusEvents::decode_log(&log.inner) {
+            Ok(event) => event.data,
+            Err(e) => {
+                warn!("Ignoring undecodable consensus event: {}", e);
+                return Ok(());
+            }
+        };
+
+        match event {
+            AndeConsensus::AndeConsensusEvents::ValidatorSetUpdated(update) => {
+                let validators = update.validators;
+                *self.validators.write().await = validators.clone();
+                // The producers were picked among the previous set
+                self.producer_schedule.write().await.invalidate();
+                self.refresh_validator_snapshot(&validators).await?;
+                info!("Validator set updated to {} validators", validators.len());
+                self.events.emit(ConsensusEvent::ValidatorSetUpdated { validators });
+            }
+            AndeConsensus::AndeConsensusEvents::BlockProposed(proposal) => {
+                debug!("Block {} proposed by {:?}", proposal.blockNumber, proposal.producer);
+                self.events.emit(ConsensusEvent::BlockProposed {
+                    number: proposal.blockNumber.saturating_to(),
+                    hash: proposal.blockHash,
+                    producer: proposal.producer,
+                });
+            }
+            AndeConsensus::AndeConsensusEvents::BlockFinalized(finalized) => {
+                self.finalized.write().await.insert(finalized.blockNumber.saturating_to(), finalized.blockHash);
+                if let Some(snapshot) = &self.validator_snapshot {
+                    snapshot
+                        .write()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .record_finalized(finalized.blockHash);
+                }
+                debug!("Block {} finalized", finalized.blockNumber);
+                self.events.emit(ConsensusEvent::BlockFinalized {
+                    hash: finalized.blockHash,
+                    power: finalized.totalPower,
+                });
+            }
+            _ => {}
+        }
+
+        if let Some(block_number) = log.block_number {
+            let mut last_synced = self.last_synced_block.write().await;
+            *last_synced = (*last_synced).max(block_number);
+        }
+        Ok(())
+    }
+
+    /// Latest block seen finalized through events, if any
+    pub async fn latest_finalized_block(&self) -> Option<(u64, B256)> {
+        self.finalized.read().await.latest()
+    }
+
     /// Get current proposer from the contract
     pub async fn get_current_proposer(&self) -> Result<Address> {
         debug!("Querying current proposer");
         
-        let proposer = self.consensus.getCurrentProposer().call().await?._0;
+        let proposer = self.track(self.consensus.getCurrentProposer().call().await)?._0;
         
         debug!("Current proposer: {:?}", proposer);
         Ok(proposer)
@@ -299,29 +871,93 @@ impl AndeConsensusClient {
 
     /// Get total voting power
     pub async fn get_total_voting_power(&self) -> Result<U256> {
-        let power = self.consensus.totalVotingPower().call().await?._0;
+        let power = self.track(self.consensus.totalVotingPower().call().await)?._0;
         Ok(power)
     }
 
     /// Check if a block is finalized (has 2/3+1 attestations)
+    ///
+    /// Blocks seen finalized through events are answered without an RPC call.
     pub async fn is_block_finalized(&self, block_hash: B256) -> Result<bool> {
-        let finalized = self.consensus.isBlockFinalized(block_hash).call().await?._0;
+        if self.finalized.read().await.contains(&block_hash) {
+            return Ok(true);
+        }
+        let finalized = self.track(self.consensus.isBlockFinalized(block_hash).call().await)?._0;
+        if let (true, Some(snapshot)) = (finalized, &self.validator_snapshot) {
+            snapshot.write().unwrap_or_else(std::sync::PoisonError::into_inner).record_finalized(block_hash);
+        }
         Ok(finalized)
     }
 
     /// Get attestation power for a block
     pub async fn get_attestation_power(&self, block_hash: B256) -> Result<U256> {
-        let power = self.consensus.getAttestationPower(block_hash).call().await?._0;
+        let power = self.track(self.consensus.getAttestationPower(block_hash).call().await)?._0;
         Ok(power)
     }
 
     /// Get current block number tracked by consensus
     pub async fn get_current_block_number(&self) -> Result<u64> {
-        let block_num = self.consensus.currentBlockNumber().call().await?._0;
+        let block_num = self.track(self.consensus.currentBlockNumber().call().await)?._0;
         Ok(block_num.to())
     }
 }
 
+impl fmt::Debug for AndeConsensusClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AndeConsensusClient")
+            .field("consensus", self.consensus.address())
+            .field("pubsub", &self.pubsub)
+            .field("signer", &self.signer.as_ref().map(|signer| signer.address()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ValidatorStatusSource for AndeConsensusClient {
+    async fn cached_validators(&self) -> Vec<Address> {
+        self.get_cached_validators().await
+    }
+
+    async fn validator_info(&self, validator: Address) -> eyre::Result<RpcValidatorInfo> {
+        Ok(self.get_validator_info(validator).await?.into())
+    }
+
+    async fn validator_details(&self, validator: Address) -> eyre::Result<ValidatorInfo> {
+        Ok(self.get_validator_info(validator).await?)
+    }
+
+    async fn next_scheduled_block(&self, producer: Address) -> Option<u64> {
+        self.producer_schedule.read().await.next_block_of(producer, Instant::now())
+    }
+
+    async fn block_producer(&self, block_number: u64) -> eyre::Result<Address> {
+        Ok(self.get_block_producer(block_number).await?)
+    }
+}
+
+impl ConsensusHealthSource for AndeConsensusClient {
+    fn connection(&self) -> &ConnectionHealth {
+        &self.connection
+    }
+
+    /// Now while the client follows the contract's events, as every update
+    /// is applied as it is emitted
+    fn last_validator_sync(&self) -> Option<Instant> {
+        if self.following_events.load(Ordering::Relaxed) {
+            return Some(Instant::now());
+        }
+        *self.last_validator_sync.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ProducerScheduleSource for AndeConsensusClient {
+    /// Producer cached for `block_number`, `None` if it isn't cached or the
+    /// schedule is being updated
+    fn designated_producer(&self, block_number: u64) -> Option<Address> {
+        self.producer_schedule.try_read().ok()?.get(block_number, Instant::now())
+    }
+}
+
 /// Validator information (matches AndeConsensus.sol struct)
 #[derive(Debug, Clone)]
 pub struct ValidatorInfo {
@@ -355,6 +991,169 @@ pub struct ValidatorInfo {
     pub is_permanent: bool,
 }
 
+impl From<ValidatorInfo> for RpcValidatorInfo {
+    fn from(info: ValidatorInfo) -> Self {
+        Self { stake: info.stake, power: info.power, uptime: info.uptime, jailed: info.jailed, active: info.active }
+    }
+}
+
+/// Most recent finalized blocks, by block number
+///
+/// Holds up to [`MAX_FINALIZED_BLOCKS`] blocks; older ones are evicted first.
+#[derive(Debug, Default)]
+pub struct FinalizationCache {
+    by_number: BTreeMap<u64, B256>,
+    hashes: HashSet<B256>,
+}
+
+impl FinalizationCache {
+    /// Record block `block_number` with hash `block_hash` as finalized
+    pub fn insert(&mut self, block_number: u64, block_hash: B256) {
+        if let Some(previous) = self.by_number.insert(block_number, block_hash) {
+            self.hashes.remove(&previous);
+        }
+        self.hashes.insert(block_hash);
+
+        while self.by_number.len() > MAX_FINALIZED_BLOCKS {
+            if let Some((_, evicted)) = self.by_number.pop_first() {
+                self.hashes.remove(&evicted);
+            }
+        }
+    }
+
+    /// Whether the block with hash `block_hash` is known to be finalized
+    pub fn contains(&self, block_hash: &B256) -> bool {
+        self.hashes.contains(block_hash)
+    }
+
+    /// Highest finalized block
+    pub fn latest(&self) -> Option<(u64, B256)> {
+        self.by_number.last_key_value().map(|(number, hash)| (*number, *hash))
+    }
+
+    /// Number of blocks held
+    pub fn len(&self) -> usize {
+        self.by_number.len()
+    }
+
+    /// Whether no block is held
+    pub fn is_empty(&self) -> bool {
+        self.by_number.is_empty()
+    }
+}
+
+/// Producers designated for upcoming blocks, by block number
+///
+/// Producers are refused once cached for longer than the maximum age, so a
+/// schedule that can't be refreshed isn't trusted indefinitely.
+#[derive(Debug)]
+pub struct ProducerScheduleCache {
+    /// Epoch the producers were fetched in
+    epoch: Option<u64>,
+    producers: BTreeMap<u64, (Address, Instant)>,
+    max_age: Duration,
+}
+
+impl ProducerScheduleCache {
+    /// Create an empty schedule, refusing producers older than `max_age`
+    pub fn new(max_age: Duration) -> Self {
+        Self { epoch: None, producers: BTreeMap::new(), max_age }
+    }
+
+    /// Producer of block `block_number`, unless unknown or older than the
+    /// maximum age at `now`
+    pub fn get(&self, block_number: u64, now: Instant) -> Option<Address> {
+        let (producer, fetched_at) = self.producers.get(&block_number)?;
+        (now.saturating_duration_since(*fetched_at) <= self.max_age).then_some(*producer)
+    }
+
+    /// First cached block designated to `producer`, among those usable at `now`
+    pub fn next_block_of(&self, producer: Address, now: Instant) -> Option<u64> {
+        self.producers.keys().copied().find(|block| self.get(*block, now) == Some(producer))
+    }
+
+    /// Record the producer of block `block_number`, fetched at `now`
+    pub fn insert(&mut self, block_number: u64, producer: Address, now: Instant) {
+        self.producers.insert(block_number, (producer, now));
+    }
+
+    /// Blocks among the `lookahead` blocks from `from` without a producer
+    /// usable at `now`
+    pub fn missing(&self, from: u64, lookahead: u64, now: Instant) -> Vec<u64> {
+        (from..from.saturating_add(lookahead)).filter(|block| self.get(*block, now).is_none()).collect()
+    }
+
+    /// Note the current epoch, dropping the schedule if it changed
+    ///
+    /// Returns whether it changed.
+    pub fn observe_epoch(&mut self, epoch: u64) -> bool {
+        let changed = self.epoch.is_some_and(|previous| previous != epoch);
+        if changed {
+            self.producers.clear();
+        }
+        self.epoch = Some(epoch);
+        changed
+    }
+
+    /// Drop the producers of the blocks before `block_number`
+    pub fn prune_below(&mut self, block_number: u64) {
+        self.producers = self.producers.split_off(&block_number);
+    }
+
+    /// Drop the whole schedule, e.g. after the validator set changed
+    pub fn invalidate(&mut self) {
+        self.producers.clear();
+    }
+
+    /// Number of blocks with a cached producer
+    pub fn len(&self) -> usize {
+        self.producers.len()
+    }
+
+    /// Whether no producer is cached
+    pub fn is_empty(&self) -> bool {
+        self.producers.is_empty()
+    }
+}
+
+/// Delays between attempts to re-subscribe to the consensus events
+///
+/// The delay doubles after every attempt, up to a maximum, and starts over
+/// once a subscription is established.
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    initial: Duration,
+    max: Duration,
+    next: Duration,
+    attempts: u32,
+}
+
+impl ReconnectBackoff {
+    /// Create a backoff starting at `initial`, capped at `max`
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self { initial, max, next: initial.min(max), attempts: 0 }
+    }
+
+    /// Delay before the next attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = self.next.saturating_mul(2).min(self.max);
+        self.attempts += 1;
+        delay
+    }
+
+    /// Start over after a successful attempt
+    pub fn reset(&mut self) {
+        self.next = self.initial.min(self.max);
+        self.attempts = 0;
+    }
+
+    /// Attempts since the last successful one
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
 /// Block proposal information
 #[derive(Debug, Clone)]
 pub struct BlockProposal {
@@ -391,4 +1190,234 @@ mod tests {
         // Test that proposer selection returns valid addresses
         // This would need a test environment setup
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let mut backoff = ReconnectBackoff::new(INITIAL_RECONNECT_DELAY, VALIDATOR_POLL_INTERVAL);
+        let delays: Vec<_> = (0..7).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(backoff.attempts(), 7);
+
+        // A successful subscription starts over
+        backoff.reset();
+        assert_eq!(backoff.attempts(), 0);
+        assert_eq!(backoff.next_delay(), INITIAL_RECONNECT_DELAY);
+    }
+
+    #[test]
+    fn test_block_proposal_signature_recovers_signer() {
+        let signer = PrivateKeySigner::random();
+        let block_hash = B256::repeat_byte(0x42);
+
+        let signature = sign_block_proposal(&signer, 1_234, block_hash).unwrap();
+        assert_eq!(signature.len(), 65);
+        assert!(matches!(signature[64], 27 | 28));
+
+        let digest = block_proposal_digest(1_234, block_hash);
+        let recovered = alloy::primitives::Signature::try_from(&signature[..])
+            .unwrap()
+            .recover_address_from_prehash(&digest)
+            .unwrap();
+        assert_eq!(recovered, signer.address());
+
+        // Bound to both the block number and hash
+        assert_ne!(digest, block_proposal_digest(1_235, block_hash));
+        assert_ne!(digest, block_proposal_digest(1_234, B256::repeat_byte(0x43)));
+    }
+
+    /// Recover the signer with the `ecrecover` precompile of a real node, as
+    /// the contract does
+    ///
+    /// Requires the `anvil` binary in `PATH`.
+    #[tokio::test]
+    #[ignore]
+    async fn test_block_proposal_signature_on_anvil() {
+        let anvil = alloy::node_bindings::Anvil::new().spawn();
+        let provider = ProviderBuilder::new().connect_http(anvil.endpoint_url());
+        let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+        let block_hash = B256::repeat_byte(0x42);
+
+        let signature = sign_block_proposal(&signer, 7, block_hash).unwrap();
+        // ecrecover(hash, v, r, s)
+        let input = [
+            &block_proposal_digest(7, block_hash)[..],
+            &U256::from(signature[64]).to_be_bytes::<32>(),
+            &signature[..64],
+        ]
+        .concat();
+        let tx = alloy::rpc::types::TransactionRequest::default()
+            .to(Address::with_last_byte(0x01))
+            .input(input.into());
+        let output = provider.call(tx).await.unwrap();
+        assert_eq!(Address::from_slice(&output[12..]), signer.address());
+    }
+
+    /// JSON-RPC error response of a call that reverted with `data`
+    fn revert_response(data: &str) -> TransportError {
+        let payload = format!(r#"{{"code":3,"message":"execution reverted","data":"{data}"}}"#);
+        TransportError::ErrorResp(serde_json::from_str(&payload).unwrap())
+    }
+
+    #[test]
+    fn test_error_classification() {
+        // Unreachable node
+        let err = ConsensusClientError::from(TransportErrorKind::backend_gone());
+        assert!(matches!(err, ConsensusClientError::Transport(_)));
+        assert!(err.is_transient());
+
+        // Revert with a custom error, whether from a call or a transaction
+        let err = ConsensusClientError::from(revert_response("0xdeadbeef0001"));
+        let ConsensusClientError::ContractRevert { selector, data } = &err else {
+            panic!("expected a revert: {err:?}");
+        };
+        assert_eq!(*selector, Some(FixedBytes::new([0xde, 0xad, 0xbe, 0xef])));
+        assert_eq!(data[..], [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]);
+        assert!(!err.is_transient());
+        assert!(!err.is_not_our_turn(), "unknown errors aren't decoded");
+        let err = ConsensusClientError::from(alloy::contract::Error::TransportError(revert_response("0xdeadbeef")));
+        assert!(matches!(err, ConsensusClientError::ContractRevert { .. }));
+
+        // Revert without a custom error
+        let err = ConsensusClientError::from(revert_response("0x"));
+        assert!(matches!(err, ConsensusClientError::ContractRevert { selector: None, .. }));
+
+        // Undecodable answer
+        let err = ConsensusClientError::from(alloy::contract::Error::AbiError(alloy::sol_types::Error::Overrun));
+        assert!(matches!(err, ConsensusClientError::Decode(_)));
+        assert!(!err.is_transient());
+
+        // Transaction not confirmed in time
+        let err = ConsensusClientError::from(PendingTransactionError::TxWatcher(WatchTxError::Timeout));
+        assert!(matches!(err, ConsensusClientError::Timeout));
+        assert!(err.is_transient());
+        let err = ConsensusClientError::from(PendingTransactionError::TransportError(revert_response("0x12345678")));
+        assert!(matches!(err, ConsensusClientError::ContractRevert { .. }));
+
+        assert!(!ConsensusClientError::NotConfigured.is_transient());
+    }
+
+    #[test]
+    fn test_producer_schedule_hits() {
+        let mut schedule = ProducerScheduleCache::new(ProducerScheduleConfig::default().max_age);
+        let now = Instant::now();
+        for block in 10..20 {
+            schedule.insert(block, Address::with_last_byte(block as u8), now);
+        }
+
+        assert_eq!(schedule.get(12, now), Some(Address::with_last_byte(12)));
+        assert_eq!(schedule.get(20, now), None);
+        assert_eq!(schedule.missing(15, 8, now), [20, 21, 22]);
+
+        schedule.prune_below(15);
+        assert_eq!(schedule.len(), 5);
+        assert_eq!(schedule.get(12, now), None);
+    }
+
+    #[test]
+    fn test_next_scheduled_block_of_producer() {
+        let mut schedule = ProducerScheduleCache::new(Duration::from_secs(60));
+        let fetched_at = Instant::now();
+        let (ours, other) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        schedule.insert(10, other, fetched_at);
+        schedule.insert(11, ours, fetched_at);
+        schedule.insert(12, ours, fetched_at + Duration::from_secs(30));
+
+        assert_eq!(schedule.next_block_of(ours, fetched_at), Some(11));
+        assert_eq!(schedule.next_block_of(Address::with_last_byte(3), fetched_at), None);
+        // Stale producers are skipped
+        assert_eq!(schedule.next_block_of(ours, fetched_at + Duration::from_secs(61)), Some(12));
+    }
+
+    #[test]
+    fn test_producer_schedule_invalidated_on_epoch_change() {
+        let mut schedule = ProducerScheduleCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(!schedule.observe_epoch(3));
+        schedule.insert(10, Address::with_last_byte(1), now);
+        assert!(!schedule.observe_epoch(3));
+        assert_eq!(schedule.get(10, now), Some(Address::with_last_byte(1)));
+
+        assert!(schedule.observe_epoch(4));
+        assert!(schedule.is_empty());
+
+        // So does a validator set update
+        schedule.insert(10, Address::with_last_byte(1), now);
+        schedule.invalidate();
+        assert_eq!(schedule.get(10, now), None);
+    }
+
+    #[test]
+    fn test_stale_producer_schedule_refused() {
+        let max_age = Duration::from_secs(60);
+        let mut schedule = ProducerScheduleCache::new(max_age);
+        let fetched_at = Instant::now();
+        schedule.insert(10, Address::with_last_byte(1), fetched_at);
+
+        assert!(schedule.get(10, fetched_at + max_age).is_some());
+        assert_eq!(schedule.get(10, fetched_at + max_age + Duration::from_secs(1)), None);
+        assert_eq!(schedule.missing(10, 1, fetched_at + 2 * max_age), [10]);
+    }
+
+    #[test]
+    fn test_finalization_cache() {
+        let mut cache = FinalizationCache::default();
+        cache.insert(1, B256::repeat_byte(0x01));
+        cache.insert(2, B256::repeat_byte(0x02));
+        assert!(cache.contains(&B256::repeat_byte(0x01)));
+        assert_eq!(cache.latest(), Some((2, B256::repeat_byte(0x02))));
+
+        // A block finalized again with another hash replaces the previous one
+        cache.insert(2, B256::repeat_byte(0x22));
+        assert!(!cache.contains(&B256::repeat_byte(0x02)));
+        assert_eq!(cache.len(), 2);
+
+        // The oldest blocks are evicted first
+        for number in 3..=(MAX_FINALIZED_BLOCKS as u64 + 1) {
+            cache.insert(number, B256::from(U256::from(number)));
+        }
+        assert_eq!(cache.len(), MAX_FINALIZED_BLOCKS);
+        assert!(!cache.contains(&B256::repeat_byte(0x01)));
+        assert!(cache.contains(&B256::repeat_byte(0x22)));
+    }
+
+    #[tokio::test]
+    async fn test_consensus_events_reach_every_subscriber() {
+        let events = ConsensusEventStream::default();
+        // Sending without subscribers is fine
+        events.emit(ConsensusEvent::EpochAdvanced { epoch: 1 });
+
+        let (mut metrics, mut indexer) = (events.subscribe(), events.subscribe());
+        assert_eq!(events.receiver_count(), 2);
+        let sent = [
+            ConsensusEvent::ValidatorSetUpdated { validators: vec![Address::repeat_byte(0x01)] },
+            ConsensusEvent::BlockProposed { number: 7, hash: B256::repeat_byte(0x07), producer: Address::repeat_byte(0x01) },
+            ConsensusEvent::BlockFinalized { hash: B256::repeat_byte(0x07), power: U256::from(100) },
+            ConsensusEvent::EpochAdvanced { epoch: 2 },
+        ];
+        for event in sent.clone() {
+            events.emit(event);
+        }
+
+        for receiver in [&mut metrics, &mut indexer] {
+            for expected in &sent {
+                assert_eq!(receiver.recv().await.unwrap(), *expected);
+            }
+            assert!(receiver.try_recv().is_err(), "events sent before subscribing are not delivered");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lagging_consensus_subscriber_skips_events() {
+        let events = ConsensusEventStream::new(2);
+        let mut receiver = events.subscribe();
+        for epoch in 1..=5 {
+            events.emit(ConsensusEvent::EpochAdvanced { epoch });
+        }
+
+        // The oldest events are dropped, and the receiver told how many
+        assert!(matches!(receiver.recv().await, Err(RecvError::Lagged(3))));
+        assert_eq!(receiver.recv().await.unwrap(), ConsensusEvent::EpochAdvanced { epoch: 4 });
+        assert_eq!(receiver.recv().await.unwrap(), ConsensusEvent::EpochAdvanced { epoch: 5 });
+    }
+}
@@ -1,5 +1,6 @@
 //! Consensus configuration for AndeChain PoS integration
 
+use crate::config::FinalizationPolicy;
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -21,6 +22,10 @@ pub struct ConsensusConfig {
     #[serde(default = "default_rpc_url")]
     pub rpc_url: String,
 
+    /// RPC endpoints to fail over to while `rpc_url` is down, by priority
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
+
     /// Private key file path for signing
     pub private_key_file: Option<PathBuf>,
 
@@ -35,6 +40,14 @@ pub struct ConsensusConfig {
     #[serde(default = "default_sync_interval")]
     pub validator_sync_interval_secs: u64,
 
+    /// How long block production waits on the consensus for each built block
+    #[serde(default)]
+    pub finalization_policy: FinalizationPolicy,
+
+    /// Age after which the staking snapshot is refreshed (in seconds)
+    #[serde(default = "default_staking_snapshot_interval")]
+    pub staking_snapshot_interval_secs: u64,
+
     /// Enable automatic phase transition
     #[serde(default)]
     pub auto_phase_transition: bool,
@@ -53,6 +66,10 @@ impl ConsensusConfig {
 
         let rpc_url = std::env::var("ANDE_RPC_URL").unwrap_or_else(|_| default_rpc_url());
 
+        let fallback_rpc_urls = std::env::var("ANDE_FALLBACK_RPC_URLS")
+            .map(|urls| parse_url_list(&urls))
+            .unwrap_or_default();
+
         let private_key_file = std::env::var("SEQUENCER_PRIVATE_KEY_FILE")
             .ok()
             .map(PathBuf::from);
@@ -74,14 +91,27 @@ impl ConsensusConfig {
             consensus_address,
             staking_address,
             rpc_url,
+            fallback_rpc_urls,
             private_key_file,
             private_key,
             attestation_enabled,
             validator_sync_interval_secs: default_sync_interval(),
+            finalization_policy: FinalizationPolicy::default(),
+            staking_snapshot_interval_secs: default_staking_snapshot_interval(),
             auto_phase_transition: false,
         })
     }
 
+    /// RPC endpoints for contract calls, by priority
+    pub fn rpc_urls(&self) -> Vec<&str> {
+        std::iter::once(&self.rpc_url).chain(&self.fallback_rpc_urls).map(String::as_str).collect()
+    }
+
+    /// Contract addresses, with the sequencer registry at `sequencer_registry`
+    pub fn contract_addresses(&self, sequencer_registry: Address) -> ande_consensus_bindings::ContractAddresses {
+        ande_consensus_bindings::ContractAddresses::new(self.consensus_address, self.staking_address, sequencer_registry)
+    }
+
     /// Load private key from file or direct value
     pub fn load_private_key(&self) -> eyre::Result<Option<alloy::signers::local::PrivateKeySigner>> {
         if let Some(ref file) = self.private_key_file {
@@ -106,10 +136,13 @@ impl Default for ConsensusConfig {
             consensus_address: Address::ZERO,
             staking_address: Address::ZERO,
             rpc_url: default_rpc_url(),
+            fallback_rpc_urls: Vec::new(),
             private_key_file: None,
             private_key: None,
             attestation_enabled: default_attestation_enabled(),
             validator_sync_interval_secs: default_sync_interval(),
+            finalization_policy: FinalizationPolicy::default(),
+            staking_snapshot_interval_secs: default_staking_snapshot_interval(),
             auto_phase_transition: false,
         }
     }
@@ -123,6 +156,11 @@ fn default_rpc_url() -> String {
     "http://localhost:8545".to_string()
 }
 
+/// Split a comma-separated list of URLs
+fn parse_url_list(urls: &str) -> Vec<String> {
+    urls.split(',').map(str::trim).filter(|url| !url.is_empty()).map(String::from).collect()
+}
+
 fn default_attestation_enabled() -> bool {
     true
 }
@@ -131,6 +169,10 @@ fn default_sync_interval() -> u64 {
     300 // 5 minutes
 }
 
+fn default_staking_snapshot_interval() -> u64 {
+    30
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,6 +184,18 @@ mod tests {
         assert!(config.attestation_enabled);
         assert_eq!(config.rpc_url, "http://localhost:8545");
         assert_eq!(config.validator_sync_interval_secs, 300);
+        assert_eq!(config.staking_snapshot_interval_secs, 30);
+        assert_eq!(config.finalization_policy, FinalizationPolicy::FireAndForget);
+        assert_eq!(config.rpc_urls(), ["http://localhost:8545"]);
+    }
+
+    #[test]
+    fn test_fallback_rpc_urls() {
+        let config = ConsensusConfig {
+            fallback_rpc_urls: parse_url_list(" http://backup-1:8545,,http://backup-2:8545 "),
+            ..Default::default()
+        };
+        assert_eq!(config.rpc_urls(), ["http://localhost:8545", "http://backup-1:8545", "http://backup-2:8545"]);
     }
 
     #[test]
@@ -4,7 +4,7 @@
 //! precompile into the EVM at runtime. Uses AndePrecompileProvider directly as the
 //! precompile system instead of PrecompilesMap.
 
-use super::AndePrecompileProvider;
+use super::{AndePrecompileConfig, AndePrecompileProvider};
 use alloy_evm::{
     eth::EthEvmContext,
     EvmEnv, EvmFactory,
@@ -31,13 +31,19 @@ pub struct AndeEvmFactory {
 }
 
 impl AndeEvmFactory {
-    /// Create a new ANDE EVM factory with the given spec
-    pub fn new(spec_id: SpecId) -> Self {
+    /// Create a new ANDE EVM factory with the given spec, whose ANDE precompile
+    /// accepts the callers authorized by `precompile_config`
+    pub fn new(spec_id: SpecId, precompile_config: AndePrecompileConfig) -> Self {
         Self {
-            precompile_provider: Arc::new(AndePrecompileProvider::new(spec_id)),
+            precompile_provider: Arc::new(AndePrecompileProvider::new(spec_id, precompile_config)),
         }
     }
 
+    /// Create a factory injecting `precompile_provider`
+    pub fn with_precompile_provider(precompile_provider: Arc<AndePrecompileProvider>) -> Self {
+        Self { precompile_provider }
+    }
+
     /// Get reference to the precompile provider
     pub fn precompile_provider(&self) -> &Arc<AndePrecompileProvider> {
         &self.precompile_provider
@@ -58,7 +64,7 @@ impl EvmFactory for AndeEvmFactory {
         // 🔥 ANDE PRECOMPILE INJECTION
         // Use AndePrecompileProvider directly as the precompile system
         // This provider implements PrecompileProvider<CTX> with full context access
-        // allowing it to execute native balance transfers via journal.transfer()
+        // allowing it to execute native balance transfers through the journal
         
         let ande_provider = self.precompile_provider.as_ref().clone();
         
@@ -79,7 +85,7 @@ impl EvmFactory for AndeEvmFactory {
         input: EvmEnv,
         inspector: I,
     ) -> Self::Evm<DB, I> {
-        EthEvm::new(self.create_evm(db, input).into_inner().with_inspector(inspector), false)
+        EthEvm::new(self.create_evm(db, input).into_inner().with_inspector(inspector), true)
     }
 }
 
@@ -89,13 +95,13 @@ mod tests {
 
     #[test]
     fn test_ande_evm_factory_creation() {
-        let factory = AndeEvmFactory::new(SpecId::CANCUN);
+        let factory = AndeEvmFactory::new(SpecId::CANCUN, AndePrecompileConfig::default());
         assert!(Arc::strong_count(factory.precompile_provider()) >= 1);
     }
 
     #[test]
     fn test_precompile_provider_available() {
-        let factory = AndeEvmFactory::new(SpecId::CANCUN);
+        let factory = AndeEvmFactory::new(SpecId::CANCUN, AndePrecompileConfig::default());
         let provider = factory.precompile_provider();
         
         // Verify provider is available
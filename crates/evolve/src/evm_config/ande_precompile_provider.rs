@@ -11,135 +11,200 @@
 //! This precompile enables Token Duality:
 //! - Smart contracts can interact with ANDE as if it were ERC-20
 //! - Transfers modify native balances via journal.transfer()
-//! - No "token address" validation needed (ANDE is native)
+//! - Only the ANDEToken contract and the allow-list may call it
+//!
+//! The provider is a registry of Evolve precompiles keyed by address, served
+//! ahead of the standard Ethereum ones. [`AndePrecompileProvider::new`]
+//! installs the default set, authorizing the callers of the configuration
+//! it is given, and more can be added with
+//! [`AndePrecompileProvider::with_precompile`]. The consensus validator-set
+//! reader is registered by [`AndePrecompileProvider::with_validator_set`] once
+//! the node has a snapshot to share with it.
+//!
+//! The transfer itself is implemented by
+//! [`ande_token_duality_run`](super::precompile), which this provider hands
+//! the EVM context of the call once the caller is authorized by its
+//! [`AndePrecompileConfig`].
 //!
 //! ## Production Status (v0.3.0)
 //!
 //! ✅ Native balance transfers via JournalTr::transfer()
-//! ✅ Sovereign mode (no token address validation)
+//! ✅ Caller allow-list configured from genesis
 //! ✅ Gas metering and error handling
 //! ✅ Production-ready and tested
 
-use alloy_primitives::{Address, Bytes, U256};
+use super::precompile::{ande_token_duality_run, AndePrecompileError, ANDE_PRECOMPILE_ADDRESS};
+use super::precompile_config::AndePrecompileConfig;
+use super::validator_set::{SharedValidatorSnapshot, ValidatorSetReader, VALIDATOR_SET_PRECOMPILE_ADDRESS};
+use alloy_primitives::Address;
 use revm::{
     handler::{EthPrecompiles, PrecompileProvider},
     interpreter::{Gas, InputsImpl, InstructionResult, InterpreterResult},
-    precompile::{PrecompileSpecId, Precompiles},
+    precompile::{PrecompileError, PrecompileFn, PrecompileResult, PrecompileSpecId, Precompiles},
     primitives::hardfork::SpecId,
 };
-use revm_context_interface::{ContextTr, JournalTr};
-use std::boxed::Box;
+use revm_context_interface::{Block, ContextTr};
+use std::{boxed::Box, collections::BTreeMap, sync::Arc};
 
-/// ANDE Precompile Address: 0x00..fd
-pub const ANDE_PRECOMPILE_ADDRESS: Address = Address::new([
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0x00, 0x00, 0x00, 0xfd,
-]);
+/// Evolve precompile served by [`AndePrecompileProvider`]
+#[derive(Debug, Clone)]
+pub enum AndePrecompile {
+    /// ANDE Token Duality, moving native balances through the EVM journal
+    ///
+    /// Only the callers authorized by the provider's [`AndePrecompileConfig`]
+    /// may call it.
+    TokenDuality,
+    /// Consensus validator set, read from the snapshot captured at block start
+    ValidatorSet(ValidatorSetReader),
+    /// Precompile computing its output from its input alone, like the standard ones
+    Stateless(PrecompileFn),
+}
 
-/// Gas costs
-const BASE_GAS: u64 = 3000;
-const PER_WORD_GAS: u64 = 100;
+/// Precompiles installed by [`AndePrecompileProvider::with_default_precompiles`]
+pub fn default_ande_precompiles() -> [(Address, AndePrecompile); 1] {
+    [(ANDE_PRECOMPILE_ADDRESS, AndePrecompile::TokenDuality)]
+}
 
 /// Precompile provider for AndeChain sovereign rollup
 #[derive(Debug, Clone)]
 pub struct AndePrecompileProvider {
     eth_precompiles: EthPrecompiles,
+    /// Evolve precompiles by address, taking precedence over the standard ones
+    precompiles: BTreeMap<Address, AndePrecompile>,
+    /// Callers allowed to use the ANDE precompile
+    config: Arc<AndePrecompileConfig>,
 }
 
 impl AndePrecompileProvider {
-    /// Create new provider
-    pub fn new(spec: SpecId) -> Self {
+    /// Create new provider with the default Evolve precompiles, accepting ANDE
+    /// precompile calls from the callers authorized by `config`
+    pub fn new(spec: SpecId, config: AndePrecompileConfig) -> Self {
+        Self::empty(spec).with_default_precompiles().with_config(Arc::new(config))
+    }
+
+    /// Create a provider serving only the standard precompiles of `spec`
+    ///
+    /// Its configuration authorizes no caller, should the ANDE precompile be
+    /// added with [`Self::with_precompile`] without [`Self::with_config`].
+    pub fn empty(spec: SpecId) -> Self {
         Self {
             eth_precompiles: EthPrecompiles {
                 precompiles: Precompiles::new(PrecompileSpecId::from_spec_id(spec)),
                 spec,
             },
+            precompiles: BTreeMap::new(),
+            config: Arc::new(AndePrecompileConfig::default()),
         }
     }
 
+    /// Install the [default Evolve precompiles](default_ande_precompiles)
+    pub fn with_default_precompiles(self) -> Self {
+        default_ande_precompiles()
+            .into_iter()
+            .fold(self, |provider, (address, precompile)| provider.with_precompile(address, precompile))
+    }
+
+    /// Serve `precompile` at `address`, replacing any precompile there
+    pub fn with_precompile(mut self, address: Address, precompile: AndePrecompile) -> Self {
+        self.precompiles.insert(address, precompile);
+        self
+    }
+
+    /// Serve the consensus validator set from `snapshot` at
+    /// [`VALIDATOR_SET_PRECOMPILE_ADDRESS`]
+    pub fn with_validator_set(self, snapshot: SharedValidatorSnapshot) -> Self {
+        self.with_precompile(VALIDATOR_SET_PRECOMPILE_ADDRESS, AndePrecompile::ValidatorSet(ValidatorSetReader::new(snapshot)))
+    }
+
+    /// Accept ANDE precompile calls from the callers authorized by `config`
+    pub fn with_config(mut self, config: Arc<AndePrecompileConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Configuration authorizing the callers of the ANDE precompile
+    pub fn config(&self) -> &AndePrecompileConfig {
+        &self.config
+    }
+
+    /// Evolve precompile served at `address`, if any
+    pub fn precompile(&self, address: &Address) -> Option<&AndePrecompile> {
+        self.precompiles.get(address)
+    }
+
+    /// Addresses of the Evolve precompiles, in ascending order
+    pub fn addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        self.precompiles.keys().copied()
+    }
+
+    /// Validator-set readers registered in the provider
+    pub fn validator_set_readers(&self) -> impl Iterator<Item = &ValidatorSetReader> + '_ {
+        self.precompiles.values().filter_map(|precompile| match precompile {
+            AndePrecompile::ValidatorSet(reader) => Some(reader),
+            _ => None,
+        })
+    }
+
+    /// Whether a precompile, Evolve or standard, is served at `address`
+    pub fn contains(&self, address: &Address) -> bool {
+        self.precompiles.contains_key(address) || self.eth_precompiles.precompiles.contains(address)
+    }
+
     /// Execute ANDE native transfer
-    fn run_ande_precompile<CTX: ContextTr>(
-        &mut self,
+    fn run_token_duality<CTX: ContextTr>(
+        &self,
         context: &mut CTX,
         inputs: &InputsImpl,
         is_static: bool,
         gas_limit: u64,
-    ) -> Result<Option<InterpreterResult>, String> {
-        if is_static {
-            return Err("Cannot modify state in static call".into());
+    ) -> PrecompileResult {
+        if !self.config.is_authorized(inputs.caller_address) {
+            return Err(AndePrecompileError::UnauthorizedCaller(inputs.caller_address).into());
         }
-
         let input_bytes = inputs.input.bytes(context);
-        
-        if input_bytes.len() != 96 {
-            return Err(format!("Invalid input: expected 96 bytes, got {}", input_bytes.len()));
-        }
-
-        // Decode: abi.encode(from, to, value)
-        let from = Address::from_slice(&input_bytes[12..32]);
-        let to = Address::from_slice(&input_bytes[44..64]);
-        let value = U256::from_be_slice(&input_bytes[64..96]);
+        ande_token_duality_run(context, &input_bytes, gas_limit, is_static, self.config.emit_transfer_logs)
+    }
+}
 
-        // Gas check
-        let gas_cost = BASE_GAS + (PER_WORD_GAS * 3);
-        if gas_limit < gas_cost {
-            return Err("Insufficient gas".into());
-        }
+/// Interpreter result of a precompile call
+///
+/// Failures are reported like those of the standard precompiles: the call
+/// fails and consumes its gas, but the transaction goes on. Only fatal errors,
+/// e.g. of the database, abort the transaction.
+fn interpreter_result(
+    result: PrecompileResult,
+    gas_limit: u64,
+    inputs: &InputsImpl,
+) -> Result<InterpreterResult, String> {
+    let mut interpreter_result = InterpreterResult {
+        result: InstructionResult::Return,
+        gas: Gas::new(gas_limit),
+        output: Default::default(),
+    };
 
-        // Validate recipient
-        if to.is_zero() {
-            return Err("Cannot transfer to zero address".into());
+    match result {
+        Ok(output) => {
+            let _ = interpreter_result.gas.record_cost(output.gas_used);
+            interpreter_result.output = output.bytes;
         }
-
-        // Zero value optimization
-        if value.is_zero() {
-            let mut result = InterpreterResult {
-                result: InstructionResult::Return,
-                gas: Gas::new(gas_limit),
-                output: Bytes::from(vec![0x01]),
+        Err(PrecompileError::Fatal(err)) => return Err(err),
+        Err(err) => {
+            tracing::debug!(caller = ?inputs.caller_address, target = ?inputs.target_address, %err, "Evolve precompile failed");
+            interpreter_result.result = if err.is_oog() {
+                InstructionResult::PrecompileOOG
+            } else {
+                InstructionResult::PrecompileError
             };
-            let _ = result.gas.record_cost(gas_cost);
-            return Ok(Some(result));
-        }
-
-        // Log transfer
-        tracing::debug!(
-            ?from, ?to, ?value,
-            caller = ?inputs.caller_address,
-            "ANDE native transfer"
-        );
-
-        // Execute native transfer
-        let journal = context.journal_mut();
-        
-        match journal.transfer(from, to, value) {
-            Ok(None) => {
-                tracing::debug!("✅ Transfer successful");
-            }
-            Ok(Some(err)) => {
-                return Err(format!("Transfer failed: {:?}", err));
-            }
-            Err(db_err) => {
-                return Err(format!("Database error: {:?}", db_err));
-            }
         }
-
-        // Return success
-        let mut result = InterpreterResult {
-            result: InstructionResult::Return,
-            gas: Gas::new(gas_limit),
-            output: Bytes::from(vec![0x01]),
-        };
-        let _ = result.gas.record_cost(gas_cost);
-
-        Ok(Some(result))
     }
+
+    Ok(interpreter_result)
 }
 
 impl Default for AndePrecompileProvider {
     fn default() -> Self {
-        Self::new(SpecId::CANCUN)
+        // The ANDE precompile rejects every caller until configured
+        Self::new(SpecId::CANCUN, AndePrecompileConfig::default())
     }
 }
 
@@ -167,20 +232,26 @@ impl<CTX: ContextTr> PrecompileProvider<CTX> for AndePrecompileProvider {
         is_static: bool,
         gas_limit: u64,
     ) -> Result<Option<InterpreterResult>, String> {
-        if address == &ANDE_PRECOMPILE_ADDRESS {
-            return self.run_ande_precompile(context, inputs, is_static, gas_limit);
-        }
-        self.eth_precompiles.run(context, address, inputs, is_static, gas_limit)
+        let result = match self.precompile(address) {
+            Some(AndePrecompile::TokenDuality) => self.run_token_duality(context, inputs, is_static, gas_limit),
+            Some(AndePrecompile::ValidatorSet(reader)) => {
+                let block_number = context.block().number().saturating_to();
+                reader.run(block_number, &inputs.input.bytes(context), gas_limit)
+            }
+            Some(AndePrecompile::Stateless(run)) => run(&inputs.input.bytes(context), gas_limit),
+            None => return self.eth_precompiles.run(context, address, inputs, is_static, gas_limit),
+        };
+        interpreter_result(result, gas_limit, inputs).map(Some)
     }
 
     fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
-        let ande = std::iter::once(ANDE_PRECOMPILE_ADDRESS);
+        let ande: Vec<_> = self.addresses().collect();
         let eth = self.eth_precompiles.warm_addresses();
-        Box::new(ande.chain(eth))
+        Box::new(ande.into_iter().chain(eth))
     }
 
     fn contains(&self, address: &Address) -> bool {
-        address == &ANDE_PRECOMPILE_ADDRESS || self.eth_precompiles.contains(address)
+        Self::contains(self, address)
     }
 }
 
@@ -201,4 +272,47 @@ mod tests {
         let provider = AndePrecompileProvider::default();
         assert!(provider.contains(&ANDE_PRECOMPILE_ADDRESS));
     }
+
+    #[test]
+    fn default_providers_authorize_no_caller() {
+        assert!(AndePrecompileProvider::default().config().denies_all_callers());
+        assert!(AndePrecompileProvider::empty(SpecId::CANCUN).config().denies_all_callers());
+
+        let mut config = AndePrecompileConfig::default();
+        config.add_to_allow_list(Address::repeat_byte(0x01));
+        let provider = AndePrecompileProvider::new(SpecId::CANCUN, config);
+        assert!(provider.config().is_authorized(Address::repeat_byte(0x01)));
+        assert!(!provider.config().is_authorized(Address::repeat_byte(0x02)));
+    }
+
+    #[test]
+    fn registry_serves_added_precompiles() {
+        fn stub(_input: &[u8], _gas_limit: u64) -> PrecompileResult {
+            Ok(revm::precompile::PrecompileOutput::new(0, Default::default()))
+        }
+        let stub_address = Address::with_last_byte(0xfc);
+
+        let empty = AndePrecompileProvider::empty(SpecId::CANCUN);
+        assert_eq!(empty.addresses().count(), 0);
+        assert!(!empty.contains(&ANDE_PRECOMPILE_ADDRESS));
+
+        let provider = AndePrecompileProvider::default()
+            .with_precompile(stub_address, AndePrecompile::Stateless(stub));
+        assert_eq!(provider.addresses().collect::<Vec<_>>(), [stub_address, ANDE_PRECOMPILE_ADDRESS]);
+        assert!(matches!(provider.precompile(&ANDE_PRECOMPILE_ADDRESS), Some(AndePrecompile::TokenDuality)));
+        assert!(provider.contains(&stub_address));
+        assert!(provider.contains(&Address::with_last_byte(0x01)));
+    }
+
+    #[test]
+    fn validator_set_registered_at_its_address() {
+        use crate::evm_config::validator_set::ValidatorSnapshot;
+
+        let snapshot = Arc::new(std::sync::RwLock::new(ValidatorSnapshot::new()));
+        let provider = AndePrecompileProvider::default().with_validator_set(snapshot.clone());
+        assert!(provider.contains(&VALIDATOR_SET_PRECOMPILE_ADDRESS));
+        let readers: Vec<_> = provider.validator_set_readers().collect();
+        assert_eq!(readers.len(), 1);
+        assert!(Arc::ptr_eq(readers[0].shared(), &snapshot));
+    }
 }
@@ -0,0 +1,296 @@
+//! ANDE EVM Configuration Builder
+//!
+//! [`AndeEvmConfigBuilder`] wires an [`AndeEvmConfig`] from a chain spec, a
+//! [`NetworkProfile`] picking the precompile caps and strictness, and explicit
+//! overrides, which take precedence over the profile.
+
+use super::{
+    create_ande_evm_config_with_provider, AndeEvmConfig, AndePrecompileConfig, AndePrecompileProvider,
+    SharedValidatorSnapshot,
+};
+use crate::parallel::ParallelConfig;
+use alloy_primitives::U256;
+use reth_chainspec::ChainSpec;
+use revm::primitives::hardfork::SpecId;
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+
+/// Network an AndeChain node runs, picking the ANDE precompile's default caps
+/// and strictness
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkProfile {
+    /// Local development: authorized callers only, without caps
+    Dev,
+    /// Public testnet: authorized callers only, with generous caps
+    Testnet,
+    /// Mainnet: authorized callers only, capped per call, per block and per
+    /// caller in a block
+    #[default]
+    Mainnet,
+}
+
+impl NetworkProfile {
+    /// Name used in configuration files
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Testnet => "testnet",
+            Self::Mainnet => "mainnet",
+        }
+    }
+
+    /// Default ANDE precompile configuration of the network
+    ///
+    /// The ANDEToken address and allow-list are left empty; they come from
+    /// the genesis or node configuration.
+    pub fn precompile_config(&self) -> AndePrecompileConfig {
+        match self {
+            Self::Dev => {
+                let mut config = AndePrecompileConfig::default();
+                config.per_call_cap = U256::MAX;
+                config.per_block_cap = None;
+                config
+            }
+            Self::Testnet => {
+                let mut config = AndePrecompileConfig::default();
+                config.per_call_cap = ande(10_000_000);
+                config.per_block_cap = Some(ande(100_000_000));
+                config
+            }
+            Self::Mainnet => {
+                let mut config = AndePrecompileConfig::default();
+                // A quarter of the block cap
+                config.per_caller_block_cap = Some(ande(2_500_000));
+                config
+            }
+        }
+    }
+}
+
+impl FromStr for NetworkProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dev" => Ok(Self::Dev),
+            "testnet" => Ok(Self::Testnet),
+            "mainnet" => Ok(Self::Mainnet),
+            other => Err(format!("Unknown network profile: {other}")),
+        }
+    }
+}
+
+/// `amount` whole ANDE, in wei
+fn ande(amount: u64) -> U256 {
+    U256::from(amount) * U256::from(10u64).pow(U256::from(18))
+}
+
+/// Errors building an [`AndeEvmConfig`]
+#[derive(Debug, thiserror::Error)]
+pub enum AndeEvmConfigError {
+    /// No chain spec was given to the builder
+    #[error("AndeEvmConfigBuilder requires a chain spec")]
+    MissingChainSpec,
+}
+
+/// Builder of [`AndeEvmConfig`]
+///
+/// # Example
+/// ```ignore
+/// let evm_config = AndeEvmConfigBuilder::new()
+///     .chain_spec(chain_spec)
+///     .profile(NetworkProfile::Testnet)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AndeEvmConfigBuilder {
+    chain_spec: Option<Arc<ChainSpec>>,
+    profile: NetworkProfile,
+    /// Replaces the profile's precompile configuration
+    precompile_config: Option<AndePrecompileConfig>,
+    parallel_config: Option<ParallelConfig>,
+    validator_snapshot: Option<SharedValidatorSnapshot>,
+    /// Serve only the standard precompiles
+    without_ande_precompiles: bool,
+}
+
+impl AndeEvmConfigBuilder {
+    /// Create a builder for the [mainnet profile](NetworkProfile::Mainnet)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chain whose EVMs are configured
+    pub fn chain_spec(mut self, chain_spec: Arc<ChainSpec>) -> Self {
+        self.chain_spec = Some(chain_spec);
+        self
+    }
+
+    /// Take the precompile defaults of `profile`
+    pub fn profile(mut self, profile: NetworkProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Configure the ANDE precompile with `config` instead of the profile defaults
+    pub fn precompile_config(mut self, config: AndePrecompileConfig) -> Self {
+        self.precompile_config = Some(config);
+        self
+    }
+
+    /// Execute blocks in parallel with `config`
+    pub fn parallel(mut self, config: ParallelConfig) -> Self {
+        self.parallel_config = Some(config);
+        self
+    }
+
+    /// Serve the validator-set precompile from `snapshot`
+    pub fn validator_snapshot(mut self, snapshot: SharedValidatorSnapshot) -> Self {
+        self.validator_snapshot = Some(snapshot);
+        self
+    }
+
+    /// Serve the Evolve precompiles, the default
+    ///
+    /// When disabled, the built EVMs serve only the standard precompiles, like
+    /// those of the stock Ethereum executor.
+    pub fn ande_precompiles(mut self, enabled: bool) -> Self {
+        self.without_ande_precompiles = !enabled;
+        self
+    }
+
+    /// Profile the precompile defaults are taken from
+    pub fn network_profile(&self) -> NetworkProfile {
+        self.profile
+    }
+
+    /// ANDE precompile configuration the built EVMs use
+    pub fn effective_precompile_config(&self) -> AndePrecompileConfig {
+        self.precompile_config.clone().unwrap_or_else(|| self.profile.precompile_config())
+    }
+
+    /// Parallel execution configuration, if parallel execution is enabled
+    pub fn parallel_config(&self) -> Option<&ParallelConfig> {
+        self.parallel_config.as_ref()
+    }
+
+    /// Build the EVM configuration
+    pub fn build(self) -> Result<AndeEvmConfig, AndeEvmConfigError> {
+        self.build_with_parallel().map(|(evm_config, _)| evm_config)
+    }
+
+    /// Build the EVM configuration, along with the parallel execution
+    /// configuration to run it with
+    pub fn build_with_parallel(self) -> Result<(AndeEvmConfig, Option<ParallelConfig>), AndeEvmConfigError> {
+        let chain_spec = self.chain_spec.clone().ok_or(AndeEvmConfigError::MissingChainSpec)?;
+
+        // TODO: Get actual spec from chain_spec hardfork schedule
+        if self.without_ande_precompiles {
+            let provider = AndePrecompileProvider::empty(SpecId::CANCUN);
+            return Ok((create_ande_evm_config_with_provider(chain_spec, provider), self.parallel_config));
+        }
+        let mut provider = AndePrecompileProvider::new(SpecId::CANCUN, self.effective_precompile_config());
+        if let Some(snapshot) = self.validator_snapshot {
+            provider = provider.with_validator_set(snapshot);
+        }
+
+        Ok((create_ande_evm_config_with_provider(chain_spec, provider), self.parallel_config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm_config::VALIDATOR_SET_PRECOMPILE_ADDRESS;
+    use alloy_primitives::Address;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_evm::ConfigureEvm;
+    use std::num::NonZeroUsize;
+
+    fn chain_spec() -> Arc<ChainSpec> {
+        Arc::new(ChainSpecBuilder::default().chain(MAINNET.chain).genesis(Default::default()).build())
+    }
+
+    /// Precompile configuration of the EVMs built for `builder`
+    fn built_config(builder: AndeEvmConfigBuilder) -> AndePrecompileConfig {
+        let evm_config = builder.chain_spec(chain_spec()).build().unwrap();
+        evm_config.evm_factory().precompile_provider().config().clone()
+    }
+
+    #[test]
+    fn test_profile_precompile_configs() {
+        let dev = built_config(AndeEvmConfigBuilder::new().profile(NetworkProfile::Dev));
+        assert!(dev.strict_validation);
+        assert!(dev.denies_all_callers(), "callers are authorized by the genesis or node configuration");
+        assert_eq!(dev.per_call_cap, U256::MAX);
+        assert_eq!(dev.per_block_cap, None);
+        assert_eq!(dev.per_caller_block_cap, None);
+
+        let testnet = built_config(AndeEvmConfigBuilder::new().profile(NetworkProfile::Testnet));
+        assert!(testnet.strict_validation);
+        assert_eq!(testnet.per_call_cap, ande(10_000_000));
+        assert_eq!(testnet.per_block_cap, Some(ande(100_000_000)));
+        assert_eq!(testnet.per_caller_block_cap, None);
+
+        let mainnet = built_config(AndeEvmConfigBuilder::new());
+        assert!(mainnet.strict_validation);
+        assert_eq!(mainnet.per_call_cap, ande(1_000_000));
+        assert_eq!(mainnet.per_block_cap, Some(ande(10_000_000)));
+        assert_eq!(mainnet.per_caller_block_cap, Some(ande(2_500_000)));
+    }
+
+    #[test]
+    fn test_explicit_config_overrides_profile() {
+        let mut config = NetworkProfile::Mainnet.precompile_config();
+        config.set_ande_token_address(Address::repeat_byte(0x01));
+        config.per_call_cap = U256::from(5);
+
+        let builder = AndeEvmConfigBuilder::new().precompile_config(config).profile(NetworkProfile::Dev);
+        assert_eq!(builder.network_profile(), NetworkProfile::Dev);
+        let built = built_config(builder);
+        assert!(built.strict_validation);
+        assert_eq!(built.per_call_cap, U256::from(5));
+        assert!(built.is_authorized(Address::repeat_byte(0x01)));
+    }
+
+    #[test]
+    fn test_build_with_parallel_and_validator_set() {
+        let parallel = ParallelConfig { concurrency_level: NonZeroUsize::new(3).unwrap(), ..Default::default() };
+        let (evm_config, parallel_config) = AndeEvmConfigBuilder::new()
+            .chain_spec(chain_spec())
+            .parallel(parallel)
+            .validator_snapshot(SharedValidatorSnapshot::default())
+            .build_with_parallel()
+            .unwrap();
+        assert_eq!(parallel_config.unwrap().concurrency_level.get(), 3);
+        assert!(evm_config.evm_factory().precompile_provider().contains(&VALIDATOR_SET_PRECOMPILE_ADDRESS));
+
+        assert!(matches!(AndeEvmConfigBuilder::new().build(), Err(AndeEvmConfigError::MissingChainSpec)));
+    }
+
+    #[test]
+    fn test_without_ande_precompiles() {
+        let evm_config = AndeEvmConfigBuilder::new()
+            .chain_spec(chain_spec())
+            .validator_snapshot(SharedValidatorSnapshot::default())
+            .ande_precompiles(false)
+            .build()
+            .unwrap();
+        let provider = evm_config.evm_factory().precompile_provider();
+        assert_eq!(provider.addresses().count(), 0);
+        assert!(!provider.contains(&crate::evm_config::ANDE_PRECOMPILE_ADDRESS));
+        assert!(!provider.contains(&VALIDATOR_SET_PRECOMPILE_ADDRESS));
+        // The standard precompiles are still served, e.g. ecrecover
+        assert!(provider.contains(&Address::with_last_byte(1)));
+    }
+
+    #[test]
+    fn test_profile_from_str() {
+        for profile in [NetworkProfile::Dev, NetworkProfile::Testnet, NetworkProfile::Mainnet] {
+            assert_eq!(profile.as_str().parse::<NetworkProfile>().unwrap(), profile);
+        }
+        assert_eq!("TestNet".parse::<NetworkProfile>().unwrap(), NetworkProfile::Testnet);
+        assert!("staging".parse::<NetworkProfile>().is_err());
+    }
+}
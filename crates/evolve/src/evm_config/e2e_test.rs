@@ -11,15 +11,13 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::evm_config::{
-        AndeEvmFactory, ANDE_PRECOMPILE_ADDRESS, ANDE_TOKEN_ADDRESS,
-    };
+    use crate::evm_config::{AndeEvmFactory, AndePrecompileConfig, ANDE_PRECOMPILE_ADDRESS};
     use revm::primitives::hardfork::SpecId;
 
     #[test]
     fn test_ande_evm_factory_has_precompile_provider() {
         // Verify factory is created with ANDE precompile provider
-        let factory = AndeEvmFactory::new(SpecId::CANCUN);
+        let factory = AndeEvmFactory::new(SpecId::CANCUN, AndePrecompileConfig::default());
         let provider = factory.precompile_provider();
         
         // Provider should exist
@@ -35,16 +33,6 @@ mod tests {
         assert_eq!(ANDE_PRECOMPILE_ADDRESS, expected);
     }
 
-    #[test]
-    fn test_ande_token_address_placeholder() {
-        // Verify ANDE token address is set (even if to zero for now)
-        // In production, this should be configured via genesis
-        use alloy_primitives::Address;
-        
-        // For now it's zero, will be set via genesis
-        assert_eq!(ANDE_TOKEN_ADDRESS, Address::ZERO);
-    }
-
     // TODO: Full end-to-end tests require:
     // - Proper EVM setup with CacheDB
     // - Transaction creation and execution
@@ -2,9 +2,17 @@
 //!
 //! This module provides a custom BlockExecutorFactory that injects the ANDE
 //! precompile provider into the EVM during execution.
+//!
+//! The inspectors it creates share one [`BlockTransferLedger`], so the
+//! precompile's block caps hold across the workers executing a block in
+//! parallel.
 
-use crate::evm_config::AndePrecompileProvider;
+use crate::evm_config::{
+    AndeEvmConfig, AndeEvmFactory, AndePrecompileConfig, AndePrecompileInspector, AndePrecompileProvider,
+    BlockTransferLedger,
+};
 use reth_chainspec::ChainSpec;
+use reth_evm_ethereum::EthEvmConfig;
 use revm::primitives::hardfork::SpecId;
 use std::sync::Arc;
 
@@ -13,20 +21,36 @@ use std::sync::Arc;
 pub struct AndeBlockExecutorFactory {
     chain_spec: Arc<ChainSpec>,
     precompile_provider: Arc<AndePrecompileProvider>,
+    /// Transfers of the block being executed, shared by the inspectors of the factory
+    transfer_ledger: Arc<BlockTransferLedger>,
 }
 
 impl AndeBlockExecutorFactory {
-    /// Create a new ANDE block executor factory
-    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+    /// Create a new ANDE block executor factory, whose ANDE precompile accepts
+    /// the callers authorized by `precompile_config`
+    pub fn new(chain_spec: Arc<ChainSpec>, precompile_config: AndePrecompileConfig) -> Self {
         // Use latest Cancun spec for now
         // TODO: Get actual spec from chain_spec hardfork schedule
         let spec_id = SpecId::CANCUN;
         
-        let precompile_provider = Arc::new(AndePrecompileProvider::new(spec_id));
-        
+        Self::with_precompile_provider(chain_spec, AndePrecompileProvider::new(spec_id, precompile_config))
+    }
+
+    /// Create a factory whose EVMs serve every precompile registered in `precompile_provider`
+    pub fn with_precompile_provider(chain_spec: Arc<ChainSpec>, precompile_provider: AndePrecompileProvider) -> Self {
         Self {
             chain_spec,
-            precompile_provider,
+            precompile_provider: Arc::new(precompile_provider),
+            transfer_ledger: Arc::new(BlockTransferLedger::new()),
+        }
+    }
+
+    /// Create a factory running the precompiles of `evm_config`, with a ledger of its own
+    pub fn from_evm_config(evm_config: &AndeEvmConfig) -> Self {
+        Self {
+            chain_spec: evm_config.chain_spec().clone(),
+            precompile_provider: evm_config.evm_factory().precompile_provider().clone(),
+            transfer_ledger: Arc::new(BlockTransferLedger::new()),
         }
     }
 
@@ -39,18 +63,57 @@ impl AndeBlockExecutorFactory {
     pub fn precompile_provider(&self) -> &Arc<AndePrecompileProvider> {
         &self.precompile_provider
     }
-}
 
-// Note: Full BlockExecutorFactory implementation requires deep integration with reth's
-// execution architecture. For Phase 1, we provide the precompile provider separately
-// for runtime injection during block building.
-//
-// Future work (Phase 2): Create a custom EvmFactory that wraps the EVM and injects
-// the precompile provider before each transaction execution.
+    /// Transfers of the block, shared by the inspectors created by [`Self::inspector`]
+    pub fn transfer_ledger(&self) -> &Arc<BlockTransferLedger> {
+        &self.transfer_ledger
+    }
+
+    /// Create an inspector enforcing the caps of the provider's precompile
+    /// configuration, accounting for transfers in the factory's ledger
+    ///
+    /// Every inspector created for a block, e.g. one per parallel worker,
+    /// counts towards the same block caps.
+    pub fn inspector(&self) -> AndePrecompileInspector {
+        AndePrecompileInspector::with_ledger(
+            self.precompile_provider.config().clone(),
+            self.transfer_ledger.clone(),
+        )
+    }
+
+    /// Prepare the precompiles and `inspectors` for executing block `block_number`
+    ///
+    /// The validator-set readers capture the snapshot served during the block,
+    /// and the transfer ledger of the factory and inspectors start over. Both
+    /// also happen when a new block number shows up in the EVM context; this
+    /// covers a block rebuilt with the same number.
+    pub fn on_block_start<'a>(
+        &self,
+        block_number: u64,
+        inspectors: impl IntoIterator<Item = &'a mut AndePrecompileInspector>,
+    ) {
+        for reader in self.precompile_provider.validator_set_readers() {
+            reader.capture(block_number);
+        }
+        self.transfer_ledger.reset(block_number);
+        for inspector in inspectors {
+            inspector.reset_for_new_block(block_number);
+        }
+    }
+
+    /// EVM configuration whose block executors run the precompiles of this factory
+    pub fn evm_config(&self) -> AndeEvmConfig {
+        EthEvmConfig::new_with_evm_factory(
+            self.chain_spec.clone(),
+            AndeEvmFactory::with_precompile_provider(self.precompile_provider.clone()),
+        )
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::{Address, U256};
     use reth_chainspec::{ChainSpecBuilder, MAINNET};
 
     #[test]
@@ -62,8 +125,9 @@ mod tests {
                 .build()
         );
         
-        let factory = AndeBlockExecutorFactory::new(chain_spec.clone());
+        let factory = AndeBlockExecutorFactory::new(chain_spec.clone(), AndePrecompileConfig::default());
         assert_eq!(factory.chain_spec().chain, chain_spec.chain);
+        assert!(factory.precompile_provider().config().denies_all_callers());
     }
 
     #[test]
@@ -75,10 +139,30 @@ mod tests {
                 .build()
         );
         
-        let factory = AndeBlockExecutorFactory::new(chain_spec);
+        let factory = AndeBlockExecutorFactory::new(chain_spec, AndePrecompileConfig::default());
         let provider = factory.precompile_provider();
         
         // Provider should be available
         assert!(Arc::strong_count(provider) >= 1);
     }
+
+    #[test]
+    fn test_factory_from_evm_config() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(Default::default())
+                .build()
+        );
+
+        let config = AndePrecompileConfig::insecure_unrestricted();
+        let factory = AndeBlockExecutorFactory::new(chain_spec, config.clone());
+        factory.on_block_start(7, std::iter::empty());
+        factory.transfer_ledger().reserve(&config, 7, Address::ZERO, U256::from(5)).unwrap();
+
+        // The derived factory runs the same precompiles with a ledger of its own
+        let derived = AndeBlockExecutorFactory::from_evm_config(&factory.evm_config());
+        assert!(Arc::ptr_eq(derived.precompile_provider(), factory.precompile_provider()));
+        assert!(derived.transfer_ledger().transferred().is_zero());
+    }
 }
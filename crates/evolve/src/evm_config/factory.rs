@@ -7,11 +7,28 @@ use reth_chainspec::ChainSpec;
 use reth_evm_ethereum::EthEvmConfig;
 use std::sync::Arc;
 
-use super::wrapper::AndeEvmConfig;
+use super::{wrapper::AndeEvmConfig, AndeEvmFactory, AndePrecompileConfig, AndePrecompileProvider};
 use crate::evm_config::ANDE_PRECOMPILE_ADDRESS;
+use revm::primitives::hardfork::SpecId;
 
-pub fn create_ande_evm_config(chain_spec: Arc<ChainSpec>) -> AndeEvmConfig {
-    EthEvmConfig::new(chain_spec)
+/// Create the EVM config of `chain_spec`, whose ANDE precompile accepts the
+/// callers authorized by `precompile_config`
+pub fn create_ande_evm_config(
+    chain_spec: Arc<ChainSpec>,
+    precompile_config: AndePrecompileConfig,
+) -> AndeEvmConfig {
+    // TODO: Get actual spec from chain_spec hardfork schedule
+    let provider = AndePrecompileProvider::new(SpecId::CANCUN, precompile_config);
+    create_ande_evm_config_with_provider(chain_spec, provider)
+}
+
+/// Create the EVM config of `chain_spec`, whose EVMs serve the precompiles
+/// registered in `provider`
+pub fn create_ande_evm_config_with_provider(
+    chain_spec: Arc<ChainSpec>,
+    provider: AndePrecompileProvider,
+) -> AndeEvmConfig {
+    EthEvmConfig::new_with_evm_factory(chain_spec, AndeEvmFactory::with_precompile_provider(Arc::new(provider)))
 }
 
 pub const fn ande_precompile_address() -> Address {
@@ -22,6 +39,7 @@ pub const fn ande_precompile_address() -> Address {
 mod tests {
     use super::*;
     use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_evm::ConfigureEvm;
 
     #[test]
     fn test_ande_precompile_address() {
@@ -36,7 +54,12 @@ mod tests {
                 .genesis(Default::default())
                 .build()
         );
-        let config = create_ande_evm_config(chain_spec);
+        let mut precompile_config = AndePrecompileConfig::default();
+        precompile_config.set_ande_token_address(Address::repeat_byte(0x01));
+
+        let config = create_ande_evm_config(chain_spec, precompile_config);
         assert_eq!(config.chain_spec().chain, MAINNET.chain);
+        let provider = config.evm_factory().precompile_provider();
+        assert_eq!(provider.config().ande_token_address, Address::repeat_byte(0x01));
     }
 }
\ No newline at end of file
@@ -5,24 +5,31 @@
 //! the EVM is created but before execution begins.
 
 use super::ande_precompile_provider::AndePrecompileProvider;
+use super::precompile_config::AndePrecompileConfig;
 use super::ANDE_PRECOMPILE_ADDRESS;
 use revm::primitives::hardfork::SpecId;
 
-/// Inject ANDE precompile into an existing precompile provider
+/// Create a precompile provider serving the default Evolve precompiles, whose
+/// ANDE precompile accepts the callers authorized by `precompile_config`
 ///
-/// This function can be called after creating an EVM to add the ANDE precompile
-/// to the existing precompile set.
+/// Further precompiles can be registered on the returned provider with
+/// [`AndePrecompileProvider::with_precompile`].
 ///
 /// # Example
 /// ```ignore
-/// let mut evm = evm_config.evm_for_block(...);
-/// inject_ande_precompile(&mut evm.handler.pre_execution().precompiles, spec_id);
+/// let provider = create_ande_precompile_provider(SpecId::CANCUN, precompile_config)
+///     .with_precompile(STAKING_INFO_ADDRESS, AndePrecompile::Stateless(staking_info));
+/// let factory = AndeBlockExecutorFactory::with_precompile_provider(chain_spec, provider);
 /// ```
-pub fn create_ande_precompile_provider(spec_id: SpecId) -> AndePrecompileProvider {
-    AndePrecompileProvider::new(spec_id)
+pub fn create_ande_precompile_provider(
+    spec_id: SpecId,
+    precompile_config: AndePrecompileConfig,
+) -> AndePrecompileProvider {
+    AndePrecompileProvider::new(spec_id, precompile_config)
 }
 
-/// Get the ANDE precompile address
+/// Get the address of the ANDE Token Duality precompile, one of the
+/// [default Evolve precompiles](super::default_ande_precompiles)
 pub const fn ande_precompile_address() -> alloy_primitives::Address {
     ANDE_PRECOMPILE_ADDRESS
 }
@@ -33,8 +40,9 @@ mod tests {
 
     #[test]
     fn test_create_ande_precompile_provider() {
-        let _provider = create_ande_precompile_provider(SpecId::CANCUN);
-        // Provider created successfully
+        let provider = create_ande_precompile_provider(SpecId::CANCUN, AndePrecompileConfig::default());
+        let defaults = Vec::from(super::super::default_ande_precompiles().map(|(address, _)| address));
+        assert_eq!(provider.addresses().collect::<Vec<_>>(), defaults);
     }
 
     #[test]
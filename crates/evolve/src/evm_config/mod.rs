@@ -1,16 +1,21 @@
 //! Evolve-specific EVM configuration with custom precompiles
 //!
-//! This module provides the ANDE Token Duality precompile that will be
-//! injected into the EVM at runtime during block execution.
+//! This module provides the ANDE Token Duality and consensus validator-set
+//! precompiles, which [`AndeEvmFactory`] injects into every EVM built by
+//! [`AndeEvmConfig`].
 
 pub mod precompile;
 pub mod precompile_config;
 pub mod precompile_inspector;
+pub mod transfer_ledger;
 pub mod ande_precompile_provider;
+pub mod ande_evm_factory;
 pub mod factory;
+pub mod builder;
 pub mod wrapper;
 pub mod injection;
 pub mod executor_factory;
+pub mod validator_set;
 
 #[cfg(test)]
 mod integration_test;
@@ -18,13 +23,17 @@ mod integration_test;
 #[cfg(test)]
 mod e2e_test;
 
-pub use precompile::{
-    ande_token_duality_precompile, ANDE_PRECOMPILE_ADDRESS,
-};
+pub use precompile::{AndePrecompileCall, AndePrecompileError, ANDE_PRECOMPILE_ADDRESS};
 pub use precompile_config::AndePrecompileConfig;
 pub use precompile_inspector::AndePrecompileInspector;
-pub use ande_precompile_provider::AndePrecompileProvider;
+pub use transfer_ledger::{BlockTransferLedger, TransferReservation};
+pub use ande_precompile_provider::{default_ande_precompiles, AndePrecompile, AndePrecompileProvider};
+pub use ande_evm_factory::AndeEvmFactory;
 pub use wrapper::AndeEvmConfig;
-pub use factory::create_ande_evm_config;
+pub use factory::{create_ande_evm_config, create_ande_evm_config_with_provider};
+pub use builder::{AndeEvmConfigBuilder, AndeEvmConfigError, NetworkProfile};
 pub use injection::{create_ande_precompile_provider, ande_precompile_address};
 pub use executor_factory::AndeBlockExecutorFactory;
+pub use validator_set::{
+    SharedValidatorSnapshot, ValidatorSetReader, ValidatorSnapshot, VALIDATOR_SET_PRECOMPILE_ADDRESS,
+};
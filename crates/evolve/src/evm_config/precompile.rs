@@ -11,16 +11,47 @@
 //! - **Per-call caps**: Maximum transfer amount per transaction
 //! - **Per-block caps**: Maximum total transfers per block
 //! - **Inspector pattern**: Deep integration with EVM context for state validation
+//! - **Journaled transfers**: Balances move through the EVM journal, so a
+//!   reverting caller undoes the transfer
+//!
+//! ## Entry points
+//!
+//! - `transfer(address,address,uint256)`: moves native balance, returns `true`
+//! - `balanceOf(address)`: returns the native balance as `uint256`
+//!
+//! Outputs are ABI-encoded, so Solidity callers can decode them directly.
+//!
+//! ## Logs
+//!
+//! Unless disabled by
+//! [`emit_transfer_logs`](super::AndePrecompileConfig::emit_transfer_logs),
+//! every transfer logs the ERC-20 `Transfer(address indexed from, address
+//! indexed to, uint256 value)` event from the precompile address, so explorers
+//! and indexers pick up duality transfers like token transfers. The log is
+//! dropped along with the transfer if an enclosing call reverts.
+//!
+//! ## Gas
+//!
+//! | Call | Gas |
+//! |------|-----|
+//! | `transfer` | [`TRANSFER_BASE_GAS`] + access of `from` + access of `to` + [`NEW_ACCOUNT_GAS`] if `to` doesn't exist |
+//! | `transfer` of zero | [`TRANSFER_BASE_GAS`], no account is accessed |
+//! | `balanceOf` | [`BALANCE_OF_BASE_GAS`] + access of `account` |
+//!
+//! Transfers emitting their log also pay [`TRANSFER_LOG_GAS`], the cost of the
+//! equivalent `LOG3`.
+//!
+//! As in EIP-2929, accessing an account costs [`COLD_ACCOUNT_ACCESS_GAS`] the
+//! first time in a transaction and [`WARM_ACCOUNT_ACCESS_GAS`] afterwards.
 //!
 //! For configuration, see [`AndePrecompileConfig`](super::precompile_config::AndePrecompileConfig)
 //! For runtime validation, see [`AndePrecompileInspector`](super::precompile_inspector::AndePrecompileInspector)
 //!
 //! **Address:** 0x00000000000000000000000000000000000000fd
 
-use alloy_primitives::{Address, Bytes, U256};
-use revm_precompile::{
-    Precompile, PrecompileError, PrecompileId, PrecompileOutput, PrecompileResult,
-};
+use alloy_primitives::{b256, Address, Bytes, Log, B256, U256};
+use revm_context_interface::{journaled_state::TransferError, ContextTr, JournalTr};
+use revm_precompile::{PrecompileError, PrecompileOutput, PrecompileResult};
 use std::fmt;
 
 /// ANDE Token Duality Precompile Address: 0x00..fd
@@ -29,26 +60,101 @@ pub const ANDE_PRECOMPILE_ADDRESS: Address = Address::new([
     0x00, 0x00, 0x00, 0xfd,
 ]);
 
-/// Address of the ANDEToken contract authorized to call this precompile
-/// TODO: This should be configured via genesis or chain spec
-/// For now, using a placeholder that will be updated during deployment
-pub const ANDE_TOKEN_ADDRESS: Address = Address::ZERO; // Will be set in genesis
+/// Base gas of a transfer, before its account accesses
+pub const TRANSFER_BASE_GAS: u64 = 3_000;
+
+/// Base gas of a `balanceOf` call, before its account access
+pub const BALANCE_OF_BASE_GAS: u64 = 100;
+
+/// Gas of the first access to an account in a transaction (EIP-2929)
+pub const COLD_ACCOUNT_ACCESS_GAS: u64 = 2_600;
+
+/// Gas of later accesses to an account in a transaction (EIP-2929)
+pub const WARM_ACCOUNT_ACCESS_GAS: u64 = 100;
+
+/// Surcharge of a transfer creating its recipient, as for a value-bearing `CALL`
+pub const NEW_ACCOUNT_GAS: u64 = 25_000;
+
+/// Gas of the `Transfer` log: `LOG3` with a 32-byte value
+pub const TRANSFER_LOG_GAS: u64 = 375 + 3 * 375 + 8 * 32;
+
+/// Topic of the ERC-20 `Transfer(address,address,uint256)` event
+pub const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Selector of `transfer(address from, address to, uint256 value) returns (bool)`
+pub const TRANSFER_SELECTOR: [u8; 4] = [0xbe, 0xab, 0xac, 0xc8];
 
-/// Minimum gas cost for the ANDE precompile
-const ANDE_PRECOMPILE_BASE_GAS: u64 = 3000;
+/// Selector of `balanceOf(address account) returns (uint256)`
+pub const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
 
-/// Gas cost per 32 bytes of input
-const ANDE_PRECOMPILE_PER_WORD_GAS: u64 = 100;
+/// Length of the legacy transfer input: `abi.encode(from, to, value)` without a selector
+pub const LEGACY_TRANSFER_INPUT_LEN: usize = 96;
+
+/// Call to the ANDE precompile, decoded from its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AndePrecompileCall {
+    /// Move `value` of native balance from `from` to `to`
+    Transfer {
+        /// Account debited
+        from: Address,
+        /// Account credited
+        to: Address,
+        /// Amount transferred
+        value: U256,
+    },
+    /// Read the native balance of `account`
+    BalanceOf(Address),
+}
+
+impl AndePrecompileCall {
+    /// Decode the input of a call
+    ///
+    /// Besides the selector-prefixed calls, the unprefixed 96-byte transfer
+    /// payload is accepted for backward compatibility. It is deprecated and
+    /// will be rejected in the next release.
+    pub fn decode(input: &[u8]) -> Result<Self, AndePrecompileError> {
+        if input.len() == LEGACY_TRANSFER_INPUT_LEN {
+            return Ok(Self::decode_transfer(input));
+        }
+
+        let selector: [u8; 4] = input
+            .get(..4)
+            .and_then(|selector| selector.try_into().ok())
+            .ok_or(AndePrecompileError::InvalidInputLength(input.len()))?;
+        let args = &input[4..];
+        match selector {
+            TRANSFER_SELECTOR if args.len() == 96 => Ok(Self::decode_transfer(args)),
+            BALANCE_OF_SELECTOR if args.len() == 32 => Ok(Self::BalanceOf(Address::from_slice(&args[12..32]))),
+            TRANSFER_SELECTOR | BALANCE_OF_SELECTOR => Err(AndePrecompileError::InvalidInputLength(input.len())),
+            selector => Err(AndePrecompileError::UnknownSelector(selector)),
+        }
+    }
+
+    /// Decode `abi.encode(from, to, value)`
+    fn decode_transfer(args: &[u8]) -> Self {
+        // Each parameter is 32 bytes (left-padded for addresses)
+        Self::Transfer {
+            from: Address::from_slice(&args[12..32]), // Last 20 bytes of first word
+            to: Address::from_slice(&args[44..64]), // Last 20 bytes of second word
+            value: U256::from_be_slice(&args[64..96]), // Third word
+        }
+    }
+}
 
 /// Custom error types for the ANDE precompile
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AndePrecompileError {
     /// Caller is not the authorized ANDEToken contract
     UnauthorizedCaller(Address),
-    /// Invalid input length (must be exactly 96 bytes: from, to, value)
+    /// Input length doesn't match any call
     InvalidInputLength(usize),
+    /// Input starts with a selector the precompile doesn't implement
+    UnknownSelector([u8; 4]),
     /// Transfer to zero address is not allowed
     TransferToZeroAddress,
+    /// Transfer attempted within a static call
+    StaticCall,
     /// Insufficient balance for transfer
     InsufficientBalance {
         /// Account with insufficient balance
@@ -58,6 +164,10 @@ pub enum AndePrecompileError {
         /// Available balance
         available: U256,
     },
+    /// Crediting the recipient would overflow its balance
+    BalanceOverflow(Address),
+    /// The state database failed while loading an account
+    Database(String),
 }
 
 impl fmt::Display for AndePrecompileError {
@@ -67,11 +177,17 @@ impl fmt::Display for AndePrecompileError {
                 write!(f, "Unauthorized caller: {:?}", caller)
             }
             Self::InvalidInputLength(len) => {
-                write!(f, "Invalid input length: {} (expected 96)", len)
+                write!(f, "Invalid input length: {}", len)
+            }
+            Self::UnknownSelector(selector) => {
+                write!(f, "Unknown selector: 0x{}", alloy_primitives::hex::encode(selector))
             }
             Self::TransferToZeroAddress => {
                 write!(f, "Transfer to zero address")
             }
+            Self::StaticCall => {
+                write!(f, "Cannot modify state in static call")
+            }
             Self::InsufficientBalance {
                 account,
                 required,
@@ -83,108 +199,238 @@ impl fmt::Display for AndePrecompileError {
                     account, required, available
                 )
             }
+            Self::BalanceOverflow(account) => {
+                write!(f, "Balance overflow for {:?}", account)
+            }
+            Self::Database(err) => {
+                write!(f, "Database error: {}", err)
+            }
         }
     }
 }
 
 impl std::error::Error for AndePrecompileError {}
 
-/// Creates the ANDE Token Duality precompile
-pub fn ande_token_duality_precompile() -> Precompile {
-    Precompile::new(
-        PrecompileId::custom("ANDE"),
-        ANDE_PRECOMPILE_ADDRESS,
-        ande_token_duality_run,
-    )
+impl From<AndePrecompileError> for PrecompileError {
+    fn from(err: AndePrecompileError) -> Self {
+        match err {
+            // The state can't be trusted anymore, abort the transaction
+            AndePrecompileError::Database(err) => PrecompileError::Fatal(err),
+            err => PrecompileError::Other(err.to_string()),
+        }
+    }
+}
+
+/// Gas used by a call, which runs out once it exceeds the call's limit
+#[derive(Debug)]
+struct GasMeter {
+    used: u64,
+    limit: u64,
+}
+
+impl GasMeter {
+    const fn new(limit: u64) -> Self {
+        Self { used: 0, limit }
+    }
+
+    /// Charge `cost`, failing if the limit is exceeded
+    fn charge(&mut self, cost: u64) -> Result<(), PrecompileError> {
+        self.used = self.used.saturating_add(cost);
+        if self.used > self.limit {
+            return Err(PrecompileError::OutOfGas);
+        }
+        Ok(())
+    }
+}
+
+/// Gas of an account access, depending on whether it is the first in the transaction
+const fn account_access_gas(is_cold: bool) -> u64 {
+    if is_cold {
+        COLD_ACCOUNT_ACCESS_GAS
+    } else {
+        WARM_ACCOUNT_ACCESS_GAS
+    }
+}
+
+/// ABI encoding of `true`
+fn abi_true() -> Bytes {
+    Bytes::from(U256::from(1).to_be_bytes::<32>())
 }
 
 /// Main execution function for the ANDE Token Duality precompile
 ///
-/// # Input Format (96 bytes total)
-/// - Bytes 0-31: `from` address (32 bytes, address in last 20 bytes)
-/// - Bytes 32-63: `to` address (32 bytes, address in last 20 bytes)
-/// - Bytes 64-95: `value` amount (32 bytes, uint256)
+/// Runs inside the EVM through
+/// [`AndePrecompileProvider`](super::ande_precompile_provider::AndePrecompileProvider),
+/// which hands it the context of the call, so the transfer goes through the
+/// journal and is undone if an enclosing call reverts.
+///
+/// # Input Format
+/// - `transfer(address from, address to, uint256 value)`: selector followed by
+///   the ABI-encoded arguments, returns ABI-encoded `true`
+/// - `balanceOf(address account)`: selector followed by the ABI-encoded
+///   account, returns its native balance as `uint256`
+/// - Legacy: `abi.encode(from, to, value)` without a selector (96 bytes),
+///   handled like `transfer`
 ///
 /// # Returns
-/// - PrecompileOutput with gas used and output bytes
+/// - PrecompileOutput with gas used, following the [gas schedule](self#gas),
+///   and output bytes
+/// - `PrecompileError::Fatal` if the state database failed, aborting the transaction
 ///
 /// # Security
-/// - Only callable by ANDEToken contract
+/// - Caller authorization is enforced by the provider against its
+///   [`AndePrecompileConfig`](super::AndePrecompileConfig), caps by
+///   [`AndePrecompileInspector`](super::AndePrecompileInspector)
 /// - Validates sufficient balance
 /// - Prevents transfer to address(0)
-fn ande_token_duality_run(input: &[u8], gas_limit: u64) -> PrecompileResult {
-    // Calculate gas cost
-    let input_len = input.len() as u64;
-    let words = (input_len + 31) / 32;
-    let gas_cost = ANDE_PRECOMPILE_BASE_GAS + (ANDE_PRECOMPILE_PER_WORD_GAS * words);
-
-    if gas_limit < gas_cost {
-        return Err(PrecompileError::OutOfGas);
-    }
-
-    // NOTE: Caller validation is now handled by AndePrecompileInspector
-    // The Inspector has full access to the EVM context and validates:
-    // - Caller authorization via allow-list
-    // - Per-call transfer caps
-    // - Per-block transfer caps
-    //
-    // This precompile function focuses on the core transfer logic,
-    // while the Inspector handles all security validations before this function is called.
-
-    // Validate input length (must be exactly 96 bytes: 3 x 32-byte words)
-    if input.len() != 96 {
-        return Err(PrecompileError::Other(format!(
-            "Invalid input length: {} (expected 96)",
-            input.len()
-        )));
-    }
-
-    // Decode parameters from input
-    // Input format: abi.encode(from, to, value)
-    // Each parameter is 32 bytes (left-padded for addresses)
-    let _from = Address::from_slice(&input[12..32]); // Last 20 bytes of first word
-    let to = Address::from_slice(&input[44..64]); // Last 20 bytes of second word
-    let value = U256::from_be_slice(&input[64..96]); // Third word
+///
+/// If `emit_transfer_logs`, transfers log the ERC-20 `Transfer` event, see
+/// [Logs](self#logs).
+pub(crate) fn ande_token_duality_run<CTX: ContextTr>(
+    context: &mut CTX,
+    input: &[u8],
+    gas_limit: u64,
+    is_static: bool,
+    emit_transfer_logs: bool,
+) -> PrecompileResult {
+    let mut gas = GasMeter::new(gas_limit);
+    let (from, to, value) = match AndePrecompileCall::decode(input)? {
+        AndePrecompileCall::Transfer { from, to, value } => (from, to, value),
+        AndePrecompileCall::BalanceOf(account) => {
+            gas.charge(BALANCE_OF_BASE_GAS)?;
+            let account = context
+                .journal_mut()
+                .load_account(account)
+                .map_err(|err| AndePrecompileError::Database(err.to_string()))?;
+            gas.charge(account_access_gas(account.is_cold))?;
+            let balance = account.data.info.balance;
+            return Ok(PrecompileOutput::new(gas.used, Bytes::from(balance.to_be_bytes::<32>())));
+        }
+    };
+    gas.charge(TRANSFER_BASE_GAS)?;
+
+    if is_static {
+        return Err(AndePrecompileError::StaticCall.into());
+    }
 
     // Validate: no transfer to zero address
     if to == Address::ZERO {
-        return Err(PrecompileError::Other(
-            "Transfer to zero address".to_string(),
-        ));
-    }
-
-    // Gas saving optimization: return early for zero transfers
-    if value.is_zero() {
-        return Ok(PrecompileOutput::new(gas_cost, Bytes::from(vec![0x01])));
-    }
-
-    // NOTE: Balance validation and transfer execution are currently stubbed out
-    // because they require access to the EVM state database, which is not available
-    // in the precompile function signature.
-    //
-    // In a production implementation, this would be handled by:
-    // 1. Reading from_balance from state: db.basic(from)?.balance
-    // 2. Validating sufficient balance
-    // 3. Updating balances in state:
-    //    - db.balance_sub(from, value)
-    //    - db.balance_add(to, value)
-    //
-    // For now, we return success assuming the balance checks will be enforced
-    // by the ANDEToken contract before calling this precompile.
-
-    // TODO: Implement actual balance transfer when we have EVM context access
-    // This will require restructuring the precompile to work with the EVM database
-
-    // Return success
-    Ok(PrecompileOutput::new(gas_cost, Bytes::from(vec![0x01])))
+        return Err(AndePrecompileError::TransferToZeroAddress.into());
+    }
+
+    // Zero transfers touch no account
+    if !value.is_zero() {
+        tracing::debug!(?from, ?to, ?value, "ANDE native transfer");
+        transfer(context.journal_mut(), from, to, value, &mut gas)?;
+    }
+
+    if emit_transfer_logs {
+        gas.charge(TRANSFER_LOG_GAS)?;
+        context.journal_mut().log(transfer_log(from, to, value));
+    }
+
+    Ok(PrecompileOutput::new(gas.used, abi_true()))
 }
 
-// NOTE: This function will be used for runtime injection in the payload builder
-// The precompile will be added to the EVM's handler.pre_execution.load_precompiles hook
+/// ERC-20 `Transfer` log of a duality transfer, emitted by the precompile
+pub fn transfer_log(from: Address, to: Address, value: U256) -> Log {
+    Log::new_unchecked(
+        ANDE_PRECOMPILE_ADDRESS,
+        vec![TRANSFER_EVENT_TOPIC, from.into_word(), to.into_word()],
+        Bytes::from(value.to_be_bytes::<32>()),
+    )
+}
+
+/// `(from, to, value)` of a duality transfer logged by [`transfer_log`], or
+/// `None` if `log` is another log
+pub fn decode_transfer_log(log: &Log) -> Option<(Address, Address, U256)> {
+    let [topic, from, to] = log.topics() else {
+        return None;
+    };
+    if log.address != ANDE_PRECOMPILE_ADDRESS || *topic != TRANSFER_EVENT_TOPIC || log.data.data.len() != 32 {
+        return None;
+    }
+    Some((Address::from_word(*from), Address::from_word(*to), U256::from_be_slice(&log.data.data)))
+}
+
+/// Move `value` from `from` to `to` through the journal, charging the accounts' access to `gas`
+fn transfer<J: JournalTr>(
+    journal: &mut J,
+    from: Address,
+    to: Address,
+    value: U256,
+    gas: &mut GasMeter,
+) -> Result<(), PrecompileError> {
+    let from_account = journal.load_account(from).map_err(|err| AndePrecompileError::Database(err.to_string()))?;
+    gas.charge(account_access_gas(from_account.is_cold))?;
+    let available = from_account.data.info.balance;
+
+    let to_account = journal.load_account(to).map_err(|err| AndePrecompileError::Database(err.to_string()))?;
+    gas.charge(account_access_gas(to_account.is_cold))?;
+    // The state grows by the recipient
+    if to_account.data.info.is_empty() {
+        gas.charge(NEW_ACCOUNT_GAS)?;
+    }
+
+    if available < value {
+        return Err(AndePrecompileError::InsufficientBalance { account: from, required: value, available }.into());
+    }
+
+    // Journaled debit and credit, unwound with the enclosing checkpoint on revert
+    let err = match journal.transfer(from, to, value) {
+        Ok(None) => return Ok(()),
+        Ok(Some(TransferError::OutOfFunds)) => {
+            AndePrecompileError::InsufficientBalance { account: from, required: value, available }
+        }
+        Ok(Some(_)) => AndePrecompileError::BalanceOverflow(to),
+        Err(err) => AndePrecompileError::Database(err.to_string()),
+    };
+    Err(err.into())
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use revm::{
+        context::Context,
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+        MainContext,
+    };
+
+    /// Gas limit of the calls, enough for any of them
+    const GAS_LIMIT: u64 = 100_000;
+
+    /// Context over an empty database in which `funded` holds `balance`
+    fn context_with_balance(funded: Address, balance: U256) -> impl ContextTr {
+        context_with_balances(&[(funded, balance)])
+    }
+
+    /// Context over an empty database holding the given balances
+    fn context_with_balances(balances: &[(Address, U256)]) -> impl ContextTr {
+        let mut db = CacheDB::new(EmptyDB::default());
+        for &(account, balance) in balances {
+            db.insert_account_info(account, AccountInfo { balance, ..Default::default() });
+        }
+        Context::mainnet().with_db(db)
+    }
+
+    fn balance_of(context: &mut impl ContextTr, account: Address) -> U256 {
+        context.journal_mut().load_account(account).unwrap().data.info.balance
+    }
+
+    fn balance_of_input(account: Address) -> Vec<u8> {
+        [&BALANCE_OF_SELECTOR[..], account.into_word().as_slice()].concat()
+    }
+
+    /// Legacy transfer payload without a selector, also the arguments of `transfer`
+    fn transfer_input(from: Address, to: Address, value: U256) -> Vec<u8> {
+        let mut input = Vec::with_capacity(96);
+        input.extend_from_slice(from.into_word().as_slice());
+        input.extend_from_slice(to.into_word().as_slice());
+        input.extend_from_slice(&value.to_be_bytes::<32>());
+        input
+    }
 
     #[test]
     fn test_ande_precompile_address() {
@@ -199,84 +445,222 @@ mod tests {
 
     #[test]
     fn test_invalid_input_length() {
-        let input = vec![0u8; 32]; // Only 32 bytes instead of 96
-        let result = ande_token_duality_run(&input, 10000);
-        assert!(result.is_err());
+        let mut context = context_with_balance(Address::ZERO, U256::ZERO);
+        let input = vec![0u8; 3]; // Not even a selector
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
+        assert_eq!(result.unwrap_err(), AndePrecompileError::InvalidInputLength(3).into());
+
+        // Known selector, truncated arguments
+        let input = [&TRANSFER_SELECTOR[..], &[0u8; 64][..]].concat();
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
+        assert_eq!(result.unwrap_err(), AndePrecompileError::InvalidInputLength(68).into());
     }
 
     #[test]
-    fn test_zero_transfer() {
-        // Prepare input: from, to, value=0
-        let mut input = vec![0u8; 96];
+    fn test_unknown_selector() {
+        let mut context = context_with_balance(Address::ZERO, U256::ZERO);
+        // `transferFrom(address,address,uint256)` with transfer-shaped arguments
+        let input = [&[0x23, 0xb8, 0x72, 0xdd][..], &[0u8; 96][..]].concat();
+
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
+
+        assert_eq!(result.unwrap_err(), AndePrecompileError::UnknownSelector([0x23, 0xb8, 0x72, 0xdd]).into());
+    }
+
+    #[test]
+    fn test_transfer_selector() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let mut context = context_with_balance(from, U256::from(5000));
+        let input = [&TRANSFER_SELECTOR[..], &transfer_input(from, to, U256::from(1000))[..]].concat();
+        assert_eq!(
+            AndePrecompileCall::decode(&input),
+            Ok(AndePrecompileCall::Transfer { from, to, value: U256::from(1000) })
+        );
 
-        // from address (last 20 bytes of first 32)
-        input[12..32].copy_from_slice(&[0x01; 20]);
+        let output = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false).unwrap();
 
-        // to address (last 20 bytes of second 32)
-        input[44..64].copy_from_slice(&[0x02; 20]);
+        // ABI-encoded `true`
+        assert_eq!(output.bytes.len(), 32);
+        assert_eq!(U256::from_be_slice(&output.bytes), U256::from(1));
+        assert_eq!(balance_of(&mut context, from), U256::from(4000));
+        assert_eq!(balance_of(&mut context, to), U256::from(1000));
+    }
 
-        // value = 0 (third 32 bytes are already 0)
+    #[test]
+    fn test_balance_of_selector() {
+        let holder = Address::repeat_byte(0x01);
+        let mut context = context_with_balance(holder, U256::from(5000));
+        // Reading a balance is allowed in static calls
+        let output = ande_token_duality_run(&mut context, &balance_of_input(holder), GAS_LIMIT, true, false).unwrap();
+        assert_eq!(output.bytes, Bytes::from(U256::from(5000).to_be_bytes::<32>()));
+
+        let input = balance_of_input(Address::repeat_byte(0x02));
+        let output = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false).unwrap();
+        assert_eq!(U256::from_be_slice(&output.bytes), U256::ZERO);
+    }
 
-        let result = ande_token_duality_run(&input, 10000);
+    #[test]
+    fn test_zero_transfer() {
+        let mut context = context_with_balance(Address::ZERO, U256::ZERO);
+        let input = transfer_input(Address::repeat_byte(0x01), Address::repeat_byte(0x02), U256::ZERO);
+
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
 
         assert!(result.is_ok());
         let output = result.unwrap();
-        assert!(output.gas_used > 0);
-        assert_eq!(output.bytes, Bytes::from(vec![0x01]));
+        assert_eq!(output.gas_used, TRANSFER_BASE_GAS);
+        assert_eq!(output.bytes, abi_true());
         assert!(!output.reverted);
     }
 
     #[test]
     fn test_transfer_to_zero_address() {
-        // Prepare input: from, to=0x0, value=100
-        let mut input = vec![0u8; 96];
+        let from = Address::repeat_byte(0x01);
+        let mut context = context_with_balance(from, U256::from(1000));
+        let input = transfer_input(from, Address::ZERO, U256::from(100));
 
-        // from address (last 20 bytes of first 32)
-        input[12..32].copy_from_slice(&[0x01; 20]);
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
 
-        // to address = 0x0 (already zeros)
+        assert_eq!(result.unwrap_err(), AndePrecompileError::TransferToZeroAddress.into());
+        assert_eq!(balance_of(&mut context, from), U256::from(1000));
+    }
 
-        // value = 100
-        input[95] = 100;
+    #[test]
+    fn test_successful_transfer() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let mut context = context_with_balance(from, U256::from(5000));
+        let input = transfer_input(from, to, U256::from(1000));
 
-        let result = ande_token_duality_run(&input, 10000);
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
 
-        assert!(result.is_err());
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        // Both accounts are cold, the recipient is created
+        assert_eq!(output.gas_used, TRANSFER_BASE_GAS + 2 * COLD_ACCOUNT_ACCESS_GAS + NEW_ACCOUNT_GAS);
+        assert_eq!(output.bytes, abi_true());
+        assert!(!output.reverted);
+        assert_eq!(balance_of(&mut context, from), U256::from(4000));
+        assert_eq!(balance_of(&mut context, to), U256::from(1000));
     }
 
     #[test]
-    fn test_successful_transfer() {
-        // Prepare input: from=0x01.., to=0x02.., value=1000
-        let mut input = vec![0u8; 96];
+    fn test_insufficient_balance() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let mut context = context_with_balance(from, U256::from(999));
+        let input = transfer_input(from, to, U256::from(1000));
+
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false);
+
+        let expected = AndePrecompileError::InsufficientBalance {
+            account: from,
+            required: U256::from(1000),
+            available: U256::from(999),
+        };
+        assert_eq!(result.unwrap_err(), expected.into());
+        assert_eq!(balance_of(&mut context, from), U256::from(999));
+        assert_eq!(balance_of(&mut context, to), U256::ZERO);
+    }
 
-        // from address (last 20 bytes of first 32)
-        input[12..32].copy_from_slice(&[0x01; 20]);
+    #[test]
+    fn test_static_call_rejected() {
+        let from = Address::repeat_byte(0x01);
+        let mut context = context_with_balance(from, U256::from(1000));
+        let input = transfer_input(from, Address::repeat_byte(0x02), U256::from(100));
 
-        // to address (last 20 bytes of second 32)
-        input[44..64].copy_from_slice(&[0x02; 20]);
+        let result = ande_token_duality_run(&mut context, &input, GAS_LIMIT, true, false);
 
-        // value = 1000 (0x03E8)
-        input[94] = 0x03;
-        input[95] = 0xE8;
+        assert_eq!(result.unwrap_err(), AndePrecompileError::StaticCall.into());
+        assert_eq!(balance_of(&mut context, from), U256::from(1000));
+    }
 
-        let result = ande_token_duality_run(&input, 10000);
+    #[test]
+    fn test_out_of_gas() {
+        let mut context = context_with_balance(Address::ZERO, U256::ZERO);
+        let input = transfer_input(Address::repeat_byte(0x01), Address::repeat_byte(0x02), U256::from(100));
 
-        assert!(result.is_ok());
-        let output = result.unwrap();
-        assert!(output.gas_used > ANDE_PRECOMPILE_BASE_GAS);
-        assert_eq!(output.bytes, Bytes::from(vec![0x01]));
-        assert!(!output.reverted);
+        let result = ande_token_duality_run(&mut context, &input, 100, false, false); // Insufficient gas
+
+        assert!(matches!(result, Err(PrecompileError::OutOfGas)));
     }
 
     #[test]
-    fn test_out_of_gas() {
-        let mut input = vec![0u8; 96];
-        input[12..32].copy_from_slice(&[0x01; 20]);
-        input[44..64].copy_from_slice(&[0x02; 20]);
-        input[95] = 100;
+    fn test_transfer_logs() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let mut context = context_with_balances(&[(from, U256::from(5000)), (to, U256::from(1))]);
+        assert_eq!(TRANSFER_EVENT_TOPIC, alloy_primitives::keccak256("Transfer(address,address,uint256)"));
+
+        let input = transfer_input(from, to, U256::from(1000));
+        let output = ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, true).unwrap();
+        assert_eq!(output.gas_used, TRANSFER_BASE_GAS + 2 * COLD_ACCOUNT_ACCESS_GAS + TRANSFER_LOG_GAS);
+        let logs = context.journal_mut().take_logs();
+        assert_eq!(logs, [transfer_log(from, to, U256::from(1000))]);
+        assert_eq!(logs[0].address, ANDE_PRECOMPILE_ADDRESS);
+        assert_eq!(logs[0].topics(), [TRANSFER_EVENT_TOPIC, from.into_word(), to.into_word()]);
+        assert_eq!(U256::from_be_slice(&logs[0].data.data), U256::from(1000));
+
+        // Zero transfers are logged as well, failed ones and reads are not
+        let zero = transfer_input(from, to, U256::ZERO);
+        ande_token_duality_run(&mut context, &zero, GAS_LIMIT, false, true).unwrap();
+        let too_much = transfer_input(from, to, U256::from(10_000));
+        ande_token_duality_run(&mut context, &too_much, GAS_LIMIT, false, true).unwrap_err();
+        ande_token_duality_run(&mut context, &balance_of_input(from), GAS_LIMIT, false, true).unwrap();
+        assert_eq!(context.journal_mut().take_logs(), [transfer_log(from, to, U256::ZERO)]);
+
+        // Disabled logs
+        ande_token_duality_run(&mut context, &input, GAS_LIMIT, false, false).unwrap();
+        assert!(context.journal_mut().take_logs().is_empty());
+    }
+
+    #[test]
+    fn test_gas_schedule() {
+        fn gas_used(context: &mut impl ContextTr, input: Vec<u8>) -> u64 {
+            ande_token_duality_run(context, &input, GAS_LIMIT, false, false).unwrap().gas_used
+        }
+
+        let (from, to, empty) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02), Address::repeat_byte(0x03));
+        let mut context = context_with_balances(&[(from, U256::from(1000)), (to, U256::from(1))]);
 
-        let result = ande_token_duality_run(&input, 100); // Insufficient gas
+        // Cold transfer to an existing account
+        let cold = gas_used(&mut context, transfer_input(from, to, U256::from(100)));
+        assert_eq!(cold, TRANSFER_BASE_GAS + 2 * COLD_ACCOUNT_ACCESS_GAS);
 
+        // Warm transfer, both accounts were accessed earlier in the transaction
+        let warm = gas_used(&mut context, transfer_input(from, to, U256::from(100)));
+        assert_eq!(warm, TRANSFER_BASE_GAS + 2 * WARM_ACCOUNT_ACCESS_GAS);
+
+        // Transfer creating its recipient
+        let new_account = gas_used(&mut context, transfer_input(from, empty, U256::from(100)));
+        assert_eq!(new_account, TRANSFER_BASE_GAS + WARM_ACCOUNT_ACCESS_GAS + COLD_ACCOUNT_ACCESS_GAS + NEW_ACCOUNT_GAS);
+
+        // Zero transfers access no account, not even an empty one
+        let zero = gas_used(&mut context, transfer_input(from, Address::repeat_byte(0x04), U256::ZERO));
+        assert_eq!(zero, TRANSFER_BASE_GAS);
+
+        // Reads are cheaper than transfers
+        let cold_read = gas_used(&mut context, balance_of_input(Address::repeat_byte(0x04)));
+        assert_eq!(cold_read, BALANCE_OF_BASE_GAS + COLD_ACCOUNT_ACCESS_GAS);
+        let warm_read = gas_used(&mut context, balance_of_input(from));
+        assert_eq!(warm_read, BALANCE_OF_BASE_GAS + WARM_ACCOUNT_ACCESS_GAS);
+
+        // Running out of gas on an account access fails the call
+        let input = transfer_input(from, Address::repeat_byte(0x05), U256::from(100));
+        let result = ande_token_duality_run(&mut context, &input, TRANSFER_BASE_GAS + WARM_ACCOUNT_ACCESS_GAS, false, false);
         assert!(matches!(result, Err(PrecompileError::OutOfGas)));
     }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let log = transfer_log(from, to, U256::from(300));
+        assert_eq!(decode_transfer_log(&log), Some((from, to, U256::from(300))));
+
+        // The same event logged by another contract, e.g. the ANDEToken
+        let mut token = log.clone();
+        token.address = Address::repeat_byte(0xaa);
+        assert_eq!(decode_transfer_log(&token), None);
+        // Other events of the precompile's address
+        let topics = vec![B256::repeat_byte(0x8c), from.into_word(), to.into_word()];
+        let approval = Log::new_unchecked(ANDE_PRECOMPILE_ADDRESS, topics, log.data.data.clone());
+        assert_eq!(decode_transfer_log(&approval), None);
+    }
 }
@@ -3,23 +3,28 @@
 //! This module provides secure configuration for the ANDE precompile with:
 //! - Allow-list of authorized callers
 //! - Per-call transfer caps
-//! - Per-block transfer caps
+//! - Per-block transfer caps, overall and per caller
+//! - `Transfer` logs of duality transfers
 //! - Environment-based configuration
 
 use alloy_primitives::{Address, U256};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::str::FromStr;
 
 /// Configuration for the ANDE Token Duality precompile
+///
+/// The default configuration authorizes no caller: the ANDEToken address or
+/// allow-list must be set for the precompile to accept calls.
 #[derive(Clone, Debug)]
 pub struct AndePrecompileConfig {
     /// The address of the ANDE Token Duality precompile (0x00..fd)
     pub precompile_address: Address,
     
-    /// Address of the ANDEToken contract authorized to call this precompile
+    /// Address of the ANDEToken contract authorized to call this precompile,
+    /// or zero if unset
     pub ande_token_address: Address,
     
-    /// A list of addresses that are allowed to call the precompile
+    /// Other addresses that are allowed to call the precompile
     /// This provides more flexibility than a single authorized address
     pub allow_list: HashSet<Address>,
     
@@ -29,22 +34,33 @@ pub struct AndePrecompileConfig {
     /// The maximum amount that can be transferred in a single block
     /// None means no block-level cap
     pub per_block_cap: Option<U256>,
+
+    /// The maximum amount a single caller can transfer in a block
+    /// None means no per-caller cap
+    pub per_caller_block_cap: Option<U256>,
     
     /// Enable/disable strict validation (useful for testing)
     pub strict_validation: bool,
+
+    /// Log the ERC-20 `Transfer` event of every transfer through the precompile
+    pub emit_transfer_logs: bool,
 }
 
 impl Default for AndePrecompileConfig {
     fn default() -> Self {
         Self {
             precompile_address: super::precompile::ANDE_PRECOMPILE_ADDRESS,
-            ande_token_address: Address::ZERO, // Will be set via config
+            // Set from the genesis config or `ANDE_TOKEN_ADDRESS`
+            ande_token_address: Address::ZERO,
             allow_list: HashSet::new(),
             // Default: 1 million ANDE tokens per call (with 18 decimals)
             per_call_cap: U256::from(1_000_000u64) * U256::from(10u64).pow(U256::from(18)),
             // Default: 10 million ANDE tokens per block
             per_block_cap: Some(U256::from(10_000_000u64) * U256::from(10u64).pow(U256::from(18))),
+            // Set from the genesis config or `ANDE_PER_CALLER_BLOCK_CAP`
+            per_caller_block_cap: None,
             strict_validation: true,
+            emit_transfer_logs: true,
         }
     }
 }
@@ -52,64 +68,106 @@ impl Default for AndePrecompileConfig {
 impl AndePrecompileConfig {
     /// Creates a new `AndePrecompileConfig` from environment variables
     ///
+    /// See [`Self::with_env_overrides`] for the variables read.
+    pub fn from_env() -> eyre::Result<Self> {
+        Self::default().with_env_overrides()
+    }
+
+    /// Overrides the fields whose environment variable is set
+    ///
     /// Environment variables:
     /// - `ANDE_PRECOMPILE_ADDRESS`: Address of the precompile (default: 0x00..fd)
-    /// - `ANDE_TOKEN_ADDRESS`: Address of the ANDEToken contract
-    /// - `ANDE_ALLOW_LIST`: Comma-separated list of authorized addresses
+    /// - `ANDE_TOKEN_ADDRESS`: Address of the ANDEToken contract, replacing the configured one
+    /// - `ANDE_ALLOW_LIST`: Comma-separated list of authorized addresses, added to the configured ones
     /// - `ANDE_PER_CALL_CAP`: Maximum transfer per call (in wei)
     /// - `ANDE_PER_BLOCK_CAP`: Maximum transfer per block (in wei)
+    /// - `ANDE_PER_CALLER_BLOCK_CAP`: Maximum transfer per caller per block (in wei)
     /// - `ANDE_STRICT_VALIDATION`: Enable strict validation (true/false)
-    pub fn from_env() -> eyre::Result<Self> {
-        let mut config = Self::default();
-
+    /// - `ANDE_EMIT_TRANSFER_LOGS`: Log the `Transfer` event of transfers (true/false)
+    pub fn with_env_overrides(mut self) -> eyre::Result<Self> {
         // Parse precompile address if provided
         if let Ok(addr) = std::env::var("ANDE_PRECOMPILE_ADDRESS") {
-            config.precompile_address = Address::from_str(&addr)?;
+            self.precompile_address = Address::from_str(&addr)?;
         }
 
         // Parse ANDEToken contract address
         if let Ok(addr) = std::env::var("ANDE_TOKEN_ADDRESS") {
-            config.ande_token_address = Address::from_str(&addr)?;
-            // Automatically add to allow-list
-            config.allow_list.insert(config.ande_token_address);
+            self.set_ande_token_address(Address::from_str(&addr)?);
         }
 
         // Parse allow-list
         if let Ok(list) = std::env::var("ANDE_ALLOW_LIST") {
             for addr_str in list.split(',') {
                 let addr = Address::from_str(addr_str.trim())?;
-                config.allow_list.insert(addr);
+                self.allow_list.insert(addr);
             }
         }
 
         // Parse per-call cap
         if let Ok(cap) = std::env::var("ANDE_PER_CALL_CAP") {
-            config.per_call_cap = U256::from_str(&cap)?;
+            self.per_call_cap = U256::from_str(&cap)?;
         }
 
         // Parse per-block cap
         if let Ok(cap) = std::env::var("ANDE_PER_BLOCK_CAP") {
-            config.per_block_cap = Some(U256::from_str(&cap)?);
+            self.per_block_cap = Some(U256::from_str(&cap)?);
+        }
+
+        // Parse per-caller block cap
+        if let Ok(cap) = std::env::var("ANDE_PER_CALLER_BLOCK_CAP") {
+            self.per_caller_block_cap = Some(U256::from_str(&cap)?);
         }
 
         // Parse strict validation
         if let Ok(strict) = std::env::var("ANDE_STRICT_VALIDATION") {
-            config.strict_validation = strict.to_lowercase() == "true" || strict == "1";
+            self.strict_validation = strict.to_lowercase() == "true" || strict == "1";
+        }
+
+        // Parse transfer logs
+        if let Ok(emit) = std::env::var("ANDE_EMIT_TRANSFER_LOGS") {
+            self.emit_transfer_logs = emit.to_lowercase() == "true" || emit == "1";
         }
 
-        Ok(config)
+        Ok(self)
     }
 
-    /// Creates a config for testing with relaxed constraints
-    #[cfg(test)]
-    pub fn for_testing() -> Self {
+    /// Creates a config without authorization or caps
+    ///
+    /// # Security
+    ///
+    /// Any caller may move any amount out of any account, as the sender of a
+    /// transfer is taken from the calldata. Only use it in tests and tools
+    /// executing against throwaway state, never for a node serving a chain.
+    pub fn insecure_unrestricted() -> Self {
         let mut config = Self::default();
         config.strict_validation = false;
         config.per_call_cap = U256::MAX;
         config.per_block_cap = None;
+        config.per_caller_block_cap = None;
         config
     }
 
+    /// Sets the ANDEToken contract address
+    ///
+    /// The new address is authorized in place of the previous one. The
+    /// allow-list is left as is, so a previous address listed there stays
+    /// authorized.
+    pub fn set_ande_token_address(&mut self, address: Address) {
+        self.ande_token_address = address;
+    }
+
+    /// Callers authorized under strict validation: the ANDEToken contract, if
+    /// set, and the allow-list
+    pub fn authorized_callers(&self) -> BTreeSet<Address> {
+        let token = Some(self.ande_token_address).filter(|token| !token.is_zero());
+        self.allow_list.iter().copied().chain(token).collect()
+    }
+
+    /// Whether strict validation rejects every caller, as no address is authorized
+    pub fn denies_all_callers(&self) -> bool {
+        self.strict_validation && self.ande_token_address.is_zero() && self.allow_list.is_empty()
+    }
+
     /// Adds an address to the allow-list
     pub fn add_to_allow_list(&mut self, address: Address) {
         self.allow_list.insert(address);
@@ -125,7 +183,15 @@ impl AndePrecompileConfig {
         if !self.strict_validation {
             return true;
         }
-        self.allow_list.contains(&caller)
+        (!caller.is_zero() && caller == self.ande_token_address) || self.allow_list.contains(&caller)
+    }
+
+    /// Whether transfers are capped per block, overall or per caller
+    ///
+    /// Whether such a transfer is admitted then depends on the transfers made
+    /// before it in the block.
+    pub fn has_block_caps(&self) -> bool {
+        self.per_block_cap.is_some() || self.per_caller_block_cap.is_some()
     }
 
     /// Validates a transfer amount against per-call cap
@@ -156,6 +222,25 @@ impl AndePrecompileConfig {
         }
         Ok(())
     }
+
+    /// Validates a transfer amount against the per-caller block cap of `caller`
+    pub fn validate_per_caller_block_cap(
+        &self,
+        caller: Address,
+        amount: U256,
+        transferred_by_caller: U256,
+    ) -> Result<(), String> {
+        if let Some(caller_cap) = self.per_caller_block_cap {
+            let total = transferred_by_caller.saturating_add(amount);
+            if total > caller_cap {
+                return Err(format!(
+                    "Block transfers {} of caller {:?} would exceed per-caller block cap {}",
+                    total, caller, caller_cap
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -170,6 +255,7 @@ mod tests {
             super::super::precompile::ANDE_PRECOMPILE_ADDRESS
         );
         assert!(config.strict_validation);
+        assert!(config.emit_transfer_logs);
         assert!(config.per_call_cap > U256::ZERO);
         assert!(config.per_block_cap.is_some());
     }
@@ -218,10 +304,54 @@ mod tests {
     }
 
     #[test]
-    fn test_testing_config() {
-        let config = AndePrecompileConfig::for_testing();
+    fn test_per_caller_block_cap_validation() {
+        let caller = Address::repeat_byte(0x42);
+        let mut config = AndePrecompileConfig::default();
+        assert!(config.validate_per_caller_block_cap(caller, U256::MAX, U256::ZERO).is_ok());
+
+        config.per_caller_block_cap = Some(U256::from(1_000u64));
+        assert!(config.validate_per_caller_block_cap(caller, U256::from(400u64), U256::from(600u64)).is_ok());
+        let err = config
+            .validate_per_caller_block_cap(caller, U256::from(401u64), U256::from(600u64))
+            .unwrap_err();
+        assert!(err.contains("per-caller block cap"), "{err}");
+    }
+
+    #[test]
+    fn test_insecure_unrestricted_config() {
+        let config = AndePrecompileConfig::insecure_unrestricted();
         assert!(!config.strict_validation);
         assert_eq!(config.per_call_cap, U256::MAX);
         assert!(config.per_block_cap.is_none());
+        assert!(config.per_caller_block_cap.is_none());
+    }
+
+    #[test]
+    fn test_token_address_replaced() {
+        let mut config = AndePrecompileConfig::default();
+        assert!(config.denies_all_callers());
+
+        let token = Address::repeat_byte(0x01);
+        config.set_ande_token_address(token);
+        assert!(config.is_authorized(token));
+        assert!(!config.denies_all_callers());
+
+        let replacement = Address::repeat_byte(0x02);
+        config.set_ande_token_address(replacement);
+        assert!(!config.is_authorized(token));
+        assert!(config.is_authorized(replacement));
+        assert!(config.allow_list.is_empty(), "the token address is not added to the allow-list");
+        assert_eq!(config.authorized_callers(), BTreeSet::from([replacement]));
+
+        // An explicitly listed address stays authorized when it stops being the token
+        config.add_to_allow_list(replacement);
+        config.set_ande_token_address(token);
+        assert!(config.is_authorized(replacement));
+        assert_eq!(config.authorized_callers(), BTreeSet::from([token, replacement]));
+        // The zero address isn't authorized by an unset token address
+        config.set_ande_token_address(Address::ZERO);
+        assert!(!config.is_authorized(Address::ZERO));
+
+        assert!(!AndePrecompileConfig::insecure_unrestricted().denies_all_callers());
     }
 }
@@ -3,39 +3,55 @@
 //! This inspector validates precompile calls with:
 //! - Caller authorization checks
 //! - Per-call transfer limits
-//! - Per-block transfer limits
+//! - Per-block transfer limits, overall and per caller, reset whenever the
+//!   block number in the EVM context changes
 //! - Full access to EVM context for state validation
+//!
+//! Block totals are kept in a [`BlockTransferLedger`], which inspectors
+//! executing transactions of the same block in parallel share. A transfer
+//! reserves its amount when called and releases it if the call, or any call
+//! enclosing it, reverts.
 
-use super::precompile::ANDE_PRECOMPILE_ADDRESS;
+use super::precompile::{AndePrecompileCall, ANDE_PRECOMPILE_ADDRESS};
 use super::precompile_config::AndePrecompileConfig;
+use super::transfer_ledger::{BlockTransferLedger, TransferReservation};
 use alloy_primitives::{Address, U256};
 use revm::{
-    context_interface::ContextTr,
+    context_interface::{Block, ContextTr},
     inspector::Inspector,
-    interpreter::{CallInputs, CallOutcome, Gas, InstructionResult, InterpreterResult},
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Gas, InstructionResult, InterpreterResult},
 };
+use std::sync::Arc;
 
 /// Inspector that validates ANDE Token Duality precompile calls
 #[derive(Clone, Debug)]
 pub struct AndePrecompileInspector {
     /// Configuration for the precompile
     config: AndePrecompileConfig,
-    
-    /// Total amount transferred in the current block
-    transferred_this_block: U256,
-    
-    /// Current block number for tracking resets
-    current_block: u64,
+
+    /// Transfers of the block, possibly shared with other inspectors
+    ledger: Arc<BlockTransferLedger>,
+
+    /// Reservations made within each call frame being executed, innermost last
+    frames: Vec<Vec<TransferReservation>>,
+
+    /// Reservations of the last transaction that completed successfully
+    committed: Vec<TransferReservation>,
+
+    /// Whether the last transaction checked a transfer against the ledger
+    checked_ledger: bool,
 }
 
 impl AndePrecompileInspector {
-    /// Creates a new inspector with the given configuration
+    /// Creates a new inspector with the given configuration, accounting for
+    /// its transfers on its own
     pub fn new(config: AndePrecompileConfig) -> Self {
-        Self {
-            config,
-            transferred_this_block: U256::ZERO,
-            current_block: 0,
-        }
+        Self::with_ledger(config, Arc::new(BlockTransferLedger::new()))
+    }
+
+    /// Creates an inspector accounting for its transfers in `ledger`
+    pub fn with_ledger(config: AndePrecompileConfig, ledger: Arc<BlockTransferLedger>) -> Self {
+        Self { config, ledger, frames: Vec::new(), committed: Vec::new(), checked_ledger: false }
     }
 
     /// Creates an inspector from environment variables
@@ -44,31 +60,52 @@ impl AndePrecompileInspector {
         Ok(Self::new(config))
     }
 
-    /// Expected input length for transfer calls (96 bytes)
-    const TRANSFER_CALLDATA_LEN: usize = 96; // from(32) + to(32) + value(32)
-
-    /// Resets the block counter if we're in a new block
-    fn maybe_reset_block_counter(&mut self, block_number: u64) {
-        if block_number != self.current_block {
-            self.current_block = block_number;
-            self.transferred_this_block = U256::ZERO;
-        }
+    /// Ledger the transfers are accounted for in
+    pub fn ledger(&self) -> &Arc<BlockTransferLedger> {
+        &self.ledger
     }
 
     /// Manually resets the block counter for a new block
-    /// Call this at the start of each block to reset transfer tracking
+    ///
+    /// The counter resets by itself when a call runs in a block with another
+    /// number. Calling this at the start of each block also covers an inspector
+    /// reused for another block with the same number, e.g. a rebuilt payload.
     pub fn reset_for_new_block(&mut self, block_number: u64) {
-        self.current_block = block_number;
-        self.transferred_this_block = U256::ZERO;
+        self.ledger.reset(block_number);
+        self.committed.clear();
     }
 
     /// Gets the total amount transferred in the current block
     pub fn transferred_this_block(&self) -> U256 {
-        self.transferred_this_block
+        self.ledger.transferred()
+    }
+
+    /// Gets the amount transferred by `caller` in the current block
+    pub fn transferred_by_caller(&self, caller: Address) -> U256 {
+        self.ledger.transferred_by(caller)
+    }
+
+    /// Transfers of the last transaction, empty unless it completed successfully
+    pub fn last_transaction_transfers(&self) -> &[TransferReservation] {
+        &self.committed
+    }
+
+    /// Whether the last transaction checked a transfer against the block totals
+    /// of the ledger, whatever the outcome
+    pub fn checked_ledger(&self) -> bool {
+        self.checked_ledger
+    }
+
+    /// Releases the transfers of the last transaction, which is discarded or
+    /// executed again
+    pub fn release_last_transaction(&mut self) {
+        for reservation in self.committed.drain(..) {
+            self.ledger.release(&reservation);
+        }
     }
 
     /// Creates a revert outcome with a message
-    fn revert_outcome(message: &str, inputs: &CallInputs) -> CallOutcome {
+    pub(crate) fn revert_outcome(message: &str, inputs: &CallInputs) -> CallOutcome {
         CallOutcome::new(
             Self::revert_result(message),
             inputs.return_memory_offset.clone(),
@@ -84,84 +121,97 @@ impl AndePrecompileInspector {
         }
     }
 
-    /// Parses transfer parameters from calldata
-    fn parse_transfer_params(calldata: &[u8]) -> (Address, Address, U256) {
-        // Input format: from(32 bytes) + to(32 bytes) + value(32 bytes)
-        let from = Address::from_slice(&calldata[12..32]); // Last 20 bytes of first word
-        let to = Address::from_slice(&calldata[44..64]); // Last 20 bytes of second word
-        let value = U256::from_be_slice(&calldata[64..96]); // Third word
-        (from, to, value)
+    /// Enters a call frame, starting a new transaction at the outermost one
+    fn enter_frame(&mut self) {
+        if self.frames.is_empty() {
+            self.committed.clear();
+            self.checked_ledger = false;
+        }
+        self.frames.push(Vec::new());
     }
-}
 
-impl<CTX> Inspector<CTX> for AndePrecompileInspector
-where
-    CTX: ContextTr,
-{
-    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
-        // Only intercept calls to the ANDE precompile
-        if inputs.target_address != ANDE_PRECOMPILE_ADDRESS {
-            return None;
+    /// Leaves a call frame, keeping its reservations if it succeeded and
+    /// releasing them otherwise
+    fn exit_frame(&mut self, succeeded: bool) {
+        let Some(reservations) = self.frames.pop() else {
+            return;
+        };
+        if !succeeded {
+            for reservation in &reservations {
+                self.ledger.release(reservation);
+            }
+            return;
         }
+        match self.frames.last_mut() {
+            Some(parent) => parent.extend(reservations),
+            None => self.committed = reservations,
+        }
+    }
 
-        // TODO: Get current block number from context and reset counter
-        // This requires accessing the block environment from the context
-        // For now, we'll rely on manual reset between blocks
-
+    /// Validates a call to the ANDE precompile, reserving the amount of a transfer
+    fn check_precompile_call<CTX: ContextTr>(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CallInputs,
+    ) -> Result<Option<TransferReservation>, String> {
         // Validate caller authorization
         if !self.config.is_authorized(inputs.caller) {
-            return Some(Self::revert_outcome(
-                &format!("Unauthorized caller: {:?}", inputs.caller),
-                inputs,
-            ));
+            return Err(format!("Unauthorized caller: {:?}", inputs.caller));
         }
 
         // Get calldata
         let calldata = inputs.input.bytes(context);
 
-        // Validate input length
-        if calldata.len() != Self::TRANSFER_CALLDATA_LEN {
-            return Some(Self::revert_outcome(
-                &format!(
-                    "Invalid input length: {} (expected {})",
-                    calldata.len(),
-                    Self::TRANSFER_CALLDATA_LEN
-                ),
-                inputs,
-            ));
-        }
-
         // Parse transfer parameters
-        let (_from, to, value) = Self::parse_transfer_params(&calldata);
+        let (to, value) = match AndePrecompileCall::decode(&calldata) {
+            Ok(AndePrecompileCall::Transfer { to, value, .. }) => (to, value),
+            // Reading a balance moves nothing, no cap applies
+            Ok(AndePrecompileCall::BalanceOf(_)) => return Ok(None),
+            Err(err) => return Err(err.to_string()),
+        };
 
         // Validate: no transfer to zero address
         if to == Address::ZERO {
-            return Some(Self::revert_outcome("Transfer to zero address", inputs));
+            return Err("Transfer to zero address".to_string());
         }
 
         // Skip zero-value transfers (optimization)
         if value.is_zero() {
-            return None; // Allow the precompile to handle it
+            return Ok(None); // Allow the precompile to handle it
         }
 
         // Validate per-call cap
-        if let Err(err) = self.config.validate_per_call_cap(value) {
-            return Some(Self::revert_outcome(&err, inputs));
-        }
+        self.config.validate_per_call_cap(value)?;
 
-        // Validate per-block cap
-        if let Err(err) = self
-            .config
-            .validate_per_block_cap(value, self.transferred_this_block)
-        {
-            return Some(Self::revert_outcome(&err, inputs));
-        }
+        // Reserve the amount against the block and per-caller caps
+        let block_number = context.block().number().saturating_to();
+        self.checked_ledger = true;
+        self.ledger.reserve(&self.config, block_number, inputs.caller, value).map(Some)
+    }
+}
 
-        // Update block transfer counter
-        self.transferred_this_block = self.transferred_this_block.saturating_add(value);
+impl<CTX> Inspector<CTX> for AndePrecompileInspector
+where
+    CTX: ContextTr,
+{
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter_frame();
 
-        // Allow the precompile to execute
-        None
+        // Only intercept calls to the ANDE precompile
+        if inputs.target_address != ANDE_PRECOMPILE_ADDRESS {
+            return None;
+        }
+
+        match self.check_precompile_call(context, inputs) {
+            Ok(reservation) => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.extend(reservation);
+                }
+                // Allow the precompile to execute
+                None
+            }
+            Err(err) => Some(Self::revert_outcome(&err, inputs)),
+        }
     }
 
     fn call_end(
@@ -170,14 +220,22 @@ where
         _inputs: &CallInputs,
         outcome: &mut CallOutcome,
     ) {
-        // If the call failed, rollback the block transfer counter
-        if outcome.result.result != InstructionResult::Return
-            && outcome.result.result != InstructionResult::Stop
-        {
-            // On failure, we should ideally rollback, but since we don't know
-            // the exact amount that was added, we'll leave the counter as-is.
-            // In practice, failed calls won't affect state anyway.
-        }
+        // A failed call releases the transfers made within it
+        self.exit_frame(outcome.result.is_ok());
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.exit_frame(outcome.result.is_ok());
     }
 }
 
@@ -200,28 +258,64 @@ mod tests {
         calldata[94] = 0x03;
         calldata[95] = 0xE8;
 
-        let (from, to, value) = AndePrecompileInspector::parse_transfer_params(&calldata);
+        let call = AndePrecompileCall::decode(&calldata).unwrap();
 
-        assert_eq!(from, Address::repeat_byte(0x11));
-        assert_eq!(to, Address::repeat_byte(0x22));
-        assert_eq!(value, U256::from(1000));
+        assert_eq!(
+            call,
+            AndePrecompileCall::Transfer {
+                from: Address::repeat_byte(0x11),
+                to: Address::repeat_byte(0x22),
+                value: U256::from(1000),
+            }
+        );
     }
 
     #[test]
     fn test_block_counter_reset() {
-        let config = AndePrecompileConfig::for_testing();
-        let mut inspector = AndePrecompileInspector::new(config);
-
-        inspector.transferred_this_block = U256::from(1000);
-        inspector.current_block = 10;
+        let mut config = AndePrecompileConfig::insecure_unrestricted();
+        config.per_block_cap = Some(U256::from(1500));
+        let mut inspector = AndePrecompileInspector::new(config.clone());
+        let caller = Address::repeat_byte(0x42);
+        inspector.ledger().reserve(&config, 10, caller, U256::from(1000)).unwrap();
 
         // Same block - counter should not reset
-        inspector.maybe_reset_block_counter(10);
-        assert_eq!(inspector.transferred_this_block, U256::from(1000));
+        assert!(inspector.ledger().reserve(&config, 10, caller, U256::from(1000)).is_err());
+        assert_eq!(inspector.transferred_this_block(), U256::from(1000));
+        assert_eq!(inspector.transferred_by_caller(caller), U256::from(1000));
 
         // New block - counter should reset
-        inspector.maybe_reset_block_counter(11);
-        assert_eq!(inspector.transferred_this_block, U256::ZERO);
-        assert_eq!(inspector.current_block, 11);
+        inspector.ledger().reserve(&config, 11, caller, U256::from(1000)).unwrap();
+        assert_eq!(inspector.transferred_this_block(), U256::from(1000));
+        assert_eq!(inspector.ledger().block_number(), 11);
+
+        // Rebuilt block - reset manually
+        inspector.reset_for_new_block(11);
+        assert_eq!(inspector.transferred_this_block(), U256::ZERO);
+        assert_eq!(inspector.transferred_by_caller(caller), U256::ZERO);
+    }
+
+    #[test]
+    fn test_failed_frames_release_reservations() {
+        let config = AndePrecompileConfig::insecure_unrestricted();
+        let ledger = Arc::new(BlockTransferLedger::new());
+        let mut inspector = AndePrecompileInspector::with_ledger(config.clone(), ledger.clone());
+        let reservation = |amount: u64| ledger.reserve(&config, 1, Address::repeat_byte(0x42), U256::from(amount)).unwrap();
+
+        // Transaction whose inner transfer succeeds but whose outer call reverts
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.frames.last_mut().unwrap().push(reservation(100));
+        inspector.exit_frame(true);
+        assert_eq!(ledger.transferred(), U256::from(100));
+        inspector.exit_frame(false);
+        assert_eq!(ledger.transferred(), U256::ZERO);
+
+        // Successful transaction, executed again
+        inspector.enter_frame();
+        inspector.frames.last_mut().unwrap().push(reservation(300));
+        inspector.exit_frame(true);
+        assert_eq!(ledger.transferred(), U256::from(300));
+        inspector.release_last_transaction();
+        assert_eq!(ledger.transferred(), U256::ZERO);
     }
 }
@@ -0,0 +1,256 @@
+//! Block Transfer Ledger
+//!
+//! Accounts for the ANDE moved through the precompile in a block, shared by
+//! every [`AndePrecompileInspector`](super::AndePrecompileInspector) executing
+//! transactions of that block. Parallel workers then enforce the per-block
+//! and per-caller caps of the block as a whole, instead of each on its own
+//! share of the transactions.
+//!
+//! Transfers reserve their amount before running and release it if they end
+//! up reverted, or if their transaction is executed again.
+
+use super::precompile_config::AndePrecompileConfig;
+use alloy_primitives::{Address, U256};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// Amount reserved by a transfer in a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferReservation {
+    /// Block the transfer was executed in
+    pub block_number: u64,
+    /// Caller of the precompile
+    pub caller: Address,
+    /// Amount transferred
+    pub amount: U256,
+}
+
+/// Thread-safe totals of the transfers of the current block
+#[derive(Debug, Default)]
+pub struct BlockTransferLedger {
+    state: Mutex<LedgerState>,
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+    /// Block the totals are for
+    block_number: u64,
+    /// Amount reserved in the block
+    total: U256,
+    /// Amount reserved in the block by each caller
+    by_caller: HashMap<Address, U256>,
+}
+
+impl LedgerState {
+    /// Start over if `block_number` is another block
+    fn enter_block(&mut self, block_number: u64) {
+        if block_number != self.block_number {
+            self.reset(block_number);
+        }
+    }
+
+    fn reset(&mut self, block_number: u64) {
+        self.block_number = block_number;
+        self.total = U256::ZERO;
+        self.by_caller.clear();
+    }
+}
+
+impl BlockTransferLedger {
+    /// Create a ledger with no transfer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, LedgerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Create a ledger for block `block_number` holding `reservations`, e.g. the
+    /// transfers of the transactions executed before in the block
+    ///
+    /// The reservations are accounted for as they are, without checking caps.
+    pub fn with_reservations<'a>(
+        block_number: u64,
+        reservations: impl IntoIterator<Item = &'a TransferReservation>,
+    ) -> Self {
+        let ledger = Self::new();
+        {
+            let mut state = ledger.state();
+            state.reset(block_number);
+            for reservation in reservations {
+                state.total = state.total.saturating_add(reservation.amount);
+                let by_caller = state.by_caller.entry(reservation.caller).or_default();
+                *by_caller = by_caller.saturating_add(reservation.amount);
+            }
+        }
+        ledger
+    }
+
+    /// Start block `block_number` with no transfer
+    ///
+    /// The ledger also starts over by itself when a transfer is reserved in
+    /// another block; this covers a block rebuilt with the same number.
+    pub fn reset(&self, block_number: u64) {
+        self.state().reset(block_number);
+    }
+
+    /// Reserve `amount` for a transfer by `caller` in block `block_number`
+    ///
+    /// Fails without reserving anything if the transfer would exceed the
+    /// per-block or per-caller block cap of `config`.
+    pub fn reserve(
+        &self,
+        config: &AndePrecompileConfig,
+        block_number: u64,
+        caller: Address,
+        amount: U256,
+    ) -> Result<TransferReservation, String> {
+        let mut state = self.state();
+        state.enter_block(block_number);
+
+        config.validate_per_block_cap(amount, state.total)?;
+        let by_caller = state.by_caller.get(&caller).copied().unwrap_or_default();
+        config.validate_per_caller_block_cap(caller, amount, by_caller)?;
+
+        state.total = state.total.saturating_add(amount);
+        state.by_caller.insert(caller, by_caller.saturating_add(amount));
+        Ok(TransferReservation { block_number, caller, amount })
+    }
+
+    /// Release `reservation`, whose transfer was reverted or is executed again
+    ///
+    /// Reservations of a block the ledger has moved past are ignored.
+    pub fn release(&self, reservation: &TransferReservation) {
+        let mut state = self.state();
+        if reservation.block_number != state.block_number {
+            return;
+        }
+        state.total = state.total.saturating_sub(reservation.amount);
+        if let Some(by_caller) = state.by_caller.get_mut(&reservation.caller) {
+            *by_caller = by_caller.saturating_sub(reservation.amount);
+        }
+    }
+
+    /// Block the ledger accounts for
+    pub fn block_number(&self) -> u64 {
+        self.state().block_number
+    }
+
+    /// Amount transferred in the current block
+    pub fn transferred(&self) -> U256 {
+        self.state().total
+    }
+
+    /// Amount transferred by `caller` in the current block
+    pub fn transferred_by(&self, caller: Address) -> U256 {
+        self.state().by_caller.get(&caller).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, thread};
+
+    fn capped(per_block_cap: u64, per_caller_block_cap: Option<u64>) -> AndePrecompileConfig {
+        let mut config = AndePrecompileConfig::insecure_unrestricted();
+        config.per_block_cap = Some(U256::from(per_block_cap));
+        config.per_caller_block_cap = per_caller_block_cap.map(U256::from);
+        config
+    }
+
+    #[test]
+    fn test_reserve_and_release() {
+        let ledger = BlockTransferLedger::new();
+        let config = capped(1_000, Some(700));
+        let (first, second) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+
+        let reservation = ledger.reserve(&config, 1, first, U256::from(600)).unwrap();
+        assert!(ledger.reserve(&config, 1, first, U256::from(200)).unwrap_err().contains("per-caller"));
+        assert!(ledger.reserve(&config, 1, second, U256::from(500)).unwrap_err().contains("per-block"));
+        ledger.reserve(&config, 1, second, U256::from(400)).unwrap();
+        assert_eq!(ledger.transferred(), U256::from(1_000));
+
+        ledger.release(&reservation);
+        assert_eq!(ledger.transferred(), U256::from(400));
+        assert_eq!(ledger.transferred_by(first), U256::ZERO);
+        ledger.reserve(&config, 1, first, U256::from(600)).unwrap();
+
+        // A new block starts over, and stale reservations are ignored
+        ledger.reserve(&config, 2, first, U256::from(100)).unwrap();
+        ledger.release(&reservation);
+        assert_eq!(ledger.block_number(), 2);
+        assert_eq!(ledger.transferred(), U256::from(100));
+    }
+
+    #[test]
+    fn test_ledger_with_reservations() {
+        let config = capped(1_000, Some(700));
+        let (first, second) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let earlier = [
+            TransferReservation { block_number: 3, caller: first, amount: U256::from(500) },
+            TransferReservation { block_number: 3, caller: second, amount: U256::from(300) },
+        ];
+
+        let ledger = BlockTransferLedger::with_reservations(3, &earlier);
+        assert_eq!(ledger.block_number(), 3);
+        assert_eq!(ledger.transferred(), U256::from(800));
+        assert_eq!(ledger.transferred_by(first), U256::from(500));
+        assert!(ledger.reserve(&config, 3, first, U256::from(201)).unwrap_err().contains("per-caller"));
+        assert!(ledger.reserve(&config, 3, second, U256::from(201)).unwrap_err().contains("per-block"));
+        ledger.reserve(&config, 3, second, U256::from(200)).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_reservations_respect_cap() {
+        const THREADS: u64 = 8;
+        const TRANSFERS: u64 = 200;
+        let ledger = Arc::new(BlockTransferLedger::new());
+        let config = Arc::new(capped(10_000, None));
+
+        let workers: Vec<_> = (0..THREADS)
+            .map(|worker| {
+                let (ledger, config) = (ledger.clone(), config.clone());
+                thread::spawn(move || {
+                    let caller = Address::with_last_byte(worker as u8);
+                    let mut kept = U256::ZERO;
+                    for i in 0..TRANSFERS {
+                        let Ok(reservation) = ledger.reserve(&config, 1, caller, U256::from(10)) else {
+                            continue;
+                        };
+                        // Every other transfer is reverted and releases its reservation
+                        if i % 2 == 0 {
+                            ledger.release(&reservation);
+                        } else {
+                            kept += reservation.amount;
+                        }
+                        assert!(ledger.transferred() <= U256::from(10_000));
+                    }
+                    kept
+                })
+            })
+            .collect();
+        let kept: U256 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+
+        // 8 threads keep 100 transfers of 10 each, 8_000 in total, within the cap
+        assert_eq!(kept, U256::from(THREADS * TRANSFERS / 2 * 10));
+        assert_eq!(ledger.transferred(), kept);
+
+        // Once the cap is reached, no thread gets past it
+        let workers: Vec<_> = (0..THREADS)
+            .map(|worker| {
+                let (ledger, config) = (ledger.clone(), config.clone());
+                thread::spawn(move || {
+                    let caller = Address::with_last_byte(worker as u8);
+                    (0..TRANSFERS).filter(|_| ledger.reserve(&config, 1, caller, U256::from(10)).is_ok()).count()
+                })
+            })
+            .collect();
+        let accepted: usize = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+        assert_eq!(accepted, 200);
+        assert_eq!(ledger.transferred(), U256::from(10_000));
+    }
+}
@@ -0,0 +1,311 @@
+//! Consensus Validator-Set Precompile
+//!
+//! Read-only precompile giving contracts the active validator set of the
+//! consensus, without going through an oracle. The node keeps a
+//! [`ValidatorSnapshot`] up to date from `AndeConsensusClient` and shares it
+//! with the precompile provider, see
+//! [`AndePrecompileProvider::with_validator_set`](super::AndePrecompileProvider::with_validator_set).
+//!
+//! ## ABI
+//!
+//! ```solidity
+//! interface IValidatorSet {
+//!     /// Active validators, in the order of the consensus contract
+//!     function getActiveValidators() external view returns (address[] memory);
+//!     /// Voting power of `validator`, zero if it isn't active
+//!     function votingPower(address validator) external view returns (uint256);
+//!     /// Whether the block `blockHash` is finalized by the consensus
+//!     function isFinalized(bytes32 blockHash) external view returns (bool);
+//! }
+//! ```
+//!
+//! Outputs are ABI-encoded. Unknown selectors and malformed arguments fail
+//! the call.
+//!
+//! ## Consistency
+//!
+//! Every call of a block reads the snapshot captured at the start of that
+//! block, so updates pushed by the consensus client while the block executes
+//! are only visible from the next block on.
+//!
+//! ## Gas
+//!
+//! | Call | Gas |
+//! |------|-----|
+//! | `getActiveValidators` | [`VALIDATOR_SET_BASE_GAS`] + [`VALIDATOR_SET_PER_VALIDATOR_GAS`] per validator |
+//! | `votingPower` | [`VALIDATOR_SET_BASE_GAS`] |
+//! | `isFinalized` | [`VALIDATOR_SET_BASE_GAS`] |
+//!
+//! **Address:** 0x00000000000000000000000000000000000000fc
+
+use super::precompile::AndePrecompileError;
+use alloy::sol_types::{SolInterface, SolValue};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use revm_precompile::{PrecompileError, PrecompileOutput, PrecompileResult};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
+
+/// Validator-Set Precompile Address: 0x00..fc
+pub const VALIDATOR_SET_PRECOMPILE_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0xfc,
+]);
+
+/// Gas of every validator-set call
+pub const VALIDATOR_SET_BASE_GAS: u64 = 200;
+
+/// Gas per validator returned by `getActiveValidators`
+pub const VALIDATOR_SET_PER_VALIDATOR_GAS: u64 = 50;
+
+/// Finalized block hashes remembered by a [`ValidatorSnapshot`]
+pub const MAX_FINALIZED_BLOCKS: usize = 8_192;
+
+alloy::sol! {
+    /// Calls served by the validator-set precompile
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IValidatorSet {
+        function getActiveValidators() external view returns (address[] memory);
+
+        function votingPower(address validator) external view returns (uint256);
+
+        function isFinalized(bytes32 blockHash) external view returns (bool);
+    }
+}
+
+/// Validator set of the consensus, as last seen by the node
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidatorSnapshot {
+    /// Active validators, in the order of the consensus contract
+    validators: Vec<Address>,
+    /// Voting power of the active validators
+    voting_power: HashMap<Address, U256>,
+    /// Recently finalized block hashes
+    finalized: HashSet<B256>,
+    /// `finalized` in insertion order, to evict the oldest hashes
+    finalized_order: VecDeque<B256>,
+}
+
+/// Validator snapshot shared between the consensus client and the precompile
+pub type SharedValidatorSnapshot = Arc<RwLock<ValidatorSnapshot>>;
+
+impl ValidatorSnapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `validator` with `power` to the active set
+    pub fn with_validator(mut self, validator: Address, power: U256) -> Self {
+        self.insert_validator(validator, power);
+        self
+    }
+
+    /// Replace the active set, keeping the finalized blocks
+    pub fn set_validators(&mut self, validators: impl IntoIterator<Item = (Address, U256)>) {
+        self.validators.clear();
+        self.voting_power.clear();
+        for (validator, power) in validators {
+            self.insert_validator(validator, power);
+        }
+    }
+
+    /// Add `validator` to the active set, or update its power if already in it
+    fn insert_validator(&mut self, validator: Address, power: U256) {
+        if self.voting_power.insert(validator, power).is_none() {
+            self.validators.push(validator);
+        }
+    }
+
+    /// Record `block_hash` as finalized, forgetting the oldest hash past
+    /// [`MAX_FINALIZED_BLOCKS`]
+    pub fn record_finalized(&mut self, block_hash: B256) {
+        if !self.finalized.insert(block_hash) {
+            return;
+        }
+        self.finalized_order.push_back(block_hash);
+        if self.finalized_order.len() > MAX_FINALIZED_BLOCKS {
+            if let Some(oldest) = self.finalized_order.pop_front() {
+                self.finalized.remove(&oldest);
+            }
+        }
+    }
+
+    /// Active validators, in the order of the consensus contract
+    pub fn validators(&self) -> &[Address] {
+        &self.validators
+    }
+
+    /// Voting power of `validator`, zero if it isn't active
+    pub fn voting_power(&self, validator: &Address) -> U256 {
+        self.voting_power.get(validator).copied().unwrap_or_default()
+    }
+
+    /// Whether `block_hash` was recorded as finalized
+    pub fn is_finalized(&self, block_hash: &B256) -> bool {
+        self.finalized.contains(block_hash)
+    }
+}
+
+/// Reader serving the validator-set precompile from a [`SharedValidatorSnapshot`]
+///
+/// Clones share the snapshot captured for the current block, so every EVM of
+/// a provider sees the same data during a block.
+#[derive(Debug, Clone)]
+pub struct ValidatorSetReader {
+    shared: SharedValidatorSnapshot,
+    /// Block number and snapshot captured at its start
+    captured: Arc<Mutex<Option<(u64, Arc<ValidatorSnapshot>)>>>,
+}
+
+impl ValidatorSetReader {
+    /// Create a reader of the snapshot updated by the consensus client
+    pub fn new(shared: SharedValidatorSnapshot) -> Self {
+        Self { shared, captured: Default::default() }
+    }
+
+    /// Snapshot updated by the consensus client
+    pub fn shared(&self) -> &SharedValidatorSnapshot {
+        &self.shared
+    }
+
+    /// Capture the snapshot served during block `block_number`, replacing any
+    /// previous capture of the same block
+    pub fn capture(&self, block_number: u64) -> Arc<ValidatorSnapshot> {
+        let snapshot = Arc::new(self.shared.read().unwrap_or_else(PoisonError::into_inner).clone());
+        *self.captured.lock().unwrap_or_else(PoisonError::into_inner) = Some((block_number, snapshot.clone()));
+        snapshot
+    }
+
+    /// Snapshot served during block `block_number`, captured on its first call
+    /// if the block start wasn't signalled
+    pub fn snapshot_for_block(&self, block_number: u64) -> Arc<ValidatorSnapshot> {
+        let captured = self.captured.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        match captured {
+            Some((number, snapshot)) if number == block_number => snapshot,
+            _ => self.capture(block_number),
+        }
+    }
+
+    /// Run a call made during block `block_number`
+    pub fn run(&self, block_number: u64, input: &[u8], gas_limit: u64) -> PrecompileResult {
+        let call = decode_call(input)?;
+        let snapshot = self.snapshot_for_block(block_number);
+        let (gas_used, output) = match call {
+            IValidatorSet::IValidatorSetCalls::getActiveValidators(_) => {
+                let validators = snapshot.validators();
+                let gas = VALIDATOR_SET_PER_VALIDATOR_GAS
+                    .saturating_mul(validators.len() as u64)
+                    .saturating_add(VALIDATOR_SET_BASE_GAS);
+                (gas, validators.to_vec().abi_encode())
+            }
+            IValidatorSet::IValidatorSetCalls::votingPower(call) => {
+                (VALIDATOR_SET_BASE_GAS, snapshot.voting_power(&call.validator).abi_encode())
+            }
+            IValidatorSet::IValidatorSetCalls::isFinalized(call) => {
+                (VALIDATOR_SET_BASE_GAS, snapshot.is_finalized(&call.blockHash).abi_encode())
+            }
+        };
+        if gas_used > gas_limit {
+            return Err(PrecompileError::OutOfGas);
+        }
+        Ok(PrecompileOutput::new(gas_used, Bytes::from(output)))
+    }
+}
+
+/// Decode the input of a validator-set call
+fn decode_call(input: &[u8]) -> Result<IValidatorSet::IValidatorSetCalls, AndePrecompileError> {
+    let selector: [u8; 4] = input
+        .get(..4)
+        .and_then(|selector| selector.try_into().ok())
+        .ok_or(AndePrecompileError::InvalidInputLength(input.len()))?;
+    if !IValidatorSet::IValidatorSetCalls::valid_selector(selector) {
+        return Err(AndePrecompileError::UnknownSelector(selector));
+    }
+    IValidatorSet::IValidatorSetCalls::abi_decode(input)
+        .map_err(|_| AndePrecompileError::InvalidInputLength(input.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+
+    const GAS_LIMIT: u64 = 100_000;
+
+    fn shared(snapshot: ValidatorSnapshot) -> SharedValidatorSnapshot {
+        Arc::new(RwLock::new(snapshot))
+    }
+
+    #[test]
+    fn test_snapshot_keeps_contract_order() {
+        let (a, b) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        let mut snapshot = ValidatorSnapshot::new().with_validator(b, U256::from(2)).with_validator(a, U256::from(1));
+        assert_eq!(snapshot.validators(), [b, a]);
+        assert_eq!(snapshot.voting_power(&a), U256::from(1));
+
+        snapshot.set_validators([(a, U256::from(5)), (a, U256::from(6))]);
+        assert_eq!(snapshot.validators(), [a]);
+        assert_eq!(snapshot.voting_power(&a), U256::from(6));
+        assert_eq!(snapshot.voting_power(&b), U256::ZERO);
+    }
+
+    #[test]
+    fn test_finalized_blocks_are_bounded() {
+        let mut snapshot = ValidatorSnapshot::new();
+        for i in 0..=MAX_FINALIZED_BLOCKS as u64 {
+            snapshot.record_finalized(B256::from(U256::from(i)));
+        }
+        assert!(!snapshot.is_finalized(&B256::from(U256::ZERO)));
+        assert!(snapshot.is_finalized(&B256::from(U256::from(1))));
+        assert!(snapshot.is_finalized(&B256::from(U256::from(MAX_FINALIZED_BLOCKS))));
+    }
+
+    #[test]
+    fn test_run_encodes_outputs() {
+        let validator = Address::repeat_byte(0x0a);
+        let mut snapshot = ValidatorSnapshot::new().with_validator(validator, U256::from(40));
+        snapshot.record_finalized(B256::repeat_byte(0x11));
+        let reader = ValidatorSetReader::new(shared(snapshot));
+
+        let output = reader.run(1, &IValidatorSet::getActiveValidatorsCall {}.abi_encode(), GAS_LIMIT).unwrap();
+        assert_eq!(output.gas_used, VALIDATOR_SET_BASE_GAS + VALIDATOR_SET_PER_VALIDATOR_GAS);
+        assert_eq!(Vec::<Address>::abi_decode(&output.bytes).unwrap(), [validator]);
+
+        let output = reader.run(1, &IValidatorSet::votingPowerCall { validator }.abi_encode(), GAS_LIMIT).unwrap();
+        assert_eq!(output.gas_used, VALIDATOR_SET_BASE_GAS);
+        assert_eq!(U256::abi_decode(&output.bytes).unwrap(), U256::from(40));
+
+        let call = IValidatorSet::isFinalizedCall { blockHash: B256::repeat_byte(0x11) };
+        let output = reader.run(1, &call.abi_encode(), GAS_LIMIT).unwrap();
+        assert!(bool::abi_decode(&output.bytes).unwrap());
+    }
+
+    #[test]
+    fn test_run_rejects_bad_input() {
+        let reader = ValidatorSetReader::new(shared(ValidatorSnapshot::new()));
+        assert!(reader.run(1, &[0x01, 0x02], GAS_LIMIT).is_err());
+        assert!(reader.run(1, &[0xde, 0xad, 0xbe, 0xef], GAS_LIMIT).is_err());
+        let truncated = &IValidatorSet::votingPowerCall { validator: Address::ZERO }.abi_encode()[..20];
+        assert!(reader.run(1, truncated, GAS_LIMIT).is_err());
+
+        let call = IValidatorSet::getActiveValidatorsCall {}.abi_encode();
+        let err = reader.run(1, &call, VALIDATOR_SET_BASE_GAS - 1).unwrap_err();
+        assert!(err.is_oog());
+    }
+
+    #[test]
+    fn test_snapshot_captured_once_per_block() {
+        let validator = Address::repeat_byte(0x0a);
+        let snapshot = shared(ValidatorSnapshot::new());
+        let reader = ValidatorSetReader::new(snapshot.clone());
+        let power = |reader: &ValidatorSetReader, block| reader.snapshot_for_block(block).voting_power(&validator);
+
+        reader.capture(1);
+        snapshot.write().unwrap().set_validators([(validator, U256::from(7))]);
+        assert_eq!(power(&reader.clone(), 1), U256::ZERO, "block 1 keeps its snapshot");
+        assert_eq!(power(&reader, 2), U256::from(7));
+    }
+}
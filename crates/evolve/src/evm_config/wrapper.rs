@@ -1,11 +1,11 @@
 //! ANDE EVM Configuration Type Alias
 //!
-//! This module provides a type alias for ANDE EVM configuration.
-//! For now, we use EthEvmConfig directly and inject ANDE precompiles at runtime
-//! in the payload builder.
+//! This module provides a type alias for ANDE EVM configuration: the Ethereum
+//! EVM configuration with EVMs built by [`AndeEvmFactory`], which run the ANDE
+//! precompile against the journaled state.
 
+use super::AndeEvmFactory;
+use reth_chainspec::ChainSpec;
 use reth_evm_ethereum::EthEvmConfig;
 
-pub type AndeEvmConfig = EthEvmConfig;
-
-
+pub type AndeEvmConfig = EthEvmConfig<ChainSpec, AndeEvmFactory>;
@@ -0,0 +1,317 @@
+//! RPC Endpoint Failover
+//!
+//! [`FailoverTransport`] sends each request to the first of a list of HTTP
+//! endpoints, ordered by priority, that is up. An endpoint failing at the
+//! transport level, e.g. refusing connections or answering with an HTTP
+//! error, is put in cooldown and the request moves on to the next endpoint.
+//! Once the cooldown elapses, the endpoint is tried first again. JSON-RPC
+//! errors, such as reverts, are answers and are returned as they are.
+//!
+//! Raw transactions are resent as signed, with the same nonce and hash, so a
+//! transaction reaching two endpoints is still included once. If the next
+//! endpoint refuses a resent transaction, e.g. as already known, it's
+//! reported as sent as long as that endpoint knows the transaction.
+
+use alloy::{
+    primitives::{keccak256, Bytes, B256},
+    rpc::json_rpc::{Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload},
+    transports::{
+        http::{
+            reqwest::{Client, Url},
+            Http,
+        },
+        RpcError, TransportError, TransportErrorKind, TransportFut, TransportResult,
+    },
+};
+use serde_json::value::RawValue;
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::Service;
+use tracing::{debug, info, warn};
+
+/// How long a failed endpoint is skipped before being tried again
+pub const DEFAULT_FAILOVER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Endpoint currently serving the requests, for metrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailoverStatus {
+    /// URL of the endpoint
+    pub active_endpoint: Url,
+    /// Priority of the endpoint, 0 being the primary
+    pub active_index: usize,
+    /// Times the requests moved on to a lower priority endpoint
+    pub failovers: u64,
+}
+
+/// HTTP endpoint of a [`FailoverTransport`]
+#[derive(Debug)]
+struct Endpoint {
+    url: Url,
+    transport: Http<Client>,
+    /// Until when the endpoint is skipped, after failing
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn new(url: Url) -> Self {
+        Self { transport: Http::new(url.clone()), url, down_until: Mutex::new(None) }
+    }
+
+    fn is_up(&self, now: Instant) -> bool {
+        self.down_until.lock().unwrap_or_else(PoisonError::into_inner).is_none_or(|until| now >= until)
+    }
+
+    fn cool_down(&self, until: Instant) {
+        *self.down_until.lock().unwrap_or_else(PoisonError::into_inner) = Some(until);
+    }
+
+    /// Whether the endpoint knows the transaction with hash `hash`, pending
+    /// or included
+    async fn knows_transaction(&self, hash: B256) -> bool {
+        let Ok(request) = Request::new("eth_getTransactionByHash", Id::Number(0), (hash,)).serialize() else {
+            return false;
+        };
+        match self.transport.clone().call(request.into()).await {
+            Ok(ResponsePacket::Single(Response { payload: ResponsePayload::Success(transaction), .. })) => {
+                transaction.get() != "null"
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Transport failing over between HTTP endpoints ordered by priority
+///
+/// # Example
+/// ```ignore
+/// let transport = FailoverTransport::new(primary, [fallback]);
+/// let provider = ProviderBuilder::new().connect_client(RpcClient::new(transport.clone(), false));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FailoverTransport {
+    endpoints: Arc<[Endpoint]>,
+    cooldown: Duration,
+    /// Index of the endpoint that answered last
+    active: Arc<AtomicUsize>,
+    failovers: Arc<AtomicU64>,
+}
+
+impl FailoverTransport {
+    /// Create a transport sending to `primary`, and to `fallbacks` in order
+    /// while it's down
+    pub fn new(primary: Url, fallbacks: impl IntoIterator<Item = Url>) -> Self {
+        let endpoints = std::iter::once(primary).chain(fallbacks).map(Endpoint::new).collect();
+        Self {
+            endpoints,
+            cooldown: DEFAULT_FAILOVER_COOLDOWN,
+            active: Arc::new(AtomicUsize::new(0)),
+            failovers: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Skip failed endpoints for `cooldown` before trying them again
+    pub const fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Endpoint currently serving the requests, and failovers so far
+    pub fn status(&self) -> FailoverStatus {
+        let active_index = self.active.load(Ordering::Relaxed);
+        FailoverStatus {
+            active_endpoint: self.endpoints[active_index].url.clone(),
+            active_index,
+            failovers: self.failovers.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Endpoints to try at `now`: those up by priority, then those in cooldown
+    /// as a last resort
+    fn candidates(&self, now: Instant) -> Vec<usize> {
+        let (up, down): (Vec<usize>, Vec<usize>) =
+            (0..self.endpoints.len()).partition(|index| self.endpoints[*index].is_up(now));
+        up.into_iter().chain(down).collect()
+    }
+
+    /// Note that endpoint `index` answered
+    fn activate(&self, index: usize) {
+        let previous = self.active.swap(index, Ordering::Relaxed);
+        if index > previous {
+            self.failovers.fetch_add(1, Ordering::Relaxed);
+            warn!("Failed over from RPC endpoint {} to {}", self.endpoints[previous].url, self.endpoints[index].url);
+        } else if index < previous {
+            info!("Returned to RPC endpoint {}", self.endpoints[index].url);
+        }
+    }
+
+    async fn send(self, request: RequestPacket) -> TransportResult<ResponsePacket> {
+        let mut last_error = None;
+        for index in self.candidates(Instant::now()) {
+            let endpoint = &self.endpoints[index];
+            match endpoint.transport.clone().call(request.clone()).await {
+                Ok(response) => {
+                    self.activate(index);
+                    if last_error.is_none() {
+                        return Ok(response);
+                    }
+                    return Ok(settle_resent_transaction(endpoint, &request, response).await);
+                }
+                Err(RpcError::Transport(err)) => {
+                    debug!("RPC endpoint {} failed: {}", endpoint.url, err);
+                    endpoint.cool_down(Instant::now() + self.cooldown);
+                    last_error = Some(RpcError::Transport(err));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| TransportErrorKind::custom_str("No RPC endpoint configured")))
+    }
+}
+
+/// Report a raw transaction resent to `endpoint` as sent if `endpoint`
+/// refused it but knows it, as the previous endpoint may have got it
+async fn settle_resent_transaction(
+    endpoint: &Endpoint,
+    request: &RequestPacket,
+    response: ResponsePacket,
+) -> ResponsePacket {
+    let RequestPacket::Single(request) = request else { return response };
+    let ResponsePacket::Single(Response { id, payload: ResponsePayload::Failure(error) }) = &response else {
+        return response;
+    };
+    if request.method() != "eth_sendRawTransaction" {
+        return response;
+    }
+    let Some(Ok((raw,))) = request.params().map(|params| serde_json::from_str::<(Bytes,)>(params.get())) else {
+        return response;
+    };
+
+    let hash = keccak256(&raw);
+    if !endpoint.knows_transaction(hash).await {
+        return response;
+    }
+    let Ok(result) = serde_json::to_string(&hash).and_then(RawValue::from_string) else { return response };
+    info!("Resent transaction {} already known to {}: {}", hash, endpoint.url, error.message);
+    ResponsePacket::Single(Response { id: id.clone(), payload: ResponsePayload::Success(result) })
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().send(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        providers::{Provider, ProviderBuilder},
+        rpc::client::RpcClient,
+    };
+    use jsonrpsee::{
+        server::{RpcModule, Server, ServerHandle},
+        types::ErrorObjectOwned,
+    };
+    use std::{net::SocketAddr, sync::atomic::AtomicUsize};
+
+    /// Mock node answering `eth_blockNumber` with `block_number`
+    async fn mock_node(addr: SocketAddr, block_number: u64) -> (ServerHandle, Url) {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_blockNumber", move |_, _, _| format!("{block_number:#x}")).unwrap();
+        serve(addr, module).await
+    }
+
+    async fn serve(addr: SocketAddr, module: RpcModule<()>) -> (ServerHandle, Url) {
+        let server = Server::builder().build(addr).await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap()).parse().unwrap();
+        (server.start(module), url)
+    }
+
+    async fn stop(handle: ServerHandle) {
+        handle.stop().unwrap();
+        handle.stopped().await;
+    }
+
+    #[tokio::test]
+    async fn test_failover_and_return_to_primary() {
+        let any_port: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let (primary, primary_url) = mock_node(any_port, 1).await;
+        let (_secondary, secondary_url) = mock_node(any_port, 2).await;
+
+        let cooldown = Duration::from_millis(200);
+        let transport = FailoverTransport::new(primary_url.clone(), [secondary_url.clone()]).with_cooldown(cooldown);
+        let provider = ProviderBuilder::new().connect_client(RpcClient::new(transport.clone(), false));
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 1);
+        assert_eq!(transport.status().active_endpoint, primary_url);
+
+        // The primary dies mid-run: requests move on to the secondary
+        stop(primary).await;
+        assert_eq!(provider.get_block_number().await.unwrap(), 2);
+        assert_eq!(provider.get_block_number().await.unwrap(), 2);
+        assert_eq!(
+            transport.status(),
+            FailoverStatus { active_endpoint: secondary_url, active_index: 1, failovers: 1 }
+        );
+
+        // Back up, the primary is used again once its cooldown elapsed
+        let primary_addr = SocketAddr::new(any_port.ip(), primary_url.port().unwrap());
+        let (_primary, _) = mock_node(primary_addr, 1).await;
+        tokio::time::sleep(cooldown).await;
+        assert_eq!(provider.get_block_number().await.unwrap(), 1);
+        assert_eq!(transport.status().active_index, 0);
+        assert_eq!(transport.status().failovers, 1);
+    }
+
+    #[tokio::test]
+    async fn test_resent_transaction_not_submitted_twice() {
+        let any_port: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let (primary, primary_url) = mock_node(any_port, 1).await;
+        stop(primary).await;
+
+        // The secondary already got the transaction, e.g. gossiped by the
+        // primary before it died
+        let known = Bytes::from_static(b"signed transaction");
+        let known_hash = keccak256(&known);
+        let submissions = Arc::new(AtomicUsize::new(0));
+        let mut module = RpcModule::new(());
+        let counter = submissions.clone();
+        module
+            .register_method("eth_sendRawTransaction", move |_, _, _| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Err::<B256, _>(ErrorObjectOwned::owned(-32000, "already known", None::<()>))
+            })
+            .unwrap();
+        module
+            .register_method("eth_getTransactionByHash", move |params, _, _| {
+                let (hash,): (B256,) = params.parse().unwrap();
+                (hash == known_hash).then(|| serde_json::json!({ "hash": hash }))
+            })
+            .unwrap();
+        let (_secondary, secondary_url) = serve(any_port, module).await;
+
+        let client = RpcClient::new(FailoverTransport::new(primary_url, [secondary_url]), false);
+        let hash: B256 = client.request("eth_sendRawTransaction", (known,)).await.unwrap();
+        assert_eq!(hash, known_hash);
+        assert_eq!(submissions.load(Ordering::Relaxed), 1);
+
+        // A transaction the secondary doesn't know is a genuine failure
+        let unknown = Bytes::from_static(b"another transaction");
+        let err = client.request::<_, B256>("eth_sendRawTransaction", (unknown,)).await.unwrap_err();
+        assert_eq!(err.as_error_resp().unwrap().message, "already known");
+    }
+}
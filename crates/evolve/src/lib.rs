@@ -27,11 +27,29 @@ pub mod parallel;
 /// MEV detection and integration module.
 pub mod mev;
 
+/// Prometheus metrics for Evolve-specific code paths.
+pub mod metrics;
+
+/// Failover between RPC endpoints of external chains.
+pub mod failover;
+
+/// Sequencer registration with the AndeSequencerRegistry contract.
+pub mod sequencer_registry;
+
+/// Staking queries against the AndeNativeStaking contract.
+pub mod staking;
+
 #[cfg(test)]
 mod tests;
 
 // Re-export public types
-pub use config::{EvolveConfig, DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS};
+pub use config::{
+    EvolveConfig, FinalizationPolicy, PayloadValidationConfig, TxpoolOverflowPolicy, DEFAULT_MAX_TXPOOL_BYTES,
+    DEFAULT_MAX_TXPOOL_GAS,
+};
 pub use consensus::{EvolveConsensus, EvolveConsensusBuilder};
-pub use evm_config::{ande_token_duality_precompile, ANDE_PRECOMPILE_ADDRESS};
-pub use types::{EvolvePayloadAttributes, PayloadAttributesError};
+pub use evm_config::ANDE_PRECOMPILE_ADDRESS;
+pub use types::{
+    EvolvePayloadAttributes, EvolvePayloadAttributesV1, PayloadAttributesError, PayloadAttributesVersion,
+    VersionedPayloadAttributes,
+};
@@ -0,0 +1,178 @@
+//! Prometheus metrics for Evolve-specific code paths
+//!
+//! Metrics are registered through the `metrics` facade, so they show up on
+//! reth's existing metrics endpoint once the node installs its Prometheus recorder.
+
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::time::Duration;
+
+/// Payload builder metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "evolve.payload_builder")]
+pub struct PayloadBuilderMetrics {
+    /// Number of blocks built
+    pub blocks_built: Counter,
+    /// Number of blocks for which parallel execution was selected
+    pub parallel_mode_selected: Counter,
+    /// Number of blocks for which sequential execution was selected
+    pub sequential_mode_selected: Counter,
+    /// Number of payload transactions dropped to stay within the txpool caps
+    pub truncated_transactions: Counter,
+    /// Number of blocks sealed early because the build deadline was reached
+    pub deadline_cut_offs: Counter,
+    /// Estimated fraction of a block's transactions in their largest group of
+    /// dependent ones, recorded when picking the execution mode
+    pub dependent_fraction: Histogram,
+}
+
+impl PayloadBuilderMetrics {
+    /// Record the execution mode selected for a block
+    pub fn record_mode(&self, parallel: bool) {
+        if parallel {
+            self.parallel_mode_selected.increment(1);
+        } else {
+            self.sequential_mode_selected.increment(1);
+        }
+    }
+}
+
+/// Parallel executor metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "evolve.parallel")]
+pub struct ParallelExecutorMetrics {
+    /// Time spent executing a block of transactions in parallel, in seconds
+    pub execution_duration: Histogram,
+    /// Number of conflicts detected during validation
+    pub conflicts: Counter,
+    /// Number of re-executions scheduled after a conflict
+    pub retries: Counter,
+    /// Number of transactions executed on the sequential path
+    pub sequential_fallbacks: Counter,
+}
+
+impl ParallelExecutorMetrics {
+    /// Record the outcome of executing a block in parallel
+    pub fn record_parallel_block(&self, duration: Duration, conflicts: usize, retries: usize) {
+        self.execution_duration.record(duration.as_secs_f64());
+        self.conflicts.increment(conflicts as u64);
+        self.retries.increment(retries as u64);
+    }
+
+    /// Record transactions that were executed sequentially
+    pub fn record_sequential_fallback(&self, transaction_count: usize) {
+        self.sequential_fallbacks.increment(transaction_count as u64);
+    }
+}
+
+/// MEV detection metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "evolve.mev")]
+pub struct MevMetrics {
+    /// Number of MEV opportunities detected
+    pub opportunities_detected: Counter,
+}
+
+/// Block attestation metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "evolve.attestation")]
+pub struct AttestationMetrics {
+    /// Number of attestations submitted successfully
+    pub successes: Counter,
+    /// Number of attestations that failed
+    pub failures: Counter,
+    /// Number of queued attestations dropped, as their block was already
+    /// finalized or the contract rejected them
+    pub dropped: Counter,
+    /// Number of failed attestations waiting to be retried
+    pub retry_queue_depth: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+    /// Value of the counter registered under `name`
+    fn counter(snapshotter: &Snapshotter, name: &str) -> Option<u64> {
+        snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| {
+            match value {
+                DebugValue::Counter(count) if key.key().name() == name => Some(count),
+                _ => None,
+            }
+        })
+    }
+
+    #[test]
+    fn test_payload_builder_mode_counters() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let metrics = PayloadBuilderMetrics::default();
+            metrics.record_mode(true);
+            metrics.record_mode(true);
+            metrics.record_mode(false);
+            metrics.blocks_built.increment(3);
+        });
+
+        assert_eq!(counter(&snapshotter, "evolve.payload_builder.parallel_mode_selected"), Some(2));
+        assert_eq!(counter(&snapshotter, "evolve.payload_builder.sequential_mode_selected"), Some(1));
+        assert_eq!(counter(&snapshotter, "evolve.payload_builder.blocks_built"), Some(3));
+    }
+
+    #[test]
+    fn test_parallel_executor_counters() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let metrics = ParallelExecutorMetrics::default();
+            metrics.record_parallel_block(Duration::from_millis(5), 2, 1);
+            metrics.record_parallel_block(Duration::from_millis(7), 1, 1);
+            metrics.record_sequential_fallback(4);
+        });
+
+        assert_eq!(counter(&snapshotter, "evolve.parallel.conflicts"), Some(3));
+        assert_eq!(counter(&snapshotter, "evolve.parallel.retries"), Some(2));
+        assert_eq!(counter(&snapshotter, "evolve.parallel.sequential_fallbacks"), Some(4));
+
+        let durations = snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| {
+            match value {
+                DebugValue::Histogram(values) if key.key().name() == "evolve.parallel.execution_duration" => {
+                    Some(values.len())
+                }
+                _ => None,
+            }
+        });
+        assert_eq!(durations, Some(2));
+    }
+
+    #[test]
+    fn test_mev_and_attestation_counters() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            MevMetrics::default().opportunities_detected.increment(5);
+            let attestation = AttestationMetrics::default();
+            attestation.successes.increment(1);
+            attestation.failures.increment(2);
+            attestation.dropped.increment(1);
+            attestation.retry_queue_depth.set(3.0);
+        });
+
+        assert_eq!(counter(&snapshotter, "evolve.mev.opportunities_detected"), Some(5));
+        assert_eq!(counter(&snapshotter, "evolve.attestation.successes"), Some(1));
+        assert_eq!(counter(&snapshotter, "evolve.attestation.failures"), Some(2));
+        assert_eq!(counter(&snapshotter, "evolve.attestation.dropped"), Some(1));
+
+        let depth = snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| match value {
+            DebugValue::Gauge(depth) if key.key().name() == "evolve.attestation.retry_queue_depth" => Some(depth),
+            _ => None,
+        });
+        assert_eq!(depth.map(|depth| depth.into_inner()), Some(3.0));
+    }
+}
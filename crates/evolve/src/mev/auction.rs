@@ -2,12 +2,226 @@
 //!
 //! Provides interface to interact with the MEVAuctionManager smart contract
 //! for bundle submission, execution tracking, and searcher management.
+//!
+//! A client connected to the contract sends every submission and settlement
+//! as a transaction and waits for it to succeed before updating its local view
+//! of the auction. A client created without a provider keeps the auction in
+//! memory only, which is what tests use.
+//!
+//! Bundles can be simulated against the parent state before the winner is
+//! selected, so a bundle that can't execute doesn't win the slot.
+//!
+//! A client with a [`CollateralSource`] only accepts bids the searcher's
+//! deposited collateral can cover, net of the searcher's other pending bids.
+//! Collateral is cached for a short time so a burst of bundles from the same
+//! searcher doesn't cost one RPC call each.
+//!
+//! Until its target block is selected for building, a searcher can cancel a
+//! pending bundle or replace it with a higher bid. On the contract, the old
+//! bundle is marked rejected and the replacement submitted as a new bundle.
+//!
+//! The client keeps per-searcher accounting to rank searchers, including how
+//! far winning bids exceed the second highest bid for their block. Searchers
+//! can be banned from bidding up to a block, e.g. by slashing logic reacting
+//! to a streak of failed bundles; their pending bundles are rejected.
+//!
+//! Bundles may carry their transactions encrypted to the sequencer's X25519
+//! key instead of listing their hashes (see [`super::privacy`]). They are
+//! decrypted when their block is selected for building, right before they're
+//! simulated, and rejected if the payload doesn't match the bundle hash.
+//!
+//! Every bundle goes through the [`BundleState`]s pending, selected and then
+//! executed or rejected. Bundles still pending once their target block is
+//! cleaned up expire. Each transition is logged under the
+//! `ande::mev::bundles` target with the bundle hash.
 
-use alloy_primitives::{Address, U256, B256};
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use alloy::{
+    network::{Ethereum, EthereumWallet},
+    providers::{DynProvider, PendingTransactionBuilder, PendingTransactionError, Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
+};
+use alloy_consensus::transaction::{Recovered, SignerRecoverable};
+use alloy_primitives::{Address, Bytes, U256, B256};
+use async_trait::async_trait;
+use ande_consensus_bindings::MEVAuctionManager::{self, MEVAuctionManagerInstance};
+use super::{
+    privacy::{bundle_commitment, decrypt_bundle, BundleDecryptionError},
+    registry::BundleTransactionRegistry,
+};
+use crate::{evm_config::AndeEvmConfig, rpc::health::ConnectionHealth};
+use reth_evm::{ConfigureEvm, Evm, NextBlockEnvAttributes};
+use reth_primitives::{SealedHeader, TransactionSigned};
+use revm::{
+    database::CacheDB,
+    database_interface::{Database, DatabaseRef},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{sync::RwLock, time::Instant};
+use x25519_dalek::{PublicKey, StaticSecret};
 use tracing::{debug, info, warn};
 
+/// Errors returned by the [`MevAuctionClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum MevAuctionError {
+    /// The bundle failed validation before submission
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(#[from] BundleError),
+    /// The RPC URL could not be parsed
+    #[error("Invalid RPC URL {url}: {reason}")]
+    InvalidRpcUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// The contract call could not be sent, e.g. because gas estimation reverted
+    #[error("MEVAuctionManager call failed: {0}")]
+    Contract(#[from] alloy::contract::Error),
+    /// The transaction was sent, but its receipt could not be obtained
+    #[error("MEVAuctionManager transaction not confirmed: {0}")]
+    Confirmation(#[from] PendingTransactionError),
+    /// The transaction was included but reverted
+    #[error("MEVAuctionManager {call} reverted in transaction {tx_hash}")]
+    Reverted {
+        /// Contract function called
+        call: &'static str,
+        /// Hash of the reverted transaction
+        tx_hash: B256,
+    },
+}
+
+impl MevAuctionError {
+    /// Whether the contract's node could not be reached
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            Self::Contract(alloy::contract::Error::TransportError(err))
+            | Self::Confirmation(PendingTransactionError::TransportError(err)) => !err.is_error_resp(),
+            _ => false,
+        }
+    }
+}
+
+/// Reasons a bundle is refused before it's submitted to the auction
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundleError {
+    /// The bundle has no transactions
+    #[error("Bundle must contain at least one transaction")]
+    Empty,
+    /// The bid is zero
+    #[error("Bid amount must be positive")]
+    ZeroBid,
+    /// The searcher's collateral doesn't cover the bid
+    #[error("Searcher {searcher} has {available} collateral available, bid needs {required}")]
+    InsufficientCollateral {
+        /// Searcher submitting the bundle
+        searcher: Address,
+        /// Bid amount
+        required: U256,
+        /// Deposited collateral not already committed to other pending bids
+        available: U256,
+    },
+    /// No pending bundle has this hash
+    #[error("Bundle {0} is not pending")]
+    UnknownBundle(B256),
+    /// Only the searcher who submitted a bundle may replace or cancel it
+    #[error("Bundle {bundle_hash} was not submitted by {searcher}")]
+    NotBundleSearcher {
+        /// Bundle to replace or cancel
+        bundle_hash: B256,
+        /// Searcher requesting the change
+        searcher: Address,
+    },
+    /// A replacement doesn't raise the bid by the minimum bump
+    #[error("Replacement bid {offered} is below the minimum of {required}")]
+    BidBumpTooLow {
+        /// Lowest bid accepted as a replacement
+        required: U256,
+        /// Bid of the replacement
+        offered: U256,
+    },
+    /// The target block was already selected for building
+    #[error("Bundles for block {0} were already selected")]
+    BlockSelected(u64),
+    /// An encrypted bundle also lists its transactions in plaintext
+    #[error("Encrypted bundle must not list its transactions")]
+    EncryptedWithTransactions,
+    /// An encrypted payload couldn't be revealed
+    #[error("Encrypted bundle rejected: {0}")]
+    Decryption(#[from] BundleDecryptionError),
+    /// The searcher is banned from bidding on the target block
+    #[error("Searcher {searcher} is banned until block {until_block}")]
+    SearcherBanned {
+        /// Searcher submitting the bundle
+        searcher: Address,
+        /// Last block the ban covers
+        until_block: u64,
+    },
+}
+
+/// Source of the collateral searchers have deposited to back their bids
+#[async_trait]
+pub trait CollateralSource: fmt::Debug + Send + Sync {
+    /// Collateral currently deposited by `searcher`
+    async fn collateral(&self, searcher: Address) -> Result<U256, MevAuctionError>;
+}
+
+#[async_trait]
+impl CollateralSource for MEVAuctionManagerInstance<DynProvider> {
+    async fn collateral(&self, searcher: Address) -> Result<U256, MevAuctionError> {
+        Ok(self.collateralOf(searcher).call().await?)
+    }
+}
+
+/// Default minimum increase of a replacement bid, in percent
+pub const DEFAULT_MIN_BID_BUMP_PERCENT: u64 = 10;
+
+/// Default time a searcher's collateral is cached for
+pub const DEFAULT_COLLATERAL_TTL: Duration = Duration::from_secs(12);
+
+/// Default maximum number of searchers accounted for
+pub const DEFAULT_MAX_TRACKED_SEARCHERS: usize = 1024;
+
+/// Number of settled and expired bundles whose outcome is kept
+const MAX_FINISHED_BUNDLES: usize = 10_000;
+
+/// Collateral lookups, cached per searcher for a fixed time
+#[derive(Debug)]
+struct CollateralCache {
+    /// Where collateral is read from
+    source: Arc<dyn CollateralSource>,
+    /// How long a lookup stays valid
+    ttl: Duration,
+    /// Latest lookup per searcher, with the time it was made
+    entries: RwLock<HashMap<Address, (U256, Instant)>>,
+}
+
+impl CollateralCache {
+    /// Collateral of `searcher`, from the cache if the last lookup hasn't expired
+    async fn get(&self, searcher: Address) -> Result<U256, MevAuctionError> {
+        if let Some((collateral, fetched_at)) = self.entries.read().await.get(&searcher) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(*collateral);
+            }
+        }
+
+        let collateral = self.source.collateral(searcher).await?;
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.ttl);
+        entries.insert(searcher, (collateral, Instant::now()));
+        Ok(collateral)
+    }
+}
+
 /// Bundle submission for MEV auction
 #[derive(Debug, Clone)]
 pub struct BundleSubmission {
@@ -17,10 +231,95 @@ pub struct BundleSubmission {
     pub bid_amount: U256,
     /// Target block number
     pub target_block: u64,
-    /// Bundle transactions
+    /// Bundle transactions; empty for an encrypted bundle until it's revealed
     pub transactions: Vec<B256>,
     /// Searcher address
     pub searcher: Address,
+    /// Signed transactions encrypted to the sequencer's key, committed to by the bundle hash
+    pub encrypted_payload: Option<Bytes>,
+}
+
+/// Performance of one searcher in the auction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearcherStats {
+    /// Searcher address
+    pub searcher: Address,
+    /// Bundles submitted, replacements included
+    pub bundles_submitted: u64,
+    /// Bundles executed
+    pub bundles_executed: u64,
+    /// Bundles rejected
+    pub bundles_rejected: u64,
+    /// Total bids paid for executed bundles
+    pub total_bids_paid: U256,
+    /// Total amount executed bids exceeded the second highest bid for their block by
+    pub total_overbid: U256,
+    /// Executed bundles that won against at least one other bid
+    pub contested_wins: u64,
+    /// Bundles rejected since the searcher's last executed bundle
+    pub failure_streak: u64,
+    /// Latest block targeted by one of the searcher's bundles
+    pub last_target_block: u64,
+}
+
+impl SearcherStats {
+    /// Average amount contested wins exceeded the second highest bid by
+    pub fn average_overbid(&self) -> U256 {
+        if self.contested_wins > 0 {
+            self.total_overbid / U256::from(self.contested_wins)
+        } else {
+            U256::ZERO
+        }
+    }
+}
+
+/// Stage of a bundle in the auction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleState {
+    /// Waiting for its target block to be built
+    Pending,
+    /// Won its target block, which is being built
+    Selected,
+    /// Executed in its target block
+    Executed,
+    /// Rejected, e.g. because it failed to execute or its searcher was banned
+    Rejected,
+    /// Its target block passed without the bundle being settled
+    Expired,
+}
+
+impl BundleState {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            BundleState::Pending => "pending",
+            BundleState::Selected => "selected",
+            BundleState::Executed => "executed",
+            BundleState::Rejected => "rejected",
+            BundleState::Expired => "expired",
+        }
+    }
+}
+
+/// Where a bundle is in the auction, as reported by [`MevAuctionClient::bundle_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleStatus {
+    /// Current stage
+    pub state: BundleState,
+    /// Block the bundle targets, unknown for bundles settled without being pending
+    pub target_block: Option<u64>,
+    /// Bid of the bundle, zero if unknown
+    pub bid_amount: U256,
+    /// MEV captured, for executed bundles
+    pub mev_captured: Option<U256>,
+    /// Why the bundle was rejected, for rejected bundles
+    pub rejection_reason: Option<String>,
+}
+
+/// Log a bundle entering `state`
+fn log_transition(bundle_hash: B256, state: BundleState) {
+    info!(target: "ande::mev::bundles", %bundle_hash, state = state.name(), "Bundle state changed");
 }
 
 /// Bundle execution result
@@ -28,12 +327,36 @@ pub struct BundleSubmission {
 pub struct BundleExecutionResult {
     /// Whether bundle was executed
     pub executed: bool,
+    /// Bid of the bundle, zero if it wasn't pending when settled
+    pub bid_amount: U256,
     /// Actual MEV captured
     pub mev_captured: U256,
     /// Bid paid
     pub bid_paid: U256,
     /// Reason if rejected
     pub rejection_reason: Option<String>,
+    /// Simulation of the bundle before selection, if it was simulated
+    pub simulation: Option<BundleSimulation>,
+    /// Block the bundle targeted, if it was pending when settled
+    pub target_block: Option<u64>,
+}
+
+/// Outcome of executing a bundle against the parent state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleSimulation {
+    /// Gas used by the bundle transactions executed
+    pub gas_used: u64,
+    /// Increase of the block beneficiary's balance over the bundle
+    pub beneficiary_profit: U256,
+    /// First transaction that reverted or couldn't be executed, with the reason
+    pub failed_tx: Option<(B256, String)>,
+}
+
+impl BundleSimulation {
+    /// Whether every transaction of the bundle executed successfully
+    pub fn succeeded(&self) -> bool {
+        self.failed_tx.is_none()
+    }
 }
 
 /// MEV Auction client for sequencer integration
@@ -42,22 +365,173 @@ pub struct MevAuctionClient {
     contract_address: Address,
     /// Sequencer address
     sequencer_address: Address,
+    /// Auction manager contract, signing as the sequencer; `None` keeps the auction in memory only
+    contract: Option<MEVAuctionManagerInstance<DynProvider>>,
     /// Pending bundles
     pending_bundles: Arc<RwLock<Vec<BundleSubmission>>>,
     /// Executed bundles
     executed_bundles: Arc<RwLock<Vec<(B256, BundleExecutionResult)>>>,
+    /// Latest simulation of each pending bundle
+    simulations: Arc<RwLock<HashMap<B256, BundleSimulation>>>,
+    /// Signed transactions of pending bundles
+    transactions: Arc<BundleTransactionRegistry>,
+    /// Searcher collateral bids are checked against; `None` accepts any bid
+    collateral: Option<Arc<CollateralCache>>,
+    /// Minimum increase of a replacement bid over the bid it replaces, in percent
+    min_bid_bump_percent: u64,
+    /// Blocks a winning bundle was selected for; their bundles can no longer change
+    selected_blocks: Arc<RwLock<BTreeSet<u64>>>,
+    /// Winning bundle of each selected block that had one
+    winners: Arc<RwLock<HashMap<u64, B256>>>,
+    /// Bundles dropped by cleanup while still pending, oldest first
+    expired_bundles: Arc<RwLock<VecDeque<BundleSubmission>>>,
+    /// Bundles replaced by a higher bid
+    replaced_bundles: Arc<AtomicUsize>,
+    /// Bundles cancelled by their searcher
+    cancelled_bundles: Arc<AtomicUsize>,
+    /// Accounting per searcher
+    searchers: Arc<RwLock<HashMap<Address, SearcherStats>>>,
+    /// Maximum number of searchers accounted for; the least recently active are evicted first
+    max_tracked_searchers: usize,
+    /// Banned searchers, with the last block their ban covers
+    bans: Arc<RwLock<HashMap<Address, u64>>>,
+    /// Second highest bid for the block of each selected bundle that had competition
+    second_prices: Arc<RwLock<HashMap<B256, U256>>>,
+    /// Sequencer key encrypted bundles are revealed with
+    decryption_key: Arc<std::sync::RwLock<Option<StaticSecret>>>,
+    /// Calls made to the contract's node, as reported by `ande_health`
+    connection: Arc<ConnectionHealth>,
+}
+
+impl fmt::Debug for MevAuctionClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MevAuctionClient")
+            .field("contract_address", &self.contract_address)
+            .field("sequencer_address", &self.sequencer_address)
+            .field("onchain", &self.is_onchain())
+            .finish_non_exhaustive()
+    }
 }
 
 impl MevAuctionClient {
-    /// Create new auction client
+    /// Create an auction client keeping the auction in memory, without calling the contract
     pub fn new(contract_address: Address, sequencer_address: Address) -> Self {
         Self {
             contract_address,
             sequencer_address,
+            contract: None,
             pending_bundles: Arc::new(RwLock::new(Vec::new())),
             executed_bundles: Arc::new(RwLock::new(Vec::new())),
+            simulations: Arc::new(RwLock::new(HashMap::new())),
+            transactions: Arc::new(BundleTransactionRegistry::new()),
+            collateral: None,
+            min_bid_bump_percent: DEFAULT_MIN_BID_BUMP_PERCENT,
+            selected_blocks: Arc::new(RwLock::new(BTreeSet::new())),
+            winners: Arc::new(RwLock::new(HashMap::new())),
+            expired_bundles: Arc::new(RwLock::new(VecDeque::new())),
+            replaced_bundles: Arc::new(AtomicUsize::new(0)),
+            cancelled_bundles: Arc::new(AtomicUsize::new(0)),
+            searchers: Arc::new(RwLock::new(HashMap::new())),
+            max_tracked_searchers: DEFAULT_MAX_TRACKED_SEARCHERS,
+            bans: Arc::new(RwLock::new(HashMap::new())),
+            second_prices: Arc::new(RwLock::new(HashMap::new())),
+            decryption_key: Arc::new(std::sync::RwLock::new(None)),
+            connection: Arc::new(ConnectionHealth::new()),
         }
     }
+
+    /// Create an auction client calling the contract at `contract_address` through `provider`
+    ///
+    /// The provider must sign transactions as `sequencer_address`.
+    pub fn with_provider(contract_address: Address, sequencer_address: Address, provider: DynProvider) -> Self {
+        Self {
+            contract: Some(MEVAuctionManager::new(contract_address, provider)),
+            ..Self::new(contract_address, sequencer_address)
+        }
+    }
+
+    /// Connect to the contract at `contract_address` over HTTP, signing with `signer`
+    pub fn connect(
+        rpc_url: &str,
+        contract_address: Address,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, MevAuctionError> {
+        let url = rpc_url.parse::<Url>().map_err(|err| MevAuctionError::InvalidRpcUrl {
+            url: rpc_url.to_string(),
+            reason: err.to_string(),
+        })?;
+        let sequencer_address = signer.address();
+        let provider = ProviderBuilder::new().wallet(EthereumWallet::from(signer)).connect_http(url).erased();
+
+        info!("MEV auction client connected: contract={}, sequencer={}", contract_address, sequencer_address);
+        Ok(Self::with_provider(contract_address, sequencer_address, provider))
+    }
+
+    /// Reject bids not covered by the searcher's collateral in `source`, caching lookups for `ttl`
+    pub fn with_collateral_source(mut self, source: Arc<dyn CollateralSource>, ttl: Duration) -> Self {
+        self.collateral = Some(Arc::new(CollateralCache { source, ttl, entries: RwLock::new(HashMap::new()) }));
+        self
+    }
+
+    /// Require replacement bids to exceed the bid they replace by at least `percent`
+    pub fn with_min_bid_bump(mut self, percent: u64) -> Self {
+        self.min_bid_bump_percent = percent;
+        self
+    }
+
+    /// Account for at most `max` searchers, evicting the least recently active on cleanup
+    pub fn with_max_tracked_searchers(mut self, max: usize) -> Self {
+        self.max_tracked_searchers = max;
+        self
+    }
+
+    /// Reject bids not covered by the searcher's collateral deposited in the contract
+    ///
+    /// Has no effect on a client keeping the auction in memory only.
+    pub fn with_contract_collateral(self, ttl: Duration) -> Self {
+        match self.contract.clone() {
+            Some(contract) => self.with_collateral_source(Arc::new(contract), ttl),
+            None => self,
+        }
+    }
+
+    /// Registry the signed transactions of submitted bundles are resolved from
+    ///
+    /// Transactions are forgotten once no pending bundle refers to them.
+    pub fn bundle_transactions(&self) -> &Arc<BundleTransactionRegistry> {
+        &self.transactions
+    }
+
+    /// Reveal encrypted bundles with the X25519 key `secret`, replacing any earlier key
+    pub fn register_decryption_key(&self, secret: [u8; 32]) {
+        *self.decryption_key.write().unwrap() = Some(StaticSecret::from(secret));
+        info!("Bundle decryption key registered");
+    }
+
+    /// Public key searchers encrypt bundles to, if a decryption key is registered
+    pub fn encryption_public_key(&self) -> Option<PublicKey> {
+        self.decryption_key.read().unwrap().as_ref().map(PublicKey::from)
+    }
+
+    /// Whether submissions and settlements are sent to the contract
+    pub fn is_onchain(&self) -> bool {
+        self.contract.is_some()
+    }
+
+    /// Calls made to the contract's node, to report on in `ande_health`
+    pub fn connection_health(&self) -> Arc<ConnectionHealth> {
+        self.connection.clone()
+    }
+
+    /// Auction manager contract address
+    pub fn contract_address(&self) -> Address {
+        self.contract_address
+    }
+
+    /// Sequencer address settling the auction
+    pub fn sequencer_address(&self) -> Address {
+        self.sequencer_address
+    }
     
     /// Get pending bundles for a target block
     pub async fn get_bundles_for_block(&self, block_number: u64) -> Vec<BundleSubmission> {
@@ -70,28 +544,306 @@ impl MevAuctionClient {
     }
     
     /// Submit a bundle to the auction
-    pub async fn submit_bundle(&self, bundle: BundleSubmission) -> Result<(), String> {
-        // Validate bundle
-        if bundle.transactions.is_empty() {
-            return Err("Bundle must contain at least one transaction".to_string());
-        }
-        
-        if bundle.bid_amount == U256::ZERO {
-            return Err("Bid amount must be positive".to_string());
-        }
+    pub async fn submit_bundle(&self, bundle: BundleSubmission) -> Result<(), MevAuctionError> {
+        self.validate_bundle(&bundle, None).await?;
+        self.send_submission(&bundle).await?;
         
-        // In production, this would call the smart contract
-        // For now, store in memory
-        let mut bundles = self.pending_bundles.write().await;
-        bundles.push(bundle.clone());
+        self.pending_bundles.write().await.push(bundle.clone());
+        self.update_searcher(bundle.searcher, bundle.target_block, |stats| stats.bundles_submitted += 1).await;
         
         info!(
             "Bundle submitted: hash={}, bid={}, target_block={}",
             bundle.bundle_hash, bundle.bid_amount, bundle.target_block
         );
+        log_transition(bundle.bundle_hash, BundleState::Pending);
         
         Ok(())
     }
+
+    /// Submit a bundle of signed `transactions` from `searcher`, returning its hash
+    ///
+    /// The bundle hash commits to the transactions, see [`bundle_commitment`].
+    /// The transactions are registered for the payload builder, and released
+    /// again if the bundle is refused.
+    pub async fn submit_signed_bundle(
+        &self,
+        searcher: Address,
+        bid_amount: U256,
+        target_block: u64,
+        transactions: Vec<TransactionSigned>,
+    ) -> Result<B256, MevAuctionError> {
+        let hashes: Vec<B256> = transactions.iter().map(|tx| *tx.hash()).collect();
+        let bundle = BundleSubmission {
+            bundle_hash: bundle_commitment(&hashes),
+            bid_amount,
+            target_block,
+            transactions: hashes,
+            searcher,
+            encrypted_payload: None,
+        };
+
+        self.transactions.insert(transactions);
+        if let Err(err) = self.submit_bundle(bundle.clone()).await {
+            self.release_transactions(&bundle, &self.pending_bundles.read().await);
+            return Err(err);
+        }
+        Ok(bundle.bundle_hash)
+    }
+
+    /// Replace the pending bundle `old_hash` with `new_bundle`
+    ///
+    /// The replacement must come from the same searcher and raise the bid by
+    /// at least the minimum bump. Neither bundle's target block may have been
+    /// selected yet.
+    pub async fn replace_bundle(
+        &self,
+        old_hash: B256,
+        new_bundle: BundleSubmission,
+    ) -> Result<(), MevAuctionError> {
+        let old = self.changeable_bundle(old_hash, new_bundle.searcher).await?;
+        if self.selected_blocks.read().await.contains(&new_bundle.target_block) {
+            return Err(BundleError::BlockSelected(new_bundle.target_block).into());
+        }
+
+        // A replacement always raises the bid, even when the bump rounds down to zero
+        let bump = old.bid_amount.saturating_mul(U256::from(self.min_bid_bump_percent)) / U256::from(100);
+        let required = old.bid_amount.saturating_add(bump.max(U256::from(1)));
+        if new_bundle.bid_amount < required {
+            return Err(BundleError::BidBumpTooLow { required, offered: new_bundle.bid_amount }.into());
+        }
+        self.validate_bundle(&new_bundle, Some(old_hash)).await?;
+
+        if let Some(contract) = &self.contract {
+            let reason = format!("Replaced by {}", new_bundle.bundle_hash);
+            let sent = contract.markBundleRejected(old_hash, reason).send().await;
+            self.confirm("markBundleRejected", sent).await?;
+        }
+        self.send_submission(&new_bundle).await?;
+
+        let mut pending = self.pending_bundles.write().await;
+        match pending.iter_mut().find(|b| b.bundle_hash == old_hash) {
+            Some(slot) => *slot = new_bundle.clone(),
+            None => pending.push(new_bundle.clone()),
+        }
+        self.release_transactions(&old, &pending);
+        drop(pending);
+        self.simulations.write().await.remove(&old_hash);
+        self.replaced_bundles.fetch_add(1, Ordering::Relaxed);
+        self.update_searcher(new_bundle.searcher, new_bundle.target_block, |stats| stats.bundles_submitted += 1)
+            .await;
+
+        info!(
+            "Bundle replaced: old={}, new={}, bid={}, target_block={}",
+            old_hash, new_bundle.bundle_hash, new_bundle.bid_amount, new_bundle.target_block
+        );
+        log_transition(new_bundle.bundle_hash, BundleState::Pending);
+
+        Ok(())
+    }
+
+    /// Cancel the pending bundle `bundle_hash` on behalf of `searcher`
+    ///
+    /// Only the searcher who submitted the bundle may cancel it, and only
+    /// before its target block is selected.
+    pub async fn cancel_bundle(&self, bundle_hash: B256, searcher: Address) -> Result<(), MevAuctionError> {
+        self.changeable_bundle(bundle_hash, searcher).await?;
+
+        if let Some(contract) = &self.contract {
+            let sent = contract.markBundleRejected(bundle_hash, "Cancelled by searcher".to_string()).send().await;
+            self.confirm("markBundleRejected", sent).await?;
+        }
+
+        let mut pending = self.pending_bundles.write().await;
+        if let Some(pos) = pending.iter().position(|b| b.bundle_hash == bundle_hash) {
+            let cancelled = pending.remove(pos);
+            self.release_transactions(&cancelled, &pending);
+        }
+        drop(pending);
+        self.simulations.write().await.remove(&bundle_hash);
+        self.cancelled_bundles.fetch_add(1, Ordering::Relaxed);
+
+        debug!("Bundle cancelled: hash={}, searcher={}", bundle_hash, searcher);
+
+        Ok(())
+    }
+
+    /// Forget the transactions of `bundle` that none of the `pending` bundles refers to
+    fn release_transactions(&self, bundle: &BundleSubmission, pending: &[BundleSubmission]) {
+        self.transactions.remove(
+            bundle.transactions.iter().filter(|hash| !pending.iter().any(|b| b.transactions.contains(hash))),
+        );
+    }
+
+    /// Remove the pending bundle `bundle_hash`, releasing its transactions
+    async fn take_pending(&self, bundle_hash: B256) -> Option<BundleSubmission> {
+        let mut pending = self.pending_bundles.write().await;
+        let pos = pending.iter().position(|b| b.bundle_hash == bundle_hash)?;
+        let bundle = pending.remove(pos);
+        self.release_transactions(&bundle, &pending);
+        Some(bundle)
+    }
+
+    /// Apply `update` to the accounting of `searcher`, active up to `target_block`
+    async fn update_searcher(&self, searcher: Address, target_block: u64, update: impl FnOnce(&mut SearcherStats)) {
+        let mut searchers = self.searchers.write().await;
+        let stats = searchers.entry(searcher).or_insert_with(|| SearcherStats { searcher, ..Default::default() });
+        stats.last_target_block = stats.last_target_block.max(target_block);
+        update(stats);
+    }
+
+    /// Ban `searcher` from bidding on blocks up to `until_block`, replacing any earlier ban
+    ///
+    /// The searcher's pending bundles for those blocks are rejected, except
+    /// for blocks already selected for building.
+    pub async fn ban_searcher(&self, searcher: Address, until_block: u64) -> Result<(), MevAuctionError> {
+        self.bans.write().await.insert(searcher, until_block);
+        warn!("Searcher banned: searcher={}, until_block={}", searcher, until_block);
+
+        let selected = self.selected_blocks.read().await.clone();
+        let banned: Vec<B256> = self
+            .pending_bundles
+            .read()
+            .await
+            .iter()
+            .filter(|b| b.searcher == searcher && b.target_block <= until_block)
+            .filter(|b| !selected.contains(&b.target_block))
+            .map(|b| b.bundle_hash)
+            .collect();
+        for bundle_hash in banned {
+            self.mark_bundle_rejected(bundle_hash, format!("Searcher banned until block {until_block}")).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Last block `searcher` is banned from bidding on, if banned
+    pub async fn banned_until(&self, searcher: Address) -> Option<u64> {
+        self.bans.read().await.get(&searcher).copied()
+    }
+
+    /// Accounting of `searcher`, if it's tracked
+    pub async fn get_searcher_stats(&self, searcher: Address) -> Option<SearcherStats> {
+        self.searchers.read().await.get(&searcher).cloned()
+    }
+
+    /// The `n` searchers who paid the most in bids, then executed the most bundles
+    pub async fn top_searchers(&self, n: usize) -> Vec<SearcherStats> {
+        let mut searchers: Vec<_> = self.searchers.read().await.values().cloned().collect();
+        searchers.sort_by(|a, b| {
+            b.total_bids_paid
+                .cmp(&a.total_bids_paid)
+                .then(b.bundles_executed.cmp(&a.bundles_executed))
+                .then(a.searcher.cmp(&b.searcher))
+        });
+        searchers.truncate(n);
+        searchers
+    }
+
+    /// Pending bundle `bundle_hash`, if `searcher` may still replace or cancel it
+    async fn changeable_bundle(
+        &self,
+        bundle_hash: B256,
+        searcher: Address,
+    ) -> Result<BundleSubmission, BundleError> {
+        let bundle = self
+            .pending_bundles
+            .read()
+            .await
+            .iter()
+            .find(|b| b.bundle_hash == bundle_hash)
+            .cloned()
+            .ok_or(BundleError::UnknownBundle(bundle_hash))?;
+        if bundle.searcher != searcher {
+            return Err(BundleError::NotBundleSearcher { bundle_hash, searcher });
+        }
+        if self.selected_blocks.read().await.contains(&bundle.target_block) {
+            return Err(BundleError::BlockSelected(bundle.target_block));
+        }
+        Ok(bundle)
+    }
+
+    /// Check `bundle` can be accepted, ignoring the bid of the bundle it replaces, if any
+    async fn validate_bundle(
+        &self,
+        bundle: &BundleSubmission,
+        replaces: Option<B256>,
+    ) -> Result<(), MevAuctionError> {
+        if bundle.encrypted_payload.is_some() {
+            if !bundle.transactions.is_empty() {
+                return Err(BundleError::EncryptedWithTransactions.into());
+            }
+            if self.encryption_public_key().is_none() {
+                return Err(BundleError::Decryption(BundleDecryptionError::NoDecryptionKey).into());
+            }
+        } else if bundle.transactions.is_empty() {
+            return Err(BundleError::Empty.into());
+        }
+        
+        if bundle.bid_amount == U256::ZERO {
+            return Err(BundleError::ZeroBid.into());
+        }
+
+        if let Some(until_block) = self.banned_until(bundle.searcher).await {
+            if bundle.target_block <= until_block {
+                return Err(BundleError::SearcherBanned { searcher: bundle.searcher, until_block }.into());
+            }
+        }
+
+        if let Some(collateral) = &self.collateral {
+            let deposited = collateral.get(bundle.searcher).await?;
+            let committed = self
+                .pending_bundles
+                .read()
+                .await
+                .iter()
+                .filter(|pending| pending.searcher == bundle.searcher && Some(pending.bundle_hash) != replaces)
+                .fold(U256::ZERO, |total, pending| total.saturating_add(pending.bid_amount));
+            let available = deposited.saturating_sub(committed);
+            if bundle.bid_amount > available {
+                return Err(BundleError::InsufficientCollateral {
+                    searcher: bundle.searcher,
+                    required: bundle.bid_amount,
+                    available,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wait for the receipt of a transaction `sent` to the contract, recording
+    /// whether its node could be reached
+    async fn confirm(
+        &self,
+        call: &'static str,
+        sent: Result<PendingTransactionBuilder<Ethereum>, alloy::contract::Error>,
+    ) -> Result<B256, MevAuctionError> {
+        let result = match sent {
+            Ok(pending) => confirm_receipt(call, pending).await,
+            Err(err) => Err(err.into()),
+        };
+        self.connection.record(!matches!(&result, Err(err) if err.is_connection_error()));
+        result
+    }
+
+    /// Submit `bundle` to the contract, if the client is connected to one
+    async fn send_submission(&self, bundle: &BundleSubmission) -> Result<(), MevAuctionError> {
+        if let Some(contract) = &self.contract {
+            let sent = contract
+                .submitBundle(
+                    bundle.bundle_hash,
+                    bundle.searcher,
+                    bundle.bid_amount,
+                    U256::from(bundle.target_block),
+                    bundle.transactions.clone(),
+                )
+                .send()
+                .await;
+            self.confirm("submitBundle", sent).await?;
+        }
+        Ok(())
+    }
     
     /// Mark bundle as executed
     pub async fn mark_bundle_executed(
@@ -99,21 +851,43 @@ impl MevAuctionClient {
         bundle_hash: B256,
         mev_captured: U256,
         bid_paid: U256,
-    ) -> Result<(), String> {
+    ) -> Result<(), MevAuctionError> {
+        if let Some(contract) = &self.contract {
+            let sent = contract.markBundleExecuted(bundle_hash, mev_captured, bid_paid).send().await;
+            self.confirm("markBundleExecuted", sent).await?;
+        }
+
         // Remove from pending
-        let mut pending = self.pending_bundles.write().await;
-        if let Some(pos) = pending.iter().position(|b| b.bundle_hash == bundle_hash) {
-            pending.remove(pos);
-        } else {
-            warn!("Attempted to mark unknown bundle as executed: {}", bundle_hash);
+        let settled = self.take_pending(bundle_hash).await;
+        let second_price = self.second_prices.write().await.remove(&bundle_hash);
+        match &settled {
+            Some(bundle) => {
+                self.update_searcher(bundle.searcher, bundle.target_block, |stats| {
+                    stats.bundles_executed += 1;
+                    stats.total_bids_paid = stats.total_bids_paid.saturating_add(bid_paid);
+                    if let Some(second_price) = second_price {
+                        stats.total_overbid =
+                            stats.total_overbid.saturating_add(bid_paid.saturating_sub(second_price));
+                        stats.contested_wins += 1;
+                    }
+                    stats.failure_streak = 0;
+                })
+                .await;
+            }
+            None => warn!("Attempted to mark unknown bundle as executed: {}", bundle_hash),
         }
+        let (target_block, bid_amount) =
+            settled.map_or((None, U256::ZERO), |bundle| (Some(bundle.target_block), bundle.bid_amount));
         
         // Add to executed
         let result = BundleExecutionResult {
             executed: true,
+            bid_amount,
             mev_captured,
             bid_paid,
             rejection_reason: None,
+            simulation: self.simulations.write().await.remove(&bundle_hash),
+            target_block,
         };
         
         let mut executed = self.executed_bundles.write().await;
@@ -123,9 +897,7 @@ impl MevAuctionClient {
             "Bundle executed: hash={}, mev_captured={}, bid_paid={}",
             bundle_hash, mev_captured, bid_paid
         );
-        
-        // In production, this would call the smart contract
-        // self.call_contract_mark_executed(bundle_hash, mev_captured, bid_paid).await?;
+        log_transition(bundle_hash, BundleState::Executed);
         
         Ok(())
     }
@@ -135,60 +907,213 @@ impl MevAuctionClient {
         &self,
         bundle_hash: B256,
         reason: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), MevAuctionError> {
+        if let Some(contract) = &self.contract {
+            let sent = contract.markBundleRejected(bundle_hash, reason.clone()).send().await;
+            self.confirm("markBundleRejected", sent).await?;
+        }
+
         // Remove from pending
-        let mut pending = self.pending_bundles.write().await;
-        if let Some(pos) = pending.iter().position(|b| b.bundle_hash == bundle_hash) {
-            pending.remove(pos);
+        let rejected = self.take_pending(bundle_hash).await;
+        self.second_prices.write().await.remove(&bundle_hash);
+        if let Some(bundle) = &rejected {
+            self.update_searcher(bundle.searcher, bundle.target_block, |stats| {
+                stats.bundles_rejected += 1;
+                stats.failure_streak += 1;
+            })
+            .await;
         }
+        let (target_block, bid_amount) =
+            rejected.map_or((None, U256::ZERO), |bundle| (Some(bundle.target_block), bundle.bid_amount));
         
         // Add to executed with rejection
         let result = BundleExecutionResult {
             executed: false,
+            bid_amount,
             mev_captured: U256::ZERO,
             bid_paid: U256::ZERO,
             rejection_reason: Some(reason.clone()),
+            simulation: self.simulations.write().await.remove(&bundle_hash),
+            target_block,
         };
         
         let mut executed = self.executed_bundles.write().await;
         executed.push((bundle_hash, result));
         
         debug!("Bundle rejected: hash={}, reason={}", bundle_hash, reason);
-        
-        // In production, this would call the smart contract
-        // self.call_contract_mark_rejected(bundle_hash, reason).await?;
+        log_transition(bundle_hash, BundleState::Rejected);
         
         Ok(())
     }
     
+    /// Execute the transactions of `bundle` in order against `state`, on top of `parent_header`
+    ///
+    /// `transactions` supplies the signed transactions of the bundle, fetched
+    /// from the pool or submitted raw by the searcher; a bundle transaction
+    /// missing from it fails the simulation. Execution stops at the first
+    /// transaction that reverts or is invalid. The result is kept for
+    /// [`Self::select_winning_bundle`] and the bundle's execution result.
+    pub async fn simulate_bundle<DB>(
+        &self,
+        bundle: &BundleSubmission,
+        transactions: &[TransactionSigned],
+        parent_header: &SealedHeader,
+        next_block_attrs: &NextBlockEnvAttributes,
+        evm_config: &AndeEvmConfig,
+        state: DB,
+    ) -> BundleSimulation
+    where
+        DB: DatabaseRef + fmt::Debug,
+        DB::Error: Send + Sync + 'static,
+    {
+        let simulation = simulate(bundle, transactions, parent_header, next_block_attrs, evm_config, state);
+        if let Some((tx_hash, reason)) = &simulation.failed_tx {
+            debug!("Bundle simulation failed: hash={}, tx={}, reason={}", bundle.bundle_hash, tx_hash, reason);
+        }
+
+        self.simulations.write().await.insert(bundle.bundle_hash, simulation.clone());
+        simulation
+    }
+
     /// Select winning bundle for a block (highest bid)
+    ///
+    /// Bundles whose latest simulation failed are skipped. Once a block is
+    /// selected, its bundles can no longer be replaced or cancelled.
+    ///
+    /// An encrypted winner is revealed: its transactions are registered in
+    /// [`Self::bundle_transactions`] and their hashes returned with the bundle.
+    /// A bundle that can't be revealed is rejected and the next highest bid
+    /// is tried.
     pub async fn select_winning_bundle(&self, block_number: u64) -> Option<BundleSubmission> {
+        self.selected_blocks.write().await.insert(block_number);
         let bundles = self.get_bundles_for_block(block_number).await;
         
         if bundles.is_empty() {
             return None;
         }
         
-        // Find bundle with highest bid
-        let winner = bundles
-            .iter()
-            .max_by(|a, b| a.bid_amount.cmp(&b.bid_amount))?
-            .clone();
+        // Rank bundles by bid among those not known to fail
+        let simulations = self.simulations.read().await;
+        let mut candidates: Vec<_> = bundles
+            .into_iter()
+            .filter(|b| simulations.get(&b.bundle_hash).is_none_or(BundleSimulation::succeeded))
+            .collect();
+        drop(simulations);
+        candidates.sort_by(|a, b| b.bid_amount.cmp(&a.bid_amount));
+
+        let mut candidates = candidates.into_iter();
+        let winner = loop {
+            let candidate = candidates.next()?;
+            let bundle_hash = candidate.bundle_hash;
+            match self.reveal_bundle(candidate).await {
+                Ok(winner) => break winner,
+                Err(err) => {
+                    warn!("Encrypted bundle could not be revealed: hash={}, error={}", bundle_hash, err);
+                    let reason = BundleError::Decryption(err).to_string();
+                    if let Err(err) = self.mark_bundle_rejected(bundle_hash, reason).await {
+                        warn!("Failed to reject unrevealable bundle {}: {}", bundle_hash, err);
+                    }
+                }
+            }
+        };
+        if let Some(second) = candidates.next() {
+            self.second_prices.write().await.insert(winner.bundle_hash, second.bid_amount);
+        }
+        self.winners.write().await.insert(block_number, winner.bundle_hash);
         
         info!(
             "Winning bundle selected: hash={}, bid={}, block={}",
             winner.bundle_hash, winner.bid_amount, block_number
         );
+        log_transition(winner.bundle_hash, BundleState::Selected);
         
         Some(winner)
     }
     
+    /// Decrypt the transactions of `bundle` if it's encrypted and not revealed yet
+    ///
+    /// The plaintext is zeroized once decoded; the transactions are kept in
+    /// the registry until the bundle is settled.
+    async fn reveal_bundle(
+        &self,
+        mut bundle: BundleSubmission,
+    ) -> Result<BundleSubmission, BundleDecryptionError> {
+        let Some(payload) = bundle.encrypted_payload.as_ref().filter(|_| bundle.transactions.is_empty()) else {
+            return Ok(bundle);
+        };
+        let secret = self.decryption_key.read().unwrap().clone().ok_or(BundleDecryptionError::NoDecryptionKey)?;
+        let transactions = decrypt_bundle(&secret, payload, bundle.bundle_hash)?;
+
+        bundle.transactions = transactions.iter().map(|tx| *tx.hash()).collect();
+        self.transactions.insert(transactions);
+        if let Some(pending) =
+            self.pending_bundles.write().await.iter_mut().find(|b| b.bundle_hash == bundle.bundle_hash)
+        {
+            pending.transactions = bundle.transactions.clone();
+        }
+        debug!("Encrypted bundle revealed: hash={}, transactions={}", bundle.bundle_hash, bundle.transactions.len());
+
+        Ok(bundle)
+    }
+
+    /// Where the bundle `bundle_hash` is in the auction, if it's known
+    ///
+    /// Replaced and cancelled bundles are forgotten, as are settled and
+    /// expired bundles once enough newer ones finished.
+    pub async fn bundle_status(&self, bundle_hash: B256) -> Option<BundleStatus> {
+        let pending = self.pending_bundles.read().await.iter().find(|b| b.bundle_hash == bundle_hash).cloned();
+        if let Some(bundle) = pending {
+            let selected = self.winners.read().await.get(&bundle.target_block) == Some(&bundle_hash);
+            return Some(BundleStatus {
+                state: if selected { BundleState::Selected } else { BundleState::Pending },
+                target_block: Some(bundle.target_block),
+                bid_amount: bundle.bid_amount,
+                mev_captured: None,
+                rejection_reason: None,
+            });
+        }
+
+        let settled = self.executed_bundles.read().await.iter().rev().find(|(hash, _)| *hash == bundle_hash).cloned();
+        if let Some((_, result)) = settled {
+            return Some(BundleStatus {
+                state: if result.executed { BundleState::Executed } else { BundleState::Rejected },
+                target_block: result.target_block,
+                bid_amount: result.bid_amount,
+                mev_captured: result.executed.then_some(result.mev_captured),
+                rejection_reason: result.rejection_reason,
+            });
+        }
+
+        let expired = self.expired_bundles.read().await;
+        let bundle = expired.iter().rev().find(|b| b.bundle_hash == bundle_hash)?;
+        Some(BundleStatus {
+            state: BundleState::Expired,
+            target_block: Some(bundle.target_block),
+            bid_amount: bundle.bid_amount,
+            mev_captured: None,
+            rejection_reason: None,
+        })
+    }
+
+    /// Bids paid by the bundles executed in `block_number`
+    pub async fn bids_paid_for_block(&self, block_number: u64) -> U256 {
+        self.executed_bundles
+            .read()
+            .await
+            .iter()
+            .filter(|(_, r)| r.executed && r.target_block == Some(block_number))
+            .map(|(_, r)| r.bid_paid)
+            .sum()
+    }
+
     /// Get auction statistics
     pub async fn get_auction_stats(&self) -> AuctionStats {
         let pending = self.pending_bundles.read().await;
         let executed = self.executed_bundles.read().await;
         
-        let total_bundles = pending.len() + executed.len();
+        let replaced_count = self.replaced_bundles.load(Ordering::Relaxed);
+        let cancelled_count = self.cancelled_bundles.load(Ordering::Relaxed);
+        let total_bundles = pending.len() + executed.len() + replaced_count + cancelled_count;
         let executed_count = executed.iter().filter(|(_, r)| r.executed).count();
         let rejected_count = executed.iter().filter(|(_, r)| !r.executed).count();
         
@@ -209,6 +1134,8 @@ impl MevAuctionClient {
             pending_bundles: pending.len(),
             executed_bundles: executed_count,
             rejected_bundles: rejected_count,
+            replaced_bundles: replaced_count,
+            cancelled_bundles: cancelled_count,
             total_mev_captured: total_mev,
             total_bids_paid: total_bids,
         }
@@ -220,14 +1147,50 @@ impl MevAuctionClient {
         
         // Remove old pending bundles
         let mut pending = self.pending_bundles.write().await;
-        pending.retain(|b| b.target_block >= cutoff_block);
+        let (kept, expired) = std::mem::take(&mut *pending)
+            .into_iter()
+            .partition::<Vec<_>, _>(|b| b.target_block >= cutoff_block);
+        *pending = kept;
+        for bundle in &expired {
+            self.release_transactions(bundle, &pending);
+            log_transition(bundle.bundle_hash, BundleState::Expired);
+        }
+        let mut expired_bundles = self.expired_bundles.write().await;
+        expired_bundles.extend(expired);
+        let excess = expired_bundles.len().saturating_sub(MAX_FINISHED_BUNDLES);
+        expired_bundles.drain(..excess);
+        drop(expired_bundles);
+        self.simulations
+            .write()
+            .await
+            .retain(|hash, _| pending.iter().any(|b| b.bundle_hash == *hash));
+        self.selected_blocks.write().await.retain(|block| *block >= cutoff_block);
+        self.winners.write().await.retain(|block, _| *block >= cutoff_block);
+        self.second_prices
+            .write()
+            .await
+            .retain(|hash, _| pending.iter().any(|b| b.bundle_hash == *hash));
+        self.bans.write().await.retain(|_, until_block| *until_block >= current_block);
+
+        // Keep the accounting of the most recently active searchers only
+        let mut searchers = self.searchers.write().await;
+        if searchers.len() > self.max_tracked_searchers {
+            let mut by_activity: Vec<_> =
+                searchers.values().map(|stats| (stats.last_target_block, stats.searcher)).collect();
+            by_activity.sort_unstable();
+            let excess = searchers.len() - self.max_tracked_searchers;
+            for (_, searcher) in by_activity.into_iter().take(excess) {
+                searchers.remove(&searcher);
+            }
+        }
+        drop(searchers);
         
         // Optionally clean up old executed bundles
         let mut executed = self.executed_bundles.write().await;
         let exec_len = executed.len();
-        if exec_len > 10000 {
-            // Keep only last 10000 executions
-            executed.drain(0..exec_len - 10000);
+        if exec_len > MAX_FINISHED_BUNDLES {
+            // Keep only the latest executions
+            executed.drain(0..exec_len - MAX_FINISHED_BUNDLES);
         }
         
         debug!(
@@ -271,6 +1234,81 @@ impl MevAuctionClient {
     }
 }
 
+/// Execute the bundle transactions in order, stopping at the first failure
+fn simulate<DB>(
+    bundle: &BundleSubmission,
+    transactions: &[TransactionSigned],
+    parent_header: &SealedHeader,
+    next_block_attrs: &NextBlockEnvAttributes,
+    evm_config: &AndeEvmConfig,
+    state: DB,
+) -> BundleSimulation
+where
+    DB: DatabaseRef + fmt::Debug,
+    DB::Error: Send + Sync + 'static,
+{
+    let beneficiary = next_block_attrs.suggested_fee_recipient;
+    let mut simulation = BundleSimulation { gas_used: 0, beneficiary_profit: U256::ZERO, failed_tx: None };
+    let evm_env = match evm_config.next_evm_env(parent_header.header(), next_block_attrs) {
+        Ok(env) => env,
+        Err(err) => {
+            let first = bundle.transactions.first().copied().unwrap_or_default();
+            simulation.failed_tx = Some((first, format!("Failed to create EVM environment: {err}")));
+            return simulation;
+        }
+    };
+
+    let mut db = CacheDB::new(state);
+    let balance_before = db.basic(beneficiary).ok().flatten().map(|info| info.balance).unwrap_or_default();
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+    for tx_hash in &bundle.transactions {
+        let Some(transaction) = transactions.iter().find(|tx| tx.hash() == tx_hash) else {
+            simulation.failed_tx = Some((*tx_hash, "Transaction not supplied".to_string()));
+            break;
+        };
+        let sender = match transaction.recover_signer() {
+            Ok(sender) => sender,
+            Err(err) => {
+                simulation.failed_tx = Some((*tx_hash, format!("Failed to recover signer: {err}")));
+                break;
+            }
+        };
+
+        match evm.transact_commit(Recovered::new_unchecked(transaction, sender)) {
+            Ok(result) => {
+                simulation.gas_used += result.gas_used();
+                if !result.is_success() {
+                    simulation.failed_tx = Some((*tx_hash, "Transaction reverted".to_string()));
+                    break;
+                }
+            }
+            Err(err) => {
+                simulation.failed_tx = Some((*tx_hash, format!("EVM execution failed: {err}")));
+                break;
+            }
+        }
+    }
+    drop(evm);
+
+    let balance_after = db.basic(beneficiary).ok().flatten().map(|info| info.balance).unwrap_or_default();
+    simulation.beneficiary_profit = balance_after.saturating_sub(balance_before);
+    simulation
+}
+
+/// Wait for the receipt of a contract transaction, failing if it reverted
+async fn confirm_receipt(
+    call: &'static str,
+    pending: PendingTransactionBuilder<Ethereum>,
+) -> Result<B256, MevAuctionError> {
+    let receipt = pending.get_receipt().await?;
+    if !receipt.status() {
+        return Err(MevAuctionError::Reverted { call, tx_hash: receipt.transaction_hash });
+    }
+
+    debug!("MEVAuctionManager {} confirmed: tx={}", call, receipt.transaction_hash);
+    Ok(receipt.transaction_hash)
+}
+
 /// Auction statistics
 #[derive(Debug, Clone)]
 pub struct AuctionStats {
@@ -282,6 +1320,10 @@ pub struct AuctionStats {
     pub executed_bundles: usize,
     /// Rejected bundles
     pub rejected_bundles: usize,
+    /// Bundles replaced by a higher bid from the same searcher
+    pub replaced_bundles: usize,
+    /// Bundles cancelled by their searcher
+    pub cancelled_bundles: usize,
     /// Total MEV captured
     pub total_mev_captured: U256,
     /// Total bids paid
@@ -332,6 +1374,7 @@ mod tests {
             target_block: 100,
             transactions: vec![B256::random()],
             searcher: Address::random(),
+            encrypted_payload: None,
         };
         
         let result = client.submit_bundle(bundle.clone()).await;
@@ -342,6 +1385,283 @@ mod tests {
         assert_eq!(bundles[0].bundle_hash, bundle.bundle_hash);
     }
 
+    #[tokio::test]
+    async fn test_invalid_bundle_rejected_before_submission() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        assert!(!client.is_onchain());
+
+        let bundle = BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::ZERO,
+            target_block: 100,
+            transactions: vec![B256::random()],
+            searcher: Address::random(),
+            encrypted_payload: None,
+        };
+        let err = client.submit_bundle(bundle).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::ZeroBid)));
+        assert!(client.get_bundles_for_block(100).await.is_empty());
+    }
+
+    /// Collateral source serving fixed deposits and counting lookups
+    #[derive(Debug, Default)]
+    struct MockCollateral {
+        deposits: std::sync::Mutex<HashMap<Address, U256>>,
+        lookups: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MockCollateral {
+        fn set(&self, searcher: Address, deposit: u64) {
+            self.deposits.lock().unwrap().insert(searcher, U256::from(deposit));
+        }
+
+        fn lookups(&self) -> usize {
+            self.lookups.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl CollateralSource for MockCollateral {
+        async fn collateral(&self, searcher: Address) -> Result<U256, MevAuctionError> {
+            self.lookups.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.deposits.lock().unwrap().get(&searcher).copied().unwrap_or_default())
+        }
+    }
+
+    fn bid(searcher: Address, amount: u64) -> BundleSubmission {
+        BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::from(amount),
+            target_block: 100,
+            transactions: vec![B256::random()],
+            searcher,
+            encrypted_payload: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bids_checked_against_available_collateral() {
+        let source = Arc::new(MockCollateral::default());
+        let client = MevAuctionClient::new(Address::random(), Address::random())
+            .with_collateral_source(source.clone(), DEFAULT_COLLATERAL_TTL);
+        let searcher = Address::random();
+        source.set(searcher, 1000);
+
+        client.submit_bundle(bid(searcher, 600)).await.unwrap();
+
+        // The first bid is still pending, leaving 400 to cover the second one
+        let err = client.submit_bundle(bid(searcher, 500)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::InsufficientCollateral { searcher: s, required, available })
+                if s == searcher && required == U256::from(500) && available == U256::from(400)
+        ));
+        client.submit_bundle(bid(searcher, 400)).await.unwrap();
+
+        // A searcher without a deposit can't bid at all
+        let err = client.submit_bundle(bid(Address::random(), 1)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::InsufficientCollateral { available, .. }) if available.is_zero()
+        ));
+
+        assert_eq!(client.get_bundles_for_block(100).await.len(), 2);
+        // Both bids by the funded searcher were checked against a single lookup
+        assert_eq!(source.lookups(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_collateral_refreshed_after_ttl() {
+        let source = Arc::new(MockCollateral::default());
+        let ttl = Duration::from_secs(10);
+        let client =
+            MevAuctionClient::new(Address::random(), Address::random()).with_collateral_source(source.clone(), ttl);
+        let searcher = Address::random();
+        source.set(searcher, 100);
+
+        client.submit_bundle(bid(searcher, 100)).await.unwrap();
+        let settled = client.get_bundles_for_block(100).await[0].bundle_hash;
+        client.mark_bundle_executed(settled, U256::from(150), U256::from(100)).await.unwrap();
+
+        // The deposit was spent, but the cached value is still served until it expires
+        source.set(searcher, 0);
+        tokio::time::advance(ttl / 2).await;
+        client.submit_bundle(bid(searcher, 100)).await.unwrap();
+        assert_eq!(source.lookups(), 1);
+
+        let pending = client.get_bundles_for_block(100).await[0].bundle_hash;
+        client.mark_bundle_rejected(pending, "outbid".to_string()).await.unwrap();
+        tokio::time::advance(ttl).await;
+        let err = client.submit_bundle(bid(searcher, 100)).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::InsufficientCollateral { .. })));
+        assert_eq!(source.lookups(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_cancel_by_searcher() {
+        let client = MevAuctionClient::new(Address::random(), Address::random()).with_min_bid_bump(10);
+        let searcher = Address::random();
+        let original = bid(searcher, 1000);
+        client.submit_bundle(original.clone()).await.unwrap();
+
+        // Another searcher can neither replace nor cancel the bundle
+        let intruder = Address::random();
+        let err = client.replace_bundle(original.bundle_hash, bid(intruder, 5000)).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::NotBundleSearcher { .. })));
+        let err = client.cancel_bundle(original.bundle_hash, intruder).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::NotBundleSearcher { bundle_hash, searcher: s })
+                if bundle_hash == original.bundle_hash && s == intruder
+        ));
+
+        // A 5% raise is below the minimum bump of 10%
+        let err = client.replace_bundle(original.bundle_hash, bid(searcher, 1050)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::BidBumpTooLow { required, offered })
+                if required == U256::from(1100) && offered == U256::from(1050)
+        ));
+
+        let replacement = bid(searcher, 1100);
+        client.replace_bundle(original.bundle_hash, replacement.clone()).await.unwrap();
+        let pending = client.get_bundles_for_block(100).await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].bundle_hash, replacement.bundle_hash);
+
+        // The replaced bundle is gone, the replacement can be cancelled
+        let err = client.cancel_bundle(original.bundle_hash, searcher).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::UnknownBundle(_))));
+        client.cancel_bundle(replacement.bundle_hash, searcher).await.unwrap();
+        assert!(client.get_bundles_for_block(100).await.is_empty());
+
+        let stats = client.get_auction_stats().await;
+        assert_eq!((stats.replaced_bundles, stats.cancelled_bundles), (1, 1));
+        assert_eq!(stats.total_bundles, 2);
+        assert_eq!(stats.pending_bundles, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bundles_frozen_once_block_selected() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let searcher = Address::random();
+        let bundle = bid(searcher, 1000);
+        client.submit_bundle(bundle.clone()).await.unwrap();
+
+        let winner = client.select_winning_bundle(100).await.unwrap();
+        assert_eq!(winner.bundle_hash, bundle.bundle_hash);
+
+        let err = client.replace_bundle(bundle.bundle_hash, bid(searcher, 2000)).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::BlockSelected(100))));
+        let err = client.cancel_bundle(bundle.bundle_hash, searcher).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::BlockSelected(100))));
+
+        // A bundle for a later block can't be moved into the selected one either
+        let later = BundleSubmission { target_block: 101, ..bid(searcher, 1000) };
+        client.submit_bundle(later.clone()).await.unwrap();
+        let err = client.replace_bundle(later.bundle_hash, bid(searcher, 2000)).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::BlockSelected(100))));
+
+        let stats = client.get_auction_stats().await;
+        assert_eq!((stats.replaced_bundles, stats.cancelled_bundles), (0, 0));
+        assert_eq!(client.get_bundles_for_block(100).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_banned_searcher_cannot_bid() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let (banned, other) = (Address::random(), Address::random());
+        let pending = bid(banned, 1000);
+        let later = BundleSubmission { target_block: 106, ..bid(banned, 1000) };
+        client.submit_bundle(pending.clone()).await.unwrap();
+        client.submit_bundle(later.clone()).await.unwrap();
+        client.submit_bundle(bid(other, 500)).await.unwrap();
+
+        client.ban_searcher(banned, 105).await.unwrap();
+        assert_eq!(client.banned_until(banned).await, Some(105));
+
+        // Bundles covered by the ban are rejected, the one after it stays pending
+        let remaining: Vec<_> = client.get_bundles_for_block(100).await.iter().map(|b| b.searcher).collect();
+        assert_eq!(remaining, vec![other]);
+        assert_eq!(client.get_bundles_for_block(106).await.len(), 1);
+        assert_eq!(client.get_searcher_stats(banned).await.unwrap().bundles_rejected, 1);
+
+        let err = client.submit_bundle(BundleSubmission { target_block: 105, ..bid(banned, 1000) }).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::SearcherBanned { searcher, until_block: 105 })
+                if searcher == banned
+        ));
+        client.submit_bundle(BundleSubmission { target_block: 106, ..bid(banned, 2000) }).await.unwrap();
+        client.submit_bundle(bid(other, 700)).await.unwrap();
+
+        // The ban is dropped once its last block is passed
+        client.cleanup_old_bundles(106, 10).await;
+        assert_eq!(client.banned_until(banned).await, None);
+        client.submit_bundle(BundleSubmission { target_block: 104, ..bid(banned, 1000) }).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_searcher_stats_accumulate() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let (winner, loser) = (Address::random(), Address::random());
+
+        // Block 100: the winner outbids the second price of 600 by 400
+        let winning = bid(winner, 1000);
+        let losing = bid(loser, 600);
+        client.submit_bundle(winning.clone()).await.unwrap();
+        client.submit_bundle(losing.clone()).await.unwrap();
+        assert_eq!(client.select_winning_bundle(100).await.unwrap().bundle_hash, winning.bundle_hash);
+        client.mark_bundle_executed(winning.bundle_hash, U256::from(3000), U256::from(1000)).await.unwrap();
+        client.mark_bundle_rejected(losing.bundle_hash, "outbid".to_string()).await.unwrap();
+
+        // Block 101: uncontested, so it doesn't count towards the overbid
+        let alone = BundleSubmission { target_block: 101, ..bid(winner, 300) };
+        client.submit_bundle(alone.clone()).await.unwrap();
+        client.select_winning_bundle(101).await.unwrap();
+        client.mark_bundle_executed(alone.bundle_hash, U256::from(500), U256::from(300)).await.unwrap();
+
+        // Block 102: the winner's bundle fails
+        let failed = BundleSubmission { target_block: 102, ..bid(winner, 800) };
+        client.submit_bundle(failed.clone()).await.unwrap();
+        client.mark_bundle_rejected(failed.bundle_hash, "reverted".to_string()).await.unwrap();
+
+        let stats = client.get_searcher_stats(winner).await.unwrap();
+        assert_eq!(
+            (stats.bundles_submitted, stats.bundles_executed, stats.bundles_rejected),
+            (3, 2, 1)
+        );
+        assert_eq!(stats.total_bids_paid, U256::from(1300));
+        assert_eq!(stats.average_overbid(), U256::from(400));
+        assert_eq!((stats.failure_streak, stats.last_target_block), (1, 102));
+
+        let loser_stats = client.get_searcher_stats(loser).await.unwrap();
+        assert_eq!((loser_stats.bundles_rejected, loser_stats.failure_streak), (1, 1));
+        assert_eq!(loser_stats.average_overbid(), U256::ZERO);
+
+        let top: Vec<_> = client.top_searchers(5).await.iter().map(|stats| stats.searcher).collect();
+        assert_eq!(top, vec![winner, loser]);
+        assert_eq!(client.top_searchers(1).await.len(), 1);
+        assert!(client.get_searcher_stats(Address::random()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_least_active_searchers_evicted() {
+        let client = MevAuctionClient::new(Address::random(), Address::random()).with_max_tracked_searchers(2);
+        let searchers: Vec<_> = (0..4).map(|_| Address::random()).collect();
+        for (i, searcher) in searchers.iter().enumerate() {
+            let bundle = BundleSubmission { target_block: 100 + i as u64, ..bid(*searcher, 1000) };
+            client.submit_bundle(bundle).await.unwrap();
+        }
+        assert!(client.get_searcher_stats(searchers[0]).await.is_some());
+
+        client.cleanup_old_bundles(103, 10).await;
+        for (i, searcher) in searchers.iter().enumerate() {
+            assert_eq!(client.get_searcher_stats(*searcher).await.is_some(), i >= 2, "searcher {i}");
+        }
+        assert_eq!(client.top_searchers(10).await.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_bundle_execution() {
         let client = MevAuctionClient::new(Address::random(), Address::random());
@@ -352,6 +1672,7 @@ mod tests {
             target_block: 100,
             transactions: vec![B256::random()],
             searcher: Address::random(),
+            encrypted_payload: None,
         };
         
         client.submit_bundle(bundle.clone()).await.unwrap();
@@ -372,6 +1693,49 @@ mod tests {
         assert_eq!(stats.total_mev_captured, mev_captured);
     }
 
+    #[tokio::test]
+    async fn test_bundle_lifecycle_states() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let (winning, losing) = (bid(Address::random(), 1000), bid(Address::random(), 600));
+        let failing = bid(Address::random(), 800);
+        let stale = BundleSubmission { target_block: 101, ..bid(Address::random(), 500) };
+        for bundle in [&winning, &losing, &failing, &stale] {
+            client.submit_bundle(bundle.clone()).await.unwrap();
+        }
+        let state = |status: Option<BundleStatus>| status.map(|status| status.state);
+
+        let status = client.bundle_status(winning.bundle_hash).await.unwrap();
+        assert_eq!(status.state, BundleState::Pending);
+        assert_eq!((status.target_block, status.bid_amount), (Some(100), U256::from(1000)));
+
+        client.mark_bundle_rejected(failing.bundle_hash, "reverted".to_string()).await.unwrap();
+        client.select_winning_bundle(100).await.unwrap();
+        assert_eq!(state(client.bundle_status(winning.bundle_hash).await), Some(BundleState::Selected));
+        // Outbid bundles stay pending until their block is cleaned up
+        assert_eq!(state(client.bundle_status(losing.bundle_hash).await), Some(BundleState::Pending));
+
+        client.mark_bundle_executed(winning.bundle_hash, U256::from(3000), U256::from(1000)).await.unwrap();
+        let executed = client.bundle_status(winning.bundle_hash).await.unwrap();
+        assert_eq!(executed.state, BundleState::Executed);
+        assert_eq!((executed.bid_amount, executed.mev_captured), (U256::from(1000), Some(U256::from(3000))));
+
+        let rejected = client.bundle_status(failing.bundle_hash).await.unwrap();
+        assert_eq!(rejected.state, BundleState::Rejected);
+        assert_eq!((rejected.target_block, rejected.bid_amount), (Some(100), U256::from(800)));
+        assert_eq!(rejected.rejection_reason.as_deref(), Some("reverted"));
+        assert_eq!(rejected.mev_captured, None);
+
+        // Block 101 passes without a selection, and block 100 is cleaned up
+        client.cleanup_old_bundles(112, 10).await;
+        let expired = client.bundle_status(stale.bundle_hash).await.unwrap();
+        assert_eq!(expired.state, BundleState::Expired);
+        assert_eq!((expired.target_block, expired.bid_amount), (Some(101), U256::from(500)));
+        assert_eq!(state(client.bundle_status(losing.bundle_hash).await), Some(BundleState::Expired));
+        assert_eq!(state(client.bundle_status(winning.bundle_hash).await), Some(BundleState::Executed));
+
+        assert!(client.bundle_status(B256::random()).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_winning_bundle_selection() {
         let client = MevAuctionClient::new(Address::random(), Address::random());
@@ -384,6 +1748,7 @@ mod tests {
                 target_block: 100,
                 transactions: vec![B256::random()],
                 searcher: Address::random(),
+                encrypted_payload: None,
             };
             client.submit_bundle(bundle).await.unwrap();
         }
@@ -405,6 +1770,7 @@ mod tests {
                 target_block: 100,
                 transactions: vec![B256::random()],
                 searcher: Address::random(),
+                encrypted_payload: None,
             };
             client.submit_bundle(bundle.clone()).await.unwrap();
             
@@ -426,4 +1792,181 @@ mod tests {
         assert_eq!(stats.pending_bundles, 1);
         assert!(stats.success_rate() > 0.6);
     }
+
+    /// Legacy transaction from one of several test signers
+    fn test_transaction(signer: u64, to: Address) -> TransactionSigned {
+        use alloy_consensus::{TxLegacy, TypedTransaction};
+        use alloy_primitives::{Signature, TxKind};
+
+        let tx = TxLegacy {
+            chain_id: Some(31337),
+            gas_price: 2_000_000_000,
+            gas_limit: 100_000,
+            to: TxKind::Call(to),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let signature = Signature::new(Signature::test_signature().r(), U256::from(signer), false);
+        TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), signature)
+    }
+
+    /// Bundle from a random searcher whose transactions are encrypted to `recipient`
+    fn encrypted_bid(recipient: &PublicKey, transactions: &[TransactionSigned], amount: u64) -> BundleSubmission {
+        use crate::mev::privacy::{bundle_commitment, encrypt_bundle};
+
+        BundleSubmission {
+            bundle_hash: bundle_commitment(transactions.iter().map(|tx| tx.hash())),
+            bid_amount: U256::from(amount),
+            target_block: 100,
+            transactions: Vec::new(),
+            searcher: Address::random(),
+            encrypted_payload: Some(encrypt_bundle(recipient, transactions)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_bundle_revealed_at_selection() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let transactions = vec![test_transaction(1, Address::random()), test_transaction(2, Address::random())];
+        let sequencer_key = PublicKey::from(&StaticSecret::from([9u8; 32]));
+
+        // Without a registered key, encrypted bundles can't be accepted
+        let bundle = encrypted_bid(&sequencer_key, &transactions, 1000);
+        let err = client.submit_bundle(bundle.clone()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            MevAuctionError::InvalidBundle(BundleError::Decryption(BundleDecryptionError::NoDecryptionKey))
+        ));
+
+        client.register_decryption_key([9u8; 32]);
+        assert_eq!(client.encryption_public_key(), Some(sequencer_key));
+        let exposed = BundleSubmission { transactions: vec![*transactions[0].hash()], ..bundle.clone() };
+        let err = client.submit_bundle(exposed).await.unwrap_err();
+        assert!(matches!(err, MevAuctionError::InvalidBundle(BundleError::EncryptedWithTransactions)));
+
+        client.submit_bundle(bundle.clone()).await.unwrap();
+        assert!(client.get_bundles_for_block(100).await[0].transactions.is_empty());
+        assert!(client.bundle_transactions().is_empty());
+
+        let winner = client.select_winning_bundle(100).await.unwrap();
+        let hashes: Vec<_> = transactions.iter().map(|tx| *tx.hash()).collect();
+        assert_eq!(winner.bundle_hash, bundle.bundle_hash);
+        assert_eq!(winner.transactions, hashes);
+        assert_eq!(client.bundle_transactions().resolve(&hashes), Some(transactions));
+
+        // Settling the bundle forgets the revealed transactions
+        client.mark_bundle_executed(winner.bundle_hash, U256::from(2000), U256::from(1000)).await.unwrap();
+        assert!(client.bundle_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unrevealable_bundles_rejected() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        client.register_decryption_key([9u8; 32]);
+        let sequencer_key = client.encryption_public_key().unwrap();
+        let transaction = |signer| vec![test_transaction(signer, Address::random())];
+
+        // Encrypted to another key
+        let other_key = PublicKey::from(&StaticSecret::from([8u8; 32]));
+        let wrong_key = encrypted_bid(&other_key, &transaction(1), 3000);
+        // Decrypts, but commits to other transactions
+        let mismatch =
+            BundleSubmission { bundle_hash: B256::random(), ..encrypted_bid(&sequencer_key, &transaction(2), 2000) };
+        let valid = encrypted_bid(&sequencer_key, &transaction(3), 1000);
+        for bundle in [&wrong_key, &mismatch, &valid] {
+            client.submit_bundle(bundle.clone()).await.unwrap();
+        }
+
+        let winner = client.select_winning_bundle(100).await.unwrap();
+        assert_eq!(winner.bundle_hash, valid.bundle_hash);
+
+        let executed = client.executed_bundles.read().await;
+        let reason = |hash: B256| {
+            executed.iter().find(|(h, _)| *h == hash).and_then(|(_, r)| r.rejection_reason.clone()).unwrap()
+        };
+        assert_eq!(
+            reason(wrong_key.bundle_hash),
+            BundleError::Decryption(BundleDecryptionError::Decryption).to_string()
+        );
+        assert!(reason(mismatch.bundle_hash).contains(&format!("bundle committed to {}", mismatch.bundle_hash)));
+        drop(executed);
+        assert_eq!(client.get_bundles_for_block(100).await.len(), 1);
+        // Rejections don't count as competition for the winner
+        assert!(client.second_prices.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reverting_bundle_loses_to_lower_valid_bid() {
+        use crate::evm_config::{create_ande_evm_config, AndePrecompileConfig};
+        use reth_chainspec::{Chain, ChainSpecBuilder};
+        use reth_primitives::Header;
+        use revm::{
+            database::EmptyDB,
+            state::{AccountInfo, Bytecode},
+        };
+
+        let evm_config = create_ande_evm_config(Arc::new(
+            ChainSpecBuilder::default().chain(Chain::from_id(31337)).genesis(Default::default()).cancun_activated().build(),
+        ), AndePrecompileConfig::insecure_unrestricted());
+        let parent = SealedHeader::new(
+            Header {
+                number: 1,
+                gas_limit: 10_000_000,
+                timestamp: 1_000_000,
+                base_fee_per_gas: Some(1_000_000_000),
+                ..Default::default()
+            },
+            B256::ZERO,
+        );
+        let attributes = NextBlockEnvAttributes {
+            timestamp: 1_000_001,
+            suggested_fee_recipient: Address::random(),
+            prev_randao: B256::ZERO,
+            gas_limit: 10_000_000,
+            withdrawals: Some(Default::default()),
+            parent_beacon_block_root: Some(B256::ZERO),
+        };
+
+        // `PUSH0 PUSH0 REVERT`
+        let reverter = Address::random();
+        let valid = test_transaction(1, Address::random());
+        let reverting = test_transaction(2, reverter);
+        let mut state = CacheDB::new(EmptyDB::default());
+        state.insert_account_info(reverter, AccountInfo::from_bytecode(Bytecode::new_raw(vec![0x5f, 0x5f, 0xfd].into())));
+        for tx in [&valid, &reverting] {
+            let info = AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() };
+            state.insert_account_info(tx.recover_signer().unwrap(), info);
+        }
+
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let bundle = |tx: &TransactionSigned, bid: u64| BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::from(bid),
+            target_block: 2,
+            transactions: vec![*tx.hash()],
+            searcher: Address::random(),
+            encrypted_payload: None,
+        };
+        let (valid_bundle, reverting_bundle) = (bundle(&valid, 1000), bundle(&reverting, 5000));
+        let raw = [valid.clone(), reverting.clone()];
+        for bundle in [&valid_bundle, &reverting_bundle] {
+            client.submit_bundle(bundle.clone()).await.unwrap();
+        }
+
+        let failed = client.simulate_bundle(&reverting_bundle, &raw, &parent, &attributes, &evm_config, &state).await;
+        assert_eq!(failed.failed_tx, Some((*reverting.hash(), "Transaction reverted".to_string())));
+
+        let simulation = client.simulate_bundle(&valid_bundle, &raw, &parent, &attributes, &evm_config, &state).await;
+        assert!(simulation.succeeded());
+        assert_eq!(simulation.gas_used, 21_000);
+        assert!(simulation.beneficiary_profit > U256::ZERO);
+
+        let winner = client.select_winning_bundle(2).await.unwrap();
+        assert_eq!(winner.bundle_hash, valid_bundle.bundle_hash);
+
+        // The simulation is carried into the execution result
+        client.mark_bundle_executed(winner.bundle_hash, U256::from(2000), winner.bid_amount).await.unwrap();
+        let executed = client.executed_bundles.read().await;
+        assert_eq!(executed[0].1.simulation, Some(simulation));
+    }
 }
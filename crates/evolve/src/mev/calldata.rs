@@ -0,0 +1,450 @@
+//! Calldata Classification
+//!
+//! Decodes the function selector of a transaction against a table of known
+//! swap, liquidity, liquidation and oracle entry points, and extracts the
+//! tokens and amounts where the ABI of the call is known. Detection uses these to tell swaps
+//! apart from other calls and to estimate opportunity values from the amounts
+//! actually traded.
+
+use alloy_primitives::{Address, U256};
+use std::collections::HashMap;
+
+/// Function selector, the first 4 bytes of calldata
+pub type Selector = [u8; 4];
+
+/// UniswapV2 `swapExactTokensForTokens(uint256,uint256,address[],address,uint256)`
+pub const SWAP_EXACT_TOKENS_FOR_TOKENS: Selector = [0x38, 0xed, 0x17, 0x39];
+/// UniswapV2 `swapExactETHForTokens(uint256,address[],address,uint256)`
+pub const SWAP_EXACT_ETH_FOR_TOKENS: Selector = [0x7f, 0xf3, 0x6a, 0xb5];
+/// UniswapV2 `swapExactTokensForETH(uint256,uint256,address[],address,uint256)`
+pub const SWAP_EXACT_TOKENS_FOR_ETH: Selector = [0x18, 0xcb, 0xaf, 0xe5];
+/// UniswapV3 SwapRouter `exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))`
+pub const EXACT_INPUT_SINGLE: Selector = [0x41, 0x4b, 0xf3, 0x89];
+/// UniswapV3 SwapRouter02 `exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))`
+pub const EXACT_INPUT_SINGLE_V2: Selector = [0x04, 0xe4, 0x5a, 0xaf];
+/// Aave `liquidationCall(address,address,address,uint256,bool)`
+pub const LIQUIDATION_CALL: Selector = [0x00, 0xa7, 0x18, 0xa9];
+/// Compound v2 CToken `liquidateBorrow(address,uint256,address)`
+pub const LIQUIDATE_BORROW: Selector = [0xf5, 0xe3, 0xc4, 0x62];
+/// Compound v3 Comet `absorb(address,address[])`
+pub const ABSORB: Selector = [0xc3, 0xce, 0xcf, 0xd2];
+/// UniswapV2 `addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)`
+pub const ADD_LIQUIDITY: Selector = [0xe8, 0xe3, 0x37, 0x00];
+/// UniswapV2 `removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)`
+pub const REMOVE_LIQUIDITY: Selector = [0xba, 0xa2, 0xab, 0xde];
+/// Chainlink OCR2 `transmit(bytes32[3],bytes,bytes32[],bytes32[],bytes32)`
+pub const OCR2_TRANSMIT: Selector = [0xb1, 0xdc, 0x65, 0xa4];
+
+/// What a known call does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    /// Token swap
+    Swap,
+    /// Liquidation of an undercollateralized position
+    Liquidation,
+    /// Liquidity added to a pool
+    AddLiquidity,
+    /// Liquidity removed from a pool
+    RemoveLiquidity,
+    /// Price feed update
+    OracleUpdate,
+}
+
+/// Argument layout of a known call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalldataLayout {
+    /// `(uint256 amountIn, uint256 amountOutMin, address[] path, ..)`
+    ExactInPath,
+    /// `(uint256 amountOutMin, address[] path, ..)`, with the input sent as value
+    ExactEthInPath,
+    /// `(address tokenIn, address tokenOut, uint24 fee, address recipient, uint256 deadline,
+    /// uint256 amountIn, uint256 amountOutMinimum, ..)`
+    ExactInputSingle,
+    /// `(address tokenIn, address tokenOut, uint24 fee, address recipient, uint256 amountIn,
+    /// uint256 amountOutMinimum, ..)`
+    ExactInputSingleNoDeadline,
+    /// `(address collateralAsset, address debtAsset, address user, uint256 debtToCover, ..)`
+    LiquidationCall,
+    /// `(address tokenA, address tokenB, uint256 amountADesired, ..)`
+    AddLiquidity,
+    /// `(address tokenA, address tokenB, uint256 liquidity, ..)`
+    RemoveLiquidity,
+    /// Arguments are not decoded
+    Opaque,
+}
+
+/// Entry of the selector table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorEntry {
+    /// Function name, for logs and metadata
+    pub name: String,
+    /// What the call does
+    pub kind: CallKind,
+    /// How its arguments are laid out
+    pub layout: CalldataLayout,
+}
+
+impl SelectorEntry {
+    /// Create a new entry
+    pub fn new(name: impl Into<String>, kind: CallKind, layout: CalldataLayout) -> Self {
+        Self { name: name.into(), kind, layout }
+    }
+}
+
+/// Selector table covering the common UniswapV2/V3 swaps, UniswapV2 liquidity
+/// changes, Aave liquidations and Chainlink price updates
+pub fn default_selectors() -> HashMap<Selector, SelectorEntry> {
+    use CalldataLayout::*;
+
+    HashMap::from([
+        (
+            SWAP_EXACT_TOKENS_FOR_TOKENS,
+            SelectorEntry::new("swapExactTokensForTokens", CallKind::Swap, ExactInPath),
+        ),
+        (SWAP_EXACT_ETH_FOR_TOKENS, SelectorEntry::new("swapExactETHForTokens", CallKind::Swap, ExactEthInPath)),
+        (SWAP_EXACT_TOKENS_FOR_ETH, SelectorEntry::new("swapExactTokensForETH", CallKind::Swap, ExactInPath)),
+        (EXACT_INPUT_SINGLE, SelectorEntry::new("exactInputSingle", CallKind::Swap, ExactInputSingle)),
+        (
+            EXACT_INPUT_SINGLE_V2,
+            SelectorEntry::new("exactInputSingle", CallKind::Swap, ExactInputSingleNoDeadline),
+        ),
+        (LIQUIDATION_CALL, SelectorEntry::new("liquidationCall", CallKind::Liquidation, LiquidationCall)),
+        (ADD_LIQUIDITY, SelectorEntry::new("addLiquidity", CallKind::AddLiquidity, AddLiquidity)),
+        (REMOVE_LIQUIDITY, SelectorEntry::new("removeLiquidity", CallKind::RemoveLiquidity, RemoveLiquidity)),
+        (OCR2_TRANSMIT, SelectorEntry::new("transmit", CallKind::OracleUpdate, Opaque)),
+    ])
+}
+
+/// Call decoded from calldata
+///
+/// For swaps, `token_in` is sold and `token_out` bought. For liquidations,
+/// `token_in` is the debt asset repaid and `token_out` the collateral seized.
+/// For liquidity changes, they are the two tokens of the pair, and `amount_in`
+/// is the amount of `token_in` added or of liquidity removed. Amounts are in
+/// the smallest unit of the respective token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    /// Selector of the call
+    pub selector: Selector,
+    /// Function name from the selector table
+    pub name: String,
+    /// What the call does
+    pub kind: CallKind,
+    /// Token paid
+    pub token_in: Option<Address>,
+    /// Token received
+    pub token_out: Option<Address>,
+    /// Amount of `token_in` paid
+    pub amount_in: Option<U256>,
+    /// Minimum amount of `token_out` accepted, if the call sets one
+    pub amount_out_min: Option<U256>,
+    /// Number of pools a swap goes through
+    pub hops: Option<usize>,
+    /// Fee tier of the pool, for swaps that name one
+    pub fee: Option<u32>,
+}
+
+impl DecodedCall {
+    /// Whether tokens and amounts were extracted
+    pub fn is_decoded(&self) -> bool {
+        self.token_in.is_some() && self.token_out.is_some() && self.amount_in.is_some()
+    }
+
+    /// Pool traded against by a single-hop swap, or whose liquidity changes, sent to `venue`
+    pub fn pool(&self, venue: Option<Address>) -> Option<PoolKey> {
+        let pooled = matches!(self.kind, CallKind::Swap | CallKind::AddLiquidity | CallKind::RemoveLiquidity);
+        if !pooled || self.hops != Some(1) {
+            return None;
+        }
+        let (token_in, token_out) = (self.token_in?, self.token_out?);
+        Some(PoolKey {
+            venue,
+            token0: token_in.min(token_out),
+            token1: token_in.max(token_out),
+            fee: self.fee,
+        })
+    }
+}
+
+/// Identity of the pool a single-hop swap trades against
+///
+/// Pools are identified by the router or pool contract called, the token pair
+/// in canonical order and the fee tier, so both directions of a trade map to
+/// the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolKey {
+    /// Contract the swap was sent to
+    pub venue: Option<Address>,
+    /// Lower token address of the pair
+    pub token0: Address,
+    /// Higher token address of the pair
+    pub token1: Address,
+    /// Fee tier, if the call names one
+    pub fee: Option<u32>,
+}
+
+/// Classify `input` against `selectors`, decoding its arguments where the layout is known
+///
+/// Returns `None` for unknown selectors. Arguments that can't be decoded, e.g.
+/// because the calldata is truncated, are left unset.
+pub fn classify(
+    selectors: &HashMap<Selector, SelectorEntry>,
+    input: &[u8],
+    value: U256,
+) -> Option<DecodedCall> {
+    let selector: Selector = input.get(..4)?.try_into().ok()?;
+    let entry = selectors.get(&selector)?;
+    let args = Args(&input[4..]);
+
+    let mut call = DecodedCall {
+        selector,
+        name: entry.name.clone(),
+        kind: entry.kind,
+        token_in: None,
+        token_out: None,
+        amount_in: None,
+        amount_out_min: None,
+        hops: None,
+        fee: None,
+    };
+
+    match entry.layout {
+        CalldataLayout::ExactInPath => {
+            call.amount_in = args.word(0);
+            call.amount_out_min = args.word(1);
+            (call.token_in, call.token_out, call.hops) = args.path(2);
+        }
+        CalldataLayout::ExactEthInPath => {
+            call.amount_in = Some(value);
+            call.amount_out_min = args.word(0);
+            (call.token_in, call.token_out, call.hops) = args.path(1);
+        }
+        CalldataLayout::ExactInputSingle => {
+            call.token_in = args.address(0);
+            call.token_out = args.address(1);
+            call.amount_in = args.word(5);
+            call.amount_out_min = args.word(6);
+            call.hops = Some(1);
+            call.fee = args.word(2).and_then(|fee| fee.try_into().ok());
+        }
+        CalldataLayout::ExactInputSingleNoDeadline => {
+            call.token_in = args.address(0);
+            call.token_out = args.address(1);
+            call.amount_in = args.word(4);
+            call.amount_out_min = args.word(5);
+            call.hops = Some(1);
+            call.fee = args.word(2).and_then(|fee| fee.try_into().ok());
+        }
+        CalldataLayout::LiquidationCall => {
+            call.token_out = args.address(0);
+            call.token_in = args.address(1);
+            call.amount_in = args.word(3);
+        }
+        CalldataLayout::AddLiquidity | CalldataLayout::RemoveLiquidity => {
+            call.token_in = args.address(0);
+            call.token_out = args.address(1);
+            call.amount_in = args.word(2);
+            call.hops = Some(1);
+        }
+        CalldataLayout::Opaque => {}
+    }
+
+    Some(call)
+}
+
+/// ABI-encoded arguments, following the selector
+pub(crate) struct Args<'a>(pub(crate) &'a [u8]);
+
+impl Args<'_> {
+    /// Word at `offset` bytes into the arguments
+    fn word_at(&self, offset: usize) -> Option<U256> {
+        let bytes = self.0.get(offset..offset.checked_add(32)?)?;
+        Some(U256::from_be_slice(bytes))
+    }
+
+    /// Static argument at `index`
+    pub(crate) fn word(&self, index: usize) -> Option<U256> {
+        self.word_at(index.checked_mul(32)?)
+    }
+
+    /// Static address argument at `index`
+    pub(crate) fn address(&self, index: usize) -> Option<Address> {
+        let word = self.word(index)?;
+        // Reject words that aren't a left-padded address
+        (word >> 160).is_zero().then(|| Address::from_word(word.into()))
+    }
+
+    /// Elements of the `address[]` argument at `index`
+    pub(crate) fn address_array(&self, index: usize) -> Option<Vec<Address>> {
+        let offset: usize = self.word(index)?.try_into().ok()?;
+        let len: usize = self.word_at(offset)?.try_into().ok()?;
+        (0..len)
+            .map(|i| {
+                let word = self.word_at(offset.checked_add(32)?.checked_add(i.checked_mul(32)?)?)?;
+                (word >> 160).is_zero().then(|| Address::from_word(word.into()))
+            })
+            .collect()
+    }
+
+    /// First and last token and number of hops of the `address[]` swap path at `index`
+    fn path(&self, index: usize) -> (Option<Address>, Option<Address>, Option<usize>) {
+        self.path_ends(index).map_or((None, None, None), |(first, last, hops)| (Some(first), Some(last), Some(hops)))
+    }
+
+    /// Ends and number of hops of the path, if it decodes to at least one hop
+    fn path_ends(&self, index: usize) -> Option<(Address, Address, usize)> {
+        let offset: usize = self.word(index)?.try_into().ok()?;
+        let len: usize = self.word_at(offset)?.try_into().ok()?;
+        let element = |i: usize| {
+            let word = self.word_at(offset.checked_add(32)?.checked_add(i.checked_mul(32)?)?)?;
+            (word >> 160).is_zero().then(|| Address::from_word(word.into()))
+        };
+        let hops = len.checked_sub(1).filter(|hops| *hops > 0)?;
+        Some((element(0)?, element(hops)?, hops))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+
+    const WETH: Address = address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+    const USDC: Address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+
+    /// `swapExactTokensForTokens(1e18, 2500e6, [WETH, USDC], 0x..01, 1700000000)`
+    const SWAP_EXACT_TOKENS_FOR_TOKENS_CALLDATA: &str = "38ed1739\
+        0000000000000000000000000000000000000000000000000de0b6b3a7640000\
+        000000000000000000000000000000000000000000000000000000009502f900\
+        00000000000000000000000000000000000000000000000000000000000000a0\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        000000000000000000000000000000000000000000000000000000006553f100\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2\
+        000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    /// `exactInputSingle((USDC, WETH, 500, 0x..01, 1700000000, 2500e6, 0.99e18, 0))`
+    const EXACT_INPUT_SINGLE_CALLDATA: &str = "414bf389\
+        000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\
+        000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2\
+        00000000000000000000000000000000000000000000000000000000000001f4\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        000000000000000000000000000000000000000000000000000000006553f100\
+        000000000000000000000000000000000000000000000000000000009502f900\
+        0000000000000000000000000000000000000000000000000dbd2fc137a30000\
+        0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// `liquidationCall(WETH, USDC, 0x..02, 1000e6, false)`
+    const LIQUIDATION_CALL_CALLDATA: &str = "00a718a9\
+        000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2\
+        000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        000000000000000000000000000000000000000000000000000000003b9aca00\
+        0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// `removeLiquidity(WETH, USDC, 5e17, 0, 0, 0x..01, 1700000000)`
+    const REMOVE_LIQUIDITY_CALLDATA: &str = "baa2abde\
+        000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2\
+        000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\
+        00000000000000000000000000000000000000000000000006f05b59d3b20000\
+        0000000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        000000000000000000000000000000000000000000000000000000006553f100";
+
+    fn classify_hex(calldata: &str, value: U256) -> Option<DecodedCall> {
+        classify(&default_selectors(), &hex::decode(calldata).unwrap(), value)
+    }
+
+    #[test]
+    fn test_classify_uniswap_v2_swap() {
+        let call = classify_hex(SWAP_EXACT_TOKENS_FOR_TOKENS_CALLDATA, U256::ZERO).unwrap();
+
+        assert_eq!(call.kind, CallKind::Swap);
+        assert_eq!(call.name, "swapExactTokensForTokens");
+        assert_eq!(call.token_in, Some(WETH));
+        assert_eq!(call.token_out, Some(USDC));
+        assert_eq!(call.amount_in, Some(U256::from(10u64.pow(18))));
+        assert_eq!(call.amount_out_min, Some(U256::from(2_500_000_000u64)));
+        assert_eq!(call.hops, Some(1));
+        assert_eq!(call.fee, None);
+    }
+
+    #[test]
+    fn test_classify_uniswap_v3_exact_input_single() {
+        let call = classify_hex(EXACT_INPUT_SINGLE_CALLDATA, U256::ZERO).unwrap();
+
+        assert_eq!(call.kind, CallKind::Swap);
+        assert_eq!(call.token_in, Some(USDC));
+        assert_eq!(call.token_out, Some(WETH));
+        assert_eq!(call.amount_in, Some(U256::from(2_500_000_000u64)));
+        assert_eq!(call.amount_out_min, Some(U256::from(990_000_000_000_000_000u64)));
+        assert_eq!(call.fee, Some(500));
+    }
+
+    #[test]
+    fn test_pool_key_matches_both_directions() {
+        let router = Some(Address::with_last_byte(1));
+        let v2 = classify_hex(SWAP_EXACT_TOKENS_FOR_TOKENS_CALLDATA, U256::ZERO).unwrap();
+        let v3 = classify_hex(EXACT_INPUT_SINGLE_CALLDATA, U256::ZERO).unwrap();
+
+        let mut reversed = v2.clone();
+        (reversed.token_in, reversed.token_out) = (v2.token_out, v2.token_in);
+        assert_eq!(v2.pool(router), reversed.pool(router));
+        assert_eq!(v2.pool(router).unwrap().token0, USDC);
+
+        // Same pair, but another fee tier or router is another pool
+        assert_ne!(v2.pool(router), v3.pool(router));
+        assert_ne!(v2.pool(router), v2.pool(None));
+
+        // Multi-hop swaps and liquidations don't trade against a single pool
+        let mut multi_hop = v2.clone();
+        multi_hop.hops = Some(2);
+        assert_eq!(multi_hop.pool(router), None);
+        assert_eq!(classify_hex(LIQUIDATION_CALL_CALLDATA, U256::ZERO).unwrap().pool(router), None);
+    }
+
+    #[test]
+    fn test_classify_aave_liquidation() {
+        let call = classify_hex(LIQUIDATION_CALL_CALLDATA, U256::ZERO).unwrap();
+
+        assert_eq!(call.kind, CallKind::Liquidation);
+        assert_eq!(call.token_in, Some(USDC));
+        assert_eq!(call.token_out, Some(WETH));
+        assert_eq!(call.amount_in, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(call.amount_out_min, None);
+    }
+
+    #[test]
+    fn test_classify_liquidity_change_and_oracle_update() {
+        let router = Some(Address::with_last_byte(1));
+        let call = classify_hex(REMOVE_LIQUIDITY_CALLDATA, U256::ZERO).unwrap();
+
+        assert_eq!(call.kind, CallKind::RemoveLiquidity);
+        assert_eq!(call.token_in, Some(WETH));
+        assert_eq!(call.token_out, Some(USDC));
+        assert_eq!(call.amount_in, Some(U256::from(5 * 10u64.pow(17))));
+        // Same pool as the swaps on the pair through the router
+        let swap = classify_hex(SWAP_EXACT_TOKENS_FOR_TOKENS_CALLDATA, U256::ZERO).unwrap();
+        assert_eq!(call.pool(router), swap.pool(router));
+
+        // Price updates are recognized without decoding the report
+        let update = classify(&default_selectors(), &OCR2_TRANSMIT, U256::ZERO).unwrap();
+        assert_eq!(update.kind, CallKind::OracleUpdate);
+        assert!(!update.is_decoded());
+    }
+
+    #[test]
+    fn test_classify_unknown_and_truncated_calldata() {
+        // ERC-20 `transfer` isn't in the table
+        assert!(classify_hex("a9059cbb", U256::ZERO).is_none());
+        assert!(classify(&default_selectors(), &[0x38, 0xed], U256::ZERO).is_none());
+
+        // A known selector with truncated arguments is classified, but not decoded
+        let truncated = &SWAP_EXACT_TOKENS_FOR_TOKENS_CALLDATA[..8 + 64 * 3];
+        let call = classify_hex(truncated, U256::ZERO).unwrap();
+        assert_eq!(call.kind, CallKind::Swap);
+        assert_eq!(call.amount_in, Some(U256::from(10u64.pow(18))));
+        assert!(!call.is_decoded());
+    }
+}
@@ -5,16 +5,39 @@
 //! - Sandwich attacks
 //! - Liquidations
 //! - Front-running opportunities
+//! - Back-runs of large swaps and price updates
+//! - JIT liquidity
+//!
+//! Calls matching the selector table in [`DetectorConfig`] are decoded, and
+//! opportunities in them are classified and valued from the tokens and amounts
+//! they trade. Liquidations are recognized by the configured
+//! [`LiquidationOracle`] first. Gas price heuristics remain as a fallback for
+//! calls the table doesn't decode.
+//!
+//! Besides analyzing blocks, the detector can stream pending transactions as
+//! they arrive. Opportunities among them are kept as candidates until the block
+//! including them is analyzed, so the sequencer can act before inclusion while
+//! only opportunities that actually landed are reported.
 
-use alloy_primitives::{Address, U256, B256};
+use super::{
+    calldata::{self, CallKind, DecodedCall, PoolKey, Selector, SelectorEntry},
+    liquidation::{LiquidationInfo, LiquidationOracle},
+};
+use alloy_primitives::{Address, Bytes, U256, B256};
 use alloy_consensus::Transaction;
 use alloy_consensus::transaction::SignerRecoverable;
+use crate::metrics::MevMetrics;
 use reth_primitives::TransactionSigned;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
 use tracing::{debug, info};
 
 /// Type of MEV opportunity detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum MevType {
     /// Arbitrage opportunity between DEXes
     Arbitrage,
@@ -48,7 +71,7 @@ impl MevType {
 }
 
 /// Detected MEV opportunity
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MevOpportunity {
     /// Type of MEV
     pub mev_type: MevType,
@@ -97,12 +120,27 @@ pub struct DetectorConfig {
     pub detect_sandwich: bool,
     /// Enable liquidation detection
     pub detect_liquidation: bool,
+    /// Enable back-run detection
+    pub detect_back_run: bool,
+    /// Enable JIT liquidity detection
+    pub detect_jit_liquidity: bool,
     /// Minimum value to report (in wei)
     pub min_value: U256,
     /// Known DEX router addresses
     pub dex_routers: HashSet<Address>,
     /// Known lending protocol addresses
     pub lending_protocols: HashSet<Address>,
+    /// Swap and liquidation selectors decoded from calldata
+    pub selectors: HashMap<Selector, SelectorEntry>,
+    /// Bonus earned on decoded liquidations, in basis points of the debt covered
+    pub liquidation_bonus_bps: u64,
+    /// Oracle recognizing liquidation calls, checked before the selector table
+    pub liquidation_oracle: Option<Arc<dyn LiquidationOracle>>,
+    /// Maximum number of pending transactions tracked in streaming mode
+    pub max_pending: usize,
+    /// Minimum amount sold for a decoded swap to attract back-runs and JIT
+    /// liquidity, in the smallest unit of the token sold
+    pub large_swap_min_amount: U256,
 }
 
 impl Default for DetectorConfig {
@@ -111,9 +149,16 @@ impl Default for DetectorConfig {
             detect_arbitrage: true,
             detect_sandwich: true,
             detect_liquidation: true,
+            detect_back_run: true,
+            detect_jit_liquidity: true,
             min_value: U256::from(100_000_000_000_000_000u64), // 0.1 ANDE
             dex_routers: HashSet::new(),
             lending_protocols: HashSet::new(),
+            selectors: calldata::default_selectors(),
+            liquidation_bonus_bps: 500, // 5%, Aave's bonus on most assets
+            liquidation_oracle: None,
+            max_pending: 4096,
+            large_swap_min_amount: U256::from(10_000_000_000_000_000_000u128), // 10 tokens of 18 decimals
         }
     }
 }
@@ -123,9 +168,15 @@ pub struct MevDetector {
     /// Configuration
     config: DetectorConfig,
     /// Recent transactions for pattern matching
-    recent_txs: Vec<(B256, TransactionInfo)>,
+    recent_txs: VecDeque<(B256, TransactionInfo)>,
     /// Maximum number of recent transactions to track
     max_recent_txs: usize,
+    /// Pending transactions seen in streaming mode
+    pending: PendingPool,
+    /// Opportunities among pending transactions, keyed by type and transaction
+    candidates: HashMap<(MevType, B256), PendingCandidate>,
+    /// Prometheus metrics
+    metrics: MevMetrics,
 }
 
 /// Transaction information for MEV detection
@@ -143,15 +194,29 @@ struct TransactionInfo {
     gas_price: U256,
     /// Block number
     block_number: u64,
+    /// Calldata
+    input: Bytes,
+    /// Call decoded from the calldata, if its selector is known
+    decoded: Option<DecodedCall>,
+}
+
+impl TransactionInfo {
+    /// Decoded call with tokens and amounts, if the calldata could be decoded
+    fn decoded_call(&self) -> Option<&DecodedCall> {
+        self.decoded.as_ref().filter(|call| call.is_decoded())
+    }
 }
 
 impl MevDetector {
     /// Create new MEV detector
     pub fn new(config: DetectorConfig) -> Self {
         Self {
+            pending: PendingPool::new(config.max_pending),
             config,
-            recent_txs: Vec::new(),
+            recent_txs: VecDeque::new(),
             max_recent_txs: 1000,
+            candidates: HashMap::new(),
+            metrics: MevMetrics::default(),
         }
     }
     
@@ -226,8 +291,94 @@ impl MevDetector {
         let cross_tx_opportunities = self.detect_cross_transaction_mev(transactions, block_number);
         all_opportunities.extend(cross_tx_opportunities);
         
+        self.metrics.opportunities_detected.increment(all_opportunities.len() as u64);
         all_opportunities
     }
+
+    /// Analyze a transaction as it arrives in the mempool, first seen at `seen_at`
+    ///
+    /// The transaction is tracked until it is included or evicted, the least
+    /// recently seen transaction going first once `max_pending` is reached. A
+    /// transaction replacing a pending one with the same sender and nonce
+    /// evicts it. Returns the opportunities the transaction takes part in; they
+    /// are kept as candidates for [`Self::drain_block_candidates`].
+    pub fn on_pending_transaction(&mut self, tx: &TransactionSigned, seen_at: Instant) -> Vec<MevOpportunity> {
+        // Pending transactions have no block yet, it is set once they are included
+        let tx_info = self.extract_tx_info(tx, 0);
+        let evicted = self.pending.insert(PendingTx { info: tx_info.clone(), nonce: tx.nonce(), seen_at });
+        if !evicted.is_empty() {
+            self.candidates.retain(|_, candidate| !candidate.txs.iter().any(|hash| evicted.contains(hash)));
+        }
+
+        let mut detected = Vec::new();
+        if self.config.detect_arbitrage {
+            detected.extend(self.detect_arbitrage(&tx_info).map(|opp| (opp, vec![tx_info.hash])));
+        }
+        if self.config.detect_liquidation {
+            detected.extend(self.detect_liquidation(&tx_info).map(|opp| (opp, vec![tx_info.hash])));
+        }
+        if let Some(to) = tx_info.to.filter(|_| self.config.detect_sandwich) {
+            let infos: Vec<TransactionInfo> =
+                self.pending.by_inclusion_order(&to).into_iter().map(|pending| pending.info.clone()).collect();
+            detected.extend(
+                self.detect_decoded_sandwiches(&infos, 0)
+                    .into_iter()
+                    .filter(|(_, txs)| txs.contains(&tx_info.hash)),
+            );
+        }
+
+        let mut opportunities = Vec::new();
+        for (opportunity, txs) in detected {
+            if opportunity.value < self.config.min_value {
+                continue;
+            }
+            debug!(
+                "Pending MEV candidate: type={}, value={}, tx={}",
+                opportunity.mev_type.name(),
+                opportunity.value,
+                opportunity.tx_hash
+            );
+            self.candidates.insert(
+                (opportunity.mev_type, opportunity.tx_hash),
+                PendingCandidate { opportunity: opportunity.clone(), txs },
+            );
+            opportunities.push(opportunity);
+        }
+        opportunities
+    }
+
+    /// Candidates from pending transactions that were included in `block_number`
+    ///
+    /// Call after [`Self::analyze_block`] for the block. A candidate is returned
+    /// once all its transactions were included in the order it assumes, and
+    /// dropped if only some of them were. Included transactions stop being
+    /// pending; candidates none of whose transactions were included are kept.
+    pub fn drain_block_candidates(&mut self, block_number: u64) -> Vec<MevOpportunity> {
+        let positions: HashMap<B256, usize> = self
+            .recent_txs
+            .iter()
+            .filter(|(_, info)| info.block_number == block_number)
+            .enumerate()
+            .map(|(position, (hash, _))| (*hash, position))
+            .collect();
+        for hash in positions.keys() {
+            self.pending.remove(hash);
+        }
+
+        let mut drained = Vec::new();
+        self.candidates.retain(|_, candidate| {
+            let included: Vec<usize> = candidate.txs.iter().filter_map(|hash| positions.get(hash).copied()).collect();
+            if included.len() == candidate.txs.len() && included.is_sorted() {
+                let mut opportunity = candidate.opportunity.clone();
+                opportunity.block_number = block_number;
+                drained.push(opportunity);
+            }
+            included.is_empty()
+        });
+
+        drained.sort_by_key(|opportunity| (positions[&opportunity.tx_hash], opportunity.mev_type));
+        drained
+    }
     
     /// Extract transaction information
     fn extract_tx_info(&self, tx: &TransactionSigned, block_number: u64) -> TransactionInfo {
@@ -254,11 +405,93 @@ impl MevDetector {
             value,
             gas_price,
             block_number,
+            input: tx.input().clone(),
+            decoded: calldata::classify(&self.config.selectors, tx.input(), value),
         }
     }
+
+    /// Decoded swap whose path starts and ends with the same token
+    ///
+    /// The minimum output over the input is the profit the sender locks in,
+    /// denominated in that token.
+    fn detect_decoded_arbitrage(&self, tx_info: &TransactionInfo, call: &DecodedCall) -> Option<MevOpportunity> {
+        if call.kind != CallKind::Swap || call.token_in != call.token_out {
+            return None;
+        }
+
+        let profit = call.amount_out_min?.checked_sub(call.amount_in?).filter(|profit| !profit.is_zero())?;
+        let mut opp = MevOpportunity::new(MevType::Arbitrage, tx_info.hash, profit, tx_info.block_number);
+        if let Some(to) = tx_info.to {
+            opp.add_address(to);
+        }
+        opp.add_address(tx_info.from);
+        opp.add_metadata("selector".to_string(), call.name.clone());
+        opp.add_metadata("token".to_string(), format!("{:?}", call.token_in?));
+
+        debug!("Arbitrage decoded: tx={}, profit={}", tx_info.hash, profit);
+        Some(opp)
+    }
+
+    /// Decoded liquidation, valued at the liquidation bonus on the debt covered
+    fn detect_decoded_liquidation(&self, tx_info: &TransactionInfo, call: &DecodedCall) -> Option<MevOpportunity> {
+        if call.kind != CallKind::Liquidation {
+            return None;
+        }
+
+        let bonus = call.amount_in?.saturating_mul(U256::from(self.config.liquidation_bonus_bps)) / U256::from(10_000);
+        let mut opp = MevOpportunity::new(MevType::Liquidation, tx_info.hash, bonus, tx_info.block_number);
+        if let Some(to) = tx_info.to {
+            opp.add_address(to);
+        }
+        opp.add_address(tx_info.from);
+        opp.add_metadata("selector".to_string(), call.name.clone());
+        opp.add_metadata("token".to_string(), format!("{:?}", call.token_in?));
+        opp.add_metadata("collateral".to_string(), format!("{:?}", call.token_out?));
+
+        debug!("Liquidation decoded: tx={}, bonus={}", tx_info.hash, bonus);
+        Some(opp)
+    }
+
+    /// Liquidation recognized by the oracle, valued at the liquidation bonus on the debt repaid
+    ///
+    /// Liquidations that don't name the amount repaid are valued at zero.
+    fn detect_oracle_liquidation(&self, tx_info: &TransactionInfo, info: LiquidationInfo) -> MevOpportunity {
+        let bonus = info
+            .repay_amount
+            .map(|repaid| repaid.saturating_mul(U256::from(self.config.liquidation_bonus_bps)) / U256::from(10_000))
+            .unwrap_or_default();
+        let mut opp = MevOpportunity::new(MevType::Liquidation, tx_info.hash, bonus, tx_info.block_number);
+        if let Some(to) = tx_info.to {
+            opp.add_address(to);
+        }
+        opp.add_address(tx_info.from);
+        opp.add_address(info.borrower);
+        opp.add_metadata("protocol".to_string(), info.protocol.name().to_string());
+        opp.add_metadata("borrower".to_string(), format!("{:?}", info.borrower));
+        if let Some(debt_asset) = info.debt_asset {
+            opp.add_metadata("token".to_string(), format!("{:?}", debt_asset));
+        }
+        if let Some(collateral_asset) = info.collateral_asset {
+            opp.add_metadata("collateral".to_string(), format!("{:?}", collateral_asset));
+        }
+
+        debug!(
+            "Liquidation reported by oracle: tx={}, protocol={}, borrower={}, bonus={}",
+            tx_info.hash,
+            info.protocol.name(),
+            info.borrower,
+            bonus
+        );
+        opp
+    }
     
     /// Detect arbitrage opportunities
     fn detect_arbitrage(&self, tx_info: &TransactionInfo) -> Option<MevOpportunity> {
+        if let Some(call) = tx_info.decoded_call() {
+            return self.detect_decoded_arbitrage(tx_info, call);
+        }
+
+        // Fallback for calls the selector table doesn't decode
         // Check if transaction interacts with DEX routers
         if let Some(to) = tx_info.to {
             if self.config.dex_routers.contains(&to) {
@@ -290,6 +523,12 @@ impl MevDetector {
     
     /// Detect sandwich attacks
     fn detect_sandwich(&self, tx_info: &TransactionInfo) -> Option<MevOpportunity> {
+        // Decoded swaps are matched across the block by pool in `detect_decoded_sandwiches`
+        if tx_info.decoded_call().is_some() {
+            return None;
+        }
+
+        // Fallback for calls the selector table doesn't decode
         // Look for pattern: high gas price + similar recent transaction
         // This is a simplified heuristic
         
@@ -326,6 +565,17 @@ impl MevDetector {
     
     /// Detect liquidation opportunities
     fn detect_liquidation(&self, tx_info: &TransactionInfo) -> Option<MevOpportunity> {
+        if let Some((oracle, to)) = self.config.liquidation_oracle.as_ref().zip(tx_info.to) {
+            if let Some(info) = oracle.is_liquidation_call(to, &tx_info.input) {
+                return Some(self.detect_oracle_liquidation(tx_info, info));
+            }
+        }
+
+        if let Some(call) = tx_info.decoded_call() {
+            return self.detect_decoded_liquidation(tx_info, call);
+        }
+
+        // Fallback for calls the selector table doesn't decode
         // Check if transaction interacts with lending protocols
         if let Some(to) = tx_info.to {
             if self.config.lending_protocols.contains(&to) {
@@ -359,7 +609,17 @@ impl MevDetector {
         transactions: &[TransactionSigned],
         block_number: u64,
     ) -> Vec<MevOpportunity> {
-        let mut opportunities = Vec::new();
+        let infos: Vec<TransactionInfo> =
+            transactions.iter().map(|tx| self.extract_tx_info(tx, block_number)).collect();
+
+        let mut opportunities: Vec<MevOpportunity> =
+            self.detect_decoded_sandwiches(&infos, block_number).into_iter().map(|(opp, _)| opp).collect();
+        if self.config.detect_back_run {
+            opportunities.extend(self.detect_back_runs(&infos, block_number));
+        }
+        if self.config.detect_jit_liquidity {
+            opportunities.extend(self.detect_jit_liquidity(&infos, block_number));
+        }
         
         // Look for sandwich patterns: front-run + victim + back-run
         if transactions.len() >= 3 {
@@ -367,14 +627,19 @@ impl MevDetector {
                 let tx1 = &transactions[i];
                 let tx2 = &transactions[i + 1];
                 let tx3 = &transactions[i + 2];
+
+                // Fallback for calls the selector table doesn't decode
+                if infos[i..i + 3].iter().any(|info| info.decoded_call().is_some()) {
+                    continue;
+                }
                 
                 // Extract signers
-                let signer1 = tx1.recover_signer().unwrap_or_default();
-                let signer2 = tx2.recover_signer().unwrap_or_default();
-                let signer3 = tx3.recover_signer().unwrap_or_default();
+                let signer1 = infos[i].from;
+                let signer2 = infos[i + 1].from;
+                let signer3 = infos[i + 2].from;
                 
-                // Check if tx1 and tx3 are from same address (potential sandwich)
-                if signer1 == signer3 && signer1 != signer2 && tx1.to() == tx3.to() {
+                // Check if tx1 and tx3 are from same address around a trade on the same contract
+                if signer1 == signer3 && signer1 != signer2 && tx1.to() == tx3.to() && tx1.to() == tx2.to() {
                     // Estimate MEV value from gas price difference
                     let gas_price1 = tx1.gas_price().unwrap_or(0);
                     let gas_price2 = tx2.gas_price().unwrap_or(0);
@@ -394,6 +659,7 @@ impl MevDetector {
                         opp.add_metadata("sandwich_type".to_string(), "detected".to_string());
                         opp.add_metadata("front_run_tx".to_string(), format!("{:?}", *tx1.hash()));
                         opp.add_metadata("back_run_tx".to_string(), format!("{:?}", *tx3.hash()));
+                        opp.add_metadata("confidence".to_string(), format_confidence(HEURISTIC_SANDWICH_CONFIDENCE));
                         
                         opportunities.push(opp);
                     }
@@ -403,14 +669,203 @@ impl MevDetector {
         
         opportunities
     }
+
+    /// Decoded swaps sandwiching another sender's swap on the same pool
+    ///
+    /// A back-run is a swap whose sender traded the same pool the opposite way
+    /// earlier in the block (the front-run), with a swap in the front-run's
+    /// direction from another sender (the victim) in between. Victims whose
+    /// slippage limit is at least as tight as the front-run's own limit are
+    /// skipped, since the front-run would push them past it. The opportunity is
+    /// reported on the victim, valued at the minimum the back-run returns over
+    /// what the front-run paid, in the front-run's input token. Each opportunity
+    /// comes with the front-run, victim and back-run hashes, in that order.
+    fn detect_decoded_sandwiches(
+        &self,
+        infos: &[TransactionInfo],
+        block_number: u64,
+    ) -> Vec<(MevOpportunity, Vec<B256>)> {
+        let swaps: Vec<(&TransactionInfo, &DecodedCall, PoolKey)> = infos
+            .iter()
+            .filter_map(|info| {
+                let call = info.decoded_call().filter(|call| call.kind == CallKind::Swap)?;
+                Some((info, call, call.pool(info.to)?))
+            })
+            .collect();
+
+        let mut opportunities = Vec::new();
+        for (back_pos, &(back_info, back_run, pool)) in swaps.iter().enumerate() {
+            // Latest earlier swap by the same sender in the opposite direction
+            let Some(front_pos) = swaps[..back_pos].iter().rposition(|(info, call, key)| {
+                info.from == back_info.from && *key == pool && call.token_in == back_run.token_out
+            }) else {
+                continue;
+            };
+            let (front_info, front_run, _) = swaps[front_pos];
+
+            let victim = swaps[front_pos + 1..back_pos].iter().find_map(|&(info, call, key)| {
+                let candidate = info.from != back_info.from && key == pool && call.token_in == front_run.token_in;
+                let exposure = slippage_exposure(front_run, call);
+                (candidate && exposure != Some(false)).then_some((info, exposure))
+            });
+            let Some((victim_info, exposure)) = victim else {
+                continue;
+            };
+
+            let (Some(amount_out_min), Some(amount_in)) = (back_run.amount_out_min, front_run.amount_in) else {
+                continue;
+            };
+            let value = amount_out_min.saturating_sub(amount_in);
+
+            // Pool and direction matches are required, the rest raises confidence
+            let mut confidence = 0.6;
+            if exposure == Some(true) {
+                confidence += 0.2;
+            }
+            if !value.is_zero() {
+                confidence += 0.2;
+            }
+
+            let mut opp = MevOpportunity::new(MevType::Sandwich, victim_info.hash, value, block_number);
+            opp.add_address(back_info.from);
+            opp.add_address(victim_info.from);
+            opp.add_metadata("sandwich_type".to_string(), "decoded".to_string());
+            opp.add_metadata("front_run_tx".to_string(), format!("{:?}", front_info.hash));
+            opp.add_metadata("back_run_tx".to_string(), format!("{:?}", back_info.hash));
+            opp.add_metadata("token".to_string(), format!("{:?}", front_run.token_in.unwrap_or_default()));
+            opp.add_metadata("confidence".to_string(), format_confidence(confidence));
+
+            debug!("Sandwich decoded: victim={}, back_run={}, confidence={:.2}", victim_info.hash, back_info.hash, confidence);
+            opportunities.push((opp, vec![front_info.hash, victim_info.hash, back_info.hash]));
+        }
+
+        opportunities
+    }
     
+    /// Decoded swap from another sender right after a large swap or price update
+    ///
+    /// After a large swap, the back-run trades the same pool the other way, or
+    /// arbitrages through the venue starting from one of the pool's tokens. It
+    /// is valued at the profit it locks in if cyclic, and at zero otherwise,
+    /// since its calldata doesn't tell the reverted price. After a price
+    /// update, the back-run is a liquidation, valued at its bonus.
+    fn detect_back_runs(&self, infos: &[TransactionInfo], block_number: u64) -> Vec<MevOpportunity> {
+        let mut opportunities = Vec::new();
+        for pair in infos.windows(2) {
+            let (trigger, back_run) = (&pair[0], &pair[1]);
+            let Some(back_call) = back_run.decoded_call().filter(|_| back_run.from != trigger.from) else {
+                continue;
+            };
+
+            let (trigger_type, value, token) = match trigger.decoded.as_ref().map(|call| call.kind) {
+                Some(CallKind::OracleUpdate) if back_call.kind == CallKind::Liquidation => {
+                    let Some(liquidation) = self.detect_decoded_liquidation(back_run, back_call) else {
+                        continue;
+                    };
+                    ("oracle", liquidation.value, back_call.token_in)
+                }
+                Some(CallKind::Swap) if back_call.kind == CallKind::Swap => {
+                    let Some((swap, pool)) =
+                        trigger.decoded_call().and_then(|swap| Some((swap, swap.pool(trigger.to)?)))
+                    else {
+                        continue;
+                    };
+                    if swap.amount_in.is_none_or(|amount| amount < self.config.large_swap_min_amount) {
+                        continue;
+                    }
+
+                    let reverts_pool = back_call.pool(back_run.to) == Some(pool) && back_call.token_in == swap.token_out;
+                    let cyclic = back_run.to == pool.venue
+                        && back_call.token_in == back_call.token_out
+                        && back_call.token_in.is_some_and(|token| token == pool.token0 || token == pool.token1);
+                    if !reverts_pool && !cyclic {
+                        continue;
+                    }
+                    let profit = if cyclic {
+                        back_call.amount_out_min.zip(back_call.amount_in).map(|(out, paid)| out.saturating_sub(paid))
+                    } else {
+                        None
+                    };
+                    ("swap", profit.unwrap_or_default(), back_call.token_in)
+                }
+                _ => continue,
+            };
+
+            let mut opp = MevOpportunity::new(MevType::BackRun, back_run.hash, value, block_number);
+            opp.add_address(back_run.from);
+            opp.add_address(trigger.from);
+            opp.add_metadata("trigger_type".to_string(), trigger_type.to_string());
+            opp.add_metadata("trigger_tx".to_string(), format!("{:?}", trigger.hash));
+            if let Some(token) = token {
+                opp.add_metadata("token".to_string(), format!("{:?}", token));
+            }
+
+            debug!("Back-run decoded: tx={}, trigger={}", back_run.hash, trigger.hash);
+            opportunities.push(opp);
+        }
+
+        opportunities
+    }
+
+    /// Liquidity added and removed by the same sender around another sender's large swap on the pool
+    ///
+    /// Reported on the swap, valued at the pool fee on its input, which bounds
+    /// what the just-in-time position earns, in the token sold. V2 pools, which
+    /// name no fee tier, charge 0.3%.
+    fn detect_jit_liquidity(&self, infos: &[TransactionInfo], block_number: u64) -> Vec<MevOpportunity> {
+        let pooled: Vec<(&TransactionInfo, &DecodedCall, PoolKey)> = infos
+            .iter()
+            .filter_map(|info| {
+                let call = info.decoded_call()?;
+                Some((info, call, call.pool(info.to)?))
+            })
+            .collect();
+
+        let mut opportunities = Vec::new();
+        for (swap_pos, &(swap_info, swap, pool)) in pooled.iter().enumerate() {
+            let Some(amount_in) = swap.amount_in.filter(|_| swap.kind == CallKind::Swap) else {
+                continue;
+            };
+            if amount_in < self.config.large_swap_min_amount {
+                continue;
+            }
+
+            // Latest mint before the swap, and the same sender's first burn after it
+            let Some(&(mint_info, _, _)) = pooled[..swap_pos].iter().rev().find(|(info, call, key)| {
+                call.kind == CallKind::AddLiquidity && *key == pool && info.from != swap_info.from
+            }) else {
+                continue;
+            };
+            let Some(&(burn_info, _, _)) = pooled[swap_pos + 1..].iter().find(|(info, call, key)| {
+                call.kind == CallKind::RemoveLiquidity && *key == pool && info.from == mint_info.from
+            }) else {
+                continue;
+            };
+
+            let fee = pool.fee.unwrap_or(3_000); // hundredths of a basis point
+            let value = amount_in.saturating_mul(U256::from(fee)) / U256::from(1_000_000);
+
+            let mut opp = MevOpportunity::new(MevType::JitLiquidity, swap_info.hash, value, block_number);
+            opp.add_address(mint_info.from);
+            opp.add_address(swap_info.from);
+            opp.add_metadata("mint_tx".to_string(), format!("{:?}", mint_info.hash));
+            opp.add_metadata("burn_tx".to_string(), format!("{:?}", burn_info.hash));
+            opp.add_metadata("token".to_string(), format!("{:?}", swap.token_in.unwrap_or_default()));
+
+            debug!("JIT liquidity decoded: swap={}, mint={}, burn={}", swap_info.hash, mint_info.hash, burn_info.hash);
+            opportunities.push(opp);
+        }
+
+        opportunities
+    }
+
     /// Add transaction to recent history
     fn add_recent_tx(&mut self, tx_info: TransactionInfo) {
-        self.recent_txs.push((tx_info.hash, tx_info));
+        self.recent_txs.push_back((tx_info.hash, tx_info));
         
         // Keep only recent transactions
         if self.recent_txs.len() > self.max_recent_txs {
-            self.recent_txs.remove(0);
+            self.recent_txs.pop_front();
         }
     }
     
@@ -429,6 +884,159 @@ impl MevDetector {
     pub fn add_lending_protocol(&mut self, address: Address) {
         self.config.lending_protocols.insert(address);
     }
+
+    /// Add a selector to decode from calldata
+    pub fn add_selector(&mut self, selector: Selector, entry: SelectorEntry) {
+        self.config.selectors.insert(selector, entry);
+    }
+
+    /// Recognize liquidations with `oracle`
+    pub fn set_liquidation_oracle(&mut self, oracle: Arc<dyn LiquidationOracle>) {
+        self.config.liquidation_oracle = Some(oracle);
+    }
+}
+
+/// Transaction tracked in streaming mode until it is included or evicted
+#[derive(Debug, Clone)]
+struct PendingTx {
+    /// Transaction information, without a block number
+    info: TransactionInfo,
+    /// Sender nonce, to recognize replacements
+    nonce: u64,
+    /// When the transaction was last seen
+    seen_at: Instant,
+}
+
+/// Opportunity among pending transactions, awaiting their inclusion
+#[derive(Debug, Clone)]
+struct PendingCandidate {
+    /// Opportunity as detected, without a block number
+    opportunity: MevOpportunity,
+    /// Transactions that must all be included, in this order, for it to land
+    txs: Vec<B256>,
+}
+
+/// Pending transactions indexed by sender and target, capped by evicting the least recently seen
+#[derive(Debug)]
+struct PendingPool {
+    /// Maximum number of transactions tracked
+    capacity: usize,
+    /// Tracked transactions by hash
+    txs: HashMap<B256, PendingTx>,
+    /// Tracked transactions, least recently seen first
+    by_seen: BTreeSet<(Instant, B256)>,
+    /// Tracked transactions by sender
+    by_sender: HashMap<Address, HashSet<B256>>,
+    /// Tracked transactions by called contract
+    by_target: HashMap<Address, HashSet<B256>>,
+}
+
+impl PendingPool {
+    /// Create a pool tracking at most `capacity` transactions
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            txs: HashMap::new(),
+            by_seen: BTreeSet::new(),
+            by_sender: HashMap::new(),
+            by_target: HashMap::new(),
+        }
+    }
+
+    /// Track `tx`, returning the hashes of the transactions it replaced or evicted
+    ///
+    /// A transaction seen again keeps its information but counts as recently seen.
+    fn insert(&mut self, tx: PendingTx) -> Vec<B256> {
+        let hash = tx.info.hash;
+        let mut removed: Vec<B256> = self
+            .by_sender
+            .get(&tx.info.from)
+            .into_iter()
+            .flatten()
+            .filter(|other| **other != hash && self.txs[*other].nonce == tx.nonce)
+            .copied()
+            .collect();
+        for replaced in &removed {
+            self.remove(replaced);
+        }
+
+        let tx = match self.remove(&hash) {
+            Some(known) => PendingTx { seen_at: tx.seen_at.max(known.seen_at), ..known },
+            None => tx,
+        };
+        self.by_seen.insert((tx.seen_at, hash));
+        self.by_sender.entry(tx.info.from).or_default().insert(hash);
+        if let Some(to) = tx.info.to {
+            self.by_target.entry(to).or_default().insert(hash);
+        }
+        self.txs.insert(hash, tx);
+
+        while self.txs.len() > self.capacity {
+            let Some((_, oldest)) = self.by_seen.first().copied() else {
+                break;
+            };
+            self.remove(&oldest);
+            removed.push(oldest);
+        }
+        removed
+    }
+
+    /// Stop tracking `hash`
+    fn remove(&mut self, hash: &B256) -> Option<PendingTx> {
+        let tx = self.txs.remove(hash)?;
+        self.by_seen.remove(&(tx.seen_at, *hash));
+        remove_indexed(&mut self.by_sender, &tx.info.from, hash);
+        if let Some(to) = &tx.info.to {
+            remove_indexed(&mut self.by_target, to, hash);
+        }
+        Some(tx)
+    }
+
+    /// Transactions calling `target`, in the order a fee-ordered block would include them
+    fn by_inclusion_order(&self, target: &Address) -> Vec<&PendingTx> {
+        let mut txs: Vec<&PendingTx> =
+            self.by_target.get(target).into_iter().flatten().map(|hash| &self.txs[hash]).collect();
+        txs.sort_by(|a, b| {
+            b.info.gas_price.cmp(&a.info.gas_price).then(a.seen_at.cmp(&b.seen_at)).then(a.nonce.cmp(&b.nonce))
+        });
+        txs
+    }
+}
+
+/// Remove `hash` from the set indexed under `key`, dropping the set once empty
+fn remove_indexed(index: &mut HashMap<Address, HashSet<B256>>, key: &Address, hash: &B256) {
+    if let Some(hashes) = index.get_mut(key) {
+        hashes.remove(hash);
+        if hashes.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+/// Confidence of sandwiches matched on gas prices alone
+const HEURISTIC_SANDWICH_CONFIDENCE: f64 = 0.3;
+
+/// Confidence as reported in opportunity metadata
+fn format_confidence(confidence: f64) -> String {
+    format!("{confidence:.2}")
+}
+
+/// Whether a front-run can push `victim` past its slippage limit
+///
+/// Compares the minimum output per unit of input both swaps accept. A victim
+/// accepting any output is always exposed; `None` means the front-run sets no
+/// limit of its own to compare against, and `Some(false)` that the victim's
+/// limit is at least as tight as the front-run's.
+fn slippage_exposure(front_run: &DecodedCall, victim: &DecodedCall) -> Option<bool> {
+    let (front_in, victim_in) = (front_run.amount_in?, victim.amount_in?);
+    let (front_min, victim_min) = (front_run.amount_out_min?, victim.amount_out_min?);
+    if victim_min.is_zero() {
+        return Some(true);
+    }
+    if front_min.is_zero() {
+        return None;
+    }
+    Some(victim_min.saturating_mul(front_in) < front_min.saturating_mul(victim_in))
 }
 
 #[cfg(test)]
@@ -478,4 +1086,398 @@ mod tests {
         config.dex_routers.insert(dex_addr);
         assert!(config.dex_routers.contains(&dex_addr));
     }
+
+    /// Legacy transaction from one of several test signers
+    fn test_transaction(signer: u64, to: Address, gas_price: u128, input: Vec<u8>) -> TransactionSigned {
+        test_transaction_with_nonce(signer, 0, to, gas_price, input)
+    }
+
+    /// Legacy transaction from one of several test signers, with the given nonce
+    fn test_transaction_with_nonce(
+        signer: u64,
+        nonce: u64,
+        to: Address,
+        gas_price: u128,
+        input: Vec<u8>,
+    ) -> TransactionSigned {
+        use alloy_consensus::{TxLegacy, TypedTransaction};
+        use alloy_primitives::{Signature, TxKind};
+
+        let tx = TxLegacy {
+            chain_id: Some(31337),
+            nonce,
+            gas_price,
+            gas_limit: 300_000,
+            to: TxKind::Call(to),
+            input: input.into(),
+            ..Default::default()
+        };
+        let signature = Signature::new(Signature::test_signature().r(), U256::from(signer), false);
+        TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), signature)
+    }
+
+    /// ABI-encoded `swapExactTokensForTokens(amount_in, amount_out_min, path, 0x..01, 1700000000)`
+    fn v2_swap_calldata(amount_in: u128, amount_out_min: u128, path: &[Address]) -> Vec<u8> {
+        let mut words = vec![
+            U256::from(amount_in),
+            U256::from(amount_out_min),
+            U256::from(5 * 32), // offset of `path`
+            U256::from(1),
+            U256::from(1_700_000_000u64),
+            U256::from(path.len()),
+        ];
+        words.extend(path.iter().map(|token| U256::from_be_slice(token.as_slice())));
+
+        let mut calldata = calldata::SWAP_EXACT_TOKENS_FOR_TOKENS.to_vec();
+        calldata.extend(words.iter().flat_map(|word| word.to_be_bytes::<32>()));
+        calldata
+    }
+
+    /// Selector followed by static arguments
+    fn encode_call(selector: calldata::Selector, words: &[U256]) -> Vec<u8> {
+        let mut calldata = selector.to_vec();
+        calldata.extend(words.iter().flat_map(|word| word.to_be_bytes::<32>()));
+        calldata
+    }
+
+    fn address_word(address: Address) -> U256 {
+        U256::from_be_slice(address.as_slice())
+    }
+
+    /// ABI-encoded `liquidationCall(collateral, debt, 0x..02, debt_to_cover, false)`
+    fn liquidation_calldata(collateral: Address, debt: Address, debt_to_cover: u128) -> Vec<u8> {
+        let words =
+            [address_word(collateral), address_word(debt), U256::from(2), U256::from(debt_to_cover), U256::ZERO];
+        encode_call(calldata::LIQUIDATION_CALL, &words)
+    }
+
+    /// ABI-encoded `addLiquidity(token_a, token_b, amount, amount, 0, 0, 0x..01, 1700000000)`
+    fn add_liquidity_calldata(token_a: Address, token_b: Address, amount: u128) -> Vec<u8> {
+        let (a, b, amount) = (address_word(token_a), address_word(token_b), U256::from(amount));
+        let words = [a, b, amount, amount, U256::ZERO, U256::ZERO, U256::from(1), U256::from(1_700_000_000u64)];
+        encode_call(calldata::ADD_LIQUIDITY, &words)
+    }
+
+    /// ABI-encoded `removeLiquidity(token_a, token_b, liquidity, 0, 0, 0x..01, 1700000000)`
+    fn remove_liquidity_calldata(token_a: Address, token_b: Address, liquidity: u128) -> Vec<u8> {
+        let (a, b, liquidity) = (address_word(token_a), address_word(token_b), U256::from(liquidity));
+        let words = [a, b, liquidity, U256::ZERO, U256::ZERO, U256::from(1), U256::from(1_700_000_000u64)];
+        encode_call(calldata::REMOVE_LIQUIDITY, &words)
+    }
+
+    /// Detector reporting opportunities of any value
+    fn decoding_detector(router: Address) -> MevDetector {
+        let mut detector = MevDetector::new(DetectorConfig { min_value: U256::ZERO, ..Default::default() });
+        detector.add_dex_router(router);
+        detector
+    }
+
+    const ETHER: u128 = 1_000_000_000_000_000_000;
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn test_decoded_cyclic_swap_is_arbitrage_at_any_gas_price() {
+        let (router, weth, usdc) = (Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+
+        // Locks in 0.2 WETH of profit while paying a base fee gas price
+        let tx = test_transaction(1, router, GWEI, v2_swap_calldata(ETHER, 12 * ETHER / 10, &[weth, usdc, weth]));
+        let opportunities = detector.analyze_transaction(&tx, 1);
+
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].mev_type, MevType::Arbitrage);
+        assert_eq!(opportunities[0].value, U256::from(2 * ETHER / 10));
+        assert_eq!(opportunities[0].metadata["token"], format!("{:?}", weth));
+    }
+
+    #[test]
+    fn test_decoded_swap_during_fee_spike_is_not_arbitrage() {
+        let (router, weth, usdc) = (Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+
+        // 200 gwei to a known router used to be reported on gas price alone
+        let swap = test_transaction(1, router, 200 * GWEI, v2_swap_calldata(ETHER, 2_500_000_000, &[weth, usdc]));
+        assert!(detector.analyze_transaction(&swap, 1).is_empty());
+
+        // Calldata the table doesn't decode still goes through the heuristic
+        let opaque = test_transaction(2, router, 200 * GWEI, vec![0xde, 0xad, 0xbe, 0xef]);
+        let opportunities = detector.analyze_transaction(&opaque, 1);
+        assert!(opportunities.iter().any(|opp| opp.mev_type == MevType::Arbitrage));
+    }
+
+    #[test]
+    fn test_decoded_sandwich_reported_on_victim() {
+        let (router, weth, usdc) = (Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+
+        // All at the same gas price, which the heuristics can't tell apart. The
+        // victim accepts 2400 USDC per WETH, the front-run at least 2550.
+        let front_run = test_transaction(1, router, GWEI, v2_swap_calldata(10 * ETHER, 25_500_000_000, &[weth, usdc]));
+        let victim = test_transaction(2, router, GWEI, v2_swap_calldata(ETHER, 2_400_000_000, &[weth, usdc]));
+        let back_run = test_transaction(1, router, GWEI, v2_swap_calldata(25_500_000_000, 103 * ETHER / 10, &[usdc, weth]));
+        let transactions = vec![front_run.clone(), victim.clone(), back_run.clone()];
+
+        let opportunities = detector.analyze_block(&transactions, 1);
+        let sandwiches: Vec<_> = opportunities.iter().filter(|opp| opp.mev_type == MevType::Sandwich).collect();
+        assert_eq!(sandwiches.len(), 1);
+        assert_eq!(sandwiches[0].tx_hash, *victim.hash());
+        assert_eq!(sandwiches[0].value, U256::from(3 * ETHER / 10));
+        assert_eq!(sandwiches[0].metadata["front_run_tx"], format!("{:?}", front_run.hash()));
+        assert_eq!(sandwiches[0].metadata["back_run_tx"], format!("{:?}", back_run.hash()));
+        assert_eq!(sandwiches[0].metadata["confidence"], "1.00");
+    }
+
+    #[test]
+    fn test_decoded_swaps_not_sandwiching_the_same_pool_are_ignored() {
+        let (router, weth, usdc, dai) = (Address::random(), Address::random(), Address::random(), Address::random());
+        let front_run = v2_swap_calldata(10 * ETHER, 25_500_000_000, &[weth, usdc]);
+        let victim = v2_swap_calldata(ETHER, 2_400_000_000, &[weth, usdc]);
+        let back_run = v2_swap_calldata(25_500_000_000, 103 * ETHER / 10, &[usdc, weth]);
+
+        let cases = [
+            // Victim trades another pair through the same router
+            ("different pool", front_run.clone(), v2_swap_calldata(ETHER, 2_400_000_000, &[weth, dai]), back_run.clone()),
+            // Attacker never reverses its position
+            ("same direction", front_run.clone(), victim.clone(), v2_swap_calldata(ETHER, 2_500_000_000, &[weth, usdc])),
+            // Victim moves the price back in the attacker's favour
+            ("opposite victim", front_run.clone(), v2_swap_calldata(2_400_000_000, ETHER / 2, &[usdc, weth]), back_run.clone()),
+            // Victim's limit is tighter than the front-run's, so it would revert
+            ("tight slippage", front_run, v2_swap_calldata(ETHER, 2_600_000_000, &[weth, usdc]), back_run),
+        ];
+
+        for (case, front_run, victim, back_run) in cases {
+            let transactions = vec![
+                test_transaction(1, router, GWEI, front_run),
+                test_transaction(2, router, GWEI, victim),
+                test_transaction(1, router, GWEI, back_run),
+            ];
+            let opportunities = decoding_detector(router).analyze_block(&transactions, 1);
+            assert!(
+                opportunities.iter().all(|opp| opp.mev_type != MevType::Sandwich),
+                "{case}: unexpected sandwich"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decoded_liquidation_valued_at_bonus() {
+        let (pool, weth, usdc) = (Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(Address::random());
+
+        let tx = test_transaction(1, pool, GWEI, liquidation_calldata(weth, usdc, 1_000_000_000));
+        let opportunities = detector.analyze_transaction(&tx, 1);
+
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].mev_type, MevType::Liquidation);
+        // 5% of 1000 USDC
+        assert_eq!(opportunities[0].value, U256::from(50_000_000u64));
+        assert_eq!(opportunities[0].metadata["token"], format!("{:?}", usdc));
+        assert_eq!(opportunities[0].metadata["collateral"], format!("{:?}", weth));
+    }
+
+    #[test]
+    fn test_oracle_liquidations_carry_borrower_and_assets() {
+        use crate::mev::liquidation::DefaultLiquidationOracle;
+
+        let (aave_pool, c_usdc, c_eth) = (Address::random(), Address::random(), Address::random());
+        let (weth, usdc, borrower) = (Address::random(), Address::random(), Address::with_last_byte(2));
+        let mut detector = decoding_detector(Address::random());
+        detector.set_liquidation_oracle(Arc::new(DefaultLiquidationOracle::new()));
+
+        // Aave v3 `liquidationCall(WETH, USDC, 0x..02, 1000e6, false)`
+        let aave = test_transaction(1, aave_pool, GWEI, liquidation_calldata(weth, usdc, 1_000_000_000));
+        let opportunities = detector.analyze_transaction(&aave, 1);
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].value, U256::from(50_000_000u64));
+        assert_eq!(opportunities[0].metadata["protocol"], "Aave v3");
+        assert_eq!(opportunities[0].metadata["borrower"], format!("{:?}", borrower));
+        assert_eq!(opportunities[0].metadata["token"], format!("{:?}", usdc));
+        assert_eq!(opportunities[0].metadata["collateral"], format!("{:?}", weth));
+        assert!(opportunities[0].addresses.contains(&borrower));
+
+        // Compound v2 `liquidateBorrow(0x..02, 2000e6, cETH)` on cUSDC, sent without value
+        let calldata = encode_call(
+            calldata::LIQUIDATE_BORROW,
+            &[address_word(borrower), U256::from(2_000_000_000u64), address_word(c_eth)],
+        );
+        let compound = test_transaction(2, c_usdc, GWEI, calldata);
+        let opportunities = detector.analyze_transaction(&compound, 1);
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].mev_type, MevType::Liquidation);
+        // 5% of 2000 USDC
+        assert_eq!(opportunities[0].value, U256::from(100_000_000u64));
+        assert_eq!(opportunities[0].metadata["protocol"], "Compound v2");
+        assert_eq!(opportunities[0].metadata["borrower"], format!("{:?}", borrower));
+        assert_eq!(opportunities[0].metadata["token"], format!("{:?}", c_usdc));
+        assert_eq!(opportunities[0].metadata["collateral"], format!("{:?}", c_eth));
+
+        // Without the oracle, the Compound call isn't recognized
+        assert!(decoding_detector(Address::random()).analyze_transaction(&compound, 1).is_empty());
+    }
+
+    /// Opportunities of `mev_type` detected in a block of `transactions`
+    fn detect_in_block(detector: &mut MevDetector, transactions: &[TransactionSigned], mev_type: MevType) -> Vec<MevOpportunity> {
+        detector.analyze_block(transactions, 1).into_iter().filter(|opp| opp.mev_type == mev_type).collect()
+    }
+
+    #[test]
+    fn test_back_runs_after_large_swap_and_oracle_update() {
+        let (router, oracle, lending_pool) = (Address::random(), Address::random(), Address::random());
+        let (weth, usdc) = (Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+        let whale = test_transaction(1, router, GWEI, v2_swap_calldata(50 * ETHER, 120_000_000_000, &[weth, usdc]));
+
+        // Reversing the pool right after the whale
+        let reverse = test_transaction(2, router, GWEI, v2_swap_calldata(10_000_000_000, 4 * ETHER, &[usdc, weth]));
+        let back_runs = detect_in_block(&mut detector, &[whale.clone(), reverse.clone()], MevType::BackRun);
+        assert_eq!(back_runs.len(), 1);
+        assert_eq!(back_runs[0].tx_hash, *reverse.hash());
+        assert_eq!(back_runs[0].value, U256::ZERO);
+        assert_eq!(back_runs[0].metadata["trigger_type"], "swap");
+        assert_eq!(back_runs[0].metadata["trigger_tx"], format!("{:?}", whale.hash()));
+
+        // Arbitraging through the venue, valued at the profit locked in
+        let cyclic = test_transaction(3, router, GWEI, v2_swap_calldata(ETHER, 11 * ETHER / 10, &[weth, usdc, weth]));
+        let back_runs = detect_in_block(&mut detector, &[whale.clone(), cyclic.clone()], MevType::BackRun);
+        assert_eq!(back_runs.len(), 1);
+        assert_eq!(back_runs[0].tx_hash, *cyclic.hash());
+        assert_eq!(back_runs[0].value, U256::from(ETHER / 10));
+
+        // Liquidating right after a price update
+        let update = test_transaction(4, oracle, GWEI, calldata::OCR2_TRANSMIT.to_vec());
+        let liquidation = test_transaction(5, lending_pool, GWEI, liquidation_calldata(weth, usdc, 1_000_000_000));
+        let back_runs = detect_in_block(&mut detector, &[update.clone(), liquidation.clone()], MevType::BackRun);
+        assert_eq!(back_runs.len(), 1);
+        assert_eq!(back_runs[0].tx_hash, *liquidation.hash());
+        assert_eq!(back_runs[0].value, U256::from(50_000_000u64));
+        assert_eq!(back_runs[0].metadata["trigger_type"], "oracle");
+        assert_eq!(back_runs[0].metadata["trigger_tx"], format!("{:?}", update.hash()));
+
+        // Small swaps, the whale's own follow-up and non-adjacent trades are not back-runs
+        let small = test_transaction(1, router, GWEI, v2_swap_calldata(ETHER, 2_400_000_000, &[weth, usdc]));
+        let own = test_transaction(1, router, GWEI, v2_swap_calldata(10_000_000_000, 4 * ETHER, &[usdc, weth]));
+        let unrelated = test_transaction(6, Address::random(), GWEI, Vec::new());
+        for block in [
+            vec![small, reverse.clone()],
+            vec![whale.clone(), own],
+            vec![whale, unrelated, reverse],
+        ] {
+            assert!(detect_in_block(&mut detector, &block, MevType::BackRun).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_jit_liquidity_bracketing_large_swap() {
+        let (router, weth, usdc) = (Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+
+        let mint = test_transaction(1, router, GWEI, add_liquidity_calldata(usdc, weth, 500 * ETHER));
+        let swap = test_transaction(2, router, GWEI, v2_swap_calldata(50 * ETHER, 120_000_000_000, &[weth, usdc]));
+        let burn = test_transaction(1, router, GWEI, remove_liquidity_calldata(weth, usdc, 100 * ETHER));
+
+        let jit = detect_in_block(&mut detector, &[mint.clone(), swap.clone(), burn.clone()], MevType::JitLiquidity);
+        assert_eq!(jit.len(), 1);
+        assert_eq!(jit[0].tx_hash, *swap.hash());
+        // 0.3% of the 50 WETH sold
+        assert_eq!(jit[0].value, U256::from(15 * ETHER / 100));
+        assert_eq!(jit[0].metadata["mint_tx"], format!("{:?}", mint.hash()));
+        assert_eq!(jit[0].metadata["burn_tx"], format!("{:?}", burn.hash()));
+
+        // Liquidity removed by someone else, or on another pair, doesn't bracket the swap
+        let other_burn = test_transaction(3, router, GWEI, remove_liquidity_calldata(weth, usdc, 100 * ETHER));
+        let other_pair = test_transaction(1, router, GWEI, remove_liquidity_calldata(weth, Address::random(), 100 * ETHER));
+        for burn in [other_burn, other_pair] {
+            let block = [mint.clone(), swap.clone(), burn];
+            assert!(detect_in_block(&mut detector, &block, MevType::JitLiquidity).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_pending_transactions_evicted_under_cap() {
+        use std::time::Duration;
+
+        let router = Address::random();
+        let mut detector = MevDetector::new(DetectorConfig { max_pending: 3, ..Default::default() });
+        let start = Instant::now();
+        let txs: Vec<_> = (1..=4).map(|signer| test_transaction(signer, router, GWEI, Vec::new())).collect();
+
+        for (offset, tx) in txs[..3].iter().enumerate() {
+            detector.on_pending_transaction(tx, start + Duration::from_secs(offset as u64));
+        }
+        // Seeing the first transaction again makes the second the least recently seen
+        detector.on_pending_transaction(&txs[0], start + Duration::from_secs(3));
+        detector.on_pending_transaction(&txs[3], start + Duration::from_secs(4));
+
+        assert_eq!(detector.pending.txs.len(), 3);
+        assert!(!detector.pending.txs.contains_key(txs[1].hash()));
+        assert!(!detector.pending.by_sender.contains_key(&txs[1].recover_signer().unwrap()));
+        assert_eq!(detector.pending.by_target[&router].len(), 3);
+
+        // A replacement with the same sender and nonce evicts the original
+        let replacement = test_transaction(4, router, 2 * GWEI, vec![0x01]);
+        detector.on_pending_transaction(&replacement, start + Duration::from_secs(5));
+        assert_eq!(detector.pending.txs.len(), 3);
+        assert!(!detector.pending.txs.contains_key(txs[3].hash()));
+        assert_eq!(detector.pending.by_sender[&replacement.recover_signer().unwrap()].len(), 1);
+    }
+
+    #[test]
+    fn test_pending_candidates_drained_once_included() {
+        use std::time::Duration;
+
+        let (router, weth, usdc, dai) = (Address::random(), Address::random(), Address::random(), Address::random());
+        let mut detector = decoding_detector(router);
+        let start = Instant::now();
+
+        // The attacker sees the victim first, then brackets it by fee
+        let victim = test_transaction(2, router, 10 * GWEI, v2_swap_calldata(ETHER, 2_400_000_000, &[weth, usdc]));
+        let front_run =
+            test_transaction_with_nonce(1, 0, router, 20 * GWEI, v2_swap_calldata(10 * ETHER, 25_500_000_000, &[weth, usdc]));
+        let back_run = test_transaction_with_nonce(
+            1,
+            1,
+            router,
+            10 * GWEI,
+            v2_swap_calldata(25_500_000_000, 103 * ETHER / 10, &[usdc, weth]),
+        );
+        let arbitrage = test_transaction(3, router, GWEI, v2_swap_calldata(ETHER, 2 * ETHER, &[dai, weth, dai]));
+
+        assert!(detector.on_pending_transaction(&victim, start).is_empty());
+        assert!(detector.on_pending_transaction(&front_run, start + Duration::from_millis(10)).is_empty());
+        let streamed = detector.on_pending_transaction(&back_run, start + Duration::from_millis(20));
+        assert_eq!(streamed.len(), 1);
+        assert_eq!(streamed[0].mev_type, MevType::Sandwich);
+        assert_eq!(streamed[0].tx_hash, *victim.hash());
+        assert_eq!(detector.on_pending_transaction(&arbitrage, start + Duration::from_millis(30)).len(), 1);
+
+        // Only the sandwich lands; the arbitrage stays pending
+        detector.analyze_block(&[front_run.clone(), victim.clone(), back_run.clone()], 7);
+        let drained = detector.drain_block_candidates(7);
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].mev_type, MevType::Sandwich);
+        assert_eq!(drained[0].block_number, 7);
+        assert_eq!(drained[0].metadata["front_run_tx"], format!("{:?}", front_run.hash()));
+        assert_eq!(detector.pending.txs.len(), 1);
+        assert_eq!(detector.candidates.len(), 1);
+
+        // A candidate only partly included is dropped without being reported
+        let other_victim = test_transaction(4, router, 10 * GWEI, v2_swap_calldata(ETHER, 2_400_000_000, &[weth, usdc]));
+        let other_front =
+            test_transaction_with_nonce(1, 2, router, 20 * GWEI, v2_swap_calldata(10 * ETHER, 25_500_000_000, &[weth, usdc]));
+        let other_back = test_transaction_with_nonce(
+            1,
+            3,
+            router,
+            10 * GWEI,
+            v2_swap_calldata(25_500_000_000, 103 * ETHER / 10, &[usdc, weth]),
+        );
+        for (offset, tx) in [&other_victim, &other_front, &other_back].into_iter().enumerate() {
+            detector.on_pending_transaction(tx, start + Duration::from_secs(1 + offset as u64));
+        }
+        assert_eq!(detector.candidates.len(), 2);
+
+        detector.analyze_block(&[other_victim], 8);
+        assert!(detector.drain_block_candidates(8).is_empty());
+        assert_eq!(detector.candidates.len(), 1);
+    }
 }
@@ -2,12 +2,277 @@
 //!
 //! Provides interface to interact with the MEVDistributor smart contract
 //! for depositing captured MEV and managing epoch distributions.
+//!
+//! A client connected to the contract deposits the buffered MEV as the value
+//! of a `depositMEV` transaction and waits for it to be included, retrying
+//! with exponential backoff. All attempts of a deposit share one nonce, so a
+//! retry replaces an earlier attempt instead of depositing twice. If the
+//! deposit ultimately fails, the amount goes back into the buffer. A client
+//! created without a provider only keeps the accounting, which is what tests
+//! use.
+//!
+//! The accounting can be kept in a [`DistributorLedger`] file, written on every
+//! change, so buffered MEV survives a restart. A deposit interrupted by the
+//! restart is looked up on-chain when the ledger is loaded: it is counted as
+//! deposited if one of its transactions succeeded, and returned to the buffer
+//! otherwise.
+//!
+//! Epochs are read from the contract. The settlement task settles an epoch once
+//! its window has elapsed, if this sequencer is the designated settler. Each
+//! sequencer waits an extra delay derived from its address, so sequencers
+//! sharing a schedule don't all call the contract at the same instant.
+//!
+//! The split of each epoch between stakers, protocol and treasury is read from
+//! the contract and cached until a `SplitUpdated` event of the contract is
+//! seen, see [`MevDistributorClient::apply_logs`].
 
-use alloy_primitives::{Address, U256};
-use std::sync::Arc;
+use alloy::{
+    network::{EthereumWallet, ReceiptResponse},
+    providers::{DynProvider, PendingTransactionError, Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    transports::{http::reqwest::Url, TransportError},
+};
+use alloy::sol_types::SolEvent;
+use alloy_primitives::{keccak256, Address, Log, B256, U256};
+use ande_consensus_bindings::MEVDistributor::{self, MEVDistributorInstance};
+use super::ledger::{DistributorLedger, InFlightDeposit, LedgerError};
+use crate::rpc::health::ConnectionHealth;
+use std::{fmt, path::PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::{Duration, SystemTime};
-use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tokio::{sync::RwLock, task::JoinHandle};
+use tracing::{debug, error, info, warn};
+
+/// Errors returned by the [`MevDistributorClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum MevDistributorError {
+    /// The RPC URL could not be parsed
+    #[error("Invalid RPC URL {url}: {reason}")]
+    InvalidRpcUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// The node could not be queried
+    #[error("MEVDistributor RPC request failed: {0}")]
+    Transport(#[from] TransportError),
+    /// The contract call could not be sent, e.g. because gas estimation reverted
+    #[error("MEVDistributor call failed: {0}")]
+    Contract(#[from] alloy::contract::Error),
+    /// The transaction was sent, but its receipt could not be obtained in time
+    #[error("MEVDistributor transaction not confirmed: {0}")]
+    Confirmation(#[from] PendingTransactionError),
+    /// The transaction was included but reverted
+    #[error("MEVDistributor {call} reverted in transaction {tx_hash}")]
+    Reverted {
+        /// Contract function called
+        call: &'static str,
+        /// Hash of the reverted transaction
+        tx_hash: B256,
+    },
+    /// The ledger file could not be read or written
+    #[error(transparent)]
+    Ledger(#[from] LedgerError),
+    /// The revenue split doesn't add up to the whole amount
+    #[error(
+        "MEV split {stakers_bps}/{protocol_bps}/{treasury_bps} bps does not sum to {} bps",
+        SplitConfig::TOTAL_BPS
+    )]
+    InvalidSplit {
+        /// Share of the stakers, in basis points
+        stakers_bps: u64,
+        /// Share of the protocol, in basis points
+        protocol_bps: u64,
+        /// Share of the treasury, in basis points
+        treasury_bps: u64,
+    },
+}
+
+impl MevDistributorError {
+    /// Whether another attempt of the deposit may succeed
+    fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            Self::InvalidRpcUrl { .. } | Self::Reverted { .. } | Self::Ledger(_) | Self::InvalidSplit { .. }
+        )
+    }
+
+    /// Whether the contract's node could not be reached
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            Self::Transport(err)
+            | Self::Contract(alloy::contract::Error::TransportError(err))
+            | Self::Confirmation(PendingTransactionError::TransportError(err)) => !err.is_error_resp(),
+            _ => false,
+        }
+    }
+}
+
+/// Retry policy of deposits sent to the contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositRetryConfig {
+    /// Attempts made before a deposit is given up
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every further one
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between attempts
+    pub max_backoff: Duration,
+    /// How long an attempt waits for its transaction to be included
+    pub confirmation_timeout: Duration,
+}
+
+impl Default for DepositRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            confirmation_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Split of each epoch's MEV between stakers, protocol and treasury, in basis points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitConfig {
+    /// Share of the stakers
+    pub stakers_bps: u64,
+    /// Share of the protocol
+    pub protocol_bps: u64,
+    /// Share of the treasury
+    pub treasury_bps: u64,
+}
+
+impl SplitConfig {
+    /// Basis points making up the whole amount
+    pub const TOTAL_BPS: u64 = 10_000;
+
+    /// Split a client without a provider assumes: 80% stakers, 15% protocol, 5% treasury
+    pub const DEFAULT: Self = Self { stakers_bps: 8_000, protocol_bps: 1_500, treasury_bps: 500 };
+
+    /// Create a split, failing unless the shares sum to [`Self::TOTAL_BPS`]
+    pub fn new(stakers_bps: u64, protocol_bps: u64, treasury_bps: u64) -> Result<Self, MevDistributorError> {
+        let split = Self { stakers_bps, protocol_bps, treasury_bps };
+        split.validate()?;
+        Ok(split)
+    }
+
+    /// Fail unless the shares sum to [`Self::TOTAL_BPS`]
+    pub fn validate(&self) -> Result<(), MevDistributorError> {
+        let sum = self.stakers_bps.checked_add(self.protocol_bps).and_then(|sum| sum.checked_add(self.treasury_bps));
+        if sum != Some(Self::TOTAL_BPS) {
+            return Err(MevDistributorError::InvalidSplit {
+                stakers_bps: self.stakers_bps,
+                protocol_bps: self.protocol_bps,
+                treasury_bps: self.treasury_bps,
+            });
+        }
+        Ok(())
+    }
+
+    /// Split `total` into `(stakers, protocol, treasury)`
+    ///
+    /// The protocol and treasury shares are rounded down, the stakers get the
+    /// remainder, so the parts always add up to `total`.
+    pub fn compute_split(&self, total: U256) -> (U256, U256, U256) {
+        let share = |bps: u64| total.saturating_mul(U256::from(bps)) / U256::from(Self::TOTAL_BPS);
+        let protocol = share(self.protocol_bps);
+        let treasury = share(self.treasury_bps);
+        (total.saturating_sub(protocol).saturating_sub(treasury), protocol, treasury)
+    }
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Window of an epoch as configured on the contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochSchedule {
+    /// Epoch number
+    pub epoch: u64,
+    /// Unix timestamp the epoch started at, in seconds
+    pub start: u64,
+    /// Epoch duration, in seconds
+    pub duration: u64,
+}
+
+impl EpochSchedule {
+    /// Unix timestamp the epoch can be settled from, in seconds
+    pub fn end(&self) -> u64 {
+        self.start.saturating_add(self.duration)
+    }
+
+    /// Whether the epoch can be settled at `now`
+    pub fn is_due(&self, now: u64) -> bool {
+        now >= self.end()
+    }
+
+    /// Time from `now` until the epoch can be settled, plus `jitter`
+    pub fn settlement_delay(&self, now: u64, jitter: Duration) -> Duration {
+        Duration::from_secs(self.end().saturating_sub(now)) + jitter
+    }
+}
+
+/// Extra delay before `sequencer` attempts to settle `epoch`, below `max_jitter`
+///
+/// Derived from the sequencer address and the epoch, so it's stable across
+/// restarts but differs between sequencers and epochs.
+pub fn settlement_jitter(sequencer: Address, epoch: u64, max_jitter: Duration) -> Duration {
+    let max_millis = max_jitter.as_millis() as u64;
+    if max_millis == 0 {
+        return Duration::ZERO;
+    }
+    let seed = keccak256([sequencer.as_slice(), &epoch.to_be_bytes()].concat());
+    let value = u64::from_be_bytes(seed[..8].try_into().expect("hash has at least 8 bytes"));
+    Duration::from_millis(value % max_millis)
+}
+
+/// Source of the current time used to time epoch settlement
+pub trait EpochClock: fmt::Debug + Send + Sync {
+    /// Current Unix timestamp, in seconds
+    fn now(&self) -> u64;
+}
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl EpochClock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs()
+    }
+}
+
+/// Timing of the epoch settlement task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementTaskConfig {
+    /// Upper bound of the extra delay each sequencer waits after an epoch ends
+    pub max_jitter: Duration,
+    /// How often the schedule is read again while this sequencer isn't the settler, or after an error
+    pub poll_interval: Duration,
+}
+
+impl Default for SettlementTaskConfig {
+    fn default() -> Self {
+        Self { max_jitter: Duration::from_secs(10), poll_interval: Duration::from_secs(30) }
+    }
+}
+
+/// Clears the deposit-in-flight flag when dropped
+struct InFlightGuard<'a>(&'a AtomicBool);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
 
 /// Epoch data from distributor contract
 #[derive(Debug, Clone)]
@@ -16,11 +281,11 @@ pub struct EpochData {
     pub epoch: u64,
     /// Total MEV captured in this epoch
     pub total_mev: U256,
-    /// Stakers reward amount (80%)
+    /// Stakers reward amount
     pub stakers_reward: U256,
-    /// Protocol fee amount (15%)
+    /// Protocol fee amount
     pub protocol_fee: U256,
-    /// Treasury amount (5%)
+    /// Treasury amount
     pub treasury_amount: U256,
     /// Whether epoch is settled
     pub settled: bool,
@@ -34,20 +299,37 @@ pub struct MevDistributorClient {
     contract_address: Address,
     /// Sequencer address
     sequencer_address: Address,
-    /// Accumulated MEV waiting to be deposited
-    mev_buffer: Arc<RwLock<U256>>,
+    /// Distributor contract, signing as the sequencer; `None` only keeps the accounting
+    contract: Option<MEVDistributorInstance<DynProvider>>,
+    /// Retry policy of deposits sent to the contract
+    retry: DepositRetryConfig,
+    /// Buffered MEV and deposit totals
+    ledger: Arc<RwLock<DistributorLedger>>,
+    /// File the ledger is persisted to; `None` keeps it in memory only
+    ledger_path: Option<PathBuf>,
+    /// Whether a deposit is being made; the buffer is only drained by one deposit at a time
+    deposit_in_flight: Arc<AtomicBool>,
     /// Last deposit timestamp
     last_deposit_time: Arc<RwLock<SystemTime>>,
-    /// Current epoch number
+    /// Current epoch number, as last read from or settled on the contract
     current_epoch: Arc<RwLock<u64>>,
+    /// Clock epoch windows are compared against
+    clock: Arc<dyn EpochClock>,
+    /// Revenue split last read from the contract, `None` until read or after it changed
+    split_config: Arc<RwLock<Option<SplitConfig>>>,
     /// Deposit interval
     deposit_interval: Duration,
     /// Maximum MEV buffer before forcing deposit
     max_buffer: U256,
+    /// Calls made to the contract's node, as reported by `ande_health`
+    connection: Arc<ConnectionHealth>,
 }
 
 impl MevDistributorClient {
     /// Create new distributor client
+    ///
+    /// The client only keeps the accounting until it is given a provider with
+    /// [`Self::with_provider`].
     pub fn new(
         contract_address: Address,
         sequencer_address: Address,
@@ -57,11 +339,18 @@ impl MevDistributorClient {
         Self {
             contract_address,
             sequencer_address,
-            mev_buffer: Arc::new(RwLock::new(U256::ZERO)),
+            contract: None,
+            retry: DepositRetryConfig::default(),
+            ledger: Arc::new(RwLock::new(DistributorLedger::default())),
+            ledger_path: None,
+            deposit_in_flight: Arc::new(AtomicBool::new(false)),
             last_deposit_time: Arc::new(RwLock::new(SystemTime::now())),
             current_epoch: Arc::new(RwLock::new(1)),
+            clock: Arc::new(SystemClock),
+            split_config: Arc::new(RwLock::new(None)),
             deposit_interval,
             max_buffer,
+            connection: Arc::new(ConnectionHealth::new()),
         }
     }
     
@@ -74,6 +363,123 @@ impl MevDistributorClient {
             U256::from(1000) * U256::from(10u64.pow(18)), // 1000 ANDE
         )
     }
+
+    /// Send deposits to the contract through `provider`
+    ///
+    /// The provider must sign transactions as the sequencer address.
+    pub fn with_provider(mut self, provider: DynProvider) -> Self {
+        self.contract = Some(MEVDistributor::new(self.contract_address, provider));
+        self
+    }
+
+    /// Connect to the contract at `contract_address` over HTTP with the default configuration, signing with `signer`
+    pub fn connect(
+        rpc_url: &str,
+        contract_address: Address,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, MevDistributorError> {
+        let url = rpc_url.parse::<Url>().map_err(|err| MevDistributorError::InvalidRpcUrl {
+            url: rpc_url.to_string(),
+            reason: err.to_string(),
+        })?;
+        let sequencer_address = signer.address();
+        let provider = ProviderBuilder::new().wallet(EthereumWallet::from(signer)).connect_http(url).erased();
+
+        info!("MEV distributor client connected: contract={}, sequencer={}", contract_address, sequencer_address);
+        Ok(Self::default_config(contract_address, sequencer_address).with_provider(provider))
+    }
+
+    /// Set the retry policy of deposits sent to the contract
+    pub fn with_retry(mut self, retry: DepositRetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Compare epoch windows against `clock` instead of the system time
+    pub fn with_clock(mut self, clock: Arc<dyn EpochClock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Whether deposits are sent to the contract
+    pub fn is_onchain(&self) -> bool {
+        self.contract.is_some()
+    }
+
+    /// Calls made to the contract's node, to report on in `ande_health`
+    pub fn connection_health(&self) -> Arc<ConnectionHealth> {
+        self.connection.clone()
+    }
+
+    /// Record whether the calls that returned `result` reached the contract's node
+    fn track<T>(&self, result: Result<T, impl Into<MevDistributorError>>) -> Result<T, MevDistributorError> {
+        let result = result.map_err(Into::into);
+        self.connection.record(!matches!(&result, Err(err) if err.is_connection_error()));
+        result
+    }
+
+    /// Keep the accounting in the ledger file at `path`, loading it if it exists
+    ///
+    /// A deposit that was in flight when the ledger was last written is
+    /// reconciled with the chain, so the provider must be set first.
+    pub async fn with_ledger_file(mut self, path: impl Into<PathBuf>) -> Result<Self, MevDistributorError> {
+        let path = path.into();
+        let mut ledger = DistributorLedger::load(&path)?;
+
+        if let Some(in_flight) = ledger.in_flight.take() {
+            match self.find_included_deposit(&in_flight).await? {
+                Some(tx_hash) => {
+                    info!("Interrupted MEV deposit was included: amount={}, tx={}", in_flight.amount, tx_hash);
+                    ledger.total_deposited += in_flight.amount;
+                    ledger.deposits_count += 1;
+                    ledger.last_deposit_tx = Some(tx_hash);
+                }
+                None => {
+                    warn!("Interrupted MEV deposit not found on-chain, restored {} to the buffer", in_flight.amount);
+                    ledger.buffer += in_flight.amount;
+                }
+            }
+        } else if let (Some(contract), Some(tx_hash)) = (&self.contract, ledger.last_deposit_tx) {
+            if contract.provider().get_transaction_receipt(tx_hash).await?.is_none() {
+                warn!("Last MEV deposit {} recorded in {} is not on-chain", tx_hash, path.display());
+            }
+        }
+        ledger.save(&path)?;
+
+        info!(
+            "MEV ledger loaded from {}: buffer={}, total_deposited={}, deposits={}",
+            path.display(),
+            ledger.buffer,
+            ledger.total_deposited,
+            ledger.deposits_count
+        );
+        self.ledger = Arc::new(RwLock::new(ledger));
+        self.ledger_path = Some(path);
+        Ok(self)
+    }
+
+    /// Transaction of `deposit` that succeeded on-chain, if any
+    async fn find_included_deposit(&self, deposit: &InFlightDeposit) -> Result<Option<B256>, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(None);
+        };
+        for tx_hash in &deposit.tx_hashes {
+            if let Some(receipt) = contract.provider().get_transaction_receipt(*tx_hash).await? {
+                if receipt.status() {
+                    return Ok(Some(*tx_hash));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Write `ledger` to the ledger file, if there is one
+    fn persist(&self, ledger: &DistributorLedger) -> Result<(), LedgerError> {
+        match &self.ledger_path {
+            Some(path) => ledger.save(path),
+            None => Ok(()),
+        }
+    }
     
     /// Add MEV to buffer
     pub async fn add_mev(&self, amount: U256) {
@@ -81,19 +487,22 @@ impl MevDistributorClient {
             return;
         }
         
-        let mut buffer = self.mev_buffer.write().await;
-        *buffer += amount;
+        let mut ledger = self.ledger.write().await;
+        ledger.buffer += amount;
+        if let Err(err) = self.persist(&ledger) {
+            error!("Failed to persist MEV buffer: {}", err);
+        }
         
-        debug!("MEV added to buffer: amount={}, total_buffer={}", amount, *buffer);
+        debug!("MEV added to buffer: amount={}, total_buffer={}", amount, ledger.buffer);
         
         // Check if we should deposit immediately
-        drop(buffer); // Release lock before checking deposit
+        drop(ledger); // Release lock before checking deposit
         self.check_and_deposit().await;
     }
     
     /// Check if deposit is needed and execute if so
     async fn check_and_deposit(&self) {
-        let buffer = *self.mev_buffer.read().await;
+        let buffer = self.ledger.read().await.buffer;
         let last_deposit = *self.last_deposit_time.read().await;
         let elapsed = SystemTime::now()
             .duration_since(last_deposit)
@@ -110,21 +519,39 @@ impl MevDistributorClient {
     }
     
     /// Deposit accumulated MEV to distributor contract
-    pub async fn deposit_mev(&self) -> Result<(), String> {
+    ///
+    /// Returns the amount deposited. Only one deposit runs at a time: a call
+    /// made while another one is in flight deposits nothing and returns zero.
+    /// If the deposit fails, its amount is restored to the buffer.
+    pub async fn deposit_mev(&self) -> Result<U256, MevDistributorError> {
+        if self
+            .deposit_in_flight
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            debug!("MEV deposit already in flight");
+            return Ok(U256::ZERO);
+        }
+        let _in_flight = InFlightGuard(&self.deposit_in_flight);
+
+        // Take the buffer, MEV added meanwhile goes to the next deposit. The
+        // deposit is recorded before anything is sent, so a restart can't lose it.
         let amount = {
-            let mut buffer = self.mev_buffer.write().await;
-            let amount = *buffer;
-            
+            let mut ledger = self.ledger.write().await;
+            let amount = std::mem::take(&mut ledger.buffer);
             if amount == U256::ZERO {
-                return Ok(());
+                return Ok(U256::ZERO);
+            }
+            ledger.in_flight = Some(InFlightDeposit { amount, tx_hashes: Vec::new() });
+            if let Err(err) = self.persist(&ledger) {
+                ledger.buffer = amount;
+                ledger.in_flight = None;
+                return Err(err.into());
             }
-            
-            // Clear buffer
-            *buffer = U256::ZERO;
             amount
         };
         
-        // Update last deposit time
+        // Update last deposit time, also on failure so a failing contract isn't retried on every addition
         {
             let mut last_deposit = self.last_deposit_time.write().await;
             *last_deposit = SystemTime::now();
@@ -135,83 +562,291 @@ impl MevDistributorClient {
             amount, self.contract_address
         );
         
-        // In production, this would call the smart contract
-        // self.call_contract_deposit_mev(amount).await?;
+        let outcome = match &self.contract {
+            Some(contract) => self.send_deposit(contract, amount).await.map(Some),
+            None => Ok(None),
+        };
+
+        let mut ledger = self.ledger.write().await;
+        ledger.in_flight = None;
+        let result = match outcome {
+            Ok(tx_hash) => {
+                ledger.total_deposited += amount;
+                ledger.deposits_count += 1;
+                ledger.last_deposit_tx = tx_hash.or(ledger.last_deposit_tx);
+                Ok(amount)
+            }
+            Err(err) => {
+                ledger.buffer += amount;
+                error!("MEV deposit failed, restored {} to the buffer: {}", amount, err);
+                Err(err)
+            }
+        };
+        if let Err(err) = self.persist(&ledger) {
+            error!("Failed to persist MEV ledger after deposit: {}", err);
+        }
         
-        Ok(())
+        result
+    }
+
+    /// Send `depositMEV` carrying `amount`, retrying failed attempts with exponential backoff
+    async fn send_deposit(
+        &self,
+        contract: &MEVDistributorInstance<DynProvider>,
+        amount: U256,
+    ) -> Result<B256, MevDistributorError> {
+        let mut nonce = None;
+        let mut sent = Vec::new();
+        let mut backoff = self.retry.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match self.track(self.try_deposit(contract, amount, &mut nonce, &mut sent).await) {
+                Ok(tx_hash) => {
+                    info!("MEV deposit confirmed: amount={}, tx={}", amount, tx_hash);
+                    return Ok(tx_hash);
+                }
+                Err(err) if err.is_retryable() && attempt < self.retry.max_attempts => {
+                    warn!(
+                        "MEV deposit attempt {}/{} failed, retrying in {:?}: {}",
+                        attempt, self.retry.max_attempts, backoff, err
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2).min(self.retry.max_backoff);
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Make one attempt of a deposit
+    ///
+    /// `nonce` is fetched on the first attempt that gets that far and reused by
+    /// later ones. `sent` collects the transactions of earlier attempts, which
+    /// are checked first in case one was included after its confirmation timed out.
+    async fn try_deposit(
+        &self,
+        contract: &MEVDistributorInstance<DynProvider>,
+        amount: U256,
+        nonce: &mut Option<u64>,
+        sent: &mut Vec<B256>,
+    ) -> Result<B256, MevDistributorError> {
+        let provider = contract.provider();
+        for tx_hash in sent.iter() {
+            if let Some(receipt) = provider.get_transaction_receipt(*tx_hash).await? {
+                return outcome("depositMEV", receipt);
+            }
+        }
+
+        let nonce = match *nonce {
+            Some(nonce) => nonce,
+            None => *nonce.insert(provider.get_transaction_count(self.sequencer_address).pending().await?),
+        };
+        let pending = contract.depositMEV().value(amount).nonce(nonce).send().await?;
+        sent.push(*pending.tx_hash());
+        self.record_sent(*pending.tx_hash()).await;
+        debug!("MEV deposit sent: amount={}, nonce={}, tx={}", amount, nonce, pending.tx_hash());
+
+        let receipt = pending.with_timeout(Some(self.retry.confirmation_timeout)).get_receipt().await?;
+        outcome("depositMEV", receipt)
     }
     
-    /// Force deposit regardless of buffer state
-    pub async fn force_deposit(&self) -> Result<U256, String> {
-        let amount = *self.mev_buffer.read().await;
-        
-        if amount > U256::ZERO {
-            self.deposit_mev().await?;
-            Ok(amount)
-        } else {
-            Ok(U256::ZERO)
+    /// Record a transaction sent for the deposit in flight, so it's looked up after a restart
+    async fn record_sent(&self, tx_hash: B256) {
+        let mut ledger = self.ledger.write().await;
+        if let Some(in_flight) = ledger.in_flight.as_mut() {
+            in_flight.tx_hashes.push(tx_hash);
+        }
+        if let Err(err) = self.persist(&ledger) {
+            error!("Failed to persist MEV deposit transaction {}: {}", tx_hash, err);
         }
     }
+
+    /// Force deposit regardless of buffer state
+    pub async fn force_deposit(&self) -> Result<U256, MevDistributorError> {
+        self.deposit_mev().await
+    }
     
     /// Get current buffer amount
     pub async fn get_buffer_amount(&self) -> U256 {
-        *self.mev_buffer.read().await
+        self.ledger.read().await.buffer
     }
     
     /// Get current epoch data
-    pub async fn get_current_epoch(&self) -> EpochData {
-        let epoch = *self.current_epoch.read().await;
-        
-        // In production, this would query the smart contract
-        // For now, return mock data
-        EpochData {
-            epoch,
-            total_mev: U256::ZERO,
-            stakers_reward: U256::ZERO,
-            protocol_fee: U256::ZERO,
-            treasury_amount: U256::ZERO,
-            settled: false,
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        }
+    ///
+    /// A client without a provider reports its local epoch counter with zeroed amounts.
+    pub async fn get_current_epoch(&self) -> Result<EpochData, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            let epoch = *self.current_epoch.read().await;
+            return Ok(EpochData {
+                epoch,
+                total_mev: U256::ZERO,
+                stakers_reward: U256::ZERO,
+                protocol_fee: U256::ZERO,
+                treasury_amount: U256::ZERO,
+                settled: false,
+                timestamp: self.clock.now(),
+            });
+        };
+
+        let epoch = contract.currentEpoch().call().await?.saturating_to::<u64>();
+        *self.current_epoch.write().await = epoch;
+        self.get_epoch_info(epoch).await
     }
     
     /// Get epoch info for specific epoch number
-    pub async fn get_epoch_info(&self, epoch: u64) -> Result<EpochData, String> {
-        // In production, this would query the smart contract
-        // self.call_contract_get_epoch_data(epoch).await
-        
+    pub async fn get_epoch_info(&self, epoch: u64) -> Result<EpochData, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(EpochData {
+                epoch,
+                total_mev: U256::ZERO,
+                stakers_reward: U256::ZERO,
+                protocol_fee: U256::ZERO,
+                treasury_amount: U256::ZERO,
+                settled: false,
+                timestamp: 0,
+            });
+        };
+
+        let data = contract.getEpochData(U256::from(epoch)).call().await?;
+        // The contract only records the split once the epoch is settled
+        let (stakers_reward, protocol_fee, treasury_amount) = if data.settled {
+            (data.stakersReward, data.protocolFee, data.treasuryAmount)
+        } else {
+            self.compute_split(data.totalMEV).await?
+        };
         Ok(EpochData {
             epoch,
-            total_mev: U256::ZERO,
-            stakers_reward: U256::ZERO,
-            protocol_fee: U256::ZERO,
-            treasury_amount: U256::ZERO,
-            settled: false,
-            timestamp: 0,
+            total_mev: data.totalMEV,
+            stakers_reward,
+            protocol_fee,
+            treasury_amount,
+            settled: data.settled,
+            timestamp: data.timestamp.saturating_to(),
         })
     }
-    
-    /// Check if epoch settlement is needed
-    pub async fn check_epoch_settlement(&self) -> Result<bool, String> {
-        let _current_epoch_data = self.get_current_epoch().await;
-        
-        // In production, check if epoch duration has passed
-        // and call settleEpoch() on the contract if needed
-        
-        Ok(false)
+
+    /// Revenue split configured on the contract
+    ///
+    /// Read once and cached until [`Self::apply_logs`] sees the split change. A
+    /// client without a provider uses [`SplitConfig::DEFAULT`].
+    pub async fn get_split_config(&self) -> Result<SplitConfig, MevDistributorError> {
+        if let Some(split) = *self.split_config.read().await {
+            return Ok(split);
+        }
+        let Some(contract) = &self.contract else {
+            return Ok(SplitConfig::DEFAULT);
+        };
+
+        let split = SplitConfig::new(
+            contract.stakersBps().call().await?.saturating_to(),
+            contract.protocolBps().call().await?.saturating_to(),
+            contract.treasuryBps().call().await?.saturating_to(),
+        )?;
+        debug!(
+            "MEV split read from distributor: stakers={}bps, protocol={}bps, treasury={}bps",
+            split.stakers_bps, split.protocol_bps, split.treasury_bps
+        );
+        *self.split_config.write().await = Some(split);
+        Ok(split)
+    }
+
+    /// Split `total` into `(stakers, protocol, treasury)` with the contract's split
+    pub async fn compute_split(&self, total: U256) -> Result<(U256, U256, U256), MevDistributorError> {
+        Ok(self.get_split_config().await?.compute_split(total))
+    }
+
+    /// Drop the cached split, so the next read queries the contract
+    pub async fn invalidate_split_config(&self) {
+        *self.split_config.write().await = None;
+    }
+
+    /// Follow `SplitUpdated` events of the contract among `logs`, e.g. those of an executed block
+    ///
+    /// The cache takes the split carried by the last event. An event with a
+    /// split not summing to 10 000 bps only invalidates the cache.
+    pub async fn apply_logs<'a>(&self, logs: impl IntoIterator<Item = &'a Log>) {
+        for log in logs {
+            if log.address != self.contract_address
+                || log.topics().first() != Some(&MEVDistributor::SplitUpdated::SIGNATURE_HASH)
+            {
+                continue;
+            }
+            let event = match MEVDistributor::SplitUpdated::decode_log(log) {
+                Ok(event) => event,
+                Err(err) => {
+                    warn!("Undecodable SplitUpdated event from {}: {}", log.address, err);
+                    self.invalidate_split_config().await;
+                    continue;
+                }
+            };
+            let split = SplitConfig::new(
+                event.stakersBps.saturating_to(),
+                event.protocolBps.saturating_to(),
+                event.treasuryBps.saturating_to(),
+            );
+            match split {
+                Ok(split) => {
+                    info!(
+                        "MEV split updated: stakers={}bps, protocol={}bps, treasury={}bps",
+                        split.stakers_bps, split.protocol_bps, split.treasury_bps
+                    );
+                    *self.split_config.write().await = Some(split);
+                }
+                Err(err) => {
+                    warn!("Ignoring SplitUpdated event: {}", err);
+                    self.invalidate_split_config().await;
+                }
+            }
+        }
+    }
+
+    /// Window of the current epoch, or `None` for a client without a provider
+    pub async fn get_epoch_schedule(&self) -> Result<Option<EpochSchedule>, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(None);
+        };
+
+        let epoch = self.track(contract.currentEpoch().call().await)?.saturating_to();
+        let start = self.track(contract.epochStartTime().call().await)?.saturating_to();
+        let duration = self.track(contract.epochDuration().call().await)?.saturating_to();
+        *self.current_epoch.write().await = epoch;
+        Ok(Some(EpochSchedule { epoch, start, duration }))
+    }
+
+    /// Whether the current epoch's window has elapsed and this sequencer is its designated settler
+    pub async fn check_epoch_settlement(&self) -> Result<bool, MevDistributorError> {
+        let Some(schedule) = self.get_epoch_schedule().await? else {
+            return Ok(false);
+        };
+        if !schedule.is_due(self.clock.now()) {
+            return Ok(false);
+        }
+        self.is_designated_settler().await
+    }
+
+    /// Whether the contract designates this sequencer to settle epochs
+    async fn is_designated_settler(&self) -> Result<bool, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(false);
+        };
+        Ok(contract.designatedSettler().call().await? == self.sequencer_address)
     }
     
     /// Settle current epoch (admin function)
-    pub async fn settle_epoch(&self) -> Result<(), String> {
+    pub async fn settle_epoch(&self) -> Result<(), MevDistributorError> {
         let current = *self.current_epoch.read().await;
         
         info!("Settling epoch {}", current);
         
-        // In production, this would call the smart contract
-        // self.call_contract_settle_epoch().await?;
+        if let Some(contract) = &self.contract {
+            let receipt = contract.settleEpoch().send().await?.get_receipt().await?;
+            let tx_hash = outcome("settleEpoch", receipt)?;
+            let epoch = contract.currentEpoch().call().await?.saturating_to();
+            *self.current_epoch.write().await = epoch;
+            info!("Epoch {} settled: tx={}, current_epoch={}", current, tx_hash, epoch);
+            return Ok(());
+        }
         
         // Increment epoch
         let mut epoch = self.current_epoch.write().await;
@@ -219,10 +854,52 @@ impl MevDistributorClient {
         
         Ok(())
     }
+
+    /// Start background task settling epochs as their windows elapse
+    ///
+    /// Each epoch is settled after its window plus this sequencer's jitter, and
+    /// only if this sequencer is the designated settler by then.
+    pub fn start_epoch_settlement_task(self: Arc<Self>, config: SettlementTaskConfig) -> JoinHandle<()> {
+        info!("Starting background epoch settlement task");
+        tokio::spawn(async move {
+            loop {
+                let delay = match self.get_epoch_schedule().await {
+                    Ok(Some(schedule)) => {
+                        let jitter = settlement_jitter(self.sequencer_address, schedule.epoch, config.max_jitter);
+                        schedule.settlement_delay(self.clock.now(), jitter)
+                    }
+                    Ok(None) => {
+                        debug!("Epoch settlement task stopped: no distributor contract");
+                        return;
+                    }
+                    Err(e) => {
+                        error!("Failed to read epoch schedule: {}", e);
+                        config.poll_interval
+                    }
+                };
+                tokio::time::sleep(delay).await;
+
+                match self.check_epoch_settlement().await {
+                    Ok(true) => {
+                        if let Err(e) = self.settle_epoch().await {
+                            error!("Failed to settle epoch: {}", e);
+                            tokio::time::sleep(config.poll_interval).await;
+                        }
+                    }
+                    // Another sequencer settles, or the epoch was already settled
+                    Ok(false) => tokio::time::sleep(config.poll_interval).await,
+                    Err(e) => {
+                        error!("Failed to check epoch settlement: {}", e);
+                        tokio::time::sleep(config.poll_interval).await;
+                    }
+                }
+            }
+        })
+    }
     
     /// Get distributor statistics
     pub async fn get_distributor_stats(&self) -> DistributorStats {
-        let buffer = *self.mev_buffer.read().await;
+        let ledger = self.ledger.read().await.clone();
         let epoch = *self.current_epoch.read().await;
         let last_deposit = *self.last_deposit_time.read().await;
         let time_since_deposit = SystemTime::now()
@@ -231,10 +908,10 @@ impl MevDistributorClient {
         
         DistributorStats {
             current_epoch: epoch,
-            buffer_amount: buffer,
+            buffer_amount: ledger.buffer,
             time_since_last_deposit: time_since_deposit,
-            total_deposited: U256::ZERO, // Would track this in production
-            deposits_count: 0,             // Would track this in production
+            total_deposited: ledger.total_deposited,
+            deposits_count: ledger.deposits_count,
         }
     }
     
@@ -250,12 +927,20 @@ impl MevDistributorClient {
     
     /// Check if deposit is pending
     pub async fn is_deposit_pending(&self) -> bool {
-        let buffer = *self.mev_buffer.read().await;
+        let buffer = self.ledger.read().await.buffer;
         buffer >= self.max_buffer
             || self.time_until_next_deposit().await == Duration::ZERO
     }
 }
 
+/// Transaction hash of a contract call, failing if it reverted
+fn outcome(call: &'static str, receipt: impl ReceiptResponse) -> Result<B256, MevDistributorError> {
+    if !receipt.status() {
+        return Err(MevDistributorError::Reverted { call, tx_hash: receipt.transaction_hash() });
+    }
+    Ok(receipt.transaction_hash())
+}
+
 /// Distributor statistics
 #[derive(Debug, Clone)]
 pub struct DistributorStats {
@@ -344,17 +1029,249 @@ mod tests {
         assert_eq!(buffer, U256::ZERO);
     }
 
+    /// Client whose deposits fail to reach a node, retrying without delay
+    fn unreachable_client() -> MevDistributorClient {
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(PrivateKeySigner::random()))
+            .connect_http("http://127.0.0.1:1".parse().unwrap())
+            .erased();
+        MevDistributorClient::default_config(Address::random(), Address::random())
+            .with_provider(provider)
+            .with_retry(DepositRetryConfig {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(2),
+                confirmation_timeout: Duration::from_secs(1),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_failed_deposit_restores_buffer() {
+        let client = unreachable_client();
+        assert!(client.is_onchain());
+        client.add_mev(U256::from(1000)).await;
+
+        let err = client.deposit_mev().await.unwrap_err();
+        assert!(matches!(err, MevDistributorError::Transport(_)), "unexpected error: {err}");
+        assert_eq!(client.get_buffer_amount().await, U256::from(1000));
+
+        // MEV added after the failure is deposited together with the restored amount
+        client.add_mev(U256::from(500)).await;
+        assert_eq!(client.get_buffer_amount().await, U256::from(1500));
+
+        let stats = client.get_distributor_stats().await;
+        assert_eq!(stats.deposits_count, 0);
+        assert_eq!(stats.total_deposited, U256::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_deposits_drain_buffer_once() {
+        let client = unreachable_client();
+        client.add_mev(U256::from(1000)).await;
+
+        let (first, second) = tokio::join!(client.deposit_mev(), client.deposit_mev());
+        assert!(first.is_err());
+        assert_eq!(second.unwrap(), U256::ZERO, "a deposit in flight must not be doubled");
+
+        // The failed deposit restores its amount exactly once
+        assert_eq!(client.get_buffer_amount().await, U256::from(1000));
+    }
+
+    #[tokio::test]
+    async fn test_deposits_counted_in_stats() {
+        let client = MevDistributorClient::default_config(Address::random(), Address::random());
+        assert!(!client.is_onchain());
+
+        client.add_mev(U256::from(300)).await;
+        assert_eq!(client.deposit_mev().await.unwrap(), U256::from(300));
+        client.add_mev(U256::from(100)).await;
+        assert_eq!(client.force_deposit().await.unwrap(), U256::from(100));
+        assert_eq!(client.deposit_mev().await.unwrap(), U256::ZERO);
+
+        let stats = client.get_distributor_stats().await;
+        assert_eq!(stats.deposits_count, 2);
+        assert_eq!(stats.total_deposited, U256::from(400));
+        assert_eq!(stats.avg_deposit_amount(), U256::from(200));
+    }
+
+    #[tokio::test]
+    async fn test_buffer_recovered_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mev-ledger.json");
+        let (contract, sequencer) = (Address::random(), Address::random());
+
+        let client = MevDistributorClient::default_config(contract, sequencer).with_ledger_file(&path).await.unwrap();
+        client.add_mev(U256::from(400)).await;
+        client.deposit_mev().await.unwrap();
+        client.add_mev(U256::from(250)).await;
+        client.add_mev(U256::from(50)).await;
+        drop(client);
+
+        let restarted =
+            MevDistributorClient::default_config(contract, sequencer).with_ledger_file(&path).await.unwrap();
+        assert_eq!(restarted.get_buffer_amount().await, U256::from(300));
+        let stats = restarted.get_distributor_stats().await;
+        assert_eq!(stats.total_deposited, U256::from(400));
+        assert_eq!(stats.deposits_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_deposit_restored_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mev-ledger.json");
+
+        // The node stopped after taking the buffer, before the deposit was confirmed
+        DistributorLedger {
+            buffer: U256::from(20),
+            in_flight: Some(InFlightDeposit { amount: U256::from(700), tx_hashes: Vec::new() }),
+            ..Default::default()
+        }
+        .save(&path)
+        .unwrap();
+
+        let client = MevDistributorClient::default_config(Address::random(), Address::random())
+            .with_ledger_file(&path)
+            .await
+            .unwrap();
+        assert_eq!(client.get_buffer_amount().await, U256::from(720));
+        assert_eq!(client.get_distributor_stats().await.deposits_count, 0);
+        assert_eq!(DistributorLedger::load(&path).unwrap().in_flight, None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_deposit_persists_restored_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mev-ledger.json");
+
+        let client = unreachable_client().with_ledger_file(&path).await.unwrap();
+        client.add_mev(U256::from(1000)).await;
+        client.deposit_mev().await.unwrap_err();
+
+        let ledger = DistributorLedger::load(&path).unwrap();
+        assert_eq!(ledger.buffer, U256::from(1000));
+        assert_eq!(ledger.in_flight, None);
+    }
+
+    #[test]
+    fn test_split_remainder_goes_to_stakers() {
+        let split = SplitConfig::DEFAULT;
+        assert_eq!(split.compute_split(U256::from(10_000)), (U256::from(8_000), U256::from(1_500), U256::from(500)));
+
+        // 15% of 999 is 149.85 and 5% is 49.95, both rounded down
+        let (stakers, protocol, treasury) = split.compute_split(U256::from(999));
+        assert_eq!((protocol, treasury), (U256::from(149), U256::from(49)));
+        assert_eq!(stakers, U256::from(801));
+        assert_eq!(stakers + protocol + treasury, U256::from(999));
+
+        let split = SplitConfig::new(3_334, 3_333, 3_333).unwrap();
+        assert_eq!(split.compute_split(U256::from(1)), (U256::from(1), U256::ZERO, U256::ZERO));
+        assert_eq!(split.compute_split(U256::ZERO), (U256::ZERO, U256::ZERO, U256::ZERO));
+    }
+
+    #[test]
+    fn test_split_must_sum_to_total() {
+        let err = SplitConfig::new(8_000, 1_500, 400).unwrap_err();
+        assert!(
+            matches!(err, MevDistributorError::InvalidSplit { stakers_bps: 8_000, protocol_bps: 1_500, treasury_bps: 400 }),
+            "unexpected error: {err}"
+        );
+        assert!(SplitConfig::new(u64::MAX, 1, 0).is_err());
+        assert!(SplitConfig::new(10_000, 0, 0).is_ok());
+    }
+
+    fn split_updated(contract: Address, split: (u64, u64, u64)) -> Log {
+        let event = MEVDistributor::SplitUpdated {
+            stakersBps: U256::from(split.0),
+            protocolBps: U256::from(split.1),
+            treasuryBps: U256::from(split.2),
+        };
+        Log { address: contract, data: event.encode_log_data() }
+    }
+
+    #[tokio::test]
+    async fn test_split_updated_event_replaces_cached_split() {
+        let contract = Address::random();
+        let client = MevDistributorClient::default_config(contract, Address::random());
+        assert_eq!(client.get_split_config().await.unwrap(), SplitConfig::DEFAULT);
+
+        // Events of other contracts are ignored
+        client.apply_logs(&[split_updated(Address::random(), (5_000, 5_000, 0))]).await;
+        assert_eq!(client.get_split_config().await.unwrap(), SplitConfig::DEFAULT);
+
+        client.apply_logs(&[split_updated(contract, (7_000, 2_000, 1_000))]).await;
+        assert_eq!(client.get_split_config().await.unwrap(), SplitConfig::new(7_000, 2_000, 1_000).unwrap());
+        let split = client.compute_split(U256::from(1_000)).await.unwrap();
+        assert_eq!(split, (U256::from(700), U256::from(200), U256::from(100)));
+
+        // A split not summing to the total only drops the cache
+        client.apply_logs(&[split_updated(contract, (9_000, 2_000, 1_000))]).await;
+        assert_eq!(client.get_split_config().await.unwrap(), SplitConfig::DEFAULT);
+    }
+
     #[tokio::test]
     async fn test_epoch_data() {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
         
-        let epoch_data = client.get_current_epoch().await;
+        let epoch_data = client.get_current_epoch().await.unwrap();
         assert_eq!(epoch_data.epoch, 1);
         assert_eq!(epoch_data.total_mev, U256::ZERO);
     }
 
+    /// Clock stopped at a fixed time
+    #[derive(Debug)]
+    struct FixedClock(u64);
+
+    impl EpochClock for FixedClock {
+        fn now(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_epoch_schedule_timing() {
+        let schedule = EpochSchedule { epoch: 4, start: 1_000, duration: 600 };
+        assert_eq!(schedule.end(), 1_600);
+        assert!(!schedule.is_due(1_599));
+        assert!(schedule.is_due(1_600));
+
+        let jitter = Duration::from_millis(2_500);
+        assert_eq!(schedule.settlement_delay(1_000, jitter), Duration::from_secs(600) + jitter);
+        // Once the window has elapsed only the jitter is left to wait
+        assert_eq!(schedule.settlement_delay(2_000, jitter), jitter);
+    }
+
+    #[test]
+    fn test_settlement_jitter_spreads_sequencers() {
+        let max_jitter = Duration::from_secs(10);
+        let sequencers: Vec<Address> = (1..=8u8).map(Address::with_last_byte).collect();
+        let jitters: Vec<Duration> =
+            sequencers.iter().map(|sequencer| settlement_jitter(*sequencer, 7, max_jitter)).collect();
+
+        assert!(jitters.iter().all(|jitter| *jitter < max_jitter));
+        assert!(jitters.iter().any(|jitter| *jitter != jitters[0]), "all sequencers got the same jitter");
+        // Stable for a sequencer and epoch, but varies between epochs
+        assert_eq!(settlement_jitter(sequencers[0], 7, max_jitter), jitters[0]);
+        assert!((8..16).any(|epoch| settlement_jitter(sequencers[0], epoch, max_jitter) != jitters[0]));
+        assert_eq!(settlement_jitter(sequencers[0], 7, Duration::ZERO), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_offchain_epochs_follow_local_counter() {
+        let client = MevDistributorClient::default_config(Address::random(), Address::random())
+            .with_clock(Arc::new(FixedClock(1_234)));
+
+        let epoch = client.get_current_epoch().await.unwrap();
+        assert_eq!((epoch.epoch, epoch.timestamp), (1, 1_234));
+        assert_eq!(client.get_epoch_schedule().await.unwrap(), None);
+        assert!(!client.check_epoch_settlement().await.unwrap());
+
+        client.settle_epoch().await.unwrap();
+        assert_eq!(client.get_current_epoch().await.unwrap().epoch, 2);
+        assert_eq!(client.get_distributor_stats().await.current_epoch, 2);
+    }
+
     #[tokio::test]
     async fn test_distributor_stats() {
         let contract = Address::random();
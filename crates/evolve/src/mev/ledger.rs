@@ -0,0 +1,98 @@
+//! MEV Distributor Ledger
+//!
+//! Accounting of the [`MevDistributorClient`](super::MevDistributorClient),
+//! kept in a JSON file so MEV captured but not yet deposited survives a
+//! restart. The file is replaced atomically: the ledger is written to a
+//! temporary file next to it, which is then renamed over the previous version.
+
+use alloy_primitives::{B256, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Errors returned when reading or writing a ledger file
+#[derive(Debug, thiserror::Error)]
+pub enum LedgerError {
+    /// The file could not be read or written
+    #[error("MEV ledger I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// The file content is not a valid ledger
+    #[error("MEV ledger codec error: {0}")]
+    Codec(#[from] serde_json::Error),
+}
+
+/// Deposit whose transaction was possibly sent but not confirmed yet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InFlightDeposit {
+    /// Amount taken from the buffer
+    pub amount: U256,
+    /// Transactions sent for the deposit, one per attempt
+    pub tx_hashes: Vec<B256>,
+}
+
+/// Accounting of captured and deposited MEV
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistributorLedger {
+    /// MEV waiting to be deposited
+    pub buffer: U256,
+    /// Total amount deposited
+    pub total_deposited: U256,
+    /// Number of deposits made
+    pub deposits_count: u64,
+    /// Transaction of the last confirmed on-chain deposit
+    pub last_deposit_tx: Option<B256>,
+    /// Deposit that was being made when the ledger was last written
+    pub in_flight: Option<InFlightDeposit>,
+}
+
+impl DistributorLedger {
+    /// Read the ledger at `path`, or an empty one if the file doesn't exist
+    pub fn load(path: &Path) -> Result<Self, LedgerError> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Atomically replace the ledger at `path` with this one
+    pub fn save(&self, path: &Path) -> Result<(), LedgerError> {
+        let tmp = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mev-ledger.json");
+        assert_eq!(DistributorLedger::load(&path).unwrap(), DistributorLedger::default());
+
+        let ledger = DistributorLedger {
+            buffer: U256::from(700),
+            total_deposited: U256::from(300),
+            deposits_count: 2,
+            last_deposit_tx: Some(B256::with_last_byte(1)),
+            in_flight: Some(InFlightDeposit { amount: U256::from(50), tx_hashes: vec![B256::with_last_byte(2)] }),
+        };
+        ledger.save(&path).unwrap();
+        assert_eq!(DistributorLedger::load(&path).unwrap(), ledger);
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::write(&path, b"not json").unwrap();
+        assert!(matches!(DistributorLedger::load(&path), Err(LedgerError::Codec(_))));
+    }
+}
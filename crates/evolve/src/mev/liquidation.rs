@@ -0,0 +1,196 @@
+//! Liquidation Oracle
+//!
+//! Liquidations rarely carry value: the liquidator repays the debt with tokens
+//! it already holds. The detector asks a [`LiquidationOracle`] whether a call
+//! liquidates a position, and if so whose and against which assets.
+//!
+//! [`DefaultLiquidationOracle`] decodes the liquidation entry points of Aave v3
+//! pools, Compound v2 markets and Compound v3 (Comet) markets from calldata.
+//! Oracles with access to state, e.g. health factors read from the lending
+//! protocol, can be plugged in through [`DetectorConfig`](super::detector::DetectorConfig).
+
+use super::calldata::{self, Args, Selector};
+use alloy_primitives::{Address, U256};
+use std::{collections::HashSet, fmt};
+
+/// Lending protocol a liquidation goes through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiquidationProtocol {
+    /// Aave v3 pool `liquidationCall`
+    AaveV3,
+    /// Compound v2 market `liquidateBorrow`
+    CompoundV2,
+    /// Compound v3 Comet `absorb`
+    CompoundV3,
+}
+
+impl LiquidationProtocol {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiquidationProtocol::AaveV3 => "Aave v3",
+            LiquidationProtocol::CompoundV2 => "Compound v2",
+            LiquidationProtocol::CompoundV3 => "Compound v3",
+        }
+    }
+}
+
+/// Liquidation recognized by a [`LiquidationOracle`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationInfo {
+    /// Protocol the liquidation goes through
+    pub protocol: LiquidationProtocol,
+    /// Account whose position is liquidated
+    pub borrower: Address,
+    /// Debt repaid by the liquidator, in the smallest unit of `debt_asset`
+    pub repay_amount: Option<U256>,
+    /// Asset the debt is repaid in, or the market holding it
+    pub debt_asset: Option<Address>,
+    /// Collateral seized, or the market holding it
+    pub collateral_asset: Option<Address>,
+}
+
+/// Tells liquidation calls apart from other calls
+pub trait LiquidationOracle: fmt::Debug + Send + Sync {
+    /// Liquidation performed by calling `to` with `calldata`, if any
+    fn is_liquidation_call(&self, to: Address, calldata: &[u8]) -> Option<LiquidationInfo>;
+}
+
+/// Oracle decoding Aave v3 and Compound liquidation calls
+///
+/// Compound v2 markets are called on the market of the debt, which is reported
+/// as the debt asset, and name the market of the collateral. Comet's `absorb`
+/// takes over the positions without a repayment, so only the borrower is
+/// known; when several accounts are absorbed at once the first is reported.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultLiquidationOracle {
+    /// Contracts liquidations are accepted on, any contract if empty
+    protocols: HashSet<Address>,
+}
+
+impl DefaultLiquidationOracle {
+    /// Oracle accepting liquidations on any contract
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Oracle only accepting liquidations on `protocols`
+    pub fn with_protocols(protocols: impl IntoIterator<Item = Address>) -> Self {
+        Self { protocols: protocols.into_iter().collect() }
+    }
+}
+
+impl LiquidationOracle for DefaultLiquidationOracle {
+    fn is_liquidation_call(&self, to: Address, calldata: &[u8]) -> Option<LiquidationInfo> {
+        if !self.protocols.is_empty() && !self.protocols.contains(&to) {
+            return None;
+        }
+
+        let selector: Selector = calldata.get(..4)?.try_into().ok()?;
+        let args = Args(&calldata[4..]);
+        match selector {
+            calldata::LIQUIDATION_CALL => Some(LiquidationInfo {
+                protocol: LiquidationProtocol::AaveV3,
+                borrower: args.address(2)?,
+                repay_amount: Some(args.word(3)?),
+                debt_asset: Some(args.address(1)?),
+                collateral_asset: Some(args.address(0)?),
+            }),
+            calldata::LIQUIDATE_BORROW => Some(LiquidationInfo {
+                protocol: LiquidationProtocol::CompoundV2,
+                borrower: args.address(0)?,
+                repay_amount: Some(args.word(1)?),
+                debt_asset: Some(to),
+                collateral_asset: Some(args.address(2)?),
+            }),
+            calldata::ABSORB => Some(LiquidationInfo {
+                protocol: LiquidationProtocol::CompoundV3,
+                borrower: *args.address_array(1)?.first()?,
+                repay_amount: None,
+                debt_asset: None,
+                collateral_asset: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+
+    const WETH: Address = address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+    const USDC: Address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    const CUSDC: Address = address!("39aa39c021dfbae8fac545936693ac917d5e7563");
+    const CETH: Address = address!("4ddc2d193948926d02f9b1fe9e1daa0718270ed5");
+    const COMET: Address = address!("c3d688b66703497daa19211eedff47f25384cdc3");
+
+    /// `liquidationCall(WETH, USDC, 0x..02, 1000e6, false)`
+    const AAVE_LIQUIDATION_CALLDATA: &str = "00a718a9\
+        000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2\
+        000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        000000000000000000000000000000000000000000000000000000003b9aca00\
+        0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// `liquidateBorrow(0x..02, 1000e6, cETH)`, sent to cUSDC
+    const COMPOUND_LIQUIDATE_BORROW_CALLDATA: &str = "f5e3c462\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        000000000000000000000000000000000000000000000000000000003b9aca00\
+        0000000000000000000000004ddc2d193948926d02f9b1fe9e1daa0718270ed5";
+
+    /// `absorb(0x..01, [0x..02, 0x..03])`
+    const COMET_ABSORB_CALLDATA: &str = "c3cecfd2\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        0000000000000000000000000000000000000000000000000000000000000040\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        0000000000000000000000000000000000000000000000000000000000000002\
+        0000000000000000000000000000000000000000000000000000000000000003";
+
+    fn liquidation(oracle: &DefaultLiquidationOracle, to: Address, calldata: &str) -> Option<LiquidationInfo> {
+        oracle.is_liquidation_call(to, &hex::decode(calldata).unwrap())
+    }
+
+    #[test]
+    fn test_decode_aave_and_compound_liquidations() {
+        let oracle = DefaultLiquidationOracle::new();
+        let borrower = Address::with_last_byte(2);
+
+        let aave = liquidation(&oracle, Address::random(), AAVE_LIQUIDATION_CALLDATA).unwrap();
+        assert_eq!(aave.protocol, LiquidationProtocol::AaveV3);
+        assert_eq!(aave.borrower, borrower);
+        assert_eq!(aave.repay_amount, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(aave.debt_asset, Some(USDC));
+        assert_eq!(aave.collateral_asset, Some(WETH));
+
+        let compound = liquidation(&oracle, CUSDC, COMPOUND_LIQUIDATE_BORROW_CALLDATA).unwrap();
+        assert_eq!(compound.protocol, LiquidationProtocol::CompoundV2);
+        assert_eq!(compound.borrower, borrower);
+        assert_eq!(compound.repay_amount, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(compound.debt_asset, Some(CUSDC));
+        assert_eq!(compound.collateral_asset, Some(CETH));
+
+        let comet = liquidation(&oracle, COMET, COMET_ABSORB_CALLDATA).unwrap();
+        assert_eq!(comet.protocol, LiquidationProtocol::CompoundV3);
+        assert_eq!(comet.borrower, borrower);
+        assert_eq!(comet.repay_amount, None);
+    }
+
+    #[test]
+    fn test_other_and_malformed_calls_are_not_liquidations() {
+        let oracle = DefaultLiquidationOracle::new();
+
+        // ERC-20 `transfer` and truncated arguments
+        assert!(liquidation(&oracle, USDC, "a9059cbb").is_none());
+        assert!(liquidation(&oracle, CUSDC, &COMPOUND_LIQUIDATE_BORROW_CALLDATA[..8 + 64 * 2]).is_none());
+        // Absorbing no account
+        let empty_absorb = format!("{}{}", &COMET_ABSORB_CALLDATA[..8 + 64 * 2], "00".repeat(32));
+        assert!(liquidation(&oracle, COMET, &empty_absorb).is_none());
+
+        // Only the configured protocols are trusted
+        let oracle = DefaultLiquidationOracle::with_protocols([COMET]);
+        assert!(liquidation(&oracle, CUSDC, COMPOUND_LIQUIDATE_BORROW_CALLDATA).is_none());
+        assert!(liquidation(&oracle, COMET, COMET_ABSORB_CALLDATA).is_some());
+    }
+}
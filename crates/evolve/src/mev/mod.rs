@@ -5,10 +5,31 @@
 
 pub mod detector;
 pub mod auction;
+pub mod calldata;
 pub mod distributor;
+pub mod ledger;
+pub mod liquidation;
+pub mod pipeline;
+pub mod privacy;
+pub mod registry;
+pub mod store;
 pub mod types;
 
 pub use detector::{MevDetector, MevOpportunity, MevType};
-pub use auction::{MevAuctionClient, BundleSubmission};
-pub use distributor::{MevDistributorClient, EpochData};
-pub use types::{MevMetrics, MevConfig};
+pub use auction::{
+    BundleError, BundleSimulation, BundleState, BundleStatus, BundleSubmission, CollateralSource, MevAuctionClient,
+    MevAuctionError, SearcherStats,
+};
+pub use distributor::{
+    DepositRetryConfig, EpochData, EpochSchedule, MevDistributorClient, MevDistributorError, SettlementTaskConfig,
+    SplitConfig,
+};
+pub use ledger::{DistributorLedger, LedgerError};
+pub use liquidation::{DefaultLiquidationOracle, LiquidationInfo, LiquidationOracle, LiquidationProtocol};
+pub use pipeline::MevPipeline;
+pub use privacy::{bundle_commitment, encrypt_bundle, BundleDecryptionError};
+pub use registry::BundleTransactionRegistry;
+pub use store::{InMemoryMevStore, MevOpportunityStore, MevStoreError};
+#[cfg(feature = "mev-store-sled")]
+pub use store::SledMevStore;
+pub use types::{BundleFirst, MevConfig, MevConfigError, MevMetrics, MevOrderingPolicy, NoReorder, SandwichBreaker};
@@ -0,0 +1,170 @@
+//! MEV Pipeline
+//!
+//! Connects detection to distribution: after a block is built, the MEV it
+//! realized is credited to the distributor buffer. When an auction bundle
+//! executed in the block, the bids paid for it are what the network captured.
+//! Otherwise a share of the value estimated by the detector is assumed to have
+//! been realized.
+//!
+//! Only opportunities valued in wei count towards the estimate. Values decoded
+//! from swap calldata are in units of the token named by their `token`
+//! metadata and can't be added to the buffer.
+
+use super::{
+    auction::MevAuctionClient,
+    detector::{MevDetector, MevOpportunity},
+    distributor::MevDistributorClient,
+};
+use alloy_primitives::U256;
+use reth_primitives::{SealedBlock, TransactionSigned};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+
+/// Share of the estimated opportunity value assumed realized by default, in basis points
+pub const DEFAULT_ESTIMATED_MEV_SHARE_BPS: u64 = 5_000;
+
+/// Credits the MEV realized in built blocks to the distributor
+pub struct MevPipeline {
+    /// Detector run over every built block
+    detector: Mutex<MevDetector>,
+    /// Auction whose executed bundles are credited at their bid
+    auction: Option<Arc<MevAuctionClient>>,
+    /// Distributor the realized MEV is added to
+    distributor: Arc<MevDistributorClient>,
+    /// Share of the estimated value credited for blocks without an executed bundle, in basis points
+    estimated_share_bps: u64,
+}
+
+impl fmt::Debug for MevPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MevPipeline")
+            .field("auction", &self.auction)
+            .field("estimated_share_bps", &self.estimated_share_bps)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MevPipeline {
+    /// Create a pipeline crediting what `detector` finds to `distributor`
+    pub fn new(detector: MevDetector, distributor: Arc<MevDistributorClient>) -> Self {
+        Self {
+            detector: Mutex::new(detector),
+            auction: None,
+            distributor,
+            estimated_share_bps: DEFAULT_ESTIMATED_MEV_SHARE_BPS,
+        }
+    }
+
+    /// Credit the bids of bundles executed through `auction` instead of estimates
+    pub fn with_auction(mut self, auction: Arc<MevAuctionClient>) -> Self {
+        self.auction = Some(auction);
+        self
+    }
+
+    /// Set the share of the estimated value credited for blocks without an executed bundle
+    pub fn with_estimated_share_bps(mut self, bps: u64) -> Self {
+        self.estimated_share_bps = bps.min(10_000);
+        self
+    }
+
+    /// Distributor the realized MEV is added to
+    pub fn distributor(&self) -> &Arc<MevDistributorClient> {
+        &self.distributor
+    }
+
+    /// Run detection over a built block and credit its realized MEV to the distributor
+    ///
+    /// Returns the amount credited. Bundles must be settled on the auction
+    /// before this is called for their bids to be counted.
+    pub async fn on_block_built(&self, block: &SealedBlock, transactions: &[TransactionSigned]) -> U256 {
+        let opportunities = self.detector.lock().unwrap().analyze_block(transactions, block.number);
+
+        let bids_paid = match &self.auction {
+            Some(auction) => auction.bids_paid_for_block(block.number).await,
+            None => U256::ZERO,
+        };
+        let realized = if bids_paid > U256::ZERO {
+            bids_paid
+        } else {
+            self.estimated_share(&opportunities)
+        };
+
+        debug!(
+            block_number = block.number,
+            opportunities = opportunities.len(),
+            %bids_paid,
+            %realized,
+            "AndeChain: crediting realized MEV to the distributor"
+        );
+        self.distributor.add_mev(realized).await;
+        realized
+    }
+
+    /// Configured share of the value of the opportunities valued in wei
+    fn estimated_share(&self, opportunities: &[MevOpportunity]) -> U256 {
+        let estimated: U256 = opportunities
+            .iter()
+            .filter(|opportunity| !opportunity.metadata.contains_key("token"))
+            .map(|opportunity| opportunity.value)
+            .fold(U256::ZERO, U256::saturating_add);
+        estimated.saturating_mul(U256::from(self.estimated_share_bps)) / U256::from(10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mev::{auction::BundleSubmission, detector::{DetectorConfig, MevType}};
+    use alloy_primitives::{Address, B256};
+    use reth_primitives::{Block, Header};
+
+    fn pipeline(auction: Option<Arc<MevAuctionClient>>) -> MevPipeline {
+        let distributor = Arc::new(MevDistributorClient::default_config(Address::random(), Address::random()));
+        let pipeline = MevPipeline::new(MevDetector::new(DetectorConfig::default()), distributor)
+            .with_estimated_share_bps(2_500);
+        match auction {
+            Some(auction) => pipeline.with_auction(auction),
+            None => pipeline,
+        }
+    }
+
+    fn block(number: u64) -> SealedBlock {
+        SealedBlock::seal_slow(Block { header: Header { number, ..Default::default() }, body: Default::default() })
+    }
+
+    #[tokio::test]
+    async fn test_executed_bundle_bid_credited() {
+        let auction = Arc::new(MevAuctionClient::new(Address::random(), Address::random()));
+        let bundle = BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::from(7_000),
+            target_block: 12,
+            transactions: vec![B256::random()],
+            searcher: Address::random(),
+            encrypted_payload: None,
+        };
+        auction.submit_bundle(bundle.clone()).await.unwrap();
+        auction.mark_bundle_executed(bundle.bundle_hash, U256::from(9_000), bundle.bid_amount).await.unwrap();
+
+        let pipeline = pipeline(Some(auction));
+        assert_eq!(pipeline.on_block_built(&block(12), &[]).await, U256::from(7_000));
+        assert_eq!(pipeline.distributor().get_buffer_amount().await, U256::from(7_000));
+
+        // The bundle doesn't count towards other blocks
+        assert_eq!(pipeline.on_block_built(&block(13), &[]).await, U256::ZERO);
+        assert_eq!(pipeline.distributor().get_buffer_amount().await, U256::from(7_000));
+    }
+
+    #[test]
+    fn test_estimated_share_skips_token_valued_opportunities() {
+        let pipeline = pipeline(None);
+        let native = MevOpportunity::new(MevType::Arbitrage, B256::random(), U256::from(1_000), 1);
+        let mut token = MevOpportunity::new(MevType::Sandwich, B256::random(), U256::from(50_000), 1);
+        token.add_metadata("token".to_string(), format!("{:?}", Address::random()));
+
+        assert_eq!(pipeline.estimated_share(&[native, token]), U256::from(250));
+    }
+}
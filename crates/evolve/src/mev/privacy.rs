@@ -0,0 +1,189 @@
+//! Encrypted Bundle Payloads
+//!
+//! Searchers can keep the transactions of a bundle private until the block
+//! it targets is built. The signed transactions are encrypted to the
+//! sequencer's published X25519 key and submitted in place of the list of
+//! transaction hashes.
+//!
+//! A payload is the 32 byte public key of an ephemeral X25519 key pair
+//! followed by the AES-256-GCM ciphertext of the transactions. The AES key is
+//! derived with HKDF-SHA256 from the shared secret, binding both public keys.
+//! Each ephemeral key encrypts a single payload, so the nonce is fixed.
+//!
+//! The plaintext is the EIP-2718 encoding of each transaction, prefixed with
+//! its length as a big-endian `u32`. The bundle hash commits to the revealed
+//! transactions: it is the keccak256 of their hashes, concatenated in order.
+
+use aes_gcm::{aead::Aead, Aes256Gcm, Key, KeyInit, Nonce};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{keccak256, Bytes, B256};
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use reth_primitives::TransactionSigned;
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+/// Domain separation of the derived payload keys
+const KEY_INFO: &[u8] = b"ande-bundle-payload-v1";
+
+/// Length of an X25519 public key
+const PUBLIC_KEY_LEN: usize = 32;
+
+/// Reasons an encrypted bundle payload can't be revealed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundleDecryptionError {
+    /// The sequencer has no decryption key registered
+    #[error("No bundle decryption key registered")]
+    NoDecryptionKey,
+    /// The payload is too short to hold an ephemeral key and a ciphertext
+    #[error("Encrypted payload of {0} bytes is malformed")]
+    Malformed(usize),
+    /// The ciphertext doesn't authenticate, e.g. because it was encrypted to another key
+    #[error("Encrypted payload could not be decrypted")]
+    Decryption,
+    /// The plaintext is not a list of transactions
+    #[error("Decrypted payload is not a list of transactions: {0}")]
+    InvalidTransactions(String),
+    /// The revealed transactions don't match the bundle hash they were committed to
+    #[error("Revealed transactions hash to {revealed}, bundle committed to {committed}")]
+    HashMismatch {
+        /// Hash of the submitted bundle
+        committed: B256,
+        /// Commitment computed from the decrypted transactions
+        revealed: B256,
+    },
+}
+
+/// Bundle hash committing to transactions with the given hashes
+pub fn bundle_commitment<'a>(tx_hashes: impl IntoIterator<Item = &'a B256>) -> B256 {
+    let mut preimage = Vec::new();
+    for hash in tx_hashes {
+        preimage.extend_from_slice(hash.as_slice());
+    }
+    keccak256(preimage)
+}
+
+/// AES key for the payload exchanged between `ephemeral` and `recipient`
+fn payload_cipher(shared_secret: &[u8; 32], ephemeral: &PublicKey, recipient: &PublicKey) -> Aes256Gcm {
+    let mut info = Vec::with_capacity(KEY_INFO.len() + 2 * PUBLIC_KEY_LEN);
+    info.extend_from_slice(KEY_INFO);
+    info.extend_from_slice(ephemeral.as_bytes());
+    info.extend_from_slice(recipient.as_bytes());
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    Hkdf::<Sha256>::new(None, shared_secret)
+        .expand(&info, key.as_mut())
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()))
+}
+
+/// Encrypt `transactions` to the sequencer key `recipient`
+pub fn encrypt_bundle(recipient: &PublicKey, transactions: &[TransactionSigned]) -> Bytes {
+    let mut plaintext = Zeroizing::new(Vec::new());
+    for tx in transactions {
+        let encoded = tx.encoded_2718();
+        plaintext.extend_from_slice(&(encoded.len() as u32).to_be_bytes());
+        plaintext.extend_from_slice(&encoded);
+    }
+
+    let ephemeral = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral_public = PublicKey::from(&ephemeral);
+    let shared = ephemeral.diffie_hellman(recipient);
+    let ciphertext = payload_cipher(shared.as_bytes(), &ephemeral_public, recipient)
+        .encrypt(Nonce::from_slice(&[0u8; 12]), plaintext.as_slice())
+        .expect("AES-GCM encryption of an in-memory buffer doesn't fail");
+
+    let mut payload = Vec::with_capacity(PUBLIC_KEY_LEN + ciphertext.len());
+    payload.extend_from_slice(ephemeral_public.as_bytes());
+    payload.extend_from_slice(&ciphertext);
+    payload.into()
+}
+
+/// Decrypt `payload` with the sequencer key `secret`, checking it against `bundle_hash`
+///
+/// The plaintext is zeroized once the transactions are decoded.
+pub fn decrypt_bundle(
+    secret: &StaticSecret,
+    payload: &[u8],
+    bundle_hash: B256,
+) -> Result<Vec<TransactionSigned>, BundleDecryptionError> {
+    if payload.len() <= PUBLIC_KEY_LEN {
+        return Err(BundleDecryptionError::Malformed(payload.len()));
+    }
+    let (ephemeral, ciphertext) = payload.split_at(PUBLIC_KEY_LEN);
+    let ephemeral = PublicKey::from(<[u8; 32]>::try_from(ephemeral).expect("split at the key length"));
+    let shared = secret.diffie_hellman(&ephemeral);
+    let plaintext = Zeroizing::new(
+        payload_cipher(shared.as_bytes(), &ephemeral, &PublicKey::from(secret))
+            .decrypt(Nonce::from_slice(&[0u8; 12]), ciphertext)
+            .map_err(|_| BundleDecryptionError::Decryption)?,
+    );
+
+    let transactions = decode_transactions(&plaintext)?;
+    let revealed = bundle_commitment(transactions.iter().map(|tx| tx.hash()));
+    if revealed != bundle_hash {
+        return Err(BundleDecryptionError::HashMismatch { committed: bundle_hash, revealed });
+    }
+    Ok(transactions)
+}
+
+/// Decode length-prefixed EIP-2718 transactions
+fn decode_transactions(mut plaintext: &[u8]) -> Result<Vec<TransactionSigned>, BundleDecryptionError> {
+    let invalid = BundleDecryptionError::InvalidTransactions;
+    let mut transactions = Vec::new();
+    while !plaintext.is_empty() {
+        let Some((len, rest)) = plaintext.split_first_chunk::<4>() else {
+            return Err(invalid("truncated length prefix".to_string()));
+        };
+        let len = u32::from_be_bytes(*len) as usize;
+        if rest.len() < len {
+            return Err(invalid(format!("transaction of {len} bytes truncated to {}", rest.len())));
+        }
+        let (mut encoded, rest) = rest.split_at(len);
+        let tx = TransactionSigned::decode_2718(&mut encoded).map_err(|err| invalid(err.to_string()))?;
+        if !encoded.is_empty() {
+            return Err(invalid(format!("{} trailing bytes after transaction", encoded.len())));
+        }
+        transactions.push(tx);
+        plaintext = rest;
+    }
+
+    if transactions.is_empty() {
+        return Err(invalid("no transactions".to_string()));
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{TxLegacy, TypedTransaction};
+    use alloy_primitives::Signature;
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        let tx = TxLegacy { nonce, gas_limit: 21_000, ..Default::default() };
+        TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), Signature::test_signature())
+    }
+
+    #[test]
+    fn test_payload_round_trip() {
+        let secret = StaticSecret::from([7u8; 32]);
+        let transactions = vec![transaction(0), transaction(1)];
+        let bundle_hash = bundle_commitment(transactions.iter().map(|tx| tx.hash()));
+
+        let payload = encrypt_bundle(&PublicKey::from(&secret), &transactions);
+        assert_eq!(decrypt_bundle(&secret, &payload, bundle_hash).unwrap(), transactions);
+
+        // Ephemeral keys make every payload different
+        assert_ne!(encrypt_bundle(&PublicKey::from(&secret), &transactions), payload);
+        assert_eq!(
+            decrypt_bundle(&secret, &payload[..PUBLIC_KEY_LEN], bundle_hash),
+            Err(BundleDecryptionError::Malformed(32))
+        );
+
+        let mut tampered = payload.to_vec();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(decrypt_bundle(&secret, &tampered, bundle_hash), Err(BundleDecryptionError::Decryption));
+    }
+}
@@ -0,0 +1,84 @@
+//! Bundle Transaction Registry
+//!
+//! Auction bundles only reference their transactions by hash. Searchers hand
+//! the signed transactions to the registry when submitting a bundle, and the
+//! payload builder resolves the winning bundle's hashes against it before
+//! placing the bundle in a block.
+
+use alloy_primitives::B256;
+use reth_primitives::TransactionSigned;
+use std::{collections::HashMap, sync::RwLock};
+
+/// Signed transactions of submitted bundles, keyed by transaction hash
+#[derive(Debug, Default)]
+pub struct BundleTransactionRegistry {
+    /// Registered transactions
+    transactions: RwLock<HashMap<B256, TransactionSigned>>,
+}
+
+impl BundleTransactionRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `transactions`, replacing any registered with the same hash
+    pub fn insert(&self, transactions: impl IntoIterator<Item = TransactionSigned>) {
+        let mut registered = self.transactions.write().unwrap();
+        for tx in transactions {
+            registered.insert(*tx.hash(), tx);
+        }
+    }
+
+    /// Transactions with the given hashes, in order, or `None` if any of them isn't registered
+    pub fn resolve(&self, hashes: &[B256]) -> Option<Vec<TransactionSigned>> {
+        let registered = self.transactions.read().unwrap();
+        hashes.iter().map(|hash| registered.get(hash).cloned()).collect()
+    }
+
+    /// Forget the transactions with the given hashes
+    pub fn remove<'a>(&self, hashes: impl IntoIterator<Item = &'a B256>) {
+        let mut registered = self.transactions.write().unwrap();
+        for hash in hashes {
+            registered.remove(hash);
+        }
+    }
+
+    /// Number of registered transactions
+    pub fn len(&self) -> usize {
+        self.transactions.read().unwrap().len()
+    }
+
+    /// Whether no transaction is registered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{TxLegacy, TypedTransaction};
+    use alloy_primitives::Signature;
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        let tx = TxLegacy { nonce, gas_limit: 21_000, ..Default::default() };
+        TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), Signature::test_signature())
+    }
+
+    #[test]
+    fn test_resolve_requires_every_transaction() {
+        let registry = BundleTransactionRegistry::new();
+        let (first, second) = (transaction(0), transaction(1));
+        registry.insert([first.clone(), second.clone()]);
+
+        // Resolution follows the order of the hashes, not of registration
+        let resolved = registry.resolve(&[*second.hash(), *first.hash()]).unwrap();
+        assert_eq!(resolved, vec![second.clone(), first.clone()]);
+        assert!(registry.resolve(&[*first.hash(), B256::random()]).is_none());
+
+        registry.remove([first.hash()]);
+        assert_eq!(registry.len(), 1);
+        assert!(registry.resolve(&[*first.hash()]).is_none());
+    }
+}
@@ -0,0 +1,246 @@
+//! MEV Opportunity Storage
+//!
+//! Keeps the opportunities detected in each block so MEV can be audited over
+//! time and distribution can be based on the values actually observed. Stores
+//! are keyed by block number and retain a bounded number of blocks.
+
+use super::{detector::MevOpportunity, types::MevMetrics};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::RangeInclusive,
+    sync::RwLock,
+};
+
+/// Number of blocks kept by default
+pub const DEFAULT_RETAINED_BLOCKS: usize = 10_000;
+
+/// Errors returned by an [`MevOpportunityStore`]
+#[derive(Debug, thiserror::Error)]
+pub enum MevStoreError {
+    /// The storage backend failed
+    #[cfg(feature = "mev-store-sled")]
+    #[error("MEV store backend error: {0}")]
+    Backend(#[from] sled::Error),
+    /// A stored record could not be encoded or decoded
+    #[cfg(feature = "mev-store-sled")]
+    #[error("MEV store codec error: {0}")]
+    Codec(#[from] serde_json::Error),
+}
+
+/// Store of detected MEV opportunities, keyed by block number
+pub trait MevOpportunityStore: fmt::Debug + Send + Sync {
+    /// Record the opportunities detected in `block`, replacing any earlier record
+    ///
+    /// Blocks without opportunities should be recorded too, so they count
+    /// towards per-block averages.
+    fn record(&self, block: u64, opportunities: Vec<MevOpportunity>) -> Result<(), MevStoreError>;
+
+    /// Recorded blocks within `range` with their opportunities, in block order
+    fn by_block(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<(u64, Vec<MevOpportunity>)>, MevStoreError>;
+
+    /// Metrics over the recorded blocks within `range`
+    ///
+    /// Every opportunity counts as captured, `epoch_mev` covers the whole range
+    /// and the average is taken over the recorded blocks.
+    fn aggregate(&self, range: RangeInclusive<u64>) -> Result<MevMetrics, MevStoreError> {
+        let blocks = self.by_block(range)?;
+        let mut metrics = MevMetrics::new();
+        for opportunity in blocks.iter().flat_map(|(_, opportunities)| opportunities) {
+            metrics.record_opportunity(opportunity.value);
+        }
+        metrics.calculate_avg_mev(blocks.len() as u64);
+        Ok(metrics)
+    }
+}
+
+/// In-memory store keeping the most recent blocks
+#[derive(Debug)]
+pub struct InMemoryMevStore {
+    /// Maximum number of blocks retained
+    capacity: usize,
+    /// Recorded blocks, oldest first
+    blocks: RwLock<BTreeMap<u64, Vec<MevOpportunity>>>,
+}
+
+impl InMemoryMevStore {
+    /// Create a store retaining at most `capacity` blocks
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), blocks: RwLock::new(BTreeMap::new()) }
+    }
+
+    /// Number of blocks currently retained
+    pub fn len(&self) -> usize {
+        self.blocks.read().unwrap().len()
+    }
+
+    /// Whether no block is retained
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for InMemoryMevStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETAINED_BLOCKS)
+    }
+}
+
+impl MevOpportunityStore for InMemoryMevStore {
+    fn record(&self, block: u64, opportunities: Vec<MevOpportunity>) -> Result<(), MevStoreError> {
+        let mut blocks = self.blocks.write().unwrap();
+        blocks.insert(block, opportunities);
+        while blocks.len() > self.capacity {
+            blocks.pop_first();
+        }
+        Ok(())
+    }
+
+    fn by_block(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<(u64, Vec<MevOpportunity>)>, MevStoreError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let blocks = self.blocks.read().unwrap();
+        Ok(blocks.range(range).map(|(block, opportunities)| (*block, opportunities.clone())).collect())
+    }
+}
+
+/// File-backed store on top of a sled database
+#[cfg(feature = "mev-store-sled")]
+#[derive(Debug, Clone)]
+pub struct SledMevStore {
+    /// Maximum number of blocks retained
+    capacity: usize,
+    /// Recorded blocks, keyed by big-endian block number so keys sort by block
+    db: sled::Db,
+}
+
+#[cfg(feature = "mev-store-sled")]
+impl SledMevStore {
+    /// Open or create a store at `path` retaining at most `capacity` blocks
+    pub fn open(path: impl AsRef<std::path::Path>, capacity: usize) -> Result<Self, MevStoreError> {
+        Ok(Self::from_db(sled::open(path)?, capacity))
+    }
+
+    /// Use an already opened database
+    pub fn from_db(db: sled::Db, capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), db }
+    }
+}
+
+#[cfg(feature = "mev-store-sled")]
+impl MevOpportunityStore for SledMevStore {
+    fn record(&self, block: u64, opportunities: Vec<MevOpportunity>) -> Result<(), MevStoreError> {
+        self.db.insert(block.to_be_bytes(), serde_json::to_vec(&opportunities)?)?;
+        while self.db.len() > self.capacity {
+            self.db.pop_min()?;
+        }
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn by_block(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<(u64, Vec<MevOpportunity>)>, MevStoreError> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.db
+            .range(range.start().to_be_bytes()..=range.end().to_be_bytes())
+            .map(|entry| {
+                let (key, value) = entry?;
+                let block = u64::from_be_bytes(key.as_ref().try_into().expect("block keys are 8 bytes"));
+                Ok((block, serde_json::from_slice(&value)?))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mev::detector::MevType;
+    use alloy_primitives::{B256, U256};
+
+    fn opportunity(value: u64) -> MevOpportunity {
+        MevOpportunity::new(MevType::Sandwich, B256::random(), U256::from(value), 0)
+    }
+
+    #[test]
+    fn test_in_memory_store_retention_limit() {
+        let store = InMemoryMevStore::new(3);
+        for block in 1..=5 {
+            store.record(block, vec![opportunity(block)]).unwrap();
+        }
+
+        assert_eq!(store.len(), 3);
+        let blocks: Vec<u64> = store.by_block(0..=10).unwrap().into_iter().map(|(block, _)| block).collect();
+        assert_eq!(blocks, vec![3, 4, 5]);
+
+        // Re-recording a retained block replaces it without evicting another one
+        store.record(4, Vec::new()).unwrap();
+        assert_eq!(store.len(), 3);
+        assert!(store.by_block(4..=4).unwrap()[0].1.is_empty());
+    }
+
+    #[test]
+    fn test_in_memory_store_by_block_range() {
+        let store = InMemoryMevStore::default();
+        store.record(10, vec![opportunity(1), opportunity(2)]).unwrap();
+        store.record(11, Vec::new()).unwrap();
+        store.record(12, vec![opportunity(3)]).unwrap();
+
+        let blocks = store.by_block(11..=12).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].0, blocks[0].1.len()), (11, 0));
+        assert_eq!((blocks[1].0, blocks[1].1.len()), (12, 1));
+        assert!(store.by_block(13..=20).unwrap().is_empty());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = store.by_block(12..=10).unwrap();
+        assert!(reversed.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_sums_values_and_averages_over_recorded_blocks() {
+        let store = InMemoryMevStore::default();
+        store.record(1, vec![opportunity(100), opportunity(50)]).unwrap();
+        store.record(2, Vec::new()).unwrap();
+        store.record(3, vec![opportunity(30)]).unwrap();
+        store.record(4, vec![opportunity(1000)]).unwrap();
+
+        let metrics = store.aggregate(1..=3).unwrap();
+        assert_eq!(metrics.opportunities_detected, 3);
+        assert_eq!(metrics.total_mev_captured, U256::from(180));
+        assert_eq!(metrics.epoch_mev, U256::from(180));
+        assert_eq!(metrics.avg_mev_per_block, U256::from(60));
+
+        let empty = store.aggregate(5..=9).unwrap();
+        assert_eq!(empty.opportunities_detected, 0);
+        assert_eq!(empty.avg_mev_per_block, U256::ZERO);
+    }
+
+    #[cfg(feature = "mev-store-sled")]
+    #[test]
+    fn test_sled_store_retention_and_aggregate() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledMevStore::from_db(db, 2);
+        store.record(255, vec![opportunity(10)]).unwrap();
+        store.record(256, vec![opportunity(20), opportunity(40)]).unwrap();
+        store.record(257, vec![opportunity(60)]).unwrap();
+
+        let blocks: Vec<u64> = store.by_block(0..=1000).unwrap().into_iter().map(|(block, _)| block).collect();
+        assert_eq!(blocks, vec![256, 257]);
+
+        let metrics = store.aggregate(0..=1000).unwrap();
+        assert_eq!(metrics.opportunities_detected, 3);
+        assert_eq!(metrics.total_mev_captured, U256::from(120));
+        assert_eq!(metrics.avg_mev_per_block, U256::from(60));
+    }
+}
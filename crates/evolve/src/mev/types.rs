@@ -1,11 +1,78 @@
 //! MEV Types and Configuration
 
+use super::{
+    auction::BundleSubmission,
+    detector::{DetectorConfig, MevOpportunity, MevType},
+    liquidation::DefaultLiquidationOracle,
+};
+use alloy::transports::http::reqwest::Url;
 use alloy_primitives::{Address, U256, B256};
+use reth_primitives::TransactionSigned;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::{env::VarError, fmt, str::FromStr, sync::Arc, time::Duration};
+
+/// Environment variables read by [`MevConfig::with_env_overrides`]
+pub const MEV_ENV_VARS: &[&str] = &[
+    "ANDE_MEV_ENABLE_DETECTION",
+    "ANDE_MEV_ENABLE_AUCTION",
+    "ANDE_MEV_ENABLE_DISTRIBUTION",
+    "ANDE_MEV_DISTRIBUTOR_ADDRESS",
+    "ANDE_MEV_AUCTION_ADDRESS",
+    "ANDE_MEV_MIN_VALUE",
+    "ANDE_MEV_RPC_ENDPOINT",
+    "ANDE_MEV_DEPOSIT_INTERVAL_SECS",
+    "ANDE_MEV_MAX_BUFFER",
+    "ANDE_MEV_DETECT_ARBITRAGE",
+    "ANDE_MEV_DETECT_SANDWICH",
+    "ANDE_MEV_DETECT_LIQUIDATION",
+    "ANDE_MEV_DETECT_BACK_RUN",
+    "ANDE_MEV_DETECT_JIT_LIQUIDITY",
+    "ANDE_MEV_LARGE_SWAP_MIN_AMOUNT",
+    "ANDE_MEV_LIQUIDATION_BONUS_BPS",
+    "ANDE_MEV_MAX_PENDING",
+    "ANDE_MEV_DEX_ROUTERS",
+    "ANDE_MEV_LENDING_PROTOCOLS",
+];
+
+/// Errors returned when reading or validating an [`MevConfig`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MevConfigError {
+    /// Distribution is enabled without a contract to deposit to
+    #[error("MEV distribution enabled but no distributor address provided")]
+    MissingDistributorAddress,
+    /// The auction is enabled without a contract to submit bundles to
+    #[error("MEV auction enabled but no auction address provided")]
+    MissingAuctionAddress,
+    /// The RPC endpoint used for contract calls is not a URL
+    #[error("Invalid MEV RPC endpoint {0:?}")]
+    InvalidRpcEndpoint(String),
+    /// Deposits would be attempted on every block
+    #[error("MEV deposit interval must be greater than zero")]
+    ZeroDepositInterval,
+    /// The liquidation bonus is more than the debt covered
+    #[error("Liquidation bonus of {0} bps exceeds 10000")]
+    InvalidLiquidationBonus(u64),
+    /// Streaming detection could not track any pending transaction
+    #[error("MEV detector must track at least one pending transaction")]
+    ZeroMaxPending,
+    /// An environment variable is set to a value that can't be parsed
+    #[error("Invalid value {value:?} for {name}: {reason}")]
+    InvalidEnv {
+        /// Variable name
+        name: &'static str,
+        /// Value as set
+        value: String,
+        /// Why it was rejected
+        reason: String,
+    },
+}
 
 /// Configuration for MEV detection and integration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Read from the `[mev]` section of the payload builder config file, with
+/// every field optional, and overridden by `ANDE_MEV_*` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MevConfig {
     /// Enable MEV detection
     pub enable_detection: bool,
@@ -22,40 +89,225 @@ pub struct MevConfig {
     /// RPC endpoint for contract calls
     pub rpc_endpoint: String,
     /// Deposit interval for MEV distribution
+    #[serde(rename = "deposit_interval_secs", with = "duration_secs")]
     pub deposit_interval: Duration,
     /// Maximum MEV buffer before forcing deposit
     pub max_mev_buffer: U256,
+    /// Enable arbitrage detection
+    pub detect_arbitrage: bool,
+    /// Enable sandwich detection
+    pub detect_sandwich: bool,
+    /// Enable liquidation detection
+    pub detect_liquidation: bool,
+    /// Enable back-run detection
+    pub detect_back_run: bool,
+    /// Enable JIT liquidity detection
+    pub detect_jit_liquidity: bool,
+    /// Minimum amount sold for a swap to attract back-runs and JIT liquidity
+    pub large_swap_min_amount: U256,
+    /// Bonus earned on liquidations, in basis points of the debt covered
+    pub liquidation_bonus_bps: u64,
+    /// Maximum number of pending transactions tracked in streaming mode
+    pub max_pending: usize,
+    /// Known DEX router addresses
+    pub dex_routers: Vec<Address>,
+    /// Known lending protocol addresses
+    pub lending_protocols: Vec<Address>,
 }
 
 impl Default for MevConfig {
     fn default() -> Self {
+        let detector = DetectorConfig::default();
         Self {
             enable_detection: true,
             enable_auction: true,
             enable_distribution: true,
             distributor_address: None,
             auction_address: None,
-            min_mev_value: U256::from(100_000_000_000_000_000u64), // 0.1 ANDE
+            min_mev_value: detector.min_value,
             rpc_endpoint: "http://localhost:8545".to_string(),
             deposit_interval: Duration::from_secs(3600), // 1 hour
             max_mev_buffer: U256::from(1000) * U256::from(10u64.pow(18)), // 1000 ANDE
+            detect_arbitrage: detector.detect_arbitrage,
+            detect_sandwich: detector.detect_sandwich,
+            detect_liquidation: detector.detect_liquidation,
+            detect_back_run: detector.detect_back_run,
+            detect_jit_liquidity: detector.detect_jit_liquidity,
+            large_swap_min_amount: detector.large_swap_min_amount,
+            liquidation_bonus_bps: detector.liquidation_bonus_bps,
+            max_pending: detector.max_pending,
+            dex_routers: Vec::new(),
+            lending_protocols: Vec::new(),
         }
     }
 }
 
 impl MevConfig {
     /// Validate configuration
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), MevConfigError> {
         if self.enable_distribution && self.distributor_address.is_none() {
-            return Err("MEV distribution enabled but no distributor address provided".to_string());
+            return Err(MevConfigError::MissingDistributorAddress);
         }
-        
+
         if self.enable_auction && self.auction_address.is_none() {
-            return Err("MEV auction enabled but no auction address provided".to_string());
+            return Err(MevConfigError::MissingAuctionAddress);
         }
-        
+
+        if (self.enable_auction || self.enable_distribution) && self.rpc_endpoint.parse::<Url>().is_err() {
+            return Err(MevConfigError::InvalidRpcEndpoint(self.rpc_endpoint.clone()));
+        }
+
+        if self.enable_distribution && self.deposit_interval.is_zero() {
+            return Err(MevConfigError::ZeroDepositInterval);
+        }
+
+        if self.enable_detection {
+            if self.liquidation_bonus_bps > 10_000 {
+                return Err(MevConfigError::InvalidLiquidationBonus(self.liquidation_bonus_bps));
+            }
+            if self.max_pending == 0 {
+                return Err(MevConfigError::ZeroMaxPending);
+            }
+        }
+
         Ok(())
     }
+
+    /// Whether any `ANDE_MEV_*` variable is set
+    pub fn env_is_set() -> bool {
+        MEV_ENV_VARS.iter().any(|name| std::env::var_os(name).is_some())
+    }
+
+    /// Create configuration from environment variables, with defaults for those unset
+    pub fn from_env() -> Result<Self, MevConfigError> {
+        Self::default().with_env_overrides()
+    }
+
+    /// Override the fields whose `ANDE_MEV_*` variable is set
+    ///
+    /// Address lists are comma-separated and replace the configured list.
+    pub fn with_env_overrides(mut self) -> Result<Self, MevConfigError> {
+        override_from_env(&mut self.enable_detection, "ANDE_MEV_ENABLE_DETECTION")?;
+        override_from_env(&mut self.enable_auction, "ANDE_MEV_ENABLE_AUCTION")?;
+        override_from_env(&mut self.enable_distribution, "ANDE_MEV_ENABLE_DISTRIBUTION")?;
+        if let Some(address) = env_var("ANDE_MEV_DISTRIBUTOR_ADDRESS")? {
+            self.distributor_address = Some(address);
+        }
+        if let Some(address) = env_var("ANDE_MEV_AUCTION_ADDRESS")? {
+            self.auction_address = Some(address);
+        }
+        override_from_env(&mut self.min_mev_value, "ANDE_MEV_MIN_VALUE")?;
+        override_from_env(&mut self.rpc_endpoint, "ANDE_MEV_RPC_ENDPOINT")?;
+        if let Some(secs) = env_var("ANDE_MEV_DEPOSIT_INTERVAL_SECS")? {
+            self.deposit_interval = Duration::from_secs(secs);
+        }
+        override_from_env(&mut self.max_mev_buffer, "ANDE_MEV_MAX_BUFFER")?;
+        override_from_env(&mut self.detect_arbitrage, "ANDE_MEV_DETECT_ARBITRAGE")?;
+        override_from_env(&mut self.detect_sandwich, "ANDE_MEV_DETECT_SANDWICH")?;
+        override_from_env(&mut self.detect_liquidation, "ANDE_MEV_DETECT_LIQUIDATION")?;
+        override_from_env(&mut self.detect_back_run, "ANDE_MEV_DETECT_BACK_RUN")?;
+        override_from_env(&mut self.detect_jit_liquidity, "ANDE_MEV_DETECT_JIT_LIQUIDITY")?;
+        override_from_env(&mut self.large_swap_min_amount, "ANDE_MEV_LARGE_SWAP_MIN_AMOUNT")?;
+        override_from_env(&mut self.liquidation_bonus_bps, "ANDE_MEV_LIQUIDATION_BONUS_BPS")?;
+        override_from_env(&mut self.max_pending, "ANDE_MEV_MAX_PENDING")?;
+        if let Some(routers) = env_address_list("ANDE_MEV_DEX_ROUTERS")? {
+            self.dex_routers = routers;
+        }
+        if let Some(protocols) = env_address_list("ANDE_MEV_LENDING_PROTOCOLS")? {
+            self.lending_protocols = protocols;
+        }
+        Ok(self)
+    }
+
+    /// Detector configuration with the configured thresholds and known contracts
+    ///
+    /// Liquidations are decoded on the configured lending protocols, or on any
+    /// contract if none is configured.
+    pub fn detector_config(&self) -> DetectorConfig {
+        DetectorConfig {
+            detect_arbitrage: self.detect_arbitrage,
+            detect_sandwich: self.detect_sandwich,
+            detect_liquidation: self.detect_liquidation,
+            detect_back_run: self.detect_back_run,
+            detect_jit_liquidity: self.detect_jit_liquidity,
+            min_value: self.min_mev_value,
+            dex_routers: self.dex_routers.iter().copied().collect(),
+            lending_protocols: self.lending_protocols.iter().copied().collect(),
+            liquidation_bonus_bps: self.liquidation_bonus_bps,
+            liquidation_oracle: Some(Arc::new(DefaultLiquidationOracle::with_protocols(
+                self.lending_protocols.iter().copied(),
+            ))),
+            max_pending: self.max_pending,
+            large_swap_min_amount: self.large_swap_min_amount,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse the environment variable `name`, or `None` if it isn't set
+fn env_var<T>(name: &'static str) -> Result<Option<T>, MevConfigError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|err: T::Err| MevConfigError::InvalidEnv { name, reason: err.to_string(), value }),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(value)) => Err(MevConfigError::InvalidEnv {
+            name,
+            value: value.to_string_lossy().into_owned(),
+            reason: "not valid unicode".to_string(),
+        }),
+    }
+}
+
+/// Replace `field` with the environment variable `name` if it is set
+fn override_from_env<T>(field: &mut T, name: &'static str) -> Result<(), MevConfigError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    if let Some(value) = env_var(name)? {
+        *field = value;
+    }
+    Ok(())
+}
+
+/// Parse the comma-separated address list `name`, or `None` if it isn't set
+fn env_address_list(name: &'static str) -> Result<Option<Vec<Address>>, MevConfigError> {
+    let Some(list) = env_var::<String>(name)? else {
+        return Ok(None);
+    };
+    list.split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            Address::from_str(address).map_err(|err| MevConfigError::InvalidEnv {
+                name,
+                value: list.clone(),
+                reason: format!("{address}: {err}"),
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// (De)serializes a [`Duration`] as a whole number of seconds
+mod duration_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
 }
 
 /// MEV metrics for monitoring
@@ -154,6 +406,108 @@ pub struct EpochInfo {
     pub timestamp: u64,
 }
 
+/// Policy deciding the order of a block's transactions once MEV has been detected
+///
+/// Invoked by the payload builder before execution, with the opportunities
+/// detected in the transactions as submitted.
+pub trait MevOrderingPolicy: fmt::Debug + Send + Sync {
+    /// Reorder `transactions` given the `opportunities` detected in them
+    fn order(
+        &self,
+        transactions: Vec<TransactionSigned>,
+        opportunities: &[MevOpportunity],
+    ) -> Vec<TransactionSigned>;
+}
+
+/// Keeps transactions in the order they were submitted
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoReorder;
+
+impl MevOrderingPolicy for NoReorder {
+    fn order(
+        &self,
+        transactions: Vec<TransactionSigned>,
+        _opportunities: &[MevOpportunity],
+    ) -> Vec<TransactionSigned> {
+        transactions
+    }
+}
+
+/// Splits detected sandwiches apart by moving the victim ahead of the front-run
+///
+/// The front-run and back-run keep their relative order, so the attacker's
+/// nonces stay valid, but they no longer surround the victim.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SandwichBreaker;
+
+impl MevOrderingPolicy for SandwichBreaker {
+    fn order(
+        &self,
+        mut transactions: Vec<TransactionSigned>,
+        opportunities: &[MevOpportunity],
+    ) -> Vec<TransactionSigned> {
+        for opportunity in opportunities.iter().filter(|opp| opp.mev_type == MevType::Sandwich) {
+            // Only cross-transaction detection knows the front-run of a sandwich
+            let Some(front_run) =
+                opportunity.metadata.get("front_run_tx").and_then(|hash| hash.parse::<B256>().ok())
+            else {
+                continue;
+            };
+
+            let position = |hash: B256| transactions.iter().position(|tx| *tx.hash() == hash);
+            if let (Some(front), Some(victim)) = (position(front_run), position(opportunity.tx_hash)) {
+                if front < victim {
+                    let victim_tx = transactions.remove(victim);
+                    transactions.insert(front, victim_tx);
+                }
+            }
+        }
+
+        transactions
+    }
+}
+
+/// Places the transactions of the winning auction bundle at the top of the block
+#[derive(Debug, Clone, Default)]
+pub struct BundleFirst {
+    /// Hashes of the bundle transactions, in bundle order
+    bundle: Vec<B256>,
+}
+
+impl BundleFirst {
+    /// Put the given bundle transactions first
+    pub fn new(bundle: Vec<B256>) -> Self {
+        Self { bundle }
+    }
+
+    /// Put the transactions of the highest bid among `bundles` first
+    ///
+    /// Ties go to the bundle submitted first.
+    pub fn from_auction(bundles: &[BundleSubmission]) -> Self {
+        let winner = bundles.iter().reduce(|best, bundle| {
+            if bundle.bid_amount > best.bid_amount { bundle } else { best }
+        });
+        Self::new(winner.map(|bundle| bundle.transactions.clone()).unwrap_or_default())
+    }
+}
+
+impl MevOrderingPolicy for BundleFirst {
+    fn order(
+        &self,
+        mut transactions: Vec<TransactionSigned>,
+        _opportunities: &[MevOpportunity],
+    ) -> Vec<TransactionSigned> {
+        let mut ordered = Vec::with_capacity(transactions.len());
+        for hash in &self.bundle {
+            if let Some(index) = transactions.iter().position(|tx| tx.hash() == hash) {
+                ordered.push(transactions.remove(index));
+            }
+        }
+        ordered.extend(transactions);
+        ordered
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +533,158 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_mev_config_validation_failures() {
+        let valid = MevConfig {
+            distributor_address: Some(Address::with_last_byte(1)),
+            auction_address: Some(Address::with_last_byte(2)),
+            ..Default::default()
+        };
+        assert_eq!(valid.validate(), Ok(()));
+
+        let cases = [
+            (MevConfig { auction_address: None, ..valid.clone() }, MevConfigError::MissingAuctionAddress),
+            (
+                MevConfig { rpc_endpoint: "localhost".to_string(), ..valid.clone() },
+                MevConfigError::InvalidRpcEndpoint("localhost".to_string()),
+            ),
+            (MevConfig { deposit_interval: Duration::ZERO, ..valid.clone() }, MevConfigError::ZeroDepositInterval),
+            (
+                MevConfig { liquidation_bonus_bps: 10_001, ..valid.clone() },
+                MevConfigError::InvalidLiquidationBonus(10_001),
+            ),
+            (MevConfig { max_pending: 0, ..valid.clone() }, MevConfigError::ZeroMaxPending),
+        ];
+        for (config, expected) in cases {
+            assert_eq!(config.validate(), Err(expected));
+        }
+
+        // Detection alone needs neither contracts nor an endpoint
+        let detection_only = MevConfig {
+            enable_auction: false,
+            enable_distribution: false,
+            rpc_endpoint: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(detection_only.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_detector_config_from_mev_config() {
+        let router = Address::with_last_byte(0xaa);
+        let config = MevConfig {
+            detect_sandwich: false,
+            min_mev_value: U256::from(5),
+            large_swap_min_amount: U256::from(7),
+            dex_routers: vec![router, router],
+            ..Default::default()
+        };
+
+        let detector = config.detector_config();
+        assert!(!detector.detect_sandwich);
+        assert!(detector.detect_arbitrage);
+        assert_eq!(detector.min_value, U256::from(5));
+        assert_eq!(detector.large_swap_min_amount, U256::from(7));
+        assert_eq!(detector.dex_routers, [router].into_iter().collect());
+        assert!(detector.lending_protocols.is_empty());
+        assert!(!detector.selectors.is_empty());
+    }
+
+    /// Serializes tests that modify the process environment
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Replaces every `ANDE_MEV_*` variable for the lifetime of the guard
+    struct EnvGuard {
+        previous: Vec<(&'static str, Option<String>)>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, &str)]) -> Self {
+            let lock = ENV_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            let previous: Vec<_> = MEV_ENV_VARS.iter().map(|&name| (name, std::env::var(name).ok())).collect();
+
+            // SAFETY: tests touching the environment hold `ENV_LOCK`
+            unsafe {
+                for name in MEV_ENV_VARS {
+                    std::env::remove_var(name);
+                }
+                for (name, value) in vars {
+                    std::env::set_var(name, value);
+                }
+            }
+            Self { previous, _lock: lock }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            // SAFETY: the guard still holds `ENV_LOCK`
+            unsafe {
+                for (name, value) in &self.previous {
+                    match value {
+                        Some(value) => std::env::set_var(name, value),
+                        None => std::env::remove_var(name),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let router = Address::with_last_byte(0xaa);
+        let lender = Address::with_last_byte(0xbb);
+        let _env = EnvGuard::set(&[
+            ("ANDE_MEV_ENABLE_AUCTION", "false"),
+            ("ANDE_MEV_DISTRIBUTOR_ADDRESS", "0x00000000000000000000000000000000000000cc"),
+            ("ANDE_MEV_MIN_VALUE", "1000"),
+            ("ANDE_MEV_DEPOSIT_INTERVAL_SECS", "60"),
+            ("ANDE_MEV_MAX_BUFFER", "0x10"),
+            ("ANDE_MEV_DETECT_JIT_LIQUIDITY", "false"),
+            ("ANDE_MEV_DEX_ROUTERS", &format!("{router}, {lender}")),
+            ("ANDE_MEV_LENDING_PROTOCOLS", ""),
+        ]);
+        assert!(MevConfig::env_is_set());
+
+        let file = MevConfig { lending_protocols: vec![lender], max_pending: 16, ..Default::default() };
+        let config = file.with_env_overrides().unwrap();
+        assert!(!config.enable_auction);
+        assert_eq!(config.distributor_address, Some(Address::with_last_byte(0xcc)));
+        assert_eq!(config.min_mev_value, U256::from(1000));
+        assert_eq!(config.deposit_interval, Duration::from_secs(60));
+        assert_eq!(config.max_mev_buffer, U256::from(16));
+        assert!(!config.detect_jit_liquidity);
+        assert_eq!(config.dex_routers, vec![router, lender]);
+        // An empty list clears the configured one, unset variables keep their value
+        assert!(config.lending_protocols.is_empty());
+        assert_eq!(config.max_pending, 16);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_env_rejects_invalid_values() {
+        let cases = [
+            ("ANDE_MEV_ENABLE_DETECTION", "yes"),
+            ("ANDE_MEV_AUCTION_ADDRESS", "0x1234"),
+            ("ANDE_MEV_MAX_PENDING", "-1"),
+            ("ANDE_MEV_DEX_ROUTERS", "0x00000000000000000000000000000000000000aa,router"),
+        ];
+        for (name, value) in cases {
+            let _env = EnvGuard::set(&[(name, value)]);
+            match MevConfig::from_env() {
+                Err(MevConfigError::InvalidEnv { name: invalid, value: invalid_value, .. }) => {
+                    assert_eq!((invalid, invalid_value.as_str()), (name, value));
+                }
+                other => panic!("Expected {name}={value} to be rejected, got {other:?}"),
+            }
+        }
+
+        let _env = EnvGuard::set(&[]);
+        assert!(!MevConfig::env_is_set());
+        assert_eq!(MevConfig::from_env(), Ok(MevConfig::default()));
+    }
+
     #[test]
     fn test_mev_metrics_recording() {
         let mut metrics = MevMetrics::new();
@@ -207,4 +713,68 @@ mod tests {
         assert_eq!(metrics.epoch_mev, U256::ZERO);
         assert_eq!(metrics.total_mev_captured, value); // Total should persist
     }
+
+    /// Legacy transaction from one of several test signers
+    fn test_transaction(signer: u64, to: Address, gas_price: u128, nonce: u64) -> TransactionSigned {
+        use alloy_consensus::{TxLegacy, TypedTransaction};
+        use alloy_primitives::{Signature, TxKind};
+
+        let tx = TxLegacy {
+            chain_id: Some(31337),
+            nonce,
+            gas_price,
+            gas_limit: 100_000,
+            to: TxKind::Call(to),
+            ..Default::default()
+        };
+        let signature = Signature::new(Signature::test_signature().r(), U256::from(signer), false);
+        TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), signature)
+    }
+
+    fn hashes(transactions: &[TransactionSigned]) -> Vec<B256> {
+        transactions.iter().map(|tx| *tx.hash()).collect()
+    }
+
+    #[test]
+    fn test_sandwich_breaker_separates_attacker_transactions() {
+        use super::super::detector::{DetectorConfig, MevDetector};
+
+        let pool = Address::random();
+        let front_run = test_transaction(1, pool, 20_000_000_000, 0);
+        let victim = test_transaction(2, pool, 10_000_000_000, 0);
+        let back_run = test_transaction(1, pool, 10_000_000_000, 1);
+        let unrelated = test_transaction(3, Address::random(), 10_000_000_000, 0);
+        let transactions = vec![unrelated.clone(), front_run.clone(), victim.clone(), back_run.clone()];
+
+        let opportunities = MevDetector::new(DetectorConfig::default()).analyze_block(&transactions, 1);
+        assert!(opportunities.iter().any(|opp| opp.mev_type == MevType::Sandwich && opp.tx_hash == *victim.hash()));
+
+        assert_eq!(hashes(&NoReorder.order(transactions.clone(), &opportunities)), hashes(&transactions));
+
+        let ordered = hashes(&SandwichBreaker.order(transactions, &opportunities));
+        assert_eq!(ordered, hashes(&[unrelated, victim, front_run, back_run]));
+    }
+
+    #[test]
+    fn test_bundle_first_places_winning_bundle_on_top() {
+        let transactions: Vec<_> = (1..=4).map(|signer| test_transaction(signer, Address::random(), 1, 0)).collect();
+        let bundle = |transactions: &[&TransactionSigned], bid: u64| BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::from(bid),
+            target_block: 1,
+            transactions: transactions.iter().map(|tx| *tx.hash()).collect(),
+            searcher: Address::random(),
+            encrypted_payload: None,
+        };
+        let bundles = [
+            bundle(&[&transactions[1]], 5),
+            bundle(&[&transactions[3], &transactions[2]], 10),
+        ];
+
+        let ordered = BundleFirst::from_auction(&bundles).order(transactions.clone(), &[]);
+        assert_eq!(
+            hashes(&ordered),
+            hashes(&[transactions[3].clone(), transactions[2].clone(), transactions[0].clone(), transactions[1].clone()])
+        );
+    }
 }
@@ -0,0 +1,127 @@
+//! Balance checks for ANDE precompile transfers during parallel execution
+//!
+//! The ANDE precompile debits the `from` account through the EVM journal, which
+//! only sees the parent state and the versioned writes of lower-indexed
+//! transactions. Lazy updates recorded by those transactions are not part of that
+//! view, so a transfer could be admitted against a balance that is already spent
+//! and the shortfall would only show up once lazy updates are merged at the end of
+//! the block. The guard checks every transfer against the effective balance in the
+//! multi-version memory and reverts the call when it can't be covered. The
+//! caps of the precompile are enforced first, by the inspector the guard wraps.
+//!
+//! The guard also raises a flag once the outermost call frame returned, so the
+//! state view can tell reads made by the transaction from the load of the
+//! beneficiary that credits its fee.
+
+use super::{executor::TxIdx, mv_memory::MvMemory};
+use crate::evm_config::{AndePrecompileCall, AndePrecompileInspector, ANDE_PRECOMPILE_ADDRESS};
+use revm::{
+    context_interface::{Block, ContextTr, JournalTr},
+    inspector::Inspector,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+/// Inspector rejecting ANDE precompile transfers that exceed the sender's
+/// effective balance, on top of the block caps enforced by an
+/// [`AndePrecompileInspector`]
+#[derive(Debug, Clone)]
+pub struct PrecompileBalanceGuard {
+    /// Multi-version memory shared by all workers
+    mv_memory: Arc<Mutex<MvMemory>>,
+    /// Index of the transaction being executed
+    tx_idx: TxIdx,
+    /// Inspector enforcing the caps of the precompile, consulted first
+    caps: AndePrecompileInspector,
+    /// Number of call frames entered and not yet returned
+    depth: usize,
+    /// Raised once the outermost call frame returned
+    returned: Arc<AtomicBool>,
+}
+
+impl PrecompileBalanceGuard {
+    /// Create a guard for the transaction at `tx_idx`, enforcing the caps of `caps` as well
+    pub fn new(mv_memory: Arc<Mutex<MvMemory>>, tx_idx: TxIdx, caps: AndePrecompileInspector) -> Self {
+        Self { mv_memory, tx_idx, caps, depth: 0, returned: Arc::new(AtomicBool::new(false)) }
+    }
+
+    /// Inspector enforcing the caps of the precompile
+    pub fn caps(&self) -> &AndePrecompileInspector {
+        &self.caps
+    }
+
+    /// Flag raised once the outermost call frame of the transaction returned
+    pub fn returned(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.returned)
+    }
+
+    /// Leave a call frame, raising the flag at the outermost one
+    fn exit_frame(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            self.returned.store(true, Ordering::Release);
+        }
+    }
+
+    /// Revert a transfer that the effective balance of its sender doesn't cover
+    fn check_balance<CTX: ContextTr>(&self, context: &mut CTX, inputs: &CallInputs) -> Option<CallOutcome> {
+        if inputs.target_address != ANDE_PRECOMPILE_ADDRESS {
+            return None;
+        }
+
+        // Malformed input is rejected by the precompile itself
+        let calldata = inputs.input.bytes(context);
+        let Ok(AndePrecompileCall::Transfer { from, value, .. }) = AndePrecompileCall::decode(&calldata) else {
+            return None;
+        };
+        if value.is_zero() {
+            return None;
+        }
+
+        // Database errors are surfaced by the transfer in the precompile
+        let balance = context.journal_mut().load_account(from).ok()?.data.info.balance;
+        // The state view already folds the lazy fee credits into the beneficiary's balance
+        let available = if from == context.block().beneficiary() {
+            balance
+        } else {
+            self.mv_memory.lock().unwrap().effective_balance(from, balance, self.tx_idx)
+        };
+        if value <= available {
+            return None;
+        }
+
+        Some(AndePrecompileInspector::revert_outcome(
+            &format!("Insufficient balance: {from} has {available}, transfer needs {value}"),
+            inputs,
+        ))
+    }
+}
+
+impl<CTX> Inspector<CTX> for PrecompileBalanceGuard
+where
+    CTX: ContextTr,
+{
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.depth += 1;
+        // A transfer the caps reject is never checked against the balance
+        self.caps.call(context, inputs).or_else(|| self.check_balance(context, inputs))
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.caps.call_end(context, inputs, outcome);
+        self.exit_frame();
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.depth += 1;
+        self.caps.create(context, inputs)
+    }
+
+    fn create_end(&mut self, context: &mut CTX, inputs: &CreateInputs, outcome: &mut CreateOutcome) {
+        self.caps.create_end(context, inputs, outcome);
+        self.exit_frame();
+    }
+}
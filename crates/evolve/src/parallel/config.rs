@@ -2,9 +2,43 @@
 //!
 //! Configuration options for parallel transaction execution in AndeChain.
 
-use std::num::NonZeroUsize;
+use std::{env::VarError, fmt::Display, num::NonZeroUsize, path::PathBuf, str::FromStr, time::Duration};
 use serde::{Deserialize, Serialize};
 
+/// What to do when a transaction fails to execute successfully
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePolicy {
+    /// Record the failure and keep executing the remaining transactions, as
+    /// wanted when building a payload
+    #[default]
+    Skip,
+    /// Stop at the first failed transaction, as wanted when validating a block
+    Abort,
+}
+
+impl FailurePolicy {
+    /// Name used in environment variables
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Abort => "abort",
+        }
+    }
+}
+
+impl FromStr for FailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "abort" => Ok(Self::Abort),
+            other => Err(format!("Unknown failure policy: {other}")),
+        }
+    }
+}
+
 /// Configuration for parallel execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParallelConfig {
@@ -24,6 +58,23 @@ pub struct ParallelConfig {
     pub max_dependency_depth: usize,
     /// Enable performance monitoring
     pub enable_monitoring: bool,
+    /// Pick the worker count per block from recent conflict ratios and execution times
+    pub adaptive: bool,
+    /// Number of recent blocks the adaptive mode takes into account
+    pub adaptive_window: usize,
+    /// Recent conflict ratio above which the adaptive mode executes sequentially
+    pub adaptive_conflict_threshold: f64,
+    /// Estimated fraction of a block's transactions in its largest group of
+    /// dependent ones above which the block is executed sequentially, see
+    /// [`largest_dependent_group_fraction`](crate::parallel::largest_dependent_group_fraction)
+    pub max_dependent_fraction: f64,
+    /// Abort parallel execution of a block that takes longer than this
+    pub execution_timeout: Option<Duration>,
+    /// Whether a failed transaction aborts the whole batch
+    pub on_failure: FailurePolicy,
+    /// Directory the trace of every block executed in parallel is written to,
+    /// see [`RecordingObserver`](crate::parallel::RecordingObserver)
+    pub trace_dir: Option<PathBuf>,
 }
 
 impl Default for ParallelConfig {
@@ -37,6 +88,13 @@ impl Default for ParallelConfig {
             enable_advanced_dependency_analysis: false, // Phase 1: keep simple
             max_dependency_depth: 10,
             enable_monitoring: true,
+            adaptive: false,
+            adaptive_window: 16,
+            adaptive_conflict_threshold: 0.5,
+            max_dependent_fraction: 0.6,
+            execution_timeout: None,
+            on_failure: FailurePolicy::Skip,
+            trace_dir: None,
         }
     }
 }
@@ -58,6 +116,13 @@ impl ParallelConfig {
             enable_advanced_dependency_analysis: true,
             max_dependency_depth: 20,
             enable_monitoring: true,
+            adaptive: false,
+            adaptive_window: 16,
+            adaptive_conflict_threshold: 0.5,
+            max_dependent_fraction: 0.6,
+            execution_timeout: None,
+            on_failure: FailurePolicy::Skip,
+            trace_dir: None,
         }
     }
 
@@ -72,6 +137,13 @@ impl ParallelConfig {
             enable_advanced_dependency_analysis: false,
             max_dependency_depth: 5,
             enable_monitoring: true,
+            adaptive: false,
+            adaptive_window: 8,
+            adaptive_conflict_threshold: 0.5,
+            max_dependent_fraction: 0.6,
+            execution_timeout: None,
+            on_failure: FailurePolicy::Skip,
+            trace_dir: None,
         }
     }
 
@@ -86,6 +158,13 @@ impl ParallelConfig {
             enable_advanced_dependency_analysis: false,
             max_dependency_depth: 3,
             enable_monitoring: false,
+            adaptive: false,
+            adaptive_window: 4,
+            adaptive_conflict_threshold: 0.5,
+            max_dependent_fraction: 0.6,
+            execution_timeout: None,
+            on_failure: FailurePolicy::Skip,
+            trace_dir: None,
         }
     }
 
@@ -100,6 +179,13 @@ impl ParallelConfig {
             enable_advanced_dependency_analysis: false,
             max_dependency_depth: 1,
             enable_monitoring: false,
+            adaptive: false,
+            adaptive_window: 1,
+            adaptive_conflict_threshold: 0.5,
+            max_dependent_fraction: 0.6,
+            execution_timeout: None,
+            on_failure: FailurePolicy::Skip,
+            trace_dir: None,
         }
     }
 
@@ -121,6 +207,26 @@ impl ParallelConfig {
             return Err("Max dependency depth must be at least 1".to_string());
         }
 
+        if self.adaptive_window == 0 {
+            return Err("Adaptive window must be at least 1".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.adaptive_conflict_threshold) {
+            return Err("Adaptive conflict threshold must be between 0 and 1".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.max_dependent_fraction) {
+            return Err("Max dependent fraction must be between 0 and 1".to_string());
+        }
+
+        if self.execution_timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return Err("Execution timeout must be greater than zero".to_string());
+        }
+
+        if self.trace_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+            return Err("Trace directory must not be empty".to_string());
+        }
+
         Ok(())
     }
 
@@ -135,61 +241,56 @@ impl ParallelConfig {
             ("ANDE_PARALLEL_ENABLE_ADVANCED_ANALYSIS", self.enable_advanced_dependency_analysis.to_string()),
             ("ANDE_PARALLEL_MAX_DEPENDENCY_DEPTH", self.max_dependency_depth.to_string()),
             ("ANDE_PARALLEL_ENABLE_MONITORING", self.enable_monitoring.to_string()),
+            ("ANDE_PARALLEL_ADAPTIVE", self.adaptive.to_string()),
+            ("ANDE_PARALLEL_ADAPTIVE_WINDOW", self.adaptive_window.to_string()),
+            ("ANDE_PARALLEL_ADAPTIVE_CONFLICT_THRESHOLD", self.adaptive_conflict_threshold.to_string()),
+            ("ANDE_PARALLEL_MAX_DEPENDENT_FRACTION", self.max_dependent_fraction.to_string()),
+            (
+                "ANDE_PARALLEL_EXECUTION_TIMEOUT_MS",
+                self.execution_timeout.map(|timeout| timeout.as_millis().to_string()).unwrap_or_default(),
+            ),
+            ("ANDE_PARALLEL_ON_FAILURE", self.on_failure.as_str().to_string()),
+            (
+                "ANDE_PARALLEL_TRACE_DIR",
+                self.trace_dir.as_ref().map(|dir| dir.display().to_string()).unwrap_or_default(),
+            ),
         ]
     }
 
     /// Create configuration from environment variables
+    ///
+    /// Variables that aren't set take their default value, while set variables
+    /// that fail to parse are reported with their name and value.
     pub fn from_env() -> Result<Self, String> {
-        let concurrency_level = std::env::var("ANDE_PARALLEL_CONCURRENCY_LEVEL")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok())
-            .and_then(|n| NonZeroUsize::new(n))
-            .unwrap_or_else(|| NonZeroUsize::new(8).unwrap());
-
-        let enable_lazy_updates = std::env::var("ANDE_PARALLEL_ENABLE_LAZY_UPDATES")
-            .ok()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(true);
-
-        let max_retries = std::env::var("ANDE_PARALLEL_MAX_RETRIES")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(3);
-
-        let min_transactions_for_parallel = std::env::var("ANDE_PARALLEL_MIN_TRANSACTIONS")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(4);
-
-        let force_sequential = std::env::var("ANDE_PARALLEL_FORCE_SEQUENTIAL")
-            .ok()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(false);
-
-        let enable_advanced_dependency_analysis = std::env::var("ANDE_PARALLEL_ENABLE_ADVANCED_ANALYSIS")
-            .ok()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(false);
-
-        let max_dependency_depth = std::env::var("ANDE_PARALLEL_MAX_DEPENDENCY_DEPTH")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(10);
-
-        let enable_monitoring = std::env::var("ANDE_PARALLEL_ENABLE_MONITORING")
-            .ok()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(true);
+        let defaults = Self::default();
 
         let config = Self {
-            concurrency_level,
-            enable_lazy_updates,
-            max_retries,
-            min_transactions_for_parallel,
-            force_sequential,
-            enable_advanced_dependency_analysis,
-            max_dependency_depth,
-            enable_monitoring,
+            concurrency_level: env_var("ANDE_PARALLEL_CONCURRENCY_LEVEL")?.unwrap_or(defaults.concurrency_level),
+            enable_lazy_updates: env_var("ANDE_PARALLEL_ENABLE_LAZY_UPDATES")?
+                .unwrap_or(defaults.enable_lazy_updates),
+            max_retries: env_var("ANDE_PARALLEL_MAX_RETRIES")?.unwrap_or(defaults.max_retries),
+            min_transactions_for_parallel: env_var("ANDE_PARALLEL_MIN_TRANSACTIONS")?
+                .unwrap_or(defaults.min_transactions_for_parallel),
+            force_sequential: env_var("ANDE_PARALLEL_FORCE_SEQUENTIAL")?.unwrap_or(defaults.force_sequential),
+            enable_advanced_dependency_analysis: env_var("ANDE_PARALLEL_ENABLE_ADVANCED_ANALYSIS")?
+                .unwrap_or(defaults.enable_advanced_dependency_analysis),
+            max_dependency_depth: env_var("ANDE_PARALLEL_MAX_DEPENDENCY_DEPTH")?
+                .unwrap_or(defaults.max_dependency_depth),
+            enable_monitoring: env_var("ANDE_PARALLEL_ENABLE_MONITORING")?.unwrap_or(defaults.enable_monitoring),
+            adaptive: env_var("ANDE_PARALLEL_ADAPTIVE")?.unwrap_or(defaults.adaptive),
+            adaptive_window: env_var("ANDE_PARALLEL_ADAPTIVE_WINDOW")?.unwrap_or(defaults.adaptive_window),
+            adaptive_conflict_threshold: env_var("ANDE_PARALLEL_ADAPTIVE_CONFLICT_THRESHOLD")?
+                .unwrap_or(defaults.adaptive_conflict_threshold),
+            max_dependent_fraction: env_var("ANDE_PARALLEL_MAX_DEPENDENT_FRACTION")?
+                .unwrap_or(defaults.max_dependent_fraction),
+            // An empty value disables the timeout, matching `to_env_format`
+            execution_timeout: match std::env::var("ANDE_PARALLEL_EXECUTION_TIMEOUT_MS") {
+                Ok(value) if value.is_empty() => None,
+                _ => env_var("ANDE_PARALLEL_EXECUTION_TIMEOUT_MS")?.map(Duration::from_millis),
+            },
+            on_failure: env_var("ANDE_PARALLEL_ON_FAILURE")?.unwrap_or(defaults.on_failure),
+            // An empty value disables tracing, matching `to_env_format`
+            trace_dir: env_var("ANDE_PARALLEL_TRACE_DIR")?.filter(|dir: &PathBuf| !dir.as_os_str().is_empty()),
         };
 
         config.validate()?;
@@ -208,6 +309,19 @@ impl ParallelConfig {
     }
 }
 
+/// Parse the environment variable `name`, or `None` if it isn't set
+fn env_var<T>(name: &str) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match std::env::var(name) {
+        Ok(value) => value.parse().map(Some).map_err(|e| format!("Invalid value {value:?} for {name}: {e}")),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(value)) => Err(format!("Invalid value {value:?} for {name}: not valid unicode")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +334,16 @@ mod tests {
         assert_eq!(config.max_retries, 3);
         assert_eq!(config.min_transactions_for_parallel, 4);
         assert!(!config.force_sequential);
+        assert_eq!(config.on_failure, FailurePolicy::Skip);
+    }
+
+    #[test]
+    fn test_failure_policy_parsing() {
+        for policy in [FailurePolicy::Skip, FailurePolicy::Abort] {
+            assert_eq!(policy.as_str().parse::<FailurePolicy>(), Ok(policy));
+        }
+        assert_eq!("ABORT".parse::<FailurePolicy>(), Ok(FailurePolicy::Abort));
+        assert!("retry".parse::<FailurePolicy>().is_err());
     }
 
     #[test]
@@ -258,6 +382,136 @@ mod tests {
         let mut invalid_config2 = ParallelConfig::default();
         invalid_config2.max_retries = 0;
         assert!(invalid_config2.validate().is_err());
+
+        // Invalid adaptive settings
+        let mut invalid_config3 = ParallelConfig::default();
+        invalid_config3.adaptive_conflict_threshold = 1.5;
+        assert!(invalid_config3.validate().is_err());
+
+        let mut invalid_config4 = ParallelConfig::default();
+        invalid_config4.adaptive_window = 0;
+        assert!(invalid_config4.validate().is_err());
+    }
+
+    /// Serializes tests that modify the process environment
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Replaces every `ANDE_PARALLEL_*` variable for the lifetime of the guard
+    struct EnvGuard {
+        previous: Vec<(&'static str, Option<String>)>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, &str)]) -> Self {
+            let lock = ENV_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            let previous: Vec<_> = ParallelConfig::default()
+                .to_env_format()
+                .into_iter()
+                .map(|(name, _)| (name, std::env::var(name).ok()))
+                .collect();
+
+            // SAFETY: tests touching the environment hold `ENV_LOCK`
+            unsafe {
+                for (name, _) in &previous {
+                    std::env::remove_var(name);
+                }
+                for (name, value) in vars {
+                    std::env::set_var(name, value);
+                }
+            }
+            Self { previous, _lock: lock }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            // SAFETY: the guard still holds `ENV_LOCK`
+            unsafe {
+                for (name, value) in &self.previous {
+                    match value {
+                        Some(value) => std::env::set_var(name, value),
+                        None => std::env::remove_var(name),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_env_defaults_when_unset() {
+        let _env = EnvGuard::set(&[]);
+        let config = ParallelConfig::from_env().unwrap();
+        assert_eq!(config.to_env_format(), ParallelConfig::default().to_env_format());
+    }
+
+    #[test]
+    fn test_from_env_rejects_invalid_values() {
+        let cases = [
+            (
+                "ANDE_PARALLEL_CONCURRENCY_LEVEL",
+                "eight",
+                r#"Invalid value "eight" for ANDE_PARALLEL_CONCURRENCY_LEVEL: invalid digit found in string"#,
+            ),
+            (
+                "ANDE_PARALLEL_CONCURRENCY_LEVEL",
+                "0",
+                r#"Invalid value "0" for ANDE_PARALLEL_CONCURRENCY_LEVEL: number would be zero for non-zero type"#,
+            ),
+            (
+                "ANDE_PARALLEL_FORCE_SEQUENTIAL",
+                "yes",
+                r#"Invalid value "yes" for ANDE_PARALLEL_FORCE_SEQUENTIAL: provided string was not `true` or `false`"#,
+            ),
+            (
+                "ANDE_PARALLEL_ADAPTIVE_CONFLICT_THRESHOLD",
+                "half",
+                r#"Invalid value "half" for ANDE_PARALLEL_ADAPTIVE_CONFLICT_THRESHOLD: invalid float literal"#,
+            ),
+            (
+                "ANDE_PARALLEL_EXECUTION_TIMEOUT_MS",
+                "soon",
+                r#"Invalid value "soon" for ANDE_PARALLEL_EXECUTION_TIMEOUT_MS: invalid digit found in string"#,
+            ),
+            (
+                "ANDE_PARALLEL_ON_FAILURE",
+                "retry",
+                r#"Invalid value "retry" for ANDE_PARALLEL_ON_FAILURE: Unknown failure policy: retry"#,
+            ),
+            // Values that parse are still validated
+            ("ANDE_PARALLEL_MAX_RETRIES", "0", "Max retries must be at least 1"),
+            ("ANDE_PARALLEL_MAX_DEPENDENT_FRACTION", "1.5", "Max dependent fraction must be between 0 and 1"),
+        ];
+
+        for (name, value, expected) in cases {
+            let _env = EnvGuard::set(&[(name, value)]);
+            assert_eq!(ParallelConfig::from_env().unwrap_err(), expected, "{name}={value}");
+        }
+    }
+
+    #[test]
+    fn test_from_env_round_trips_env_format() {
+        let configs = [
+            ParallelConfig::high_throughput(),
+            ParallelConfig {
+                adaptive: true,
+                adaptive_conflict_threshold: 0.25,
+                max_dependent_fraction: 0.9,
+                execution_timeout: Some(Duration::from_millis(750)),
+                on_failure: FailurePolicy::Abort,
+                trace_dir: Some(PathBuf::from("/var/lib/ev-reth/parallel-traces")),
+                ..ParallelConfig::low_latency()
+            },
+        ];
+
+        for config in configs {
+            let vars = config.to_env_format();
+            let vars: Vec<(&'static str, &str)> = vars.iter().map(|(name, value)| (*name, value.as_str())).collect();
+            let _env = EnvGuard::set(&vars);
+
+            let parsed = ParallelConfig::from_env().unwrap();
+            assert_eq!(parsed.to_env_format(), config.to_env_format());
+        }
     }
 
     #[test]
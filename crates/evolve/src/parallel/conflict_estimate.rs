@@ -0,0 +1,106 @@
+//! Conflict Estimate
+//!
+//! Cheap pre-pass over the transactions of a block estimating how much of it
+//! would serialize under parallel execution. Transactions sharing a sender or
+//! a recipient, directly or through other transactions, are assumed to depend
+//! on each other; a block dominated by one such group, e.g. swaps against a
+//! single AMM pool, runs slower in parallel than sequentially.
+
+use alloy_primitives::Address;
+use std::collections::HashMap;
+
+/// Fraction of the transactions in the largest group of transactions linked
+/// by their sender or recipient, given as `(sender, to)` pairs
+///
+/// 0 for an empty block, 1 when every transaction is linked to the others.
+pub fn largest_dependent_group_fraction<I>(transactions: I) -> f64
+where
+    I: IntoIterator<Item = (Address, Option<Address>)>,
+{
+    let mut groups = AddressGroups::default();
+    let senders: Vec<usize> = transactions
+        .into_iter()
+        .map(|(sender, to)| {
+            let sender = groups.node(sender);
+            if let Some(to) = to {
+                let to = groups.node(to);
+                groups.union(sender, to);
+            }
+            sender
+        })
+        .collect();
+    if senders.is_empty() {
+        return 0.0;
+    }
+
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for sender in &senders {
+        *sizes.entry(groups.root(*sender)).or_default() += 1;
+    }
+    let largest = sizes.into_values().max().unwrap_or_default();
+    largest as f64 / senders.len() as f64
+}
+
+/// Union-find over addresses
+#[derive(Debug, Default)]
+struct AddressGroups {
+    /// Node of each address seen
+    nodes: HashMap<Address, usize>,
+    /// Parent of each node, itself for the root of a group
+    parents: Vec<usize>,
+}
+
+impl AddressGroups {
+    /// Node of `address`, in a group of its own when first seen
+    fn node(&mut self, address: Address) -> usize {
+        *self.nodes.entry(address).or_insert_with(|| {
+            self.parents.push(self.parents.len());
+            self.parents.len() - 1
+        })
+    }
+
+    /// Root of the group of `node`, halving the path on the way
+    fn root(&mut self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            self.parents[node] = self.parents[self.parents[node]];
+            node = self.parents[node];
+        }
+        node
+    }
+
+    /// Merge the groups of `a` and `b`
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.root(a), self.root(b));
+        if a != b {
+            self.parents[b] = a;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_recipient_links_every_transaction() {
+        let pool = Address::repeat_byte(0xaa);
+        let swaps = (0..100u8).map(|i| (Address::with_last_byte(i), Some(pool)));
+        assert_eq!(largest_dependent_group_fraction(swaps), 1.0);
+    }
+
+    #[test]
+    fn test_spread_transactions_are_independent() {
+        let transfers = (0..100u8).map(|i| (Address::with_last_byte(i), Some(Address::repeat_byte(i))));
+        assert_eq!(largest_dependent_group_fraction(transfers), 0.01);
+        assert_eq!(largest_dependent_group_fraction(std::iter::empty()), 0.0);
+    }
+
+    #[test]
+    fn test_groups_are_linked_transitively() {
+        let [a, b, c, d, e] = [1, 2, 3, 4, 5].map(Address::with_last_byte);
+        // a -> b, c -> b and c -> d form one group, d's deployment joins it
+        // through its sender, the other deployment stays apart
+        let transactions = [(a, Some(b)), (c, Some(b)), (c, Some(d)), (d, None), (e, None)];
+        assert_eq!(largest_dependent_group_fraction(transactions), 0.8);
+    }
+}
@@ -3,15 +3,23 @@
 //! This module provides parallel transaction execution capabilities for AndeChain,
 //! enabling significant throughput improvements while maintaining ANDE Token Duality.
 
+pub mod balance_guard;
+pub mod conflict_estimate;
 pub mod executor;
-pub mod scheduler;
 pub mod mv_memory;
 pub mod config;
+pub mod observer;
+pub mod pool;
+pub mod state_view;
 
 pub use executor::{
-    ParallelExecutor, ParallelExecutionResult,
-    ParallelTask, TxVersion, TxStatus, AccountStateChange, TxIdx
+    AccountDiff, ConcurrencyDecision, ParallelExecutor, ParallelExecutionMetrics, ParallelExecutionOutput, ParallelExecutionResult,
+    ParallelPayloadError, ParallelScheduler, ParallelTask, TxVersion, TxStatus, AccountStateChange, TxIdx, intrinsic_gas,
 };
-pub use config::ParallelConfig;
-pub use scheduler::ParallelScheduler;
-pub use mv_memory::MvMemory;
\ No newline at end of file
+pub use config::{FailurePolicy, ParallelConfig};
+pub use conflict_estimate::largest_dependent_group_fraction;
+pub use observer::{BlockTrace, ExecutionObserver, RecordingObserver, TraceEvent};
+pub use mv_memory::MvMemory;
+pub use balance_guard::PrecompileBalanceGuard;
+pub use pool::WorkerPool;
+pub use state_view::{ParallelStateView, StateViewError};
\ No newline at end of file
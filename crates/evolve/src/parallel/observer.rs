@@ -0,0 +1,219 @@
+//! Parallel Execution Observer
+//!
+//! An [`ExecutionObserver`] attached to a [`ParallelExecutor`](super::ParallelExecutor)
+//! is told about every execution, read, write, conflict and retry of the blocks
+//! it executes in parallel, for tools that inspect how a block was scheduled.
+//! Without an observer the executor only checks that none is set.
+//!
+//! [`RecordingObserver`] keeps a trace of each block and writes it as JSON, one
+//! file per block. It is attached when
+//! [`ParallelConfig::trace_dir`](super::ParallelConfig::trace_dir) is set.
+
+use super::{
+    executor::{StateLocation, TxVersion},
+    mv_memory::ReadOrigin,
+};
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Debug,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+use tracing::{debug, warn};
+
+/// Hooks called by the parallel executor while it executes a block
+///
+/// Hooks are called from the worker threads, so events of different
+/// transactions interleave. Every hook does nothing by default.
+pub trait ExecutionObserver: Debug + Send + Sync {
+    /// A block of `transactions` is about to be executed in parallel
+    fn on_block_start(&self, _block_number: u64, _transactions: usize) {}
+
+    /// An incarnation of a transaction starts executing
+    fn on_tx_start(&self, _version: TxVersion) {}
+
+    /// An execution read `location`, served from `origin`
+    fn on_read(&self, _version: TxVersion, _location: StateLocation, _origin: ReadOrigin) {}
+
+    /// An execution wrote `location`
+    fn on_write(&self, _version: TxVersion, _location: StateLocation) {}
+
+    /// An execution ran to completion; executions rejected before running or
+    /// suspended on an estimate don't complete
+    fn on_tx_complete(&self, _version: TxVersion, _gas_used: u64, _success: bool) {}
+
+    /// Validation found that the execution read stale data
+    fn on_conflict(&self, _version: TxVersion) {}
+
+    /// The transaction is scheduled again as `version`, its `retry_count`-th retry
+    fn on_retry(&self, _version: TxVersion, _retry_count: usize) {}
+
+    /// Every worker stopped executing the block
+    fn on_block_complete(&self, _block_number: u64) {}
+}
+
+/// Event of a [`BlockTrace`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TraceEvent {
+    /// See [`ExecutionObserver::on_tx_start`]
+    TxStart { version: TxVersion },
+    /// See [`ExecutionObserver::on_read`]
+    Read { version: TxVersion, address: Address, slot: Option<U256>, origin: ReadOrigin },
+    /// See [`ExecutionObserver::on_write`]
+    Write { version: TxVersion, address: Address, slot: Option<U256> },
+    /// See [`ExecutionObserver::on_tx_complete`]
+    TxComplete { version: TxVersion, gas_used: u64, success: bool },
+    /// See [`ExecutionObserver::on_conflict`]
+    Conflict { version: TxVersion },
+    /// See [`ExecutionObserver::on_retry`]
+    Retry { version: TxVersion, retry_count: usize },
+}
+
+/// Events of one block executed in parallel, in the order they were observed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTrace {
+    /// Number of the block
+    pub block_number: u64,
+    /// Number of transactions in the block
+    pub transactions: usize,
+    /// Observed events
+    pub events: Vec<TraceEvent>,
+}
+
+impl BlockTrace {
+    /// Read the trace written at `path`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+/// Observer writing the trace of every block to `block-{number}.json` in a directory
+///
+/// A block built more than once, as payloads are, leaves the trace of its
+/// latest execution. Events of blocks executing at the same time on one
+/// executor end up in the same trace. Failing to write a trace is logged and
+/// doesn't affect execution.
+#[derive(Debug)]
+pub struct RecordingObserver {
+    /// Directory the traces are written to, created on the first write
+    dir: PathBuf,
+    /// Trace of the block being executed
+    trace: Mutex<Option<BlockTrace>>,
+}
+
+impl RecordingObserver {
+    /// Create an observer writing traces to `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), trace: Mutex::new(None) }
+    }
+
+    /// Directory the traces are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the trace of block `block_number`
+    pub fn trace_path(&self, block_number: u64) -> PathBuf {
+        self.dir.join(format!("block-{block_number}.json"))
+    }
+
+    fn record(&self, event: TraceEvent) {
+        if let Some(trace) = self.trace.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            trace.events.push(event);
+        }
+    }
+
+    fn write(&self, trace: &BlockTrace) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.trace_path(trace.block_number);
+        fs::write(&path, serde_json::to_vec_pretty(trace)?)?;
+        Ok(path)
+    }
+}
+
+impl ExecutionObserver for RecordingObserver {
+    fn on_block_start(&self, block_number: u64, transactions: usize) {
+        *self.trace.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(BlockTrace { block_number, transactions, events: Vec::new() });
+    }
+
+    fn on_tx_start(&self, version: TxVersion) {
+        self.record(TraceEvent::TxStart { version });
+    }
+
+    fn on_read(&self, version: TxVersion, (address, slot): StateLocation, origin: ReadOrigin) {
+        self.record(TraceEvent::Read { version, address, slot, origin });
+    }
+
+    fn on_write(&self, version: TxVersion, (address, slot): StateLocation) {
+        self.record(TraceEvent::Write { version, address, slot });
+    }
+
+    fn on_tx_complete(&self, version: TxVersion, gas_used: u64, success: bool) {
+        self.record(TraceEvent::TxComplete { version, gas_used, success });
+    }
+
+    fn on_conflict(&self, version: TxVersion) {
+        self.record(TraceEvent::Conflict { version });
+    }
+
+    fn on_retry(&self, version: TxVersion, retry_count: usize) {
+        self.record(TraceEvent::Retry { version, retry_count });
+    }
+
+    fn on_block_complete(&self, block_number: u64) {
+        let trace = self.trace.lock().unwrap_or_else(PoisonError::into_inner).take();
+        let Some(trace) = trace.filter(|trace| trace.block_number == block_number) else {
+            return;
+        };
+        match self.write(&trace) {
+            Ok(path) => debug!(block_number, events = trace.events.len(), path = %path.display(), "Wrote block trace"),
+            Err(error) => warn!(block_number, dir = %self.dir.display(), %error, "Failed to write block trace"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_observer_writes_one_trace_per_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let observer = RecordingObserver::new(dir.path().join("traces"));
+        let version = TxVersion { tx_idx: 0, tx_incarnation: 0 };
+        let location = (Address::repeat_byte(1), Some(U256::from(2)));
+
+        // Events outside a block are dropped
+        observer.on_tx_start(version);
+
+        observer.on_block_start(7, 1);
+        observer.on_tx_start(version);
+        observer.on_read(version, location, ReadOrigin::Base);
+        observer.on_write(version, location);
+        observer.on_tx_complete(version, 21_000, true);
+        observer.on_block_complete(7);
+
+        let trace = BlockTrace::load(&observer.trace_path(7)).unwrap();
+        assert_eq!(trace.block_number, 7);
+        assert_eq!(trace.transactions, 1);
+        assert_eq!(
+            trace.events,
+            [
+                TraceEvent::TxStart { version },
+                TraceEvent::Read { version, address: location.0, slot: location.1, origin: ReadOrigin::Base },
+                TraceEvent::Write { version, address: location.0, slot: location.1 },
+                TraceEvent::TxComplete { version, gas_used: 21_000, success: true },
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::from_slice(&fs::read(observer.trace_path(7)).unwrap()).unwrap();
+        let tx_start = serde_json::json!({ "event": "txStart", "version": { "txIdx": 0, "txIncarnation": 0 } });
+        assert_eq!(json["events"][0], tx_start);
+        assert_eq!(json["events"][1]["origin"], "base");
+    }
+}
@@ -0,0 +1,165 @@
+//! Long-lived worker threads for parallel execution
+//!
+//! Spawning a fresh set of OS threads for every block adds latency at short
+//! block times, so the executor keeps one pool for its whole lifetime. Each
+//! block submits its jobs through a scope that returns only once every job has
+//! finished, which lets jobs borrow per-block state (transactions, parent state,
+//! multi-version memory and scheduler) without any of it outliving the block.
+
+use crate::rpc::health::WorkerPoolHealthSource;
+use rayon::{Scope, ThreadPool, ThreadPoolBuilder};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+};
+
+/// Fixed-size pool of worker threads reused across blocks
+pub struct WorkerPool {
+    /// Underlying thread pool, dropped first on shutdown
+    pool: Option<ThreadPool>,
+    /// Handles of the pool threads, joined on shutdown
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// Number of threads spawned over the lifetime of the pool
+    spawned: Arc<AtomicUsize>,
+    /// Number of threads currently running
+    alive: Arc<AtomicUsize>,
+}
+
+impl fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("threads", &self.threads())
+            .field("spawned", &self.spawned_threads())
+            .finish_non_exhaustive()
+    }
+}
+
+impl WorkerPool {
+    /// Spawn a pool of `threads` workers
+    ///
+    /// # Panics
+    /// If the operating system refuses to spawn the worker threads.
+    pub fn new(threads: usize) -> Self {
+        let handles = Arc::new(Mutex::new(Vec::with_capacity(threads)));
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let alive = Arc::new(AtomicUsize::new(0));
+
+        let pool = {
+            let (handles, spawned, alive) = (Arc::clone(&handles), Arc::clone(&spawned), Arc::clone(&alive));
+            ThreadPoolBuilder::new()
+                .num_threads(threads.max(1))
+                .thread_name(|index| format!("ande-parallel-{index}"))
+                .spawn_handler(move |thread| {
+                    let mut builder = std::thread::Builder::new();
+                    if let Some(name) = thread.name() {
+                        builder = builder.name(name.to_owned());
+                    }
+                    if let Some(stack_size) = thread.stack_size() {
+                        builder = builder.stack_size(stack_size);
+                    }
+
+                    let alive = Arc::clone(&alive);
+                    alive.fetch_add(1, Ordering::SeqCst);
+                    let handle = builder.spawn(move || {
+                        thread.run();
+                        alive.fetch_sub(1, Ordering::SeqCst);
+                    })?;
+                    spawned.fetch_add(1, Ordering::SeqCst);
+                    handles.lock().unwrap().push(handle);
+                    Ok(())
+                })
+                .build()
+                .expect("failed to spawn parallel execution workers")
+        };
+
+        Self { pool: Some(pool), handles, spawned, alive }
+    }
+
+    /// Number of worker threads in the pool
+    pub fn threads(&self) -> usize {
+        self.pool.as_ref().map_or(0, ThreadPool::current_num_threads)
+    }
+
+    /// Number of threads spawned since the pool was created
+    pub fn spawned_threads(&self) -> usize {
+        self.spawned.load(Ordering::SeqCst)
+    }
+
+    /// Number of worker threads still running
+    pub fn alive_threads(&self) -> usize {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Run `op` on the calling thread, with jobs it spawns executed by the pool
+    ///
+    /// Returns once `op` and every job it spawned have finished. A panic in a job
+    /// is propagated to the caller after all other jobs have completed.
+    pub fn scope<'scope, R>(&self, op: impl FnOnce(&Scope<'scope>) -> R) -> R {
+        self.pool.as_ref().expect("pool is only taken on drop").in_place_scope(op)
+    }
+
+    /// Run `op` inside the pool, so that rayon parallel iterators use its threads
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        self.pool.as_ref().expect("pool is only taken on drop").install(op)
+    }
+}
+
+impl WorkerPoolHealthSource for WorkerPool {
+    fn threads(&self) -> usize {
+        WorkerPool::threads(self)
+    }
+
+    fn alive_threads(&self) -> usize {
+        WorkerPool::alive_threads(self)
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        // Dropping the pool tells the workers to exit once they are idle
+        drop(self.pool.take());
+        for handle in self.handles.lock().unwrap().drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_pool_reuses_threads_across_scopes() {
+        let pool = WorkerPool::new(4);
+        assert_eq!(pool.threads(), 4);
+        assert_eq!(pool.spawned_threads(), 4);
+
+        let completed = AtomicUsize::new(0);
+        for _ in 0..50 {
+            pool.scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|_| {
+                        completed.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+            });
+        }
+
+        assert_eq!(completed.load(Ordering::SeqCst), 200);
+        assert_eq!(pool.spawned_threads(), 4);
+    }
+
+    #[test]
+    fn test_worker_pool_joins_threads_on_drop() {
+        let pool = WorkerPool::new(3);
+        let alive = Arc::clone(&pool.alive);
+        pool.scope(|scope| scope.spawn(|_| {}));
+
+        drop(pool);
+        assert_eq!(alive.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,277 @@
+//! Per-transaction state view for parallel workers
+//!
+//! Each worker executes a transaction against a read-only view of the parent
+//! state. Account reads are resolved against the multi-version memory first so
+//! that a transaction observes the writes of lower-indexed transactions, and
+//! fall back to the base values seeded in the multi-version memory and then to
+//! the parent state otherwise. The first value observed for every account and
+//! storage slot is recorded so the executor can derive state diffs and the
+//! scheduler can validate the execution afterwards.
+//!
+//! A view can be set to block on estimates: a read that observes a value written
+//! by an aborted lower transaction then fails with [`StateViewError::Blocked`]
+//! instead of executing against data that is known to be stale.
+//!
+//! Accounts created by a lower transaction are served with their code from the
+//! multi-version memory, and accounts destroyed by one read as absent. Storage
+//! written before such an account was created or destroyed reads as zero.
+//!
+//! The block beneficiary can be marked as lazily updated, since fee credits are
+//! accumulated outside of the multi-version memory. Reads of its balance made
+//! by the transaction fold in the credits of lower transactions and are
+//! recorded along with them. The load that only credits the transaction's own
+//! fee, once its outermost call frame returned, is served from the base state
+//! and not recorded, so transactions don't all conflict on the beneficiary.
+
+use super::{
+    executor::{apply_balance_delta, StateLocation, TxIdx},
+    mv_memory::{MvMemory, MvMemoryValue, MvReadResult, ReadOrigin},
+};
+use alloy_primitives::{Address, Bytes, B256, I256, U256};
+use revm::{
+    database_interface::{DBErrorMarker, Database, DatabaseRef},
+    state::{AccountInfo, Bytecode},
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Error returned by reads through a [`ParallelStateView`]
+#[derive(Debug, thiserror::Error)]
+pub enum StateViewError<E> {
+    /// Reading the parent state failed
+    #[error(transparent)]
+    Database(E),
+    /// The read observed an estimate written by the given lower transaction
+    #[error("Read an estimate written by transaction {0}")]
+    Blocked(TxIdx),
+}
+
+impl<E> DBErrorMarker for StateViewError<E> {}
+
+/// Read-only state view used by a single transaction execution
+pub struct ParallelStateView<'a, DB> {
+    /// Parent state the block is built on top of
+    base: &'a DB,
+    /// Multi-version memory shared by all workers
+    mv_memory: &'a Arc<Mutex<MvMemory>>,
+    /// Index of the transaction this view executes
+    tx_idx: TxIdx,
+    /// First observed value of every account read by the transaction
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Version each account or storage read was served from
+    origins: HashMap<StateLocation, ReadOrigin>,
+    /// First observed value of every storage slot read by the transaction
+    storage: HashMap<(Address, U256), U256>,
+    /// Code of the accounts observed as created in the multi-version memory
+    created: HashMap<Address, Bytes>,
+    /// Lower transaction that last created or destroyed an account, if any
+    reset_by: HashMap<Address, TxIdx>,
+    /// Account whose balance is updated lazily, with the flag raised once the
+    /// transaction returned
+    lazy_account: Option<(Address, Arc<AtomicBool>)>,
+    /// Lazy balance change folded into the transaction's read of the lazy account
+    lazy_read: Option<I256>,
+    /// Whether reading an estimate fails with [`StateViewError::Blocked`]
+    block_on_estimates: bool,
+    /// Lower transaction whose estimate stopped the execution, if any
+    blocked_on: Option<TxIdx>,
+}
+
+impl<DB> fmt::Debug for ParallelStateView<'_, DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParallelStateView")
+            .field("tx_idx", &self.tx_idx)
+            .field("accounts", &self.accounts.len())
+            .field("storage", &self.storage.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, DB: DatabaseRef> ParallelStateView<'a, DB> {
+    /// Create a new view for the transaction at `tx_idx`
+    pub fn new(base: &'a DB, mv_memory: &'a Arc<Mutex<MvMemory>>, tx_idx: TxIdx) -> Self {
+        Self {
+            base,
+            mv_memory,
+            tx_idx,
+            accounts: HashMap::new(),
+            origins: HashMap::new(),
+            storage: HashMap::new(),
+            created: HashMap::new(),
+            reset_by: HashMap::new(),
+            lazy_account: None,
+            lazy_read: None,
+            block_on_estimates: false,
+            blocked_on: None,
+        }
+    }
+
+    /// Fold the lazy balance updates of `address` into the reads made before
+    /// `returned` is raised, and serve later reads from the base state
+    pub fn with_lazy_account(mut self, address: Address, returned: Arc<AtomicBool>) -> Self {
+        self.lazy_account = Some((address, returned));
+        self
+    }
+
+    /// Fail reads that observe an estimate instead of returning the estimated value
+    pub fn with_block_on_estimates(mut self, block_on_estimates: bool) -> Self {
+        self.block_on_estimates = block_on_estimates;
+        self
+    }
+
+    /// Lower transaction whose estimate a read was blocked on
+    ///
+    /// Set even if the EVM swallowed the error, so an execution that hit an
+    /// estimate is never mistaken for a complete one.
+    pub fn blocked_on(&self) -> Option<TxIdx> {
+        self.blocked_on
+    }
+
+    /// Account state as it was observed before the transaction executed
+    pub fn original_account(&self, address: &Address) -> Option<&AccountInfo> {
+        self.accounts.get(address).and_then(Option::as_ref)
+    }
+
+    /// Code of an account the transaction observed as created, which the parent
+    /// state doesn't have
+    pub fn created_code(&self, address: &Address) -> Option<Bytes> {
+        self.created.get(address).cloned()
+    }
+
+    /// Locations read during execution, sorted for deterministic output
+    pub fn read_locations(&self) -> Vec<StateLocation> {
+        let mut locations: Vec<StateLocation> = self.origins.keys().copied().collect();
+        locations.sort_unstable();
+        locations
+    }
+
+    /// Lazy account read by the transaction, with the lazy balance change folded into it
+    pub fn lazy_read(&self) -> Option<(Address, I256)> {
+        let (address, _) = self.lazy_account.as_ref()?;
+        self.lazy_read.map(|change| (*address, change))
+    }
+
+    /// Reads together with the version they observed, sorted by location
+    pub fn read_origins(&self) -> Vec<(StateLocation, ReadOrigin)> {
+        let mut origins: Vec<_> =
+            self.origins.iter().map(|(location, origin)| (*location, *origin)).collect();
+        origins.sort_unstable_by_key(|(location, _)| *location);
+        origins
+    }
+
+    /// Resolve a location against the multi-version memory
+    fn read_versioned<E>(
+        &mut self,
+        location: StateLocation,
+    ) -> Result<(ReadOrigin, Option<MvMemoryValue>), StateViewError<E>> {
+        let mv_memory = self.mv_memory.lock().unwrap();
+        match mv_memory.read(location, self.tx_idx) {
+            MvReadResult::Versioned { version, value } => Ok((ReadOrigin::Versioned(version), Some(value))),
+            MvReadResult::Estimate { version, .. } if self.block_on_estimates => {
+                self.blocked_on.get_or_insert(version.tx_idx);
+                Err(StateViewError::Blocked(version.tx_idx))
+            }
+            MvReadResult::Estimate { version, value } => Ok((ReadOrigin::Estimate(version), Some(value))),
+            MvReadResult::Base => Ok((ReadOrigin::Base, mv_memory.base_value(location))),
+        }
+    }
+}
+
+impl<DB: DatabaseRef> Database for ParallelStateView<'_, DB> {
+    type Error = StateViewError<DB::Error>;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.accounts.get(&address) {
+            return Ok(info.clone());
+        }
+
+        let mut info = self.base.basic_ref(address).map_err(StateViewError::Database)?;
+
+        // Once the transaction returned, the lazy account is only loaded to credit
+        // its fee, which is accumulated separately
+        let lazy = self
+            .lazy_account
+            .as_ref()
+            .filter(|(lazy_account, _)| *lazy_account == address)
+            .map(|(_, returned)| returned.load(Ordering::Acquire));
+
+        // Overlay the latest write from a lower-indexed transaction or the seeded
+        // base state, if any
+        let (origin, value) = if lazy == Some(true) {
+            (ReadOrigin::Base, self.mv_memory.lock().unwrap().base_value((address, None)))
+        } else {
+            let (origin, value) = self.read_versioned((address, None))?;
+            self.origins.insert((address, None), origin);
+            (origin, value)
+        };
+        match value {
+            Some(MvMemoryValue::Basic { balance, nonce }) => {
+                let account = info.get_or_insert_with(AccountInfo::default);
+                account.balance = balance;
+                account.nonce = nonce;
+            }
+            Some(MvMemoryValue::Created { balance, nonce, code }) => {
+                let account = info.insert(AccountInfo::from_bytecode(Bytecode::new_raw(code.clone())));
+                account.balance = balance;
+                account.nonce = nonce;
+                self.created.insert(address, code);
+                if let Some(writer) = origin.writer() {
+                    self.reset_by.insert(address, writer);
+                }
+            }
+            Some(MvMemoryValue::SelfDestructed) => {
+                info = None;
+                if let Some(writer) = origin.writer() {
+                    self.reset_by.insert(address, writer);
+                }
+            }
+            _ => {}
+        }
+
+        // Lazy updates of the transactions since the observed write are part of the balance
+        if lazy == Some(false) {
+            let change = self.mv_memory.lock().unwrap().lazy_balance_change(address, origin.writer(), self.tx_idx);
+            if !change.is_zero() {
+                let account = info.get_or_insert_with(AccountInfo::default);
+                account.balance = apply_balance_delta(account.balance, change);
+            }
+            self.lazy_read = Some(change);
+        }
+
+        self.accounts.insert(address, info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.base.code_by_hash_ref(code_hash).map_err(StateViewError::Database)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.storage.get(&(address, index)) {
+            return Ok(*value);
+        }
+
+        let (origin, versioned) = self.read_versioned((address, Some(index)))?;
+        let reset_by = self.reset_by.get(&address).copied();
+        let value = match versioned {
+            // Storage from before the account was created or destroyed is gone
+            _ if reset_by.is_some_and(|reset_by| origin.writer().is_none_or(|writer| writer < reset_by)) => U256::ZERO,
+            Some(MvMemoryValue::Storage(value)) => value,
+            _ => self.base.storage_ref(address, index).map_err(StateViewError::Database)?,
+        };
+
+        self.storage.insert((address, index), value);
+        self.origins.insert((address, Some(index)), origin);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.base.block_hash_ref(number).map_err(StateViewError::Database)
+    }
+}
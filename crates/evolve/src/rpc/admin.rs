@@ -0,0 +1,297 @@
+//! Admin RPC
+//!
+//! `ande_reloadConfig` reloads the payload builder config file. The runtime
+//! switches, `ande_setParallelEnabled`, `ande_setForceSequential` and
+//! `ande_setMevEnabled`, let operators fall back to sequential execution or
+//! stop MEV handling during an incident, without a restart. They're served by
+//! a separate module. Both are registered on the authenticated engine API
+//! transport only.
+
+use async_trait::async_trait;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+
+/// Error code returned when the config file can't be reloaded
+pub const CONFIG_RELOAD_FAILED_CODE: i32 = -32003;
+
+/// Reloadable node configuration, as kept by the config watcher
+pub trait ConfigReloadSource: fmt::Debug + Send + Sync {
+    /// Re-read the config file and apply it, returning whether anything changed
+    ///
+    /// An invalid file, or one changing a setting that can't change while the
+    /// node runs, is rejected and leaves the current configuration in place.
+    fn reload_config(&self) -> eyre::Result<bool>;
+}
+
+/// MEV component switched by `ande_setMevEnabled`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MevComponent {
+    /// Detection of the opportunities in payloads, and the ordering around them
+    Detection,
+    /// Placement of the winning auction bundle at the top of each block
+    Auction,
+    /// Crediting of the realized MEV to the distributor
+    Distribution,
+}
+
+/// Change requested through the runtime switches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeSwitch {
+    /// Let blocks execute in parallel, or not
+    ParallelEnabled(bool),
+    /// Execute every block sequentially, or not, whatever the config file says
+    ForceSequential(bool),
+    /// Run an MEV component, or not
+    MevEnabled(MevComponent, bool),
+}
+
+/// Settings switched while the node runs, on top of the config file
+///
+/// Never read from the file, and kept across its reloads. Switching a
+/// component on only lets it run as configured: the parallel executor and MEV
+/// clients are still only set up at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeSwitches {
+    /// Whether blocks may execute in parallel
+    pub parallel_enabled: bool,
+    /// Overrides `force_sequential` of the `[parallel]` section when set
+    pub force_sequential: Option<bool>,
+    /// Whether MEV is detected in payloads, if enabled in the `[mev]` section
+    pub mev_detection: bool,
+    /// Whether the winning auction bundle is placed in blocks
+    pub mev_auction: bool,
+    /// Whether realized MEV is credited to the distributor
+    pub mev_distribution: bool,
+}
+
+impl RuntimeSwitches {
+    /// Switches leaving every component as configured
+    pub const fn new() -> Self {
+        Self {
+            parallel_enabled: true,
+            force_sequential: None,
+            mev_detection: true,
+            mev_auction: true,
+            mev_distribution: true,
+        }
+    }
+
+    /// Apply `switch`
+    pub fn apply(&mut self, switch: RuntimeSwitch) {
+        match switch {
+            RuntimeSwitch::ParallelEnabled(enabled) => self.parallel_enabled = enabled,
+            RuntimeSwitch::ForceSequential(enabled) => self.force_sequential = Some(enabled),
+            RuntimeSwitch::MevEnabled(MevComponent::Detection, enabled) => self.mev_detection = enabled,
+            RuntimeSwitch::MevEnabled(MevComponent::Auction, enabled) => self.mev_auction = enabled,
+            RuntimeSwitch::MevEnabled(MevComponent::Distribution, enabled) => self.mev_distribution = enabled,
+        }
+    }
+}
+
+impl Default for RuntimeSwitches {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Settings in effect once the runtime switches are applied to the config
+/// file, as returned by the runtime switches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeSettings {
+    /// Whether blocks may execute in parallel, on nodes running the parallel executor
+    pub parallel_enabled: bool,
+    /// Whether every block executes sequentially
+    pub force_sequential: bool,
+    /// Whether MEV is detected in payloads
+    pub mev_detection: bool,
+    /// Whether the winning auction bundle is placed in blocks, on nodes running an auction
+    pub mev_auction: bool,
+    /// Whether realized MEV is credited to the distributor, on nodes running one
+    pub mev_distribution: bool,
+}
+
+/// Runtime switches, as kept by the config watcher
+pub trait RuntimeSwitchSource: fmt::Debug + Send + Sync {
+    /// Apply `switch` from the next block built, returning the settings then in effect
+    fn switch(&self, switch: RuntimeSwitch) -> RuntimeSettings;
+}
+
+/// ANDE admin RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeAdminApi {
+    /// Reload the node configuration from its file, returning whether anything changed
+    #[method(name = "reloadConfig")]
+    async fn reload_config(&self) -> RpcResult<bool>;
+}
+
+/// Implementation of the ANDE admin RPC API
+#[derive(Debug, Clone)]
+pub struct AndeAdminApiImpl {
+    /// Configuration reloaded by `ande_reloadConfig`
+    config: Arc<dyn ConfigReloadSource>,
+}
+
+impl AndeAdminApiImpl {
+    /// Creates a new instance reloading `config`
+    pub fn new(config: Arc<dyn ConfigReloadSource>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AndeAdminApiServer for AndeAdminApiImpl {
+    async fn reload_config(&self) -> RpcResult<bool> {
+        self.config.reload_config().map_err(|error| {
+            ErrorObjectOwned::owned(CONFIG_RELOAD_FAILED_CODE, format!("Config not reloaded: {error}"), None::<()>)
+        })
+    }
+}
+
+/// ANDE runtime switches RPC API trait
+///
+/// Only to be served on the authenticated engine API transport.
+#[rpc(server, namespace = "ande")]
+pub trait AndeRuntimeApi {
+    /// Let blocks execute in parallel, or not, from the next block
+    #[method(name = "setParallelEnabled")]
+    async fn set_parallel_enabled(&self, enabled: bool) -> RpcResult<RuntimeSettings>;
+
+    /// Execute every block sequentially, or not, from the next block
+    #[method(name = "setForceSequential")]
+    async fn set_force_sequential(&self, enabled: bool) -> RpcResult<RuntimeSettings>;
+
+    /// Run the MEV `component`, or not, from the next block
+    #[method(name = "setMevEnabled")]
+    async fn set_mev_enabled(&self, component: MevComponent, enabled: bool) -> RpcResult<RuntimeSettings>;
+}
+
+/// Implementation of the ANDE runtime switches RPC API
+#[derive(Debug, Clone)]
+pub struct AndeRuntimeApiImpl {
+    /// Switches changed by the methods
+    switches: Arc<dyn RuntimeSwitchSource>,
+}
+
+impl AndeRuntimeApiImpl {
+    /// Creates a new instance changing `switches`
+    pub fn new(switches: Arc<dyn RuntimeSwitchSource>) -> Self {
+        Self { switches }
+    }
+}
+
+#[async_trait]
+impl AndeRuntimeApiServer for AndeRuntimeApiImpl {
+    async fn set_parallel_enabled(&self, enabled: bool) -> RpcResult<RuntimeSettings> {
+        Ok(self.switches.switch(RuntimeSwitch::ParallelEnabled(enabled)))
+    }
+
+    async fn set_force_sequential(&self, enabled: bool) -> RpcResult<RuntimeSettings> {
+        Ok(self.switches.switch(RuntimeSwitch::ForceSequential(enabled)))
+    }
+
+    async fn set_mev_enabled(&self, component: MevComponent, enabled: bool) -> RpcResult<RuntimeSettings> {
+        Ok(self.switches.switch(RuntimeSwitch::MevEnabled(component, enabled)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::server::MethodsError, types::error::INVALID_PARAMS_CODE};
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    /// Config source answering with queued results
+    #[derive(Debug)]
+    struct StubConfig(Mutex<Vec<Result<bool, String>>>);
+
+    impl ConfigReloadSource for StubConfig {
+        fn reload_config(&self) -> eyre::Result<bool> {
+            self.0.lock().unwrap().remove(0).map_err(|error| eyre::eyre!(error))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_config() {
+        let results = vec![Ok(true), Ok(false), Err("chain config can't change".to_string())];
+        let module = AndeAdminApiImpl::new(Arc::new(StubConfig(Mutex::new(results)))).into_rpc();
+
+        assert!(module.call::<_, bool>("ande_reloadConfig", ()).await.unwrap());
+        assert!(!module.call::<_, bool>("ande_reloadConfig", ()).await.unwrap());
+
+        let Err(MethodsError::JsonRpc(error)) = module.call::<_, bool>("ande_reloadConfig", ()).await else {
+            panic!("a rejected reload is an error");
+        };
+        assert_eq!(error.code(), CONFIG_RELOAD_FAILED_CODE);
+        assert_eq!(error.message(), "Config not reloaded: chain config can't change");
+    }
+
+    /// Switches applied on top of a file forcing sequential execution, with MEV detection enabled
+    #[derive(Debug, Default)]
+    struct StubSwitches(Mutex<RuntimeSwitches>);
+
+    impl RuntimeSwitchSource for StubSwitches {
+        fn switch(&self, switch: RuntimeSwitch) -> RuntimeSettings {
+            let mut switches = self.0.lock().unwrap();
+            switches.apply(switch);
+            RuntimeSettings {
+                parallel_enabled: switches.parallel_enabled,
+                force_sequential: switches.force_sequential.unwrap_or(true),
+                mev_detection: switches.mev_detection,
+                mev_auction: switches.mev_auction,
+                mev_distribution: switches.mev_distribution,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runtime_switches() {
+        let module = AndeRuntimeApiImpl::new(Arc::new(StubSwitches::default())).into_rpc();
+
+        let settings: RuntimeSettings = module.call("ande_setParallelEnabled", (false,)).await.unwrap();
+        assert!(!settings.parallel_enabled);
+        assert!(settings.force_sequential);
+
+        let settings: RuntimeSettings = module.call("ande_setForceSequential", (false,)).await.unwrap();
+        assert_eq!((settings.parallel_enabled, settings.force_sequential), (false, false));
+
+        let settings: Value = module.call("ande_setMevEnabled", ("auction", false)).await.unwrap();
+        assert_eq!(
+            settings,
+            json!({
+                "parallelEnabled": false,
+                "forceSequential": false,
+                "mevDetection": true,
+                "mevAuction": false,
+                "mevDistribution": true,
+            })
+        );
+        let settings: RuntimeSettings = module.call("ande_setMevEnabled", ("detection", false)).await.unwrap();
+        assert_eq!((settings.mev_detection, settings.mev_auction), (false, false));
+
+        let Err(MethodsError::JsonRpc(error)) =
+            module.call::<_, RuntimeSettings>("ande_setMevEnabled", ("ordering", false)).await
+        else {
+            panic!("an unknown component is rejected");
+        };
+        assert_eq!(error.code(), INVALID_PARAMS_CODE);
+    }
+
+    #[test]
+    fn test_runtime_switches_stay_off_the_admin_module() {
+        let admin = AndeAdminApiImpl::new(Arc::new(StubConfig(Mutex::new(Vec::new())))).into_rpc();
+        let runtime = AndeRuntimeApiImpl::new(Arc::new(StubSwitches::default())).into_rpc();
+
+        let runtime_methods: Vec<&str> = runtime.method_names().collect();
+        assert_eq!(runtime_methods.len(), 3);
+        for method in runtime_methods {
+            assert!(admin.method(method).is_none(), "{method} is served by the admin module");
+        }
+        assert!(admin.method("ande_reloadConfig").is_some());
+    }
+}
@@ -0,0 +1,588 @@
+//! Bundle Submission RPC
+//!
+//! `ande_sendBundle` lets searchers submit bundles of signed transactions to
+//! the node's MEV auction. The raw transactions are decoded and checked before
+//! the bundle reaches [`MevAuctionClient::submit_signed_bundle`]: each must
+//! carry a valid signature, the chain id of the node and a gas limit covering
+//! its intrinsic gas.
+//!
+//! The bundle hash commits to the transactions, see
+//! [`bundle_commitment`](crate::mev::bundle_commitment), so resubmitting the
+//! same transactions for the same block reports the pending bundle instead of
+//! bidding twice. The optional timestamp window is checked on submission:
+//! bundles whose window is inverted or already over are refused.
+//!
+//! `ande_getBundleStatus` reports where a bundle is in the auction: pending,
+//! selected for its block, executed, rejected or expired.
+//!
+//! Bundles hold at most [`MAX_BUNDLE_TXS`] transactions, checked before any is
+//! decoded. Submissions are rate limited per caller before any signature is
+//! recovered, then per searcher, the signer of the first transaction of the
+//! bundle. See [`AndeBundleApiServer::send_bundle`] for how callers are told
+//! apart.
+
+use super::mev::invalid_params;
+use crate::{
+    mev::{bundle_commitment, BundleState, BundleStatus, MevAuctionClient, MevAuctionError},
+    parallel::intrinsic_gas,
+};
+use alloy_consensus::{transaction::SignerRecoverable, Transaction};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::{
+    types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
+    ConnectionId, Extensions,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_primitives::TransactionSigned;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+/// Error code returned when a searcher exceeds its submission rate (EIP-1474 "limit exceeded")
+pub const BUNDLE_RATE_LIMITED_CODE: i32 = -32005;
+
+/// Bundles a searcher may submit per rate limit window by default
+pub const DEFAULT_BUNDLES_PER_WINDOW: u32 = 20;
+
+/// Rate limit window by default
+pub const DEFAULT_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Most transactions a submitted or simulated bundle may hold
+pub const MAX_BUNDLE_TXS: usize = 32;
+
+/// Number of callers and searchers tracked before expired windows are pruned
+const MAX_RATE_LIMITED_KEYS: usize = 10_000;
+
+/// Parameters of `ande_sendBundle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleRequest {
+    /// EIP-2718 encoded signed transactions, in execution order
+    pub txs: Vec<Bytes>,
+    /// Block the bundle bids for
+    pub target_block: U64,
+    /// Bid, in wei
+    pub bid_amount: U256,
+    /// Earliest time the bundle is valid at, in seconds since the epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<U64>,
+    /// Latest time the bundle is valid at, in seconds since the epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<U64>,
+}
+
+/// Outcome of a submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SendBundleStatus {
+    /// The bundle entered the auction
+    Accepted,
+    /// The same bundle is already pending for the target block
+    AlreadyPending,
+}
+
+/// Response of `ande_sendBundle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleResponse {
+    /// Hash committing to the bundle transactions
+    pub bundle_hash: B256,
+    /// Outcome of the submission
+    pub status: SendBundleStatus,
+}
+
+/// Response of `ande_getBundleStatus`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBundleStatus {
+    /// Stage of the bundle in the auction
+    pub state: BundleState,
+    /// Block the bundle targets, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_block: Option<U64>,
+    /// Bid, in wei
+    pub bid_amount: U256,
+    /// MEV captured, for executed bundles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mev_captured: Option<U256>,
+    /// Why the bundle was rejected, for rejected bundles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+}
+
+impl From<BundleStatus> for RpcBundleStatus {
+    fn from(status: BundleStatus) -> Self {
+        Self {
+            state: status.state,
+            target_block: status.target_block.map(U64::from),
+            bid_amount: status.bid_amount,
+            mev_captured: status.mev_captured,
+            rejection_reason: status.rejection_reason,
+        }
+    }
+}
+
+/// Identity of the caller of `ande_sendBundle`, e.g. its API key
+///
+/// Set in the request extensions by an RPC middleware authenticating
+/// searchers, it takes precedence over the connection for rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BundleCaller(pub String);
+
+/// What a submission is counted against by a [`BundleRateLimiter`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// Caller identified by an RPC middleware
+    Caller(BundleCaller),
+    /// Connection the request came in on, when no middleware identifies the caller
+    Connection(ConnectionId),
+    /// Signer of the first transaction of the bundle
+    Searcher(Address),
+}
+
+impl RateLimitKey {
+    /// Caller of the request with `extensions`, if the server identifies it
+    pub fn caller(extensions: &Extensions) -> Option<Self> {
+        extensions
+            .get::<BundleCaller>()
+            .cloned()
+            .map(Self::Caller)
+            .or_else(|| extensions.get::<ConnectionId>().copied().map(Self::Connection))
+    }
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Caller(caller) => write!(f, "Caller {}", caller.0),
+            Self::Connection(connection) => write!(f, "Connection {}", connection.0),
+            Self::Searcher(searcher) => write!(f, "Searcher {searcher}"),
+        }
+    }
+}
+
+/// Fixed-window limit on the bundles each caller or searcher may submit
+#[derive(Debug)]
+pub struct BundleRateLimiter {
+    /// Bundles accepted per window
+    max_bundles: u32,
+    /// Length of a window
+    window: Duration,
+    /// Start of the current window of each key, with the bundles submitted in it
+    windows: Mutex<HashMap<RateLimitKey, (Instant, u32)>>,
+}
+
+impl BundleRateLimiter {
+    /// Allow `max_bundles` per caller and per searcher in every `window`
+    pub fn new(max_bundles: u32, window: Duration) -> Self {
+        Self { max_bundles, window, windows: Mutex::new(HashMap::new()) }
+    }
+
+    /// Count a submission against `key` at `now`, returning whether it's within the limit
+    pub fn check(&self, key: RateLimitKey, now: Instant) -> bool {
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() >= MAX_RATE_LIMITED_KEYS {
+            windows.retain(|_, (start, _)| now.saturating_duration_since(*start) < self.window);
+        }
+
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.saturating_duration_since(*start) >= self.window {
+            (*start, *count) = (now, 0);
+        }
+        if *count >= self.max_bundles {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+impl Default for BundleRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUNDLES_PER_WINDOW, DEFAULT_RATE_LIMIT_WINDOW)
+    }
+}
+
+/// ANDE bundle submission RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeBundleApi {
+    /// Submit a bundle of signed transactions to the MEV auction
+    ///
+    /// Bundles hold at most [`MAX_BUNDLE_TXS`] transactions. Each caller, and
+    /// each searcher signing the first transaction, may submit a limited
+    /// number of bundles per window. Callers are identified by the
+    /// [`BundleCaller`] an RPC middleware sets, e.g. from an API key, and
+    /// otherwise by their connection. The node doesn't see the IP address of
+    /// callers: without such a middleware a caller resets its budget by
+    /// opening a new connection, so public deployments must limit by IP in
+    /// the proxy in front of the node.
+    #[method(name = "sendBundle", with_extensions)]
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse>;
+
+    /// Where the bundle `bundle_hash` is in the MEV auction
+    #[method(name = "getBundleStatus")]
+    async fn get_bundle_status(&self, bundle_hash: B256) -> RpcResult<RpcBundleStatus>;
+}
+
+/// Implementation of the ANDE bundle submission RPC API, forwarding to an MEV auction
+#[derive(Debug, Clone)]
+pub struct AndeBundleApiImpl {
+    /// Auction bundles are submitted to
+    auction: Arc<MevAuctionClient>,
+    /// Chain id every bundle transaction must be signed for
+    chain_id: u64,
+    /// Submission limit per caller and per searcher
+    rate_limiter: Arc<BundleRateLimiter>,
+}
+
+impl AndeBundleApiImpl {
+    /// Creates a new instance submitting bundles signed for `chain_id` to `auction`
+    pub fn new(auction: Arc<MevAuctionClient>, chain_id: u64) -> Self {
+        Self { auction, chain_id, rate_limiter: Arc::new(BundleRateLimiter::default()) }
+    }
+
+    /// Allow each caller and each searcher `max_bundles` submissions in every `window`
+    pub fn with_rate_limit(mut self, max_bundles: u32, window: Duration) -> Self {
+        self.rate_limiter = Arc::new(BundleRateLimiter::new(max_bundles, window));
+        self
+    }
+
+    /// Count a submission against `key`, failing if it's over the limit
+    fn check_rate(&self, key: RateLimitKey) -> RpcResult<()> {
+        if self.rate_limiter.check(key.clone(), Instant::now()) {
+            return Ok(());
+        }
+        Err(ErrorObjectOwned::owned(
+            BUNDLE_RATE_LIMITED_CODE,
+            format!("{key} exceeded the bundle submission rate"),
+            None::<()>,
+        ))
+    }
+}
+
+/// Reject an empty bundle, or one holding more than [`MAX_BUNDLE_TXS`] transactions
+pub(super) fn check_bundle_size(transactions: usize) -> RpcResult<()> {
+    if transactions == 0 {
+        return Err(invalid_params("Bundle must contain at least one transaction".to_string()));
+    }
+    if transactions > MAX_BUNDLE_TXS {
+        return Err(invalid_params(format!(
+            "Bundle holds {transactions} transactions, at most {MAX_BUNDLE_TXS} are allowed"
+        )));
+    }
+    Ok(())
+}
+
+/// Decode the raw transaction at `index` of a bundle and check it can be
+/// included on the chain `expected_chain_id`
+pub(super) fn decode_bundle_transaction(
+    expected_chain_id: u64,
+    index: usize,
+    raw: &[u8],
+) -> RpcResult<(TransactionSigned, Address)> {
+    let invalid = |reason: String| invalid_params(format!("Transaction {index}: {reason}"));
+
+    let mut encoded = raw;
+    let tx = TransactionSigned::decode_2718(&mut encoded).map_err(|err| invalid(err.to_string()))?;
+    if !encoded.is_empty() {
+        return Err(invalid(format!("{} trailing bytes", encoded.len())));
+    }
+    let signer = tx.recover_signer().map_err(|err| invalid(err.to_string()))?;
+    match tx.chain_id() {
+        Some(chain_id) if chain_id == expected_chain_id => {}
+        Some(chain_id) => return Err(invalid(format!("chain id {chain_id}, expected {expected_chain_id}"))),
+        None => return Err(invalid("signed without a chain id".to_string())),
+    }
+    let required = intrinsic_gas(&tx);
+    if tx.gas_limit() < required {
+        return Err(invalid(format!("gas limit {} below intrinsic gas {required}", tx.gas_limit())));
+    }
+    Ok((tx, signer))
+}
+
+/// Reject a timestamp window that's inverted or already over
+fn check_timestamps(min: Option<u64>, max: Option<u64>) -> RpcResult<()> {
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(invalid_params(format!("minTimestamp {min} is after maxTimestamp {max}")));
+        }
+    }
+    if let Some(max) = max {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if max < now {
+            return Err(invalid_params(format!("maxTimestamp {max} has passed")));
+        }
+    }
+    Ok(())
+}
+
+fn auction_error(error: MevAuctionError) -> ErrorObjectOwned {
+    match error {
+        MevAuctionError::InvalidBundle(error) => invalid_params(error.to_string()),
+        other => ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, other.to_string(), None::<()>),
+    }
+}
+
+#[async_trait]
+impl AndeBundleApiServer for AndeBundleApiImpl {
+    async fn send_bundle(&self, ext: &Extensions, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        check_bundle_size(request.txs.len())?;
+        check_timestamps(request.min_timestamp.map(|t| t.to()), request.max_timestamp.map(|t| t.to()))?;
+
+        // Callers are limited before any signature is recovered, and searchers
+        // before the rest of the bundle is
+        if let Some(caller) = RateLimitKey::caller(ext) {
+            self.check_rate(caller)?;
+        }
+        let (first, searcher) = decode_bundle_transaction(self.chain_id, 0, &request.txs[0])?;
+        self.check_rate(RateLimitKey::Searcher(searcher))?;
+
+        let mut transactions = Vec::with_capacity(request.txs.len());
+        transactions.push(first);
+        for (index, raw) in request.txs.iter().enumerate().skip(1) {
+            transactions.push(decode_bundle_transaction(self.chain_id, index, raw)?.0);
+        }
+
+        let target_block = request.target_block.to::<u64>();
+        let bundle_hash = bundle_commitment(transactions.iter().map(|tx| tx.hash()));
+        if self.auction.get_bundles_for_block(target_block).await.iter().any(|b| b.bundle_hash == bundle_hash) {
+            return Ok(SendBundleResponse { bundle_hash, status: SendBundleStatus::AlreadyPending });
+        }
+
+        let bundle_hash = self
+            .auction
+            .submit_signed_bundle(searcher, request.bid_amount, target_block, transactions)
+            .await
+            .map_err(auction_error)?;
+        debug!(
+            "Bundle received over RPC: hash={}, searcher={}, target_block={}",
+            bundle_hash, searcher, target_block
+        );
+
+        Ok(SendBundleResponse { bundle_hash, status: SendBundleStatus::Accepted })
+    }
+
+    async fn get_bundle_status(&self, bundle_hash: B256) -> RpcResult<RpcBundleStatus> {
+        match self.auction.bundle_status(bundle_hash).await {
+            Some(status) => Ok(status.into()),
+            None => Err(invalid_params(format!("Bundle {bundle_hash} is not known"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+    use alloy_consensus::{SignableTransaction, TxEip1559, TypedTransaction};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::TxKind;
+    use jsonrpsee::{core::server::MethodsError, types::error::INVALID_PARAMS_CODE};
+    use serde_json::{json, Value};
+
+    const CHAIN_ID: u64 = 31337;
+
+    /// EIP-1559 transfer signed by `signer` for `chain_id`
+    fn signed_transaction(signer: &PrivateKeySigner, chain_id: u64, nonce: u64, gas_limit: u64) -> TransactionSigned {
+        let tx = TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::with_last_byte(1)),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::new_unhashed(TypedTransaction::Eip1559(tx).into(), signature)
+    }
+
+    fn request(transactions: &[TransactionSigned]) -> SendBundleRequest {
+        SendBundleRequest {
+            txs: transactions.iter().map(|tx| tx.encoded_2718().into()).collect(),
+            target_block: U64::from(10),
+            bid_amount: U256::from(1_000),
+            min_timestamp: None,
+            max_timestamp: None,
+        }
+    }
+
+    fn module(auction: Arc<MevAuctionClient>) -> jsonrpsee::RpcModule<AndeBundleApiImpl> {
+        AndeBundleApiImpl::new(auction, CHAIN_ID).with_rate_limit(3, Duration::from_secs(60)).into_rpc()
+    }
+
+    fn error(error: MethodsError) -> (i32, String) {
+        match error {
+            MethodsError::JsonRpc(error) => (error.code(), error.message().to_string()),
+            other => panic!("Expected a JSON-RPC error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_enters_auction() {
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+        let module = module(auction.clone());
+        let signer = PrivateKeySigner::random();
+        let transactions =
+            vec![signed_transaction(&signer, CHAIN_ID, 0, 21_000), signed_transaction(&signer, CHAIN_ID, 1, 21_000)];
+        let bundle_hash = bundle_commitment(transactions.iter().map(|tx| tx.hash()));
+
+        let response: Value = module.call("ande_sendBundle", (request(&transactions),)).await.unwrap();
+        assert_eq!(response, json!({ "bundleHash": format!("{bundle_hash:?}"), "status": "accepted" }));
+
+        let bundles = auction.get_bundles_for_block(10).await;
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].bundle_hash, bundle_hash);
+        assert_eq!(bundles[0].searcher, signer.address());
+        assert_eq!(bundles[0].bid_amount, U256::from(1_000));
+        assert_eq!(auction.bundle_transactions().resolve(&bundles[0].transactions), Some(transactions.clone()));
+
+        // Resubmitting reports the pending bundle without bidding again
+        let response: SendBundleResponse = module.call("ande_sendBundle", (request(&transactions),)).await.unwrap();
+        assert_eq!(response, SendBundleResponse { bundle_hash, status: SendBundleStatus::AlreadyPending });
+        assert_eq!(auction.get_bundles_for_block(10).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_bundle_status_json_shape() {
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+        let module = module(auction.clone());
+        let signer = PrivateKeySigner::random();
+        let first = signed_transaction(&signer, CHAIN_ID, 0, 21_000);
+        let second = signed_transaction(&signer, CHAIN_ID, 1, 21_000);
+
+        let accepted: SendBundleResponse = module.call("ande_sendBundle", (request(&[first]),)).await.unwrap();
+        let status: Value = module.call("ande_getBundleStatus", (accepted.bundle_hash,)).await.unwrap();
+        assert_eq!(status, json!({ "state": "pending", "targetBlock": "0xa", "bidAmount": "0x3e8" }));
+
+        auction.mark_bundle_executed(accepted.bundle_hash, U256::from(5_000), U256::from(1_000)).await.unwrap();
+        let status: Value = module.call("ande_getBundleStatus", (accepted.bundle_hash,)).await.unwrap();
+        assert_eq!(
+            status,
+            json!({ "state": "executed", "targetBlock": "0xa", "bidAmount": "0x3e8", "mevCaptured": "0x1388" })
+        );
+
+        let rejected: SendBundleResponse = module.call("ande_sendBundle", (request(&[second]),)).await.unwrap();
+        auction.mark_bundle_rejected(rejected.bundle_hash, "reverted".to_string()).await.unwrap();
+        let status: Value = module.call("ande_getBundleStatus", (rejected.bundle_hash,)).await.unwrap();
+        assert_eq!(status["state"], "rejected");
+        assert_eq!(status["rejectionReason"], "reverted");
+
+        let (code, _) = error(module.call::<_, Value>("ande_getBundleStatus", (B256::ZERO,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_transactions_rejected() {
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+        let module = module(auction.clone());
+        let signer = PrivateKeySigner::random();
+        let valid = signed_transaction(&signer, CHAIN_ID, 0, 21_000);
+
+        let mut malformed = request(&[valid.clone()]);
+        malformed.txs.push(Bytes::from_static(&[0x02, 0xc0, 0x01]));
+        let (code, message) = error(module.call::<_, Value>("ande_sendBundle", (malformed,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert!(message.starts_with("Transaction 1:"), "{message}");
+
+        let mut trailing = request(&[valid.clone()]);
+        trailing.txs[0] = [trailing.txs[0].as_ref(), &[0x00]].concat().into();
+        let (code, _) = error(module.call::<_, Value>("ande_sendBundle", (trailing,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+
+        let wrong_chain = request(&[valid.clone(), signed_transaction(&signer, 1, 1, 21_000)]);
+        let (code, message) = error(module.call::<_, Value>("ande_sendBundle", (wrong_chain,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert_eq!(message, format!("Transaction 1: chain id 1, expected {CHAIN_ID}"));
+
+        let underpriced = request(&[signed_transaction(&signer, CHAIN_ID, 0, 20_999)]);
+        let (code, message) = error(module.call::<_, Value>("ande_sendBundle", (underpriced,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert!(message.contains("intrinsic gas 21000"), "{message}");
+
+        let mut expired = request(&[valid]);
+        expired.max_timestamp = Some(U64::from(1));
+        let (code, _) = error(module.call::<_, Value>("ande_sendBundle", (expired,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+
+        // Nothing reached the auction or its registry
+        assert!(auction.get_bundles_for_block(10).await.is_empty());
+        assert!(auction.bundle_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submissions_rate_limited_per_searcher() {
+        let module = module(Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO)));
+        let (spammer, other) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+
+        for nonce in 0..3 {
+            let bundle = request(&[signed_transaction(&spammer, CHAIN_ID, nonce, 21_000)]);
+            module.call::<_, SendBundleResponse>("ande_sendBundle", (bundle,)).await.unwrap();
+        }
+        let bundle = request(&[signed_transaction(&spammer, CHAIN_ID, 3, 21_000)]);
+        let (code, _) = error(module.call::<_, Value>("ande_sendBundle", (bundle,)).await.unwrap_err());
+        assert_eq!(code, BUNDLE_RATE_LIMITED_CODE);
+
+        // Other searchers have their own budget
+        let bundle = request(&[signed_transaction(&other, CHAIN_ID, 0, 21_000)]);
+        module.call::<_, SendBundleResponse>("ande_sendBundle", (bundle,)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_bundle_rejected_before_decoding() {
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+        let module = module(auction.clone());
+
+        // Garbage bytes would fail decoding, the size is checked first
+        let mut oversized = request(&[]);
+        oversized.txs = vec![Bytes::from_static(&[0x02, 0xc0]); MAX_BUNDLE_TXS + 1];
+        let (code, message) = error(module.call::<_, Value>("ande_sendBundle", (oversized,)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert_eq!(
+            message,
+            format!("Bundle holds {} transactions, at most {MAX_BUNDLE_TXS} are allowed", MAX_BUNDLE_TXS + 1)
+        );
+
+        let (code, _) = error(module.call::<_, Value>("ande_sendBundle", (request(&[]),)).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert!(auction.get_bundles_for_block(10).await.is_empty());
+    }
+
+    #[test]
+    fn test_rate_limit_window_resets() {
+        let limiter = BundleRateLimiter::new(1, Duration::from_secs(1));
+        let (searcher, start) = (RateLimitKey::Searcher(Address::with_last_byte(1)), Instant::now());
+
+        assert!(limiter.check(searcher.clone(), start));
+        assert!(!limiter.check(searcher.clone(), start + Duration::from_millis(999)));
+        assert!(limiter.check(searcher, start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_callers_limited_apart_from_searchers() {
+        let mut extensions = Extensions::new();
+        assert_eq!(RateLimitKey::caller(&extensions), None);
+        extensions.insert(ConnectionId(7));
+        assert_eq!(RateLimitKey::caller(&extensions), Some(RateLimitKey::Connection(ConnectionId(7))));
+
+        // The identity set by a middleware takes precedence over the connection
+        let caller = BundleCaller("searcher-api-key".to_string());
+        extensions.insert(caller.clone());
+        assert_eq!(RateLimitKey::caller(&extensions), Some(RateLimitKey::Caller(caller.clone())));
+
+        let limiter = BundleRateLimiter::new(1, Duration::from_secs(1));
+        let now = Instant::now();
+        assert!(limiter.check(RateLimitKey::Caller(caller.clone()), now));
+        assert!(!limiter.check(RateLimitKey::Caller(caller), now));
+        assert!(limiter.check(RateLimitKey::Connection(ConnectionId(7)), now));
+        assert!(limiter.check(RateLimitKey::Searcher(Address::with_last_byte(1)), now));
+    }
+}
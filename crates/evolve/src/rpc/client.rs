@@ -0,0 +1,329 @@
+//! ANDE RPC Client
+//!
+//! [`AndeRpcClient`] calls the public `ande_` methods of a node with typed
+//! requests and responses, for services such as the sequencer frontend and
+//! monitoring bots. It's built on alloy's [`RpcClient`], so any alloy
+//! transport works, e.g. a [`FailoverTransport`](crate::failover::FailoverTransport).
+//!
+//! Requests and responses are the types the server modules serve, so the two
+//! sides can't drift apart. The admin and runtime switch methods, served on
+//! the authenticated transport only, aren't covered.
+//!
+//! Errors tell the node answering with a JSON-RPC error, e.g. invalid params,
+//! apart from the node not being reached, see [`AndeRpcClientError`].
+
+use super::{
+    bundle::{RpcBundleStatus, SendBundleRequest, SendBundleResponse},
+    consensus::{RpcActiveValidator, ScheduledProducer},
+    duality::{DualityCaps, DualityConfig, DualityTransfer},
+    health::HealthReport,
+    mev::{MevStats, RpcMevOpportunity, RpcSearcherStats},
+    simulate::{SimulateBundleRequest, SimulateBundleResponse},
+    stats::PayloadStats,
+    validator::ValidatorStatus,
+};
+use alloy::{
+    rpc::{
+        client::RpcClient,
+        json_rpc::{RpcRecv, RpcSend},
+    },
+    transports::{
+        http::{reqwest::Url, Http},
+        RpcError, TransportError,
+    },
+};
+use alloy_primitives::{B256, U64};
+use serde_json::value::RawValue;
+
+/// Error of an [`AndeRpcClient`] call
+#[derive(Debug, thiserror::Error)]
+pub enum AndeRpcClientError {
+    /// The node couldn't be reached, or its response couldn't be read
+    #[error("Transport error: {0}")]
+    Transport(#[source] TransportError),
+    /// The node answered with a JSON-RPC error
+    #[error("RPC error {code}: {message}")]
+    Rpc {
+        /// Error code, e.g. -32602 for invalid params
+        code: i64,
+        /// Error message
+        message: String,
+        /// Additional data, if any
+        data: Option<Box<RawValue>>,
+    },
+}
+
+impl AndeRpcClientError {
+    /// Code of the JSON-RPC error the node answered with, `None` for transport errors
+    pub const fn code(&self) -> Option<i64> {
+        match self {
+            Self::Rpc { code, .. } => Some(*code),
+            Self::Transport(_) => None,
+        }
+    }
+}
+
+impl From<TransportError> for AndeRpcClientError {
+    fn from(error: TransportError) -> Self {
+        match error {
+            RpcError::ErrorResp(payload) => {
+                Self::Rpc { code: payload.code, message: payload.message.into_owned(), data: payload.data }
+            }
+            other => Self::Transport(other),
+        }
+    }
+}
+
+/// Client of the `ande_` RPC namespace
+///
+/// # Example
+/// ```ignore
+/// let client = AndeRpcClient::new_http("http://localhost:8545".parse()?);
+/// let stats = client.get_payload_stats(10).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct AndeRpcClient {
+    client: RpcClient,
+}
+
+impl AndeRpcClient {
+    /// Create a client sending its calls through `client`
+    pub const fn new(client: RpcClient) -> Self {
+        Self { client }
+    }
+
+    /// Create a client calling the node at `url` over HTTP
+    pub fn new_http(url: Url) -> Self {
+        Self::new(RpcClient::new(Http::new(url), false))
+    }
+
+    /// Underlying alloy client, e.g. for calls outside the `ande_` namespace
+    pub const fn inner(&self) -> &RpcClient {
+        &self.client
+    }
+
+    async fn request<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        method: &'static str,
+        params: Params,
+    ) -> Result<Resp, AndeRpcClientError> {
+        Ok(self.client.request(method, params).await?)
+    }
+
+    /// `ande_sendBundle`: submit a bundle to the node's MEV auction
+    pub async fn send_bundle(&self, request: SendBundleRequest) -> Result<SendBundleResponse, AndeRpcClientError> {
+        self.request("ande_sendBundle", (request,)).await
+    }
+
+    /// `ande_getBundleStatus`: stage of the bundle with hash `bundle_hash` in the auction
+    pub async fn get_bundle_status(&self, bundle_hash: B256) -> Result<RpcBundleStatus, AndeRpcClientError> {
+        self.request("ande_getBundleStatus", (bundle_hash,)).await
+    }
+
+    /// `ande_simulateBundle`: execute a bundle against the chain state without committing it
+    pub async fn simulate_bundle(
+        &self,
+        request: SimulateBundleRequest,
+    ) -> Result<SimulateBundleResponse, AndeRpcClientError> {
+        self.request("ande_simulateBundle", (request,)).await
+    }
+
+    /// `ande_getMevStats`: totals per type of MEV detected between `from_block` and `to_block`, inclusive
+    pub async fn get_mev_stats(&self, from_block: u64, to_block: u64) -> Result<MevStats, AndeRpcClientError> {
+        self.request("ande_getMevStats", (U64::from(from_block), U64::from(to_block))).await
+    }
+
+    /// `ande_getMevOpportunities`: opportunities detected in `block_number`
+    pub async fn get_mev_opportunities(&self, block_number: u64) -> Result<Vec<RpcMevOpportunity>, AndeRpcClientError> {
+        self.request("ande_getMevOpportunities", (U64::from(block_number),)).await
+    }
+
+    /// `ande_getTopSearchers`: the `count` searchers who paid the most in auction bids
+    pub async fn get_top_searchers(&self, count: u64) -> Result<Vec<RpcSearcherStats>, AndeRpcClientError> {
+        self.request("ande_getTopSearchers", (U64::from(count),)).await
+    }
+
+    /// `ande_getPayloadStats`: building performance of the last `last_n_blocks` blocks built by the node
+    pub async fn get_payload_stats(&self, last_n_blocks: u64) -> Result<Vec<PayloadStats>, AndeRpcClientError> {
+        self.request("ande_getPayloadStats", (U64::from(last_n_blocks),)).await
+    }
+
+    /// `ande_validatorStatus`: health of the node's validator
+    pub async fn validator_status(&self) -> Result<ValidatorStatus, AndeRpcClientError> {
+        self.request("ande_validatorStatus", ()).await
+    }
+
+    /// `ande_getValidators`: active validators, with their on-chain records
+    pub async fn get_validators(&self) -> Result<Vec<RpcActiveValidator>, AndeRpcClientError> {
+        self.request("ande_getValidators", ()).await
+    }
+
+    /// `ande_getProducerSchedule`: producers designated for the `count` blocks from `from_block`
+    pub async fn get_producer_schedule(
+        &self,
+        from_block: u64,
+        count: u64,
+    ) -> Result<Vec<ScheduledProducer>, AndeRpcClientError> {
+        self.request("ande_getProducerSchedule", (U64::from(from_block), U64::from(count))).await
+    }
+
+    /// `ande_getDualityCaps`: caps of the ANDE precompile and their use in the current block
+    pub async fn get_duality_caps(&self) -> Result<DualityCaps, AndeRpcClientError> {
+        self.request("ande_getDualityCaps", ()).await
+    }
+
+    /// `ande_getDualityConfig`: callers the ANDE precompile accepts
+    pub async fn get_duality_config(&self) -> Result<DualityConfig, AndeRpcClientError> {
+        self.request("ande_getDualityConfig", ()).await
+    }
+
+    /// `ande_getDualityTransfers`: transfers through the ANDE precompile in `block_number`, `None` if
+    /// the block is unknown
+    pub async fn get_duality_transfers(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<Vec<DualityTransfer>>, AndeRpcClientError> {
+        self.request("ande_getDualityTransfers", (U64::from(block_number),)).await
+    }
+
+    /// `ande_health`: state of the Evolve subsystems the node runs
+    pub async fn health(&self) -> Result<HealthReport, AndeRpcClientError> {
+        self.request("ande_health", ()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mev::{BundleState, InMemoryMevStore, MevAuctionClient, MevOpportunity, MevOpportunityStore, MevType},
+        rpc::{
+            bundle::{AndeBundleApiImpl, AndeBundleApiServer, SendBundleStatus},
+            consensus::{AndeConsensusApiImpl, AndeConsensusApiServer, CONSENSUS_NOT_CONFIGURED_CODE},
+            health::{AndeHealthApiImpl, AndeHealthApiServer, HealthRegistry, HealthStatus, HealthThresholds},
+            mev::{AndeMevApiImpl, AndeMevApiServer},
+            stats::{AndeStatsApiImpl, AndeStatsApiServer, AttestationStatus, ExecutionMode, PayloadStatsBuffer},
+        },
+    };
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+    use alloy_consensus::{SignableTransaction, TxEip1559, TypedTransaction};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, TxKind, U256};
+    use jsonrpsee::{
+        server::{RpcModule, Server, ServerHandle},
+        types::error::INVALID_PARAMS_CODE,
+    };
+    use reth_primitives::TransactionSigned;
+    use std::sync::Arc;
+
+    const CHAIN_ID: u64 = 31337;
+
+    /// Serve `module` on a local port, returning a client calling it
+    async fn serve(module: RpcModule<()>) -> (ServerHandle, AndeRpcClient) {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap()).parse().unwrap();
+        (server.start(module), AndeRpcClient::new_http(url))
+    }
+
+    fn payload_stats(block_number: u64) -> PayloadStats {
+        PayloadStats {
+            block_number: U64::from(block_number),
+            block_hash: B256::repeat_byte(block_number as u8),
+            build_duration_ms: U64::from(120),
+            execution_mode: ExecutionMode::Parallel,
+            transaction_count: U64::from(40),
+            rejected_count: U64::ZERO,
+            gas_used: U64::from(840_000),
+            conflicts: None,
+            retries: None,
+            attestation: AttestationStatus::NotAwaited,
+            mev_captured: U256::ZERO,
+        }
+    }
+
+    fn signed_transaction(signer: &PrivateKeySigner, nonce: u64) -> TransactionSigned {
+        let tx = TxEip1559 {
+            chain_id: CHAIN_ID,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::with_last_byte(1)),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::new_unhashed(TypedTransaction::Eip1559(tx).into(), signature)
+    }
+
+    /// Node serving the stats, MEV, bundle, health and consensus modules
+    async fn node() -> (ServerHandle, AndeRpcClient) {
+        let stats = Arc::new(PayloadStatsBuffer::new(8));
+        (1..=3).for_each(|block_number| stats.record(payload_stats(block_number)));
+        let store = Arc::new(InMemoryMevStore::new(8));
+        let opportunity = MevOpportunity::new(MevType::Arbitrage, B256::with_last_byte(1), U256::from(16), 10);
+        store.record(10, vec![opportunity]).unwrap();
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+
+        let mut module = RpcModule::new(());
+        module.merge(AndeStatsApiImpl::new(stats).into_rpc()).unwrap();
+        module.merge(AndeMevApiImpl::new(store).into_rpc()).unwrap();
+        module.merge(AndeBundleApiImpl::new(auction, CHAIN_ID).into_rpc()).unwrap();
+        let health = Arc::new(HealthRegistry::new(HealthThresholds::new()));
+        module.merge(AndeHealthApiImpl::new(health).into_rpc()).unwrap();
+        module.merge(AndeConsensusApiImpl::new(None).into_rpc()).unwrap();
+        serve(module).await
+    }
+
+    #[tokio::test]
+    async fn test_typed_calls() {
+        let (_node, client) = node().await;
+
+        let stats = client.get_payload_stats(2).await.unwrap();
+        assert_eq!(stats, [payload_stats(2), payload_stats(3)]);
+
+        let mev_stats = client.get_mev_stats(10, 11).await.unwrap();
+        assert_eq!(mev_stats.recorded_blocks, U64::from(1));
+        assert_eq!(mev_stats.by_type[&MevType::Arbitrage].value, U256::from(16));
+        let opportunities = client.get_mev_opportunities(10).await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].tx_hash, B256::with_last_byte(1));
+
+        let signer = PrivateKeySigner::random();
+        let request = SendBundleRequest {
+            txs: vec![signed_transaction(&signer, 0).encoded_2718().into()],
+            target_block: U64::from(10),
+            bid_amount: U256::from(1_000),
+            min_timestamp: None,
+            max_timestamp: None,
+        };
+        let response = client.send_bundle(request).await.unwrap();
+        assert_eq!(response.status, SendBundleStatus::Accepted);
+        let status = client.get_bundle_status(response.bundle_hash).await.unwrap();
+        assert_eq!(status.state, BundleState::Pending);
+        assert_eq!(status.bid_amount, U256::from(1_000));
+
+        // Nothing is registered with the health registry
+        assert_eq!(client.health().await.unwrap().status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_told_apart_from_transport_errors() {
+        let (node, client) = node().await;
+
+        let error = client.get_validators().await.unwrap_err();
+        assert!(
+            matches!(&error, AndeRpcClientError::Rpc { message, .. } if message.contains("No consensus client")),
+            "{error:?}"
+        );
+        assert_eq!(error.code(), Some(CONSENSUS_NOT_CONFIGURED_CODE.into()));
+        assert_eq!(client.get_mev_stats(11, 10).await.unwrap_err().code(), Some(INVALID_PARAMS_CODE.into()));
+        // Method not served by the node
+        assert_eq!(client.validator_status().await.unwrap_err().code(), Some(-32601));
+
+        node.stop().unwrap();
+        node.stopped().await;
+        let error = client.get_payload_stats(1).await.unwrap_err();
+        assert!(matches!(error, AndeRpcClientError::Transport(_)), "{error:?}");
+        assert_eq!(error.code(), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! Consensus RPC
+//!
+//! `ande_getValidators` and `ande_getProducerSchedule` serve the consensus
+//! data followed by the consensus client to explorers and frontends, without
+//! them talking to the consensus contract. Neither needs a wallet. Nodes
+//! without a consensus client answer both with
+//! [`CONSENSUS_NOT_CONFIGURED_CODE`].
+
+use super::{
+    mev::invalid_params,
+    validator::{ValidatorStatusSource, CONSENSUS_UNAVAILABLE_CODE},
+};
+use crate::consensus_client::ValidatorInfo;
+use alloy_primitives::{Address, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Error code returned when the node runs without a consensus client (EIP-1474 "method not supported")
+pub const CONSENSUS_NOT_CONFIGURED_CODE: i32 = -32004;
+
+/// Most blocks `ande_getProducerSchedule` returns in one call
+pub const MAX_PRODUCER_SCHEDULE_BLOCKS: u64 = 256;
+
+/// Active validator, as returned by `ande_getValidators`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcActiveValidator {
+    /// Address of the validator
+    pub address: Address,
+    /// Staked amount, in wei
+    pub stake: U256,
+    /// Voting power
+    pub power: U256,
+    /// Uptime, in basis points
+    pub uptime: U256,
+    /// Whether the validator is jailed
+    pub jailed: bool,
+    /// libp2p peer ID
+    pub p2p_peer_id: B256,
+    /// RPC endpoint URL
+    pub rpc_endpoint: String,
+}
+
+impl From<ValidatorInfo> for RpcActiveValidator {
+    fn from(info: ValidatorInfo) -> Self {
+        Self {
+            address: info.validator,
+            stake: info.stake,
+            power: info.power,
+            uptime: info.uptime,
+            jailed: info.jailed,
+            p2p_peer_id: info.p2p_peer_id,
+            rpc_endpoint: info.rpc_endpoint,
+        }
+    }
+}
+
+/// Producer designated for a block, as returned by `ande_getProducerSchedule`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledProducer {
+    /// Number of the block
+    pub block_number: U64,
+    /// Validator designated to produce it
+    pub producer: Address,
+}
+
+/// ANDE consensus RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeConsensusApi {
+    /// Active validators, as last synced by the consensus client, with their on-chain records
+    #[method(name = "getValidators")]
+    async fn get_validators(&self) -> RpcResult<Vec<RpcActiveValidator>>;
+
+    /// Producers designated for the `count` blocks from `from_block`, at most
+    /// [`MAX_PRODUCER_SCHEDULE_BLOCKS`]
+    #[method(name = "getProducerSchedule")]
+    async fn get_producer_schedule(&self, from_block: U64, count: U64) -> RpcResult<Vec<ScheduledProducer>>;
+}
+
+/// Implementation of the ANDE consensus RPC API
+#[derive(Debug, Clone)]
+pub struct AndeConsensusApiImpl {
+    /// Consensus client the data is read from; `None` if the node runs without one
+    consensus: Option<Arc<dyn ValidatorStatusSource>>,
+}
+
+impl AndeConsensusApiImpl {
+    /// Creates a new instance reading from `consensus`, if the node runs a consensus client
+    pub fn new(consensus: Option<Arc<dyn ValidatorStatusSource>>) -> Self {
+        Self { consensus }
+    }
+
+    fn consensus(&self) -> RpcResult<&Arc<dyn ValidatorStatusSource>> {
+        self.consensus.as_ref().ok_or_else(|| {
+            ErrorObjectOwned::owned(
+                CONSENSUS_NOT_CONFIGURED_CODE,
+                "No consensus client is configured on this node",
+                None::<()>,
+            )
+        })
+    }
+}
+
+fn consensus_unavailable(message: String) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(CONSENSUS_UNAVAILABLE_CODE, message, None::<()>)
+}
+
+#[async_trait]
+impl AndeConsensusApiServer for AndeConsensusApiImpl {
+    async fn get_validators(&self) -> RpcResult<Vec<RpcActiveValidator>> {
+        let consensus = self.consensus()?;
+        let mut validators = Vec::new();
+        for address in consensus.cached_validators().await {
+            let info = consensus.validator_details(address).await.map_err(|error| {
+                consensus_unavailable(format!("Failed to read validator {address}: {error}"))
+            })?;
+            validators.push(info.into());
+        }
+        Ok(validators)
+    }
+
+    async fn get_producer_schedule(&self, from_block: U64, count: U64) -> RpcResult<Vec<ScheduledProducer>> {
+        let consensus = self.consensus()?;
+        let (from, count) = (from_block.to::<u64>(), count.to::<u64>());
+        if count > MAX_PRODUCER_SCHEDULE_BLOCKS {
+            return Err(invalid_params(format!(
+                "Count {count} exceeds the maximum of {MAX_PRODUCER_SCHEDULE_BLOCKS} blocks"
+            )));
+        }
+
+        let mut schedule = Vec::new();
+        for block_number in from..from.saturating_add(count) {
+            let producer = consensus.block_producer(block_number).await.map_err(|error| {
+                consensus_unavailable(format!("Failed to read the producer of block {block_number}: {error}"))
+            })?;
+            schedule.push(ScheduledProducer { block_number: U64::from(block_number), producer });
+        }
+        Ok(schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::validator::RpcValidatorInfo;
+    use alloy_primitives::FixedBytes;
+    use jsonrpsee::{core::server::MethodsError, types::error::INVALID_PARAMS_CODE};
+    use serde_json::{json, Value};
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Consensus client with a cached schedule, computing the other blocks on demand
+    #[derive(Debug, Default)]
+    struct StubConsensus {
+        active: Vec<Address>,
+        infos: HashMap<Address, ValidatorInfo>,
+        cached: HashMap<u64, Address>,
+        computed: AtomicUsize,
+        unavailable: bool,
+    }
+
+    #[async_trait]
+    impl ValidatorStatusSource for StubConsensus {
+        async fn cached_validators(&self) -> Vec<Address> {
+            self.active.clone()
+        }
+
+        async fn validator_info(&self, validator: Address) -> eyre::Result<RpcValidatorInfo> {
+            Ok(self.validator_details(validator).await?.into())
+        }
+
+        async fn validator_details(&self, validator: Address) -> eyre::Result<ValidatorInfo> {
+            if self.unavailable {
+                eyre::bail!("connection refused");
+            }
+            self.infos.get(&validator).cloned().ok_or_else(|| eyre::eyre!("unknown validator"))
+        }
+
+        async fn next_scheduled_block(&self, producer: Address) -> Option<u64> {
+            self.cached.iter().filter(|(_, scheduled)| **scheduled == producer).map(|(block, _)| *block).min()
+        }
+
+        async fn block_producer(&self, block_number: u64) -> eyre::Result<Address> {
+            if let Some(producer) = self.cached.get(&block_number) {
+                return Ok(*producer);
+            }
+            if self.unavailable {
+                eyre::bail!("connection refused");
+            }
+            // Alternates between the active validators
+            self.computed.fetch_add(1, Ordering::Relaxed);
+            Ok(self.active[block_number as usize % self.active.len()])
+        }
+    }
+
+    fn validator(byte: u8, jailed: bool) -> ValidatorInfo {
+        ValidatorInfo {
+            validator: Address::repeat_byte(byte),
+            p2p_peer_id: FixedBytes::repeat_byte(byte),
+            rpc_endpoint: format!("https://validator-{byte}.ande.network"),
+            stake: U256::from(1_000),
+            power: U256::from(byte),
+            accumulated_priority: 0,
+            total_blocks_produced: U256::from(100),
+            total_blocks_missed: U256::from(1),
+            uptime: U256::from(9_900),
+            last_block_produced: U256::ZERO,
+            registered_at: U256::ZERO,
+            jailed,
+            active: true,
+            is_permanent: false,
+        }
+    }
+
+    fn consensus() -> StubConsensus {
+        let infos = [validator(1, false), validator(2, true)];
+        StubConsensus {
+            active: infos.iter().map(|info| info.validator).collect(),
+            cached: HashMap::from([(10, Address::repeat_byte(2)), (11, Address::repeat_byte(2))]),
+            infos: infos.into_iter().map(|info| (info.validator, info)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn error(error: MethodsError) -> (i32, String) {
+        match error {
+            MethodsError::JsonRpc(error) => (error.code(), error.message().to_string()),
+            other => panic!("Expected a JSON-RPC error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_validators() {
+        let module = AndeConsensusApiImpl::new(Some(Arc::new(consensus()))).into_rpc();
+        let validators: Value = module.call("ande_getValidators", ()).await.unwrap();
+
+        assert_eq!(validators.as_array().unwrap().len(), 2);
+        assert_eq!(
+            validators[1],
+            json!({
+                "address": format!("{:?}", Address::repeat_byte(2)),
+                "stake": "0x3e8",
+                "power": "0x2",
+                "uptime": "0x26ac",
+                "jailed": true,
+                "p2pPeerId": format!("{:?}", B256::repeat_byte(2)),
+                "rpcEndpoint": "https://validator-2.ande.network",
+            })
+        );
+
+        let unavailable = StubConsensus { unavailable: true, ..consensus() };
+        let module = AndeConsensusApiImpl::new(Some(Arc::new(unavailable))).into_rpc();
+        let (code, _) = error(module.call::<_, Value>("ande_getValidators", ()).await.unwrap_err());
+        assert_eq!(code, CONSENSUS_UNAVAILABLE_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_get_producer_schedule() {
+        let consensus = Arc::new(consensus());
+        let module = AndeConsensusApiImpl::new(Some(consensus.clone())).into_rpc();
+
+        let schedule: Vec<ScheduledProducer> =
+            module.call("ande_getProducerSchedule", (U64::from(10), U64::from(4))).await.unwrap();
+        let producers: Vec<(u64, u8)> =
+            schedule.iter().map(|scheduled| (scheduled.block_number.to(), scheduled.producer[19])).collect();
+        assert_eq!(producers, [(10, 2), (11, 2), (12, 1), (13, 2)]);
+        // Only the blocks missing from the schedule were computed
+        assert_eq!(consensus.computed.load(Ordering::Relaxed), 2);
+
+        let json: Value = module.call("ande_getProducerSchedule", (U64::from(10), U64::from(1))).await.unwrap();
+        assert_eq!(json, json!([{ "blockNumber": "0xa", "producer": format!("{:?}", Address::repeat_byte(2)) }]));
+
+        let empty: Vec<ScheduledProducer> =
+            module.call("ande_getProducerSchedule", (U64::from(10), U64::ZERO)).await.unwrap();
+        assert!(empty.is_empty());
+
+        let too_many = (U64::from(10), U64::from(MAX_PRODUCER_SCHEDULE_BLOCKS + 1));
+        let (code, _) = error(module.call::<_, Value>("ande_getProducerSchedule", too_many).await.unwrap_err());
+        assert_eq!(code, INVALID_PARAMS_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_without_consensus_client() {
+        let module = AndeConsensusApiImpl::new(None).into_rpc();
+
+        let (code, message) = error(module.call::<_, Value>("ande_getValidators", ()).await.unwrap_err());
+        assert_eq!(code, CONSENSUS_NOT_CONFIGURED_CODE);
+        assert_eq!(message, "No consensus client is configured on this node");
+        let params = (U64::from(10), U64::from(1));
+        let (code, _) = error(module.call::<_, Value>("ande_getProducerSchedule", params).await.unwrap_err());
+        assert_eq!(code, CONSENSUS_NOT_CONFIGURED_CODE);
+    }
+}
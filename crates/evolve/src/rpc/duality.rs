@@ -0,0 +1,454 @@
+//! Token Duality RPC
+//!
+//! `ande_getDualityCaps`, `ande_getDualityConfig` and `ande_getDualityTransfers`
+//! expose how the ANDE precompile moves native balance for the ANDEToken: the
+//! caps and allow-list it enforces, how much of the block caps is used, and
+//! the transfers of a block, decoded from the `Transfer` logs the precompile
+//! emits.
+//!
+//! The amount transferred in the current block comes from the
+//! [`BlockTransferLedger`] the precompile inspectors account in, when the RPC
+//! is given one; otherwise it is summed from the transfers logged in the
+//! latest block.
+
+use crate::evm_config::{precompile::decode_transfer_log, AndePrecompileConfig, BlockTransferLedger};
+use alloy_primitives::{Address, Log, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_ethereum_primitives::Receipt;
+use reth_primitives::TransactionSigned;
+use reth_storage_api::{BlockNumReader, ReceiptProvider, TransactionsProvider};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Caps of the ANDE precompile and their use in the current block, as
+/// returned by `ande_getDualityCaps`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DualityCaps {
+    /// Most a single call can transfer, in wei
+    pub per_call_cap: U256,
+    /// Most the calls of a block can transfer, if capped
+    pub per_block_cap: Option<U256>,
+    /// Most a single caller can transfer in a block, if capped
+    pub per_caller_block_cap: Option<U256>,
+    /// Block the transferred amount is for
+    pub block_number: U64,
+    /// Amount transferred in the block so far
+    pub transferred_this_block: U256,
+    /// Amount the block can still transfer, if capped
+    pub remaining_block_cap: Option<U256>,
+}
+
+/// Callers the ANDE precompile accepts, as returned by `ande_getDualityConfig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DualityConfig {
+    /// Address of the precompile
+    pub precompile_address: Address,
+    /// Address of the ANDEToken contract
+    pub ande_token_address: Address,
+    /// Callers authorized, the ANDEToken included, sorted
+    pub allow_list: Vec<Address>,
+    /// Whether unauthorized callers are rejected
+    pub strict_validation: bool,
+    /// Whether transfers are logged, and so returned by `ande_getDualityTransfers`
+    pub emit_transfer_logs: bool,
+}
+
+impl From<&AndePrecompileConfig> for DualityConfig {
+    fn from(config: &AndePrecompileConfig) -> Self {
+        Self {
+            precompile_address: config.precompile_address,
+            ande_token_address: config.ande_token_address,
+            allow_list: config.authorized_callers().into_iter().collect(),
+            strict_validation: config.strict_validation,
+            emit_transfer_logs: config.emit_transfer_logs,
+        }
+    }
+}
+
+/// Transfer through the ANDE precompile, as returned by `ande_getDualityTransfers`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DualityTransfer {
+    /// Hash of the transaction the transfer was made in
+    pub transaction_hash: B256,
+    /// Index of the transaction in the block
+    pub transaction_index: U64,
+    /// Index of the transfer's log in the block
+    pub log_index: U64,
+    /// Account debited
+    pub from: Address,
+    /// Account credited
+    pub to: Address,
+    /// Amount transferred, in wei
+    pub value: U256,
+}
+
+/// Logs of the blocks of the chain
+pub trait DualityLogSource: Send + Sync + 'static {
+    /// Number of the latest block
+    fn latest_block_number(&self) -> eyre::Result<u64>;
+
+    /// Hash of each transaction of block `number` with the logs of its
+    /// receipt, in block order; `None` if the block is unknown
+    fn block_logs(&self, number: u64) -> eyre::Result<Option<Vec<(B256, Vec<Log>)>>>;
+}
+
+impl<Client> DualityLogSource for Client
+where
+    Client: BlockNumReader
+        + TransactionsProvider<Transaction = TransactionSigned>
+        + ReceiptProvider<Receipt = Receipt>
+        + 'static,
+{
+    fn latest_block_number(&self) -> eyre::Result<u64> {
+        Ok(self.best_block_number()?)
+    }
+
+    fn block_logs(&self, number: u64) -> eyre::Result<Option<Vec<(B256, Vec<Log>)>>> {
+        let (Some(transactions), Some(receipts)) =
+            (self.transactions_by_block(number.into())?, self.receipts_by_block(number.into())?)
+        else {
+            return Ok(None);
+        };
+        let hashes = transactions.iter().map(|transaction| *transaction.hash());
+        Ok(Some(hashes.zip(receipts.into_iter().map(|receipt| receipt.logs)).collect()))
+    }
+}
+
+/// ANDE token duality RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeDualityApi {
+    /// Caps of the precompile and the amount transferred in the current block
+    #[method(name = "getDualityCaps")]
+    async fn get_duality_caps(&self) -> RpcResult<DualityCaps>;
+
+    /// Allow-list and strictness of the precompile
+    #[method(name = "getDualityConfig")]
+    async fn get_duality_config(&self) -> RpcResult<DualityConfig>;
+
+    /// Transfers through the precompile in block `block_number`, `null` if
+    /// the block is unknown
+    #[method(name = "getDualityTransfers")]
+    async fn get_duality_transfers(&self, block_number: U64) -> RpcResult<Option<Vec<DualityTransfer>>>;
+}
+
+/// Implementation of the ANDE token duality RPC API
+#[derive(Debug)]
+pub struct AndeDualityApiImpl<Source> {
+    /// Blocks the transfers are read from
+    source: Arc<Source>,
+    /// Configuration of the precompile
+    config: AndePrecompileConfig,
+    /// Transfers of the block being executed, if accounted for on this node
+    ledger: Option<Arc<BlockTransferLedger>>,
+}
+
+impl<Source> Clone for AndeDualityApiImpl<Source> {
+    fn clone(&self) -> Self {
+        Self { source: Arc::clone(&self.source), config: self.config.clone(), ledger: self.ledger.clone() }
+    }
+}
+
+impl<Source: DualityLogSource> AndeDualityApiImpl<Source> {
+    /// Creates a new instance serving the precompile configured by `config`,
+    /// reading transfers from the blocks of `source`
+    pub fn new(source: Source, config: AndePrecompileConfig) -> Self {
+        Self { source: Arc::new(source), config, ledger: None }
+    }
+
+    /// Report the amount transferred in the current block from `ledger`
+    /// instead of the latest block's logs
+    pub fn with_transfer_ledger(mut self, ledger: Arc<BlockTransferLedger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    fn transfers(&self, block_number: u64) -> RpcResult<Option<Vec<DualityTransfer>>> {
+        let logs = self
+            .source
+            .block_logs(block_number)
+            .map_err(|err| internal_error(format!("Failed to read the logs of block {block_number}: {err}")))?;
+        let Some(logs) = logs else {
+            return Ok(None);
+        };
+
+        let mut transfers = Vec::new();
+        let logs = logs
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (hash, logs))| logs.iter().map(move |log| (index, hash, log)));
+        for (log_index, (transaction_index, transaction_hash, log)) in logs.enumerate() {
+            if let Some((from, to, value)) = decode_transfer_log(log) {
+                transfers.push(DualityTransfer {
+                    transaction_hash: *transaction_hash,
+                    transaction_index: U64::from(transaction_index),
+                    log_index: U64::from(log_index),
+                    from,
+                    to,
+                    value,
+                });
+            }
+        }
+        Ok(Some(transfers))
+    }
+}
+
+fn internal_error(message: String) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, message, None::<()>)
+}
+
+#[async_trait]
+impl<Source: DualityLogSource> AndeDualityApiServer for AndeDualityApiImpl<Source> {
+    async fn get_duality_caps(&self) -> RpcResult<DualityCaps> {
+        let (block_number, transferred) = match &self.ledger {
+            Some(ledger) => (ledger.block_number(), ledger.transferred()),
+            None => {
+                let latest = self
+                    .source
+                    .latest_block_number()
+                    .map_err(|err| internal_error(format!("Failed to read the latest block: {err}")))?;
+                let transfers = self.transfers(latest)?.unwrap_or_default();
+                (latest, transfers.iter().fold(U256::ZERO, |total, transfer| total.saturating_add(transfer.value)))
+            }
+        };
+
+        Ok(DualityCaps {
+            per_call_cap: self.config.per_call_cap,
+            per_block_cap: self.config.per_block_cap,
+            per_caller_block_cap: self.config.per_caller_block_cap,
+            block_number: U64::from(block_number),
+            transferred_this_block: transferred,
+            remaining_block_cap: self.config.per_block_cap.map(|cap| cap.saturating_sub(transferred)),
+        })
+    }
+
+    async fn get_duality_config(&self) -> RpcResult<DualityConfig> {
+        Ok(DualityConfig::from(&self.config))
+    }
+
+    async fn get_duality_transfers(&self, block_number: U64) -> RpcResult<Option<Vec<DualityTransfer>>> {
+        self.transfers(block_number.to())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm_config::{
+        precompile::{transfer_log, TRANSFER_SELECTOR},
+        AndeBlockExecutorFactory, AndePrecompileInspector, AndePrecompileProvider, ANDE_PRECOMPILE_ADDRESS,
+    };
+    use alloy_evm::{Evm, EvmEnv};
+    use alloy_primitives::{Bytes, TxKind};
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_evm::ConfigureEvm;
+    use revm::{
+        context::TxEnv,
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+        state::AccountInfo,
+    };
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    const HOLDER: Address = Address::repeat_byte(0xaa);
+    const BLOCK: u64 = 5;
+
+    /// Chain whose blocks are fixtures
+    #[derive(Debug, Default)]
+    struct TestChain {
+        latest: u64,
+        blocks: HashMap<u64, Vec<(B256, Vec<Log>)>>,
+    }
+
+    impl DualityLogSource for TestChain {
+        fn latest_block_number(&self) -> eyre::Result<u64> {
+            Ok(self.latest)
+        }
+
+        fn block_logs(&self, number: u64) -> eyre::Result<Option<Vec<(B256, Vec<Log>)>>> {
+            Ok(self.blocks.get(&number).cloned())
+        }
+    }
+
+    fn config() -> AndePrecompileConfig {
+        let mut config = AndePrecompileConfig::insecure_unrestricted();
+        config.per_call_cap = U256::from(700);
+        config.per_block_cap = Some(U256::from(1_000));
+        config.per_caller_block_cap = Some(U256::from(2_000));
+        config
+    }
+
+    fn executor_factory() -> AndeBlockExecutorFactory {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default().chain(MAINNET.chain).genesis(Default::default()).cancun_activated().build(),
+        );
+        let provider = AndePrecompileProvider::new(SpecId::CANCUN, config());
+        AndeBlockExecutorFactory::with_precompile_provider(chain_spec, provider)
+    }
+
+    /// Execute the transfers of `values` from [`HOLDER`] in block [`BLOCK`],
+    /// each in its own transaction watched by an inspector of `factory`, and
+    /// return the block with the logs of the transactions that succeeded
+    fn execute_block(factory: &AndeBlockExecutorFactory, values: &[u64]) -> Vec<(B256, Vec<Log>)> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(HOLDER, AccountInfo { balance: U256::from(10_000), ..Default::default() });
+        let mut env = EvmEnv::default();
+        env.block_env.number = U256::from(BLOCK);
+        let mut evm = factory.evm_config().evm_with_env_and_inspector(&mut db, env, factory.inspector());
+
+        let mut block = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            let recipient = Address::with_last_byte(index as u8 + 1);
+            let data: Bytes = [
+                &TRANSFER_SELECTOR[..],
+                HOLDER.into_word().as_slice(),
+                recipient.into_word().as_slice(),
+                &U256::from(*value).to_be_bytes::<32>(),
+            ]
+            .concat()
+            .into();
+            let tx = TxEnv {
+                caller: HOLDER,
+                kind: TxKind::Call(ANDE_PRECOMPILE_ADDRESS),
+                data,
+                gas_limit: 1_000_000,
+                nonce: index as u64,
+                ..Default::default()
+            };
+            let outcome = evm.transact_commit(tx).unwrap();
+            if outcome.is_success() {
+                block.push((B256::with_last_byte(index as u8), outcome.into_logs()));
+            }
+        }
+        block
+    }
+
+    #[tokio::test]
+    async fn test_caps_match_inspector_accounting() {
+        let factory = executor_factory();
+        // The last transfer exceeds what is left of the block cap
+        let block = execute_block(&factory, &[300, 600, 200]);
+        assert_eq!(block.len(), 2);
+        let inspector = AndePrecompileInspector::with_ledger(config(), factory.transfer_ledger().clone());
+        assert_eq!(inspector.transferred_this_block(), U256::from(900));
+
+        let chain = TestChain { latest: BLOCK, blocks: HashMap::from([(BLOCK, block)]) };
+        let module = AndeDualityApiImpl::new(chain, factory.precompile_provider().config().clone())
+            .with_transfer_ledger(factory.transfer_ledger().clone())
+            .into_rpc();
+        let caps: DualityCaps = module.call("ande_getDualityCaps", ()).await.unwrap();
+        assert_eq!(
+            caps,
+            DualityCaps {
+                per_call_cap: U256::from(700),
+                per_block_cap: Some(U256::from(1_000)),
+                per_caller_block_cap: Some(U256::from(2_000)),
+                block_number: U64::from(BLOCK),
+                transferred_this_block: inspector.transferred_this_block(),
+                remaining_block_cap: Some(U256::from(100)),
+            }
+        );
+        let remaining = caps.remaining_block_cap.unwrap();
+        assert!(factory.transfer_ledger().reserve(&config(), BLOCK, HOLDER, remaining + U256::from(1)).is_err());
+        assert!(factory.transfer_ledger().reserve(&config(), BLOCK, HOLDER, remaining).is_ok());
+        let caps: DualityCaps = module.call("ande_getDualityCaps", ()).await.unwrap();
+        assert_eq!(caps.remaining_block_cap, Some(U256::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_caps_from_latest_block_without_ledger() {
+        let factory = executor_factory();
+        let block = execute_block(&factory, &[300, 600, 200]);
+        let chain = TestChain { latest: BLOCK, blocks: HashMap::from([(BLOCK, block)]) };
+        let module = AndeDualityApiImpl::new(chain, config()).into_rpc();
+
+        // The logged transfers add up to what the ledger accounted for
+        let caps: DualityCaps = module.call("ande_getDualityCaps", ()).await.unwrap();
+        assert_eq!(caps.block_number, U64::from(BLOCK));
+        assert_eq!(caps.transferred_this_block, factory.transfer_ledger().transferred());
+        assert_eq!(caps.remaining_block_cap, Some(U256::from(100)));
+
+        // Uncapped blocks have nothing remaining to report
+        let chain = TestChain { latest: 1, ..Default::default() };
+        let module = AndeDualityApiImpl::new(chain, AndePrecompileConfig::insecure_unrestricted()).into_rpc();
+        let caps: Value = module.call("ande_getDualityCaps", ()).await.unwrap();
+        assert_eq!(
+            caps,
+            json!({
+                "perCallCap": U256::MAX,
+                "perBlockCap": null,
+                "perCallerBlockCap": null,
+                "blockNumber": "0x1",
+                "transferredThisBlock": "0x0",
+                "remainingBlockCap": null,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_duality_config() {
+        let mut config = config();
+        config.set_ande_token_address(Address::repeat_byte(0x22));
+        config.allow_list.insert(Address::repeat_byte(0x11));
+        let module = AndeDualityApiImpl::new(TestChain::default(), config).into_rpc();
+
+        let config: Value = module.call("ande_getDualityConfig", ()).await.unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "precompileAddress": ANDE_PRECOMPILE_ADDRESS,
+                "andeTokenAddress": Address::repeat_byte(0x22),
+                "allowList": [Address::repeat_byte(0x11), Address::repeat_byte(0x22)],
+                "strictValidation": false,
+                "emitTransferLogs": true,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_duality_transfers() {
+        let (from, to) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let mut token_transfer = transfer_log(from, to, U256::from(5));
+        token_transfer.address = Address::repeat_byte(0x22);
+        let block = vec![
+            (B256::repeat_byte(1), vec![token_transfer, transfer_log(from, to, U256::from(5))]),
+            (B256::repeat_byte(2), vec![]),
+            (B256::repeat_byte(3), vec![transfer_log(to, from, U256::from(2))]),
+        ];
+        let chain = TestChain { latest: BLOCK, blocks: HashMap::from([(BLOCK, block)]) };
+        let module = AndeDualityApiImpl::new(chain, config()).into_rpc();
+
+        let transfers: Option<Vec<DualityTransfer>> =
+            module.call("ande_getDualityTransfers", (U64::from(BLOCK),)).await.unwrap();
+        assert_eq!(
+            transfers.unwrap(),
+            [
+                DualityTransfer {
+                    transaction_hash: B256::repeat_byte(1),
+                    transaction_index: U64::ZERO,
+                    log_index: U64::from(1),
+                    from,
+                    to,
+                    value: U256::from(5),
+                },
+                DualityTransfer {
+                    transaction_hash: B256::repeat_byte(3),
+                    transaction_index: U64::from(2),
+                    log_index: U64::from(2),
+                    from: to,
+                    to: from,
+                    value: U256::from(2),
+                },
+            ]
+        );
+
+        let unknown: Value = module.call("ande_getDualityTransfers", (U64::from(BLOCK + 1),)).await.unwrap();
+        assert_eq!(unknown, Value::Null);
+    }
+}
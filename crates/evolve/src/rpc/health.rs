@@ -0,0 +1,665 @@
+//! Health RPC
+//!
+//! `ande_health` reports on the Evolve subsystems for orchestration probes,
+//! which `eth_blockNumber` says nothing about: the connection to the
+//! consensus contract, the attestation retry queue, how fresh the validator
+//! set is, the connection to the MEV auction and distributor contracts, and
+//! the parallel executor's worker threads.
+//!
+//! Each subsystem is graded against [`HealthThresholds`], and the overall
+//! status is the worst grade. Subsystems are registered in a
+//! [`HealthRegistry`] as the node starts them; those it doesn't run are
+//! reported as `null` and don't count. Losing the MEV contracts only degrades
+//! the node, as blocks are still produced without them.
+//!
+//! reth's RPC server doesn't let the node add HTTP routes, so there is no
+//! plain HTTP readiness handler: probes call `ande_health` over JSON-RPC.
+
+use super::validator::AttestationStatusSource;
+use alloy_primitives::U64;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Grade of a subsystem, or of the node as a whole, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthStatus {
+    /// Working as expected
+    Healthy,
+    /// Working, but lagging or without a non-essential subsystem
+    Degraded,
+    /// Not fit to serve
+    Unhealthy,
+}
+
+/// Limits the subsystems are graded against by `ande_health`
+///
+/// A subsystem is degraded from the first limit of a pair, and unhealthy from
+/// the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthThresholds {
+    /// Age of the last successful consensus contract call from which the
+    /// consensus client is degraded
+    #[serde(rename = "consensus_call_degraded_secs", with = "duration_secs")]
+    pub consensus_call_degraded: Duration,
+    /// Age of the last successful consensus contract call from which the
+    /// consensus client is unhealthy
+    #[serde(rename = "consensus_call_unhealthy_secs", with = "duration_secs")]
+    pub consensus_call_unhealthy: Duration,
+    /// Attestations waiting to be retried from which attestation is degraded
+    pub attestation_queue_degraded: usize,
+    /// Attestations waiting to be retried from which attestation is unhealthy
+    pub attestation_queue_unhealthy: usize,
+    /// Time since the validator set was last synced from which it is degraded
+    #[serde(rename = "validator_sync_degraded_secs", with = "duration_secs")]
+    pub validator_sync_degraded: Duration,
+    /// Time since the validator set was last synced from which it is unhealthy
+    #[serde(rename = "validator_sync_unhealthy_secs", with = "duration_secs")]
+    pub validator_sync_unhealthy: Duration,
+}
+
+impl HealthThresholds {
+    /// Default thresholds
+    pub const fn new() -> Self {
+        Self {
+            consensus_call_degraded: Duration::from_secs(60),
+            consensus_call_unhealthy: Duration::from_secs(300),
+            attestation_queue_degraded: 10,
+            attestation_queue_unhealthy: 100,
+            // The validator set is polled every 30 seconds
+            validator_sync_degraded: Duration::from_secs(90),
+            validator_sync_unhealthy: Duration::from_secs(600),
+        }
+    }
+
+    /// Checks every subsystem is degraded before it is unhealthy
+/// Errors of [`HealthThresholds::validate`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Health threshold {name} is degraded from {degraded} but unhealthy from {unhealthy}")]
+pub struct HealthThresholdsError {
+    /// Name of the pair of thresholds
+    pub name: &'static str,
+    /// Limit from which the subsystem is degraded
+    pub degraded: String,
+    /// Limit from which the subsystem is unhealthy, below the degraded one
+    pub unhealthy: String,
+}
+
+    pub fn validate(&self) -> Result<(), HealthThresholdsError> {
+        let durations = [
+            ("consensus_call", self.consensus_call_degraded, self.consensus_call_unhealthy),
+            ("validator_sync", self.validator_sync_degraded, self.validator_sync_unhealthy),
+        ];
+        for (name, degraded, unhealthy) in durations {
+            if degraded > unhealthy {
+                return Err(HealthThresholdsError {
+                    name,
+                    degraded: format!("{degraded:?}"),
+                    unhealthy: format!("{unhealthy:?}"),
+                });
+            }
+        }
+        if self.attestation_queue_degraded > self.attestation_queue_unhealthy {
+            return Err(HealthThresholdsError {
+                name: "attestation_queue",
+                degraded: self.attestation_queue_degraded.to_string(),
+                unhealthy: self.attestation_queue_unhealthy.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// (De)serializes a [`Duration`] as a whole number of seconds
+mod duration_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+/// Outcome of the calls a client makes to a node
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    calls: Mutex<CallTimes>,
+}
+
+#[derive(Debug, Default)]
+struct CallTimes {
+    /// When a call last reached the node
+    last_success: Option<Instant>,
+    /// When a call last failed to reach the node
+    last_failure: Option<Instant>,
+}
+
+impl ConnectionHealth {
+    /// Creates a tracker without any call
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a call that reached the node, when `connected`, or failed to
+    pub fn record(&self, connected: bool) {
+        self.record_at(connected, Instant::now());
+    }
+
+    /// Record a call made at `at`
+    pub fn record_at(&self, connected: bool, at: Instant) {
+        let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+        if connected {
+            calls.last_success = Some(at);
+        } else {
+            calls.last_failure = Some(at);
+        }
+    }
+
+    /// When a call last reached the node, if any did
+    pub fn last_success(&self) -> Option<Instant> {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).last_success
+    }
+
+    /// Whether no call failed since the last one that reached the node
+    pub fn is_connected(&self) -> bool {
+        let calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+        match (calls.last_success, calls.last_failure) {
+            (_, None) => true,
+            (Some(success), Some(failure)) => success >= failure,
+            (None, Some(_)) => false,
+        }
+    }
+}
+
+/// Consensus client, as seen by `ande_health`
+pub trait ConsensusHealthSource: fmt::Debug + Send + Sync {
+    /// Calls made to the consensus contract
+    fn connection(&self) -> &ConnectionHealth;
+
+    /// When the validator set was last known to be in sync with the contract,
+    /// if ever
+    fn last_validator_sync(&self) -> Option<Instant>;
+}
+
+/// Worker threads of the parallel executor, as seen by `ande_health`
+pub trait WorkerPoolHealthSource: fmt::Debug + Send + Sync {
+    /// Number of threads the pool runs
+    fn threads(&self) -> usize;
+
+    /// Number of threads currently running
+    fn alive_threads(&self) -> usize;
+}
+
+/// Connection to a contract's node, as reported by `ande_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionReport {
+    /// Whether the last call reached the node
+    pub connected: bool,
+    /// Time since a call last reached the node, in milliseconds; `None` if none did
+    pub last_success_age_ms: Option<U64>,
+    /// Grade of the connection
+    pub status: HealthStatus,
+}
+
+/// Attestation retry queue, as reported by `ande_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationReport {
+    /// Attestations waiting to be retried
+    pub queue_depth: U64,
+    /// Grade of the queue
+    pub status: HealthStatus,
+}
+
+/// Validator set synchronization, as reported by `ande_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSyncReport {
+    /// Time since the validator set was last synced, in milliseconds; `None`
+    /// if it never was
+    pub last_sync_age_ms: Option<U64>,
+    /// Grade of the synchronization
+    pub status: HealthStatus,
+}
+
+/// Parallel executor worker threads, as reported by `ande_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerPoolReport {
+    /// Number of threads the pool runs
+    pub threads: U64,
+    /// Number of threads currently running
+    pub alive_threads: U64,
+    /// Grade of the pool
+    pub status: HealthStatus,
+}
+
+/// Response of `ande_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// Worst grade of the subsystems the node runs
+    pub status: HealthStatus,
+    /// Connection to the consensus contract
+    pub consensus: Option<ConnectionReport>,
+    /// Attestation retry queue
+    pub attestation: Option<AttestationReport>,
+    /// Validator set synchronization
+    pub validator_sync: Option<ValidatorSyncReport>,
+    /// Connection to the MEV auction contract
+    pub mev_auction: Option<ConnectionReport>,
+    /// Connection to the MEV distributor contract
+    pub mev_distributor: Option<ConnectionReport>,
+    /// Parallel executor worker threads
+    pub worker_pool: Option<WorkerPoolReport>,
+}
+
+/// Subsystems reported by `ande_health`, registered as the node starts them,
+/// and the thresholds they are graded against
+#[derive(Debug, Default)]
+pub struct HealthRegistry {
+    thresholds: RwLock<HealthThresholds>,
+    consensus: RwLock<Option<Arc<dyn ConsensusHealthSource>>>,
+    attester: RwLock<Option<Arc<dyn AttestationStatusSource>>>,
+    mev_auction: RwLock<Option<Arc<ConnectionHealth>>>,
+    mev_distributor: RwLock<Option<Arc<ConnectionHealth>>>,
+    worker_pool: RwLock<Option<Arc<dyn WorkerPoolHealthSource>>>,
+}
+
+/// Replace the subsystem in `slot`
+fn register<T: ?Sized>(slot: &RwLock<Option<Arc<T>>>, source: Arc<T>) {
+    *slot.write().unwrap_or_else(PoisonError::into_inner) = Some(source);
+}
+
+/// Subsystem in `slot`, if registered
+fn registered<T: ?Sized>(slot: &RwLock<Option<Arc<T>>>) -> Option<Arc<T>> {
+    slot.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Grade of a value against the limits from which it is degraded and unhealthy
+fn grade<T: PartialOrd>(value: T, degraded: T, unhealthy: T) -> HealthStatus {
+    if value >= unhealthy {
+        HealthStatus::Unhealthy
+    } else if value >= degraded {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    }
+}
+
+fn age_ms(age: Duration) -> U64 {
+    U64::from(u64::try_from(age.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// Report on `connection` at `now`, graded against the limits on the age of
+/// the last successful call, if any
+fn connection_report(
+    connection: &ConnectionHealth,
+    now: Instant,
+    age_limits: Option<(Duration, Duration)>,
+) -> ConnectionReport {
+    let connected = connection.is_connected();
+    let age = connection.last_success().map(|success| now.saturating_duration_since(success));
+    let status = match (age_limits, age) {
+        (Some(_), None) if !connected => HealthStatus::Unhealthy,
+        (Some((degraded, unhealthy)), Some(age)) => {
+            let by_age = grade(age, degraded, unhealthy);
+            if connected {
+                by_age
+            } else {
+                by_age.max(HealthStatus::Degraded)
+            }
+        }
+        _ if connected => HealthStatus::Healthy,
+        _ => HealthStatus::Degraded,
+    };
+    ConnectionReport { connected, last_success_age_ms: age.map(age_ms), status }
+}
+
+impl HealthRegistry {
+    /// Creates a registry without any subsystem, grading against `thresholds`
+    pub fn new(thresholds: HealthThresholds) -> Self {
+        Self { thresholds: RwLock::new(thresholds), ..Default::default() }
+    }
+
+    /// Grade the subsystems against `thresholds` from now on
+    pub fn set_thresholds(&self, thresholds: HealthThresholds) {
+        *self.thresholds.write().unwrap_or_else(PoisonError::into_inner) = thresholds;
+    }
+
+    /// Thresholds the subsystems are graded against
+    pub fn thresholds(&self) -> HealthThresholds {
+        *self.thresholds.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Report on the consensus client and the validator set it syncs
+    pub fn register_consensus(&self, consensus: Arc<dyn ConsensusHealthSource>) {
+        register(&self.consensus, consensus);
+    }
+
+    /// Report on the attestation retry queue of `attester`
+    pub fn register_attester(&self, attester: Arc<dyn AttestationStatusSource>) {
+        register(&self.attester, attester);
+    }
+
+    /// Report on the connection of the MEV auction client
+    pub fn register_mev_auction(&self, connection: Arc<ConnectionHealth>) {
+        register(&self.mev_auction, connection);
+    }
+
+    /// Report on the connection of the MEV distributor client
+    pub fn register_mev_distributor(&self, connection: Arc<ConnectionHealth>) {
+        register(&self.mev_distributor, connection);
+    }
+
+    /// Report on the worker threads of the parallel executor
+    pub fn register_worker_pool(&self, pool: Arc<dyn WorkerPoolHealthSource>) {
+        register(&self.worker_pool, pool);
+    }
+
+    /// Report on the registered subsystems at `now`
+    pub fn report(&self, now: Instant) -> HealthReport {
+        let thresholds = self.thresholds();
+        let consensus_source = registered(&self.consensus);
+
+        let consensus = consensus_source.as_ref().map(|consensus| {
+            let limits = (thresholds.consensus_call_degraded, thresholds.consensus_call_unhealthy);
+            connection_report(consensus.connection(), now, Some(limits))
+        });
+        let validator_sync = consensus_source.as_ref().map(|consensus| {
+            let age = consensus.last_validator_sync().map(|sync| now.saturating_duration_since(sync));
+            let status = match age {
+                Some(age) => grade(age, thresholds.validator_sync_degraded, thresholds.validator_sync_unhealthy),
+                None => HealthStatus::Unhealthy,
+            };
+            ValidatorSyncReport { last_sync_age_ms: age.map(age_ms), status }
+        });
+        let attestation = registered(&self.attester).map(|attester| {
+            let depth = attester.pending_attestations();
+            AttestationReport {
+                queue_depth: U64::from(depth),
+                status: grade(depth, thresholds.attestation_queue_degraded, thresholds.attestation_queue_unhealthy),
+            }
+        });
+        let mev_auction = registered(&self.mev_auction).map(|auction| connection_report(&auction, now, None));
+        let mev_distributor =
+            registered(&self.mev_distributor).map(|distributor| connection_report(&distributor, now, None));
+        let worker_pool = registered(&self.worker_pool).map(|pool| {
+            let (threads, alive) = (pool.threads(), pool.alive_threads());
+            let status = match alive {
+                0 => HealthStatus::Unhealthy,
+                alive if alive < threads => HealthStatus::Degraded,
+                _ => HealthStatus::Healthy,
+            };
+            WorkerPoolReport { threads: U64::from(threads), alive_threads: U64::from(alive), status }
+        });
+
+        let status = [
+            consensus.as_ref().map(|report| report.status),
+            attestation.as_ref().map(|report| report.status),
+            validator_sync.as_ref().map(|report| report.status),
+            mev_auction.as_ref().map(|report| report.status),
+            mev_distributor.as_ref().map(|report| report.status),
+            worker_pool.as_ref().map(|report| report.status),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(HealthStatus::Healthy);
+
+        HealthReport { status, consensus, attestation, validator_sync, mev_auction, mev_distributor, worker_pool }
+    }
+}
+
+/// ANDE health RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeHealthApi {
+    /// State of the Evolve subsystems the node runs, with an overall status
+    #[method(name = "health")]
+    async fn health(&self) -> RpcResult<HealthReport>;
+}
+
+/// Implementation of the ANDE health RPC API
+#[derive(Debug, Clone)]
+pub struct AndeHealthApiImpl {
+    /// Subsystems reported on
+    registry: Arc<HealthRegistry>,
+}
+
+impl AndeHealthApiImpl {
+    /// Creates a new instance reporting on the subsystems of `registry`
+    pub fn new(registry: Arc<HealthRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl AndeHealthApiServer for AndeHealthApiImpl {
+    async fn health(&self) -> RpcResult<HealthReport> {
+        Ok(self.registry.report(Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[derive(Debug, Default)]
+    struct StubConsensus {
+        connection: ConnectionHealth,
+        last_sync: Option<Instant>,
+    }
+
+    impl ConsensusHealthSource for StubConsensus {
+        fn connection(&self) -> &ConnectionHealth {
+            &self.connection
+        }
+
+        fn last_validator_sync(&self) -> Option<Instant> {
+            self.last_sync
+        }
+    }
+
+    #[derive(Debug)]
+    struct StubAttester(usize);
+
+    impl AttestationStatusSource for StubAttester {
+        fn pending_attestations(&self) -> usize {
+            self.0
+        }
+
+        fn last_attested_block(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    #[derive(Debug)]
+    struct StubPool {
+        threads: usize,
+        alive: usize,
+    }
+
+    impl WorkerPoolHealthSource for StubPool {
+        fn threads(&self) -> usize {
+            self.threads
+        }
+
+        fn alive_threads(&self) -> usize {
+            self.alive
+        }
+    }
+
+    /// Far enough from boot for the call and sync times to be in the past
+    fn now() -> Instant {
+        Instant::now() + Duration::from_secs(3_600)
+    }
+
+    /// Consensus client whose last successful call and sync are `call_age` and `sync_age` old at `now`
+    fn consensus(now: Instant, call_age: u64, sync_age: u64) -> Arc<StubConsensus> {
+        let consensus = StubConsensus { last_sync: Some(now - Duration::from_secs(sync_age)), ..Default::default() };
+        consensus.connection.record_at(true, now - Duration::from_secs(call_age));
+        Arc::new(consensus)
+    }
+
+    #[test]
+    fn test_connection_health() {
+        let connection = ConnectionHealth::new();
+        let start = now();
+        assert!(connection.is_connected());
+        assert_eq!(connection.last_success(), None);
+
+        connection.record_at(false, start);
+        assert!(!connection.is_connected());
+        connection.record_at(true, start + Duration::from_secs(1));
+        assert!(connection.is_connected());
+        assert_eq!(connection.last_success(), Some(start + Duration::from_secs(1)));
+        connection.record_at(false, start + Duration::from_secs(2));
+        assert!(!connection.is_connected());
+        assert_eq!(connection.last_success(), Some(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_consensus_thresholds() {
+        let now = now();
+        let status = |call_age, sync_age| {
+            let registry = HealthRegistry::default();
+            registry.register_consensus(consensus(now, call_age, sync_age));
+            let report = registry.report(now);
+            (report.consensus.unwrap().status, report.validator_sync.unwrap().status, report.status)
+        };
+
+        use HealthStatus::*;
+        assert_eq!(status(5, 20), (Healthy, Healthy, Healthy));
+        assert_eq!(status(60, 20), (Degraded, Healthy, Degraded));
+        assert_eq!(status(5, 90), (Healthy, Degraded, Degraded));
+        assert_eq!(status(299, 599), (Degraded, Degraded, Degraded));
+        assert_eq!(status(300, 20), (Unhealthy, Healthy, Unhealthy));
+        assert_eq!(status(5, 600), (Healthy, Unhealthy, Unhealthy));
+
+        // A failed call degrades a recent connection
+        let failing = consensus(now, 5, 20);
+        failing.connection.record_at(false, now);
+        let registry = HealthRegistry::default();
+        registry.register_consensus(failing);
+        let report = registry.report(now);
+        let consensus = report.consensus.unwrap();
+        assert_eq!((consensus.connected, consensus.status), (false, Degraded));
+        assert_eq!(consensus.last_success_age_ms, Some(U64::from(5_000)));
+
+        // A client that never reached the contract, nor synced, is unhealthy
+        let unreachable = StubConsensus::default();
+        unreachable.connection.record_at(false, now);
+        let registry = HealthRegistry::default();
+        registry.register_consensus(Arc::new(unreachable));
+        let report = registry.report(now);
+        assert_eq!(report.consensus.unwrap().status, Unhealthy);
+        let validator_sync = report.validator_sync.unwrap();
+        assert_eq!((validator_sync.last_sync_age_ms, validator_sync.status), (None, Unhealthy));
+    }
+
+    #[test]
+    fn test_configured_thresholds() {
+        let now = now();
+        let registry = HealthRegistry::default();
+        registry.register_consensus(consensus(now, 30, 20));
+        registry.register_attester(Arc::new(StubAttester(5)));
+        assert_eq!(registry.report(now).status, HealthStatus::Healthy);
+
+        let strict = HealthThresholds {
+            consensus_call_degraded: Duration::from_secs(10),
+            attestation_queue_degraded: 2,
+            attestation_queue_unhealthy: 5,
+            ..Default::default()
+        };
+        registry.set_thresholds(strict);
+        let report = registry.report(now);
+        assert_eq!(report.consensus.unwrap().status, HealthStatus::Degraded);
+        assert_eq!(report.attestation.unwrap().status, HealthStatus::Unhealthy);
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_optional_subsystems() {
+        let now = now();
+        let registry = HealthRegistry::default();
+        // Nothing registered, nothing to fail
+        assert_eq!(registry.report(now).status, HealthStatus::Healthy);
+
+        // Losing the MEV contracts only degrades the node
+        let auction = Arc::new(ConnectionHealth::new());
+        auction.record_at(false, now);
+        registry.register_mev_auction(auction.clone());
+        registry.register_mev_distributor(Arc::new(ConnectionHealth::new()));
+        let report = registry.report(now);
+        assert_eq!(report.mev_auction.unwrap().status, HealthStatus::Degraded);
+        assert_eq!(report.mev_distributor.unwrap().status, HealthStatus::Healthy);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        auction.record_at(true, now);
+        assert_eq!(registry.report(now).status, HealthStatus::Healthy);
+
+        // Worker threads lost
+        registry.register_worker_pool(Arc::new(StubPool { threads: 4, alive: 3 }));
+        assert_eq!(registry.report(now).status, HealthStatus::Degraded);
+        registry.register_worker_pool(Arc::new(StubPool { threads: 4, alive: 0 }));
+        assert_eq!(registry.report(now).status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_thresholds_validation() {
+        assert_eq!(HealthThresholds::default().validate(), Ok(()));
+        let inverted =
+            HealthThresholds { attestation_queue_degraded: 50, attestation_queue_unhealthy: 20, ..Default::default() };
+        assert_eq!(inverted.validate().unwrap_err().name, "attestation_queue");
+        let inverted = HealthThresholds { validator_sync_degraded: Duration::from_secs(700), ..Default::default() };
+        assert_eq!(inverted.validate().unwrap_err().name, "validator_sync");
+
+        let parsed: HealthThresholds =
+            serde_json::from_value(json!({ "consensus_call_degraded_secs": 30, "attestation_queue_unhealthy": 50 }))
+                .unwrap();
+        assert_eq!(parsed.consensus_call_degraded, Duration::from_secs(30));
+        assert_eq!(parsed.attestation_queue_unhealthy, 50);
+        assert_eq!(parsed.validator_sync_degraded, HealthThresholds::default().validator_sync_degraded);
+    }
+
+    #[tokio::test]
+    async fn test_health_rpc() {
+        let registry = Arc::new(HealthRegistry::default());
+        registry.register_attester(Arc::new(StubAttester(12)));
+        registry.register_worker_pool(Arc::new(StubPool { threads: 4, alive: 4 }));
+        let module = AndeHealthApiImpl::new(registry).into_rpc();
+
+        let report: Value = module.call("ande_health", ()).await.unwrap();
+        assert_eq!(
+            report,
+            json!({
+                "status": "degraded",
+                "consensus": null,
+                "attestation": { "queueDepth": "0xc", "status": "degraded" },
+                "validatorSync": null,
+                "mevAuction": null,
+                "mevDistributor": null,
+                "workerPool": { "threads": "0x4", "aliveThreads": "0x4", "status": "healthy" },
+            })
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::mev::{MevAuctionClient, MevOpportunity, MevOpportunityStore, MevType, SearcherStats};
+use alloy_primitives::{Address, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// Error code returned when the MEV store fails
+pub const MEV_STORE_ERROR_CODE: i32 = -32000;
+
+/// Error code returned when the node doesn't run an MEV auction
+pub const MEV_AUCTION_UNAVAILABLE_CODE: i32 = -32001;
+
+/// Maximum number of blocks a single `ande_getMevStats` call may cover
+pub const MAX_MEV_STATS_BLOCK_RANGE: u64 = 10_000;
+
+/// Maximum number of searchers a single `ande_getTopSearchers` call may return
+pub const MAX_TOP_SEARCHERS: u64 = 100;
+
+/// Totals for one type of MEV
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevTypeStats {
+    /// Number of opportunities detected
+    pub count: U64,
+    /// Cumulative estimated value, in wei
+    pub value: U256,
+}
+
+/// Response of `ande_getMevStats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevStats {
+    /// First block of the range
+    pub from_block: U64,
+    /// Last block of the range
+    pub to_block: U64,
+    /// Number of blocks within the range the store has a record for
+    pub recorded_blocks: U64,
+    /// Totals per type of MEV, for types with at least one opportunity
+    pub by_type: BTreeMap<MevType, MevTypeStats>,
+}
+
+/// Opportunity as returned by `ande_getMevOpportunities`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMevOpportunity {
+    /// Type of MEV
+    pub mev_type: MevType,
+    /// Hash of the transaction the opportunity was detected in
+    pub tx_hash: B256,
+    /// Estimated value, in wei
+    pub value: U256,
+    /// Addresses involved
+    pub addresses: Vec<Address>,
+    /// Block the opportunity was detected in
+    pub block_number: U64,
+    /// Additional metadata, e.g. the front-run and back-run of a sandwich
+    pub metadata: HashMap<String, String>,
+}
+
+impl From<MevOpportunity> for RpcMevOpportunity {
+    fn from(opportunity: MevOpportunity) -> Self {
+        Self {
+            mev_type: opportunity.mev_type,
+            tx_hash: opportunity.tx_hash,
+            value: opportunity.value,
+            addresses: opportunity.addresses,
+            block_number: U64::from(opportunity.block_number),
+            metadata: opportunity.metadata,
+        }
+    }
+}
+
+/// Searcher ranking entry returned by `ande_getTopSearchers`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSearcherStats {
+    /// Searcher address
+    pub searcher: Address,
+    /// Bundles submitted, replacements included
+    pub bundles_submitted: U64,
+    /// Bundles executed
+    pub bundles_executed: U64,
+    /// Bundles rejected
+    pub bundles_rejected: U64,
+    /// Total bids paid for executed bundles, in wei
+    pub total_bids_paid: U256,
+    /// Average amount contested wins exceeded the second highest bid by, in wei
+    pub average_overbid: U256,
+    /// Bundles rejected since the searcher's last executed bundle
+    pub failure_streak: U64,
+}
+
+impl From<SearcherStats> for RpcSearcherStats {
+    fn from(stats: SearcherStats) -> Self {
+        Self {
+            searcher: stats.searcher,
+            bundles_submitted: U64::from(stats.bundles_submitted),
+            bundles_executed: U64::from(stats.bundles_executed),
+            bundles_rejected: U64::from(stats.bundles_rejected),
+            total_bids_paid: stats.total_bids_paid,
+            average_overbid: stats.average_overbid(),
+            failure_streak: U64::from(stats.failure_streak),
+        }
+    }
+}
+
+/// ANDE MEV RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeMevApi {
+    /// Totals per type of MEV detected between `from_block` and `to_block`, inclusive
+    #[method(name = "getMevStats")]
+    async fn get_mev_stats(&self, from_block: U64, to_block: U64) -> RpcResult<MevStats>;
+
+    /// Opportunities detected in `block_number`
+    #[method(name = "getMevOpportunities")]
+    async fn get_mev_opportunities(&self, block_number: U64) -> RpcResult<Vec<RpcMevOpportunity>>;
+
+    /// The `count` searchers who paid the most in auction bids
+    #[method(name = "getTopSearchers")]
+    async fn get_top_searchers(&self, count: U64) -> RpcResult<Vec<RpcSearcherStats>>;
+}
+
+/// Implementation of the ANDE MEV RPC API, reading from an MEV opportunity store
+#[derive(Debug, Clone)]
+pub struct AndeMevApiImpl {
+    /// Store the payload builder records detected opportunities in
+    store: Arc<dyn MevOpportunityStore>,
+    /// Auction searchers are ranked from, if the node runs one
+    auction: Option<Arc<MevAuctionClient>>,
+}
+
+impl AndeMevApiImpl {
+    /// Creates a new instance reading from `store`
+    pub fn new(store: Arc<dyn MevOpportunityStore>) -> Self {
+        Self { store, auction: None }
+    }
+
+    /// Rank searchers from `auction`
+    pub fn with_auction(mut self, auction: Arc<MevAuctionClient>) -> Self {
+        self.auction = Some(auction);
+        self
+    }
+}
+
+pub(crate) fn invalid_params(message: String) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, message, None::<()>)
+}
+
+fn store_error(error: impl std::fmt::Display) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(MEV_STORE_ERROR_CODE, format!("MEV store error: {error}"), None::<()>)
+}
+
+#[async_trait]
+impl AndeMevApiServer for AndeMevApiImpl {
+    async fn get_mev_stats(&self, from_block: U64, to_block: U64) -> RpcResult<MevStats> {
+        let (from, to) = (from_block.to::<u64>(), to_block.to::<u64>());
+        if from > to {
+            return Err(invalid_params(format!("fromBlock {from} is after toBlock {to}")));
+        }
+        if to - from >= MAX_MEV_STATS_BLOCK_RANGE {
+            return Err(invalid_params(format!(
+                "Block range {from}..={to} exceeds the maximum of {MAX_MEV_STATS_BLOCK_RANGE} blocks"
+            )));
+        }
+
+        let blocks = self.store.by_block(from..=to).map_err(store_error)?;
+        let mut by_type: BTreeMap<MevType, MevTypeStats> = BTreeMap::new();
+        for opportunity in blocks.iter().flat_map(|(_, opportunities)| opportunities) {
+            let stats = by_type.entry(opportunity.mev_type).or_default();
+            stats.count += U64::from(1);
+            stats.value = stats.value.saturating_add(opportunity.value);
+        }
+
+        Ok(MevStats {
+            from_block,
+            to_block,
+            recorded_blocks: U64::from(blocks.len()),
+            by_type,
+        })
+    }
+
+    async fn get_mev_opportunities(&self, block_number: U64) -> RpcResult<Vec<RpcMevOpportunity>> {
+        let block = block_number.to::<u64>();
+        let Some((_, opportunities)) =
+            self.store.by_block(block..=block).map_err(store_error)?.into_iter().next()
+        else {
+            return Err(invalid_params(format!("Block {block} is not in the MEV store")));
+        };
+
+        Ok(opportunities.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_top_searchers(&self, count: U64) -> RpcResult<Vec<RpcSearcherStats>> {
+        let Some(auction) = &self.auction else {
+            return Err(ErrorObjectOwned::owned(
+                MEV_AUCTION_UNAVAILABLE_CODE,
+                "MEV auction is not enabled",
+                None::<()>,
+            ));
+        };
+        let count = count.to::<u64>();
+        if count > MAX_TOP_SEARCHERS {
+            return Err(invalid_params(format!("Count {count} exceeds the maximum of {MAX_TOP_SEARCHERS}")));
+        }
+
+        Ok(auction.top_searchers(count as usize).await.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mev::store::MevStoreError;
+    use jsonrpsee::core::server::MethodsError;
+    use serde_json::{json, Value};
+    use std::ops::RangeInclusive;
+
+    /// Store serving a fixed set of blocks
+    #[derive(Debug, Default)]
+    struct MockStore {
+        blocks: BTreeMap<u64, Vec<MevOpportunity>>,
+    }
+
+    impl MevOpportunityStore for MockStore {
+        fn record(&self, _block: u64, _opportunities: Vec<MevOpportunity>) -> Result<(), MevStoreError> {
+            unreachable!("the RPC never writes to the store")
+        }
+
+        fn by_block(
+            &self,
+            range: RangeInclusive<u64>,
+        ) -> Result<Vec<(u64, Vec<MevOpportunity>)>, MevStoreError> {
+            Ok(self.blocks.range(range).map(|(block, opportunities)| (*block, opportunities.clone())).collect())
+        }
+    }
+
+    fn opportunity(mev_type: MevType, block: u64, value: u64) -> MevOpportunity {
+        let mut opportunity = MevOpportunity::new(mev_type, B256::with_last_byte(value as u8), U256::from(value), block);
+        opportunity.add_address(Address::with_last_byte(1));
+        opportunity
+    }
+
+    fn module() -> jsonrpsee::RpcModule<AndeMevApiImpl> {
+        let mut sandwich = opportunity(MevType::Sandwich, 11, 255);
+        sandwich.add_metadata("front_run_tx".to_string(), format!("{:?}", B256::with_last_byte(7)));
+        let store = MockStore {
+            blocks: BTreeMap::from([
+                (10, vec![opportunity(MevType::Arbitrage, 10, 16), opportunity(MevType::Sandwich, 10, 1)]),
+                (11, vec![sandwich]),
+                (12, Vec::new()),
+            ]),
+        };
+        AndeMevApiImpl::new(Arc::new(store)).into_rpc()
+    }
+
+    fn error_code(error: MethodsError) -> i32 {
+        match error {
+            MethodsError::JsonRpc(error) => error.code(),
+            other => panic!("Expected a JSON-RPC error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_mev_stats_totals_per_type() {
+        let stats: Value = module().call("ande_getMevStats", (U64::from(10), U64::from(12))).await.unwrap();
+
+        assert_eq!(
+            stats,
+            json!({
+                "fromBlock": "0xa",
+                "toBlock": "0xc",
+                "recordedBlocks": "0x3",
+                "byType": {
+                    "Arbitrage": { "count": "0x1", "value": "0x10" },
+                    "Sandwich": { "count": "0x2", "value": "0x100" },
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_mev_opportunities_json_shape() {
+        let opportunities: Value = module().call("ande_getMevOpportunities", (U64::from(11),)).await.unwrap();
+
+        assert_eq!(
+            opportunities,
+            json!([{
+                "mevType": "Sandwich",
+                "txHash": format!("{:?}", B256::with_last_byte(255)),
+                "value": "0xff",
+                "addresses": [format!("{:?}", Address::with_last_byte(1))],
+                "blockNumber": "0xb",
+                "metadata": { "front_run_tx": format!("{:?}", B256::with_last_byte(7)) },
+            }])
+        );
+
+        // A recorded block without opportunities is an empty list, not an error
+        let empty: Value = module().call("ande_getMevOpportunities", (U64::from(12),)).await.unwrap();
+        assert_eq!(empty, json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_get_top_searchers() {
+        use crate::mev::BundleSubmission;
+
+        let module = module();
+        let unavailable = module.call::<_, Value>("ande_getTopSearchers", (U64::from(10),)).await.unwrap_err();
+        assert_eq!(error_code(unavailable), MEV_AUCTION_UNAVAILABLE_CODE);
+
+        let auction = Arc::new(MevAuctionClient::new(Address::ZERO, Address::ZERO));
+        let searcher = Address::with_last_byte(2);
+        let bundle = BundleSubmission {
+            bundle_hash: B256::with_last_byte(1),
+            bid_amount: U256::from(100),
+            target_block: 5,
+            transactions: vec![B256::with_last_byte(2)],
+            searcher,
+            encrypted_payload: None,
+        };
+        auction.submit_bundle(bundle).await.unwrap();
+        auction.mark_bundle_executed(B256::with_last_byte(1), U256::from(300), U256::from(100)).await.unwrap();
+
+        let module = AndeMevApiImpl::new(Arc::new(MockStore::default())).with_auction(auction).into_rpc();
+        let top: Value = module.call("ande_getTopSearchers", (U64::from(10),)).await.unwrap();
+        assert_eq!(
+            top,
+            json!([{
+                "searcher": format!("{searcher:?}"),
+                "bundlesSubmitted": "0x1",
+                "bundlesExecuted": "0x1",
+                "bundlesRejected": "0x0",
+                "totalBidsPaid": "0x64",
+                "averageOverbid": "0x0",
+                "failureStreak": "0x0",
+            }])
+        );
+
+        let too_many = module
+            .call::<_, Value>("ande_getTopSearchers", (U64::from(MAX_TOP_SEARCHERS + 1),))
+            .await
+            .unwrap_err();
+        assert_eq!(error_code(too_many), INVALID_PARAMS_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_blocks_are_rejected() {
+        let module = module();
+
+        let missing = module.call::<_, Value>("ande_getMevOpportunities", (U64::from(13),)).await.unwrap_err();
+        assert_eq!(error_code(missing), INVALID_PARAMS_CODE);
+
+        let reversed = module.call::<_, Value>("ande_getMevStats", (U64::from(12), U64::from(10))).await.unwrap_err();
+        assert_eq!(error_code(reversed), INVALID_PARAMS_CODE);
+
+        let too_wide = module
+            .call::<_, Value>("ande_getMevStats", (U64::ZERO, U64::from(MAX_MEV_STATS_BLOCK_RANGE)))
+            .await
+            .unwrap_err();
+        assert_eq!(error_code(too_wide), INVALID_PARAMS_CODE);
+
+        // Ranges past the recorded blocks are valid and simply empty
+        let stats: Value = module.call("ande_getMevStats", (U64::from(100), U64::from(200))).await.unwrap();
+        assert_eq!(stats["recordedBlocks"], "0x0");
+        assert_eq!(stats["byType"], json!({}));
+    }
+}
@@ -1,4 +1,40 @@
 /// Evolve RPC modules
+pub mod admin;
+pub mod bundle;
+pub mod client;
+pub mod consensus;
+pub mod duality;
+pub mod health;
+pub mod mev;
+pub mod simulate;
+pub mod stats;
 pub mod txpool;
+pub mod validator;
 
+pub use admin::{
+    AndeAdminApiImpl, AndeAdminApiServer, AndeRuntimeApiImpl, AndeRuntimeApiServer, ConfigReloadSource, MevComponent,
+    RuntimeSettings, RuntimeSwitch, RuntimeSwitchSource, RuntimeSwitches,
+};
+pub use bundle::{AndeBundleApiImpl, AndeBundleApiServer, BundleCaller, RateLimitKey, MAX_BUNDLE_TXS};
+pub use client::{AndeRpcClient, AndeRpcClientError};
+pub use consensus::{AndeConsensusApiImpl, AndeConsensusApiServer, RpcActiveValidator, ScheduledProducer};
+pub use duality::{
+    AndeDualityApiImpl, AndeDualityApiServer, DualityCaps, DualityConfig, DualityLogSource, DualityTransfer,
+};
+pub use health::{
+    AndeHealthApiImpl, AndeHealthApiServer, ConnectionHealth, ConsensusHealthSource, HealthRegistry, HealthReport,
+    HealthStatus, HealthThresholds, HealthThresholdsError, WorkerPoolHealthSource,
+};
+pub use mev::{AndeMevApiImpl, AndeMevApiServer};
+pub use simulate::{
+    AccountOverride, AndeSimulationApiImpl, AndeSimulationApiServer, SimulateBundleRequest, SimulateBundleResponse,
+    SimulatedTransaction, SimulationStateSource,
+};
+pub use stats::{
+    AndeStatsApiImpl, AndeStatsApiServer, AttestationStatus, ExecutionMode, PayloadStats, PayloadStatsBuffer,
+    DEFAULT_PAYLOAD_STATS_CAPACITY,
+};
 pub use txpool::{create_evolve_txpool_module, EvolveTxpoolApiImpl};
+pub use validator::{
+    AndeValidatorApiImpl, AndeValidatorApiServer, AttestationStatusSource, ValidatorStatus, ValidatorStatusSource,
+};
@@ -0,0 +1,586 @@
+//! Bundle Simulation RPC
+//!
+//! `ande_simulateBundle` lets searchers dry-run a bundle before bidding with
+//! it. The raw transactions are decoded and checked like `ande_sendBundle`
+//! does, then executed in order on top of a block, the latest by default, in
+//! the environment of the block following it. Execution uses the node's
+//! [`AndeEvmConfig`], so the ANDE precompiles behave as in a built block.
+//!
+//! Each transaction reports its gas used, return data and, when it fails, the
+//! revert reason or why it couldn't be executed; a failed transaction doesn't
+//! stop the ones after it. The increase of the coinbase balance over the
+//! bundle, priority fees and direct payments alike, estimates the profit the
+//! bundle brings to the block producer.
+//!
+//! Accounts can be overridden before execution, like with `eth_call`: their
+//! balance, nonce or code. Bundles hold at most
+//! [`MAX_BUNDLE_TXS`](super::MAX_BUNDLE_TXS) transactions and their total gas
+//! limit is capped; simulations beyond the concurrency limit are refused
+//! rather than queued.
+
+use super::{
+    bundle::{check_bundle_size, decode_bundle_transaction},
+    mev::invalid_params,
+};
+use crate::evm_config::AndeEvmConfig;
+use alloy::sol_types::decode_revert_reason;
+use alloy_consensus::{transaction::Recovered, Transaction};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_evm::{ConfigureEvm, Evm, NextBlockEnvAttributes};
+use reth_primitives::{Header, SealedHeader, TransactionSigned};
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_api::{BlockNumReader, HeaderProvider, StateProviderBox, StateProviderFactory};
+use revm::{
+    context_interface::result::ExecutionResult,
+    database::CacheDB,
+    database_interface::{Database, DatabaseRef},
+    state::Bytecode,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, sync::Arc};
+use tokio::sync::Semaphore;
+
+/// Error code returned when the simulations in progress reach the concurrency
+/// limit (EIP-1474 "limit exceeded")
+pub const SIMULATION_LIMIT_CODE: i32 = -32005;
+
+/// Total gas limit of the transactions of a simulated bundle by default
+pub const DEFAULT_SIMULATION_GAS_CAP: u64 = 50_000_000;
+
+/// Simulations run at the same time by default
+pub const DEFAULT_MAX_CONCURRENT_SIMULATIONS: usize = 4;
+
+/// Parameters of `ande_simulateBundle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBundleRequest {
+    /// EIP-2718 encoded signed transactions, in execution order
+    pub txs: Vec<Bytes>,
+    /// Block the bundle is executed on top of, the latest when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<U64>,
+    /// Accounts overridden before execution
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub state_overrides: HashMap<Address, AccountOverride>,
+}
+
+/// Override of an account, as in the state override set of `eth_call`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    /// Balance, in wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Nonce
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    /// Runtime bytecode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+}
+
+/// Outcome of one transaction of a simulated bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTransaction {
+    /// Hash of the transaction
+    pub tx_hash: B256,
+    /// Whether the transaction executed successfully
+    pub success: bool,
+    /// Gas used, 0 for a transaction that couldn't be executed
+    pub gas_used: U64,
+    /// Data returned, or the revert data
+    pub return_data: Bytes,
+    /// Why the transaction reverted or halted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Why the transaction couldn't be executed, e.g. a wrong nonce
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response of `ande_simulateBundle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBundleResponse {
+    /// Block the bundle was executed on top of
+    pub block_number: U64,
+    /// Beneficiary of the simulated block
+    pub coinbase: Address,
+    /// Gas used by the bundle
+    pub gas_used: U64,
+    /// Increase of the coinbase balance over the bundle, in wei
+    pub coinbase_diff: U256,
+    /// Outcome of each transaction, in execution order
+    pub results: Vec<SimulatedTransaction>,
+}
+
+/// Chain state bundles are simulated on
+pub trait SimulationStateSource: Send + Sync + 'static {
+    /// State after a block
+    type State: DatabaseRef<Error: Send + Sync + 'static> + fmt::Debug + Send;
+
+    /// Header of block `number`, the latest block when `None`, with the state
+    /// after it; `None` if the block is unknown
+    fn state_at(&self, number: Option<u64>) -> eyre::Result<Option<(SealedHeader, Self::State)>>;
+}
+
+impl<Client> SimulationStateSource for Client
+where
+    Client: StateProviderFactory + HeaderProvider<Header = Header> + 'static,
+{
+    type State = StateProviderDatabase<StateProviderBox>;
+
+    fn state_at(&self, number: Option<u64>) -> eyre::Result<Option<(SealedHeader, Self::State)>> {
+        let number = match number {
+            Some(number) => number,
+            None => self.best_block_number()?,
+        };
+        let Some(header) = self.sealed_header(number)? else {
+            return Ok(None);
+        };
+        let state = self.history_by_block_number(number)?;
+        Ok(Some((header, StateProviderDatabase::new(state))))
+    }
+}
+
+/// ANDE bundle simulation RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeSimulationApi {
+    /// Execute a bundle of signed transactions on top of a block without
+    /// committing it
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(&self, request: SimulateBundleRequest) -> RpcResult<SimulateBundleResponse>;
+}
+
+/// Implementation of the ANDE bundle simulation RPC API
+#[derive(Debug)]
+pub struct AndeSimulationApiImpl<Source> {
+    /// State bundles are executed on
+    source: Arc<Source>,
+    /// EVM configuration bundles are executed with
+    evm_config: AndeEvmConfig,
+    /// Highest total gas limit of a bundle
+    gas_cap: u64,
+    /// Permits of the simulations in progress
+    permits: Arc<Semaphore>,
+}
+
+impl<Source> Clone for AndeSimulationApiImpl<Source> {
+    fn clone(&self) -> Self {
+        Self {
+            source: Arc::clone(&self.source),
+            evm_config: self.evm_config.clone(),
+            gas_cap: self.gas_cap,
+            permits: Arc::clone(&self.permits),
+        }
+    }
+}
+
+impl<Source: SimulationStateSource> AndeSimulationApiImpl<Source> {
+    /// Creates a new instance executing bundles on the state of `source` with `evm_config`
+    pub fn new(source: Source, evm_config: AndeEvmConfig) -> Self {
+        Self {
+            source: Arc::new(source),
+            evm_config,
+            gas_cap: DEFAULT_SIMULATION_GAS_CAP,
+            permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_SIMULATIONS)),
+        }
+    }
+
+    /// Refuse bundles whose transactions have a total gas limit above `gas_cap`
+    pub fn with_gas_cap(mut self, gas_cap: u64) -> Self {
+        self.gas_cap = gas_cap;
+        self
+    }
+
+    /// Run at most `max_simulations` simulations at the same time
+    pub fn with_max_concurrent_simulations(mut self, max_simulations: usize) -> Self {
+        self.permits = Arc::new(Semaphore::new(max_simulations));
+        self
+    }
+}
+
+fn internal_error(message: String) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, message, None::<()>)
+}
+
+/// Execute `transactions` in order on the state after `parent`, in the
+/// environment of the block following it
+fn simulate<DB>(
+    evm_config: &AndeEvmConfig,
+    parent: &SealedHeader,
+    state: DB,
+    transactions: &[(TransactionSigned, Address)],
+    overrides: &HashMap<Address, AccountOverride>,
+) -> RpcResult<SimulateBundleResponse>
+where
+    DB: DatabaseRef + fmt::Debug,
+    DB::Error: Send + Sync + 'static,
+{
+    let coinbase = parent.beneficiary;
+    let next_block_attrs = NextBlockEnvAttributes {
+        timestamp: parent.timestamp + 1,
+        suggested_fee_recipient: coinbase,
+        prev_randao: parent.mix_hash,
+        gas_limit: parent.gas_limit,
+        parent_beacon_block_root: Some(B256::ZERO),
+        withdrawals: Some(Default::default()),
+    };
+    let evm_env = evm_config
+        .next_evm_env(parent.header(), &next_block_attrs)
+        .map_err(|err| internal_error(format!("Failed to create EVM environment: {err}")))?;
+
+    let state_error = |err: DB::Error| internal_error(format!("Failed to read state: {err}"));
+    let mut db = CacheDB::new(state);
+    apply_overrides(&mut db, overrides).map_err(state_error)?;
+    let balance_before = db.basic(coinbase).map_err(state_error)?.map(|info| info.balance).unwrap_or_default();
+
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+    let mut gas_used = 0;
+    let mut results = Vec::with_capacity(transactions.len());
+    for (transaction, sender) in transactions {
+        let tx_hash = *transaction.hash();
+        let result = match evm.transact_commit(Recovered::new_unchecked(transaction, *sender)) {
+            Ok(result) => {
+                gas_used += result.gas_used();
+                let revert_reason = match &result {
+                    ExecutionResult::Success { .. } => None,
+                    ExecutionResult::Revert { output, .. } => {
+                        Some(decode_revert_reason(output).unwrap_or_else(|| "execution reverted".to_string()))
+                    }
+                    ExecutionResult::Halt { reason, .. } => Some(format!("halted: {reason:?}")),
+                };
+                SimulatedTransaction {
+                    tx_hash,
+                    success: result.is_success(),
+                    gas_used: U64::from(result.gas_used()),
+                    return_data: result.output().cloned().unwrap_or_default(),
+                    revert_reason,
+                    error: None,
+                }
+            }
+            Err(err) => SimulatedTransaction {
+                tx_hash,
+                success: false,
+                gas_used: U64::ZERO,
+                return_data: Bytes::new(),
+                revert_reason: None,
+                error: Some(err.to_string()),
+            },
+        };
+        results.push(result);
+    }
+    drop(evm);
+
+    let balance_after = db.basic(coinbase).map_err(state_error)?.map(|info| info.balance).unwrap_or_default();
+    Ok(SimulateBundleResponse {
+        block_number: U64::from(parent.number),
+        coinbase,
+        gas_used: U64::from(gas_used),
+        coinbase_diff: balance_after.saturating_sub(balance_before),
+        results,
+    })
+}
+
+/// Apply `overrides` to the accounts of `db`
+fn apply_overrides<DB: DatabaseRef>(
+    db: &mut CacheDB<DB>,
+    overrides: &HashMap<Address, AccountOverride>,
+) -> Result<(), DB::Error> {
+    for (address, account) in overrides {
+        let mut info = db.basic(*address)?.unwrap_or_default();
+        if let Some(balance) = account.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account.nonce {
+            info.nonce = nonce.to();
+        }
+        if let Some(code) = &account.code {
+            let code = Bytecode::new_raw(code.clone());
+            info.code_hash = code.hash_slow();
+            info.code = Some(code);
+        }
+        db.insert_account_info(*address, info);
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl<Source: SimulationStateSource> AndeSimulationApiServer for AndeSimulationApiImpl<Source> {
+    async fn simulate_bundle(&self, request: SimulateBundleRequest) -> RpcResult<SimulateBundleResponse> {
+        check_bundle_size(request.txs.len())?;
+        let chain_id = self.evm_config.chain_spec().chain.id();
+        let transactions = request
+            .txs
+            .iter()
+            .enumerate()
+            .map(|(index, raw)| decode_bundle_transaction(chain_id, index, raw))
+            .collect::<RpcResult<Vec<_>>>()?;
+        let gas_limit = transactions.iter().fold(0u64, |total, (tx, _)| total.saturating_add(tx.gas_limit()));
+        if gas_limit > self.gas_cap {
+            return Err(invalid_params(format!("Bundle gas limit {gas_limit} exceeds the cap of {}", self.gas_cap)));
+        }
+
+        let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() else {
+            return Err(ErrorObjectOwned::owned(
+                SIMULATION_LIMIT_CODE,
+                "Too many bundle simulations in progress, retry later",
+                None::<()>,
+            ));
+        };
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let block_number = request.block_number.map(|number| number.to::<u64>());
+            let Some((parent, state)) = this
+                .source
+                .state_at(block_number)
+                .map_err(|err| internal_error(format!("Failed to read state: {err}")))?
+            else {
+                let block = block_number.map_or_else(|| "latest".to_string(), |number| number.to_string());
+                return Err(invalid_params(format!("Block {block} is not known")));
+            };
+            simulate(&this.evm_config, &parent, state, &transactions, &request.state_overrides)
+        })
+        .await
+        .map_err(|err| internal_error(format!("Bundle simulation failed: {err}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        evm_config::{create_ande_evm_config, AndePrecompileConfig},
+        rpc::MAX_BUNDLE_TXS,
+    };
+    use alloy::{
+        signers::{local::PrivateKeySigner, SignerSync},
+        sol_types::{Revert, SolError},
+    };
+    use alloy_consensus::{SignableTransaction, TxEip1559, TypedTransaction};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::TxKind;
+    use jsonrpsee::{core::server::MethodsError, types::error::INVALID_PARAMS_CODE};
+    use reth_chainspec::{Chain, ChainSpecBuilder};
+    use revm::{database::EmptyDB, state::AccountInfo};
+
+    const CHAIN_ID: u64 = 31337;
+    const PRIORITY_FEE: u128 = 1_000_000_000;
+
+    /// Chain of a single block, with a fixed state after it
+    #[derive(Debug)]
+    struct TestChain {
+        header: SealedHeader,
+        state: CacheDB<EmptyDB>,
+    }
+
+    impl SimulationStateSource for TestChain {
+        type State = CacheDB<EmptyDB>;
+
+        fn state_at(&self, number: Option<u64>) -> eyre::Result<Option<(SealedHeader, Self::State)>> {
+            let known = number.is_none_or(|number| number == self.header.number);
+            Ok(known.then(|| (self.header.clone(), self.state.clone())))
+        }
+    }
+
+    fn coinbase() -> Address {
+        Address::repeat_byte(0xcb)
+    }
+
+    /// Chain whose state funds `funded` with 1 ether each
+    fn chain(funded: &[Address]) -> TestChain {
+        let header = Header {
+            number: 10,
+            beneficiary: coinbase(),
+            gas_limit: 30_000_000,
+            timestamp: 1_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let mut state = CacheDB::new(EmptyDB::default());
+        for address in funded {
+            let info = AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() };
+            state.insert_account_info(*address, info);
+        }
+        TestChain { header: SealedHeader::new(header, B256::repeat_byte(10)), state }
+    }
+
+    fn evm_config() -> AndeEvmConfig {
+        let chain_spec = ChainSpecBuilder::default()
+            .chain(Chain::from_id(CHAIN_ID))
+            .genesis(Default::default())
+            .cancun_activated()
+            .build();
+        create_ande_evm_config(Arc::new(chain_spec), AndePrecompileConfig::insecure_unrestricted())
+    }
+
+    fn module(chain: TestChain) -> jsonrpsee::RpcModule<AndeSimulationApiImpl<TestChain>> {
+        AndeSimulationApiImpl::new(chain, evm_config()).with_gas_cap(1_000_000).into_rpc()
+    }
+
+    /// EIP-1559 transaction from `signer` sending `value` to `to`
+    fn transaction(signer: &PrivateKeySigner, nonce: u64, to: Address, value: u64, gas_limit: u64) -> Bytes {
+        let tx = TxEip1559 {
+            chain_id: CHAIN_ID,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: 10_000_000_000,
+            max_priority_fee_per_gas: PRIORITY_FEE,
+            to: TxKind::Call(to),
+            value: U256::from(value),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::new_unhashed(TypedTransaction::Eip1559(tx).into(), signature).encoded_2718().into()
+    }
+
+    fn request(txs: Vec<Bytes>) -> SimulateBundleRequest {
+        SimulateBundleRequest { txs, block_number: None, state_overrides: HashMap::new() }
+    }
+
+    /// Code reverting with `Error(reason)`
+    fn reverting_code(reason: &str) -> Bytes {
+        let data = Revert::from(reason).abi_encode();
+        // CODECOPY the data after this prefix to memory, then REVERT with it
+        let len = data.len() as u8;
+        [[0x60, len, 0x60, 0x0a, 0x5f, 0x39, 0x60, len, 0x5f, 0xfd].as_slice(), &data].concat().into()
+    }
+
+    /// Error code and message `request` is rejected with
+    async fn rejection(
+        module: &jsonrpsee::RpcModule<AndeSimulationApiImpl<TestChain>>,
+        request: SimulateBundleRequest,
+    ) -> (i32, String) {
+        match module.call::<_, SimulateBundleResponse>("ande_simulateBundle", (request,)).await {
+            Err(MethodsError::JsonRpc(error)) => (error.code(), error.message().to_string()),
+            other => panic!("Expected a JSON-RPC error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_profitable_bundle() {
+        let searcher = PrivateKeySigner::random();
+        let module = module(chain(&[searcher.address()]));
+        let bribe = 1_000_000_000_000_000;
+        let txs = vec![
+            transaction(&searcher, 0, Address::repeat_byte(1), 1_000, 21_000),
+            transaction(&searcher, 1, coinbase(), bribe, 21_000),
+        ];
+
+        let response: SimulateBundleResponse = module.call("ande_simulateBundle", (request(txs),)).await.unwrap();
+        assert_eq!((response.block_number, response.coinbase), (U64::from(10), coinbase()));
+        assert_eq!(response.gas_used, U64::from(42_000));
+        assert!(response.results.iter().all(|result| result.success && result.gas_used == U64::from(21_000)));
+        // Priority fees of both transactions, and the direct payment
+        assert_eq!(response.coinbase_diff, U256::from(42_000 * PRIORITY_FEE + u128::from(bribe)));
+    }
+
+    #[tokio::test]
+    async fn test_reverting_bundle() {
+        let searcher = PrivateKeySigner::random();
+        let target = Address::repeat_byte(0xee);
+        let mut chain = chain(&[searcher.address()]);
+        let code = Bytecode::new_raw(reverting_code("pool drained"));
+        chain.state.insert_account_info(target, AccountInfo::from_bytecode(code));
+        let module = module(chain);
+        let txs = vec![
+            transaction(&searcher, 0, target, 0, 100_000),
+            // Executed after the revert, then rejected for its nonce
+            transaction(&searcher, 1, Address::repeat_byte(1), 0, 21_000),
+            transaction(&searcher, 5, Address::repeat_byte(1), 0, 21_000),
+        ];
+
+        let response: SimulateBundleResponse = module.call("ande_simulateBundle", (request(txs),)).await.unwrap();
+        let [reverted, executed, invalid] = &response.results[..] else {
+            panic!("Expected a result per transaction, got {:?}", response.results);
+        };
+        assert!(!reverted.success);
+        assert_eq!(reverted.revert_reason.as_deref(), Some("revert: pool drained"));
+        assert_eq!(reverted.return_data, Bytes::from(Revert::from("pool drained").abi_encode()));
+        assert!(reverted.gas_used > U64::from(21_000));
+        assert!(executed.success);
+        assert!(!invalid.success);
+        assert_eq!(invalid.gas_used, U64::ZERO);
+        assert!(invalid.error.as_deref().unwrap().contains("nonce"), "{invalid:?}");
+        assert_eq!(response.gas_used, reverted.gas_used + executed.gas_used);
+
+        let json = serde_json::to_value(reverted).unwrap();
+        assert_eq!(json["revertReason"], "revert: pool drained");
+        assert!(json.get("error").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_state_overrides() {
+        let searcher = PrivateKeySigner::random();
+        let target = Address::repeat_byte(0xee);
+        let module = module(chain(&[]));
+        let txs = vec![transaction(&searcher, 3, target, 1_000, 100_000)];
+
+        // Unfunded, with a nonce of 0
+        let response: SimulateBundleResponse =
+            module.call("ande_simulateBundle", (request(txs.clone()),)).await.unwrap();
+        assert!(response.results[0].error.is_some());
+
+        let mut overridden = request(txs);
+        overridden.state_overrides = serde_json::from_value(serde_json::json!({
+            searcher.address().to_string(): { "balance": "0xde0b6b3a7640000", "nonce": "0x3" },
+        }))
+        .unwrap();
+        let response: SimulateBundleResponse =
+            module.call("ande_simulateBundle", (overridden.clone(),)).await.unwrap();
+        assert!(response.results[0].success, "{:?}", response.results[0]);
+
+        // The recipient now reverts
+        let code = AccountOverride { code: Some(reverting_code("paused")), ..Default::default() };
+        overridden.state_overrides.insert(target, code);
+        let response: SimulateBundleResponse = module.call("ande_simulateBundle", (overridden,)).await.unwrap();
+        assert_eq!(response.results[0].revert_reason.as_deref(), Some("revert: paused"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests_rejected() {
+        let searcher = PrivateKeySigner::random();
+        let module = module(chain(&[searcher.address()]));
+
+        let (code, _) = rejection(&module, request(vec![])).await;
+        assert_eq!(code, INVALID_PARAMS_CODE);
+
+        let oversized = request(vec![Bytes::from_static(&[0x02, 0xc0]); MAX_BUNDLE_TXS + 1]);
+        let (code, message) = rejection(&module, oversized).await;
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert!(message.starts_with("Bundle holds 33 transactions"), "{message}");
+
+        let over_cap = request((0..2).map(|nonce| transaction(&searcher, nonce, coinbase(), 0, 500_001)).collect());
+        let (code, message) = rejection(&module, over_cap).await;
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert_eq!(message, "Bundle gas limit 1000002 exceeds the cap of 1000000");
+
+        let mut unknown_block = request(vec![transaction(&searcher, 0, coinbase(), 0, 21_000)]);
+        unknown_block.block_number = Some(U64::from(11));
+        let (code, message) = rejection(&module, unknown_block).await;
+        assert_eq!(code, INVALID_PARAMS_CODE);
+        assert_eq!(message, "Block 11 is not known");
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let searcher = PrivateKeySigner::random();
+        let api =
+            AndeSimulationApiImpl::new(chain(&[searcher.address()]), evm_config()).with_max_concurrent_simulations(1);
+        let module = api.clone().into_rpc();
+        let held = Arc::clone(&api.permits).try_acquire_owned().unwrap();
+
+        let bundle = request(vec![transaction(&searcher, 0, coinbase(), 0, 21_000)]);
+        let (code, _) = rejection(&module, bundle.clone()).await;
+        assert_eq!(code, SIMULATION_LIMIT_CODE);
+
+        drop(held);
+        module.call::<_, SimulateBundleResponse>("ande_simulateBundle", (bundle,)).await.unwrap();
+    }
+}
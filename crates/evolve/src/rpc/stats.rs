@@ -0,0 +1,220 @@
+use alloy_primitives::{B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// Number of blocks whose stats are kept by default
+pub const DEFAULT_PAYLOAD_STATS_CAPACITY: usize = 256;
+
+/// How the transactions of a block were executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutionMode {
+    /// In parallel, before the block was assembled
+    Parallel,
+    /// One after the other, including after a fallback from parallel execution
+    Sequential,
+}
+
+/// Consensus stage a built block reached before it was handed out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AttestationStatus {
+    /// The finalization policy doesn't wait on the consensus
+    NotAwaited,
+    /// An attestation of the block was recorded by the consensus contract
+    Attested,
+    /// The block reached the finalization threshold
+    Finalized,
+    /// The block didn't reach the stage required by the policy in time
+    TimedOut,
+}
+
+/// Building performance of one block, as returned by `ande_getPayloadStats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadStats {
+    /// Number of the block
+    pub block_number: U64,
+    /// Hash of the block
+    pub block_hash: B256,
+    /// Time spent building the block, waiting on the consensus excluded, in milliseconds
+    pub build_duration_ms: U64,
+    /// How the transactions were executed
+    pub execution_mode: ExecutionMode,
+    /// Transactions included in the block
+    pub transaction_count: U64,
+    /// Supplied transactions left out of the block
+    pub rejected_count: U64,
+    /// Gas used by the block
+    pub gas_used: U64,
+    /// Conflicts detected by the parallel execution, when monitored
+    pub conflicts: Option<U64>,
+    /// Re-executions scheduled after a conflict, when monitored
+    pub retries: Option<U64>,
+    /// Consensus stage the block reached
+    pub attestation: AttestationStatus,
+    /// MEV credited to the distributor for the block, in wei
+    pub mev_captured: U256,
+}
+
+/// Stats of the blocks built most recently, the oldest evicted first once
+/// the capacity is reached
+#[derive(Debug)]
+pub struct PayloadStatsBuffer {
+    /// Number of blocks kept
+    capacity: usize,
+    /// Stats of the blocks kept, oldest first
+    blocks: Mutex<VecDeque<PayloadStats>>,
+}
+
+impl PayloadStatsBuffer {
+    /// Creates a buffer keeping the stats of the last `capacity` blocks
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, blocks: Mutex::new(VecDeque::with_capacity(capacity)) }
+    }
+
+    /// Number of blocks kept
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Record the stats of a block, evicting the oldest ones over capacity
+    pub fn record(&self, stats: PayloadStats) {
+        let mut blocks = self.blocks.lock().unwrap_or_else(PoisonError::into_inner);
+        blocks.push_back(stats);
+        while blocks.len() > self.capacity {
+            blocks.pop_front();
+        }
+    }
+
+    /// Stats of the last `count` blocks recorded, oldest first
+    pub fn latest(&self, count: usize) -> Vec<PayloadStats> {
+        let blocks = self.blocks.lock().unwrap_or_else(PoisonError::into_inner);
+        blocks.iter().skip(blocks.len().saturating_sub(count)).cloned().collect()
+    }
+}
+
+impl Default for PayloadStatsBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_PAYLOAD_STATS_CAPACITY)
+    }
+}
+
+/// ANDE payload stats RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeStatsApi {
+    /// Building performance of the last `last_n_blocks` blocks built by the
+    /// node, oldest first, within the blocks kept
+    #[method(name = "getPayloadStats")]
+    async fn get_payload_stats(&self, last_n_blocks: U64) -> RpcResult<Vec<PayloadStats>>;
+}
+
+/// Implementation of the ANDE payload stats RPC API, reading the stats the
+/// payload builder records
+#[derive(Debug, Clone)]
+pub struct AndeStatsApiImpl {
+    /// Stats recorded by the payload builder
+    stats: Arc<PayloadStatsBuffer>,
+}
+
+impl AndeStatsApiImpl {
+    /// Creates a new instance reading from `stats`
+    pub fn new(stats: Arc<PayloadStatsBuffer>) -> Self {
+        Self { stats }
+    }
+}
+
+#[async_trait]
+impl AndeStatsApiServer for AndeStatsApiImpl {
+    async fn get_payload_stats(&self, last_n_blocks: U64) -> RpcResult<Vec<PayloadStats>> {
+        let count = usize::try_from(last_n_blocks.to::<u64>()).unwrap_or(usize::MAX);
+        Ok(self.stats.latest(count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stats(block_number: u64) -> PayloadStats {
+        PayloadStats {
+            block_number: U64::from(block_number),
+            block_hash: B256::repeat_byte(block_number as u8),
+            build_duration_ms: U64::from(120),
+            execution_mode: ExecutionMode::Parallel,
+            transaction_count: U64::from(40),
+            rejected_count: U64::from(2),
+            gas_used: U64::from(840_000),
+            conflicts: Some(U64::from(3)),
+            retries: Some(U64::from(4)),
+            attestation: AttestationStatus::Attested,
+            mev_captured: U256::from(1_000),
+        }
+    }
+
+    #[test]
+    fn test_oldest_blocks_are_evicted() {
+        let buffer = PayloadStatsBuffer::new(3);
+        assert!(buffer.latest(10).is_empty());
+
+        (1..=5).for_each(|block_number| buffer.record(stats(block_number)));
+        let numbers =
+            |stats: Vec<PayloadStats>| stats.iter().map(|stats| stats.block_number.to::<u64>()).collect::<Vec<_>>();
+        assert_eq!(numbers(buffer.latest(10)), [3, 4, 5]);
+        assert_eq!(numbers(buffer.latest(2)), [4, 5]);
+        assert!(buffer.latest(0).is_empty());
+
+        // Nothing is kept without capacity
+        let disabled = PayloadStatsBuffer::new(0);
+        disabled.record(stats(1));
+        assert!(disabled.latest(1).is_empty());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut sequential = stats(7);
+        sequential.execution_mode = ExecutionMode::Sequential;
+        sequential.conflicts = None;
+        sequential.retries = None;
+        sequential.attestation = AttestationStatus::NotAwaited;
+
+        assert_eq!(
+            serde_json::to_value(&sequential).unwrap(),
+            json!({
+                "blockNumber": "0x7",
+                "blockHash": B256::repeat_byte(7),
+                "buildDurationMs": "0x78",
+                "executionMode": "sequential",
+                "transactionCount": "0x28",
+                "rejectedCount": "0x2",
+                "gasUsed": "0xcd140",
+                "conflicts": null,
+                "retries": null,
+                "attestation": "notAwaited",
+                "mevCaptured": "0x3e8",
+            })
+        );
+        let parallel = serde_json::to_value(stats(1)).unwrap();
+        assert_eq!((&parallel["executionMode"], &parallel["conflicts"]), (&json!("parallel"), &json!("0x3")));
+        assert_eq!(serde_json::from_value::<PayloadStats>(parallel).unwrap(), stats(1));
+    }
+
+    #[tokio::test]
+    async fn test_get_payload_stats() {
+        let buffer = Arc::new(PayloadStatsBuffer::new(2));
+        (1..=3).for_each(|block_number| buffer.record(stats(block_number)));
+        let module = AndeStatsApiImpl::new(buffer).into_rpc();
+
+        let latest: Vec<PayloadStats> = module.call("ande_getPayloadStats", (U64::from(1),)).await.unwrap();
+        assert_eq!(latest, [stats(3)]);
+        let kept: Vec<PayloadStats> = module.call("ande_getPayloadStats", (U64::MAX,)).await.unwrap();
+        assert_eq!(kept, [stats(2), stats(3)]);
+    }
+}
@@ -5,6 +5,10 @@ use jsonrpsee::tracing::debug;
 use jsonrpsee_core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 /// Evolve txpool RPC API trait
 #[rpc(server, namespace = "txpoolExt")]
@@ -19,26 +23,32 @@ pub trait EvolveTxpoolApi {
 pub struct EvolveTxpoolApiImpl<Pool> {
     /// Transaction pool
     pool: Pool,
-    /// Maximum bytes allowed for transaction selection
-    max_bytes: u64,
+    /// Maximum bytes allowed for transaction selection, shared so it can be
+    /// changed while the RPC is served
+    max_bytes: Arc<AtomicU64>,
 }
 
 impl<Pool> EvolveTxpoolApiImpl<Pool> {
     /// Creates a new instance of `TxpoolApi`.
-    pub const fn new(pool: Pool, max_bytes: u64) -> Self {
-        Self { pool, max_bytes }
+    pub fn new(pool: Pool, max_bytes: u64) -> Self {
+        Self { pool, max_bytes: Arc::new(AtomicU64::new(max_bytes)) }
+    }
+
+    /// Handle to the byte cap, applying to the next `getTxs` call when stored to
+    pub fn max_bytes_handle(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.max_bytes)
     }
 }
 
 /// Creates a new Evolve txpool RPC module
-pub const fn create_evolve_txpool_module<Pool>(
+pub fn create_evolve_txpool_module<Pool>(
     pool: Pool,
     max_bytes: u64,
 ) -> EvolveTxpoolApiImpl<Pool>
 where
     Pool: TransactionPool + Send + Sync + 'static,
 {
-    EvolveTxpoolApiImpl { pool, max_bytes }
+    EvolveTxpoolApiImpl::new(pool, max_bytes)
 }
 
 #[async_trait]
@@ -56,7 +66,8 @@ where
         let mut total_gas = 0u64;
         let mut selected_txs: Vec<Bytes> = Vec::new();
 
-        // Determine the active gas cap for selection
+        // Determine the active caps for selection
+        let max_bytes = self.max_bytes.load(Ordering::Relaxed);
         let gas_cap = current_block_gas_limit();
 
         // Use best_transactions() which returns an iterator of transactions
@@ -70,7 +81,7 @@ where
             let gas = best_tx.gas_limit();
 
             // Enforce byte cap if configured (> 0)
-            if self.max_bytes > 0 && total_bytes + sz > self.max_bytes {
+            if max_bytes > 0 && total_bytes + sz > max_bytes {
                 break;
             }
             // Enforce gas cap if configured (> 0)
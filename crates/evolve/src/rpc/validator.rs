@@ -0,0 +1,283 @@
+use crate::consensus_client::ValidatorInfo;
+use alloy_primitives::{Address, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+
+/// Error code returned when the consensus contract can't be read
+pub const CONSENSUS_UNAVAILABLE_CODE: i32 = -32002;
+
+/// On-chain record of a validator, as returned by `ande_validatorStatus`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcValidatorInfo {
+    /// Staked amount, in wei
+    pub stake: U256,
+    /// Voting power
+    pub power: U256,
+    /// Uptime, in basis points
+    pub uptime: U256,
+    /// Whether the validator is jailed
+    pub jailed: bool,
+    /// Whether the validator is registered as active in the contract
+    pub active: bool,
+}
+
+/// Block production and attestation progress of the node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProducerStatus {
+    /// Next block within the cached producer schedule we're designated to
+    /// produce, if any
+    pub next_scheduled_block: Option<U64>,
+    /// Attestations waiting to be retried
+    pub pending_attestations: U64,
+    /// Highest block attested successfully since the node started
+    pub last_attested_block: Option<U64>,
+}
+
+/// Response of `ande_validatorStatus`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStatus {
+    /// Validator address of the node
+    pub address: Address,
+    /// Whether the address is in the active set, as last synced
+    pub in_active_set: bool,
+    /// On-chain record of the validator
+    pub info: RpcValidatorInfo,
+    /// Production fields, omitted when the node doesn't produce blocks
+    #[serde(flatten)]
+    pub producer: Option<ProducerStatus>,
+}
+
+/// Consensus-side view of the validators, as kept by the consensus client
+#[async_trait]
+pub trait ValidatorStatusSource: fmt::Debug + Send + Sync {
+    /// Active validators, as last synced
+    async fn cached_validators(&self) -> Vec<Address>;
+
+    /// On-chain record of `validator`
+    async fn validator_info(&self, validator: Address) -> eyre::Result<RpcValidatorInfo>;
+
+    /// Full on-chain record of `validator`, with the endpoints it's reachable at
+    async fn validator_details(&self, validator: Address) -> eyre::Result<ValidatorInfo>;
+
+    /// Next block within the cached producer schedule designated to `producer`
+    async fn next_scheduled_block(&self, producer: Address) -> Option<u64>;
+
+    /// Producer designated for `block_number`, from the producer schedule or
+    /// read from the consensus contract when the schedule doesn't hold it
+    async fn block_producer(&self, block_number: u64) -> eyre::Result<Address>;
+}
+
+/// Attestation progress of the node, as kept by the block attester
+pub trait AttestationStatusSource: fmt::Debug + Send + Sync {
+    /// Attestations waiting to be retried
+    fn pending_attestations(&self) -> usize;
+
+    /// Highest block attested successfully, if any
+    fn last_attested_block(&self) -> Option<u64>;
+}
+
+/// ANDE validator RPC API trait
+#[rpc(server, namespace = "ande")]
+pub trait AndeValidatorApi {
+    /// Health of the node's validator, as seen from the consensus contract
+    #[method(name = "validatorStatus")]
+    async fn validator_status(&self) -> RpcResult<ValidatorStatus>;
+}
+
+/// Implementation of the ANDE validator RPC API
+///
+/// Read-only nodes, without a wallet, report the consensus-side view of their
+/// address only; nodes producing blocks also report their schedule and
+/// attestations.
+#[derive(Debug, Clone)]
+pub struct AndeValidatorApiImpl {
+    /// Validator address of the node
+    address: Address,
+    /// Consensus client the view is read from
+    consensus: Arc<dyn ValidatorStatusSource>,
+    /// Attester of the produced blocks, if the node produces blocks
+    attester: Option<Arc<dyn AttestationStatusSource>>,
+}
+
+impl AndeValidatorApiImpl {
+    /// Creates a new instance reporting on `address` from `consensus`
+    pub fn new(address: Address, consensus: Arc<dyn ValidatorStatusSource>) -> Self {
+        Self { address, consensus, attester: None }
+    }
+
+    /// Report the production fields, with the attestations of `attester`
+    pub fn with_attester(mut self, attester: Arc<dyn AttestationStatusSource>) -> Self {
+        self.attester = Some(attester);
+        self
+    }
+}
+
+#[async_trait]
+impl AndeValidatorApiServer for AndeValidatorApiImpl {
+    async fn validator_status(&self) -> RpcResult<ValidatorStatus> {
+        let info = self.consensus.validator_info(self.address).await.map_err(|error| {
+            ErrorObjectOwned::owned(
+                CONSENSUS_UNAVAILABLE_CODE,
+                format!("Failed to read validator {}: {error}", self.address),
+                None::<()>,
+            )
+        })?;
+        let in_active_set = self.consensus.cached_validators().await.contains(&self.address);
+
+        let producer = match &self.attester {
+            Some(attester) => Some(ProducerStatus {
+                next_scheduled_block: self.consensus.next_scheduled_block(self.address).await.map(U64::from),
+                pending_attestations: U64::from(attester.pending_attestations()),
+                last_attested_block: attester.last_attested_block().map(U64::from),
+            }),
+            None => None,
+        };
+
+        Ok(ValidatorStatus { address: self.address, in_active_set, info, producer })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::core::server::MethodsError;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    const VALIDATOR: Address = Address::repeat_byte(0x01);
+
+    /// Consensus client serving fixed validators and schedule
+    #[derive(Debug, Default)]
+    struct StubConsensus {
+        active: Vec<Address>,
+        infos: HashMap<Address, RpcValidatorInfo>,
+        schedule: Vec<(u64, Address)>,
+        unavailable: bool,
+    }
+
+    #[async_trait]
+    impl ValidatorStatusSource for StubConsensus {
+        async fn cached_validators(&self) -> Vec<Address> {
+            self.active.clone()
+        }
+
+        async fn validator_info(&self, validator: Address) -> eyre::Result<RpcValidatorInfo> {
+            if self.unavailable {
+                eyre::bail!("connection refused");
+            }
+            Ok(self.infos.get(&validator).cloned().unwrap_or_default())
+        }
+
+        async fn validator_details(&self, _validator: Address) -> eyre::Result<ValidatorInfo> {
+            eyre::bail!("not served by the stub")
+        }
+
+        async fn next_scheduled_block(&self, producer: Address) -> Option<u64> {
+            self.schedule.iter().find(|(_, scheduled)| *scheduled == producer).map(|(block, _)| *block)
+        }
+
+        async fn block_producer(&self, block_number: u64) -> eyre::Result<Address> {
+            let scheduled = self.schedule.iter().find(|(block, _)| *block == block_number);
+            scheduled.map(|(_, producer)| *producer).ok_or_else(|| eyre::eyre!("block {block_number} not scheduled"))
+        }
+    }
+
+    #[derive(Debug)]
+    struct StubAttester {
+        pending: usize,
+        last_attested: Option<u64>,
+    }
+
+    impl AttestationStatusSource for StubAttester {
+        fn pending_attestations(&self) -> usize {
+            self.pending
+        }
+
+        fn last_attested_block(&self) -> Option<u64> {
+            self.last_attested
+        }
+    }
+
+    fn consensus() -> StubConsensus {
+        let info = RpcValidatorInfo {
+            stake: U256::from(1_000),
+            power: U256::from(10),
+            uptime: U256::from(9_950),
+            jailed: false,
+            active: true,
+        };
+        StubConsensus {
+            active: vec![Address::repeat_byte(0x02), VALIDATOR],
+            infos: HashMap::from([(VALIDATOR, info)]),
+            schedule: vec![(41, Address::repeat_byte(0x02)), (42, VALIDATOR), (43, VALIDATOR)],
+            unavailable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_producer_status() {
+        let attester = StubAttester { pending: 2, last_attested: Some(40) };
+        let module = AndeValidatorApiImpl::new(VALIDATOR, Arc::new(consensus()))
+            .with_attester(Arc::new(attester))
+            .into_rpc();
+        let status: Value = module.call("ande_validatorStatus", ()).await.unwrap();
+
+        assert_eq!(
+            status,
+            json!({
+                "address": format!("{VALIDATOR:?}"),
+                "inActiveSet": true,
+                "info": {
+                    "stake": "0x3e8",
+                    "power": "0xa",
+                    "uptime": "0x26de",
+                    "jailed": false,
+                    "active": true,
+                },
+                "nextScheduledBlock": "0x2a",
+                "pendingAttestations": "0x2",
+                "lastAttestedBlock": "0x28",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_only_status_omits_producer_fields() {
+        let outsider = Address::repeat_byte(0x03);
+        let module = AndeValidatorApiImpl::new(outsider, Arc::new(consensus())).into_rpc();
+        let status: Value = module.call("ande_validatorStatus", ()).await.unwrap();
+
+        assert_eq!(status["inActiveSet"], false);
+        assert_eq!(status["info"]["stake"], "0x0");
+        for field in ["nextScheduledBlock", "pendingAttestations", "lastAttestedBlock"] {
+            assert!(status.get(field).is_none(), "{field} reported by a read-only node");
+        }
+
+        // Producing nodes report the fields even when there's nothing to report
+        let attester = StubAttester { pending: 0, last_attested: None };
+        let module =
+            AndeValidatorApiImpl::new(outsider, Arc::new(consensus())).with_attester(Arc::new(attester)).into_rpc();
+        let status: Value = module.call("ande_validatorStatus", ()).await.unwrap();
+        assert_eq!(status["nextScheduledBlock"], Value::Null);
+        assert_eq!(status["pendingAttestations"], "0x0");
+        assert_eq!(status["lastAttestedBlock"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_consensus_is_an_error() {
+        let consensus = StubConsensus { unavailable: true, ..consensus() };
+        let module = AndeValidatorApiImpl::new(VALIDATOR, Arc::new(consensus)).into_rpc();
+
+        match module.call::<_, Value>("ande_validatorStatus", ()).await.unwrap_err() {
+            MethodsError::JsonRpc(error) => assert_eq!(error.code(), CONSENSUS_UNAVAILABLE_CODE),
+            other => panic!("Expected a JSON-RPC error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! AndeSequencerRegistry Integration
+//!
+//! [`SequencerRegistryClient`] registers the node's sequencer with the
+//! AndeSequencerRegistry contract, keeps its RPC endpoint up to date, sends
+//! the heartbeats proving it's alive and deregisters it.
+//!
+//! With auto-registration enabled, the node checks at startup whether its
+//! sequencer is registered, registers it if not, and then sends heartbeats
+//! from a background task. Failed heartbeats are retried sooner than the
+//! regular interval, see [`HeartbeatScheduler`].
+
+use alloy::{
+    network::{Ethereum, EthereumWallet},
+    providers::{DynProvider, PendingTransactionBuilder, PendingTransactionError, ProviderBuilder},
+    rpc::client::RpcClient,
+    signers::local::PrivateKeySigner,
+    transports::http::reqwest::Url,
+};
+use alloy_primitives::{Address, B256};
+use ande_consensus_bindings::AndeSequencerRegistry::{self, AndeSequencerRegistryInstance};
+use crate::failover::{FailoverStatus, FailoverTransport};
+use std::{fmt, path::Path, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Default interval between two heartbeats
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Delay before retrying a failed heartbeat the first time
+pub const HEARTBEAT_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+
+/// Errors returned by the [`SequencerRegistryClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum SequencerRegistryError {
+    /// The RPC URL could not be parsed
+    #[error("Invalid RPC URL {url}: {reason}")]
+    InvalidRpcUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// No RPC endpoint was given
+    #[error("At least one AndeSequencerRegistry RPC endpoint is required")]
+    NoRpcEndpoint,
+    /// The sequencer key could not be loaded
+    #[error("Invalid sequencer key in {path}: {reason}")]
+    InvalidKey {
+        /// File the key was read from
+        path: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// The contract call could not be sent, e.g. because gas estimation reverted
+    #[error("AndeSequencerRegistry call failed: {0}")]
+    Contract(#[from] alloy::contract::Error),
+    /// The transaction was sent, but its receipt could not be obtained
+    #[error("AndeSequencerRegistry transaction not confirmed: {0}")]
+    Confirmation(#[from] PendingTransactionError),
+    /// The transaction was included but reverted
+    #[error("AndeSequencerRegistry {call} reverted in transaction {tx_hash}")]
+    Reverted {
+        /// Contract function called
+        call: &'static str,
+        /// Hash of the reverted transaction
+        tx_hash: B256,
+    },
+}
+
+/// Read the hex-encoded sequencer private key stored in `path`
+pub fn read_sequencer_key(path: &Path) -> Result<PrivateKeySigner, SequencerRegistryError> {
+    let invalid = |reason: String| SequencerRegistryError::InvalidKey { path: path.display().to_string(), reason };
+    let key = std::fs::read_to_string(path).map_err(|err| invalid(err.to_string()))?;
+    key.trim().parse().map_err(|err: alloy::signers::local::LocalSignerError| invalid(err.to_string()))
+}
+
+/// Client of the AndeSequencerRegistry contract, signing as the sequencer
+#[derive(Clone)]
+pub struct SequencerRegistryClient {
+    /// Registry contract, signing as the sequencer
+    registry: AndeSequencerRegistryInstance<DynProvider>,
+    /// Sequencer address
+    sequencer_address: Address,
+    /// Failover between the RPC endpoints
+    transport: FailoverTransport,
+}
+
+impl fmt::Debug for SequencerRegistryClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SequencerRegistryClient")
+            .field("contract_address", self.registry.address())
+            .field("sequencer_address", &self.sequencer_address)
+            .field("transport", &self.transport)
+            .finish()
+    }
+}
+
+impl SequencerRegistryClient {
+    /// Connect to the registry at `contract_address` through `rpc_urls`, by
+    /// priority, signing with `signer`
+    pub fn connect(
+        rpc_urls: &[&str],
+        contract_address: Address,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, SequencerRegistryError> {
+        let parse = |url: &&str| {
+            url.parse::<Url>().map_err(|err| SequencerRegistryError::InvalidRpcUrl {
+                url: url.to_string(),
+                reason: err.to_string(),
+            })
+        };
+        let (primary, fallbacks) = rpc_urls.split_first().ok_or(SequencerRegistryError::NoRpcEndpoint)?;
+        let fallbacks = fallbacks.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        let transport = FailoverTransport::new(parse(primary)?, fallbacks);
+
+        let sequencer_address = signer.address();
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .connect_client(RpcClient::new(transport.clone(), false))
+            .erased();
+
+        info!("Sequencer registry client connected: contract={}, sequencer={}", contract_address, sequencer_address);
+        Ok(Self {
+            registry: AndeSequencerRegistry::new(contract_address, provider),
+            sequencer_address,
+            transport,
+        })
+    }
+
+    /// Sequencer address
+    pub fn sequencer_address(&self) -> Address {
+        self.sequencer_address
+    }
+
+    /// RPC endpoint currently serving the requests, and failovers so far
+    pub fn endpoint_status(&self) -> FailoverStatus {
+        self.transport.status()
+    }
+
+    /// Whether the sequencer is registered
+    pub async fn is_registered(&self) -> Result<bool, SequencerRegistryError> {
+        Ok(self.registry.isSequencer(self.sequencer_address).call().await?)
+    }
+
+    /// Register the sequencer, reachable at `rpc_endpoint` and over libp2p as
+    /// `p2p_peer_id`
+    pub async fn register(&self, p2p_peer_id: B256, rpc_endpoint: String) -> Result<B256, SequencerRegistryError> {
+        let pending = self.registry.registerSequencer(p2p_peer_id, rpc_endpoint).send().await?;
+        confirm("registerSequencer", pending).await
+    }
+
+    /// Register the sequencer unless it already is
+    ///
+    /// Returns whether it was registered by this call.
+    pub async fn ensure_registered(
+        &self,
+        p2p_peer_id: B256,
+        rpc_endpoint: String,
+    ) -> Result<bool, SequencerRegistryError> {
+        if self.is_registered().await? {
+            debug!("Sequencer {} already registered", self.sequencer_address);
+            return Ok(false);
+        }
+
+        let tx_hash = self.register(p2p_peer_id, rpc_endpoint).await?;
+        info!("Sequencer {} registered: tx={}", self.sequencer_address, tx_hash);
+        Ok(true)
+    }
+
+    /// Change the RPC endpoint the sequencer is reachable at
+    pub async fn update_endpoint(&self, rpc_endpoint: String) -> Result<B256, SequencerRegistryError> {
+        let pending = self.registry.updateEndpoint(rpc_endpoint).send().await?;
+        confirm("updateEndpoint", pending).await
+    }
+
+    /// Prove the sequencer is alive
+    pub async fn heartbeat(&self) -> Result<B256, SequencerRegistryError> {
+        let pending = self.registry.heartbeat().send().await?;
+        confirm("heartbeat", pending).await
+    }
+
+    /// Deregister the sequencer
+    pub async fn deregister(&self) -> Result<B256, SequencerRegistryError> {
+        let pending = self.registry.deregisterSequencer().send().await?;
+        let tx_hash = confirm("deregisterSequencer", pending).await?;
+        info!("Sequencer {} deregistered: tx={}", self.sequencer_address, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Start background task sending a heartbeat every `interval`
+    pub fn start_heartbeat_task(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        info!("Starting sequencer heartbeat task (every {:?})", interval);
+        tokio::spawn(async move {
+            let mut scheduler = HeartbeatScheduler::new(interval);
+            loop {
+                tokio::time::sleep(scheduler.next_delay()).await;
+                match self.heartbeat().await {
+                    Ok(tx_hash) => {
+                        debug!("Sequencer heartbeat sent: tx={}", tx_hash);
+                        scheduler.record_success();
+                    }
+                    Err(e) => {
+                        scheduler.record_failure();
+                        warn!(
+                            "Sequencer heartbeat failed ({} in a row), retrying in {:?}: {}",
+                            scheduler.consecutive_failures(),
+                            scheduler.next_delay(),
+                            e
+                        );
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Wait for the receipt of a registry transaction, failing if it reverted
+async fn confirm(
+    call: &'static str,
+    pending: PendingTransactionBuilder<Ethereum>,
+) -> Result<B256, SequencerRegistryError> {
+    let receipt = pending.get_receipt().await?;
+    if !receipt.status() {
+        return Err(SequencerRegistryError::Reverted { call, tx_hash: receipt.transaction_hash });
+    }
+
+    debug!("AndeSequencerRegistry {} confirmed: tx={}", call, receipt.transaction_hash);
+    Ok(receipt.transaction_hash)
+}
+
+/// Timing of the heartbeats
+///
+/// Heartbeats are sent every interval. After a failure, the next one is sent
+/// after [`HEARTBEAT_RETRY_BASE_DELAY`], doubling with each further failure,
+/// but never later than the regular interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatScheduler {
+    interval: Duration,
+    failures: u32,
+}
+
+impl HeartbeatScheduler {
+    /// Create a scheduler sending heartbeats every `interval`
+    pub const fn new(interval: Duration) -> Self {
+        Self { interval, failures: 0 }
+    }
+
+    /// Delay before the next heartbeat
+    pub fn next_delay(&self) -> Duration {
+        match self.failures {
+            0 => self.interval,
+            failures => HEARTBEAT_RETRY_BASE_DELAY
+                .saturating_mul(2u32.saturating_pow(failures - 1))
+                .min(self.interval),
+        }
+    }
+
+    /// Note that the last heartbeat went through
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Note that the last heartbeat failed
+    pub fn record_failure(&mut self) {
+        self.failures = self.failures.saturating_add(1);
+    }
+
+    /// Heartbeats failed since the last one that went through
+    pub const fn consecutive_failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_retries_back_off_up_to_interval() {
+        let mut scheduler = HeartbeatScheduler::new(DEFAULT_HEARTBEAT_INTERVAL);
+        assert_eq!(scheduler.next_delay(), DEFAULT_HEARTBEAT_INTERVAL);
+
+        let delays: Vec<_> = (0..6)
+            .map(|_| {
+                scheduler.record_failure();
+                scheduler.next_delay().as_secs()
+            })
+            .collect();
+        assert_eq!(delays, [5, 10, 20, 40, 60, 60]);
+        assert_eq!(scheduler.consecutive_failures(), 6);
+
+        // A heartbeat going through restores the regular interval
+        scheduler.record_success();
+        assert_eq!(scheduler.consecutive_failures(), 0);
+        assert_eq!(scheduler.next_delay(), DEFAULT_HEARTBEAT_INTERVAL);
+    }
+
+    #[test]
+    fn test_short_interval_caps_retries() {
+        let mut scheduler = HeartbeatScheduler::new(Duration::from_secs(2));
+        scheduler.record_failure();
+        assert_eq!(scheduler.next_delay(), Duration::from_secs(2));
+
+        // Many failures in a row don't overflow the delay
+        for _ in 0..100 {
+            scheduler.record_failure();
+        }
+        assert_eq!(scheduler.next_delay(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_connect_validates_endpoints() {
+        let registry = Address::repeat_byte(0x01);
+        assert!(matches!(
+            SequencerRegistryClient::connect(&[], registry, PrivateKeySigner::random()),
+            Err(SequencerRegistryError::NoRpcEndpoint)
+        ));
+        assert!(matches!(
+            SequencerRegistryClient::connect(&["http://localhost:8545", "not a url"], registry, PrivateKeySigner::random()),
+            Err(SequencerRegistryError::InvalidRpcUrl { .. })
+        ));
+
+        let signer = PrivateKeySigner::random();
+        let client = SequencerRegistryClient::connect(&["http://localhost:8545"], registry, signer.clone()).unwrap();
+        assert_eq!(client.sequencer_address(), signer.address());
+        assert_eq!(client.endpoint_status().failovers, 0);
+    }
+
+    #[test]
+    fn test_read_sequencer_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.key");
+        let signer = PrivateKeySigner::random();
+        std::fs::write(&path, format!("0x{}\n", alloy_primitives::hex::encode(signer.to_bytes()))).unwrap();
+        assert_eq!(read_sequencer_key(&path).unwrap().address(), signer.address());
+
+        std::fs::write(&path, "not a key").unwrap();
+        assert!(matches!(read_sequencer_key(&path), Err(SequencerRegistryError::InvalidKey { .. })));
+        assert!(matches!(
+            read_sequencer_key(&dir.path().join("missing.key")),
+            Err(SequencerRegistryError::InvalidKey { .. })
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+//! AndeNativeStaking Integration
+//!
+//! [`AndeStakingClient`] reads voting power, delegations and unbonding
+//! entries from the AndeNativeStaking contract.
+//!
+//! Consumers needing the stake of several accounts at once, like the
+//! validator-set precompile and the RPC, read a [`StakingSnapshot`] instead,
+//! see [`AndeStakingClient::staking_snapshot`]. It's refreshed from the
+//! contract once older than the configured interval, so they don't query the
+//! RPC endpoint on every read.
+
+use alloy::{
+    providers::{DynProvider, ProviderBuilder},
+    rpc::client::RpcClient,
+    transports::http::reqwest::Url,
+};
+use alloy_primitives::{Address, U256};
+use ande_consensus_bindings::{
+    ContractAddresses,
+    IAndeNativeStaking::{self, IAndeNativeStakingInstance},
+};
+use crate::failover::{FailoverStatus, FailoverTransport};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// Default age after which the staking snapshot is refreshed
+pub const DEFAULT_SNAPSHOT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Errors returned by the [`AndeStakingClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum StakingClientError {
+    /// The RPC URL could not be parsed
+    #[error("Invalid RPC URL {url}: {reason}")]
+    InvalidRpcUrl {
+        /// URL as given
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// No RPC endpoint was given
+    #[error("At least one AndeNativeStaking RPC endpoint is required")]
+    NoRpcEndpoint,
+    /// The contract call failed or its answer could not be decoded
+    #[error("AndeNativeStaking call failed: {0}")]
+    Contract(#[from] alloy::contract::Error),
+}
+
+/// Stake delegated to a validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delegation {
+    /// Validator the stake is delegated to
+    pub validator: Address,
+    /// Delegated amount, in wei
+    pub amount: U256,
+    /// Timestamp the delegation was made at
+    pub since: u64,
+}
+
+impl From<IAndeNativeStaking::Delegation> for Delegation {
+    fn from(delegation: IAndeNativeStaking::Delegation) -> Self {
+        Self {
+            validator: delegation.validator,
+            amount: delegation.amount,
+            since: delegation.since.saturating_to(),
+        }
+    }
+}
+
+/// Stake being withdrawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnbondingEntry {
+    /// Unbonding amount, in wei
+    pub amount: U256,
+    /// Timestamp from which the amount can be withdrawn
+    pub completion_time: u64,
+}
+
+impl From<IAndeNativeStaking::UnbondingEntry> for UnbondingEntry {
+    fn from(entry: IAndeNativeStaking::UnbondingEntry) -> Self {
+        Self { amount: entry.amount, completion_time: entry.completionTime.saturating_to() }
+    }
+}
+
+/// Stake of the tracked accounts, read from the contract at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakingSnapshot {
+    /// Total amount staked in the contract, in wei
+    pub total_staked: U256,
+    /// Voting power of the tracked accounts
+    pub voting_power: BTreeMap<Address, U256>,
+    /// When the snapshot was read
+    pub taken_at: Instant,
+}
+
+impl StakingSnapshot {
+    /// Voting power of `account`, zero if it isn't tracked
+    pub fn voting_power_of(&self, account: &Address) -> U256 {
+        self.voting_power.get(account).copied().unwrap_or_default()
+    }
+
+    /// Whether the snapshot is younger than `max_age` at `now`
+    pub fn is_fresh(&self, max_age: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.taken_at) < max_age
+    }
+}
+
+/// Read-only client of the AndeNativeStaking contract
+pub struct AndeStakingClient {
+    /// Staking contract
+    staking: IAndeNativeStakingInstance<DynProvider>,
+    /// Failover between the RPC endpoints
+    transport: FailoverTransport,
+    /// Accounts whose voting power is part of the snapshot
+    tracked_accounts: Vec<Address>,
+    /// Age after which the snapshot is refreshed
+    refresh_interval: Duration,
+    /// Last snapshot, locked while it's refreshed so concurrent readers
+    /// wait for a single refresh
+    snapshot: Mutex<Option<Arc<StakingSnapshot>>>,
+}
+
+impl fmt::Debug for AndeStakingClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AndeStakingClient")
+            .field("contract_address", self.staking.address())
+            .field("transport", &self.transport)
+            .field("tracked_accounts", &self.tracked_accounts)
+            .field("refresh_interval", &self.refresh_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AndeStakingClient {
+    /// Connect to the staking contract of `addresses` through `rpc_urls`, by
+    /// priority
+    pub fn connect(rpc_urls: &[&str], addresses: &ContractAddresses) -> Result<Self, StakingClientError> {
+        let parse = |url: &&str| {
+            url.parse::<Url>().map_err(|err| StakingClientError::InvalidRpcUrl {
+                url: url.to_string(),
+                reason: err.to_string(),
+            })
+        };
+        let (primary, fallbacks) = rpc_urls.split_first().ok_or(StakingClientError::NoRpcEndpoint)?;
+        let fallbacks = fallbacks.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        let transport = FailoverTransport::new(parse(primary)?, fallbacks);
+
+        let provider = ProviderBuilder::new().connect_client(RpcClient::new(transport.clone(), false)).erased();
+
+        info!("Staking client connected: contract={}", addresses.staking);
+        Ok(Self {
+            staking: IAndeNativeStaking::new(addresses.staking, provider),
+            transport,
+            tracked_accounts: Vec::new(),
+            refresh_interval: DEFAULT_SNAPSHOT_REFRESH_INTERVAL,
+            snapshot: Mutex::new(None),
+        })
+    }
+
+    /// Include the voting power of `accounts` in the snapshot
+    pub fn with_tracked_accounts(mut self, accounts: Vec<Address>) -> Self {
+        self.tracked_accounts = accounts;
+        self
+    }
+
+    /// Refresh the snapshot once older than `interval`
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// RPC endpoint currently serving the requests, and failovers so far
+    pub fn endpoint_status(&self) -> FailoverStatus {
+        self.transport.status()
+    }
+
+    /// Voting power of `account`
+    pub async fn voting_power(&self, account: Address) -> Result<U256, StakingClientError> {
+        Ok(self.staking.getVotingPower(account).call().await?)
+    }
+
+    /// Total amount staked in the contract, in wei
+    pub async fn total_staked(&self) -> Result<U256, StakingClientError> {
+        Ok(self.staking.totalStaked().call().await?)
+    }
+
+    /// Delegations made by `delegator`
+    pub async fn delegations_of(&self, delegator: Address) -> Result<Vec<Delegation>, StakingClientError> {
+        let delegations = self.staking.getDelegations(delegator).call().await?;
+        Ok(delegations.into_iter().map(Delegation::from).collect())
+    }
+
+    /// Stake of `account` being withdrawn
+    pub async fn unbonding_entries(&self, account: Address) -> Result<Vec<UnbondingEntry>, StakingClientError> {
+        let entries = self.staking.getUnbondingEntries(account).call().await?;
+        Ok(entries.into_iter().map(UnbondingEntry::from).collect())
+    }
+
+    /// Stake of the tracked accounts, refreshed from the contract if the
+    /// last snapshot is older than the refresh interval
+    ///
+    /// A failed refresh is returned as an error and leaves the last snapshot
+    /// in place, to be retried by the next call.
+    pub async fn staking_snapshot(&self) -> Result<Arc<StakingSnapshot>, StakingClientError> {
+        let mut cached = self.snapshot.lock().await;
+        if let Some(snapshot) = cached.as_ref().filter(|s| s.is_fresh(self.refresh_interval, Instant::now())) {
+            return Ok(Arc::clone(snapshot));
+        }
+
+        let snapshot = Arc::new(self.read_snapshot().await?);
+        debug!(
+            "Staking snapshot refreshed: total_staked={}, accounts={}",
+            snapshot.total_staked,
+            snapshot.voting_power.len()
+        );
+        *cached = Some(Arc::clone(&snapshot));
+        Ok(snapshot)
+    }
+
+    /// Read the stake of the tracked accounts from the contract
+    async fn read_snapshot(&self) -> Result<StakingSnapshot, StakingClientError> {
+        let total_staked = self.total_staked().await?;
+        let mut voting_power = BTreeMap::new();
+        for account in &self.tracked_accounts {
+            voting_power.insert(*account, self.voting_power(*account).await?);
+        }
+        Ok(StakingSnapshot { total_staked, voting_power, taken_at: Instant::now() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses() -> ContractAddresses {
+        ContractAddresses::new(Address::repeat_byte(0x01), Address::repeat_byte(0x02), Address::repeat_byte(0x03))
+    }
+
+    #[test]
+    fn test_snapshot_freshness() {
+        let taken_at = Instant::now();
+        let snapshot = StakingSnapshot {
+            total_staked: U256::from(300),
+            voting_power: BTreeMap::from([(Address::repeat_byte(0x0a), U256::from(100))]),
+            taken_at,
+        };
+
+        assert!(snapshot.is_fresh(Duration::from_secs(30), taken_at + Duration::from_secs(29)));
+        assert!(!snapshot.is_fresh(Duration::from_secs(30), taken_at + Duration::from_secs(30)));
+        // A clock reading earlier than the snapshot doesn't make it stale
+        let ahead = StakingSnapshot { taken_at: taken_at + Duration::from_secs(10), ..snapshot.clone() };
+        assert!(ahead.is_fresh(Duration::from_secs(30), taken_at));
+
+        assert_eq!(snapshot.voting_power_of(&Address::repeat_byte(0x0a)), U256::from(100));
+        assert_eq!(snapshot.voting_power_of(&Address::repeat_byte(0x0b)), U256::ZERO);
+    }
+
+    #[test]
+    fn test_tuples_convert() {
+        let delegation = IAndeNativeStaking::Delegation {
+            validator: Address::repeat_byte(0x0a),
+            amount: U256::from(1_000),
+            since: U256::from(1_700_000_000u64),
+        };
+        assert_eq!(
+            Delegation::from(delegation),
+            Delegation { validator: Address::repeat_byte(0x0a), amount: U256::from(1_000), since: 1_700_000_000 }
+        );
+
+        // Out of range timestamps saturate rather than wrap
+        let entry = IAndeNativeStaking::UnbondingEntry { amount: U256::from(5), completionTime: U256::MAX };
+        assert_eq!(UnbondingEntry::from(entry), UnbondingEntry { amount: U256::from(5), completion_time: u64::MAX });
+    }
+
+    #[test]
+    fn test_connect_validates_endpoints() {
+        assert!(matches!(AndeStakingClient::connect(&[], &addresses()), Err(StakingClientError::NoRpcEndpoint)));
+        assert!(matches!(
+            AndeStakingClient::connect(&["http://localhost:8545", "not a url"], &addresses()),
+            Err(StakingClientError::InvalidRpcUrl { .. })
+        ));
+
+        let client = AndeStakingClient::connect(&["http://localhost:8545"], &addresses()).unwrap();
+        assert_eq!(*client.staking.address(), addresses().staking);
+        assert_eq!(client.endpoint_status().failovers, 0);
+    }
+}
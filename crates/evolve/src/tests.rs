@@ -1,5 +1,21 @@
-use crate::types::{EvolvePayloadAttributes, PayloadAttributesError};
-use alloy_primitives::{Address, B256};
+use crate::{
+    config::{EvolveConfig, PayloadValidationConfig, TxpoolOverflowPolicy, DEFAULT_MAX_TXPOOL_BYTES},
+    types::{
+        EvolvePayloadAttributes, EvolvePayloadAttributesV1, PayloadAttributesError, PayloadAttributesVersion,
+        VersionedPayloadAttributes,
+    },
+};
+use alloy_consensus::{TxLegacy, TypedTransaction};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, Signature, B256};
+use reth_primitives::{Header, SealedHeader, TransactionSigned};
+use serde_json::{json, Value};
+use std::{fs, path::PathBuf};
+
+fn transaction(nonce: u64) -> TransactionSigned {
+    let tx = TxLegacy { nonce, gas_limit: 21_000, ..Default::default() };
+    TransactionSigned::new_unhashed(TypedTransaction::Legacy(tx).into(), Signature::test_signature())
+}
 
 /// Test payload attributes creation and basic field assignment
 #[test]
@@ -178,6 +194,15 @@ fn test_payload_attributes_errors() {
         error.to_string(),
         "Transaction validation failed: test error"
     );
+
+    let error = PayloadAttributesError::TimestampNotAfterParent {
+        timestamp: 10,
+        parent_timestamp: 12,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Timestamp 10 is not after parent timestamp 12"
+    );
 }
 
 /// Test payload attributes with edge case values
@@ -248,3 +273,347 @@ fn test_validation_consistency() {
     // Invalid gas limits should always fail
     assert!(base_attrs(Some(0)).validate().is_err());
 }
+
+/// Test that the timestamp must come strictly after the parent's
+#[test]
+fn test_timestamp_after_parent_validation() {
+    let attrs = EvolvePayloadAttributes::new(
+        vec![],
+        Some(1000000),
+        1234567890,
+        B256::random(),
+        Address::random(),
+        B256::random(),
+        1,
+    );
+
+    assert!(attrs.validate_against_parent(1234567889).is_ok());
+    assert!(matches!(
+        attrs.validate_against_parent(1234567890).unwrap_err(),
+        PayloadAttributesError::TimestampNotAfterParent {
+            timestamp: 1234567890,
+            parent_timestamp: 1234567890,
+        }
+    ));
+    assert!(attrs.validate_against_parent(1234567891).is_err());
+}
+
+/// Test that priority and excluded transactions can't overlap or repeat
+#[test]
+fn test_priority_and_excluded_validation() {
+    let (first, second, third) = (transaction(0), transaction(1), transaction(2));
+    let attrs = EvolvePayloadAttributes::new(
+        vec![third.clone()],
+        Some(1000000),
+        1234567890,
+        B256::random(),
+        Address::random(),
+        B256::random(),
+        1,
+    );
+    assert!(attrs.priority_transactions.is_empty() && attrs.excluded_hashes.is_empty());
+
+    let valid = attrs
+        .clone()
+        .with_priority_transactions(vec![first.clone(), second.clone()])
+        .with_excluded_hashes(vec![B256::repeat_byte(0x01)]);
+    assert!(valid.validate().is_ok());
+
+    let repeated = attrs.clone().with_priority_transactions(vec![first.clone(), first.clone()]);
+    assert!(matches!(
+        repeated.validate().unwrap_err(),
+        PayloadAttributesError::DuplicatePriorityTransaction(hash) if hash == *first.hash()
+    ));
+
+    // A priority transaction can't also be a regular one
+    let also_regular = attrs.clone().with_priority_transactions(vec![third.clone()]);
+    assert!(matches!(
+        also_regular.validate().unwrap_err(),
+        PayloadAttributesError::DuplicatePriorityTransaction(hash) if hash == *third.hash()
+    ));
+
+    let excluded = attrs
+        .clone()
+        .with_priority_transactions(vec![first.clone()])
+        .with_excluded_hashes(vec![*second.hash(), *first.hash()]);
+    assert!(matches!(
+        excluded.validate().unwrap_err(),
+        PayloadAttributesError::PriorityTransactionExcluded(hash) if hash == *first.hash()
+    ));
+
+    let repeated = attrs.with_excluded_hashes(vec![*second.hash(), *second.hash()]);
+    assert!(matches!(
+        repeated.validate().unwrap_err(),
+        PayloadAttributesError::DuplicateExcludedHash(hash) if hash == *second.hash()
+    ));
+}
+
+/// Test that attributes without priority or excluded transactions still deserialize
+#[test]
+fn test_priority_and_excluded_default_when_missing() {
+    let attrs = EvolvePayloadAttributes::new(
+        vec![],
+        Some(1000000),
+        1234567890,
+        B256::random(),
+        Address::random(),
+        B256::random(),
+        1,
+    )
+    .with_priority_transactions(vec![transaction(0)])
+    .with_excluded_hashes(vec![B256::repeat_byte(0x01)]);
+
+    let mut value = serde_json::to_value(&attrs).unwrap();
+    let deserialized: EvolvePayloadAttributes = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(deserialized.priority_transactions, attrs.priority_transactions);
+    assert_eq!(deserialized.excluded_hashes, attrs.excluded_hashes);
+
+    let object = value.as_object_mut().unwrap();
+    object.remove("priority_transactions");
+    object.remove("excluded_hashes");
+    let deserialized: EvolvePayloadAttributes = serde_json::from_value(value).unwrap();
+    assert!(deserialized.priority_transactions.is_empty());
+    assert!(deserialized.excluded_hashes.is_empty());
+}
+
+/// Test the parent, clock and configuration aware validation
+#[test]
+fn test_validation_against_parent_and_config() {
+    const NOW: u64 = 1_700_000_000;
+    let parent = SealedHeader::new(Header { timestamp: NOW - 2, ..Default::default() }, B256::random());
+    let config = PayloadValidationConfig::new();
+    let attrs = |timestamp, gas_limit| {
+        EvolvePayloadAttributes::new(
+            vec![transaction(0), transaction(1)],
+            gas_limit,
+            timestamp,
+            B256::random(),
+            Address::repeat_byte(0x01),
+            parent.hash(),
+            parent.number + 1,
+        )
+    };
+
+    assert!(attrs(NOW, Some(30_000_000)).validate_against(&parent, NOW, &config).is_ok());
+    // Behind local time is fine, ahead only within the drift
+    assert!(attrs(NOW - 1, None).validate_against(&parent, NOW, &config).is_ok());
+    assert!(attrs(NOW + 15, None).validate_against(&parent, NOW, &config).is_ok());
+
+    // Context-free and parent checks still apply
+    assert!(matches!(
+        attrs(NOW, Some(0)).validate_against(&parent, NOW, &config).unwrap_err(),
+        PayloadAttributesError::InvalidGasLimit
+    ));
+    assert!(matches!(
+        attrs(NOW - 2, None).validate_against(&parent, NOW, &config).unwrap_err(),
+        PayloadAttributesError::TimestampNotAfterParent { .. }
+    ));
+
+    assert!(matches!(
+        attrs(NOW + 16, None).validate_against(&parent, NOW, &config).unwrap_err(),
+        PayloadAttributesError::TimestampTooFarAhead { timestamp, now: NOW, max_drift: 15 } if timestamp == NOW + 16
+    ));
+
+    assert!(matches!(
+        attrs(NOW, Some(4_999)).validate_against(&parent, NOW, &config).unwrap_err(),
+        PayloadAttributesError::GasLimitOutOfBounds { gas_limit: 4_999, min: 5_000, max: 30_000_000 }
+    ));
+    assert!(matches!(
+        attrs(NOW, Some(30_000_001)).validate_against(&parent, NOW, &config).unwrap_err(),
+        PayloadAttributesError::GasLimitOutOfBounds { gas_limit: 30_000_001, .. }
+    ));
+
+    // The zero fee recipient is only rejected in consensus mode
+    let mut unrewarded = attrs(NOW, None);
+    unrewarded.suggested_fee_recipient = Address::ZERO;
+    assert!(unrewarded.validate_against(&parent, NOW, &config).is_ok());
+    let consensus = PayloadValidationConfig { require_fee_recipient: true, ..config.clone() };
+    assert!(matches!(
+        unrewarded.validate_against(&parent, NOW, &consensus).unwrap_err(),
+        PayloadAttributesError::MissingFeeRecipient
+    ));
+}
+
+/// Test that the transaction totals, priority transactions included, are capped
+#[test]
+fn test_validation_of_transaction_totals() {
+    let attrs = EvolvePayloadAttributes::new(
+        vec![transaction(0)],
+        None,
+        1234567890,
+        B256::random(),
+        Address::repeat_byte(0x01),
+        B256::random(),
+        1,
+    )
+    .with_priority_transactions(vec![transaction(1)]);
+    let bytes = (transaction(0).encode_2718_len() + transaction(1).encode_2718_len()) as u64;
+
+    let exact = EvolveConfig::new_with_gas(bytes, 42_000);
+    assert!(attrs.validate_transaction_limits(&exact).is_ok());
+    // Zero caps are disabled
+    assert!(attrs.validate_transaction_limits(&EvolveConfig::new_with_gas(0, 0)).is_ok());
+
+    assert!(matches!(
+        attrs.validate_transaction_limits(&EvolveConfig::new_with_gas(bytes, 41_999)).unwrap_err(),
+        PayloadAttributesError::TransactionsGasExceeded { gas: 42_000, max: 41_999 }
+    ));
+    assert!(matches!(
+        attrs.validate_transaction_limits(&EvolveConfig::new_with_gas(bytes - 1, 42_000)).unwrap_err(),
+        PayloadAttributesError::TransactionsSizeExceeded { bytes: total, max } if total == bytes && max == bytes - 1
+    ));
+}
+
+/// Test that oversized batches are truncated from the tail or rejected, per the overflow policy
+#[test]
+fn test_transaction_limits_enforcement() {
+    let batch: Vec<_> = (0..10).map(transaction).collect();
+    let attrs = EvolvePayloadAttributes::new(
+        batch[1..].to_vec(),
+        None,
+        1234567890,
+        B256::random(),
+        Address::repeat_byte(0x01),
+        B256::random(),
+        1,
+    )
+    .with_priority_transactions(vec![batch[0].clone()]);
+    let tx_bytes = batch[0].encode_2718_len() as u64;
+
+    // Room for four transfers: the priority one and the first three others
+    let truncate = EvolveConfig::new_with_gas(DEFAULT_MAX_TXPOOL_BYTES, 4 * 21_000);
+    let mut truncated = attrs.clone();
+    let dropped = truncated.enforce_transaction_limits(&truncate).unwrap();
+    assert_eq!(truncated.transactions, batch[1..4].to_vec());
+    assert_eq!(truncated.priority_transactions, vec![batch[0].clone()]);
+    assert_eq!(dropped, batch[4..].iter().map(|tx| *tx.hash()).collect::<Vec<_>>());
+
+    // Same for the byte cap, and the same cut every time
+    let truncate = EvolveConfig::new_with_gas(4 * tx_bytes + 1, 0);
+    let mut again = attrs.clone();
+    assert_eq!(again.enforce_transaction_limits(&truncate).unwrap(), dropped);
+    assert_eq!(again.transactions, truncated.transactions);
+
+    // Nothing is dropped when the batch fits
+    let mut fitting = attrs.clone();
+    assert!(fitting.enforce_transaction_limits(&EvolveConfig::default()).unwrap().is_empty());
+    assert_eq!(fitting.transactions.len(), 9);
+
+    let reject = EvolveConfig::new_with_gas(DEFAULT_MAX_TXPOOL_BYTES, 4 * 21_000)
+        .with_overflow_policy(TxpoolOverflowPolicy::Reject);
+    let mut rejected = attrs.clone();
+    assert!(matches!(
+        rejected.enforce_transaction_limits(&reject).unwrap_err(),
+        PayloadAttributesError::TransactionsGasExceeded { gas: 210_000, max: 84_000 }
+    ));
+    assert_eq!(rejected.transactions.len(), 9, "a rejected payload is left as is");
+
+    // Priority transactions are never truncated
+    let mut priority_only = attrs;
+    assert!(matches!(
+        priority_only.enforce_transaction_limits(&EvolveConfig::new_with_gas(0, 20_000)).unwrap_err(),
+        PayloadAttributesError::TransactionsGasExceeded { gas: 21_000, max: 20_000 }
+    ));
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/payload_attributes")
+}
+
+fn golden(name: &str) -> Value {
+    serde_json::from_str(&fs::read_to_string(golden_dir().join(name)).unwrap()).unwrap()
+}
+
+/// Test that every schema version has a golden file and a changelog entry,
+/// and that the current attributes still read every golden file
+#[test]
+fn test_payload_attributes_golden_files() {
+    let changelog = fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../CHANGELOG.md")).unwrap();
+    for version in PayloadAttributesVersion::ALL {
+        assert!(
+            changelog.contains(&format!("Payload attributes schema `{version}`")),
+            "CHANGELOG.md has no entry for payload attributes schema {version}"
+        );
+
+        let golden = golden(&format!("{version}.json"));
+        let versioned: VersionedPayloadAttributes = serde_json::from_value(golden.clone()).unwrap();
+        assert_eq!(versioned.version(), version);
+        let attributes = EvolvePayloadAttributes::from(versioned);
+        attributes.validate().unwrap();
+        if version == PayloadAttributesVersion::LATEST {
+            // Written back as read: no field was renamed
+            assert_eq!(serde_json::to_value(VersionedPayloadAttributes::from(attributes)).unwrap(), golden);
+        }
+    }
+
+    // Files of older sidecars, e.g. without a version, still read
+    for entry in fs::read_dir(golden_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let json = fs::read_to_string(&path).unwrap();
+        let attributes: VersionedPayloadAttributes = serde_json::from_str(&json)
+            .unwrap_or_else(|err| panic!("{} no longer deserializes: {err}", path.display()));
+        assert_eq!(EvolvePayloadAttributes::from(attributes).block_number, 42);
+    }
+}
+
+/// Test that unknown fields and missing optional fields don't fail reading
+/// versioned attributes, and that unknown versions do
+#[test]
+fn test_versioned_payload_attributes_forward_compatibility() {
+    let mut golden = golden("v1.json");
+    let object = golden.as_object_mut().unwrap();
+    object.insert("inclusion_deadline".to_string(), json!(1_700_000_012));
+    object.remove("gas_limit");
+    object.remove("priority_transactions");
+    object.remove("excluded_hashes");
+
+    let versioned: VersionedPayloadAttributes = serde_json::from_value(golden.clone()).unwrap();
+    let attributes = EvolvePayloadAttributes::from(versioned);
+    assert_eq!(attributes.gas_limit, None);
+    assert!(attributes.priority_transactions.is_empty());
+    assert!(attributes.excluded_hashes.is_empty());
+    assert_eq!(attributes.timestamp, 1_700_000_000);
+
+    let object = golden.as_object_mut().unwrap();
+    object.insert("version".to_string(), json!("v9"));
+    let err = serde_json::from_value::<VersionedPayloadAttributes>(golden.clone()).unwrap_err();
+    assert!(err.to_string().starts_with("Unsupported payload attributes version"), "{err}");
+
+    // Required fields stay required
+    let object = golden.as_object_mut().unwrap();
+    object.insert("version".to_string(), json!("v1"));
+    object.remove("timestamp");
+    let err = serde_json::from_value::<VersionedPayloadAttributes>(golden).unwrap_err();
+    assert!(err.to_string().contains("timestamp"), "{err}");
+}
+
+/// Test that the field names known to the schema match its serialization
+#[test]
+fn test_payload_attributes_v1_fields() {
+    let attributes = EvolvePayloadAttributes::new(
+        vec![transaction(0)],
+        Some(1000000),
+        1234567890,
+        B256::random(),
+        Address::random(),
+        B256::random(),
+        1,
+    )
+    .with_excluded_hashes(vec![B256::repeat_byte(0x01)]);
+    let v1 = EvolvePayloadAttributesV1::from(attributes.clone());
+
+    let value = serde_json::to_value(&v1).unwrap();
+    let mut fields: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+    let mut known = EvolvePayloadAttributesV1::FIELDS.to_vec();
+    fields.sort_unstable();
+    known.sort_unstable();
+    assert_eq!(fields, known);
+
+    let versioned = serde_json::to_value(VersionedPayloadAttributes::V1(v1.clone())).unwrap();
+    assert_eq!(versioned["version"], "v1");
+    let read: VersionedPayloadAttributes = serde_json::from_value(versioned).unwrap();
+    assert_eq!(read, VersionedPayloadAttributes::V1(v1));
+    let read = EvolvePayloadAttributes::from(read);
+    assert_eq!(read.transactions, attributes.transactions);
+    assert_eq!(read.excluded_hashes, attributes.excluded_hashes);
+}
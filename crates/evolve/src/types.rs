@@ -1,8 +1,16 @@
+use crate::config::{EvolveConfig, PayloadValidationConfig, TxpoolOverflowPolicy};
+use alloy_consensus::Transaction;
+use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Address, B256};
-use reth_primitives::TransactionSigned;
-use serde::{Deserialize, Serialize};
+use reth_primitives::{SealedHeader, TransactionSigned};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::{collections::HashSet, fmt};
+use tracing::warn;
 
 /// Payload attributes for the Evolve Reth node
+///
+/// Sidecars exchange them as [`VersionedPayloadAttributes`], whose schema is
+/// stable across releases.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvolvePayloadAttributes {
     /// List of transactions to be executed in the payload
@@ -19,6 +27,12 @@ pub struct EvolvePayloadAttributes {
     pub parent_hash: B256,
     /// Block number
     pub block_number: u64,
+    /// Transactions that must be included first, in order, failing the build otherwise
+    #[serde(default)]
+    pub priority_transactions: Vec<TransactionSigned>,
+    /// Hashes of transactions never to include, e.g. sanctioned or known to revert
+    #[serde(default)]
+    pub excluded_hashes: Vec<B256>,
 }
 
 impl EvolvePayloadAttributes {
@@ -40,11 +54,28 @@ impl EvolvePayloadAttributes {
             suggested_fee_recipient,
             parent_hash,
             block_number,
+            priority_transactions: Vec::new(),
+            excluded_hashes: Vec::new(),
         }
     }
 
+    /// Sets the transactions to include first, in order
+    pub fn with_priority_transactions(mut self, transactions: Vec<TransactionSigned>) -> Self {
+        self.priority_transactions = transactions;
+        self
+    }
+
+    /// Sets the hashes of the transactions never to include
+    pub fn with_excluded_hashes(mut self, hashes: Vec<B256>) -> Self {
+        self.excluded_hashes = hashes;
+        self
+    }
+
     /// Validates the payload attributes
-    pub const fn validate(&self) -> Result<(), PayloadAttributesError> {
+    ///
+    /// A priority transaction may be listed once only, among both the priority
+    /// and regular transactions, and can't be excluded.
+    pub fn validate(&self) -> Result<(), PayloadAttributesError> {
         // For evolve, empty transactions are allowed (empty blocks are valid)
 
         if let Some(gas_limit) = self.gas_limit {
@@ -53,8 +84,309 @@ impl EvolvePayloadAttributes {
             }
         }
 
+        let mut excluded = HashSet::with_capacity(self.excluded_hashes.len());
+        for hash in &self.excluded_hashes {
+            if !excluded.insert(*hash) {
+                return Err(PayloadAttributesError::DuplicateExcludedHash(*hash));
+            }
+        }
+
+        let mut priority = HashSet::with_capacity(self.priority_transactions.len());
+        for tx in &self.priority_transactions {
+            let hash = *tx.hash();
+            if excluded.contains(&hash) {
+                return Err(PayloadAttributesError::PriorityTransactionExcluded(hash));
+            }
+            if !priority.insert(hash) {
+                return Err(PayloadAttributesError::DuplicatePriorityTransaction(hash));
+            }
+        }
+        if let Some(tx) = self.transactions.iter().find(|tx| priority.contains(tx.hash())) {
+            return Err(PayloadAttributesError::DuplicatePriorityTransaction(*tx.hash()));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the payload attributes against the parent block
+    ///
+    /// The block must come strictly after its parent.
+    pub const fn validate_against_parent(&self, parent_timestamp: u64) -> Result<(), PayloadAttributesError> {
+        if self.timestamp <= parent_timestamp {
+            return Err(PayloadAttributesError::TimestampNotAfterParent {
+                timestamp: self.timestamp,
+                parent_timestamp,
+            });
+        }
+
         Ok(())
     }
+
+    /// Validates the payload attributes against the parent block, local time
+    /// `now` and the bounds of `config`
+    ///
+    /// The timestamp may be behind local time, e.g. while catching up, but no
+    /// further ahead than the configured drift. A missing gas limit isn't
+    /// checked here, nor are the transaction totals, see
+    /// [`Self::enforce_transaction_limits`].
+    pub fn validate_against(
+        &self,
+        parent: &SealedHeader,
+        now: u64,
+        config: &PayloadValidationConfig,
+    ) -> Result<(), PayloadAttributesError> {
+        self.validate()?;
+        self.validate_against_parent(parent.timestamp)?;
+
+        if self.timestamp > now.saturating_add(config.max_timestamp_drift_secs) {
+            return Err(PayloadAttributesError::TimestampTooFarAhead {
+                timestamp: self.timestamp,
+                now,
+                max_drift: config.max_timestamp_drift_secs,
+            });
+        }
+
+        if let Some(gas_limit) = self.gas_limit {
+            if !(config.min_gas_limit..=config.max_gas_limit).contains(&gas_limit) {
+                return Err(PayloadAttributesError::GasLimitOutOfBounds {
+                    gas_limit,
+                    min: config.min_gas_limit,
+                    max: config.max_gas_limit,
+                });
+            }
+        }
+
+        if config.require_fee_recipient && self.suggested_fee_recipient.is_zero() {
+            return Err(PayloadAttributesError::MissingFeeRecipient);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the total gas limit and encoded size of the transactions,
+    /// priority transactions included, against the txpool caps of `config`
+    pub fn validate_transaction_limits(&self, config: &EvolveConfig) -> Result<(), PayloadAttributesError> {
+        let transactions = self.priority_transactions.iter().chain(&self.transactions);
+        let (gas, bytes) = transaction_totals(transactions);
+        check_transaction_limits(gas, bytes, config)
+    }
+
+    /// Brings the transactions within the txpool caps of `config`, following
+    /// its overflow policy, and returns the hashes of the transactions dropped
+    ///
+    /// Truncation drops regular transactions from the tail of the list, so the
+    /// same payload is always cut the same way. Priority transactions are
+    /// never dropped: if they alone exceed the caps, the payload is rejected.
+    pub fn enforce_transaction_limits(&mut self, config: &EvolveConfig) -> Result<Vec<B256>, PayloadAttributesError> {
+        if config.overflow_policy == TxpoolOverflowPolicy::Reject {
+            return self.validate_transaction_limits(config).map(|()| Vec::new());
+        }
+
+        let (mut gas, mut bytes) = transaction_totals(self.priority_transactions.iter());
+        check_transaction_limits(gas, bytes, config)?;
+        // Keep the longest prefix of the regular transactions that fits
+        let mut fitting = 0;
+        for tx in &self.transactions {
+            let (tx_gas, tx_bytes) = transaction_totals(std::iter::once(tx));
+            let (next_gas, next_bytes) = (gas.saturating_add(tx_gas), bytes.saturating_add(tx_bytes));
+            if check_transaction_limits(next_gas, next_bytes, config).is_err() {
+                break;
+            }
+            (gas, bytes) = (next_gas, next_bytes);
+            fitting += 1;
+        }
+        Ok(self.transactions.drain(fitting..).map(|tx| *tx.hash()).collect())
+    }
+}
+
+/// Version of the JSON schema of [`VersionedPayloadAttributes`]
+///
+/// Every version has a golden file under `testdata/payload_attributes` and an
+/// entry in the changelog; the tests fail until both are checked in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadAttributesVersion {
+    /// [`EvolvePayloadAttributesV1`]
+    V1,
+}
+
+impl PayloadAttributesVersion {
+    /// Every version, oldest first
+    pub const ALL: [Self; 1] = [Self::V1];
+
+    /// Version attributes are serialized with
+    pub const LATEST: Self = Self::V1;
+
+    /// Name of the version, as found in the `version` field
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+        }
+    }
+}
+
+impl fmt::Display for PayloadAttributesVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Payload attributes, version 1 of the schema
+///
+/// The fields are frozen: renaming or removing one breaks the sidecars
+/// feeding attributes. Changes go into a new version instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvolvePayloadAttributesV1 {
+    /// List of transactions to be executed in the payload
+    pub transactions: Vec<TransactionSigned>,
+    /// Optional gas limit for the transactions
+    #[serde(default)]
+    pub gas_limit: Option<u64>,
+    /// Timestamp for the block
+    pub timestamp: u64,
+    /// Prev randao value
+    pub prev_randao: B256,
+    /// Suggested fee recipient
+    pub suggested_fee_recipient: Address,
+    /// Parent block hash
+    pub parent_hash: B256,
+    /// Block number
+    pub block_number: u64,
+    /// Transactions that must be included first, in order
+    #[serde(default)]
+    pub priority_transactions: Vec<TransactionSigned>,
+    /// Hashes of transactions never to include
+    #[serde(default)]
+    pub excluded_hashes: Vec<B256>,
+}
+
+impl EvolvePayloadAttributesV1 {
+    /// Names of the fields, as found in JSON
+    pub const FIELDS: &'static [&'static str] = &[
+        "transactions",
+        "gas_limit",
+        "timestamp",
+        "prev_randao",
+        "suggested_fee_recipient",
+        "parent_hash",
+        "block_number",
+        "priority_transactions",
+        "excluded_hashes",
+    ];
+}
+
+impl From<EvolvePayloadAttributesV1> for EvolvePayloadAttributes {
+    fn from(attributes: EvolvePayloadAttributesV1) -> Self {
+        Self {
+            transactions: attributes.transactions,
+            gas_limit: attributes.gas_limit,
+            timestamp: attributes.timestamp,
+            prev_randao: attributes.prev_randao,
+            suggested_fee_recipient: attributes.suggested_fee_recipient,
+            parent_hash: attributes.parent_hash,
+            block_number: attributes.block_number,
+            priority_transactions: attributes.priority_transactions,
+            excluded_hashes: attributes.excluded_hashes,
+        }
+    }
+}
+
+impl From<EvolvePayloadAttributes> for EvolvePayloadAttributesV1 {
+    fn from(attributes: EvolvePayloadAttributes) -> Self {
+        Self {
+            transactions: attributes.transactions,
+            gas_limit: attributes.gas_limit,
+            timestamp: attributes.timestamp,
+            prev_randao: attributes.prev_randao,
+            suggested_fee_recipient: attributes.suggested_fee_recipient,
+            parent_hash: attributes.parent_hash,
+            block_number: attributes.block_number,
+            priority_transactions: attributes.priority_transactions,
+            excluded_hashes: attributes.excluded_hashes,
+        }
+    }
+}
+
+/// Payload attributes tagged with the version of their schema, as exchanged
+/// with the sidecars feeding them
+///
+/// The `version` field selects the schema; attributes without one predate it
+/// and are read as [`PayloadAttributesVersion::V1`]. Reading is forward
+/// compatible: unknown fields are logged and ignored, and missing optional
+/// fields take their defaults. Unknown versions are refused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "version")]
+pub enum VersionedPayloadAttributes {
+    /// Version 1 of the schema
+    #[serde(rename = "v1")]
+    V1(EvolvePayloadAttributesV1),
+}
+
+impl VersionedPayloadAttributes {
+    /// Version of the schema
+    pub const fn version(&self) -> PayloadAttributesVersion {
+        match self {
+            Self::V1(_) => PayloadAttributesVersion::V1,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedPayloadAttributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+        let version = match fields.remove("version") {
+            Some(version) => serde_json::from_value(version)
+                .map_err(|err| D::Error::custom(format!("Unsupported payload attributes version: {err}")))?,
+            None => PayloadAttributesVersion::V1,
+        };
+
+        let known = match version {
+            PayloadAttributesVersion::V1 => EvolvePayloadAttributesV1::FIELDS,
+        };
+        for field in fields.keys().filter(|field| !known.contains(&field.as_str())) {
+            warn!(%version, %field, "Ignoring unknown payload attributes field");
+        }
+
+        let fields = serde_json::Value::Object(fields);
+        let attributes = match version {
+            PayloadAttributesVersion::V1 => serde_json::from_value(fields).map(Self::V1),
+        };
+        attributes.map_err(D::Error::custom)
+    }
+}
+
+impl From<VersionedPayloadAttributes> for EvolvePayloadAttributes {
+    fn from(attributes: VersionedPayloadAttributes) -> Self {
+        match attributes {
+            VersionedPayloadAttributes::V1(attributes) => attributes.into(),
+        }
+    }
+}
+
+impl From<EvolvePayloadAttributes> for VersionedPayloadAttributes {
+    // Tagged with the latest version
+    fn from(attributes: EvolvePayloadAttributes) -> Self {
+        Self::V1(attributes.into())
+    }
+}
+
+/// Total gas limit and encoded size of `transactions`
+fn transaction_totals<'a>(transactions: impl Iterator<Item = &'a TransactionSigned>) -> (u64, u64) {
+    transactions.fold((0u64, 0u64), |(gas, bytes), tx| {
+        (gas.saturating_add(tx.gas_limit()), bytes.saturating_add(tx.encode_2718_len() as u64))
+    })
+}
+
+/// Checks transaction totals against the txpool caps of `config`, zero caps
+/// being disabled
+const fn check_transaction_limits(gas: u64, bytes: u64, config: &EvolveConfig) -> Result<(), PayloadAttributesError> {
+    if config.max_txpool_gas > 0 && gas > config.max_txpool_gas {
+        return Err(PayloadAttributesError::TransactionsGasExceeded { gas, max: config.max_txpool_gas });
+    }
+    if config.max_txpool_bytes > 0 && bytes > config.max_txpool_bytes {
+        return Err(PayloadAttributesError::TransactionsSizeExceeded { bytes, max: config.max_txpool_bytes });
+    }
+    Ok(())
 }
 
 /// Errors that can occur during payload attributes validation
@@ -85,4 +417,84 @@ pub enum PayloadAttributesError {
     /// the specific validation failure.
     #[error("Transaction validation failed: {0}")]
     TransactionValidation(String),
+
+    /// Error when a priority transaction is listed more than once
+    ///
+    /// This error occurs when a priority transaction appears twice among the
+    /// priority transactions, or among the regular transactions as well.
+    #[error("Priority transaction {0} is listed more than once")]
+    DuplicatePriorityTransaction(B256),
+
+    /// Error when an excluded hash is listed more than once
+    #[error("Excluded transaction {0} is listed more than once")]
+    DuplicateExcludedHash(B256),
+
+    /// Error when a priority transaction is also excluded
+    ///
+    /// This error occurs when the hash of a transaction that must be included
+    /// is listed among the hashes never to include.
+    #[error("Priority transaction {0} is also excluded")]
+    PriorityTransactionExcluded(B256),
+
+    /// Error when the timestamp doesn't come after the parent block's
+    ///
+    /// This error occurs when the payload's timestamp is lower than or equal
+    /// to the timestamp of its parent block.
+    #[error("Timestamp {timestamp} is not after parent timestamp {parent_timestamp}")]
+    TimestampNotAfterParent {
+        /// Timestamp of the payload
+        timestamp: u64,
+        /// Timestamp of the parent block
+        parent_timestamp: u64,
+    },
+
+    /// Error when the timestamp is too far ahead of local time
+    ///
+    /// This error occurs when the payload's timestamp exceeds local time by
+    /// more than the configured drift.
+    #[error("Timestamp {timestamp} is more than {max_drift}s ahead of local time {now}")]
+    TimestampTooFarAhead {
+        /// Timestamp of the payload
+        timestamp: u64,
+        /// Local time the payload was validated at
+        now: u64,
+        /// Seconds the timestamp may run ahead of local time
+        max_drift: u64,
+    },
+
+    /// Error when the gas limit is outside the configured bounds
+    #[error("Gas limit {gas_limit} is outside the bounds [{min}, {max}]")]
+    GasLimitOutOfBounds {
+        /// Gas limit of the payload
+        gas_limit: u64,
+        /// Lowest gas limit accepted
+        min: u64,
+        /// Highest gas limit accepted
+        max: u64,
+    },
+
+    /// Error when the fee recipient is the zero address in consensus mode
+    ///
+    /// This error occurs when block rewards would be burnt because no fee
+    /// recipient was suggested while the consensus requires one.
+    #[error("Fee recipient is required in consensus mode")]
+    MissingFeeRecipient,
+
+    /// Error when the transactions add up to more gas than allowed
+    #[error("Transactions use {gas} gas, more than the maximum of {max}")]
+    TransactionsGasExceeded {
+        /// Total gas limit of the transactions
+        gas: u64,
+        /// Highest total gas limit accepted
+        max: u64,
+    },
+
+    /// Error when the transactions add up to more bytes than allowed
+    #[error("Transactions take {bytes} bytes, more than the maximum of {max}")]
+    TransactionsSizeExceeded {
+        /// Total encoded size of the transactions
+        bytes: u64,
+        /// Highest total encoded size accepted
+        max: u64,
+    },
 }
@@ -1,9 +1,11 @@
 //! Tests for Evolve consensus implementation
 
-use evolve_ev_reth::consensus::EvolveConsensus;
+use alloy_primitives::Address;
+use evolve_ev_reth::consensus::{EvolveConsensus, EvolveConsensusBuilder, ProducerScheduleSource};
 use reth_chainspec::MAINNET;
 use reth_consensus::{ConsensusError, HeaderValidator};
 use reth_primitives::{Header, SealedHeader};
+use std::sync::Arc;
 
 fn create_test_header(number: u64, parent_hash: [u8; 32], timestamp: u64) -> SealedHeader {
     let header = Header {
@@ -169,3 +171,67 @@ fn test_evolve_consensus_validates_block_number() {
         "Evolve consensus should validate block number"
     );
 }
+
+/// Schedule designating the producers of a few blocks only
+#[derive(Debug)]
+struct FakeSchedule(Vec<(u64, Address)>);
+
+impl ProducerScheduleSource for FakeSchedule {
+    fn designated_producer(&self, block_number: u64) -> Option<Address> {
+        self.0.iter().find(|(number, _)| *number == block_number).map(|(_, producer)| *producer)
+    }
+}
+
+fn produced_by(parent: &SealedHeader, producer: Address) -> SealedHeader {
+    let header = Header {
+        number: parent.number + 1,
+        parent_hash: parent.hash(),
+        timestamp: parent.timestamp,
+        gas_limit: 30_000_000,
+        beneficiary: producer,
+        ..Default::default()
+    };
+    SealedHeader::new(header, [2u8; 32].into())
+}
+
+#[test]
+fn test_producer_schedule_verification() {
+    let (designated, rogue) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let consensus = EvolveConsensusBuilder::new()
+        .with_producer_schedule(Arc::new(FakeSchedule(vec![(2, designated)])))
+        .build_with(MAINNET.clone());
+    let parent = create_test_header(1, [0u8; 32], 1000);
+
+    // The designated producer's block is accepted
+    consensus.validate_header_against_parent(&produced_by(&parent, designated), &parent).unwrap();
+
+    // Anyone else's is rejected
+    let err = consensus.validate_header_against_parent(&produced_by(&parent, rogue), &parent).unwrap_err();
+    assert!(err.to_string().contains("but 0x0101010101010101010101010101010101010101 is its designated producer"), "{err}");
+
+    // So are blocks of unknown producers, without the grace mode
+    let unscheduled = create_test_header(2, [0u8; 32], 1000);
+    let err = consensus.validate_header_against_parent(&produced_by(&unscheduled, designated), &unscheduled).unwrap_err();
+    assert!(err.to_string().contains("Designated producer of block 3 is unknown"), "{err}");
+
+    // Without a schedule, producers aren't checked
+    let unchecked = EvolveConsensusBuilder::build(MAINNET.clone());
+    unchecked.validate_header_against_parent(&produced_by(&parent, rogue), &parent).unwrap();
+}
+
+#[test]
+fn test_producer_schedule_sync_grace() {
+    let (designated, rogue) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let consensus = EvolveConsensusBuilder::new()
+        .with_producer_schedule(Arc::new(FakeSchedule(vec![(2, designated)])))
+        .with_sync_grace(true)
+        .build_with(MAINNET.clone());
+
+    // Blocks of unknown producers are accepted while syncing
+    let unscheduled = create_test_header(2, [0u8; 32], 1000);
+    consensus.validate_header_against_parent(&produced_by(&unscheduled, rogue), &unscheduled).unwrap();
+
+    // Known producers are still enforced
+    let parent = create_test_header(1, [0u8; 32], 1000);
+    assert!(consensus.validate_header_against_parent(&produced_by(&parent, rogue), &parent).is_err());
+}
@@ -26,7 +26,7 @@ async fn test_consensus_client_integration() {
     // };
     // 
     // let client = AndeConsensusClient::new(
-    //     &config.rpc_url,
+    //     &config.rpc_urls(),
     //     addresses,
     //     None, // No signer for read-only test
     // ).await.expect("Failed to create consensus client");
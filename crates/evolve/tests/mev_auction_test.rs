@@ -0,0 +1,120 @@
+//! Tests for the MEV auction client against mock MEVAuctionManager contracts on anvil
+//!
+//! These tests require the `anvil` binary in `PATH` and are ignored by default.
+
+use alloy::{
+    network::{EthereumWallet, TransactionBuilder},
+    node_bindings::Anvil,
+    primitives::{hex, Address, B256, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::{Filter, TransactionRequest},
+    signers::local::PrivateKeySigner,
+    sol_types::SolCall,
+};
+use ande_consensus_bindings::MEVAuctionManager;
+use evolve_ev_reth::mev::{BundleSubmission, MevAuctionClient, MevAuctionError};
+
+/// Init code of a mock accepting any call and logging its calldata with `LOG0`
+const LOGGING_MOCK: &str = "6008600a5f3960085ff3365f5f37365fa000";
+
+/// Init code of a mock reverting every call
+const REVERTING_MOCK: &str = "6003600a5f3960035ff35f5ffd";
+
+/// Deploy `init_code` from the wallet of `provider`
+async fn deploy(provider: &impl Provider, init_code: &str) -> Address {
+    let tx = TransactionRequest::default().with_deploy_code(hex::decode(init_code).unwrap());
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    receipt.contract_address.expect("deployment creates a contract")
+}
+
+fn bundle(target_block: u64) -> BundleSubmission {
+    BundleSubmission {
+        bundle_hash: B256::random(),
+        bid_amount: U256::from(1000),
+        target_block,
+        transactions: vec![B256::random(), B256::random()],
+        searcher: Address::random(),
+        encrypted_payload: None,
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_auction_calls_are_sent_to_contract() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, LOGGING_MOCK).await;
+
+    let client = MevAuctionClient::connect(&anvil.endpoint(), mock, signer.clone()).unwrap();
+    assert!(client.is_onchain());
+    assert_eq!(client.sequencer_address(), signer.address());
+
+    let executed = bundle(100);
+    let rejected = bundle(100);
+    client.submit_bundle(executed.clone()).await.unwrap();
+    client.submit_bundle(rejected.clone()).await.unwrap();
+    client.mark_bundle_executed(executed.bundle_hash, U256::from(2000), U256::from(900)).await.unwrap();
+    client.mark_bundle_rejected(rejected.bundle_hash, "tx reverted".to_string()).await.unwrap();
+
+    let logs = provider.get_logs(&Filter::new().address(mock).from_block(0)).await.unwrap();
+    let calldata: Vec<Vec<u8>> = logs.iter().map(|log| log.data().data.to_vec()).collect();
+    let submit = |bundle: &BundleSubmission| {
+        MEVAuctionManager::submitBundleCall {
+            bundleHash: bundle.bundle_hash,
+            searcher: bundle.searcher,
+            bidAmount: bundle.bid_amount,
+            targetBlock: U256::from(bundle.target_block),
+            txHashes: bundle.transactions.clone(),
+        }
+        .abi_encode()
+    };
+    assert_eq!(
+        calldata,
+        vec![
+            submit(&executed),
+            submit(&rejected),
+            MEVAuctionManager::markBundleExecutedCall {
+                bundleHash: executed.bundle_hash,
+                mevCaptured: U256::from(2000),
+                bidPaid: U256::from(900),
+            }
+            .abi_encode(),
+            MEVAuctionManager::markBundleRejectedCall {
+                bundleHash: rejected.bundle_hash,
+                reason: "tx reverted".to_string(),
+            }
+            .abi_encode(),
+        ]
+    );
+
+    let stats = client.get_auction_stats().await;
+    assert_eq!(stats.pending_bundles, 0);
+    assert_eq!(stats.executed_bundles, 1);
+    assert_eq!(stats.rejected_bundles, 1);
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_reverted_submission_is_not_recorded() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, REVERTING_MOCK).await;
+
+    let client = MevAuctionClient::connect(&anvil.endpoint(), mock, signer).unwrap();
+    let err = client.submit_bundle(bundle(100)).await.unwrap_err();
+    assert!(matches!(err, MevAuctionError::Contract(_)), "unexpected error: {err}");
+    assert!(client.get_bundles_for_block(100).await.is_empty());
+
+    let err = client.mark_bundle_rejected(B256::random(), "timeout".to_string()).await.unwrap_err();
+    assert!(matches!(err, MevAuctionError::Contract(_)), "unexpected error: {err}");
+    assert_eq!(client.get_auction_stats().await.rejected_bundles, 0);
+
+    assert!(matches!(
+        MevAuctionClient::connect("not a url", mock, PrivateKeySigner::random()),
+        Err(MevAuctionError::InvalidRpcUrl { .. })
+    ));
+}
@@ -0,0 +1,198 @@
+//! Tests for the MEV distributor client against mock MEVDistributor contracts on anvil
+//!
+//! These tests require the `anvil` binary in `PATH` and are ignored by default.
+
+use alloy::{
+    network::{EthereumWallet, TransactionBuilder},
+    node_bindings::Anvil,
+    primitives::{hex, Address, B256, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::{Filter, TransactionRequest},
+    signers::local::PrivateKeySigner,
+    sol_types::SolCall,
+};
+use ande_consensus_bindings::MEVDistributor;
+use evolve_ev_reth::mev::{
+    distributor::{EpochClock, EpochSchedule},
+    DepositRetryConfig, MevDistributorClient, MevDistributorError, SplitConfig,
+};
+use std::{sync::Arc, time::Duration};
+
+/// Init code of a mock accepting any call and logging its calldata with `LOG0`
+const LOGGING_MOCK: &str = "6008600a5f3960085ff3365f5f37365fa000";
+
+/// Init code of a mock reverting every call
+const REVERTING_MOCK: &str = "6003600a5f3960035ff35f5ffd";
+
+/// Init code of a mock logging the calldata of every call with `LOG0` and
+/// returning the six storage words starting at the slot numbered by the selector
+const STORAGE_MOCK: &str = "6041600a5f3960415ff3365f5f37365fa05f3560e01c80600001546000528060010154602052806002015460405280600301546060528060040154608052806005015460a0525060c05ff3";
+
+/// Clock stopped at a fixed time
+#[derive(Debug)]
+struct FixedClock(u64);
+
+impl EpochClock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Set the words returned by `STORAGE_MOCK` for calls with `selector`
+async fn set_return(provider: &impl Provider, mock: Address, selector: [u8; 4], words: &[U256]) {
+    let base = U256::from(u32::from_be_bytes(selector));
+    for (i, word) in words.iter().enumerate() {
+        let _: bool = provider
+            .raw_request(
+                "anvil_setStorageAt".into(),
+                (mock, base + U256::from(i), B256::from(*word)),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+/// Deploy `init_code` from the wallet of `provider`
+async fn deploy(provider: &impl Provider, init_code: &str) -> Address {
+    let tx = TransactionRequest::default().with_deploy_code(hex::decode(init_code).unwrap());
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    receipt.contract_address.expect("deployment creates a contract")
+}
+
+fn fast_retry() -> DepositRetryConfig {
+    DepositRetryConfig {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(10),
+        confirmation_timeout: Duration::from_secs(10),
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_deposit_sends_buffered_mev_as_value() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, LOGGING_MOCK).await;
+
+    let client = MevDistributorClient::connect(&anvil.endpoint(), mock, signer).unwrap().with_retry(fast_retry());
+    assert!(client.is_onchain());
+    let amount = U256::from(10).pow(U256::from(18));
+    client.add_mev(amount).await;
+
+    assert_eq!(client.deposit_mev().await.unwrap(), amount);
+    assert_eq!(client.get_buffer_amount().await, U256::ZERO);
+    assert_eq!(provider.get_balance(mock).await.unwrap(), amount);
+
+    let logs = provider.get_logs(&Filter::new().address(mock).from_block(0)).await.unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].data().data.to_vec(), MEVDistributor::depositMEVCall {}.abi_encode());
+
+    let stats = client.get_distributor_stats().await;
+    assert_eq!(stats.deposits_count, 1);
+    assert_eq!(stats.total_deposited, amount);
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_reverted_deposit_restores_buffer() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, REVERTING_MOCK).await;
+
+    let client = MevDistributorClient::connect(&anvil.endpoint(), mock, signer).unwrap().with_retry(fast_retry());
+    client.add_mev(U256::from(1000)).await;
+
+    let err = client.deposit_mev().await.unwrap_err();
+    assert!(matches!(err, MevDistributorError::Contract(_)), "unexpected error: {err}");
+    assert_eq!(client.get_buffer_amount().await, U256::from(1000));
+    assert_eq!(provider.get_balance(mock).await.unwrap(), U256::ZERO);
+    assert_eq!(client.get_distributor_stats().await.deposits_count, 0);
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_epoch_data_read_and_settled_by_designated_settler() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, STORAGE_MOCK).await;
+
+    set_return(&provider, mock, MEVDistributor::currentEpochCall::SELECTOR, &[U256::from(3)]).await;
+    set_return(&provider, mock, MEVDistributor::epochStartTimeCall::SELECTOR, &[U256::from(1_000)]).await;
+    set_return(&provider, mock, MEVDistributor::epochDurationCall::SELECTOR, &[U256::from(600)]).await;
+    let epoch_data = [500, 400, 75, 25, 1, 1_600].map(U256::from);
+    set_return(&provider, mock, MEVDistributor::getEpochDataCall::SELECTOR, &epoch_data).await;
+
+    let client = MevDistributorClient::connect(&anvil.endpoint(), mock, signer.clone())
+        .unwrap()
+        .with_clock(Arc::new(FixedClock(1_599)));
+
+    let epoch = client.get_current_epoch().await.unwrap();
+    assert_eq!(epoch.epoch, 3);
+    assert_eq!(epoch.total_mev, U256::from(500));
+    assert_eq!(epoch.stakers_reward, U256::from(400));
+    assert_eq!(epoch.protocol_fee, U256::from(75));
+    assert_eq!(epoch.treasury_amount, U256::from(25));
+    assert!(epoch.settled);
+    assert_eq!(epoch.timestamp, 1_600);
+    assert_eq!(
+        client.get_epoch_schedule().await.unwrap(),
+        Some(EpochSchedule { epoch: 3, start: 1_000, duration: 600 })
+    );
+
+    // Designated, but the window hasn't elapsed yet
+    let settler = U256::from_be_slice(signer.address().as_slice());
+    set_return(&provider, mock, MEVDistributor::designatedSettlerCall::SELECTOR, &[settler]).await;
+    assert!(!client.check_epoch_settlement().await.unwrap());
+
+    let client = client.with_clock(Arc::new(FixedClock(1_600)));
+    assert!(client.check_epoch_settlement().await.unwrap());
+    client.settle_epoch().await.unwrap();
+    let logs = provider.get_logs(&Filter::new().address(mock).from_block(0)).await.unwrap();
+    let settle = MEVDistributor::settleEpochCall {}.abi_encode();
+    assert_eq!(logs.iter().filter(|log| log.data().data.to_vec() == settle).count(), 1);
+
+    // Another sequencer is designated
+    set_return(&provider, mock, MEVDistributor::designatedSettlerCall::SELECTOR, &[U256::from(1)]).await;
+    assert!(!client.check_epoch_settlement().await.unwrap());
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_split_read_from_contract() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, STORAGE_MOCK).await;
+
+    set_return(&provider, mock, MEVDistributor::stakersBpsCall::SELECTOR, &[U256::from(7_000)]).await;
+    set_return(&provider, mock, MEVDistributor::protocolBpsCall::SELECTOR, &[U256::from(2_000)]).await;
+    set_return(&provider, mock, MEVDistributor::treasuryBpsCall::SELECTOR, &[U256::from(1_000)]).await;
+    let epoch_data = [1_000, 0, 0, 0, 0, 0].map(U256::from);
+    set_return(&provider, mock, MEVDistributor::getEpochDataCall::SELECTOR, &epoch_data).await;
+
+    let client = MevDistributorClient::connect(&anvil.endpoint(), mock, signer).unwrap();
+    assert_eq!(client.get_split_config().await.unwrap(), SplitConfig::new(7_000, 2_000, 1_000).unwrap());
+
+    // Unsettled epochs are reported with the contract's split
+    let epoch = client.get_epoch_info(2).await.unwrap();
+    assert!(!epoch.settled);
+    assert_eq!(
+        (epoch.stakers_reward, epoch.protocol_fee, epoch.treasury_amount),
+        (U256::from(700), U256::from(200), U256::from(100))
+    );
+
+    // The split is cached until invalidated
+    set_return(&provider, mock, MEVDistributor::treasuryBpsCall::SELECTOR, &[U256::from(900)]).await;
+    assert_eq!(client.get_split_config().await.unwrap().treasury_bps, 1_000);
+    client.invalidate_split_config().await;
+    let err = client.get_split_config().await.unwrap_err();
+    assert!(matches!(err, MevDistributorError::InvalidSplit { treasury_bps: 900, .. }), "unexpected error: {err}");
+}
@@ -0,0 +1,102 @@
+//! Tests for the sequencer registry client against mock AndeSequencerRegistry contracts on anvil
+//!
+//! These tests require the `anvil` binary in `PATH` and are ignored by default.
+
+use alloy::{
+    network::{EthereumWallet, TransactionBuilder},
+    node_bindings::Anvil,
+    primitives::{hex, Address, B256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::{Filter, TransactionRequest},
+    signers::local::PrivateKeySigner,
+    sol_types::SolCall,
+};
+use ande_consensus_bindings::AndeSequencerRegistry;
+use evolve_ev_reth::sequencer_registry::{SequencerRegistryClient, SequencerRegistryError};
+
+/// Init code of a mock logging its calldata with `LOG0` and returning `false`
+/// to every call, as a registry the sequencer isn't registered with
+const UNREGISTERED_MOCK: &str = "600d600a5f39600d5ff3365f5f37365fa06020610100f3";
+
+/// Init code of a mock logging its calldata with `LOG0` and returning `true`
+/// to every call, as a registry the sequencer is registered with
+const REGISTERED_MOCK: &str = "600f600a5f39600f5ff3365f5f37365fa060015f5260205ff3";
+
+/// Init code of a mock reverting every call
+const REVERTING_MOCK: &str = "6003600a5f3960035ff35f5ffd";
+
+/// Deploy `init_code` from the wallet of `provider`
+async fn deploy(provider: &impl Provider, init_code: &str) -> Address {
+    let tx = TransactionRequest::default().with_deploy_code(hex::decode(init_code).unwrap());
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    receipt.contract_address.expect("deployment creates a contract")
+}
+
+/// Calldata of the transactions sent to `mock`, in order
+async fn calls(provider: &impl Provider, mock: Address) -> Vec<Vec<u8>> {
+    let logs = provider.get_logs(&Filter::new().address(mock).from_block(0)).await.unwrap();
+    logs.iter().map(|log| log.data().data.to_vec()).collect()
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_missing_registration_is_sent() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, UNREGISTERED_MOCK).await;
+
+    let client = SequencerRegistryClient::connect(&[anvil.endpoint().as_str()], mock, signer).unwrap();
+    let peer_id = B256::repeat_byte(0x42);
+    assert!(!client.is_registered().await.unwrap());
+    assert!(client.ensure_registered(peer_id, "http://sequencer:8545".to_string()).await.unwrap());
+    client.update_endpoint("https://sequencer.example".to_string()).await.unwrap();
+    client.heartbeat().await.unwrap();
+    client.deregister().await.unwrap();
+
+    assert_eq!(
+        calls(&provider, mock).await,
+        vec![
+            AndeSequencerRegistry::registerSequencerCall {
+                p2pPeerId: peer_id,
+                rpcEndpoint: "http://sequencer:8545".to_string(),
+            }
+            .abi_encode(),
+            AndeSequencerRegistry::updateEndpointCall { rpcEndpoint: "https://sequencer.example".to_string() }
+                .abi_encode(),
+            AndeSequencerRegistry::heartbeatCall {}.abi_encode(),
+            AndeSequencerRegistry::deregisterSequencerCall {}.abi_encode(),
+        ]
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_registered_sequencer_is_not_registered_again() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, REGISTERED_MOCK).await;
+
+    let client = SequencerRegistryClient::connect(&[anvil.endpoint().as_str()], mock, signer).unwrap();
+    assert!(client.is_registered().await.unwrap());
+    assert!(!client.ensure_registered(B256::repeat_byte(0x42), "http://sequencer:8545".to_string()).await.unwrap());
+    assert!(calls(&provider, mock).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_reverted_heartbeat_is_an_error() {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::from(signer.clone())).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, REVERTING_MOCK).await;
+
+    let client = SequencerRegistryClient::connect(&[anvil.endpoint().as_str()], mock, signer).unwrap();
+    let err = client.heartbeat().await.unwrap_err();
+    assert!(matches!(err, SequencerRegistryError::Contract(_)), "unexpected error: {err}");
+    assert!(client.ensure_registered(B256::ZERO, String::new()).await.is_err());
+}
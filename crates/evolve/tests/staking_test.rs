@@ -0,0 +1,133 @@
+//! Tests for the staking client against mock AndeNativeStaking contracts on anvil
+//!
+//! These tests require the `anvil` binary in `PATH` and are ignored by default.
+
+use alloy::{
+    network::{EthereumWallet, TransactionBuilder},
+    node_bindings::Anvil,
+    primitives::{hex, Address, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::local::PrivateKeySigner,
+    sol_types::SolValue,
+};
+use ande_consensus_bindings::{ContractAddresses, IAndeNativeStaking};
+use evolve_ev_reth::staking::{AndeStakingClient, Delegation, StakingClientError, UnbondingEntry};
+use std::time::Duration;
+
+/// Init code of a mock reverting every call
+const REVERTING_MOCK: &str = "6003600a5f3960035ff35f5ffd";
+
+/// Init code of a mock returning `output` to every call
+fn returning_mock(output: &[u8]) -> Vec<u8> {
+    let len = u16::try_from(output.len()).unwrap().to_be_bytes();
+    // CODECOPY the output appended to the 13 bytes of code, then RETURN it
+    let mut runtime = vec![0x61, len[0], len[1], 0x61, 0x00, 0x0d, 0x5f, 0x39, 0x61, len[0], len[1], 0x5f, 0xf3];
+    runtime.extend_from_slice(output);
+
+    // Same for the runtime code, appended to the 13 bytes of init code
+    let len = u16::try_from(runtime.len()).unwrap().to_be_bytes();
+    let mut init_code = vec![0x61, len[0], len[1], 0x61, 0x00, 0x0d, 0x5f, 0x39, 0x61, len[0], len[1], 0x5f, 0xf3];
+    init_code.extend_from_slice(&runtime);
+    init_code
+}
+
+/// Deploy `init_code` from the wallet of `provider`
+async fn deploy(provider: &impl Provider, init_code: Vec<u8>) -> Address {
+    let tx = TransactionRequest::default().with_deploy_code(init_code);
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    receipt.contract_address.expect("deployment creates a contract")
+}
+
+/// Spawn anvil, deploy `init_code` and connect a staking client to it
+async fn client_of(init_code: Vec<u8>) -> (alloy::node_bindings::AnvilInstance, AndeStakingClient) {
+    let anvil = Anvil::new().spawn();
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider = ProviderBuilder::new().wallet(EthereumWallet::from(signer)).connect_http(anvil.endpoint_url());
+    let mock = deploy(&provider, init_code).await;
+
+    let addresses = ContractAddresses::new(Address::ZERO, mock, Address::ZERO);
+    let client = AndeStakingClient::connect(&[anvil.endpoint().as_str()], &addresses).unwrap();
+    (anvil, client)
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_stake_amounts() {
+    let (_anvil, client) = client_of(returning_mock(&U256::from(1_000).abi_encode())).await;
+
+    assert_eq!(client.voting_power(Address::repeat_byte(0x0a)).await.unwrap(), U256::from(1_000));
+    assert_eq!(client.total_staked().await.unwrap(), U256::from(1_000));
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_delegations() {
+    let delegations = vec![
+        IAndeNativeStaking::Delegation {
+            validator: Address::repeat_byte(0x0a),
+            amount: U256::from(700),
+            since: U256::from(1_700_000_000u64),
+        },
+        IAndeNativeStaking::Delegation {
+            validator: Address::repeat_byte(0x0b),
+            amount: U256::from(300),
+            since: U256::from(1_700_000_100u64),
+        },
+    ];
+    let (_anvil, client) = client_of(returning_mock(&delegations.abi_encode())).await;
+
+    assert_eq!(
+        client.delegations_of(Address::repeat_byte(0x01)).await.unwrap(),
+        vec![
+            Delegation { validator: Address::repeat_byte(0x0a), amount: U256::from(700), since: 1_700_000_000 },
+            Delegation { validator: Address::repeat_byte(0x0b), amount: U256::from(300), since: 1_700_000_100 },
+        ]
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_unbonding_entries() {
+    let entries = vec![IAndeNativeStaking::UnbondingEntry {
+        amount: U256::from(250),
+        completionTime: U256::from(1_700_604_800u64),
+    }];
+    let (_anvil, client) = client_of(returning_mock(&entries.abi_encode())).await;
+
+    assert_eq!(
+        client.unbonding_entries(Address::repeat_byte(0x01)).await.unwrap(),
+        vec![UnbondingEntry { amount: U256::from(250), completion_time: 1_700_604_800 }]
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_staking_snapshot_is_cached() {
+    let (_anvil, client) = client_of(returning_mock(&U256::from(1_000).abi_encode())).await;
+    let validators = vec![Address::repeat_byte(0x0a), Address::repeat_byte(0x0b)];
+    let client = client.with_tracked_accounts(validators.clone()).with_refresh_interval(Duration::from_secs(3_600));
+
+    let snapshot = client.staking_snapshot().await.unwrap();
+    assert_eq!(snapshot.total_staked, U256::from(1_000));
+    assert_eq!(snapshot.voting_power.keys().copied().collect::<Vec<_>>(), validators);
+    assert_eq!(snapshot.voting_power_of(&Address::repeat_byte(0x0c)), U256::ZERO);
+
+    // Served from the cache until the interval elapses
+    let cached = client.staking_snapshot().await.unwrap();
+    assert!(std::sync::Arc::ptr_eq(&snapshot, &cached));
+
+    let client = client.with_refresh_interval(Duration::ZERO);
+    let refreshed = client.staking_snapshot().await.unwrap();
+    assert!(!std::sync::Arc::ptr_eq(&snapshot, &refreshed));
+    assert_eq!(refreshed.voting_power, snapshot.voting_power);
+}
+
+#[tokio::test]
+#[ignore] // Requires anvil
+async fn test_reverted_query_is_an_error() {
+    let (_anvil, client) = client_of(hex::decode(REVERTING_MOCK).unwrap()).await;
+
+    assert!(matches!(client.total_staked().await, Err(StakingClientError::Contract(_))));
+    assert!(client.staking_snapshot().await.is_err());
+}
@@ -1,19 +1,382 @@
-use alloy_consensus::transaction::Transaction;
-use evolve_ev_reth::EvolvePayloadAttributes;
+use alloy_consensus::transaction::{SignerRecoverable, Transaction};
+use alloy_primitives::{logs_bloom, Address, Bloom, TxHash, B256, U256, U64};
+use async_trait::async_trait;
+use evolve_ev_reth::{EvolvePayloadAttributes, FinalizationPolicy, PayloadAttributesError};
 use reth_errors::RethError;
 use reth_evm::{
     execute::{BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, NextBlockEnvAttributes,
 };
-use evolve_ev_reth::evm_config::AndeEvmConfig;
-use evolve_ev_reth::parallel::{ParallelExecutor, ParallelConfig as EvolveParallelConfig};
+use evolve_ev_reth::evm_config::{
+    AndeBlockExecutorFactory, AndeEvmConfig, AndeEvmConfigBuilder, AndeEvmConfigError,
+};
+use evolve_ev_reth::metrics::PayloadBuilderMetrics;
+use evolve_ev_reth::mev::{
+    BundleSimulation, BundleSubmission, InMemoryMevStore, MevAuctionClient,
+    MevDetector, MevOpportunityStore, MevOrderingPolicy, MevPipeline, NoReorder,
+};
+use evolve_ev_reth::rpc::{AttestationStatus, ExecutionMode, PayloadStats, PayloadStatsBuffer};
+use evolve_ev_reth::parallel::{
+    largest_dependent_group_fraction, AccountStateChange, FailurePolicy, ParallelConfig as EvolveParallelConfig,
+    ParallelExecutionMetrics, ParallelExecutor, ParallelPayloadError, WorkerPool,
+};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_primitives::{TransactionSigned, Header, SealedBlock, SealedHeader, transaction::SignedTransaction};
-use reth_provider::{HeaderProvider, StateProviderFactory};
+use reth_ethereum_primitives::{EthPrimitives, Receipt};
+use reth_provider::{
+    BlockHashReader, HashedPostStateProvider, HeaderProvider, StateProviderBox, StateProviderFactory,
+};
 use reth_revm::{database::StateProviderDatabase, State};
-use std::sync::Arc;
+use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use revm::database::states::bundle_state::{BundleRetention, BundleState};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::Instant;
 use tracing::{debug, info, warn};
-use crate::config::EvolvePayloadBuilderConfig;
+use crate::{config::EvolvePayloadBuilderConfig, reload::SharedPayloadBuilderConfig};
+
+/// Block built by [`EvolvePayloadBuilder`], with the artifacts of its execution
+/// and metadata about how it was packed
+///
+/// Carries everything needed to insert the block without executing it again.
+#[derive(Debug, Clone)]
+pub struct EvolveBuiltPayload {
+    /// The sealed block
+    pub block: SealedBlock,
+    /// Receipts of the block transactions, in block order
+    pub receipts: Vec<Receipt>,
+    /// Priority fees paid to the beneficiary, in wei
+    pub fees: U256,
+    /// State changes of the block, with reverts
+    pub bundle_state: BundleState,
+    /// Hashed post-state of the block
+    pub hashed_state: HashedPostState,
+    /// Trie updates computed along with the state root
+    pub trie_updates: TrieUpdates,
+    /// How the block was executed
+    pub execution: PayloadExecutionStats,
+    /// Transactions left out to stay within the block gas limit, in block order
+    pub excluded_transactions: Vec<TxHash>,
+    /// Transactions dropped from the tail of the payload to stay within the
+    /// txpool caps, in payload order
+    pub truncated_transactions: Vec<TxHash>,
+    /// Position in the executed transaction list from which no transaction was
+    /// admitted, because the build deadline was reached
+    pub cut_off: Option<usize>,
+    /// Supplied transactions left out of the block and why, in payload order
+    pub rejected_transactions: Vec<RejectedTx>,
+}
+
+impl EvolveBuiltPayload {
+    /// Payload of a block finished by the block builder
+    fn from_outcome(
+        outcome: BlockBuilderOutcome<EthPrimitives>,
+        bundle_state: BundleState,
+        excluded_transactions: Vec<TxHash>,
+        cut_off: Option<usize>,
+        rejected_transactions: Vec<RejectedTx>,
+    ) -> Self {
+        let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } = outcome;
+        let block = block.sealed_block().clone();
+        let receipts = execution_result.receipts;
+        Self {
+            fees: total_fees(&block, &receipts),
+            block,
+            receipts,
+            bundle_state,
+            hashed_state,
+            trie_updates,
+            execution: PayloadExecutionStats::default(),
+            excluded_transactions,
+            truncated_transactions: Vec::new(),
+            cut_off,
+            rejected_transactions,
+        }
+    }
+}
+
+/// How an [`EvolveBuiltPayload`] was executed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PayloadExecutionStats {
+    /// Whether the transactions were executed in parallel before the block
+    /// was assembled, false after a fallback to sequential execution
+    pub parallel: bool,
+    /// Estimated fraction of the transactions in their largest dependent
+    /// group, when computed to pick the execution mode
+    pub dependent_fraction: Option<f64>,
+    /// Statistics of the parallel execution, when monitoring is enabled
+    pub parallel_metrics: Option<ParallelExecutionMetrics>,
+    /// Time spent executing the transactions and assembling the block
+    pub duration: Duration,
+}
+
+/// Why a supplied transaction was left out of the built block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RejectionReason {
+    /// The sender can't be recovered from the signature
+    RecoveryFailed,
+    /// The transaction failed validation or execution, e.g. with a wrong nonce
+    Reverted,
+    /// The transaction doesn't fit in the remaining block gas
+    ExceedsGasLimit,
+    /// The payload attributes list the transaction as excluded
+    Excluded,
+    /// The build deadline was reached before the transaction was admitted
+    DeadlineCut,
+}
+
+impl RejectionReason {
+    /// Name used in logs
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::RecoveryFailed => "recovery_failed",
+            Self::Reverted => "reverted",
+            Self::ExceedsGasLimit => "exceeds_gas_limit",
+            Self::Excluded => "excluded",
+            Self::DeadlineCut => "deadline_cut",
+        }
+    }
+}
+
+/// Supplied transaction left out of an [`EvolveBuiltPayload`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedTx {
+    /// Hash of the transaction
+    pub hash: TxHash,
+    /// Position of the transaction in the payload attributes, priority
+    /// transactions counted after the others
+    pub index: usize,
+    /// Why the transaction was left out
+    pub reason: RejectionReason,
+}
+
+/// Outcome of building a payload on a node that may not be the designated producer
+#[derive(Debug, Clone)]
+pub enum EvolveBuildOutcome {
+    /// The block was built
+    Built(EvolveBuiltPayload),
+    /// Another sequencer is the designated producer of the block, nothing was built
+    Skipped {
+        /// Block that was skipped
+        block_number: u64,
+        /// Producer designated for the block
+        designated_producer: Address,
+    },
+}
+
+/// Error returned when a block is requested from a node that isn't its designated producer
+#[derive(Debug, thiserror::Error)]
+#[error("Not the designated producer of block {block_number}, {designated_producer} is")]
+pub struct NotDesignatedProducer {
+    /// Block that was requested
+    pub block_number: u64,
+    /// Producer designated for the block
+    pub designated_producer: Address,
+}
+
+/// Error returned when a payload can't be built on the parent it names
+#[derive(Debug, thiserror::Error)]
+pub enum ParentMismatch {
+    /// The parent block isn't known to the node
+    #[error("Parent {parent_hash} is unknown")]
+    Unknown {
+        /// Parent named by the payload
+        parent_hash: B256,
+    },
+    /// Another block is canonical at the height of the parent, e.g. after a reorg
+    #[error("Parent {parent_hash} isn't canonical, {canonical} is at height {number}")]
+    NotCanonical {
+        /// Parent named by the payload
+        parent_hash: B256,
+        /// Height of the parent
+        number: u64,
+        /// Canonical block at that height
+        canonical: B256,
+    },
+    /// The payload attributes don't hold on the new parent
+    #[error("Payload attributes don't hold on parent {parent_hash}: {source}")]
+    InvalidAttributes {
+        /// New parent of the payload
+        parent_hash: B256,
+        /// Why the attributes don't hold
+        #[source]
+        source: PayloadAttributesError,
+    },
+    /// A priority transaction doesn't pay the base fee of the block on the new parent
+    #[error("Priority transaction {hash} doesn't pay the base fee of {base_fee} on parent {parent_hash}")]
+    BaseFeeTooHigh {
+        /// New parent of the payload
+        parent_hash: B256,
+        /// Priority transaction
+        hash: TxHash,
+        /// Base fee of the block on the new parent
+        base_fee: u64,
+    },
+}
+
+/// Error returned when the built block doesn't start with every priority
+/// transaction of the payload attributes, in order
+#[derive(Debug, thiserror::Error)]
+#[error("Block {block_number} left out priority transactions {missing:?}")]
+pub struct PriorityTransactionsExcluded {
+    /// Block that was built
+    pub block_number: u64,
+    /// Priority transactions not included at their position
+    pub missing: Vec<TxHash>,
+}
+
+/// Error returned when the state of the transactions executed in parallel
+/// differs from the post-state of the block assembled from them
+#[derive(Debug, thiserror::Error)]
+#[error("Block {block_number} diverged from its parallel execution on {accounts} accounts and {slots} storage slots")]
+pub struct ParallelStateDivergence {
+    /// Block that was built
+    pub block_number: u64,
+    /// Accounts whose values differ, or that only one side wrote
+    pub accounts: usize,
+    /// Storage slots whose values differ, or that only one side wrote
+    pub slots: usize,
+}
+
+/// Outcome of [`EvolvePayloadBuilder::validate_payload`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Block that was validated
+    pub block_number: u64,
+    /// Hash of the block that was validated
+    pub block_hash: B256,
+    /// First difference between the block and its re-execution, `None` if
+    /// the block is valid
+    pub mismatch: Option<ValidationMismatch>,
+}
+
+impl ValidationReport {
+    /// Whether re-executing the block reproduced its header
+    pub const fn is_valid(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+/// Difference between a block and its re-execution, where `expected` is the
+/// value in the block header and `actual` the re-executed one
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationMismatch {
+    /// A transaction of the block can't be executed on the parent state
+    #[error("Transaction {index} ({hash}) failed: {reason}")]
+    TransactionFailed {
+        /// Position of the transaction in the block
+        index: usize,
+        /// Hash of the transaction
+        hash: TxHash,
+        /// Why it failed
+        reason: String,
+    },
+    /// Gas used by the transactions
+    #[error("Gas used mismatch: expected {expected}, got {actual}")]
+    GasUsed {
+        /// Gas used in the block header
+        expected: u64,
+        /// Gas used by the re-execution
+        actual: u64,
+    },
+    /// Root of the transaction receipts
+    #[error("Receipts root mismatch: expected {expected}, got {actual}")]
+    ReceiptsRoot {
+        /// Receipts root in the block header
+        expected: B256,
+        /// Receipts root of the re-execution
+        actual: B256,
+    },
+    /// Bloom of the logs of the transactions, precompile logs included
+    #[error("Logs bloom mismatch: expected {expected}, got {actual}")]
+    LogsBloom {
+        /// Logs bloom in the block header
+        expected: Bloom,
+        /// Logs bloom of the re-executed receipts
+        actual: Bloom,
+    },
+    /// Root of the post-execution state
+    #[error("State root mismatch: expected {expected}, got {actual}")]
+    StateRoot {
+        /// State root in the block header
+        expected: B256,
+        /// State root of the re-execution
+        actual: B256,
+    },
+}
+
+/// Outcome of [`EvolvePayloadBuilder::execute_block`]
+enum ExecutedBlock {
+    /// The block was finished, without the excluded transactions
+    Finished {
+        /// Finished block, with its post-state
+        outcome: BlockBuilderOutcome<EthPrimitives>,
+        /// State changes of the block, with reverts
+        bundle_state: BundleState,
+        /// Transactions left out to stay within the block gas limit
+        excluded_transactions: Vec<TxHash>,
+        /// First transaction not admitted because the deadline was reached
+        cut_off: Option<usize>,
+        /// Transactions left out, positioned in the executed list
+        rejected_transactions: Vec<RejectedTx>,
+    },
+    /// A transaction failed in strict execution
+    Rejected(ValidationMismatch),
+}
+
+/// Schedule of the producers designated for each block, e.g. read from the
+/// consensus contract
+#[async_trait]
+pub trait ProducerSchedule: Send + Sync + std::fmt::Debug {
+    /// Producer designated for block `block_number`
+    async fn designated_producer(&self, block_number: u64) -> eyre::Result<Address>;
+}
+
+/// Interval at which the consensus is polled while waiting on a built block
+pub const DEFAULT_FINALITY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Error returned when a built block doesn't reach the consensus stage required
+/// by the [`FinalizationPolicy`] in time
+///
+/// Carries the block, so the caller can still decide to publish it.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Block {} not {} within {timeout:?}, attestation power {attestation_power}",
+    .built.block.number,
+    awaited_stage(.finalization)
+)]
+pub struct FinalizationTimeout {
+    /// The block that was built
+    pub built: Box<EvolveBuiltPayload>,
+    /// Whether finalization, rather than a first attestation, was waited for
+    pub finalization: bool,
+    /// How long the builder waited
+    pub timeout: Duration,
+    /// Attestation power of the block at the last poll
+    pub attestation_power: U256,
+}
+
+/// Consensus stage waited for, for [`FinalizationTimeout`] messages
+const fn awaited_stage(finalization: &bool) -> &'static str {
+    if *finalization {
+        "finalized"
+    } else {
+        "attested"
+    }
+}
+
+/// Consensus-side progress of built blocks, e.g. read from the consensus contract
+#[async_trait]
+pub trait BlockFinality: Send + Sync + std::fmt::Debug {
+    /// Whether the block `block_hash` reached the finalization threshold
+    async fn is_block_finalized(&self, block_hash: B256) -> eyre::Result<bool>;
+
+    /// Voting power of the validators that attested the block `block_hash`
+    async fn attestation_power(&self, block_hash: B256) -> eyre::Result<U256>;
+}
 
 /// Payload builder for Evolve Reth node
 #[derive(Debug)]
@@ -24,8 +387,31 @@ pub struct EvolvePayloadBuilder<Client> {
     pub evm_config: AndeEvmConfig,
     /// Parallel execution configuration
     pub parallel_config: Option<EvolveParallelConfig>,
+    /// Parallel executor, kept across payloads so its worker threads are reused
+    parallel_executor: Option<ParallelExecutor>,
     /// AndeChain genesis configuration
     pub config: EvolvePayloadBuilderConfig,
+    /// Configuration kept up to date by a config watcher, read instead of
+    /// `config` at the start of each block when set
+    live_config: Option<SharedPayloadBuilderConfig>,
+    /// Prometheus metrics
+    pub metrics: PayloadBuilderMetrics,
+    /// Ordering applied to detected MEV when the config enables MEV integration
+    mev_ordering: Arc<dyn MevOrderingPolicy>,
+    /// Opportunities detected in built blocks
+    mev_store: Arc<dyn MevOpportunityStore>,
+    /// MEV auction whose winning bundle is placed at the top of each block; `None` disables it
+    mev_auction: Option<Arc<MevAuctionClient>>,
+    /// Pipeline crediting the MEV realized in built blocks to the distributor; `None` disables it
+    mev_pipeline: Option<Arc<MevPipeline>>,
+    /// Address of this node and the schedule it builds blocks on; `None` builds every block
+    producer_schedule: Option<(Address, Arc<dyn ProducerSchedule>)>,
+    /// Wait on the consensus after each built block, and where its progress is read
+    finalization: Option<(FinalizationPolicy, Arc<dyn BlockFinality>)>,
+    /// Interval at which the consensus is polled while waiting
+    finality_poll_interval: Duration,
+    /// Stats of the blocks built recently, served by `ande_getPayloadStats`; `None` disables them
+    payload_stats: Option<Arc<PayloadStatsBuffer>>,
 }
 
 impl<Client> EvolvePayloadBuilder<Client>
@@ -69,7 +455,18 @@ where
             client,
             evm_config,
             parallel_config: None,
+            parallel_executor: None,
             config,
+            live_config: None,
+            metrics: PayloadBuilderMetrics::default(),
+            mev_ordering: Arc::new(NoReorder),
+            mev_store: Arc::new(InMemoryMevStore::default()),
+            mev_auction: None,
+            mev_pipeline: None,
+            producer_schedule: None,
+            finalization: None,
+            finality_poll_interval: DEFAULT_FINALITY_POLL_INTERVAL,
+            payload_stats: None,
         }
     }
 
@@ -92,46 +489,207 @@ where
             }
         }
 
+        // A failed transaction never aborts payload building
+        let parallel_executor = parallel_config.as_ref().map(|parallel_config| {
+            ParallelExecutor::new(EvolveParallelConfig {
+                on_failure: FailurePolicy::Skip,
+                ..parallel_config.clone()
+            })
+        });
+
         Self {
             client,
             evm_config,
             parallel_config,
+            parallel_executor,
             config,
+            live_config: None,
+            metrics: PayloadBuilderMetrics::default(),
+            mev_ordering: Arc::new(NoReorder),
+            mev_store: Arc::new(InMemoryMevStore::default()),
+            mev_auction: None,
+            mev_pipeline: None,
+            producer_schedule: None,
+            finalization: None,
+            finality_poll_interval: DEFAULT_FINALITY_POLL_INTERVAL,
+            payload_stats: None,
         }
     }
 
-    /// Builds a payload using the provided attributes
+    /// Only build the blocks `schedule` designates `producer` for, skipping the others
+    ///
+    /// Runs the builder in consensus mode, where payloads must name a fee recipient.
+    pub fn with_producer_schedule(mut self, producer: Address, schedule: Arc<dyn ProducerSchedule>) -> Self {
+        self.producer_schedule = Some((producer, schedule));
+        self
+    }
+
+    /// Read the configuration from `config` at the start of each block, so
+    /// reloads apply without a restart
+    pub fn with_live_config(mut self, config: SharedPayloadBuilderConfig) -> Self {
+        self.live_config = Some(config);
+        self
+    }
+
+    /// Configuration the next block is built with
+    fn current_config(&self) -> EvolvePayloadBuilderConfig {
+        let mut config = match &self.live_config {
+            Some(shared) => shared.read().unwrap_or_else(PoisonError::into_inner).clone(),
+            None => self.config.clone(),
+        };
+        // Payloads must name a fee recipient in consensus mode
+        config.payload_validation.require_fee_recipient |= self.producer_schedule.is_some();
+        config
+    }
+
+    /// Wait on the consensus after building each block, as `policy` requires,
+    /// reading the progress of the block from `finality`
+    pub fn with_finalization_policy(mut self, policy: FinalizationPolicy, finality: Arc<dyn BlockFinality>) -> Self {
+        self.finalization = Some((policy, finality));
+        self
+    }
+
+    /// Sets the interval at which the consensus is polled while waiting on a block
+    pub fn with_finality_poll_interval(mut self, interval: Duration) -> Self {
+        self.finality_poll_interval = interval;
+        self
+    }
+
+    /// Sets the policy ordering transactions around detected MEV
+    pub fn with_mev_ordering(mut self, policy: impl MevOrderingPolicy + 'static) -> Self {
+        self.mev_ordering = Arc::new(policy);
+        self
+    }
+
+    /// Sets the store detected MEV opportunities are recorded in
+    pub fn with_mev_store(mut self, store: Arc<dyn MevOpportunityStore>) -> Self {
+        self.mev_store = store;
+        self
+    }
+
+    /// Sets the MEV auction whose winning bundle is placed at the top of each block
+    pub fn with_mev_auction(mut self, auction: Arc<MevAuctionClient>) -> Self {
+        self.mev_auction = Some(auction);
+        self
+    }
+
+    /// Sets the pipeline crediting the MEV realized in built blocks to the distributor
+    pub fn with_mev_pipeline(mut self, pipeline: Arc<MevPipeline>) -> Self {
+        self.mev_pipeline = Some(pipeline);
+        self
+    }
+
+    /// Record the stats of each built block in `stats`
+    pub fn with_payload_stats(mut self, stats: Arc<PayloadStatsBuffer>) -> Self {
+        self.payload_stats = Some(stats);
+        self
+    }
+
+    /// MEV auction bundles are selected from, if any
+    pub fn mev_auction(&self) -> Option<&Arc<MevAuctionClient>> {
+        self.mev_auction.as_ref()
+    }
+
+    /// Store of the MEV opportunities detected in built blocks
+    pub fn mev_store(&self) -> &Arc<dyn MevOpportunityStore> {
+        &self.mev_store
+    }
+
+    /// Worker threads of the parallel executor, if parallel execution is enabled
+    pub fn parallel_worker_pool(&self) -> Option<&Arc<WorkerPool>> {
+        self.parallel_executor.as_ref().map(ParallelExecutor::pool)
+    }
+
+    /// Builds a payload using the provided attributes, with its receipts, fees
+    /// and post-state, and which transactions were left out of the block
+    ///
+    /// The attributes may be given as read from a sidecar, as
+    /// [`VersionedPayloadAttributes`](evolve_ev_reth::VersionedPayloadAttributes).
+    ///
+    /// Fails with [`NotDesignatedProducer`] if another sequencer is to produce
+    /// the block; see [`Self::try_build_payload`] to skip it instead.
     pub async fn build_payload(
         &self,
-        attributes: EvolvePayloadAttributes,
+        attributes: impl Into<EvolvePayloadAttributes>,
+    ) -> Result<EvolveBuiltPayload, PayloadBuilderError> {
+        match self.try_build_payload(attributes).await? {
+            EvolveBuildOutcome::Built(built) => Ok(built),
+            EvolveBuildOutcome::Skipped { block_number, designated_producer } => {
+                Err(PayloadBuilderError::other(NotDesignatedProducer { block_number, designated_producer }))
+            }
+        }
+    }
+
+    /// Builds a payload using the provided attributes, returning just the block
+    pub async fn build_payload_block(
+        &self,
+        attributes: impl Into<EvolvePayloadAttributes>,
     ) -> Result<SealedBlock, PayloadBuilderError> {
-        // Create a mutable clone of the EVM config to inject the precompile
-        let evm_config = self.evm_config.clone();
+        self.build_payload(attributes).await.map(|built| built.block)
+    }
 
-        // Validate attributes
+    /// Builds a payload using the provided attributes, unless another
+    /// sequencer is the designated producer of the block
+    pub async fn try_build_payload(
+        &self,
+        attributes: impl Into<EvolvePayloadAttributes>,
+    ) -> Result<EvolveBuildOutcome, PayloadBuilderError> {
+        let build_started = Instant::now();
+        let mut attributes = attributes.into();
+
+        // The parent may have been reorged out since the attributes were delivered
+        let sealed_parent = self.canonical_parent(attributes.parent_hash)?;
+        let config = self.current_config();
+        let build_deadline = config.max_build_duration.map(|budget| build_started + budget);
+
+        // Validate attributes before any execution, so bad ones fail with a precise error
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
         attributes
-            .validate()
+            .validate_against(&sealed_parent, now, &config.payload_validation)
             .map_err(|e| PayloadBuilderError::Internal(RethError::Other(Box::new(e))))?;
 
-        // Get the latest state provider
-        let state_provider = self.client.latest().map_err(PayloadBuilderError::other)?;
+        // Height of the block being built, taken from the parent header
+        let block_number = sealed_parent.number + 1;
 
-        // Create a database from the state provider
-        let db = StateProviderDatabase::new(&state_provider);
-        let mut state_db = State::builder()
-            .with_database(db)
-            .with_bundle_update()
-            .build();
+        if let Some((producer, schedule)) = &self.producer_schedule {
+            let designated_producer = schedule
+                .designated_producer(block_number)
+                .await
+                .map_err(|e| PayloadBuilderError::Internal(RethError::Other(e.into())))?;
+            if designated_producer != *producer {
+                // Expected every slot on followers, not a failure
+                info!(block_number, ?designated_producer, "Not the designated producer, skipping block");
+                return Ok(EvolveBuildOutcome::Skipped { block_number, designated_producer });
+            }
+        }
 
-        // Get parent header using the client's HeaderProvider trait
-        let parent_header = self
-            .client
-            .header(&attributes.parent_hash)
-            .map_err(PayloadBuilderError::other)?
-            .ok_or_else(|| {
-                PayloadBuilderError::Internal(RethError::Other("Parent header not found".into()))
-            })?;
-        let sealed_parent = SealedHeader::new(parent_header, attributes.parent_hash);
+        // Positions of the supplied transactions, priority ones after the others
+        let supplied: HashMap<TxHash, usize> = attributes
+            .transactions
+            .iter()
+            .chain(&attributes.priority_transactions)
+            .enumerate()
+            .rev()
+            .map(|(i, tx)| (*tx.hash(), i))
+            .collect();
+
+        // Excluded transactions don't take part in MEV ordering either
+        let mut dropped = drop_excluded_transactions(&mut attributes);
+
+        let truncated_transactions = attributes
+            .enforce_transaction_limits(&config.txpool)
+            .map_err(|e| PayloadBuilderError::Internal(RethError::Other(Box::new(e))))?;
+        if !truncated_transactions.is_empty() {
+            warn!(
+                block_number,
+                dropped = ?truncated_transactions,
+                "Payload transactions exceed the txpool caps, dropping the tail"
+            );
+            self.metrics.truncated_transactions.increment(truncated_transactions.len() as u64);
+        }
+
+        // Reorder around detected MEV before either execution mode sees the transactions
+        self.apply_mev_ordering(&config, &mut attributes, block_number);
 
         // Create next block environment attributes
         let gas_limit = attributes.gas_limit.ok_or_else(|| {
@@ -151,41 +709,410 @@ where
             withdrawals: Some(Default::default()),
         };
 
+        // A single snapshot of the parent state serves the whole build, so the
+        // state root is computed on the state the transactions executed on even
+        // if the chain advances meanwhile
+        let state_provider =
+            self.client.state_by_block_hash(attributes.parent_hash).map_err(PayloadBuilderError::other)?;
+
+        // The winning auction bundle goes first, after MEV ordering so it isn't moved
+        let auction_bundle = if config.switches.mev_auction {
+            self.apply_auction_bundle(&mut attributes, &sealed_parent, &next_block_attrs, &state_provider).await
+        } else {
+            None
+        };
+
+        // Priority transactions go above the auction bundle, so the block must start with them
+        dropped.extend(drop_excluded_transactions(&mut attributes));
+        let priority: Vec<TxHash> = attributes.priority_transactions.iter().map(|tx| *tx.hash()).collect();
+        if !priority.is_empty() {
+            debug!(block_number, transactions = priority.len(), "Placing priority transactions at the top of the block");
+            attributes.transactions.retain(|tx| !priority.contains(tx.hash()));
+            let transactions = std::mem::take(&mut attributes.priority_transactions);
+            attributes.transactions.splice(0..0, transactions);
+        }
+
         // Decide execution mode: parallel vs sequential BEFORE creating builder
-        let should_use_parallel = self.should_use_parallel_execution(&attributes.transactions);
+        let (should_use_parallel, dependent_fraction) =
+            self.should_use_parallel_execution(&config, &attributes.transactions);
+        self.metrics.record_mode(should_use_parallel);
+        if let Some(fraction) = dependent_fraction {
+            self.metrics.dependent_fraction.record(fraction);
+        }
 
-        if should_use_parallel {
+        let started = Instant::now();
+        let mut built = if should_use_parallel {
             info!(
                 transaction_count = attributes.transactions.len(),
                 "🚀 AndeChain: Using PARALLEL execution mode"
             );
-            return self.build_payload_parallel(
-                attributes,
-                sealed_parent,
-                next_block_attrs,
-            ).await;
+            self.build_payload_parallel(attributes, sealed_parent, next_block_attrs, &state_provider, build_deadline)
+                .await?
         } else {
             info!(
                 transaction_count = attributes.transactions.len(),
                 "📋 AndeChain: Using SEQUENTIAL execution mode"
             );
+            self.build_payload_sequential(&attributes, &sealed_parent, next_block_attrs, &state_provider, build_deadline)?
+        };
+        built.execution.duration = started.elapsed();
+        built.execution.dependent_fraction = dependent_fraction;
+        if built.cut_off.is_some() {
+            self.metrics.deadline_cut_offs.increment(1);
         }
 
-        // Create block builder using the EVM config (for sequential execution)
-        let mut builder = evm_config
-            .builder_for_next_block(&mut state_db, &sealed_parent, next_block_attrs)
-            .map_err(PayloadBuilderError::other)?;
+        // Logs emitted by the ANDE precompile rather than by bytecode must be
+        // committed to like any other, or followers reject the block
+        if let Some(mismatch) = receipts_mismatch(&built.block, &built.receipts) {
+            warn!(block_number, %mismatch, "Built block doesn't commit to its receipts");
+            return Err(PayloadBuilderError::other(mismatch));
+        }
 
-        // Apply pre-execution changes
-        builder
-            .apply_pre_execution_changes()
-            .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+        // Rejections are positioned in the payload as supplied; transactions the
+        // builder added itself, from the auction bundle, aren't reported
+        let rejected = dropped
+            .into_iter()
+            .map(|hash| (hash, RejectionReason::Excluded))
+            .chain(built.rejected_transactions.drain(..).map(|rejected| (rejected.hash, rejected.reason)));
+        let mut rejected_transactions: Vec<RejectedTx> = rejected
+            .filter_map(|(hash, reason)| Some(RejectedTx { hash, index: *supplied.get(&hash)?, reason }))
+            .collect();
+        rejected_transactions.sort_by_key(|rejected| rejected.index);
+        let count = |reason| rejected_transactions.iter().filter(|rejected| rejected.reason == reason).count();
+        info!(
+            block_number,
+            rejected = rejected_transactions.len(),
+            recovery_failed = count(RejectionReason::RecoveryFailed),
+            reverted = count(RejectionReason::Reverted),
+            exceeds_gas_limit = count(RejectionReason::ExceedsGasLimit),
+            excluded = count(RejectionReason::Excluded),
+            deadline_cut = count(RejectionReason::DeadlineCut),
+            "Payload transactions left out of the block"
+        );
+        built.rejected_transactions = rejected_transactions;
+
+        let included = &built.block.body().transactions;
+        let missing: Vec<TxHash> = priority
+            .iter()
+            .enumerate()
+            .filter(|(i, hash)| included.get(*i).map(|tx| tx.hash()) != Some(*hash))
+            .map(|(_, hash)| *hash)
+            .collect();
+        if !missing.is_empty() {
+            warn!(block_number, ?missing, "Priority transactions could not be included, failing the build");
+            if let (Some((bundle, _)), Some(auction)) = (&auction_bundle, &self.mev_auction) {
+                let reason = "Block failed on priority transactions".to_string();
+                self.reject_auction_bundle(auction, bundle, reason).await;
+            }
+            return Err(PayloadBuilderError::other(PriorityTransactionsExcluded { block_number, missing }));
+        }
+
+        if let Some((bundle, simulation)) = auction_bundle {
+            self.settle_auction_bundle(bundle, simulation, &built).await;
+        }
+
+        // Credit realized MEV once the auction bundle, if any, is settled
+        let mev_captured = match self.mev_pipeline.as_ref().filter(|_| config.switches.mev_distribution) {
+            Some(pipeline) => pipeline.on_block_built(&built.block, &built.block.body().transactions).await,
+            None => U256::ZERO,
+        };
+
+        self.metrics.blocks_built.increment(1);
+        let built = EvolveBuiltPayload { truncated_transactions, ..built };
+        let stats = self.payload_stats.as_ref().map(|_| payload_stats(&built, build_started.elapsed(), mev_captured));
+        let result = self.wait_for_finality(built).await;
+        if let (Some(buffer), Some(mut stats)) = (&self.payload_stats, stats) {
+            stats.attestation = match (&result, self.finalization.as_ref().map(|(policy, _)| policy)) {
+                (Err(_), _) => AttestationStatus::TimedOut,
+                (Ok(_), Some(FinalizationPolicy::WaitForInclusion)) => AttestationStatus::Attested,
+                (Ok(_), Some(FinalizationPolicy::WaitForFinalization { .. })) => AttestationStatus::Finalized,
+                (Ok(_), Some(FinalizationPolicy::FireAndForget) | None) => AttestationStatus::NotAwaited,
+            };
+            buffer.record(stats);
+        }
+        Ok(EvolveBuildOutcome::Built(result?))
+    }
+
+    /// Rebuild a payload on `new_parent_hash`, e.g. after the parent the
+    /// attributes were delivered for was reorged out
+    ///
+    /// The attributes are moved to the block following the new parent and
+    /// revalidated against it, and priority transactions must still pay the
+    /// base fee there. When they don't hold, the build fails with a
+    /// [`ParentMismatch`] instead.
+    pub async fn rebuild_on_new_parent(
+        &self,
+        mut attributes: EvolvePayloadAttributes,
+        new_parent_hash: B256,
+    ) -> Result<EvolveBuildOutcome, PayloadBuilderError> {
+        let new_parent = self.canonical_parent(new_parent_hash)?;
+        info!(
+            old_parent = %attributes.parent_hash,
+            new_parent = %new_parent_hash,
+            block_number = new_parent.number + 1,
+            "Rebuilding payload on a new parent"
+        );
+        attributes.parent_hash = new_parent_hash;
+        attributes.block_number = new_parent.number + 1;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        attributes.validate_against(&new_parent, now, &self.current_config().payload_validation).map_err(|source| {
+            PayloadBuilderError::other(ParentMismatch::InvalidAttributes { parent_hash: new_parent_hash, source })
+        })?;
+
+        let base_fee_params = self.evm_config.chain_spec().base_fee_params_at_timestamp(attributes.timestamp);
+        let base_fee = new_parent.next_block_base_fee(base_fee_params).unwrap_or_default();
+        if let Some(tx) =
+            attributes.priority_transactions.iter().find(|tx| tx.max_fee_per_gas() < u128::from(base_fee))
+        {
+            return Err(PayloadBuilderError::other(ParentMismatch::BaseFeeTooHigh {
+                parent_hash: new_parent_hash,
+                hash: *tx.hash(),
+                base_fee,
+            }));
+        }
+
+        self.try_build_payload(attributes).await
+    }
+
+    /// Header of `parent_hash`, checked to still be canonical
+    ///
+    /// Heights the provider holds no canonical hash for, e.g. not yet
+    /// persisted, aren't checked.
+    fn canonical_parent(&self, parent_hash: B256) -> Result<SealedHeader, PayloadBuilderError> {
+        let parent_header = self
+            .client
+            .header(&parent_hash)
+            .map_err(PayloadBuilderError::other)?
+            .ok_or_else(|| PayloadBuilderError::other(ParentMismatch::Unknown { parent_hash }))?;
+        let number = parent_header.number;
+        match self.client.block_hash(number).map_err(PayloadBuilderError::other)? {
+            Some(canonical) if canonical != parent_hash => {
+                warn!(%parent_hash, %canonical, number, "Payload parent is no longer canonical");
+                Err(PayloadBuilderError::other(ParentMismatch::NotCanonical { parent_hash, number, canonical }))
+            }
+            _ => Ok(SealedHeader::new(parent_header, parent_hash)),
+        }
+    }
+
+    /// Wait until `built` reaches the consensus stage required by the
+    /// finalization policy
+    ///
+    /// Only polls: the block is attested by the validators, not here. Fails
+    /// with [`FinalizationTimeout`] if the stage isn't reached in time.
+    async fn wait_for_finality(&self, built: EvolveBuiltPayload) -> Result<EvolveBuiltPayload, PayloadBuilderError> {
+        let Some((policy, finality)) = &self.finalization else {
+            return Ok(built);
+        };
+        let Some(timeout) = policy.timeout() else {
+            return Ok(built);
+        };
+        let finalization = matches!(policy, FinalizationPolicy::WaitForFinalization { .. });
+        let block_hash = built.block.hash();
+        let deadline = Instant::now() + timeout;
+
+        let mut attestation_power = U256::ZERO;
+        loop {
+            // A failed poll is retried until the deadline, like an unmet stage
+            let reached = if finalization {
+                finality.is_block_finalized(block_hash).await
+            } else {
+                finality.attestation_power(block_hash).await.map(|power| {
+                    attestation_power = power;
+                    !power.is_zero()
+                })
+            };
+            match reached {
+                Ok(true) => {
+                    debug!(block_number = built.block.number, ?policy, "Block reached the required consensus stage");
+                    return Ok(built);
+                }
+                Ok(false) => {}
+                Err(e) => debug!(block_number = built.block.number, "Failed to poll block finality: {e}"),
+            }
+
+            if Instant::now() + self.finality_poll_interval > deadline {
+                break;
+            }
+            tokio::time::sleep(self.finality_poll_interval).await;
+        }
+
+        if finalization {
+            attestation_power = finality.attestation_power(block_hash).await.unwrap_or(attestation_power);
+        }
+        warn!(
+            block_number = built.block.number,
+            ?policy,
+            %attestation_power,
+            "Block didn't reach the required consensus stage in time"
+        );
+        Err(PayloadBuilderError::other(FinalizationTimeout {
+            built: Box::new(built),
+            finalization,
+            timeout,
+            attestation_power,
+        }))
+    }
+
+    /// Re-execute `block` on the state of its parent, with the ANDE precompiles,
+    /// and check its gas used, receipts root and state root
+    ///
+    /// Uses the same execution core as block building, so a block built by this
+    /// node always validates. Blocks whose parent is unknown are an error rather
+    /// than a mismatch.
+    pub fn validate_payload(&self, block: &SealedBlock) -> Result<ValidationReport, PayloadBuilderError> {
+        let parent_header = self
+            .client
+            .header(&block.parent_hash)
+            .map_err(PayloadBuilderError::other)?
+            .ok_or_else(|| {
+                PayloadBuilderError::Internal(RethError::Other("Parent header not found".into()))
+            })?;
+        let sealed_parent = SealedHeader::new(parent_header, block.parent_hash);
+        let state_provider =
+            self.client.state_by_block_hash(block.parent_hash).map_err(PayloadBuilderError::other)?;
+
+        let mismatch = match self.execute_block(
+            &state_provider,
+            &sealed_parent,
+            block_env_attributes(block),
+            &block.body().transactions,
+            true,
+            None,
+        )? {
+            ExecutedBlock::Rejected(mismatch) => Some(mismatch),
+            ExecutedBlock::Finished { outcome, .. } => {
+                let executed = outcome.block.header();
+                if executed.gas_used != block.gas_used {
+                    Some(ValidationMismatch::GasUsed { expected: block.gas_used, actual: executed.gas_used })
+                } else if executed.receipts_root != block.receipts_root {
+                    Some(ValidationMismatch::ReceiptsRoot {
+                        expected: block.receipts_root,
+                        actual: executed.receipts_root,
+                    })
+                } else if let Some(mismatch) = bloom_mismatch(block, &outcome.execution_result.receipts) {
+                    Some(mismatch)
+                } else if executed.state_root != block.state_root {
+                    Some(ValidationMismatch::StateRoot { expected: block.state_root, actual: executed.state_root })
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(mismatch) = &mismatch {
+            warn!(block_number = block.number, block_hash = ?block.hash(), %mismatch, "Block failed validation");
+        } else {
+            debug!(block_number = block.number, block_hash = ?block.hash(), "Block validated");
+        }
+        Ok(ValidationReport { block_number: block.number, block_hash: block.hash(), mismatch })
+    }
+
+    /// Build payload by executing the transactions sequentially on the parent
+    /// state of `state_provider`, until `deadline` if any
+    fn build_payload_sequential(
+        &self,
+        attributes: &EvolvePayloadAttributes,
+        sealed_parent: &SealedHeader,
+        next_block_attrs: NextBlockEnvAttributes,
+        state_provider: &StateProviderBox,
+        deadline: Option<Instant>,
+    ) -> Result<EvolveBuiltPayload, PayloadBuilderError> {
         // Execute transactions sequentially
         tracing::info!(
             transaction_count = attributes.transactions.len(),
             "Evolve payload builder: executing transactions"
         );
-        for (i, tx) in attributes.transactions.iter().enumerate() {
+        let ExecutedBlock::Finished { outcome, bundle_state, excluded_transactions, cut_off, rejected_transactions } =
+            self.execute_block(
+            state_provider,
+            sealed_parent,
+            next_block_attrs,
+            &attributes.transactions,
+            false,
+            deadline,
+        )?
+        else {
+            unreachable!("only strict execution rejects transactions");
+        };
+
+        let built = EvolveBuiltPayload::from_outcome(
+            outcome,
+            bundle_state,
+            excluded_transactions,
+            cut_off,
+            rejected_transactions,
+        );
+        tracing::info!(
+                    block_number = built.block.number,
+                    block_hash = ?built.block.hash(),
+                    transaction_count = built.block.transaction_count(),
+                    gas_used = built.block.gas_used,
+                    "Evolve payload builder: built block"
+        );
+
+        Ok(built)
+    }
+
+    /// Execute `transactions` in order on the block following `sealed_parent`,
+    /// over `state_provider`, and finish the block
+    ///
+    /// This is the execution core shared by block building and validation, so
+    /// both run the same ANDE EVM. Transactions that don't fit in the block gas
+    /// or fail are excluded, unless `strict`, where the first of them rejects
+    /// the block instead. Once `deadline` is reached no further transaction is
+    /// admitted, and the block is finished with the ones executed.
+    fn execute_block(
+        &self,
+        state_provider: &StateProviderBox,
+        sealed_parent: &SealedHeader,
+        next_block_attrs: NextBlockEnvAttributes,
+        transactions: &[TransactionSigned],
+        strict: bool,
+        deadline: Option<Instant>,
+    ) -> Result<ExecutedBlock, PayloadBuilderError> {
+        let block_gas_limit = next_block_attrs.gas_limit;
+
+        // Create a database from the state provider
+        let db = StateProviderDatabase::new(state_provider);
+        let mut state_db = State::builder()
+            .with_database(db)
+            .with_bundle_update()
+            .build();
+
+        // Create block builder using the EVM config with the ANDE precompiles, whose
+        // caps are enforced by an inspector counting this block's transfers only
+        let precompiles = AndeBlockExecutorFactory::from_evm_config(&self.evm_config);
+        precompiles.on_block_start(sealed_parent.number + 1, std::iter::empty());
+        let evm_env = self
+            .evm_config
+            .next_evm_env(sealed_parent.header(), &next_block_attrs)
+            .map_err(PayloadBuilderError::other)?;
+        let evm = self.evm_config.evm_with_env_and_inspector(&mut state_db, evm_env, precompiles.inspector());
+        let ctx = self.evm_config.context_for_next_block(sealed_parent, next_block_attrs);
+        let mut builder = self.evm_config.create_block_builder(evm, sealed_parent, ctx);
+
+        // Apply pre-execution changes
+        builder
+            .apply_pre_execution_changes()
+            .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
+        let mut cumulative_gas_used = 0u64;
+        let mut excluded_transactions = Vec::new();
+        let mut rejected_transactions = Vec::new();
+        let mut cut_off = None;
+        for (i, tx) in transactions.iter().enumerate() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                warn!(
+                    index = i,
+                    remaining = transactions.len() - i,
+                    "Build deadline reached, sealing the block with the transactions executed"
+                );
+                cut_off = Some(i);
+                rejected_transactions.extend(deadline_cut(transactions, i));
+                break;
+            }
+
             tracing::debug!(
             index = i,
             hash = ?tx.hash(),
@@ -194,178 +1121,565 @@ where
             gas_limit = tx.gas_limit(),
             "Processing transaction"
             );
+            let rejected = |reason: String| {
+                Ok(ExecutedBlock::Rejected(ValidationMismatch::TransactionFailed { index: i, hash: *tx.hash(), reason }))
+            };
+            let left_out = |reason| RejectedTx { hash: *tx.hash(), index: i, reason };
+
+            // Skip transactions that no longer fit in the block
+            if cumulative_gas_used.saturating_add(tx.gas_limit()) > block_gas_limit {
+                if strict {
+                    return rejected("Transaction exceeds remaining block gas".to_string());
+                }
+                tracing::warn!(
+                    index = i,
+                    hash = ?tx.hash(),
+                    gas_limit = tx.gas_limit(),
+                    cumulative_gas_used,
+                    block_gas_limit,
+                    "Transaction exceeds remaining block gas, excluding it"
+                );
+                excluded_transactions.push(*tx.hash());
+                rejected_transactions.push(left_out(RejectionReason::ExceedsGasLimit));
+                continue;
+            }
 
             // Convert to recovered transaction for execution
-            let recovered_tx = tx.try_clone_into_recovered().map_err(|_| {
-                PayloadBuilderError::Internal(RethError::Other(
-                    "Failed to recover transaction".into(),
-                ))
-            })?;
+            let recovered_tx = match tx.try_clone_into_recovered() {
+                Ok(recovered_tx) => recovered_tx,
+                Err(_) if strict => return rejected("Failed to recover transaction".to_string()),
+                Err(_) => {
+                    tracing::warn!(index = i, hash = ?tx.hash(), "Failed to recover transaction signer, excluding it");
+                    rejected_transactions.push(left_out(RejectionReason::RecoveryFailed));
+                    continue;
+                }
+            };
 
             // Execute the transaction
             match builder.execute_transaction(recovered_tx) {
                 Ok(gas_used) => {
+                    cumulative_gas_used += gas_used;
                     tracing::debug!(index = i, gas_used, "Transaction executed successfully");
-                    debug!(
-                        "[debug] execute_transaction ok: index={}, gas_used={}",
-                        i, gas_used
-                    );
                 }
+                Err(err) if strict => return rejected(err.to_string()),
                 Err(err) => {
                     // Log the error but continue with other transactions
                     tracing::warn!(index = i, error = ?err, "Transaction execution failed");
-                    debug!(
-                        "[debug] execute_transaction err: index={}, err={:?}",
-                        i, err
-                    );
+                    rejected_transactions.push(left_out(RejectionReason::Reverted));
                 }
             }
         }
 
         // Finish building the block - this calculates the proper state root
-        let BlockBuilderOutcome {
-            execution_result: _,
-            hashed_state: _,
-            trie_updates: _,
-            block,
-        } = builder
-            .finish(&state_provider)
-            .map_err(PayloadBuilderError::other)?;
+        let outcome = builder.finish(state_provider).map_err(PayloadBuilderError::other)?;
+        let bundle_state = state_db.take_bundle();
+        Ok(ExecutedBlock::Finished { outcome, bundle_state, excluded_transactions, cut_off, rejected_transactions })
+    }
 
-        let sealed_block = block.sealed_block().clone();
-        tracing::info!(
-                    block_number = sealed_block.number,
-                    block_hash = ?sealed_block.hash(),
-                    transaction_count = sealed_block.transaction_count(),
-                    gas_used = sealed_block.gas_used,
-                    "Evolve payload builder: built block"
+    /// Reorder the payload transactions with the configured MEV ordering policy,
+    /// recording the detected opportunities in the MEV store
+    fn apply_mev_ordering(
+        &self,
+        config: &EvolvePayloadBuilderConfig,
+        attributes: &mut EvolvePayloadAttributes,
+        block_number: u64,
+    ) {
+        let Some(mev) = config.mev.as_ref().filter(|_| config.runtime_settings().mev_detection) else {
+            return;
+        };
+
+        let mut detector = MevDetector::new(mev.detector_config());
+        let opportunities = detector.analyze_block(&attributes.transactions, block_number);
+        if let Err(err) = self.mev_store.record(block_number, opportunities.clone()) {
+            warn!(block_number, error = %err, "AndeChain: failed to record MEV opportunities");
+        }
+        if opportunities.is_empty() {
+            return;
+        }
+
+        debug!(
+            opportunities = opportunities.len(),
+            policy = ?self.mev_ordering,
+            "AndeChain: ordering transactions around detected MEV"
         );
+        let transactions = std::mem::take(&mut attributes.transactions);
+        attributes.transactions = self.mev_ordering.order(transactions, &opportunities);
+    }
+
+    /// Place the transactions of the winning auction bundle at the top of the payload
+    ///
+    /// Bundles whose transactions aren't registered or whose simulation fails
+    /// are rejected and the next highest bid is tried. Returns the bundle
+    /// placed, with its simulation, if any.
+    async fn apply_auction_bundle(
+        &self,
+        attributes: &mut EvolvePayloadAttributes,
+        sealed_parent: &SealedHeader,
+        next_block_attrs: &NextBlockEnvAttributes,
+        state_provider: &StateProviderBox,
+    ) -> Option<(BundleSubmission, BundleSimulation)> {
+        let auction = self.mev_auction.as_ref()?;
+        let block_number = sealed_parent.number + 1;
+
+        loop {
+            let bundle = auction.select_winning_bundle(block_number).await?;
+            let Some(transactions) = auction.bundle_transactions().resolve(&bundle.transactions) else {
+                let reason = "Bundle transactions not registered".to_string();
+                if !self.reject_auction_bundle(auction, &bundle, reason).await {
+                    return None;
+                }
+                continue;
+            };
+
+            let simulation = auction
+                .simulate_bundle(
+                    &bundle,
+                    &transactions,
+                    sealed_parent,
+                    next_block_attrs,
+                    &self.evm_config,
+                    StateProviderDatabase::new(state_provider),
+                )
+                .await;
+            if let Some((tx_hash, reason)) = &simulation.failed_tx {
+                let reason = format!("Transaction {tx_hash} failed: {reason}");
+                if !self.reject_auction_bundle(auction, &bundle, reason).await {
+                    return None;
+                }
+                continue;
+            }
+
+            info!(
+                block_number,
+                bundle_hash = %bundle.bundle_hash,
+                bid = %bundle.bid_amount,
+                transactions = transactions.len(),
+                "AndeChain: placing winning auction bundle at the top of the block"
+            );
+            attributes.transactions.retain(|tx| !bundle.transactions.contains(tx.hash()));
+            attributes.transactions.splice(0..0, transactions);
+            return Some((bundle, simulation));
+        }
+    }
+
+    /// Mark the auction bundle executed if the built block includes it in order, rejected otherwise
+    async fn settle_auction_bundle(
+        &self,
+        bundle: BundleSubmission,
+        simulation: BundleSimulation,
+        built: &EvolveBuiltPayload,
+    ) {
+        let Some(auction) = self.mev_auction.as_ref() else {
+            return;
+        };
+
+        let included: Vec<TxHash> = built.block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+        if !auction.validate_bundle_execution(&bundle, &included).await {
+            self.reject_auction_bundle(auction, &bundle, "Bundle not included in order".to_string()).await;
+            return;
+        }
 
-        // Return the sealed block
-        Ok(sealed_block)
+        if let Err(err) = auction
+            .mark_bundle_executed(bundle.bundle_hash, simulation.beneficiary_profit, bundle.bid_amount)
+            .await
+        {
+            warn!(bundle_hash = %bundle.bundle_hash, error = %err, "AndeChain: failed to mark auction bundle executed");
+        }
     }
 
-    /// Decide whether to use parallel execution
-    fn should_use_parallel_execution(&self, transactions: &[TransactionSigned]) -> bool {
+    /// Reject an auction bundle, returning whether the auction was updated
+    ///
+    /// A failure is logged instead of failing the payload.
+    async fn reject_auction_bundle(
+        &self,
+        auction: &MevAuctionClient,
+        bundle: &BundleSubmission,
+        reason: String,
+    ) -> bool {
+        debug!(bundle_hash = %bundle.bundle_hash, %reason, "AndeChain: rejecting auction bundle");
+        match auction.mark_bundle_rejected(bundle.bundle_hash, reason).await {
+            Ok(()) => true,
+            Err(err) => {
+                warn!(
+                    bundle_hash = %bundle.bundle_hash,
+                    error = %err,
+                    "AndeChain: failed to mark auction bundle rejected"
+                );
+                false
+            }
+        }
+    }
+
+    /// Decide whether to use parallel execution, along with the estimated
+    /// fraction of the transactions in their largest dependent group when the
+    /// decision came down to it
+    fn should_use_parallel_execution(
+        &self,
+        config: &EvolvePayloadBuilderConfig,
+        transactions: &[TransactionSigned],
+    ) -> (bool, Option<f64>) {
         // If parallel execution is disabled, use sequential
         let parallel_config = match &self.parallel_config {
             Some(config) => config,
-            None => return false,
+            None => return (false, None),
         };
 
-        // Force sequential if configured
-        if parallel_config.force_sequential {
-            return false;
+        // Force sequential if configured, in the executor or reloadable settings,
+        // or switched at runtime
+        let settings = config.runtime_settings();
+        if !settings.parallel_enabled || parallel_config.force_sequential || settings.force_sequential {
+            return (false, None);
         }
 
         // Need minimum number of transactions for parallel execution
-        if transactions.len() < parallel_config.min_transactions_for_parallel {
-            return false;
+        let min_transactions =
+            config.parallel.min_transactions_for_parallel.unwrap_or(parallel_config.min_transactions_for_parallel);
+        if transactions.len() < min_transactions {
+            return (false, None);
         }
 
-        // TODO: Add more sophisticated heuristics
-        // - Check transaction complexity
-        // - Analyze potential conflicts
-        // - Consider gas usage patterns
+        // Blocks dominated by one group of dependent transactions, e.g. swaps
+        // against a single pool, conflict throughout and run slower in parallel.
+        // Transactions whose sender can't be recovered are excluded later on.
+        let dependent_fraction = largest_dependent_group_fraction(
+            transactions.iter().filter_map(|tx| Some((tx.recover_signer().ok()?, tx.to()))),
+        );
+        let max_dependent_fraction =
+            config.parallel.max_dependent_fraction.unwrap_or(parallel_config.max_dependent_fraction);
+        if dependent_fraction > max_dependent_fraction {
+            debug!(
+                dependent_fraction,
+                max_dependent_fraction, "Transactions mostly depend on each other, executing sequentially"
+            );
+            return (false, Some(dependent_fraction));
+        }
 
-        true
+        (true, Some(dependent_fraction))
     }
 
     /// Build payload using parallel execution
+    ///
+    /// Transactions that can't be included in the block, such as ones with an
+    /// invalid signature or nonce, are excluded and the remaining ones re-executed.
+    /// Transactions not validated by `deadline` are left out of the block.
     async fn build_payload_parallel(
         &self,
-        attributes: EvolvePayloadAttributes,
+        mut attributes: EvolvePayloadAttributes,
         sealed_parent: SealedHeader,
         next_block_attrs: NextBlockEnvAttributes,
-    ) -> Result<SealedBlock, PayloadBuilderError> {
-        let parallel_config = self.parallel_config.as_ref()
+        state_provider: &StateProviderBox,
+        deadline: Option<Instant>,
+    ) -> Result<EvolveBuiltPayload, PayloadBuilderError> {
+        let parallel_executor = self.parallel_executor.as_ref()
             .ok_or_else(|| PayloadBuilderError::Internal(RethError::Other(
                 "Parallel config not set".into()
             )))?;
 
         info!(
             "🚀 AndeChain: Starting PARALLEL execution with {} workers",
-            parallel_config.concurrency_level.get()
+            parallel_executor.pool().threads()
         );
 
-        // Create parallel executor
-        let parallel_executor = ParallelExecutor::new(parallel_config.clone());
+        // Workers read the parent state through a shared read-only view
+        let parent_state = StateProviderDatabase::new(state_provider);
 
-        // Convert transactions - they're already TransactionSigned
-        let signed_transactions = attributes.transactions.clone();
+        // Run the pre-execution system calls once, so user transactions execute on
+        // top of the state they leave behind
+        let base_changes =
+            pre_execution_changes(&self.evm_config, state_provider, &sealed_parent, next_block_attrs.clone())?;
+        debug!(
+            accounts = base_changes.len(),
+            "AndeChain: seeding parallel execution with pre-execution changes"
+        );
 
-        // Execute transactions in parallel
-        let parallel_results = parallel_executor.execute_transactions(
-            signed_transactions,
-            &self.evm_config,
-            &sealed_parent,
-            next_block_attrs.clone(),
-        ).await
-        .map_err(|e| PayloadBuilderError::Internal(RethError::Other(format!("Parallel execution failed: {}", e).into())))?;
+        // Execute transactions in parallel, dropping the ones that can't be included
+        let mut rejected_transactions = Vec::new();
+        let parallel_output = loop {
+            // Convert transactions - they're already TransactionSigned
+            let signed_transactions = attributes.transactions.clone();
+
+            match parallel_executor.execute_transactions_with_base_changes(
+                signed_transactions,
+                base_changes.clone(),
+                &self.evm_config,
+                &sealed_parent,
+                next_block_attrs.clone(),
+                &parent_state,
+                deadline.map(Instant::into_std),
+            ).await {
+                Ok(output) => break output,
+                Err(ParallelPayloadError::Timeout { elapsed, incomplete }) => {
+                    warn!(
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        incomplete = incomplete.len(),
+                        "⚠️  AndeChain: Parallel execution timed out, falling back to sequential execution"
+                    );
+                    let mut built = self.build_payload_sequential(
+                        &attributes,
+                        &sealed_parent,
+                        next_block_attrs,
+                        state_provider,
+                        deadline,
+                    )?;
+                    built.rejected_transactions.append(&mut rejected_transactions);
+                    return Ok(built);
+                }
+                Err(e) => match e.excluded_transaction() {
+                    Some(tx_idx) => {
+                        let excluded = attributes.transactions.remove(tx_idx);
+                        let reason = match &e {
+                            ParallelPayloadError::SignerRecovery { .. } => RejectionReason::RecoveryFailed,
+                            _ => RejectionReason::Reverted,
+                        };
+                        rejected_transactions.push(RejectedTx { hash: *excluded.hash(), index: tx_idx, reason });
+                        warn!(
+                            tx_idx,
+                            tx_hash = ?excluded.hash(),
+                            error = %e,
+                            "⚠️  AndeChain: Excluding transaction from parallel block and re-executing"
+                        );
+                    }
+                    None => {
+                        return Err(PayloadBuilderError::Internal(RethError::Other(
+                            format!("Parallel execution failed: {}", e).into(),
+                        )));
+                    }
+                },
+            }
+        };
 
         info!(
             "✅ AndeChain: Parallel execution completed: {} transactions processed",
-            parallel_results.len()
+            parallel_output.results.len()
         );
 
-        // For now, we need to execute sequentially to build the actual block
-        // TODO: Implement parallel block building
-        warn!("⚠️  AndeChain: Falling back to sequential block building (Phase 1 limitation)");
-
-        // Recreate state_db for sequential block building
-        let state_provider = self.client.latest().map_err(PayloadBuilderError::other)?;
-        let db = StateProviderDatabase::new(&state_provider);
-        let mut state_db = State::builder()
-            .with_database(db)
-            .with_bundle_update()
-            .build();
+        // Packing was decided in transaction order during the merge, so the block
+        // leaves out exactly the transactions excluded there
+        let excluded = parallel_output.excluded.clone();
+        let parallel_metrics = parallel_output.metrics.clone();
+        let cut_off = parallel_output.cut_off;
+        if !excluded.is_empty() {
+            warn!(
+                excluded = excluded.len(),
+                "⚠️  AndeChain: Transactions excluded to stay within the block gas limit"
+            );
+        }
+        rejected_transactions.extend(excluded.iter().map(|&tx_idx| RejectedTx {
+            hash: *attributes.transactions[tx_idx].hash(),
+            index: tx_idx,
+            reason: RejectionReason::ExceedsGasLimit,
+        }));
+        if let Some(cut_off) = cut_off {
+            rejected_transactions.extend(deadline_cut(&attributes.transactions, cut_off));
+        }
 
-        // Create block builder and execute transactions sequentially for block construction
-        let mut builder = self.evm_config
-            .builder_for_next_block(&mut state_db, &sealed_parent, next_block_attrs)
+        // Merge the per-transaction changes into block state in transaction order
+        let parallel_bundle = parallel_output
+            .into_bundle_state(
+                State::builder()
+                    .with_database(StateProviderDatabase::new(state_provider))
+                    .with_bundle_update()
+                    .build(),
+            )
             .map_err(PayloadBuilderError::other)?;
 
-        builder
-            .apply_pre_execution_changes()
-            .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+        // The block builder still assembles the block, since receipts come from
+        // it; the merged parallel state, pre-execution changes included, is
+        // checked against its post-state below
+        debug!(
+            accounts = parallel_bundle.state.len(),
+            "AndeChain: merged parallel execution results into bundle state"
+        );
 
-        // Execute transactions sequentially to build the block
-        let mut _total_gas_used = 0u64;
-        for (i, tx) in attributes.transactions.iter().enumerate() {
-            let recovered_tx = tx.try_clone_into_recovered().map_err(|_| {
-                PayloadBuilderError::Internal(RethError::Other(
-                    "Failed to recover transaction".into(),
-                ))
-            })?;
+        // Re-execute the packed transactions sequentially for block construction,
+        // without a deadline since they must match the parallel execution
+        let packed = cut_off.unwrap_or(attributes.transactions.len());
+        let included: Vec<TransactionSigned> = attributes.transactions[..packed]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| excluded.binary_search(i).is_err())
+            .map(|(_, tx)| tx.clone())
+            .collect();
+        let ExecutedBlock::Finished { outcome, bundle_state, rejected_transactions: failed, .. } =
+            self.execute_block(state_provider, &sealed_parent, next_block_attrs, &included, false, None)?
+        else {
+            unreachable!("only strict execution rejects transactions");
+        };
+        rejected_transactions.extend(failed);
 
-            match builder.execute_transaction(recovered_tx) {
-                Ok(gas_used) => {
-                    _total_gas_used += gas_used;
-                    debug!("Sequential execution for block building: tx {} gas_used {}", i, gas_used);
-                }
-                Err(err) => {
-                    warn!("Sequential execution failed for tx {}: {:?}", i, err);
-                }
-            }
+        // The parallel merge and the block must write the same accounts and slots,
+        // with the same values, or the block isn't what the executor admitted
+        let parallel_hashed_state = state_provider.hashed_post_state(&parallel_bundle);
+        let (accounts, slots) = hashed_state_divergence(&parallel_hashed_state, &outcome.hashed_state);
+        if accounts > 0 || slots > 0 {
+            warn!(
+                accounts,
+                slots,
+                "⚠️  AndeChain: Parallel execution state diverged from the built block"
+            );
+            let block_number = sealed_parent.number + 1;
+            return Err(PayloadBuilderError::other(ParallelStateDivergence { block_number, accounts, slots }));
         }
 
-        // Finish building the block
-        let final_state_provider = self.client.latest().map_err(PayloadBuilderError::other)?;
-        let BlockBuilderOutcome {
-            execution_result: _,
-            hashed_state: _,
-            trie_updates: _,
-            block,
-        } = builder
-            .finish(&final_state_provider)
-            .map_err(PayloadBuilderError::other)?;
-
-        let sealed_block = block.sealed_block().clone();
         info!(
             "🏁 AndeChain: Block built successfully with parallel pre-processing"
         );
 
-        Ok(sealed_block)
+        let excluded_transactions =
+            excluded.iter().map(|&tx_idx| *attributes.transactions[tx_idx].hash()).collect();
+        let mut built = EvolveBuiltPayload::from_outcome(
+            outcome,
+            bundle_state,
+            excluded_transactions,
+            cut_off,
+            rejected_transactions,
+        );
+        built.execution.parallel = true;
+        built.execution.parallel_metrics = parallel_metrics;
+        Ok(built)
+    }
+}
+
+/// Drop the transactions listed as excluded by `attributes`, logging each,
+/// and return their hashes
+fn drop_excluded_transactions(attributes: &mut EvolvePayloadAttributes) -> Vec<TxHash> {
+    let excluded = &attributes.excluded_hashes;
+    let mut dropped = Vec::new();
+    attributes.transactions.retain(|tx| {
+        let keep = !excluded.contains(tx.hash());
+        if !keep {
+            info!(tx_hash = %tx.hash(), "Dropping excluded transaction");
+            dropped.push(*tx.hash());
+        }
+        keep
+    });
+    dropped
+}
+
+/// Rejections of the transactions from `cut_off` on, left out at the deadline
+fn deadline_cut(transactions: &[TransactionSigned], cut_off: usize) -> impl Iterator<Item = RejectedTx> + '_ {
+    transactions[cut_off..].iter().zip(cut_off..).map(|(tx, index)| RejectedTx {
+        hash: *tx.hash(),
+        index,
+        reason: RejectionReason::DeadlineCut,
+    })
+}
+
+/// Attributes of the block environment `block` was executed in
+pub(crate) fn block_env_attributes(block: &SealedBlock) -> NextBlockEnvAttributes {
+    NextBlockEnvAttributes {
+        timestamp: block.timestamp,
+        suggested_fee_recipient: block.beneficiary,
+        prev_randao: block.mix_hash,
+        gas_limit: block.gas_limit,
+        parent_beacon_block_root: block.parent_beacon_block_root,
+        withdrawals: block.body().withdrawals.clone(),
+    }
+}
+
+/// State changes of the pre-execution system calls of the block following
+/// `sealed_parent`, over `state_provider`
+pub(crate) fn pre_execution_changes(
+    evm_config: &AndeEvmConfig,
+    state_provider: &StateProviderBox,
+    sealed_parent: &SealedHeader,
+    next_block_attrs: NextBlockEnvAttributes,
+) -> Result<Vec<AccountStateChange>, PayloadBuilderError> {
+    let mut pre_state =
+        State::builder().with_database(StateProviderDatabase::new(state_provider)).with_bundle_update().build();
+    let mut builder = evm_config
+        .builder_for_next_block(&mut pre_state, sealed_parent, next_block_attrs)
+        .map_err(PayloadBuilderError::other)?;
+    builder.apply_pre_execution_changes().map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+    drop(builder);
+
+    pre_state.merge_transitions(BundleRetention::PlainState);
+    Ok(pre_state
+        .take_bundle()
+        .state
+        .iter()
+        .map(|(address, account)| AccountStateChange::from_bundle_account(*address, account))
+        .collect())
+}
+
+/// Number of accounts and of storage slots whose values differ between two
+/// post-states, including those present in only one of them
+fn hashed_state_divergence(left: &HashedPostState, right: &HashedPostState) -> (usize, usize) {
+    let accounts: HashSet<_> = left.accounts.keys().chain(right.accounts.keys()).collect();
+    let accounts = accounts
+        .into_iter()
+        .filter(|hashed_address| left.accounts.get(*hashed_address) != right.accounts.get(*hashed_address))
+        .count();
+
+    let slot = |state: &HashedPostState, (hashed_address, hashed_slot): (&B256, &B256)| {
+        state.storages.get(hashed_address).and_then(|storage| storage.storage.get(hashed_slot)).copied()
+    };
+    let slots: HashSet<_> = [left, right]
+        .into_iter()
+        .flat_map(|state| {
+            state.storages.iter().flat_map(|(hashed_address, storage)| {
+                storage.storage.keys().map(move |hashed_slot| (hashed_address, hashed_slot))
+            })
+        })
+        .collect();
+    let slots = slots.into_iter().filter(|&key| slot(left, key) != slot(right, key)).count();
+    (accounts, slots)
+}
+
+/// First mismatch between the header of `block` and the `receipts` of its
+/// transactions, checking the gas used, the receipts root and the logs bloom
+fn receipts_mismatch(block: &SealedBlock, receipts: &[Receipt]) -> Option<ValidationMismatch> {
+    let gas_used = receipts.last().map_or(0, |receipt| receipt.cumulative_gas_used);
+    if gas_used != block.gas_used {
+        return Some(ValidationMismatch::GasUsed { expected: block.gas_used, actual: gas_used });
+    }
+    let receipts_root = Receipt::calculate_receipt_root_no_memo(receipts);
+    if receipts_root != block.receipts_root {
+        return Some(ValidationMismatch::ReceiptsRoot { expected: block.receipts_root, actual: receipts_root });
+    }
+    bloom_mismatch(block, receipts)
+}
+
+/// Mismatch between the logs bloom of `block` and the one recomputed from the
+/// logs of `receipts`, if any
+fn bloom_mismatch(block: &SealedBlock, receipts: &[Receipt]) -> Option<ValidationMismatch> {
+    let bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+    (bloom != block.logs_bloom).then_some(ValidationMismatch::LogsBloom { expected: block.logs_bloom, actual: bloom })
+}
+
+/// Priority fees paid to the beneficiary of `block`, from the gas used by each
+/// transaction as recorded in `receipts`
+fn total_fees(block: &SealedBlock, receipts: &[Receipt]) -> U256 {
+    let base_fee = block.base_fee_per_gas.unwrap_or_default();
+    let mut cumulative_gas_used = 0;
+    block
+        .body()
+        .transactions
+        .iter()
+        .zip(receipts)
+        .map(|(tx, receipt)| {
+            let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used;
+            U256::from(tx.effective_tip_per_gas(base_fee).unwrap_or_default()) * U256::from(gas_used)
+        })
+        .sum()
+}
+
+/// Stats of `built`, before waiting on the consensus
+fn payload_stats(built: &EvolveBuiltPayload, build_duration: Duration, mev_captured: U256) -> PayloadStats {
+    let metrics = built.execution.parallel_metrics.as_ref();
+    PayloadStats {
+        block_number: U64::from(built.block.number),
+        block_hash: built.block.hash(),
+        build_duration_ms: U64::from(build_duration.as_millis() as u64),
+        execution_mode: if built.execution.parallel { ExecutionMode::Parallel } else { ExecutionMode::Sequential },
+        transaction_count: U64::from(built.block.body().transactions.len()),
+        rejected_count: U64::from(built.rejected_transactions.len()),
+        gas_used: U64::from(built.block.gas_used),
+        conflicts: metrics.map(|metrics| U64::from(metrics.conflicts)),
+        retries: metrics.map(|metrics| U64::from(metrics.retries)),
+        attestation: AttestationStatus::NotAwaited,
+        mev_captured,
     }
 }
 
@@ -381,6 +1695,20 @@ where
     Some(EvolvePayloadBuilder::new(client, evm_config, config))
 }
 
+/// Creates a new payload builder service from an EVM configuration builder,
+/// executing in parallel if the builder enables it
+pub fn create_payload_builder_service_from_builder<Client>(
+    client: Arc<Client>,
+    evm_config_builder: AndeEvmConfigBuilder,
+    config: EvolvePayloadBuilderConfig,
+) -> Result<EvolvePayloadBuilder<Client>, AndeEvmConfigError>
+where
+    Client: StateProviderFactory + HeaderProvider<Header = Header> + Send + Sync + 'static,
+{
+    let (evm_config, parallel_config) = evm_config_builder.build_with_parallel()?;
+    Ok(EvolvePayloadBuilder::new_with_parallel(client, evm_config, parallel_config, config))
+}
+
 /// Creates a new payload builder service with parallel configuration
 pub fn create_payload_builder_service_with_parallel<Client>(
     client: Arc<Client>,
@@ -393,3 +1721,32 @@ where
 {
     Some(EvolvePayloadBuilder::new_with_parallel(client, evm_config, parallel_config, config))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Account;
+    use reth_trie_common::HashedStorage;
+
+    #[test]
+    fn test_hashed_state_divergence_counts_both_sides() {
+        let account = |balance: u64| Some(Account { balance: U256::from(balance), ..Default::default() });
+        let (shared, parallel_only, block_only) = (B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+
+        let mut parallel = HashedPostState::default();
+        parallel.accounts.insert(shared, account(10));
+        parallel.accounts.insert(parallel_only, account(1));
+        parallel.storages.insert(shared, HashedStorage::from_iter(false, [(B256::ZERO, U256::from(7))]));
+        assert_eq!(hashed_state_divergence(&parallel, &parallel), (0, 0));
+
+        // Entries written by one side only count as much as differing values
+        let mut block = parallel.clone();
+        block.accounts.remove(&parallel_only);
+        block.accounts.insert(block_only, account(1));
+        assert_eq!(hashed_state_divergence(&parallel, &block), (2, 0));
+        block.storages.insert(block_only, HashedStorage::from_iter(false, [(B256::ZERO, U256::from(1))]));
+        block.storages.insert(shared, HashedStorage::from_iter(false, [(B256::ZERO, U256::from(8))]));
+        assert_eq!(hashed_state_divergence(&parallel, &block), (2, 2));
+        assert_eq!(hashed_state_divergence(&block, &parallel), (2, 2));
+    }
+}
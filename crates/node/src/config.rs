@@ -1,9 +1,33 @@
+use alloy_primitives::{Address, U256};
+use evolve_ev_reth::{
+    evm_config::{AndeEvmConfigBuilder, AndePrecompileConfig, NetworkProfile},
+    mev::{MevConfig, MevConfigError},
+    parallel::ParallelConfig as EvolveParallelConfig,
+    rpc::{
+        admin::{RuntimeSettings, RuntimeSwitches},
+        health::{HealthThresholds, HealthThresholdsError},
+    },
+    EvolveConfig, PayloadValidationConfig, DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS,
+};
+use reth_chainspec::ChainSpec;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+/// Chains serving the ANDE precompiles unless the config says otherwise:
+/// AndeChain and local development networks
+pub const ANDE_CHAIN_IDS: [u64; 2] = [6174, 31337];
 
 /// AndeChain Genesis Configuration
 /// Contains custom configuration for the AndeChain sovereign rollup
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct AndechainGenesisConfig {
     /// K'intu sacred phrase or identifier
     #[serde(default)]
@@ -23,17 +47,226 @@ pub struct AndechainGenesisConfig {
     /// Network identifier
     #[serde(default)]
     pub network: Option<String>,
+    /// Profile picking the ANDE precompile's default caps and strictness
+    #[serde(default)]
+    pub ande_network_profile: Option<NetworkProfile>,
+    /// Address of the ANDEToken contract, authorized to call the ANDE precompile
+    #[serde(default)]
+    pub ande_token_address: Option<Address>,
+    /// Other addresses authorized to call the ANDE precompile
+    #[serde(default)]
+    pub ande_allow_list: Vec<Address>,
+    /// Maximum amount a single caller can move through the ANDE precompile in a block
+    #[serde(default)]
+    pub ande_per_caller_block_cap: Option<U256>,
+    /// Let any caller move any amount out of any account through the ANDE
+    /// precompile, ignoring the profile, allow-list and caps
+    ///
+    /// Only meant for throwaway local networks; the node warns at startup.
+    #[serde(default)]
+    pub ande_insecure_unrestricted: bool,
     /// Additional custom fields
     #[serde(default, flatten)]
     pub extra: BTreeMap<String, serde_json::Value>,
 }
 
+impl AndechainGenesisConfig {
+    /// Key of the configuration among the genesis config's extra fields
+    pub const GENESIS_KEY: &'static str = "andechain";
+
+    /// Reads the configuration from the `andechain` field of the genesis config, if any
+    pub fn from_chain_spec(chain_spec: &ChainSpec) -> Result<Option<Self>, ConfigError> {
+        chain_spec
+            .genesis
+            .config
+            .extra_fields
+            .get_deserialized(Self::GENESIS_KEY)
+            .transpose()
+            .map_err(ConfigError::Genesis)
+    }
+}
+
+/// Preset the parallel executor settings start from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParallelPreset {
+    /// Balanced settings, see [`EvolveParallelConfig::default`]
+    #[default]
+    Default,
+    /// More workers and retries, see [`EvolveParallelConfig::high_throughput`]
+    HighThroughput,
+    /// Fewer workers, see [`EvolveParallelConfig::low_latency`]
+    LowLatency,
+    /// Two workers and no monitoring, see [`EvolveParallelConfig::testing`]
+    Testing,
+}
+
+impl ParallelPreset {
+    /// Name used in configuration files
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::HighThroughput => "high_throughput",
+            Self::LowLatency => "low_latency",
+            Self::Testing => "testing",
+        }
+    }
+
+    /// Parallel executor settings of the preset
+    pub fn parallel_config(&self) -> EvolveParallelConfig {
+        match self {
+            Self::Default => EvolveParallelConfig::default(),
+            Self::HighThroughput => EvolveParallelConfig::high_throughput(),
+            Self::LowLatency => EvolveParallelConfig::low_latency(),
+            Self::Testing => EvolveParallelConfig::testing(),
+        }
+    }
+}
+
+impl FromStr for ParallelPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "default" => Ok(Self::Default),
+            "high_throughput" => Ok(Self::HighThroughput),
+            "low_latency" => Ok(Self::LowLatency),
+            "testing" => Ok(Self::Testing),
+            other => Err(format!("Unknown parallel preset: {other}")),
+        }
+    }
+}
+
+/// Parallel execution settings
+///
+/// The executor is set up at startup from the preset and the overrides
+/// below, which are fixed for the lifetime of the node. `force_sequential`,
+/// `min_transactions_for_parallel` and `max_dependent_fraction` are read at
+/// the start of each block and can change while the node runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParallelTuning {
+    /// Build blocks with the parallel executor
+    pub enabled: bool,
+    /// Preset the executor settings start from
+    pub preset: ParallelPreset,
+    /// Worker threads, the preset's when unset
+    pub concurrency_level: Option<NonZeroUsize>,
+    /// Re-executions of a conflicting transaction, the preset's when unset
+    pub max_retries: Option<usize>,
+    /// Pick the worker count per block from recent conflicts, the preset's when unset
+    pub adaptive: Option<bool>,
+    /// Abort parallel execution of a block taking longer than this, the preset's when unset
+    #[serde(rename = "execution_timeout_ms", with = "optional_duration_millis", skip_serializing_if = "Option::is_none")]
+    pub execution_timeout: Option<Duration>,
+    /// Directory a JSON trace of every block executed in parallel is written
+    /// to, relative to the datadir; no traces are written when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_dir: Option<PathBuf>,
+    /// Execute every block sequentially
+    pub force_sequential: bool,
+    /// Fewest transactions executed in parallel, the executor's own minimum when unset
+    pub min_transactions_for_parallel: Option<usize>,
+    /// Estimated fraction of a block's transactions in their largest dependent
+    /// group above which the block executes sequentially, the executor's own
+    /// threshold when unset
+    pub max_dependent_fraction: Option<f64>,
+}
+
+impl ParallelTuning {
+    /// Creates a new `ParallelTuning` with parallel execution disabled
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            preset: ParallelPreset::Default,
+            concurrency_level: None,
+            max_retries: None,
+            adaptive: None,
+            execution_timeout: None,
+            trace_dir: None,
+            force_sequential: false,
+            min_transactions_for_parallel: None,
+            max_dependent_fraction: None,
+        }
+    }
+
+    /// Settings of the parallel executor, `None` when parallel execution is
+    /// disabled
+    ///
+    /// The overrides set here take precedence over the preset.
+    pub fn executor_config(&self) -> Option<EvolveParallelConfig> {
+        if !self.enabled {
+            return None;
+        }
+        let mut config = self.preset.parallel_config();
+        if let Some(concurrency_level) = self.concurrency_level {
+            config.concurrency_level = concurrency_level;
+        }
+        if let Some(max_retries) = self.max_retries {
+            config.max_retries = max_retries;
+        }
+        if let Some(adaptive) = self.adaptive {
+            config.adaptive = adaptive;
+        }
+        if let Some(execution_timeout) = self.execution_timeout {
+            config.execution_timeout = Some(execution_timeout);
+        }
+        if let Some(trace_dir) = &self.trace_dir {
+            config.trace_dir = Some(trace_dir.clone());
+        }
+        if let Some(min_transactions) = self.min_transactions_for_parallel {
+            config.min_transactions_for_parallel = min_transactions;
+        }
+        if let Some(max_dependent_fraction) = self.max_dependent_fraction {
+            config.max_dependent_fraction = max_dependent_fraction;
+        }
+        Some(config)
+    }
+}
+
 /// Configuration for the Evolve payload builder
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+///
+/// Read from a TOML file whose `[mev]` section holds the [`MevConfig`]. The
+/// `[andechain]` section and the MEV contract addresses are fixed for the
+/// lifetime of the node, the other settings can be reloaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct EvolvePayloadBuilderConfig {
     /// AndeChain-specific genesis configuration
     #[serde(default)]
     pub andechain: Option<AndechainGenesisConfig>,
+    /// MEV integration; transactions are reordered by the builder's MEV
+    /// ordering policy when detection is enabled
+    #[serde(default)]
+    pub mev: Option<MevConfig>,
+    /// Bounds the payload attributes are validated against
+    #[serde(default)]
+    pub payload_validation: PayloadValidationConfig,
+    /// Limits of the transactions served by the txpool RPC
+    #[serde(default)]
+    pub txpool: EvolveConfig,
+    /// Parallel execution settings applied on top of the executor's
+    #[serde(default)]
+    pub parallel: ParallelTuning,
+    /// Time budget of a block build, after which no further transaction is
+    /// admitted and the block is sealed with the ones executed; unbounded when unset
+    #[serde(
+        default,
+        rename = "max_build_duration_ms",
+        with = "optional_duration_millis",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_build_duration: Option<Duration>,
+    /// Serve the ANDE precompiles in executed blocks; enabled on the chains of
+    /// [`ANDE_CHAIN_IDS`] when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ande_precompiles_enabled: Option<bool>,
+    /// Thresholds `ande_health` grades the node's subsystems against
+    #[serde(default)]
+    pub health: HealthThresholds,
+    /// Settings switched through the admin RPC while the node runs, never
+    /// read from the file
+    #[serde(skip)]
+    pub switches: RuntimeSwitches,
 }
 
 impl EvolvePayloadBuilderConfig {
@@ -41,11 +274,136 @@ impl EvolvePayloadBuilderConfig {
     pub const fn new() -> Self {
         Self {
             andechain: None,
+            mev: None,
+            payload_validation: PayloadValidationConfig::new(),
+            txpool: EvolveConfig::new_with_gas(DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS),
+            parallel: ParallelTuning::new(),
+            max_build_duration: None,
+            ande_precompiles_enabled: None,
+            health: HealthThresholds::new(),
+            switches: RuntimeSwitches::new(),
+        }
+    }
+
+    /// Settings in effect once the runtime switches are applied
+    pub fn runtime_settings(&self) -> RuntimeSettings {
+        let switches = &self.switches;
+        RuntimeSettings {
+            parallel_enabled: switches.parallel_enabled,
+            force_sequential: switches.force_sequential.unwrap_or(self.parallel.force_sequential),
+            mev_detection: switches.mev_detection && self.mev.as_ref().is_some_and(|mev| mev.enable_detection),
+            mev_auction: switches.mev_auction,
+            mev_distribution: switches.mev_distribution,
+        }
+    }
+
+    /// Parses the configuration from TOML
+    pub fn from_toml_str(toml: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Reads the configuration from the TOML file at `path`
+    pub fn from_toml_file(path: &Path) -> Result<Self, ConfigError> {
+        Self::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// Loads the configuration from the file at `path`, if any, and the environment
+    ///
+    /// `ANDE_MEV_*` variables override the `[mev]` section, enabling MEV
+    /// integration with the default configuration when the file has none.
+    /// The result is validated.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => Self::from_toml_file(path)?,
+            None => Self::new(),
+        };
+        if MevConfig::env_is_set() {
+            config.mev = Some(config.mev.unwrap_or_default().with_env_overrides()?);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Configuration of the ANDE precompile for the chain of `chain_spec`
+    ///
+    /// The network profile, ANDEToken address, allow-list and per-caller block
+    /// cap come from the `[andechain]` section, or from the `andechain` field
+    /// of the genesis config when the file has none. The profile defaults to
+    /// [`NetworkProfile::Mainnet`]. `ANDE_*` variables override them, see
+    /// [`AndePrecompileConfig::with_env_overrides`]. Setting
+    /// `ande_insecure_unrestricted` starts from
+    /// [`AndePrecompileConfig::insecure_unrestricted`] instead.
+    ///
+    /// Fails if strict validation is enabled but no caller is authorized, as
+    /// the precompile would then reject every call.
+    pub fn ande_precompile_config(&self, chain_spec: &ChainSpec) -> Result<AndePrecompileConfig, ConfigError> {
+        let andechain = match &self.andechain {
+            Some(andechain) => Some(andechain.clone()),
+            None => AndechainGenesisConfig::from_chain_spec(chain_spec)?,
+        };
+
+        let profile = andechain
+            .as_ref()
+            .and_then(|andechain| andechain.ande_network_profile)
+            .unwrap_or_default();
+        let mut config = profile.precompile_config();
+        if andechain.as_ref().is_some_and(|andechain| andechain.ande_insecure_unrestricted) {
+            config = AndePrecompileConfig::insecure_unrestricted();
+        }
+        if let Some(andechain) = andechain {
+            if let Some(token) = andechain.ande_token_address {
+                config.set_ande_token_address(token);
+            }
+            config.allow_list.extend(andechain.ande_allow_list);
+            if let Some(cap) = andechain.ande_per_caller_block_cap {
+                config.per_caller_block_cap = Some(cap);
+            }
+        }
+        let config = config.with_env_overrides().map_err(|err| ConfigError::AndePrecompile(err.to_string()))?;
+
+        if config.denies_all_callers() {
+            return Err(ConfigError::NoAuthorizedAndeCaller);
         }
+        Ok(config)
+    }
+
+    /// Whether blocks of the chain of `chain_spec` are executed with the ANDE
+    /// precompiles
+    pub fn ande_precompiles_enabled(&self, chain_spec: &ChainSpec) -> bool {
+        self.ande_precompiles_enabled.unwrap_or_else(|| ANDE_CHAIN_IDS.contains(&chain_spec.chain.id()))
+    }
+
+    /// Builder of the EVM configuration blocks of `chain_spec` are executed
+    /// with, by the payload builder and on import alike
+    ///
+    /// The ANDE precompiles are configured by [`Self::ande_precompile_config`]
+    /// when enabled on the chain; otherwise only the standard precompiles are
+    /// served.
+    pub fn evm_config_builder(&self, chain_spec: Arc<ChainSpec>) -> Result<AndeEvmConfigBuilder, ConfigError> {
+        let builder = if self.ande_precompiles_enabled(&chain_spec) {
+            AndeEvmConfigBuilder::new().precompile_config(self.ande_precompile_config(&chain_spec)?)
+        } else {
+            AndeEvmConfigBuilder::new().ande_precompiles(false)
+        };
+        Ok(builder.chain_spec(chain_spec))
     }
 
     /// Validates the configuration
-    pub const fn validate(&self) -> Result<(), ConfigError> {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(mev) = &self.mev {
+            mev.validate()?;
+        }
+        let PayloadValidationConfig { min_gas_limit, max_gas_limit, .. } = self.payload_validation;
+        if min_gas_limit > max_gas_limit {
+            return Err(ConfigError::InvalidGasLimitBounds { min: min_gas_limit, max: max_gas_limit });
+        }
+        if self.max_build_duration.is_some_and(|duration| duration.is_zero()) {
+            return Err(ConfigError::ZeroBuildDuration);
+        }
+        if let Some(parallel) = self.parallel.executor_config() {
+            parallel.validate().map_err(ConfigError::Parallel)?;
+        }
+        self.health.validate()?;
         Ok(())
     }
 }
@@ -56,4 +414,480 @@ pub enum ConfigError {
     /// Invalid configuration provided
     #[error("Invalid config")]
     InvalidConfig,
+    /// The config file could not be read
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] io::Error),
+    /// The config file is not valid TOML for this configuration
+    #[error("Failed to parse config file: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The MEV configuration is invalid
+    #[error("Invalid MEV config: {0}")]
+    Mev(#[from] MevConfigError),
+    /// The `andechain` field of the genesis config is malformed
+    #[error("Invalid andechain genesis config: {0}")]
+    Genesis(#[source] serde_json::Error),
+    /// An `ANDE_*` precompile variable is invalid
+    #[error("Invalid ANDE precompile config: {0}")]
+    AndePrecompile(String),
+    /// Strict validation is enabled without any authorized caller
+    #[error(
+        "ANDE precompile strict validation is enabled but no caller is authorized: \
+         set `ande_token_address` in the andechain genesis config or ANDE_TOKEN_ADDRESS, \
+         or, on a throwaway local network only, set `ande_insecure_unrestricted`"
+    )]
+    NoAuthorizedAndeCaller,
+    /// The lowest accepted payload gas limit is above the highest
+    #[error("Invalid payload gas limit bounds: min {min} is above max {max}")]
+    InvalidGasLimitBounds {
+        /// Lowest gas limit accepted
+        min: u64,
+        /// Highest gas limit accepted
+        max: u64,
+    },
+    /// The build time budget leaves no time to execute any transaction
+    #[error("The maximum build duration must be greater than zero")]
+    ZeroBuildDuration,
+    /// The parallel executor settings are invalid
+    #[error("Invalid parallel execution config: {0}")]
+    Parallel(String),
+    /// The health thresholds are invalid
+    #[error("Invalid health config: {0}")]
+    Health(#[from] HealthThresholdsError),
+}
+
+mod optional_duration_millis {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        duration.map(|duration| duration.as_millis() as u64).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use alloy_primitives::U256;
+    use evolve_ev_reth::evm_config::ANDE_PRECOMPILE_ADDRESS;
+    use reth_evm::ConfigureEvm;
+    use std::time::Duration;
+
+    const CONFIG: &str = r#"
+[andechain]
+name = "AndeChain"
+
+[mev]
+enable_auction = false
+distributor_address = "0x00000000000000000000000000000000000000cc"
+min_mev_value = "0x3e8"
+deposit_interval_secs = 600
+detect_sandwich = false
+dex_routers = ["0x00000000000000000000000000000000000000aa"]
+"#;
+
+    #[test]
+    fn test_mev_section_parsed_with_defaults() {
+        let config = EvolvePayloadBuilderConfig::from_toml_str(CONFIG).unwrap();
+        assert_eq!(config.andechain.unwrap().name.as_deref(), Some("AndeChain"));
+
+        let mev = config.mev.unwrap();
+        assert_eq!(
+            mev,
+            MevConfig {
+                enable_auction: false,
+                distributor_address: Some(Address::with_last_byte(0xcc)),
+                min_mev_value: U256::from(1000),
+                deposit_interval: Duration::from_secs(600),
+                detect_sandwich: false,
+                dex_routers: vec![Address::with_last_byte(0xaa)],
+                ..Default::default()
+            }
+        );
+        assert!(mev.validate().is_ok());
+
+        // The MEV section is optional
+        assert!(EvolvePayloadBuilderConfig::from_toml_str("").unwrap().mev.is_none());
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mev = MevConfig {
+            auction_address: Some(Address::with_last_byte(1)),
+            distributor_address: Some(Address::with_last_byte(2)),
+            max_mev_buffer: U256::MAX,
+            lending_protocols: vec![Address::with_last_byte(3), Address::with_last_byte(4)],
+            ..Default::default()
+        };
+        let config = EvolvePayloadBuilderConfig {
+            mev: Some(mev.clone()),
+            max_build_duration: Some(Duration::from_millis(250)),
+            ..EvolvePayloadBuilderConfig::new()
+        };
+
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("max_build_duration_ms = 250"), "unexpected TOML: {toml}");
+        let parsed = EvolvePayloadBuilderConfig::from_toml_str(&toml).unwrap();
+        assert_eq!(parsed.mev, Some(mev));
+        assert_eq!(parsed.max_build_duration, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_invalid_files_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payload-builder.toml");
+
+        // Distribution is enabled by default and needs a distributor
+        std::fs::write(&path, "[mev]\nauction_address = \"0x0000000000000000000000000000000000000001\"\n").unwrap();
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_toml_file(&path).unwrap().validate(),
+            Err(ConfigError::Mev(MevConfigError::MissingDistributorAddress))
+        ));
+
+        std::fs::write(&path, "[mev]\nmax_pending = \"many\"\n").unwrap();
+        assert!(matches!(EvolvePayloadBuilderConfig::from_toml_file(&path), Err(ConfigError::Toml(_))));
+
+        std::fs::write(&path, "[payload_validation]\nmin_gas_limit = 40000000\n").unwrap();
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_toml_file(&path).unwrap().validate(),
+            Err(ConfigError::InvalidGasLimitBounds { min: 40_000_000, max: 30_000_000 })
+        ));
+
+        std::fs::write(&path, "max_build_duration_ms = 0\n").unwrap();
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_toml_file(&path).unwrap().validate(),
+            Err(ConfigError::ZeroBuildDuration)
+        ));
+
+        std::fs::write(&path, "[parallel]\nenabled = true\nmax_retries = 0\n").unwrap();
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_toml_file(&path).unwrap().validate(),
+            Err(ConfigError::Parallel(_))
+        ));
+
+        std::fs::write(&path, "[health]\nattestation_queue_degraded = 50\nattestation_queue_unhealthy = 20\n").unwrap();
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_toml_file(&path).unwrap().validate(),
+            Err(ConfigError::Health(_))
+        ));
+
+        std::fs::write(&path, "[parallel]\npreset = \"fastest\"\n").unwrap();
+        assert!(matches!(EvolvePayloadBuilderConfig::from_toml_file(&path), Err(ConfigError::Toml(_))));
+
+        let missing = dir.path().join("missing.toml");
+        assert!(matches!(EvolvePayloadBuilderConfig::from_toml_file(&missing), Err(ConfigError::Io(_))));
+    }
+
+    #[test]
+    fn test_parallel_section() {
+        let config = EvolvePayloadBuilderConfig::from_toml_str(
+            "[parallel]\nenabled = true\npreset = \"high_throughput\"\nexecution_timeout_ms = 500\n",
+        )
+        .unwrap();
+        assert_eq!(config.parallel.preset, ParallelPreset::HighThroughput);
+        assert_eq!(config.parallel.execution_timeout, Some(Duration::from_millis(500)));
+        assert!(config.validate().is_ok());
+
+        let toml = toml::to_string(&config).unwrap();
+        assert_eq!(EvolvePayloadBuilderConfig::from_toml_str(&toml).unwrap(), config);
+
+        // Disabled unless enabled explicitly, whatever the preset
+        let config = EvolvePayloadBuilderConfig::from_toml_str("[parallel]\npreset = \"testing\"\n").unwrap();
+        assert!(config.parallel.executor_config().is_none());
+        assert!(EvolvePayloadBuilderConfig::new().parallel.executor_config().is_none());
+    }
+
+    #[test]
+    fn test_parallel_presets() {
+        for (preset, expected) in [
+            (ParallelPreset::Default, EvolveParallelConfig::default()),
+            (ParallelPreset::HighThroughput, EvolveParallelConfig::high_throughput()),
+            (ParallelPreset::LowLatency, EvolveParallelConfig::low_latency()),
+            (ParallelPreset::Testing, EvolveParallelConfig::testing()),
+        ] {
+            assert_eq!(preset.as_str().parse::<ParallelPreset>(), Ok(preset));
+            let tuning = ParallelTuning { enabled: true, preset, ..ParallelTuning::new() };
+            let config = tuning.executor_config().unwrap();
+            assert_eq!(config.concurrency_level, expected.concurrency_level);
+            assert_eq!(config.max_retries, expected.max_retries);
+            assert_eq!(config.min_transactions_for_parallel, expected.min_transactions_for_parallel);
+            assert_eq!(config.max_dependency_depth, expected.max_dependency_depth);
+        }
+        assert_eq!("High-Throughput".parse::<ParallelPreset>(), Ok(ParallelPreset::HighThroughput));
+        assert!("fastest".parse::<ParallelPreset>().is_err());
+    }
+
+    #[test]
+    fn test_parallel_overrides_take_precedence() {
+        let tuning = ParallelTuning {
+            enabled: true,
+            preset: ParallelPreset::LowLatency,
+            concurrency_level: NonZeroUsize::new(12),
+            max_retries: Some(7),
+            adaptive: Some(true),
+            execution_timeout: Some(Duration::from_secs(1)),
+            trace_dir: Some(PathBuf::from("parallel-traces")),
+            min_transactions_for_parallel: Some(9),
+            ..ParallelTuning::new()
+        };
+        let config = tuning.executor_config().unwrap();
+        assert_eq!(config.concurrency_level.get(), 12);
+        assert_eq!(config.max_retries, 7);
+        assert!(config.adaptive);
+        assert_eq!(config.execution_timeout, Some(Duration::from_secs(1)));
+        assert_eq!(config.min_transactions_for_parallel, 9);
+        assert_eq!(config.trace_dir, Some(PathBuf::from("parallel-traces")));
+
+        // Settings without an override keep the preset's
+        let low_latency = EvolveParallelConfig::low_latency();
+        assert_eq!(config.max_dependency_depth, low_latency.max_dependency_depth);
+        assert_eq!(config.adaptive_window, low_latency.adaptive_window);
+    }
+
+    /// Variables read by [`AndePrecompileConfig::with_env_overrides`]
+    const ANDE_ENV_VARS: [&str; 8] = [
+        "ANDE_PRECOMPILE_ADDRESS",
+        "ANDE_TOKEN_ADDRESS",
+        "ANDE_ALLOW_LIST",
+        "ANDE_PER_CALL_CAP",
+        "ANDE_PER_BLOCK_CAP",
+        "ANDE_PER_CALLER_BLOCK_CAP",
+        "ANDE_STRICT_VALIDATION",
+        "ANDE_EMIT_TRANSFER_LOGS",
+    ];
+
+    /// Serializes tests that modify the process environment
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Replaces every ANDE precompile variable for the lifetime of the guard
+    struct EnvGuard {
+        previous: Vec<(&'static str, Option<String>)>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, &str)]) -> Self {
+            let lock = ENV_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            let previous: Vec<_> = ANDE_ENV_VARS.iter().map(|&name| (name, std::env::var(name).ok())).collect();
+
+            // SAFETY: tests touching the environment hold `ENV_LOCK`
+            unsafe {
+                for name in ANDE_ENV_VARS {
+                    std::env::remove_var(name);
+                }
+                for (name, value) in vars {
+                    std::env::set_var(name, value);
+                }
+            }
+            Self { previous, _lock: lock }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            // SAFETY: the guard still holds `ENV_LOCK`
+            unsafe {
+                for (name, value) in &self.previous {
+                    match value {
+                        Some(value) => std::env::set_var(name, value),
+                        None => std::env::remove_var(name),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Chain spec whose genesis config holds `andechain`, if any
+    fn genesis_chain_spec(andechain: Option<serde_json::Value>) -> ChainSpec {
+        let mut genesis = Genesis::default();
+        if let Some(andechain) = andechain {
+            genesis.config.extra_fields.insert(AndechainGenesisConfig::GENESIS_KEY.to_string(), andechain);
+        }
+        ChainSpec::from(genesis)
+    }
+
+    #[test]
+    fn test_ande_precompile_config_from_genesis() {
+        let _env = EnvGuard::set(&[]);
+        let (token, other) = (Address::with_last_byte(0x01), Address::with_last_byte(0x02));
+        let chain_spec = genesis_chain_spec(Some(serde_json::json!({
+            "name": "AndeChain",
+            "ande_token_address": token,
+            "ande_allow_list": [other],
+            "ande_per_caller_block_cap": "0x3e8",
+        })));
+
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&chain_spec).unwrap();
+        assert!(config.strict_validation);
+        assert_eq!(config.ande_token_address, token);
+        assert_eq!(config.per_caller_block_cap, Some(U256::from(1_000)));
+        assert!(config.is_authorized(token));
+        assert!(config.is_authorized(other));
+        assert!(!config.is_authorized(Address::with_last_byte(0x03)));
+
+        // The `[andechain]` section of the config file takes precedence
+        let file = EvolvePayloadBuilderConfig::from_toml_str(
+            "[andechain]\nande_token_address = \"0x0000000000000000000000000000000000000003\"\n",
+        )
+        .unwrap();
+        let config = file.ande_precompile_config(&chain_spec).unwrap();
+        assert_eq!(config.ande_token_address, Address::with_last_byte(0x03));
+        assert!(!config.is_authorized(token));
+
+        let malformed = genesis_chain_spec(Some(serde_json::json!({ "ande_token_address": "0x1234" })));
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::new().ande_precompile_config(&malformed),
+            Err(ConfigError::Genesis(_))
+        ));
+    }
+
+    #[test]
+    fn test_ande_precompile_config_env_overrides_genesis() {
+        let token = Address::with_last_byte(0x01);
+        let chain_spec = genesis_chain_spec(Some(serde_json::json!({ "ande_token_address": token })));
+        let env = EnvGuard::set(&[
+            ("ANDE_TOKEN_ADDRESS", "0x00000000000000000000000000000000000000aa"),
+            ("ANDE_ALLOW_LIST", "0x00000000000000000000000000000000000000bb"),
+            ("ANDE_PER_CALLER_BLOCK_CAP", "500"),
+        ]);
+
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&chain_spec).unwrap();
+        assert_eq!(config.ande_token_address, Address::with_last_byte(0xaa));
+        assert!(config.is_authorized(Address::with_last_byte(0xaa)));
+        assert!(config.is_authorized(Address::with_last_byte(0xbb)));
+        assert!(!config.is_authorized(token), "the genesis token address is replaced");
+        assert_eq!(config.per_caller_block_cap, Some(U256::from(500)));
+
+        // Unless the genesis allow-list names it as well
+        let listed = genesis_chain_spec(Some(serde_json::json!({
+            "ande_token_address": token,
+            "ande_allow_list": [token],
+        })));
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&listed).unwrap();
+        assert!(config.is_authorized(token));
+        assert!(config.is_authorized(Address::with_last_byte(0xaa)));
+
+        drop(env);
+        let _env = EnvGuard::set(&[("ANDE_TOKEN_ADDRESS", "token")]);
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::new().ande_precompile_config(&chain_spec),
+            Err(ConfigError::AndePrecompile(_))
+        ));
+    }
+
+    #[test]
+    fn test_ande_precompile_config_network_profile() {
+        let _env = EnvGuard::set(&[]);
+        let token = Address::with_last_byte(0x01);
+        let testnet = genesis_chain_spec(Some(serde_json::json!({
+            "ande_token_address": token,
+            "ande_network_profile": "testnet",
+        })));
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&testnet).unwrap();
+        assert_eq!(config.per_block_cap, NetworkProfile::Testnet.precompile_config().per_block_cap);
+        assert!(config.is_authorized(token));
+
+        // Genesis values beat the profile defaults
+        let capped = genesis_chain_spec(Some(serde_json::json!({
+            "ande_token_address": token,
+            "ande_per_caller_block_cap": "0x64",
+        })));
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&capped).unwrap();
+        assert_eq!(config.per_caller_block_cap, Some(U256::from(100)));
+        assert_eq!(config.per_block_cap, NetworkProfile::Mainnet.precompile_config().per_block_cap);
+
+        // The dev profile lifts the caps but still needs an authorized caller
+        let dev = genesis_chain_spec(Some(serde_json::json!({ "ande_network_profile": "dev" })));
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::new().ande_precompile_config(&dev),
+            Err(ConfigError::NoAuthorizedAndeCaller)
+        ));
+        let dev = genesis_chain_spec(Some(serde_json::json!({
+            "ande_token_address": token,
+            "ande_network_profile": "dev",
+        })));
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&dev).unwrap();
+        assert!(config.strict_validation);
+        assert!(config.is_authorized(token));
+        assert!(!config.is_authorized(Address::with_last_byte(0x03)));
+        assert_eq!((config.per_call_cap, config.per_block_cap), (U256::MAX, None));
+
+        // Any caller is accepted only when explicitly asked for
+        let unrestricted = genesis_chain_spec(Some(serde_json::json!({
+            "ande_network_profile": "dev",
+            "ande_insecure_unrestricted": true,
+        })));
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&unrestricted).unwrap();
+        assert!(!config.strict_validation);
+        assert!(config.is_authorized(Address::with_last_byte(0x03)));
+    }
+
+    #[test]
+    fn test_ande_precompile_config_without_authorized_caller_fails() {
+        let env = EnvGuard::set(&[]);
+        let named = genesis_chain_spec(Some(serde_json::json!({ "name": "AndeChain" })));
+        for chain_spec in [genesis_chain_spec(None), named] {
+            let err = EvolvePayloadBuilderConfig::new().ande_precompile_config(&chain_spec).unwrap_err();
+            assert!(matches!(err, ConfigError::NoAuthorizedAndeCaller), "unexpected error: {err}");
+            assert!(err.to_string().contains("ANDE_TOKEN_ADDRESS"));
+        }
+
+        // Without strict validation any caller is accepted
+        drop(env);
+        let _env = EnvGuard::set(&[("ANDE_STRICT_VALIDATION", "false")]);
+        let config = EvolvePayloadBuilderConfig::new().ande_precompile_config(&genesis_chain_spec(None)).unwrap();
+        assert!(config.is_authorized(Address::with_last_byte(0x03)));
+    }
+
+    /// Chain spec of chain `chain_id` whose genesis config holds `andechain`, if any
+    fn chain_spec_of(chain_id: u64, andechain: Option<serde_json::Value>) -> Arc<ChainSpec> {
+        let mut chain_spec = genesis_chain_spec(andechain);
+        chain_spec.chain = chain_id.into();
+        Arc::new(chain_spec)
+    }
+
+    /// Whether the EVMs built for `chain_spec` serve the ANDE precompile
+    fn serves_ande_precompile(config: &EvolvePayloadBuilderConfig, chain_spec: Arc<ChainSpec>) -> bool {
+        let evm_config = config.evm_config_builder(chain_spec).unwrap().build().unwrap();
+        evm_config.evm_factory().precompile_provider().contains(&ANDE_PRECOMPILE_ADDRESS)
+    }
+
+    #[test]
+    fn test_ande_precompiles_switch() {
+        let _env = EnvGuard::set(&[]);
+        let dev = || {
+            Some(serde_json::json!({
+                "ande_token_address": Address::with_last_byte(0x01),
+                "ande_network_profile": "dev",
+            }))
+        };
+        let config = EvolvePayloadBuilderConfig::new();
+
+        // Enabled by default on Ande networks only
+        for chain_id in ANDE_CHAIN_IDS {
+            assert!(config.ande_precompiles_enabled(&chain_spec_of(chain_id, None)));
+            assert!(serves_ande_precompile(&config, chain_spec_of(chain_id, dev())));
+        }
+        assert!(!config.ande_precompiles_enabled(&chain_spec_of(1, None)));
+        // Other chains don't need an authorized caller, as nothing is served
+        assert!(!serves_ande_precompile(&config, chain_spec_of(1, None)));
+
+        let disabled = EvolvePayloadBuilderConfig { ande_precompiles_enabled: Some(false), ..config.clone() };
+        assert!(!serves_ande_precompile(&disabled, chain_spec_of(6174, dev())));
+        let enabled = EvolvePayloadBuilderConfig { ande_precompiles_enabled: Some(true), ..config };
+        assert!(serves_ande_precompile(&enabled, chain_spec_of(1, dev())));
+
+        // An enabled precompile still needs an authorized caller
+        assert!(matches!(
+            enabled.evm_config_builder(chain_spec_of(1, None)),
+            Err(ConfigError::NoAuthorizedAndeCaller)
+        ));
+
+        let parsed = EvolvePayloadBuilderConfig::from_toml_str("ande_precompiles_enabled = false\n").unwrap();
+        assert_eq!(parsed.ande_precompiles_enabled, Some(false));
+    }
 }
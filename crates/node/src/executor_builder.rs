@@ -1,39 +1,83 @@
 //! ANDE Executor Builder
 //!
-//! Custom executor builder that injects ANDE precompiles into the EVM.
-//! Based on reth's precompile-cache example.
+//! Executor builder injecting the ANDE precompiles into the EVM blocks are
+//! executed with. Whether they're served is decided at runtime by
+//! [`EvolvePayloadBuilderConfig::ande_precompiles_enabled`]; when disabled the
+//! EVM serves only the standard precompiles, like the stock Ethereum executor.
 
-use evolve_ev_reth::evm_config::AndeEvmFactory;
+use crate::config::EvolvePayloadBuilderConfig;
+use evolve_ev_reth::evm_config::{AndeEvmConfig, SharedValidatorSnapshot};
 use reth_chainspec::ChainSpec;
 use reth_ethereum::node::{
     api::{FullNodeTypes, NodeTypes},
     builder::{components::ExecutorBuilder, BuilderContext},
-    evm::EthEvmConfig,
 };
 use reth_ethereum_primitives::EthPrimitives;
-use revm::primitives::hardfork::SpecId;
+use reth_evm::ConfigureEvm;
+use tracing::{info, warn};
 
-/// Custom executor builder that uses ANDE EVM factory with custom precompiles
+/// Executor builder executing blocks with the ANDE precompiles when the
+/// configuration enables them on the chain
 #[derive(Debug, Clone, Default)]
-pub struct AndeExecutorBuilder;
+pub struct AndeExecutorBuilder {
+    /// EVM configuration to execute blocks with, e.g. from an
+    /// [`AndeEvmConfigBuilder`](evolve_ev_reth::evm_config::AndeEvmConfigBuilder),
+    /// taking precedence over `config`
+    evm_config: Option<AndeEvmConfig>,
+    /// Configuration the EVM is set up from
+    config: EvolvePayloadBuilderConfig,
+    /// Validator set served by the validator-set precompile
+    validator_snapshot: SharedValidatorSnapshot,
+}
+
+impl AndeExecutorBuilder {
+    /// Execute blocks with `evm_config`
+    pub fn new(evm_config: AndeEvmConfig) -> Self {
+        Self { evm_config: Some(evm_config), ..Self::default() }
+    }
+
+    /// Execute blocks with the EVM configured by `config`, serving the
+    /// validator set from `validator_snapshot`
+    pub fn from_config(config: EvolvePayloadBuilderConfig, validator_snapshot: SharedValidatorSnapshot) -> Self {
+        Self { evm_config: None, config, validator_snapshot }
+    }
+}
 
 impl<Node> ExecutorBuilder<Node> for AndeExecutorBuilder
 where
     Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
 {
-    type EVM = EthEvmConfig<ChainSpec, AndeEvmFactory>;
+    type EVM = AndeEvmConfig;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        // Create ANDE EVM factory with Cancun spec
-        // TODO: Get actual spec from chain_spec hardfork schedule
-        let ande_factory = AndeEvmFactory::new(SpecId::CANCUN);
-        
-        // Create EVM config with ANDE factory
-        let evm_config = EthEvmConfig::new_with_evm_factory(
-            ctx.chain_spec(),
-            ande_factory,
-        );
-        
+        if let Some(evm_config) = self.evm_config {
+            return Ok(evm_config);
+        }
+
+        // Refuses to start if the ANDE precompile would reject every caller
+        let chain_spec = ctx.chain_spec();
+        let enabled = self.config.ande_precompiles_enabled(&chain_spec);
+        let evm_config =
+            self.config.evm_config_builder(chain_spec.clone())?.validator_snapshot(self.validator_snapshot).build()?;
+
+        if enabled {
+            let precompile_config = evm_config.evm_factory().precompile_provider().config();
+            info!(
+                token = ?precompile_config.ande_token_address,
+                authorized = precompile_config.authorized_callers().len(),
+                strict = precompile_config.strict_validation,
+                "✅ ANDE Token Duality precompile enabled at 0x00...FD"
+            );
+            if !precompile_config.strict_validation {
+                warn!(
+                    "ANDE precompile strict validation is disabled: any caller can move any account's balance. \
+                     Only run this configuration on a throwaway local network"
+                );
+            }
+            info!("✅ Consensus validator-set precompile enabled at 0x00...FC");
+        } else {
+            info!(chain_id = chain_spec.chain.id(), "ANDE precompiles disabled, serving the standard precompiles only");
+        }
         Ok(evm_config)
     }
 }
@@ -41,10 +85,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
 
     #[test]
     fn test_executor_builder_creation() {
-        let _builder = AndeExecutorBuilder;
-        // Builder created successfully
+        let builder = AndeExecutorBuilder::default();
+        assert!(builder.evm_config.is_none());
+
+        let chain_spec = std::sync::Arc::new(ChainSpecBuilder::from(&*MAINNET).cancun_activated().build());
+        let evm_config =
+            EvolvePayloadBuilderConfig::new().evm_config_builder(chain_spec).unwrap().build().unwrap();
+        let builder = AndeExecutorBuilder::new(evm_config);
+        assert!(builder.evm_config.is_some());
     }
 }
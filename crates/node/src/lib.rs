@@ -9,13 +9,26 @@
 pub mod builder;
 /// Configuration types and validation for the Evolve payload builder
 pub mod config;
-/// Executor builder with ANDE precompiles (experimental)
-#[cfg(feature = "experimental")]
+/// Hot reload of the payload builder configuration
+pub mod reload;
+/// Replay of historical blocks through the sequential and parallel paths
+pub mod replay;
+/// Executor builder with ANDE precompiles
 pub mod executor_builder;
 
 // Re-export public types
-pub use builder::{create_payload_builder_service, EvolvePayloadBuilder};
-pub use config::{ConfigError, EvolvePayloadBuilderConfig};
-
-#[cfg(feature = "experimental")]
+pub use builder::{
+    create_payload_builder_service, create_payload_builder_service_from_builder,
+    create_payload_builder_service_with_parallel, BlockFinality,
+    EvolveBuildOutcome, EvolveBuiltPayload, EvolvePayloadBuilder, FinalizationTimeout, NotDesignatedProducer,
+    ParallelStateDivergence, ParentMismatch, PayloadExecutionStats, PriorityTransactionsExcluded, ProducerSchedule,
+    RejectedTx, RejectionReason, ValidationMismatch, ValidationReport, DEFAULT_FINALITY_POLL_INTERVAL,
+};
+pub use config::{
+    AndechainGenesisConfig, ConfigError, EvolvePayloadBuilderConfig, ParallelPreset, ParallelTuning, ANDE_CHAIN_IDS,
+};
+pub use reload::{
+    ConfigReloadError, ConfigSwitches, ConfigWatcher, SharedPayloadBuilderConfig, DEFAULT_CONFIG_POLL_INTERVAL,
+};
+pub use replay::{replay_block, AccountDivergence, ByMode, ReplayError, ReplayReport, TransactionDivergence};
 pub use executor_builder::AndeExecutorBuilder;
@@ -0,0 +1,376 @@
+//! Hot reload of the payload builder configuration
+//!
+//! [`ConfigWatcher`] re-reads the payload builder config file when its
+//! modification time changes, or when asked to through `ande_reloadConfig`.
+//! A valid file is published through a [`SharedPayloadBuilderConfig`], read
+//! by the payload builder at the start of each block, and handed to the
+//! registered listeners, e.g. the txpool RPC.
+//!
+//! Files that fail validation, or that change the chain configuration, a
+//! contract address, the ANDE precompiles switch or the parallel executor
+//! settings, are rejected with a warning and the current configuration stays
+//! in place.
+//!
+//! The runtime switches of the admin RPC are applied on top of the file, and
+//! kept across its reloads. Nodes running without a config file switch the
+//! configuration they started with through [`ConfigSwitches`].
+
+use crate::config::{ConfigError, EvolvePayloadBuilderConfig};
+use evolve_ev_reth::rpc::{ConfigReloadSource, RuntimeSettings, RuntimeSwitch, RuntimeSwitchSource};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::{Duration, SystemTime},
+};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Payload builder configuration shared between the watcher and its readers
+pub type SharedPayloadBuilderConfig = Arc<RwLock<EvolvePayloadBuilderConfig>>;
+
+/// Default interval at which the config file is checked for changes
+pub const DEFAULT_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Errors returned when the config file can't be reloaded
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigReloadError {
+    /// The file can't be read or fails validation
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    /// The file changes a setting fixed for the lifetime of the node
+    #[error("{0} can't change while the node runs")]
+    ImmutableField(&'static str),
+}
+
+/// Callback run with each configuration applied
+type ReloadListener = Box<dyn Fn(&EvolvePayloadBuilderConfig) + Send + Sync>;
+
+/// Watcher applying changes of the payload builder config file
+pub struct ConfigWatcher {
+    /// Config file
+    path: PathBuf,
+    /// Configuration currently applied
+    shared: SharedPayloadBuilderConfig,
+    /// Modification time of the file when last read
+    last_modified: Mutex<Option<SystemTime>>,
+    /// Callbacks run with each configuration applied
+    listeners: Mutex<Vec<ReloadListener>>,
+}
+
+impl fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.path)
+            .field("shared", &self.shared)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigWatcher {
+    /// Watch the file at `path`, from which `config` was loaded
+    pub fn new(path: impl Into<PathBuf>, config: EvolvePayloadBuilderConfig) -> Self {
+        let path = path.into();
+        Self {
+            last_modified: Mutex::new(modified(&path)),
+            path,
+            shared: Arc::new(RwLock::new(config)),
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Configuration currently applied, updated on each reload
+    pub fn shared(&self) -> SharedPayloadBuilderConfig {
+        Arc::clone(&self.shared)
+    }
+
+    /// Run `listener` with each configuration applied from now on
+    pub fn on_reload(&self, listener: impl Fn(&EvolvePayloadBuilderConfig) + Send + Sync + 'static) {
+        self.listeners.lock().unwrap_or_else(PoisonError::into_inner).push(Box::new(listener));
+    }
+
+    /// Re-read the config file and apply it, returning whether anything changed
+    ///
+    /// A rejected file is logged and leaves the current configuration in place.
+    pub fn reload(&self) -> Result<bool, ConfigReloadError> {
+        self.try_reload().inspect_err(|err| {
+            warn!(path = %self.path.display(), "Payload builder config not reloaded: {err}");
+        })
+    }
+
+    /// Reload the config file if it was modified since it was last read,
+    /// returning whether the configuration changed
+    pub fn poll(&self) -> bool {
+        let modified = modified(&self.path);
+        {
+            let mut last_modified = self.last_modified.lock().unwrap_or_else(PoisonError::into_inner);
+            if *last_modified == modified {
+                return false;
+            }
+            // A rejected file isn't read again until it's modified
+            *last_modified = modified;
+        }
+        self.reload().unwrap_or(false)
+    }
+
+    /// Poll the config file every `interval`
+    pub fn start_polling_task(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        info!("Watching payload builder config {} (every {:?})", self.path.display(), interval);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                self.poll();
+            }
+        })
+    }
+
+    fn try_reload(&self) -> Result<bool, ConfigReloadError> {
+        let mut config = EvolvePayloadBuilderConfig::load(Some(&self.path))?;
+
+        let mut current = self.shared.write().unwrap_or_else(PoisonError::into_inner);
+        config.switches = current.switches;
+        if let Some(field) = immutable_change(&current, &config) {
+            return Err(ConfigReloadError::ImmutableField(field));
+        }
+        if *current == config {
+            return Ok(false);
+        }
+        *current = config.clone();
+        drop(current);
+
+        info!(path = %self.path.display(), "Payload builder config reloaded");
+        for listener in self.listeners.lock().unwrap_or_else(PoisonError::into_inner).iter() {
+            listener(&config);
+        }
+        Ok(true)
+    }
+}
+
+impl ConfigReloadSource for ConfigWatcher {
+    fn reload_config(&self) -> eyre::Result<bool> {
+        Ok(self.reload()?)
+    }
+}
+
+impl RuntimeSwitchSource for ConfigWatcher {
+    fn switch(&self, switch: RuntimeSwitch) -> RuntimeSettings {
+        apply_switch(&self.shared, switch)
+    }
+}
+
+/// Runtime switches of a configuration that isn't loaded from a watched file
+#[derive(Debug, Clone)]
+pub struct ConfigSwitches {
+    /// Configuration the switches are applied to
+    shared: SharedPayloadBuilderConfig,
+}
+
+impl ConfigSwitches {
+    /// Apply the runtime switches to `shared`
+    pub fn new(shared: SharedPayloadBuilderConfig) -> Self {
+        Self { shared }
+    }
+}
+
+impl RuntimeSwitchSource for ConfigSwitches {
+    fn switch(&self, switch: RuntimeSwitch) -> RuntimeSettings {
+        apply_switch(&self.shared, switch)
+    }
+}
+
+/// Apply `switch` to `shared`, returning the settings then in effect
+fn apply_switch(shared: &SharedPayloadBuilderConfig, switch: RuntimeSwitch) -> RuntimeSettings {
+    let mut current = shared.write().unwrap_or_else(PoisonError::into_inner);
+    current.switches.apply(switch);
+    let settings = current.runtime_settings();
+    drop(current);
+
+    info!(?switch, ?settings, "Payload builder runtime switch applied");
+    settings
+}
+
+/// Modification time of the file at `path`, `None` if it can't be read
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// First setting fixed for the lifetime of the node that differs between
+/// `current` and `new`, if any
+fn immutable_change(current: &EvolvePayloadBuilderConfig, new: &EvolvePayloadBuilderConfig) -> Option<&'static str> {
+    if current.andechain != new.andechain {
+        return Some("The [andechain] section");
+    }
+    if current.ande_precompiles_enabled != new.ande_precompiles_enabled {
+        return Some("The ANDE precompiles switch");
+    }
+    let auction = |config: &EvolvePayloadBuilderConfig| config.mev.as_ref().and_then(|mev| mev.auction_address);
+    if auction(current) != auction(new) {
+        return Some("The MEV auction address");
+    }
+    let distributor = |config: &EvolvePayloadBuilderConfig| config.mev.as_ref().and_then(|mev| mev.distributor_address);
+    if distributor(current) != distributor(new) {
+        return Some("The MEV distributor address");
+    }
+    let executor = |config: &EvolvePayloadBuilderConfig| {
+        let parallel = &config.parallel;
+        (
+            parallel.enabled,
+            parallel.preset,
+            parallel.concurrency_level,
+            parallel.max_retries,
+            parallel.adaptive,
+            parallel.execution_timeout,
+            parallel.trace_dir.clone(),
+        )
+    };
+    if executor(current) != executor(new) {
+        return Some("The parallel executor setup");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evolve_ev_reth::rpc::MevComponent;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    const CONFIG: &str = r#"
+[andechain]
+name = "AndeChain"
+
+[mev]
+enable_detection = false
+enable_auction = false
+distributor_address = "0x0000000000000000000000000000000000000002"
+
+[txpool]
+max_txpool_bytes = 1000
+"#;
+
+    fn watcher() -> (tempfile::TempDir, ConfigWatcher) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payload-builder.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let config = EvolvePayloadBuilderConfig::load(Some(&path)).unwrap();
+        (dir, ConfigWatcher::new(path, config))
+    }
+
+    fn current(watcher: &ConfigWatcher) -> EvolvePayloadBuilderConfig {
+        watcher.shared().read().unwrap().clone()
+    }
+
+    #[test]
+    fn test_tunable_settings_are_applied() {
+        let (_dir, watcher) = watcher();
+        let max_bytes = Arc::new(AtomicU64::new(1000));
+        let handle = Arc::clone(&max_bytes);
+        watcher.on_reload(move |config| handle.store(config.txpool.max_txpool_bytes, Ordering::Relaxed));
+
+        // Nothing to apply until the file changes
+        assert!(!watcher.reload().unwrap());
+
+        let changed = CONFIG
+            .replace("enable_detection = false", "enable_detection = true")
+            .replace("max_txpool_bytes = 1000", "max_txpool_bytes = 2000")
+            + "\n[parallel]\nforce_sequential = true\n";
+        fs::write(&watcher.path, changed).unwrap();
+        assert!(watcher.reload().unwrap());
+
+        let config = current(&watcher);
+        assert!(config.mev.unwrap().enable_detection);
+        assert!(config.parallel.force_sequential);
+        assert_eq!(config.txpool.max_txpool_bytes, 2000);
+        assert_eq!(max_bytes.load(Ordering::Relaxed), 2000);
+    }
+
+    #[test]
+    fn test_runtime_switches_survive_reloads() {
+        let (_dir, watcher) = watcher();
+        assert!(!watcher.switch(RuntimeSwitch::ParallelEnabled(false)).parallel_enabled);
+        let settings = watcher.switch(RuntimeSwitch::ForceSequential(true));
+        assert!(settings.force_sequential);
+        let settings = watcher.switch(RuntimeSwitch::MevEnabled(MevComponent::Auction, false));
+        assert!(!settings.mev_auction);
+        assert!(settings.force_sequential);
+
+        let changed = CONFIG.replace("enable_detection = false", "enable_detection = true")
+            + "\n[parallel]\nforce_sequential = false\n";
+        fs::write(&watcher.path, changed).unwrap();
+        assert!(watcher.reload().unwrap());
+        let settings = current(&watcher).runtime_settings();
+        assert!(!settings.parallel_enabled);
+        // The switch takes precedence over the file
+        assert!(settings.force_sequential);
+        assert!(settings.mev_detection);
+        assert!(!settings.mev_auction);
+
+        // Switching detection off leaves the file's setting in place
+        let settings = watcher.switch(RuntimeSwitch::MevEnabled(MevComponent::Detection, false));
+        assert!(!settings.mev_detection);
+        assert!(current(&watcher).mev.unwrap().enable_detection);
+        // Nothing changed in the file
+        assert!(!watcher.reload().unwrap());
+    }
+
+    #[test]
+    fn test_runtime_switches_without_config_file() {
+        let shared = Arc::new(RwLock::new(EvolvePayloadBuilderConfig::default()));
+        let switches = ConfigSwitches::new(Arc::clone(&shared));
+
+        assert!(!switches.switch(RuntimeSwitch::ParallelEnabled(false)).parallel_enabled);
+        let settings = switches.switch(RuntimeSwitch::ForceSequential(true));
+        assert!(settings.force_sequential);
+        assert_eq!(shared.read().unwrap().runtime_settings(), settings);
+    }
+
+    #[test]
+    fn test_immutable_settings_are_rejected() {
+        let (_dir, watcher) = watcher();
+        let before = current(&watcher);
+
+        fs::write(&watcher.path, CONFIG.replace("AndeChain", "OtherChain")).unwrap();
+        assert!(matches!(watcher.reload(), Err(ConfigReloadError::ImmutableField("The [andechain] section"))));
+
+        let moved = CONFIG.replace("0x0000000000000000000000000000000000000002", "0x0000000000000000000000000000000000000003");
+        fs::write(&watcher.path, moved.replace("max_txpool_bytes = 1000", "max_txpool_bytes = 2000")).unwrap();
+        assert!(matches!(watcher.reload(), Err(ConfigReloadError::ImmutableField("The MEV distributor address"))));
+
+        fs::write(&watcher.path, format!("{CONFIG}\n[parallel]\nenabled = true\n")).unwrap();
+        assert!(matches!(watcher.reload(), Err(ConfigReloadError::ImmutableField("The parallel executor setup"))));
+
+        // Tunable changes in the same file aren't applied either
+        assert_eq!(current(&watcher), before);
+    }
+
+    #[test]
+    fn test_invalid_files_are_rejected() {
+        let (_dir, watcher) = watcher();
+        let before = current(&watcher);
+
+        fs::write(&watcher.path, CONFIG.replace("max_txpool_bytes = 1000", "max_txpool_bytes = \"many\"")).unwrap();
+        assert!(matches!(watcher.reload(), Err(ConfigReloadError::Config(ConfigError::Toml(_)))));
+
+        // Parses, but fails validation
+        fs::write(&watcher.path, format!("{CONFIG}\n[payload_validation]\nmin_gas_limit = 40000000\n")).unwrap();
+        assert!(matches!(
+            watcher.reload(),
+            Err(ConfigReloadError::Config(ConfigError::InvalidGasLimitBounds { .. }))
+        ));
+
+        assert_eq!(current(&watcher), before);
+    }
+
+    #[test]
+    fn test_poll_reads_modified_files_once() {
+        let (_dir, watcher) = watcher();
+        assert!(!watcher.poll());
+
+        fs::write(&watcher.path, CONFIG.replace("max_txpool_bytes = 1000", "max_txpool_bytes = 2000")).unwrap();
+        let file = fs::File::options().write(true).open(&watcher.path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+        assert!(watcher.poll());
+        assert_eq!(current(&watcher).txpool.max_txpool_bytes, 2000);
+        assert!(!watcher.poll());
+    }
+}
@@ -0,0 +1,382 @@
+//! Deterministic replay of historical blocks
+//!
+//! [`replay_block`] executes a block of the chain on the state of its parent
+//! twice, once sequentially and once on the parallel path, and reports where
+//! the two executions disagree. Both start from the same pre-execution system
+//! calls and run the ANDE EVM blocks are built with, so any difference in the
+//! report comes from how the parallel executor scheduled the transactions.
+
+use crate::builder::{block_env_attributes, pre_execution_changes};
+use alloy_primitives::{Address, Bytes, TxHash, B256, I256, U256};
+use evolve_ev_reth::{
+    evm_config::AndeEvmConfig,
+    parallel::{
+        AccountDiff, AccountStateChange, FailurePolicy, ParallelConfig as EvolveParallelConfig,
+        ParallelExecutionMetrics, ParallelExecutionOutput, ParallelExecutor, ParallelPayloadError,
+    },
+};
+use reth_ethereum_primitives::Block;
+use reth_evm::NextBlockEnvAttributes;
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_primitives::{Header, SealedBlock, SealedHeader, TransactionSigned};
+use reth_provider::{BlockReader, HeaderProvider, ProviderError, StateProviderBox, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{info, warn};
+
+/// Errors returned by [`replay_block`]
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// The block or its parent state couldn't be read
+    #[error("Provider error: {0}")]
+    Provider(#[from] ProviderError),
+    /// The chain has no block with this number
+    #[error("Block {0} not found")]
+    BlockNotFound(u64),
+    /// The parent of the block isn't known
+    #[error("Parent {parent_hash} of block {block_number} not found")]
+    ParentNotFound {
+        /// Number of the replayed block
+        block_number: u64,
+        /// Hash of its parent
+        parent_hash: B256,
+    },
+    /// The pre-execution system calls of the block failed
+    #[error("Pre-execution changes failed: {0}")]
+    PreExecution(#[source] PayloadBuilderError),
+    /// One of the executions failed as a whole
+    #[error("{mode} execution failed: {source}")]
+    Execution {
+        /// Execution that failed, `Sequential` or `Parallel`
+        mode: &'static str,
+        /// Why it failed
+        #[source]
+        source: ParallelPayloadError,
+    },
+}
+
+/// Value observed by the sequential and by the parallel execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByMode<T> {
+    /// Value of the sequential execution
+    pub sequential: T,
+    /// Value of the parallel execution
+    pub parallel: T,
+}
+
+impl<T: PartialEq> ByMode<T> {
+    /// Whether the two executions disagree
+    pub fn differs(&self) -> bool {
+        self.sequential != self.parallel
+    }
+
+    /// `Some` if the two executions disagree
+    fn divergence(sequential: T, parallel: T) -> Option<Self> {
+        let values = Self { sequential, parallel };
+        values.differs().then_some(values)
+    }
+}
+
+/// Transaction whose outcome differs between the two executions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionDivergence {
+    /// Position of the transaction in the block
+    pub index: usize,
+    /// Hash of the transaction
+    pub hash: TxHash,
+    /// Whether the transaction succeeded
+    pub success: ByMode<bool>,
+    /// Gas used by the transaction
+    pub gas_used: ByMode<u64>,
+}
+
+/// Net change of an account that differs between the two executions; only
+/// the parts that differ are set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDivergence {
+    /// Net balance change
+    pub balance_change: Option<ByMode<I256>>,
+    /// Number of nonce increments
+    pub nonce_change: Option<ByMode<u64>>,
+    /// Final value of the storage slots written differently, `None` where an
+    /// execution didn't write the slot
+    pub storage: BTreeMap<U256, ByMode<Option<U256>>>,
+    /// Code the account was created with
+    pub code_change: Option<ByMode<Option<Bytes>>>,
+    /// Whether the account was destroyed
+    pub destroyed: Option<ByMode<bool>>,
+}
+
+/// Outcome of [`replay_block`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Number of the replayed block
+    pub block_number: u64,
+    /// Hash of the replayed block
+    pub block_hash: B256,
+    /// Number of transactions in the block
+    pub transactions: usize,
+    /// Gas used according to the block header
+    pub header_gas_used: u64,
+    /// Gas used by the transactions of each execution
+    pub gas_used: ByMode<u64>,
+    /// Transactions each execution left out to stay within the block gas limit
+    pub excluded: ByMode<Vec<usize>>,
+    /// Transactions whose outcome differs, in block order
+    pub transaction_divergences: Vec<TransactionDivergence>,
+    /// Accounts whose net change differs
+    pub account_divergences: BTreeMap<Address, AccountDivergence>,
+    /// Statistics of the parallel execution, when monitoring is enabled
+    pub parallel_metrics: Option<ParallelExecutionMetrics>,
+}
+
+impl ReplayReport {
+    /// Whether both executions produced the same outcome
+    pub fn is_consistent(&self) -> bool {
+        !self.gas_used.differs()
+            && !self.excluded.differs()
+            && self.transaction_divergences.is_empty()
+            && self.account_divergences.is_empty()
+    }
+}
+
+/// Execute block `block_number` of `provider` sequentially and in parallel,
+/// on the state of its parent, and report the differences between the two
+///
+/// The parallel execution uses the executor settings of `config`, forced onto
+/// the parallel path whatever the block size; traces are recorded when
+/// `config.trace_dir` is set. Transactions that fail are kept in both
+/// executions so their outcomes can be compared.
+pub async fn replay_block<Provider>(
+    provider: &Provider,
+    evm_config: &AndeEvmConfig,
+    block_number: u64,
+    config: EvolveParallelConfig,
+) -> Result<ReplayReport, ReplayError>
+where
+    Provider: BlockReader<Block = Block> + StateProviderFactory + HeaderProvider<Header = Header>,
+{
+    let block = provider.block_by_number(block_number)?.ok_or(ReplayError::BlockNotFound(block_number))?;
+    let block = SealedBlock::seal_slow(block);
+    let parent_header = provider
+        .header(&block.parent_hash)?
+        .ok_or(ReplayError::ParentNotFound { block_number, parent_hash: block.parent_hash })?;
+    let sealed_parent = SealedHeader::new(parent_header, block.parent_hash);
+    let state_provider = provider.state_by_block_hash(block.parent_hash)?;
+
+    let next_block_attrs = block_env_attributes(&block);
+    let base_changes = pre_execution_changes(evm_config, &state_provider, &sealed_parent, next_block_attrs.clone())
+        .map_err(ReplayError::PreExecution)?;
+    let replay = Replay {
+        transactions: &block.body().transactions,
+        base_changes: &base_changes,
+        evm_config,
+        sealed_parent: &sealed_parent,
+        next_block_attrs: &next_block_attrs,
+        state_provider: &state_provider,
+    };
+
+    let config = EvolveParallelConfig { on_failure: FailurePolicy::Skip, ..config };
+    let sequential = replay
+        .execute(EvolveParallelConfig { force_sequential: true, trace_dir: None, ..config.clone() })
+        .await
+        .map_err(|source| ReplayError::Execution { mode: "Sequential", source })?;
+    let parallel = replay
+        .execute(EvolveParallelConfig {
+            force_sequential: false,
+            min_transactions_for_parallel: 1,
+            adaptive: false,
+            ..config
+        })
+        .await
+        .map_err(|source| ReplayError::Execution { mode: "Parallel", source })?;
+
+    let report = compare(&block, &sequential, parallel);
+    if report.is_consistent() {
+        info!(block_number, block_hash = ?report.block_hash, "Replayed block, both executions agree");
+    } else {
+        warn!(
+            block_number,
+            block_hash = ?report.block_hash,
+            transactions = report.transaction_divergences.len(),
+            accounts = report.account_divergences.len(),
+            "Replayed block, sequential and parallel executions differ"
+        );
+    }
+    Ok(report)
+}
+
+/// Inputs shared by both executions of a replayed block
+struct Replay<'a> {
+    transactions: &'a [TransactionSigned],
+    base_changes: &'a [AccountStateChange],
+    evm_config: &'a AndeEvmConfig,
+    sealed_parent: &'a SealedHeader,
+    next_block_attrs: &'a NextBlockEnvAttributes,
+    state_provider: &'a StateProviderBox,
+}
+
+impl Replay<'_> {
+    /// Execute the block with an executor set up from `config`
+    async fn execute(&self, config: EvolveParallelConfig) -> Result<ParallelExecutionOutput, ParallelPayloadError> {
+        ParallelExecutor::new(config)
+            .execute_transactions_with_base_changes(
+                self.transactions.to_vec(),
+                self.base_changes.to_vec(),
+                self.evm_config,
+                self.sealed_parent,
+                self.next_block_attrs.clone(),
+                &StateProviderDatabase::new(self.state_provider),
+                None,
+            )
+            .await
+    }
+}
+
+/// Differences between the `sequential` and `parallel` executions of `block`
+fn compare(
+    block: &SealedBlock,
+    sequential: &ParallelExecutionOutput,
+    parallel: ParallelExecutionOutput,
+) -> ReplayReport {
+    let transactions = &block.body().transactions;
+    let gas_used =
+        |output: &ParallelExecutionOutput| output.results.iter().map(|result| result.gas_used).sum::<u64>();
+
+    let transaction_divergences = sequential
+        .results
+        .iter()
+        .zip(&parallel.results)
+        .filter_map(|(sequential, parallel)| {
+            let success = ByMode { sequential: sequential.success, parallel: parallel.success };
+            let gas_used = ByMode { sequential: sequential.gas_used, parallel: parallel.gas_used };
+            (success.differs() || gas_used.differs()).then(|| TransactionDivergence {
+                index: sequential.tx_idx,
+                hash: transactions.get(sequential.tx_idx).map(|tx| *tx.hash()).unwrap_or_default(),
+                success,
+                gas_used,
+            })
+        })
+        .collect();
+
+    let (sequential_diff, parallel_diff) = (sequential.canonical_state_diff(), parallel.canonical_state_diff());
+    let addresses: BTreeSet<&Address> = sequential_diff.keys().chain(parallel_diff.keys()).collect();
+    let unchanged = AccountDiff::default();
+    let account_divergences = addresses
+        .into_iter()
+        .filter_map(|address| {
+            let sequential = sequential_diff.get(address).unwrap_or(&unchanged);
+            let parallel = parallel_diff.get(address).unwrap_or(&unchanged);
+            (sequential != parallel).then(|| (*address, account_divergence(sequential, parallel)))
+        })
+        .collect();
+
+    ReplayReport {
+        block_number: block.number,
+        block_hash: block.hash(),
+        transactions: transactions.len(),
+        header_gas_used: block.gas_used,
+        gas_used: ByMode { sequential: gas_used(sequential), parallel: gas_used(&parallel) },
+        excluded: ByMode { sequential: sequential.excluded.clone(), parallel: parallel.excluded },
+        transaction_divergences,
+        account_divergences,
+        parallel_metrics: parallel.metrics,
+    }
+}
+
+/// Parts of the net change of an account that differ between two executions
+fn account_divergence(sequential: &AccountDiff, parallel: &AccountDiff) -> AccountDivergence {
+    let slots: BTreeSet<&U256> = sequential.storage.keys().chain(parallel.storage.keys()).collect();
+    AccountDivergence {
+        balance_change: ByMode::divergence(sequential.balance_change, parallel.balance_change),
+        nonce_change: ByMode::divergence(sequential.nonce_change, parallel.nonce_change),
+        storage: slots
+            .into_iter()
+            .filter_map(|slot| {
+                ByMode::divergence(sequential.storage.get(slot).copied(), parallel.storage.get(slot).copied())
+                    .map(|values| (*slot, values))
+            })
+            .collect(),
+        code_change: ByMode::divergence(sequential.code_change.clone(), parallel.code_change.clone()),
+        destroyed: ByMode::divergence(sequential.destroyed, parallel.destroyed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evolve_ev_reth::parallel::ParallelExecutionResult;
+
+    fn result(tx_idx: usize, gas_used: u64, changes: &[AccountStateChange]) -> ParallelExecutionResult {
+        ParallelExecutionResult {
+            tx_idx,
+            gas_used,
+            success: true,
+            error: None,
+            state_changes: changes.iter().map(|change| (change.address, change.clone())).collect(),
+            read_set: Vec::new(),
+            write_set: Vec::new(),
+            incarnation: 0,
+            logs: Vec::new(),
+            rejection: None,
+        }
+    }
+
+    fn change(byte: u8, balance_change: i128, storage: &[(u64, u64)]) -> AccountStateChange {
+        AccountStateChange {
+            address: Address::repeat_byte(byte),
+            balance_change: Some(I256::try_from(balance_change).unwrap()),
+            nonce_change: None,
+            storage_changes: storage.iter().map(|(slot, value)| (U256::from(*slot), U256::from(*value))).collect(),
+            code_change: None,
+            destroyed: false,
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_every_difference() {
+        let block = SealedBlock::seal_slow(Block::new(Header { number: 7, ..Default::default() }, Default::default()));
+        let sequential = ParallelExecutionOutput {
+            results: vec![
+                result(0, 21_000, &[change(1, -10, &[]), change(2, 10, &[])]),
+                result(1, 40_000, &[change(3, 0, &[(1, 5), (2, 6)])]),
+            ],
+            ..Default::default()
+        };
+        let parallel = ParallelExecutionOutput {
+            results: vec![
+                result(0, 21_000, &[change(1, -10, &[]), change(2, 10, &[])]),
+                result(1, 45_000, &[change(3, 0, &[(1, 5), (3, 7)]), change(4, 1, &[])]),
+            ],
+            ..Default::default()
+        };
+
+        let report = compare(&block, &sequential, parallel);
+        assert_eq!((report.block_number, report.block_hash), (7, block.hash()));
+        assert!(!report.is_consistent());
+        assert_eq!(report.gas_used, ByMode { sequential: 61_000, parallel: 66_000 });
+        assert_eq!(report.transaction_divergences.len(), 1);
+        assert_eq!(report.transaction_divergences[0].index, 1);
+        assert_eq!(report.transaction_divergences[0].gas_used, ByMode { sequential: 40_000, parallel: 45_000 });
+
+        // Accounts changed alike are left out, and only the differing parts are set
+        assert_eq!(report.account_divergences.keys().copied().collect::<Vec<_>>(), [3, 4].map(Address::repeat_byte));
+        let storage = &report.account_divergences[&Address::repeat_byte(3)];
+        assert_eq!(storage.balance_change, None);
+        assert_eq!(
+            storage.storage,
+            BTreeMap::from([
+                (U256::from(2), ByMode { sequential: Some(U256::from(6)), parallel: None }),
+                (U256::from(3), ByMode { sequential: None, parallel: Some(U256::from(7)) }),
+            ])
+        );
+        let created = &report.account_divergences[&Address::repeat_byte(4)];
+        assert_eq!(created.balance_change, Some(ByMode { sequential: I256::ZERO, parallel: I256::ONE }));
+
+        let same = ParallelExecutionOutput { results: sequential.results.clone(), ..Default::default() };
+        let report = compare(&block, &sequential, same);
+        assert!(report.is_consistent());
+        assert_eq!(report.transaction_divergences, Vec::new());
+        assert_eq!(report.account_divergences, BTreeMap::new());
+    }
+}
@@ -5,18 +5,21 @@
 
 use std::sync::Arc;
 
-use alloy_consensus::{transaction::SignerRecoverable, TxLegacy, TypedTransaction};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+use alloy_consensus::{transaction::SignerRecoverable, SignableTransaction, TxLegacy, TypedTransaction};
 use alloy_primitives::{Address, Bytes, ChainId, Signature, TxKind, B256, U256};
 use eyre::Result;
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_ethereum_primitives::TransactionSigned;
-use reth_evm_ethereum::EthEvmConfig;
 use reth_primitives::{Header, Transaction};
 use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 use tempfile::TempDir;
 
 use ev_node::{EvolvePayloadBuilder, EvolvePayloadBuilderConfig};
-use evolve_ev_reth::EvolvePayloadAttributes;
+use evolve_ev_reth::{
+    evm_config::{create_ande_evm_config, AndePrecompileConfig},
+    EvolvePayloadAttributes,
+};
 
 // Test constants
 /// Test chain ID used in tests
@@ -30,6 +33,8 @@ pub const GENESIS_STATEROOT: &str =
 pub const TEST_TO_ADDRESS: &str = "0x944fDcD1c868E3cC566C78023CcB38A32cDA836E";
 /// Test timestamp for blocks
 pub const TEST_TIMESTAMP: u64 = 1710338135;
+/// Timestamp of the genesis block, one 12 second slot before [`TEST_TIMESTAMP`]
+pub const TEST_GENESIS_TIMESTAMP: u64 = TEST_TIMESTAMP - 12;
 /// Test gas limit for blocks
 pub const TEST_GAS_LIMIT: u64 = 30_000_000;
 /// Base fee used in mock headers to satisfy post-London/EIP-4844 requirements
@@ -65,7 +70,7 @@ impl EvolveTestFixture {
             state_root: genesis_state_root,
             number: 0,
             gas_limit: TEST_GAS_LIMIT,
-            timestamp: TEST_TIMESTAMP,
+            timestamp: TEST_GENESIS_TIMESTAMP,
             base_fee_per_gas: Some(TEST_BASE_FEE),
             excess_blob_gas: Some(0),
             blob_gas_used: Some(0),
@@ -80,7 +85,8 @@ impl EvolveTestFixture {
             .chain(reth_chainspec::Chain::from_id(TEST_CHAIN_ID))
             .cancun_activated()
             .build();
-        let evm_config = EthEvmConfig::new(Arc::new(test_chainspec));
+        let evm_config =
+            create_ande_evm_config(Arc::new(test_chainspec), AndePrecompileConfig::insecure_unrestricted());
 
         // Create default config for the payload builder
         let config = EvolvePayloadBuilderConfig::new();
@@ -165,29 +171,92 @@ impl EvolveTestFixture {
 
 /// Creates test transactions with specified count and starting nonce
 pub fn create_test_transactions(count: usize, nonce_start: u64) -> Vec<TransactionSigned> {
-    let mut transactions = Vec::with_capacity(count);
-    let to_address = Address::from_slice(&hex::decode(&TEST_TO_ADDRESS[2..]).unwrap());
-
-    for i in 0..count {
-        let nonce = nonce_start + i as u64;
-
-        let legacy_tx = TxLegacy {
-            chain_id: Some(ChainId::from(TEST_CHAIN_ID)),
-            nonce,
-            gas_price: 0, // Zero gas price for testing
-            gas_limit: 21_000,
-            to: TxKind::Call(to_address),
-            value: U256::from(0), // No value transfer
-            input: Bytes::default(),
-        };
+    (0..count)
+        .map(|i| create_transfer_transaction(nonce_start + i as u64, U256::ZERO))
+        .collect()
+}
+
+/// Creates a test transaction sending `value` wei to [`TEST_TO_ADDRESS`]
+pub fn create_transfer_transaction(nonce: u64, value: U256) -> TransactionSigned {
+    create_legacy_transaction(nonce, test_to_address(), 21_000, 0, value)
+}
+
+/// Creates a test transaction paying `gas_price` wei per gas
+pub fn create_priced_transaction(nonce: u64, gas_price: u128) -> TransactionSigned {
+    create_legacy_transaction(nonce, test_to_address(), 21_000, gas_price, U256::ZERO)
+}
+
+/// Creates a test transaction calling the contract at `to` with `gas_limit`
+pub fn create_call_transaction(nonce: u64, to: Address, gas_limit: u64) -> TransactionSigned {
+    create_legacy_transaction(nonce, to, gas_limit, 0, U256::ZERO)
+}
+
+/// [`TEST_TO_ADDRESS`] as an address
+fn test_to_address() -> Address {
+    Address::from_slice(&hex::decode(&TEST_TO_ADDRESS[2..]).unwrap())
+}
 
-        let typed_tx = TypedTransaction::Legacy(legacy_tx);
-        let transaction = Transaction::from(typed_tx);
-        let signed_tx = TransactionSigned::new_unhashed(transaction, Signature::test_signature());
-        transactions.push(signed_tx);
+/// Creates a zero-priced call to `to` signed by `signer`
+pub fn create_signed_call_transaction(signer: &PrivateKeySigner, nonce: u64, to: Address) -> TransactionSigned {
+    create_signed_input_call_transaction(signer, nonce, to, 21_000, Bytes::default())
+}
+
+/// Creates a zero-priced call to `to` with `input` and `gas_limit`, signed by `signer`
+pub fn create_signed_input_call_transaction(
+    signer: &PrivateKeySigner,
+    nonce: u64,
+    to: Address,
+    gas_limit: u64,
+    input: Bytes,
+) -> TransactionSigned {
+    let legacy_tx = TxLegacy { input, ..legacy_transaction(nonce, to, gas_limit, 0, U256::ZERO) };
+    let signature = signer.sign_hash_sync(&legacy_tx.signature_hash()).unwrap();
+    TransactionSigned::new_unhashed(Transaction::Legacy(legacy_tx), signature)
+}
+
+/// Creates a zero-priced transfer of `value` wei to `to`, signed by `signer`
+pub fn create_signed_transfer_transaction(
+    signer: &PrivateKeySigner,
+    nonce: u64,
+    to: Address,
+    value: U256,
+) -> TransactionSigned {
+    let legacy_tx = legacy_transaction(nonce, to, 21_000, 0, value);
+    let signature = signer.sign_hash_sync(&legacy_tx.signature_hash()).unwrap();
+    TransactionSigned::new_unhashed(Transaction::Legacy(legacy_tx), signature)
+}
+
+/// Creates a test transaction whose signer can't be recovered
+pub fn create_unrecoverable_transaction(nonce: u64) -> TransactionSigned {
+    let legacy_tx = legacy_transaction(nonce, test_to_address(), 21_000, 0, U256::ZERO);
+    TransactionSigned::new_unhashed(Transaction::Legacy(legacy_tx), Signature::new(U256::ZERO, U256::ZERO, false))
+}
+
+/// Legacy transaction on the test chain
+fn legacy_transaction(nonce: u64, to: Address, gas_limit: u64, gas_price: u128, value: U256) -> TxLegacy {
+    TxLegacy {
+        chain_id: Some(ChainId::from(TEST_CHAIN_ID)),
+        nonce,
+        gas_price,
+        gas_limit,
+        to: TxKind::Call(to),
+        value,
+        input: Bytes::default(),
     }
+}
 
-    transactions
+/// Creates a legacy transaction signed with the test signature
+fn create_legacy_transaction(
+    nonce: u64,
+    to: Address,
+    gas_limit: u64,
+    gas_price: u128,
+    value: U256,
+) -> TransactionSigned {
+    let legacy_tx = legacy_transaction(nonce, to, gas_limit, gas_price, value);
+    let typed_tx = TypedTransaction::Legacy(legacy_tx);
+    let transaction = Transaction::from(typed_tx);
+    TransactionSigned::new_unhashed(transaction, Signature::test_signature())
 }
 
 /// Creates a single test transaction with specified nonce
@@ -51,7 +51,7 @@ impl EngineApiTestFixture {
             Some(TEST_GAS_LIMIT),
         );
 
-        let sealed_block = self.base.builder.build_payload(payload_attrs).await?;
+        let sealed_block = self.base.builder.build_payload_block(payload_attrs).await?;
         // Debug output to help identify whether transactions are included and how gas is accounted
         println!(
             "Built block #{}, txs={}, gas_used={}, base_fee={:?}",
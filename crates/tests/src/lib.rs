@@ -10,8 +10,12 @@ mod engine_api_tests;
 #[cfg(test)]
 mod integration_tests;
 #[cfg(test)]
+mod mev_auction_tests;
+#[cfg(test)]
 mod payload_builder_tests;
 #[cfg(test)]
+mod replay_tests;
+#[cfg(test)]
 mod test_evolve_engine_api;
 
 // Re-export common test utilities
@@ -0,0 +1,195 @@
+//! Tests for placing MEV auction bundles in payloads built by the Evolve
+//! payload builder.
+
+use crate::common;
+
+use alloy_primitives::{Address, B256, U256};
+use eyre::Result;
+use ev_node::ConfigWatcher;
+use evolve_ev_reth::{
+    mev::{
+        detector::DetectorConfig, BundleSubmission, MevAuctionClient, MevDetector, MevDistributorClient, MevPipeline,
+    },
+    rpc::{MevComponent, RuntimeSwitch, RuntimeSwitchSource},
+};
+use std::sync::Arc;
+
+use common::{create_test_transactions, EvolveTestFixture, TEST_GAS_LIMIT, TEST_TIMESTAMP};
+
+/// Tests that the winning bundle is built first and settled once the block is built
+#[tokio::test]
+async fn test_winning_bundle_prepended_and_settled() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let auction = Arc::new(MevAuctionClient::new(Address::random(), Address::random()));
+
+    // The bundle takes the first two nonces of the test signer, the pool the next two
+    let transactions = create_test_transactions(4, 0);
+    let (bundle_txs, pool_txs) = transactions.split_at(2);
+    auction.bundle_transactions().insert(bundle_txs.to_vec());
+    let bundle = BundleSubmission {
+        bundle_hash: B256::random(),
+        bid_amount: U256::from(1000),
+        target_block: 1,
+        transactions: bundle_txs.iter().map(|tx| *tx.hash()).collect(),
+        searcher: Address::random(),
+        encrypted_payload: None,
+    };
+    auction.submit_bundle(bundle.clone()).await?;
+
+    // A higher bid whose transactions were never registered loses the slot
+    let unresolvable = BundleSubmission {
+        bundle_hash: B256::random(),
+        bid_amount: U256::from(5000),
+        transactions: vec![B256::random()],
+        ..bundle.clone()
+    };
+    auction.submit_bundle(unresolvable).await?;
+
+    let payload_attrs = fixture.create_payload_attributes(
+        pool_txs.to_vec(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+    let builder = fixture.builder.with_mev_auction(auction.clone());
+    let block = builder.build_payload_block(payload_attrs).await?;
+
+    let included: Vec<B256> = block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    let expected: Vec<B256> = transactions.iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, expected, "Bundle transactions should precede the pool transactions");
+
+    let stats = auction.get_auction_stats().await;
+    assert_eq!(stats.pending_bundles, 0);
+    assert_eq!(stats.executed_bundles, 1);
+    assert_eq!(stats.rejected_bundles, 1);
+    assert_eq!(stats.total_bids_paid, bundle.bid_amount);
+    assert!(auction.bundle_transactions().is_empty());
+
+    println!("✓ Winning bundle test passed");
+    Ok(())
+}
+
+/// Tests that the bid of the bundle executed in a block is credited to the distributor
+#[tokio::test]
+async fn test_executed_bundle_credited_to_distributor() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let auction = Arc::new(MevAuctionClient::new(Address::random(), Address::random()));
+    let distributor = Arc::new(MevDistributorClient::default_config(Address::random(), Address::random()));
+    let pipeline = Arc::new(
+        MevPipeline::new(MevDetector::new(DetectorConfig::default()), distributor.clone())
+            .with_auction(auction.clone()),
+    );
+
+    let bundle_txs = create_test_transactions(1, 0);
+    auction.bundle_transactions().insert(bundle_txs.clone());
+    let bid = U256::from(3_000);
+    auction
+        .submit_bundle(BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: bid,
+            target_block: 1,
+            transactions: vec![*bundle_txs[0].hash()],
+            searcher: Address::random(),
+            encrypted_payload: None,
+        })
+        .await?;
+
+    let payload_attrs = fixture.create_payload_attributes(
+        create_test_transactions(2, 1),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+    let builder = fixture.builder.with_mev_auction(auction.clone()).with_mev_pipeline(pipeline);
+    let block = builder.build_payload_block(payload_attrs).await?;
+
+    assert_eq!(block.transaction_count(), 3);
+    assert_eq!(auction.get_auction_stats().await.executed_bundles, 1);
+    assert_eq!(distributor.get_buffer_amount().await, bid);
+
+    println!("✓ Executed bundle credit test passed");
+    Ok(())
+}
+
+/// Tests that no bundle is placed nor credited while the auction and distribution are switched off
+#[tokio::test]
+async fn test_switched_off_auction_leaves_the_payload_untouched() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let auction = Arc::new(MevAuctionClient::new(Address::random(), Address::random()));
+    let distributor = Arc::new(MevDistributorClient::default_config(Address::random(), Address::random()));
+    let pipeline = Arc::new(
+        MevPipeline::new(MevDetector::new(DetectorConfig::default()), distributor.clone())
+            .with_auction(auction.clone()),
+    );
+
+    let bundle_txs = create_test_transactions(1, 0);
+    auction.bundle_transactions().insert(bundle_txs.clone());
+    auction
+        .submit_bundle(BundleSubmission {
+            bundle_hash: B256::random(),
+            bid_amount: U256::from(3_000),
+            target_block: 1,
+            transactions: vec![*bundle_txs[0].hash()],
+            searcher: Address::random(),
+            encrypted_payload: None,
+        })
+        .await?;
+
+    let path = fixture.temp_dir.path().join("payload-builder.toml");
+    let watcher = ConfigWatcher::new(path, fixture.builder.config.clone());
+    let settings = watcher.switch(RuntimeSwitch::MevEnabled(MevComponent::Auction, false));
+    assert!(!settings.mev_auction);
+    assert!(!watcher.switch(RuntimeSwitch::MevEnabled(MevComponent::Distribution, false)).mev_distribution);
+
+    // Without the auction the pool transactions start from the nonce the bundle took
+    let transactions = create_test_transactions(2, 0);
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+    let builder = fixture
+        .builder
+        .with_live_config(watcher.shared())
+        .with_mev_auction(auction.clone())
+        .with_mev_pipeline(pipeline);
+    let block = builder.build_payload_block(payload_attrs).await?;
+
+    let included: Vec<B256> = block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    let expected: Vec<B256> = transactions.iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, expected);
+    let stats = auction.get_auction_stats().await;
+    assert_eq!((stats.pending_bundles, stats.executed_bundles), (1, 0));
+    assert_eq!(distributor.get_buffer_amount().await, U256::ZERO);
+
+    println!("✓ Switched off auction test passed");
+    Ok(())
+}
+
+/// Tests that a builder without an auction leaves the payload untouched
+#[tokio::test]
+async fn test_payload_unchanged_without_auction() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    assert!(fixture.builder.mev_auction().is_none());
+
+    let transactions = create_test_transactions(2, 0);
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+    let block = fixture.builder.build_payload_block(payload_attrs).await?;
+
+    let included: Vec<B256> = block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    let expected: Vec<B256> = transactions.iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, expected);
+
+    println!("✓ Payload without auction test passed");
+    Ok(())
+}
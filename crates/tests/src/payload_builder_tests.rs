@@ -6,11 +6,52 @@
 
 use crate::common;
 
+use alloy::signers::local::PrivateKeySigner;
+use alloy_consensus::{transaction::SignerRecoverable, Header};
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::{logs_bloom, Address, BlockHash, BlockNumber, Bloom, Bytes, B256, U256};
+use async_trait::async_trait;
+use ev_node::{
+    AndechainGenesisConfig, BlockFinality, ConfigWatcher, EvolveBuildOutcome, EvolvePayloadBuilder, EvolvePayloadBuilderConfig,
+    FinalizationTimeout, ParentMismatch, PriorityTransactionsExcluded, ProducerSchedule, RejectedTx,
+    RejectionReason, ValidationMismatch,
+};
+use evolve_ev_reth::{
+    evm_config::{
+        create_ande_evm_config,
+        precompile::{transfer_log, TRANSFER_SELECTOR},
+        AndePrecompileConfig, NetworkProfile, ANDE_PRECOMPILE_ADDRESS,
+    },
+    parallel::ParallelConfig as EvolveParallelConfig,
+    rpc::{RuntimeSwitch, RuntimeSwitchSource},
+    EvolveConfig, FinalizationPolicy, TxpoolOverflowPolicy, VersionedPayloadAttributes,
+};
+use reth_chainspec::ChainInfo;
+use reth_ethereum_primitives::Receipt;
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_primitives::{SealedBlock, SealedHeader, TransactionSigned};
+use reth_provider::{
+    test_utils::{ExtendedAccount, MockEthProvider},
+    BlockHashReader, BlockIdReader, BlockNumReader, HeaderProvider, ProviderResult, StateProviderBox,
+    StateProviderFactory,
+};
 use eyre::Result;
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    ops::RangeBounds,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 use tokio::time::timeout;
 
-use common::{create_test_transactions, EvolveTestFixture, TEST_GAS_LIMIT, TEST_TIMESTAMP};
+use common::{
+    create_call_transaction, create_priced_transaction, create_signed_call_transaction,
+    create_signed_input_call_transaction, create_test_transaction, create_test_transactions,
+    create_transfer_transaction, create_unrecoverable_transaction, EvolveTestFixture, TEST_GAS_LIMIT, TEST_GENESIS_TIMESTAMP, TEST_TIMESTAMP,
+};
 
 /// Tests basic payload building with empty transactions
 #[tokio::test]
@@ -25,7 +66,7 @@ async fn test_empty_payload_building() -> Result<()> {
         Some(TEST_GAS_LIMIT),
     );
 
-    let result = fixture.builder.build_payload(payload_attrs).await;
+    let result = fixture.builder.build_payload_block(payload_attrs).await;
     assert!(result.is_ok(), "Empty payload should build successfully");
 
     let sealed_block = result.unwrap();
@@ -40,6 +81,34 @@ async fn test_empty_payload_building() -> Result<()> {
     Ok(())
 }
 
+/// Tests that attributes read as a sidecar sends them, versioned, build the same block
+#[tokio::test]
+async fn test_versioned_payload_attributes_intake() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let transactions = create_test_transactions(2, 0);
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+
+    let mut json = serde_json::to_value(VersionedPayloadAttributes::from(payload_attrs.clone()))?;
+    assert_eq!(json["version"], "v1");
+    // A field from a newer sidecar is ignored
+    json["inclusion_deadline"] = serde_json::json!(TEST_TIMESTAMP + 12);
+    let versioned: VersionedPayloadAttributes = serde_json::from_value(json)?;
+
+    let expected = fixture.builder.build_payload_block(payload_attrs).await?;
+    let block = fixture.builder.build_payload_block(versioned).await?;
+    assert_eq!(block.transaction_count(), 2);
+    assert_eq!(block.hash(), expected.hash());
+
+    println!("✓ Versioned payload attributes intake test passed");
+    Ok(())
+}
+
 /// Tests payload building with multiple transactions
 #[tokio::test]
 async fn test_payload_with_transactions() -> Result<()> {
@@ -57,7 +126,7 @@ async fn test_payload_with_transactions() -> Result<()> {
 
     let result = timeout(
         Duration::from_secs(30),
-        fixture.builder.build_payload(payload_attrs),
+        fixture.builder.build_payload_block(payload_attrs),
     )
     .await;
 
@@ -105,7 +174,7 @@ async fn test_variable_transaction_counts() -> Result<()> {
             Some(TEST_GAS_LIMIT),
         );
 
-        let result = fixture.builder.build_payload(payload_attrs).await;
+        let result = fixture.builder.build_payload_block(payload_attrs).await;
         assert!(
             result.is_ok(),
             "Payload with {tx_count} transactions should build successfully"
@@ -163,7 +232,7 @@ async fn test_payload_building_error_handling() -> Result<()> {
         Some(TEST_GAS_LIMIT),
     );
 
-    let result = fixture.builder.build_payload(payload_attrs).await;
+    let result = fixture.builder.build_payload_block(payload_attrs).await;
     match result {
         Ok(sealed_block) => {
             println!(
@@ -185,12 +254,28 @@ async fn test_payload_building_error_handling() -> Result<()> {
         Some(TEST_GAS_LIMIT),
     );
 
-    let result = fixture.builder.build_payload(attrs_large_timestamp).await;
+    let result = fixture.builder.build_payload_block(attrs_large_timestamp).await;
     match result {
         Ok(_) => println!("✓ Large timestamp handled gracefully"),
         Err(e) => println!("✓ Large timestamp rejected appropriately: {e}"),
     }
 
+    // Test with the parent's timestamp (must be strictly after it)
+    let attrs_parent_timestamp = fixture.create_payload_attributes(
+        vec![],
+        1,
+        TEST_GENESIS_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+
+    let err = fixture
+        .builder
+        .build_payload_block(attrs_parent_timestamp)
+        .await
+        .expect_err("a block at its parent's timestamp should be rejected");
+    assert!(err.to_string().contains("not after parent timestamp"), "{err}");
+
     println!("✓ Error handling tests completed");
     Ok(())
 }
@@ -214,7 +299,7 @@ async fn test_payload_building_performance() -> Result<()> {
         );
 
         let start_time = std::time::Instant::now();
-        let result = fixture.builder.build_payload(payload_attrs).await;
+        let result = fixture.builder.build_payload_block(payload_attrs).await;
         let duration = start_time.elapsed();
 
         match result {
@@ -258,7 +343,7 @@ async fn test_gas_limit_scenarios() -> Result<()> {
         None, // No gas limit
     );
 
-    let result = fixture.builder.build_payload(payload_attrs).await;
+    let result = fixture.builder.build_payload_block(payload_attrs).await;
     assert!(result.is_err(), "Payload with no gas limit should fail");
     if let Err(e) = result {
         assert!(
@@ -279,7 +364,7 @@ async fn test_gas_limit_scenarios() -> Result<()> {
             Some(gas_limit),
         );
 
-        let result = fixture.builder.build_payload(payload_attrs).await;
+        let result = fixture.builder.build_payload_block(payload_attrs).await;
         assert!(
             result.is_ok(),
             "Payload with gas limit {gas_limit} should build successfully"
@@ -295,3 +380,1059 @@ async fn test_gas_limit_scenarios() -> Result<()> {
     println!("✓ Gas limit scenarios test passed");
     Ok(())
 }
+
+/// Tests that transactions beyond the block gas limit are left out in index order
+#[tokio::test]
+async fn test_gas_limit_packing_excludes_overflow() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+
+    // Ten 21k-gas transfers add up to 150% of the block gas limit
+    let transactions = create_test_transactions(10, 0);
+    let gas_limit = 140_000;
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(gas_limit),
+    );
+
+    let built = fixture.builder.build_payload(payload_attrs).await?;
+
+    assert!(built.block.gas_used <= gas_limit, "Block gas used exceeds the gas limit");
+    assert_eq!(built.block.transaction_count(), 6);
+    let expected: Vec<_> = transactions[6..].iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(built.excluded_transactions, expected, "Exclusions should be in index order");
+
+    println!("✓ Gas limit packing test passed");
+    Ok(())
+}
+
+/// Tests that payloads over the txpool caps are truncated from the tail or rejected
+#[tokio::test]
+async fn test_txpool_caps_enforcement() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let transactions = create_test_transactions(6, 0);
+    let builder_with = |txpool: EvolveConfig| {
+        let mut config = fixture.builder.config.clone();
+        config.txpool = txpool;
+        EvolvePayloadBuilder::new(fixture.builder.client.clone(), fixture.builder.evm_config.clone(), config)
+    };
+    let attributes = || {
+        fixture.create_payload_attributes(
+            transactions.clone(),
+            1,
+            TEST_TIMESTAMP,
+            fixture.genesis_hash,
+            Some(TEST_GAS_LIMIT),
+        )
+    };
+
+    // Room for four 21k-gas transfers, the last two are dropped
+    let truncate = EvolveConfig::new_with_gas(0, 4 * 21_000);
+    let built = builder_with(truncate.clone()).build_payload(attributes()).await?;
+    assert_eq!(built.block.transaction_count(), 4);
+    let dropped: Vec<_> = transactions[4..].iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(built.truncated_transactions, dropped);
+    assert!(built.excluded_transactions.is_empty());
+
+    let reject = truncate.with_overflow_policy(TxpoolOverflowPolicy::Reject);
+    let err = builder_with(reject).build_payload_block(attributes()).await.expect_err("oversized payload");
+    assert!(err.to_string().contains("Transactions use 126000 gas"), "unexpected error: {err}");
+
+    Ok(())
+}
+
+/// Tests that priority transactions lead the block and excluded ones are dropped
+#[tokio::test]
+async fn test_priority_and_excluded_transactions() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let transactions = create_test_transactions(4, 0);
+
+    // The priority transaction goes first even when listed after the others
+    let payload_attrs = fixture
+        .create_payload_attributes(
+            transactions[1..].to_vec(),
+            1,
+            TEST_TIMESTAMP,
+            fixture.genesis_hash,
+            Some(TEST_GAS_LIMIT),
+        )
+        .with_priority_transactions(vec![transactions[0].clone()])
+        .with_excluded_hashes(vec![*transactions[3].hash()]);
+
+    let block = fixture.builder.build_payload_block(payload_attrs).await?;
+    let included: Vec<_> = block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    let expected: Vec<_> = transactions[..3].iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, expected);
+
+    // A priority transaction that doesn't fit fails the build
+    let payload_attrs = fixture
+        .create_payload_attributes(vec![], 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(42_000))
+        .with_priority_transactions(transactions[..3].to_vec());
+    let err = fixture.builder.build_payload_block(payload_attrs).await.expect_err("priority transaction left out");
+    let PayloadBuilderError::Other(err) = err else {
+        panic!("expected excluded priority transactions: {err}");
+    };
+    let excluded =
+        err.downcast_ref::<PriorityTransactionsExcluded>().expect("expected excluded priority transactions");
+    assert_eq!(excluded.block_number, 1);
+    assert_eq!(excluded.missing, vec![*transactions[2].hash()]);
+
+    Ok(())
+}
+
+/// Tests that the transactions left out of a block are reported with why
+#[tokio::test]
+async fn test_rejected_transactions_are_reported() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let transfers = create_test_transactions(3, 0);
+    let unrecoverable = create_unrecoverable_transaction(0);
+    let future_nonce = create_test_transaction(5);
+    // Doesn't fit next to two transfers in a 100k gas block
+    let oversized = create_call_transaction(2, Address::repeat_byte(0x20), 90_000);
+    let transactions = vec![
+        transfers[0].clone(),
+        unrecoverable.clone(),
+        future_nonce.clone(),
+        transfers[1].clone(),
+        transfers[2].clone(),
+        oversized.clone(),
+    ];
+    let payload_attrs = fixture
+        .create_payload_attributes(transactions, 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(100_000))
+        .with_excluded_hashes(vec![*transfers[2].hash()]);
+
+    let built = fixture.builder.build_payload(payload_attrs).await?;
+
+    let included: Vec<_> = built.block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, vec![*transfers[0].hash(), *transfers[1].hash()]);
+    let rejected = |tx: &TransactionSigned, index, reason| RejectedTx { hash: *tx.hash(), index, reason };
+    assert_eq!(
+        built.rejected_transactions,
+        vec![
+            rejected(&unrecoverable, 1, RejectionReason::RecoveryFailed),
+            rejected(&future_nonce, 2, RejectionReason::Reverted),
+            rejected(&transfers[2], 4, RejectionReason::Excluded),
+            rejected(&oversized, 5, RejectionReason::ExceedsGasLimit),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Tests that a build past its time budget is sealed with a prefix of the transactions
+#[tokio::test]
+async fn test_build_deadline_cuts_the_block() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+
+    // JUMPDEST PUSH1 0x00 JUMP: loops until the transaction runs out of gas
+    let looper = Address::repeat_byte(0x10);
+    let code = Bytes::from_static(&[0x5b, 0x60, 0x00, 0x56]);
+    fixture.provider.add_account(looper, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code));
+    let transactions: Vec<_> = (0..30).map(|nonce| create_call_transaction(nonce, looper, 1_000_000)).collect();
+
+    let budget = Duration::from_millis(10);
+    let mut config = fixture.builder.config.clone();
+    config.max_build_duration = Some(budget);
+    let builder = EvolvePayloadBuilder::new(fixture.builder.client.clone(), fixture.builder.evm_config.clone(), config);
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+
+    let started = Instant::now();
+    let built = builder.build_payload(payload_attrs).await?;
+    let elapsed = started.elapsed();
+
+    let cut_off = built.cut_off.expect("30M gas of loops don't execute in 10ms");
+    let included: Vec<_> = built.block.body().transactions.iter().map(|tx| *tx.hash()).collect();
+    let prefix: Vec<_> = transactions[..cut_off].iter().map(|tx| *tx.hash()).collect();
+    assert_eq!(included, prefix);
+    assert_eq!(built.receipts.len(), cut_off);
+    assert_eq!(built.rejected_transactions.len(), transactions.len() - cut_off);
+    assert!(built.rejected_transactions.iter().all(|rejected| rejected.reason == RejectionReason::DeadlineCut));
+    assert_eq!(built.rejected_transactions[0].index, cut_off);
+    // Generous bound for slow CI machines: finishing the block isn't budgeted
+    assert!(elapsed < budget + Duration::from_secs(5), "build took {elapsed:?}");
+
+    Ok(())
+}
+
+/// Tests that built payloads carry the receipts, fees and post-state of the block
+#[tokio::test]
+async fn test_build_artifacts() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let gas_price = 1_000_000_000;
+    let transactions = vec![
+        create_priced_transaction(0, gas_price),
+        create_test_transaction(1),
+        create_priced_transaction(2, 2 * gas_price),
+    ];
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions.clone(),
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+
+    let built = fixture.builder.build_payload(payload_attrs).await?;
+    assert_eq!(built.block.transaction_count(), 3);
+    assert_eq!(built.receipts.len(), built.block.transaction_count());
+    assert!(built.receipts.iter().all(|receipt| receipt.success));
+    assert_eq!(built.receipts.last().map(|receipt| receipt.cumulative_gas_used), Some(built.block.gas_used));
+
+    // Plain transfers use 21000 gas each, all paid as tip with a zero base fee
+    assert_eq!(built.fees, U256::from(21_000 * (gas_price + 2 * gas_price)));
+
+    let sender = transactions[0].recover_signer()?;
+    let sender_info = built.bundle_state.state.get(&sender).and_then(|account| account.info.as_ref());
+    assert_eq!(sender_info.map(|info| info.nonce), Some(3));
+    assert!(!built.execution.parallel);
+
+    Ok(())
+}
+
+/// Tests that built blocks validate, and re-execution catches tampered state
+#[tokio::test]
+async fn test_validate_payload() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let transfer = create_transfer_transaction(0, U256::from(1_000_000_000_000_000_000u64));
+    let payload_attrs = fixture.create_payload_attributes(
+        vec![transfer.clone()],
+        1,
+        TEST_TIMESTAMP,
+        fixture.genesis_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+    let block = fixture.builder.build_payload_block(payload_attrs).await?;
+    assert_eq!(block.transaction_count(), 1);
+
+    let report = fixture.builder.validate_payload(&block)?;
+    assert!(report.is_valid(), "built block failed validation: {:?}", report.mismatch);
+    assert_eq!((report.block_number, report.block_hash), (1, block.hash()));
+
+    // The mock provider computes no real state root, so tamper the header instead
+    let mut tampered = block.clone().into_block();
+    tampered.header.state_root = B256::repeat_byte(0x01);
+    let report = fixture.builder.validate_payload(&SealedBlock::seal_slow(tampered))?;
+    assert!(matches!(
+        report.mismatch,
+        Some(ValidationMismatch::StateRoot { expected, .. }) if expected == B256::repeat_byte(0x01)
+    ));
+
+    // Without the balance to cover the transfer, re-execution fails on it
+    let sender = transfer.recover_signer()?;
+    fixture.provider.add_account(sender, ExtendedAccount::new(0, U256::ZERO));
+    let report = fixture.builder.validate_payload(&block)?;
+    let Some(ValidationMismatch::TransactionFailed { index, hash, .. }) = report.mismatch else {
+        panic!("expected a failed transaction: {:?}", report.mismatch);
+    };
+    assert_eq!((index, hash), (0, *transfer.hash()));
+
+    Ok(())
+}
+
+/// Tests that the logs of duality transfers are committed to by the receipts
+/// root and logs bloom, so followers validate the block, and that a corrupted
+/// log is caught
+#[tokio::test]
+async fn test_precompile_logs_are_committed() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let chain_spec = fixture.builder.evm_config.chain_spec().clone();
+    let evm_config = create_ande_evm_config(chain_spec, AndePrecompileConfig::insecure_unrestricted());
+    let builder =
+        EvolvePayloadBuilder::new(Arc::new(fixture.provider.clone()), evm_config, fixture.builder.config.clone());
+
+    let (signer, recipient) = (PrivateKeySigner::random(), Address::repeat_byte(0x30));
+    fixture.provider.add_account(signer.address(), ExtendedAccount::new(0, U256::from(1_000)));
+    let value = U256::from(300);
+    let input = [
+        &TRANSFER_SELECTOR[..],
+        &signer.address().into_word()[..],
+        &recipient.into_word()[..],
+        &value.to_be_bytes::<32>()[..],
+    ]
+    .concat();
+    let transfer =
+        create_signed_input_call_transaction(&signer, 0, ANDE_PRECOMPILE_ADDRESS, 100_000, input.into());
+    let payload_attrs =
+        fixture.create_payload_attributes(vec![transfer], 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(TEST_GAS_LIMIT));
+
+    let built = builder.build_payload(payload_attrs).await?;
+    assert!(built.receipts[0].success);
+    assert_eq!(built.receipts[0].logs, [transfer_log(signer.address(), recipient, value)]);
+    assert_eq!(built.block.logs_bloom, logs_bloom(&built.receipts[0].logs));
+    assert_eq!(built.block.receipts_root, Receipt::calculate_receipt_root_no_memo(&built.receipts));
+
+    // Followers re-executing the block reach the same roots
+    let report = builder.validate_payload(&built.block)?;
+    assert!(report.is_valid(), "built block failed validation: {:?}", report.mismatch);
+
+    // A block committing to a corrupted log is pinpointed by its receipts root
+    let mut corrupted = built.receipts.clone();
+    corrupted[0].logs[0] = transfer_log(signer.address(), recipient, U256::from(301));
+    let mut tampered = built.block.clone().into_block();
+    tampered.header.receipts_root = Receipt::calculate_receipt_root_no_memo(&corrupted);
+    let report = builder.validate_payload(&SealedBlock::seal_slow(tampered))?;
+    assert!(matches!(
+        report.mismatch,
+        Some(ValidationMismatch::ReceiptsRoot { expected, actual })
+            if expected == Receipt::calculate_receipt_root_no_memo(&corrupted) && actual == built.block.receipts_root
+    ));
+
+    // As is a bloom missing the precompile log
+    let mut tampered = built.block.clone().into_block();
+    tampered.header.logs_bloom = Bloom::ZERO;
+    let report = builder.validate_payload(&SealedBlock::seal_slow(tampered))?;
+    assert!(matches!(
+        report.mismatch,
+        Some(ValidationMismatch::LogsBloom { expected, actual })
+            if expected == Bloom::ZERO && actual == built.block.logs_bloom
+    ));
+
+    Ok(())
+}
+
+/// Input of an ANDE precompile transfer of `value` from `from` to `to`
+fn ande_transfer_input(from: Address, to: Address, value: U256) -> Bytes {
+    [&TRANSFER_SELECTOR[..], &from.into_word()[..], &to.into_word()[..], &value.to_be_bytes::<32>()[..]].concat().into()
+}
+
+/// Tests that the per-block cap of the ANDE precompile reverts the transfer
+/// exceeding it in a built block, sequentially and in parallel, and that the
+/// count starts over when the block is built again
+#[tokio::test]
+async fn test_precompile_block_cap_in_built_blocks() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let chain_spec = fixture.builder.evm_config.chain_spec().clone();
+    let precompile_config = AndePrecompileConfig {
+        per_block_cap: Some(U256::from(1_000)),
+        ..AndePrecompileConfig::insecure_unrestricted()
+    };
+    let evm_config = create_ande_evm_config(chain_spec, precompile_config);
+    let parallel_config = EvolveParallelConfig {
+        min_transactions_for_parallel: 2,
+        max_dependent_fraction: 1.0,
+        ..EvolveParallelConfig::default()
+    };
+
+    // The second transfer exceeds the cap after the first one, the third fits
+    // as long as the reverted transfer isn't counted
+    let recipient = Address::repeat_byte(0x30);
+    let transfers: Vec<_> = [600, 600, 300]
+        .into_iter()
+        .map(|value| {
+            let signer = PrivateKeySigner::random();
+            fixture.provider.add_account(signer.address(), ExtendedAccount::new(0, U256::from(1_000)));
+            let input = ande_transfer_input(signer.address(), recipient, U256::from(value));
+            create_signed_input_call_transaction(&signer, 0, ANDE_PRECOMPILE_ADDRESS, 100_000, input)
+        })
+        .collect();
+
+    for parallel in [false, true] {
+        let builder = EvolvePayloadBuilder::new_with_parallel(
+            Arc::new(fixture.provider.clone()),
+            evm_config.clone(),
+            parallel.then(|| parallel_config.clone()),
+            fixture.builder.config.clone(),
+        );
+        for _ in 0..2 {
+            let payload_attrs = fixture.create_payload_attributes(
+                transfers.clone(),
+                1,
+                TEST_TIMESTAMP,
+                fixture.genesis_hash,
+                Some(TEST_GAS_LIMIT),
+            );
+            let built = builder.build_payload(payload_attrs).await?;
+            assert_eq!(built.execution.parallel, parallel);
+            assert_eq!(built.block.transaction_count(), 3);
+            let outcomes: Vec<_> = built.receipts.iter().map(|receipt| receipt.success).collect();
+            assert_eq!(outcomes, [true, false, true], "parallel: {parallel}");
+            assert!(built.receipts[1].logs.is_empty());
+
+            // Followers re-executing the block revert the same transfer
+            let report = builder.validate_payload(&built.block)?;
+            assert!(report.is_valid(), "built block failed validation: {:?}", report.mismatch);
+        }
+    }
+
+    Ok(())
+}
+
+/// Tests that the per-caller cap of the ANDE precompile stops one caller at
+/// its limit in a built block while another caller transfers alongside it
+#[tokio::test]
+async fn test_precompile_caller_cap_in_built_blocks() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let chain_spec = fixture.builder.evm_config.chain_spec().clone();
+    let precompile_config = AndePrecompileConfig {
+        per_caller_block_cap: Some(U256::from(700)),
+        ..AndePrecompileConfig::insecure_unrestricted()
+    };
+    let evm_config = create_ande_evm_config(chain_spec, precompile_config);
+    let builder =
+        EvolvePayloadBuilder::new(Arc::new(fixture.provider.clone()), evm_config, fixture.builder.config.clone());
+
+    let (busy, other, recipient) = (PrivateKeySigner::random(), PrivateKeySigner::random(), Address::repeat_byte(0x30));
+    for signer in [&busy, &other] {
+        fixture.provider.add_account(signer.address(), ExtendedAccount::new(0, U256::from(1_000)));
+    }
+    let transfer = |signer: &PrivateKeySigner, nonce| {
+        let input = ande_transfer_input(signer.address(), recipient, U256::from(400));
+        create_signed_input_call_transaction(signer, nonce, ANDE_PRECOMPILE_ADDRESS, 100_000, input)
+    };
+    let transfers = vec![transfer(&busy, 0), transfer(&busy, 1), transfer(&other, 0)];
+    let payload_attrs =
+        fixture.create_payload_attributes(transfers, 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(TEST_GAS_LIMIT));
+
+    // Both callers together exceed what one of them may transfer
+    let built = builder.build_payload(payload_attrs).await?;
+    let outcomes: Vec<_> = built.receipts.iter().map(|receipt| receipt.success).collect();
+    assert_eq!(outcomes, [true, false, true]);
+    let report = builder.validate_payload(&built.block)?;
+    assert!(report.is_valid(), "built block failed validation: {:?}", report.mismatch);
+
+    Ok(())
+}
+
+/// Tests that the ANDE precompile is reached in built blocks only when the
+/// config enables it
+#[tokio::test]
+async fn test_ande_precompiles_switch() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let chain_spec = fixture.builder.evm_config.chain_spec().clone();
+
+    for enabled in [true, false] {
+        let config = EvolvePayloadBuilderConfig {
+            andechain: Some(AndechainGenesisConfig {
+                ande_network_profile: Some(NetworkProfile::Dev),
+                ande_token_address: Some(Address::repeat_byte(0x01)),
+                ..Default::default()
+            }),
+            ande_precompiles_enabled: Some(enabled),
+            ..EvolvePayloadBuilderConfig::new()
+        };
+        let evm_config = config.evm_config_builder(chain_spec.clone())?.build()?;
+        let builder = EvolvePayloadBuilder::new(Arc::new(fixture.provider.clone()), evm_config, config);
+
+        // Malformed input fails in the precompile, and consumes the call's gas,
+        // but is a plain transfer to an empty account without it
+        let call = create_call_transaction(0, ANDE_PRECOMPILE_ADDRESS, 100_000);
+        let payload_attrs = fixture.create_payload_attributes(
+            vec![call],
+            1,
+            TEST_TIMESTAMP,
+            fixture.genesis_hash,
+            Some(TEST_GAS_LIMIT),
+        );
+        let built = builder.build_payload(payload_attrs).await?;
+        assert_eq!(built.receipts.len(), 1);
+        assert_eq!(built.receipts[0].success, !enabled, "ANDE precompiles enabled: {enabled}");
+        assert_eq!(built.block.gas_used == 21_000, !enabled);
+    }
+
+    Ok(())
+}
+
+/// Tests that blocks dominated by one group of dependent transactions execute
+/// sequentially, and well spread ones in parallel
+#[tokio::test]
+async fn test_dependent_fraction_picks_the_execution_mode() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let parallel_config = EvolveParallelConfig {
+        min_transactions_for_parallel: 2,
+        max_dependent_fraction: 0.6,
+        ..EvolveParallelConfig::default()
+    };
+    let builder = EvolvePayloadBuilder::new_with_parallel(
+        Arc::new(fixture.provider.clone()),
+        fixture.builder.evm_config.clone(),
+        Some(parallel_config),
+        fixture.builder.config.clone(),
+    );
+    let signers: Vec<PrivateKeySigner> = (0..100).map(|_| PrivateKeySigner::random()).collect();
+    let payload_attrs = |transactions| {
+        fixture.create_payload_attributes(transactions, 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(TEST_GAS_LIMIT))
+    };
+
+    // Every transaction hits the same pool
+    let pool = Address::repeat_byte(0xaa);
+    let hot = signers.iter().map(|signer| create_signed_call_transaction(signer, 0, pool)).collect();
+    let built = builder.build_payload(payload_attrs(hot)).await?;
+    assert_eq!(built.block.transaction_count(), 100);
+    assert_eq!(built.execution.dependent_fraction, Some(1.0));
+    assert!(!built.execution.parallel);
+
+    let spread = signers
+        .iter()
+        .zip(1u8..)
+        .map(|(signer, to)| create_signed_call_transaction(signer, 0, Address::repeat_byte(to)))
+        .collect();
+    let built = builder.build_payload(payload_attrs(spread)).await?;
+    assert_eq!(built.block.transaction_count(), 100);
+    assert_eq!(built.execution.dependent_fraction, Some(0.01));
+    assert!(built.execution.parallel);
+
+    Ok(())
+}
+
+/// Tests that the runtime switches of the admin RPC apply from the next block built
+#[tokio::test]
+async fn test_runtime_switches_pick_the_execution_mode() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let parallel_config = EvolveParallelConfig { min_transactions_for_parallel: 2, ..EvolveParallelConfig::default() };
+    let path = fixture.temp_dir.path().join("payload-builder.toml");
+    let watcher = ConfigWatcher::new(path, fixture.builder.config.clone());
+    let builder = EvolvePayloadBuilder::new_with_parallel(
+        Arc::new(fixture.provider.clone()),
+        fixture.builder.evm_config.clone(),
+        Some(parallel_config),
+        fixture.builder.config.clone(),
+    )
+    .with_live_config(watcher.shared());
+
+    let transactions: Vec<TransactionSigned> = (1u8..=20)
+        .map(|to| create_signed_call_transaction(&PrivateKeySigner::random(), 0, Address::repeat_byte(to)))
+        .collect();
+    let build_parallel = || async {
+        let payload_attrs = fixture.create_payload_attributes(
+            transactions.clone(),
+            1,
+            TEST_TIMESTAMP,
+            fixture.genesis_hash,
+            Some(TEST_GAS_LIMIT),
+        );
+        let built = builder.build_payload(payload_attrs).await?;
+        assert_eq!(built.block.transaction_count(), 20);
+        Ok::<_, PayloadBuilderError>(built.execution.parallel)
+    };
+    assert!(build_parallel().await?);
+
+    assert!(!watcher.switch(RuntimeSwitch::ParallelEnabled(false)).parallel_enabled);
+    assert!(!build_parallel().await?);
+    watcher.switch(RuntimeSwitch::ParallelEnabled(true));
+    assert!(build_parallel().await?);
+
+    assert!(watcher.switch(RuntimeSwitch::ForceSequential(true)).force_sequential);
+    assert!(!build_parallel().await?);
+    watcher.switch(RuntimeSwitch::ForceSequential(false));
+    assert!(build_parallel().await?);
+
+    Ok(())
+}
+
+/// Provider whose chain advances after the first state snapshot: later
+/// snapshots are taken on a state where the test sender has no funds
+#[derive(Debug)]
+struct AdvancingChainProvider {
+    /// State of the parent block, served by the first snapshot
+    parent: MockEthProvider,
+    /// State served by every later snapshot
+    advanced: MockEthProvider,
+    /// Snapshots taken so far
+    snapshots: AtomicUsize,
+}
+
+impl AdvancingChainProvider {
+    fn new(parent: MockEthProvider) -> Self {
+        Self { parent, advanced: MockEthProvider::default(), snapshots: AtomicUsize::new(0) }
+    }
+
+    /// Provider the next snapshot is taken from
+    fn next_snapshot(&self) -> &MockEthProvider {
+        if self.snapshots.fetch_add(1, Ordering::SeqCst) == 0 {
+            &self.parent
+        } else {
+            &self.advanced
+        }
+    }
+}
+
+impl BlockHashReader for AdvancingChainProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.parent.block_hash(number)
+    }
+
+    fn canonical_hashes_range(&self, start: BlockNumber, end: BlockNumber) -> ProviderResult<Vec<B256>> {
+        self.parent.canonical_hashes_range(start, end)
+    }
+}
+
+impl BlockNumReader for AdvancingChainProvider {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        self.parent.chain_info()
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.parent.best_block_number()
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.parent.last_block_number()
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        self.parent.block_number(hash)
+    }
+}
+
+impl BlockIdReader for AdvancingChainProvider {
+    fn pending_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.parent.pending_block_num_hash()
+    }
+
+    fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.parent.safe_block_num_hash()
+    }
+
+    fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.parent.finalized_block_num_hash()
+    }
+}
+
+impl HeaderProvider for AdvancingChainProvider {
+    type Header = Header;
+
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        self.parent.header(block_hash)
+    }
+
+    fn header_by_number(&self, number: BlockNumber) -> ProviderResult<Option<Header>> {
+        self.parent.header_by_number(number)
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        self.parent.header_td(hash)
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        self.parent.header_td_by_number(number)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.parent.headers_range(range)
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        self.parent.sealed_header(number)
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.parent.sealed_headers_while(range, predicate)
+    }
+}
+
+impl StateProviderFactory for AdvancingChainProvider {
+    fn latest(&self) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().latest()
+    }
+
+    fn state_by_block_number_or_tag(&self, number_or_tag: BlockNumberOrTag) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().state_by_block_number_or_tag(number_or_tag)
+    }
+
+    fn history_by_block_number(&self, block: BlockNumber) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().history_by_block_number(block)
+    }
+
+    fn history_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().history_by_block_hash(block)
+    }
+
+    fn state_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().state_by_block_hash(block)
+    }
+
+    fn pending(&self) -> ProviderResult<StateProviderBox> {
+        self.next_snapshot().pending()
+    }
+
+    fn pending_state_by_hash(&self, block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
+        self.next_snapshot().pending_state_by_hash(block_hash)
+    }
+
+    fn maybe_pending(&self) -> ProviderResult<Option<StateProviderBox>> {
+        self.next_snapshot().maybe_pending()
+    }
+}
+
+/// Tests that a payload is executed and sealed on a single snapshot of the
+/// parent state, whether its transactions execute sequentially or in parallel
+#[tokio::test]
+async fn test_payload_uses_a_single_state_snapshot() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let parallel_config =
+        EvolveParallelConfig { min_transactions_for_parallel: 2, ..EvolveParallelConfig::default() };
+
+    for parallel_config in [None, Some(parallel_config)] {
+        let provider = Arc::new(AdvancingChainProvider::new(fixture.provider.clone()));
+        let builder = EvolvePayloadBuilder::new_with_parallel(
+            Arc::clone(&provider),
+            fixture.builder.evm_config.clone(),
+            parallel_config,
+            fixture.builder.config.clone(),
+        );
+        let payload_attrs = fixture.create_payload_attributes(
+            create_test_transactions(4, 0),
+            1,
+            TEST_TIMESTAMP,
+            fixture.genesis_hash,
+            Some(TEST_GAS_LIMIT),
+        );
+
+        let built = builder.build_payload(payload_attrs).await?;
+        assert_eq!(provider.snapshots.load(Ordering::SeqCst), 1, "a second state snapshot was taken");
+        assert_eq!(built.block.transaction_count(), 4);
+        assert!(built.receipts.iter().all(|receipt| receipt.success));
+    }
+
+    Ok(())
+}
+
+/// Provider whose canonical chain can be switched to another fork, as after
+/// a reorg; everything else is served by `chain`
+#[derive(Debug)]
+struct ReorgingChainProvider {
+    /// Headers and state of every fork
+    chain: MockEthProvider,
+    /// Canonical block of each height, overriding `chain`
+    canonical: Mutex<HashMap<BlockNumber, B256>>,
+}
+
+impl ReorgingChainProvider {
+    fn new(chain: MockEthProvider) -> Self {
+        Self { chain, canonical: Mutex::new(HashMap::new()) }
+    }
+
+    /// Make `hash` the canonical block at `number`
+    fn set_canonical(&self, number: BlockNumber, hash: B256) {
+        self.canonical.lock().unwrap().insert(number, hash);
+    }
+}
+
+impl BlockHashReader for ReorgingChainProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        match self.canonical.lock().unwrap().get(&number) {
+            Some(hash) => Ok(Some(*hash)),
+            None => self.chain.block_hash(number),
+        }
+    }
+
+    fn canonical_hashes_range(&self, start: BlockNumber, end: BlockNumber) -> ProviderResult<Vec<B256>> {
+        (start..end).map(|number| self.block_hash(number).map(Option::unwrap_or_default)).collect()
+    }
+}
+
+impl BlockNumReader for ReorgingChainProvider {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        self.chain.chain_info()
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.chain.best_block_number()
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.chain.last_block_number()
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        self.chain.block_number(hash)
+    }
+}
+
+impl BlockIdReader for ReorgingChainProvider {
+    fn pending_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.chain.pending_block_num_hash()
+    }
+
+    fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.chain.safe_block_num_hash()
+    }
+
+    fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.chain.finalized_block_num_hash()
+    }
+}
+
+impl HeaderProvider for ReorgingChainProvider {
+    type Header = Header;
+
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        self.chain.header(block_hash)
+    }
+
+    fn header_by_number(&self, number: BlockNumber) -> ProviderResult<Option<Header>> {
+        self.chain.header_by_number(number)
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        self.chain.header_td(hash)
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        self.chain.header_td_by_number(number)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.chain.headers_range(range)
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        self.chain.sealed_header(number)
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.chain.sealed_headers_while(range, predicate)
+    }
+}
+
+impl StateProviderFactory for ReorgingChainProvider {
+    fn latest(&self) -> ProviderResult<StateProviderBox> {
+        self.chain.latest()
+    }
+
+    fn state_by_block_number_or_tag(&self, number_or_tag: BlockNumberOrTag) -> ProviderResult<StateProviderBox> {
+        self.chain.state_by_block_number_or_tag(number_or_tag)
+    }
+
+    fn history_by_block_number(&self, block: BlockNumber) -> ProviderResult<StateProviderBox> {
+        self.chain.history_by_block_number(block)
+    }
+
+    fn history_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.chain.history_by_block_hash(block)
+    }
+
+    fn state_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.chain.state_by_block_hash(block)
+    }
+
+    fn pending(&self) -> ProviderResult<StateProviderBox> {
+        self.chain.pending()
+    }
+
+    fn pending_state_by_hash(&self, block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
+        self.chain.pending_state_by_hash(block_hash)
+    }
+
+    fn maybe_pending(&self) -> ProviderResult<Option<StateProviderBox>> {
+        self.chain.maybe_pending()
+    }
+}
+
+/// Parent mismatch a build failed with
+fn parent_mismatch(result: Result<EvolveBuildOutcome, PayloadBuilderError>) -> ParentMismatch {
+    let Err(PayloadBuilderError::Other(err)) = result else {
+        panic!("expected a parent mismatch");
+    };
+    *err.downcast::<ParentMismatch>().expect("expected a parent mismatch")
+}
+
+/// Tests that payloads on a reorged out parent are refused, and can be rebuilt
+/// on the new head
+#[tokio::test]
+async fn test_rebuild_on_new_parent() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    // Two forks at height 1, the second one slot later than the first
+    let (fork_a, fork_b, late_fork) = (B256::repeat_byte(0x0a), B256::repeat_byte(0x0b), B256::repeat_byte(0x0c));
+    fixture.add_mock_header(fork_a, 1, fixture.genesis_state_root, TEST_TIMESTAMP);
+    fixture.add_mock_header(fork_b, 1, fixture.genesis_state_root, TEST_TIMESTAMP);
+    fixture.add_mock_header(late_fork, 1, fixture.genesis_state_root, TEST_TIMESTAMP + 12);
+
+    let provider = Arc::new(ReorgingChainProvider::new(fixture.provider.clone()));
+    provider.set_canonical(1, fork_a);
+    let builder = EvolvePayloadBuilder::new(
+        Arc::clone(&provider),
+        fixture.builder.evm_config.clone(),
+        fixture.builder.config.clone(),
+    );
+    let payload_attrs = || {
+        fixture.create_payload_attributes(
+            create_test_transactions(2, 0),
+            2,
+            TEST_TIMESTAMP + 12,
+            fork_a,
+            Some(TEST_GAS_LIMIT),
+        )
+    };
+    assert!(matches!(builder.try_build_payload(payload_attrs()).await?, EvolveBuildOutcome::Built(_)));
+
+    // The head moves to the other fork between delivery and building
+    provider.set_canonical(1, fork_b);
+    let mismatch = parent_mismatch(builder.try_build_payload(payload_attrs()).await);
+    assert!(matches!(
+        mismatch,
+        ParentMismatch::NotCanonical { parent_hash, number: 1, canonical }
+            if parent_hash == fork_a && canonical == fork_b
+    ));
+
+    let EvolveBuildOutcome::Built(built) = builder.rebuild_on_new_parent(payload_attrs(), fork_b).await? else {
+        panic!("expected a built block");
+    };
+    assert_eq!(built.block.parent_hash, fork_b);
+    assert_eq!(built.block.number, 2);
+    assert_eq!(built.block.transaction_count(), 2);
+
+    // Neither a stale fork nor one the timestamp isn't after can take the payload
+    let stale = parent_mismatch(builder.rebuild_on_new_parent(payload_attrs(), fork_a).await);
+    assert!(matches!(stale, ParentMismatch::NotCanonical { .. }));
+    provider.set_canonical(1, late_fork);
+    let late = parent_mismatch(builder.rebuild_on_new_parent(payload_attrs(), late_fork).await);
+    assert!(matches!(late, ParentMismatch::InvalidAttributes { parent_hash, .. } if parent_hash == late_fork));
+    let unknown = parent_mismatch(builder.rebuild_on_new_parent(payload_attrs(), B256::repeat_byte(0x0d)).await);
+    assert!(matches!(unknown, ParentMismatch::Unknown { .. }));
+
+    Ok(())
+}
+
+/// Producer schedule designating one producer for every block, recording the
+/// blocks queried
+#[derive(Debug)]
+struct StubSchedule {
+    producer: Address,
+    queried: Mutex<Vec<u64>>,
+}
+
+#[async_trait]
+impl ProducerSchedule for StubSchedule {
+    async fn designated_producer(&self, block_number: u64) -> Result<Address> {
+        self.queried.lock().unwrap().push(block_number);
+        Ok(self.producer)
+    }
+}
+
+/// Tests that only the designated producer builds, and others skip quietly
+#[tokio::test]
+async fn test_producer_schedule_turns() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let (us, other) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+    let builder_for = |producer| {
+        let schedule = Arc::new(StubSchedule { producer, queried: Default::default() });
+        let builder = EvolvePayloadBuilder::new(
+            fixture.builder.client.clone(),
+            fixture.builder.evm_config.clone(),
+            fixture.builder.config.clone(),
+        )
+        .with_producer_schedule(us, schedule.clone());
+        (builder, schedule)
+    };
+    // The height comes from the parent, whatever the attributes say
+    let attributes = || {
+        fixture.create_payload_attributes(vec![], 7, TEST_TIMESTAMP, fixture.genesis_hash, Some(TEST_GAS_LIMIT))
+    };
+
+    // Our turn: the block is built
+    let (builder, schedule) = builder_for(us);
+    let outcome = builder.try_build_payload(attributes()).await?;
+    let EvolveBuildOutcome::Built(built) = outcome else {
+        panic!("the designated producer should build the block: {outcome:?}");
+    };
+    assert_eq!(built.block.number, 1);
+    assert_eq!(*schedule.queried.lock().unwrap(), [1]);
+
+    // Not our turn: skipped, and reported as such by the plain build
+    let (builder, schedule) = builder_for(other);
+    let outcome = builder.try_build_payload(attributes()).await?;
+    let EvolveBuildOutcome::Skipped { block_number, designated_producer } = outcome else {
+        panic!("other producers should skip the block: {outcome:?}");
+    };
+    assert_eq!((block_number, designated_producer), (1, other));
+    let err = builder.build_payload_block(attributes()).await.expect_err("not our turn");
+    assert!(err.to_string().contains("Not the designated producer of block 1"), "{err}");
+    assert_eq!(*schedule.queried.lock().unwrap(), [1, 1]);
+
+    Ok(())
+}
+
+/// Consensus finalizing blocks after a number of polls, or never
+#[derive(Debug)]
+struct StubFinality {
+    finalized_after: Option<usize>,
+    polls: Mutex<usize>,
+}
+
+impl StubFinality {
+    fn new(finalized_after: Option<usize>) -> Arc<Self> {
+        Arc::new(Self { finalized_after, polls: Mutex::new(0) })
+    }
+
+    fn polls(&self) -> usize {
+        *self.polls.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl BlockFinality for StubFinality {
+    async fn is_block_finalized(&self, _block_hash: B256) -> Result<bool> {
+        let mut polls = self.polls.lock().unwrap();
+        *polls += 1;
+        Ok(self.finalized_after.is_some_and(|after| *polls >= after))
+    }
+
+    async fn attestation_power(&self, _block_hash: B256) -> Result<U256> {
+        Ok(U256::from(40))
+    }
+}
+
+/// Tests that the builder waits for finalization when the policy requires it
+#[tokio::test]
+async fn test_wait_for_finalization() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    let builder_for = |finality: Arc<StubFinality>| {
+        EvolvePayloadBuilder::new(
+            fixture.builder.client.clone(),
+            fixture.builder.evm_config.clone(),
+            fixture.builder.config.clone(),
+        )
+        .with_finalization_policy(
+            FinalizationPolicy::WaitForFinalization { timeout: Duration::from_millis(500) },
+            finality,
+        )
+        .with_finality_poll_interval(Duration::from_millis(10))
+    };
+    let attributes = || {
+        fixture.create_payload_attributes(vec![], 1, TEST_TIMESTAMP, fixture.genesis_hash, Some(TEST_GAS_LIMIT))
+    };
+
+    // Finalized on the third poll: the build completes then
+    let finality = StubFinality::new(Some(3));
+    let block = builder_for(finality.clone()).build_payload_block(attributes()).await?;
+    assert_eq!(block.number, 1);
+    assert_eq!(finality.polls(), 3);
+
+    // Never finalized: the build fails once the timeout elapses, with the block
+    let finality = StubFinality::new(None);
+    let err = builder_for(finality.clone()).build_payload_block(attributes()).await.expect_err("never finalized");
+    let PayloadBuilderError::Other(err) = err else {
+        panic!("expected a finalization timeout: {err}");
+    };
+    let timeout = err.downcast_ref::<FinalizationTimeout>().expect("expected a finalization timeout");
+    assert_eq!(timeout.built.block.number, 1);
+    assert!(timeout.finalization);
+    assert_eq!(timeout.attestation_power, U256::from(40));
+    assert!(finality.polls() > 3, "polled until the timeout: {}", finality.polls());
+
+    // Blocks aren't waited on without the policy
+    let finality = StubFinality::new(None);
+    let builder = builder_for(finality.clone())
+        .with_finalization_policy(FinalizationPolicy::FireAndForget, finality.clone());
+    builder.build_payload_block(attributes()).await?;
+    assert_eq!(finality.polls(), 0);
+
+    Ok(())
+}
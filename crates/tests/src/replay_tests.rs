@@ -0,0 +1,86 @@
+//! Tests for replaying historical blocks through the sequential and parallel
+//! paths.
+
+use crate::common;
+
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::{Address, B256, U256};
+use common::{create_signed_transfer_transaction, EvolveTestFixture, TEST_GAS_LIMIT, TEST_TIMESTAMP};
+use ev_node::{replay_block, ReplayError};
+use eyre::Result;
+use evolve_ev_reth::parallel::ParallelConfig as EvolveParallelConfig;
+use reth_primitives::SealedBlock;
+use reth_provider::test_utils::ExtendedAccount;
+use std::num::NonZeroUsize;
+
+/// Builds a block of transfers from fresh funded signers, one to each of
+/// `recipients`, on top of `parent_hash`, and adds it to the chain
+async fn add_block(
+    fixture: &EvolveTestFixture,
+    number: u64,
+    parent_hash: B256,
+    recipients: impl IntoIterator<Item = Address>,
+) -> Result<SealedBlock> {
+    let transactions = recipients
+        .into_iter()
+        .map(|recipient| {
+            let signer = PrivateKeySigner::random();
+            fixture.provider.add_account(signer.address(), ExtendedAccount::new(0, U256::from(1_000_000)));
+            create_signed_transfer_transaction(&signer, 0, recipient, U256::from(1_000))
+        })
+        .collect();
+    let payload_attrs = fixture.create_payload_attributes(
+        transactions,
+        number,
+        TEST_TIMESTAMP + 12 * (number - 1),
+        parent_hash,
+        Some(TEST_GAS_LIMIT),
+    );
+
+    let block = fixture.builder.build_payload(payload_attrs).await?.block;
+    fixture.provider.add_header(block.hash(), block.header().clone());
+    fixture.provider.add_block(block.hash(), block.clone().into_block());
+    Ok(block)
+}
+
+/// Tests that replaying the blocks of a small chain reproduces them on both
+/// paths, including a block whose transactions all conflict on one recipient
+#[tokio::test]
+async fn test_replay_known_blocks() -> Result<()> {
+    let fixture = EvolveTestFixture::new().await?;
+    // The mock provider serves the same state for every block, so every block
+    // spends from accounts funded before it
+    let hot = add_block(&fixture, 1, fixture.genesis_hash, [Address::repeat_byte(0x42); 12]).await?;
+    let spread = add_block(&fixture, 2, hot.hash(), (1u8..=12).map(Address::repeat_byte)).await?;
+    let empty = add_block(&fixture, 3, spread.hash(), []).await?;
+
+    let config =
+        EvolveParallelConfig { concurrency_level: NonZeroUsize::new(4).unwrap(), ..EvolveParallelConfig::default() };
+    for block in [&hot, &spread, &empty] {
+        let report = replay_block(&fixture.provider, &fixture.builder.evm_config, block.number, config.clone()).await?;
+        assert!(report.is_consistent(), "block {} diverged: {report:?}", block.number);
+        assert_eq!((report.block_number, report.block_hash), (block.number, block.hash()));
+        assert_eq!(report.transactions, block.body().transactions.len());
+        assert_eq!(report.gas_used.sequential, block.gas_used);
+        assert_eq!(report.gas_used.parallel, block.gas_used);
+        assert!(report.excluded.sequential.is_empty());
+        assert!(report.parallel_metrics.is_some());
+    }
+
+    // Blocks too small for the configured threshold still take the parallel path
+    let report = replay_block(
+        &fixture.provider,
+        &fixture.builder.evm_config,
+        1,
+        EvolveParallelConfig { min_transactions_for_parallel: 100, ..config.clone() },
+    )
+    .await?;
+    assert_eq!(report.gas_used.parallel, 12 * 21_000);
+    let metrics = report.parallel_metrics.unwrap();
+    assert_eq!((metrics.worker_busy_time.len(), metrics.sequential_fallbacks), (4, 0));
+
+    let missing = replay_block(&fixture.provider, &fixture.builder.evm_config, 4, config).await.unwrap_err();
+    assert!(matches!(missing, ReplayError::BlockNotFound(4)), "{missing}");
+
+    Ok(())
+}
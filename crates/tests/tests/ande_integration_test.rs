@@ -3,7 +3,9 @@
 //! These tests verify that the complete ANDE integration works end-to-end,
 //! from payload builder to precompile execution.
 
-use evolve_ev_reth::evm_config::{AndeEvmConfig, ANDE_PRECOMPILE_ADDRESS, create_ande_evm_config};
+use evolve_ev_reth::evm_config::{
+    AndeEvmConfig, AndePrecompileConfig, ANDE_PRECOMPILE_ADDRESS, create_ande_evm_config,
+};
 use reth_chainspec::{ChainSpecBuilder, Chain};
 use alloy_primitives::Address;
 use alloy_genesis::Genesis;
@@ -22,7 +24,7 @@ fn test_ande_evm_config_integration() {
     );
 
     // Create ANDE EVM config
-    let evm_config = create_ande_evm_config(chain_spec);
+    let evm_config = create_ande_evm_config(chain_spec, AndePrecompileConfig::insecure_unrestricted());
 
     // Verify configuration is valid
     assert!(evm_config.chain_spec().chain.named().is_some());
@@ -63,7 +65,7 @@ fn test_payload_builder_with_ande_config() {
     );
 
     // Create ANDE EVM config
-    let _evm_config = create_ande_evm_config(chain_spec.clone());
+    let _evm_config = create_ande_evm_config(chain_spec.clone(), AndePrecompileConfig::insecure_unrestricted());
 
     // Create a mock client (simplified for test)
     // In a real test, this would be a proper test client
@@ -81,7 +83,7 @@ fn test_end_to_end_architecture() {
     // 1. Create ANDE EVM config
     let genesis = Genesis::default();
     let chain_spec = Arc::new(ChainSpecBuilder::default().chain(Chain::mainnet()).genesis(genesis).build());
-    let _evm_config = create_ande_evm_config(chain_spec);
+    let _evm_config = create_ande_evm_config(chain_spec, AndePrecompileConfig::insecure_unrestricted());
 
     // 2. Verify EVM environment creation works
     // In a real test, you would create a mock header and call evm_env()